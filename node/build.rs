@@ -1,4 +1,4 @@
-use stdlib_fetch::{fetch, FetchConfig};
+use stdlib_fetch::{build, fetch, FetchConfig};
 use substrate_build_script_utils::{generate_cargo_keys, rerun_if_git_head_changed};
 
 fn main() {
@@ -21,4 +21,8 @@ fn main() {
         build_with_dove: true,
     })
     .unwrap();
+
+    // Runtime modules of the Move VM pallet requests and resources (e.g. `0x1::Schedule`).
+    println!("cargo:rerun-if-changed=framework");
+    build("./framework").unwrap();
 }
@@ -0,0 +1,73 @@
+/// Callbacks executed by the runtime: the Move VM pallet subscribes the request of the subscriber.
+module Std::Callback {
+    use Std::Event;
+    use Std::Signer;
+    use Std::Vector;
+
+    const E_NOT_STD: u64 = 1;
+    const E_NOT_SUBSCRIBER: u64 = 2;
+
+    /// Every block initialization.
+    const TRIGGER_BLOCK: u8 = 0;
+    /// Free native balance of the subscriber grew.
+    const TRIGGER_DEPOSIT: u8 = 1;
+
+    /// Addresses holding the subscriber capability, granted by `0x1`.
+    struct Subscribers has key {
+        addresses: vector<address>,
+    }
+
+    struct SubscribeRequest has drop, store {
+        trigger: u8,
+        callback: vector<u8>,
+        gas_limit: u64,
+    }
+
+    struct UnsubscribeRequest has drop, store {
+        trigger: u8,
+    }
+
+    /// Grant the subscriber capability to the address.
+    public fun add_subscriber(std: &signer, subscriber: address) acquires Subscribers {
+        assert!(Signer::address_of(std) == @Std, E_NOT_STD);
+        if (!exists<Subscribers>(@Std)) {
+            move_to(std, Subscribers { addresses: Vector::empty<address>() });
+        };
+        Vector::push_back(&mut borrow_global_mut<Subscribers>(@Std).addresses, subscriber);
+    }
+
+    /// Whether the address holds the subscriber capability.
+    public fun is_subscriber(addr: address): bool acquires Subscribers {
+        if (!exists<Subscribers>(@Std)) {
+            return false
+        };
+        Vector::contains(&borrow_global<Subscribers>(@Std).addresses, &addr)
+    }
+
+    /// Subscribe `callback` script executed signed by the account every block.
+    public fun on_block(account: &signer, callback: vector<u8>, gas_limit: u64)
+    acquires Subscribers {
+        subscribe(account, TRIGGER_BLOCK, callback, gas_limit);
+    }
+
+    /// Subscribe `callback` script executed signed by the account once it receives coins.
+    public fun on_deposit(account: &signer, callback: vector<u8>, gas_limit: u64)
+    acquires Subscribers {
+        subscribe(account, TRIGGER_DEPOSIT, callback, gas_limit);
+    }
+
+    /// Remove the callback of the trigger.
+    public fun unsubscribe(account: &signer, trigger: u8) {
+        let handle = Event::new_event_handle<UnsubscribeRequest>(account);
+        Event::emit_event(&mut handle, UnsubscribeRequest { trigger });
+        Event::destroy_handle(handle);
+    }
+
+    fun subscribe(account: &signer, trigger: u8, callback: vector<u8>, gas_limit: u64)
+    acquires Subscribers {
+        assert!(is_subscriber(Signer::address_of(account)), E_NOT_SUBSCRIBER);
+        let handle = Event::new_event_handle<SubscribeRequest>(account);
+        Event::emit_event(&mut handle, SubscribeRequest { trigger, callback, gas_limit });
+        Event::destroy_handle(handle);
+    }
+}
@@ -0,0 +1,17 @@
+/// Follow-up scripts: the Move VM pallet schedules the request for execution on behalf of the signer.
+module Std::Schedule {
+    use Std::Event;
+
+    struct ScheduleRequest has drop, store {
+        delay: u64,
+        gas_limit: u64,
+        script: vector<u8>,
+    }
+
+    /// Schedule `script` signed by `account` in `delay` blocks.
+    public fun schedule(account: &signer, delay: u64, gas_limit: u64, script: vector<u8>) {
+        let handle = Event::new_event_handle<ScheduleRequest>(account);
+        Event::emit_event(&mut handle, ScheduleRequest { delay, gas_limit, script });
+        Event::destroy_handle(handle);
+    }
+}
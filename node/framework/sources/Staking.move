@@ -0,0 +1,48 @@
+/// Staking state of the runtime, stored by the Move VM pallet.
+module Std::Staking {
+    /// Current staking round, stored under `0x1` every block.
+    struct Round has key {
+        index: u64,
+        first_block: u64,
+        length: u64,
+        collators: vector<address>,
+        total_staked: u128,
+    }
+
+    /// Stake of the signer, stored while its script runs.
+    struct Stake has key {
+        delegated: u128,
+        collator_bond: u128,
+    }
+
+    /// Current round index.
+    public fun round_index(): u64 acquires Round {
+        borrow_global<Round>(@Std).index
+    }
+
+    /// Collators selected for the current round.
+    public fun collators(): vector<address> acquires Round {
+        *&borrow_global<Round>(@Std).collators
+    }
+
+    /// Total amount staked by collators and delegators.
+    public fun total_staked(): u128 acquires Round {
+        borrow_global<Round>(@Std).total_staked
+    }
+
+    /// Amount the signer delegates to collators, zero if nothing is staked.
+    public fun delegated(addr: address): u128 acquires Stake {
+        if (!exists<Stake>(addr)) {
+            return 0
+        };
+        borrow_global<Stake>(addr).delegated
+    }
+
+    /// Self bond of the collator candidate, zero for other accounts.
+    public fun collator_bond(addr: address): u128 acquires Stake {
+        if (!exists<Stake>(addr)) {
+            return 0
+        };
+        borrow_global<Stake>(addr).collator_bond
+    }
+}
@@ -0,0 +1,16 @@
+/// Treasury spends: the Move VM pallet spends the treasury as requested by allowed spenders.
+module Std::Treasury {
+    use Std::Event;
+
+    struct SpendRequest has drop, store {
+        amount: u128,
+        beneficiary: address,
+    }
+
+    /// Request the treasury to pay `amount` of native coins to the `beneficiary`.
+    public fun spend(account: &signer, amount: u128, beneficiary: address) {
+        let handle = Event::new_event_handle<SpendRequest>(account);
+        Event::emit_event(&mut handle, SpendRequest { amount, beneficiary });
+        Event::destroy_handle(handle);
+    }
+}
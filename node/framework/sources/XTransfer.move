@@ -0,0 +1,55 @@
+/// Cross-chain transfers: the Move VM pallet sends the request from the signer account.
+/// Coins are sent from the native balance of the signer, i.e. its `PontAccount` balance.
+module Std::XTransfer {
+    use Std::Event;
+    use Std::Vector;
+
+    const PARACHAIN: u8 = 0;
+    const ACCOUNT_ID_32: u8 = 1;
+
+    struct Junction has copy, drop, store {
+        kind: u8,
+        parachain: u64,
+        key: vector<u8>,
+    }
+
+    struct Location has copy, drop, store {
+        parents: u8,
+        junctions: vector<Junction>,
+    }
+
+    struct TransferRequest has drop, store {
+        ticker: vector<u8>,
+        amount: u64,
+        dest: Location,
+        dest_weight: u64,
+    }
+
+    /// Account `id` on the relay chain.
+    public fun relay_account(id: vector<u8>): Location {
+        let junctions = Vector::empty<Junction>();
+        Vector::push_back(&mut junctions, Junction { kind: ACCOUNT_ID_32, parachain: 0, key: id });
+        Location { parents: 1, junctions }
+    }
+
+    /// Account `id` on the sibling parachain `para_id`.
+    public fun sibling_account(para_id: u64, id: vector<u8>): Location {
+        let junctions = Vector::empty<Junction>();
+        Vector::push_back(&mut junctions, Junction { kind: PARACHAIN, parachain: para_id, key: x"" });
+        Vector::push_back(&mut junctions, Junction { kind: ACCOUNT_ID_32, parachain: 0, key: id });
+        Location { parents: 1, junctions }
+    }
+
+    /// Transfer `amount` of `ticker` currency from `account` to `dest`.
+    public fun transfer(
+        account: &signer,
+        ticker: vector<u8>,
+        amount: u64,
+        dest: Location,
+        dest_weight: u64,
+    ) {
+        let handle = Event::new_event_handle<TransferRequest>(account);
+        Event::emit_event(&mut handle, TransferRequest { ticker, amount, dest, dest_weight });
+        Event::destroy_handle(handle);
+    }
+}
@@ -154,7 +154,18 @@ fn paused_extrinsics() -> Vec<(Vec<u8>, Vec<u8>)> {
         ),
         ("ParachainStaking", vec!["join_candidates", "delegate"]),
         ("Treasury", vec!["propose_spend"]),
-        ("Mvm", vec!["execute", "publish_module", "publish_package"]),
+        (
+            "Mvm",
+            vec![
+                "execute",
+                "publish_module",
+                "publish_package",
+                "publish_package_with_metadata",
+                "stage_module_chunk",
+                "publish_staged_module",
+                "publish_module_with_source_map",
+            ],
+        ),
         (
             "MultiSig",
             vec![
@@ -184,8 +195,8 @@ fn paused_extrinsics() -> Vec<(Vec<u8>, Vec<u8>)> {
     .collect()
 }
 
-/// Local development config.
-pub fn development_config() -> Result<ChainSpec, String> {
+/// Local development config, `move_state` entries are stored in Move VM genesis.
+pub fn development_config(move_state: Vec<(Vec<u8>, Vec<u8>)>) -> Result<ChainSpec, String> {
     let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
     let parachain_id = ParaId::from(2000);
 
@@ -238,6 +249,12 @@ pub fn development_config() -> Result<ChainSpec, String> {
                 vec![],
                 // Parachain id
                 parachain_id,
+                // Chain name published to Move contracts
+                "pontem_dev",
+                // Chain id of ed25519 signed transactions
+                108,
+                // Move state
+                move_state.clone(),
             )
         },
         // Bootnodes
@@ -258,8 +275,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
     ))
 }
 
-/// Local testnet configuration.
-pub fn local_testnet_config() -> Result<ChainSpec, String> {
+/// Local testnet configuration, `move_state` entries are stored in Move VM genesis.
+pub fn local_testnet_config(move_state: Vec<(Vec<u8>, Vec<u8>)>) -> Result<ChainSpec, String> {
     let wasm_binary = WASM_BINARY.ok_or_else(|| "Testnet wasm not available".to_string())?;
     let parachain_id = ParaId::from(2000);
 
@@ -328,6 +345,12 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
                 vec![],
                 // Parachain ID
                 parachain_id,
+                // Chain name published to Move contracts
+                "pontem_testnet",
+                // Chain id of ed25519 signed transactions
+                107,
+                // Move state
+                move_state.clone(),
             )
         },
         // Bootnodes
@@ -461,6 +484,12 @@ pub fn westend_config() -> Result<ChainSpec, String> {
                 paused_extrinsics(),
                 // Parachain ID
                 parachain_id,
+                // Chain name published to Move contracts
+                "nox_westend",
+                // Chain id of ed25519 signed transactions
+                106,
+                // Move state
+                vec![],
             )
         },
         // Bootnodes
@@ -707,6 +736,12 @@ pub fn nox_config() -> Result<ChainSpec, String> {
                 paused_extrinsics(),
                 // Parachain ID
                 parachain_id,
+                // Chain name published to Move contracts
+                "nox_mainnet",
+                // Chain id of ed25519 signed transactions
+                105,
+                // Move state
+                vec![],
             )
         },
         // Bootnodes
@@ -737,6 +772,9 @@ fn genesis(
     vesting: Vec<(AccountId, BlockNumber, BlockNumber, Balance)>,
     paused: Vec<(Vec<u8>, Vec<u8>)>,
     id: ParaId,
+    chain_name: &str,
+    aptos_chain_id: u8,
+    move_state: Vec<(Vec<u8>, Vec<u8>)>,
 ) -> GenesisConfig {
     let (init_module, init_func, init_args) = build_vm_config();
 
@@ -744,6 +782,8 @@ fn genesis(
         include_bytes!("../move/move-stdlib/build/MoveStdlib/bundles/MoveStdlib.pac").to_vec();
     let pont_framework =
         include_bytes!("../move/pont-stdlib/build/PontStdlib/bundles/PontStdlib.pac").to_vec();
+    let runtime_framework =
+        include_bytes!("../framework/build/PontRuntime/bundles/PontRuntime.pac").to_vec();
 
     GenesisConfig {
         tokens: TokensConfig { balances: vec![] },
@@ -786,9 +826,13 @@ fn genesis(
         mvm: MvmConfig {
             move_stdlib,
             pont_framework,
+            runtime_framework,
             init_module,
             init_func,
             init_args,
+            move_state,
+            chain_name: chain_name.as_bytes().to_vec(),
+            aptos_chain_id,
             ..Default::default()
         },
         transaction_pause: TransactionPauseConfig {
@@ -42,11 +42,135 @@ pub struct Cli {
     #[clap(long)]
     pub dev_service: bool,
 
+    /// Keep offchain-indexed Move events for N finalized blocks.
+    /// Older events are exported to `--mvm-events-sink` and pruned from the database.
+    /// Enables offchain indexing.
+    #[clap(long)]
+    pub mvm_events_retention: Option<u64>,
+
+    /// Sink for pruned Move events: `none`, `file:<dir>` or `s3:<endpoint>/<bucket>`.
+    #[clap(long, default_value = "none")]
+    pub mvm_events_sink: crate::events_archive::EventsSinkConfig,
+
+    /// Execution deadline (in milliseconds) for Move VM RPC calls, e.g. gas estimation.
+    /// Converted to gas, so execution stops inside the VM once deadline is reached.
+    /// Clients can request shorter deadlines per call.
+    #[clap(long)]
+    pub mvm_rpc_deadline: Option<u64>,
+
+    /// Maximum size (in bytes) of Move modules accepted by RPC calls.
+    #[clap(long)]
+    pub mvm_rpc_max_module_bytes: Option<usize>,
+
+    /// Maximum number of modules in Move packages accepted by RPC calls.
+    #[clap(long)]
+    pub mvm_rpc_max_package_modules: Option<usize>,
+
+    /// Maximum size (in bytes) of Move scripts and transactions accepted by RPC calls.
+    #[clap(long)]
+    pub mvm_rpc_max_script_bytes: Option<usize>,
+
+    /// Maximum number of Move transaction arguments accepted by RPC calls.
+    #[clap(long)]
+    pub mvm_rpc_max_args: Option<usize>,
+
+    /// Maximum number of Move VM gas estimations executed at once by RPC calls,
+    /// other estimations wait for a free worker.
+    #[clap(long)]
+    pub mvm_rpc_max_estimations: Option<usize>,
+
+    /// Timeout (in milliseconds) of Move VM gas estimation RPC calls, including the wait
+    /// for a free worker.
+    #[clap(long)]
+    pub mvm_rpc_estimation_timeout: Option<u64>,
+
+    /// Maximum number of blocks scanned by `mvm_getResourceHistory` RPC calls.
+    #[clap(long)]
+    pub mvm_rpc_max_history_blocks: Option<u64>,
+
+    /// Budget (in cost units per second) of expensive Move VM RPC calls served by the node:
+    /// gas estimations and simulations cost 10, traces 20, resource history 10,
+    /// ABIs 1 per module. Not limited by default.
+    #[clap(long)]
+    pub mvm_rpc_rate_limit: Option<u32>,
+
+    /// Budget (in cost units per second) of expensive Move VM RPC calls of a single WebSocket
+    /// connection, see `--mvm-rpc-rate-limit`. Not limited by default.
+    #[clap(long)]
+    pub mvm_rpc_connection_rate_limit: Option<u32>,
+
+    /// Enable `mvm_executeWithDebug` RPC returning node logs captured during execution,
+    /// e.g. Move `Debug::print` output. Available with `--dev-service` only.
+    #[clap(long)]
+    pub mvm_debug_rpc: bool,
+
+    /// Serve Aptos compatible REST API (accounts resources and modules, transaction simulation)
+    /// on the address, e.g. `127.0.0.1:8080`.
+    #[clap(long)]
+    pub mvm_rest_addr: Option<std::net::SocketAddr>,
+
+    /// Serve GraphQL API of Move state (modules, resources, events) on the address,
+    /// e.g. `127.0.0.1:8081`. Events require offchain indexing.
+    #[clap(long)]
+    pub mvm_graphql_addr: Option<std::net::SocketAddr>,
+
+    /// Move state exported by `export-move-state` to store in genesis of `dev` and `local`
+    /// chains, e.g. to run a dev chain with the state forked from the live one.
+    #[clap(long, parse(from_os_str))]
+    pub mvm_genesis_state: Option<PathBuf>,
+
     /// Relaychain arguments
     #[clap(raw = true)]
     pub relaychain_args: Vec<String>,
 }
 
+impl Cli {
+    /// Move events archive configuration, if retention is set.
+    pub fn events_archive(&self) -> Option<crate::events_archive::EventsArchiveConfig> {
+        self.mvm_events_retention
+            .map(|retention| crate::events_archive::EventsArchiveConfig {
+                retention,
+                sink: self.mvm_events_sink.clone(),
+            })
+    }
+
+    /// Limits of Move VM RPC requests, defaults are used for limits not set.
+    pub fn rpc_limits(&self) -> sp_mvm_rpc::limits::RequestLimits {
+        let default = sp_mvm_rpc::limits::RequestLimits::default();
+        sp_mvm_rpc::limits::RequestLimits {
+            max_module_bytes: self.mvm_rpc_max_module_bytes.unwrap_or(default.max_module_bytes),
+            max_package_modules: self
+                .mvm_rpc_max_package_modules
+                .unwrap_or(default.max_package_modules),
+            max_script_bytes: self.mvm_rpc_max_script_bytes.unwrap_or(default.max_script_bytes),
+            max_args: self.mvm_rpc_max_args.unwrap_or(default.max_args),
+            max_estimations: self
+                .mvm_rpc_max_estimations
+                .unwrap_or(default.max_estimations),
+            estimation_timeout_ms: self
+                .mvm_rpc_estimation_timeout
+                .unwrap_or(default.estimation_timeout_ms),
+            max_history_blocks: self
+                .mvm_rpc_max_history_blocks
+                .unwrap_or(default.max_history_blocks),
+            rate_limit: self.mvm_rpc_rate_limit,
+            connection_rate_limit: self.mvm_rpc_connection_rate_limit,
+        }
+    }
+}
+
+/// Move VM utilities.
+#[derive(Debug, Clap)]
+pub enum MvmSubcommand {
+    /// Compare ABI of the local package with modules published on chain.
+    #[clap(name = "diff-abi")]
+    DiffAbi(crate::diff_abi::DiffAbiCmd),
+
+    /// Capture replay bundle of the Move VM call from a live node.
+    #[clap(name = "capture-replay")]
+    CaptureReplay(crate::replay::CaptureReplayCmd),
+}
+
 #[derive(Debug, Clap)]
 pub enum Subcommand {
     /// Export the genesis state of the parachain.
@@ -72,6 +196,10 @@ pub enum Subcommand {
     /// Export the state of a given block into a chain spec.
     ExportState(sc_cli::ExportStateCmd),
 
+    /// Export Move modules and resources of a given block into a directory.
+    #[clap(name = "export-move-state")]
+    ExportMoveState(crate::export_move_state::ExportMoveStateCmd),
+
     /// Import blocks.
     ImportBlocks(sc_cli::ImportBlocksCmd),
 
@@ -81,9 +209,21 @@ pub enum Subcommand {
     /// Revert the chain to a previous state.
     Revert(sc_cli::RevertCmd),
 
+    /// Move VM utilities.
+    #[clap(subcommand)]
+    Mvm(MvmSubcommand),
+
+    /// Publish, run and replay Move contracts.
+    #[clap(subcommand)]
+    Move(crate::move_cmd::MoveSubcommand),
+
     /// The custom benchmark subcommmand benchmarking runtime pallets.
     #[clap(name = "benchmark", about = "Benchmark runtime pallets.")]
     Benchmark(frame_benchmarking_cli::BenchmarkCmd),
+
+    /// Try runtime upgrade or blocks execution against the live state.
+    #[clap(name = "try-runtime")]
+    TryRuntime(try_runtime_cli::TryRuntimeCmd),
 }
 
 /// Command for exporting the genesis state of the parachain
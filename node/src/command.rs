@@ -19,7 +19,9 @@ use crate::{
     chain_spec,
     service::{self, new_partial},
 };
-use crate::cli::{Cli, Subcommand, RelayChainCli};
+use crate::cli::{Cli, MvmSubcommand, Subcommand, RelayChainCli};
+use crate::export_move_state::read_move_state;
+use crate::move_cmd::MoveSubcommand;
 use cumulus_primitives_core::ParaId;
 use sc_cli::{
     ChainSpec, DefaultConfigurationValues, ImportParams, KeystoreParams, NetworkParams, Result,
@@ -33,7 +35,7 @@ use sp_core::{
     crypto::{self, Ss58AddressFormat},
 };
 use polkadot_parachain::primitives::AccountIdConversion;
-use std::{io::Write, net::SocketAddr};
+use std::{io::Write, net::SocketAddr, path::Path};
 use sp_runtime::traits::Block as _;
 use log::info;
 use codec::Encode;
@@ -42,10 +44,19 @@ fn set_default_ss58_version() {
     crypto::set_default_ss58_version(Ss58AddressFormat::custom(constants::SS58_PREFIX.into()));
 }
 
-fn load_spec(id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
+fn load_spec(
+    id: &str,
+    move_state: Option<&Path>,
+) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
+    let move_state = match move_state {
+        Some(dir) if matches!(id, "dev" | "" | "local") => read_move_state(dir)?,
+        Some(_) => return Err("Move genesis state is supported by dev and local chains".into()),
+        None => vec![],
+    };
+
     Ok(match id {
-        "dev" => Box::new(chain_spec::development_config()?),
-        "" | "local" => Box::new(chain_spec::local_testnet_config()?),
+        "dev" => Box::new(chain_spec::development_config(move_state)?),
+        "" | "local" => Box::new(chain_spec::local_testnet_config(move_state)?),
         "nox" => Box::new(chain_spec::nox_config()?),
         "westend" => Box::new(chain_spec::westend_config()?),
         path => Box::new(chain_spec::ChainSpec::from_json_file(
@@ -80,7 +91,7 @@ impl SubstrateCli for Cli {
     }
 
     fn load_spec(&self, id: &str) -> std::result::Result<Box<dyn sc_service::ChainSpec>, String> {
-        load_spec(id)
+        load_spec(id, self.mvm_genesis_state.as_deref())
     }
 
     fn native_runtime_version(_: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
@@ -167,6 +178,11 @@ pub fn run() -> sc_cli::Result<()> {
                 Ok(cmd.run(components.client, config.chain_spec))
             })
         }
+        Some(Subcommand::ExportMoveState(cmd)) => {
+            construct_async_run!(|components, cli, cmd, config| {
+                Ok(cmd.run(components.client))
+            })
+        }
         Some(Subcommand::ImportBlocks(cmd)) => {
             construct_async_run!(|components, cli, cmd, config| {
                 Ok(cmd.run(components.client, components.import_queue))
@@ -189,6 +205,19 @@ pub fn run() -> sc_cli::Result<()> {
         Some(Subcommand::Revert(cmd)) => construct_async_run!(|components, cli, cmd, config| {
             Ok(cmd.run(components.client, components.backend))
         }),
+        Some(Subcommand::Mvm(cmd)) => match cmd {
+            MvmSubcommand::DiffAbi(cmd) => cmd.run(),
+            MvmSubcommand::CaptureReplay(cmd) => cmd.run(),
+        },
+        Some(Subcommand::Move(cmd)) => match cmd {
+            MoveSubcommand::Publish(cmd) => cmd.run(),
+            MoveSubcommand::Run(cmd) => cmd.run(),
+            MoveSubcommand::Replay(cmd) => {
+                construct_async_run!(|components, cli, cmd, config| {
+                    Ok(cmd.run(components.client, components.backend))
+                })
+            }
+        },
         Some(Subcommand::Benchmark(cmd)) => {
             if cfg!(feature = "runtime-benchmarks") {
                 let runner = cli.create_runner(cmd)?;
@@ -202,12 +231,39 @@ pub fn run() -> sc_cli::Result<()> {
                     .into())
             }
         }
+        Some(Subcommand::TryRuntime(cmd)) => {
+            if cfg!(feature = "try-runtime") {
+                let runner = cli.create_runner(cmd)?;
+
+                // Task manager for the remote state download.
+                let registry = &runner
+                    .config()
+                    .prometheus_config
+                    .as_ref()
+                    .map(|cfg| &cfg.registry);
+                let task_manager =
+                    sc_service::TaskManager::new(runner.config().tokio_handle.clone(), *registry)
+                        .map_err(|err| format!("Error: {:?}", err))?;
+
+                runner.async_run(|config| {
+                    Ok((
+                        cmd.run::<Block, service::ParachainRuntimeExecutor>(config),
+                        task_manager,
+                    ))
+                })
+            } else {
+                Err("Try-runtime wasn't enabled when building the node. \
+				You can enable it with `--features try-runtime`."
+                    .into())
+            }
+        }
         Some(Subcommand::ExportGenesisState(params)) => {
             let mut builder = sc_cli::LoggerBuilder::new("");
             builder.with_profiling(sc_tracing::TracingReceiver::Log, "");
             let _ = builder.init();
 
-            let chain_spec = load_spec(&params.chain.clone().unwrap_or_default())?;
+            let chain = params.chain.clone().unwrap_or_default();
+            let chain_spec = load_spec(&chain, cli.mvm_genesis_state.as_deref())?;
 
             let state_version = Cli::native_runtime_version(&chain_spec).state_version();
 
@@ -250,11 +306,32 @@ pub fn run() -> sc_cli::Result<()> {
         }
         None => {
             let runner = cli.create_runner(&cli.run.normalize())?;
+            let events_archive = cli.events_archive();
+            let mvm_rpc_deadline = cli.mvm_rpc_deadline;
+            let mvm_rpc_limits = cli.rpc_limits();
+            let mvm_debug_rpc = cli.mvm_debug_rpc;
+            let mvm_rest_addr = cli.mvm_rest_addr;
+            let mvm_graphql_addr = cli.mvm_graphql_addr;
             runner.run_node_until_exit(|config| async move {
                 if cli.dev_service {
                     let author_id =
                         chain_spec::get_from_seed::<nimbus_primitives::NimbusId>("Alice");
-                    return service::new_dev(config, author_id, cli.sealing).map_err(Into::into);
+                    return service::new_dev(
+                        config,
+                        author_id,
+                        cli.sealing,
+                        events_archive,
+                        mvm_rpc_deadline,
+                        mvm_rpc_limits,
+                        mvm_debug_rpc,
+                        mvm_rest_addr,
+                        mvm_graphql_addr,
+                    )
+                    .map_err(Into::into);
+                }
+
+                if mvm_debug_rpc {
+                    log::warn!("--mvm-debug-rpc is available with --dev-service only, ignored");
                 }
 
                 let para_id =
@@ -291,10 +368,19 @@ pub fn run() -> sc_cli::Result<()> {
                     }
                 );
 
-                crate::service::start_node(config, polkadot_config, id)
-                    .await
-                    .map(|r| r.0)
-                    .map_err(Into::into)
+                crate::service::start_node(
+                    config,
+                    polkadot_config,
+                    id,
+                    events_archive,
+                    mvm_rpc_deadline,
+                    mvm_rpc_limits,
+                    mvm_rest_addr,
+                    mvm_graphql_addr,
+                )
+                .await
+                .map(|r| r.0)
+                .map_err(Into::into)
             })
         }
     }
@@ -0,0 +1,339 @@
+//! `pontem mvm diff-abi` command: compares modules of the local package with modules
+//! published on chain and reports ABI changes before the upgrade.
+//!
+//! Verdict for every module is given by the same compatibility check Move VM runs
+//! when a module is republished, so the report matches publish behavior.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
+
+use clap::Parser as Clap;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sp_core::bytes::{from_hex, to_hex};
+
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::compatibility::Compatibility;
+use move_binary_format::file_format::{CompiledModule, Visibility};
+use move_binary_format::normalized;
+use move_core_types::language_storage::{ModuleId, CORE_CODE_ADDRESS};
+use move_vm::types::ModulePackage;
+
+use crate::rpc_client::RpcClient;
+
+#[derive(Debug, Clap)]
+pub struct DiffAbiCmd {
+    /// Package bundle (`.pac`) or directory with compiled modules (`.mv`).
+    #[clap(parse(from_os_str))]
+    pub package: PathBuf,
+
+    /// HTTP RPC endpoint of the node to compare with.
+    #[clap(long)]
+    pub against_chain: String,
+
+    /// Block hash to fetch modules at, best block by default.
+    #[clap(long)]
+    pub at: Option<String>,
+
+    /// Print report as JSON.
+    #[clap(long)]
+    pub json: bool,
+}
+
+/// Kind of the ABI change.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    StructAdded,
+    StructRemoved,
+    StructChanged,
+    FunctionAdded,
+    FunctionRemoved,
+    FunctionChanged,
+    FriendAdded,
+    FriendRemoved,
+}
+
+/// Single ABI change of the module.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AbiChange {
+    pub kind: ChangeKind,
+    pub name: String,
+    /// Change breaks modules or scripts linked against the old version.
+    pub breaking: bool,
+}
+
+/// Module status against on-chain version.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleStatus {
+    /// Module is not published yet.
+    New,
+    /// Bytecode is the same.
+    Unchanged,
+    /// Module can be republished.
+    Compatible,
+    /// Module would be rejected on publish.
+    Incompatible,
+}
+
+/// Compatibility report of the module.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModuleReport {
+    pub module: String,
+    pub status: ModuleStatus,
+    pub changes: Vec<AbiChange>,
+}
+
+impl DiffAbiCmd {
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let modules = load_package(&self.package)?;
+        let runtime = sc_cli::build_runtime()?;
+        let reports = runtime.block_on(self.reports(modules))?;
+
+        if self.json {
+            let json = serde_json::to_string_pretty(&reports).map_err(|e| e.to_string())?;
+            println!("{}", json);
+        } else {
+            for report in &reports {
+                print_report(report);
+            }
+        }
+
+        if reports
+            .iter()
+            .any(|report| report.status == ModuleStatus::Incompatible)
+        {
+            return Err("Package contains incompatible module updates".into());
+        }
+        Ok(())
+    }
+
+    async fn reports(&self, modules: Vec<CompiledModule>) -> Result<Vec<ModuleReport>, String> {
+        let client = RpcClient::new(&self.against_chain)?;
+        let mut reports = Vec::with_capacity(modules.len());
+        for module in modules {
+            let on_chain = get_module(&client, &module.self_id(), self.at.as_deref()).await?;
+            let report = match on_chain {
+                Some(bytecode) => {
+                    let old = CompiledModule::deserialize(&bytecode).map_err(|e| {
+                        format!("Failed to deserialize {}: {:?}", module.self_id(), e)
+                    })?;
+                    diff_modules(&old, &module)
+                }
+                None => ModuleReport {
+                    module: module.self_id().to_string(),
+                    status: ModuleStatus::New,
+                    changes: vec![],
+                },
+            };
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+}
+
+/// Loads modules from package bundle or from directory with `.mv` files.
+fn load_package(path: &Path) -> Result<Vec<CompiledModule>, String> {
+    let bytecodes = if path.is_dir() {
+        let mut files = std::fs::read_dir(path)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |ext| ext == "mv"))
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+            .iter()
+            .map(|file| {
+                std::fs::read(file).map_err(|e| format!("Failed to read {:?}: {}", file, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        let bytes =
+            std::fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        package_modules(&bytes)
+            .map_err(|e| format!("Failed to parse package {:?}: {}", path, e))?
+    };
+
+    bytecodes
+        .iter()
+        .map(|bytecode| {
+            CompiledModule::deserialize(bytecode)
+                .map_err(|e| format!("Failed to deserialize module: {:?}", e))
+        })
+        .collect()
+}
+
+/// Bytecode of the modules in the package bundle (`.pac`).
+pub fn package_modules(bundle: &[u8]) -> Result<Vec<Vec<u8>>, String> {
+    // Address is used only for the transaction, modules keep their own addresses.
+    let (modules, _) = ModulePackage::try_from(bundle)
+        .map_err(|e| format!("{:?}", e))?
+        .into_tx(CORE_CODE_ADDRESS)
+        .into_inner();
+    Ok(modules)
+}
+
+/// Compares local module with the on-chain one.
+pub fn diff_modules(old: &CompiledModule, new: &CompiledModule) -> ModuleReport {
+    let module = new.self_id().to_string();
+    let (old_bytes, new_bytes) = (serialize(old), serialize(new));
+    if old_bytes == new_bytes {
+        return ModuleReport {
+            module,
+            status: ModuleStatus::Unchanged,
+            changes: vec![],
+        };
+    }
+
+    let visibility: BTreeMap<_, _> = old
+        .function_defs()
+        .iter()
+        .map(|def| {
+            let handle = old.function_handle_at(def.function);
+            (old.identifier_at(handle.name).to_owned(), def.visibility)
+        })
+        .collect();
+    let (old_friends, new_friends) = (friends(old), friends(new));
+
+    let old = normalized::Module::new(old);
+    let new = normalized::Module::new(new);
+
+    let mut changes = Vec::new();
+    diff_maps(
+        &old.structs,
+        &new.structs,
+        (ChangeKind::StructAdded, ChangeKind::StructRemoved, ChangeKind::StructChanged),
+        |_| true,
+        &mut changes,
+    );
+    // Friend functions can be changed together with their friends.
+    diff_maps(
+        &old.exposed_functions,
+        &new.exposed_functions,
+        (
+            ChangeKind::FunctionAdded,
+            ChangeKind::FunctionRemoved,
+            ChangeKind::FunctionChanged,
+        ),
+        |name| visibility.get(name) != Some(&Visibility::Friend),
+        &mut changes,
+    );
+    for friend in new_friends.iter().filter(|f| !old_friends.contains(f)) {
+        changes.push(AbiChange {
+            kind: ChangeKind::FriendAdded,
+            name: friend.to_string(),
+            breaking: false,
+        });
+    }
+    for friend in old_friends.iter().filter(|f| !new_friends.contains(f)) {
+        changes.push(AbiChange {
+            kind: ChangeKind::FriendRemoved,
+            name: friend.to_string(),
+            breaking: false,
+        });
+    }
+
+    let status = if Compatibility::check(&old, &new).is_fully_compatible() {
+        ModuleStatus::Compatible
+    } else {
+        ModuleStatus::Incompatible
+    };
+
+    ModuleReport {
+        module,
+        status,
+        changes,
+    }
+}
+
+fn diff_maps<K: ToString + Ord, V: PartialEq>(
+    old: &BTreeMap<K, V>,
+    new: &BTreeMap<K, V>,
+    (added, removed, changed): (ChangeKind, ChangeKind, ChangeKind),
+    is_breaking: impl Fn(&K) -> bool,
+    changes: &mut Vec<AbiChange>,
+) {
+    for (name, value) in old {
+        match new.get(name) {
+            None => changes.push(AbiChange {
+                kind: removed,
+                name: name.to_string(),
+                breaking: is_breaking(name),
+            }),
+            Some(new_value) if new_value != value => changes.push(AbiChange {
+                kind: changed,
+                name: name.to_string(),
+                breaking: is_breaking(name),
+            }),
+            _ => {}
+        }
+    }
+    for name in new.keys().filter(|name| !old.contains_key(name)) {
+        changes.push(AbiChange {
+            kind: added,
+            name: name.to_string(),
+            breaking: false,
+        });
+    }
+}
+
+fn friends(module: &CompiledModule) -> Vec<ModuleId> {
+    module
+        .friend_decls()
+        .iter()
+        .map(|handle| module.module_id_for_handle(handle))
+        .collect()
+}
+
+fn serialize(module: &CompiledModule) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // Serialization of deserialized module can't fail.
+    let _ = module.serialize(&mut bytes);
+    bytes
+}
+
+fn print_report(report: &ModuleReport) {
+    let status = match report.status {
+        ModuleStatus::New => "new",
+        ModuleStatus::Unchanged => "unchanged",
+        ModuleStatus::Compatible => "compatible",
+        ModuleStatus::Incompatible => "INCOMPATIBLE",
+    };
+    println!("{}: {}", report.module, status);
+
+    for change in &report.changes {
+        let (sign, item) = match change.kind {
+            ChangeKind::StructAdded => ('+', "struct"),
+            ChangeKind::StructRemoved => ('-', "struct"),
+            ChangeKind::StructChanged => ('~', "struct"),
+            ChangeKind::FunctionAdded => ('+', "function"),
+            ChangeKind::FunctionRemoved => ('-', "function"),
+            ChangeKind::FunctionChanged => ('~', "function"),
+            ChangeKind::FriendAdded => ('+', "friend"),
+            ChangeKind::FriendRemoved => ('-', "friend"),
+        };
+        let breaking = if change.breaking { " (breaking)" } else { "" };
+        println!("  {} {} {}{}", sign, item, change.name, breaking);
+    }
+}
+
+/// Fetches module bytecode with `mvm_getModule`.
+async fn get_module(
+    client: &RpcClient,
+    id: &ModuleId,
+    at: Option<&str>,
+) -> Result<Option<Vec<u8>>, String> {
+    let module_id = bcs::to_bytes(id).map_err(|e| e.to_string())?;
+    let result = client
+        .request("mvm_getModule", json!([to_hex(&module_id, false), at]))
+        .await
+        .map_err(|e| format!("{} ({})", e, id))?;
+
+    match result {
+        Value::Null => Ok(None),
+        Value::String(hex) => from_hex(&hex).map(Some).map_err(|e| e.to_string()),
+        result => Err(format!("Unexpected mvm_getModule result: {}", result)),
+    }
+}
@@ -0,0 +1,360 @@
+//! Deferred pruning of the offchain-indexed Move events.
+//!
+//! Runtime writes every Move event into the offchain index (see `sp_mvm::event::offchain`).
+//! Once a block is finalized and older than the retention period, its events are exported
+//! through the configured sink and only after successful export removed from the database.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use codec::{Decode, Encode};
+use futures::{future::BoxFuture, StreamExt};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sc_client_api::{Backend, BlockchainEvents};
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto};
+use sp_mvm::event::{offchain, MoveEventRecord};
+
+/// Offchain key of the last block which events are already archived.
+const LAST_ARCHIVED_KEY: &[u8] = b"mvm::events_archived";
+
+/// Max amount of blocks archived per finality notification.
+const MAX_BLOCKS_PER_ROUND: u64 = 1000;
+
+/// Configuration of the events archive.
+#[derive(Debug, Clone)]
+pub struct EventsArchiveConfig {
+    /// Events are kept in the database for `retention` finalized blocks.
+    pub retention: u64,
+    /// Where pruned events are exported.
+    pub sink: EventsSinkConfig,
+}
+
+/// Sink for the pruned events.
+#[derive(Debug, Clone)]
+pub enum EventsSinkConfig {
+    /// Drop events without export.
+    None,
+    /// Write one JSON file per block into directory.
+    File(PathBuf),
+    /// Put one JSON object per block into S3-compatible storage.
+    S3(S3Config),
+}
+
+impl FromStr for EventsSinkConfig {
+    type Err = String;
+
+    /// Parses `none`, `file:<dir>` or `s3:<endpoint>/<bucket>`.
+    ///
+    /// S3 credentials are read from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`
+    /// and `AWS_REGION` (`us-east-1` by default) environment variables.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "none" {
+            Ok(Self::None)
+        } else if let Some(dir) = s.strip_prefix("file:") {
+            Ok(Self::File(PathBuf::from(dir)))
+        } else if let Some(url) = s.strip_prefix("s3:") {
+            let (endpoint, bucket) = url
+                .trim_end_matches('/')
+                .rsplit_once('/')
+                .ok_or("s3 sink should be in format `s3:<endpoint>/<bucket>`")?;
+            let env = |name: &str| {
+                std::env::var(name).map_err(|_| format!("{} is not set for s3 sink", name))
+            };
+            Ok(Self::S3(S3Config {
+                endpoint: endpoint.to_owned(),
+                bucket: bucket.to_owned(),
+                region: env("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_owned()),
+                access_key: env("AWS_ACCESS_KEY_ID")?,
+                secret_key: env("AWS_SECRET_ACCESS_KEY")?,
+            }))
+        } else {
+            Err("unknown events sink, expected `none`, `file:<dir>` or `s3:<endpoint>/<bucket>`"
+                .into())
+        }
+    }
+}
+
+/// S3-compatible endpoint configuration.
+#[derive(Clone)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+impl std::fmt::Debug for S3Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3Config")
+            .field("endpoint", &self.endpoint)
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .finish()
+    }
+}
+
+/// Events of one block prepared for export.
+#[derive(Serialize)]
+pub struct EventsBatch {
+    pub block: u64,
+    pub hash: String,
+    pub events: Vec<ArchivedEvent>,
+}
+
+/// Exported Move event.
+#[derive(Serialize)]
+pub struct ArchivedEvent {
+    pub index: u32,
+    pub guid: String,
+    pub type_tag: String,
+    pub message: String,
+}
+
+impl ArchivedEvent {
+    fn new(index: u32, record: MoveEventRecord) -> Self {
+        Self {
+            index,
+            guid: format!("0x{}", hex(&record.guid)),
+            type_tag: String::from_utf8_lossy(&record.ty_tag).into_owned(),
+            message: format!("0x{}", hex(&record.message)),
+        }
+    }
+}
+
+/// Pluggable sink for pruned events.
+pub trait EventsSink: Send + Sync {
+    /// Exports events of one block. Events are pruned only if export succeeded.
+    fn export<'a>(&'a self, batch: &'a EventsBatch) -> BoxFuture<'a, Result<(), String>>;
+}
+
+/// Sink dropping events.
+pub struct NoneSink;
+
+impl EventsSink for NoneSink {
+    fn export<'a>(&'a self, _: &'a EventsBatch) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Sink writing `<block>.json` files into directory.
+pub struct FileSink(PathBuf);
+
+impl EventsSink for FileSink {
+    fn export<'a>(&'a self, batch: &'a EventsBatch) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            std::fs::create_dir_all(&self.0).map_err(|e| e.to_string())?;
+            let json = serde_json::to_vec(batch).map_err(|e| e.to_string())?;
+            std::fs::write(self.0.join(format!("{}.json", batch.block)), json)
+                .map_err(|e| e.to_string())
+        })
+    }
+}
+
+/// Sink putting `<block>.json` objects into S3-compatible bucket (path-style, AWS SigV4).
+pub struct S3Sink {
+    config: S3Config,
+    client: hyper::Client<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>>,
+}
+
+impl S3Sink {
+    pub fn new(config: S3Config) -> Self {
+        let connector = hyper_rustls::HttpsConnector::with_native_roots();
+        Self {
+            config,
+            client: hyper::Client::builder().build(connector),
+        }
+    }
+
+    fn request(&self, key: &str, body: Vec<u8>) -> Result<hyper::Request<hyper::Body>, String> {
+        let uri: hyper::Uri = format!("{}/{}/{}", self.config.endpoint, self.config.bucket, key)
+            .parse()
+            .map_err(|e: hyper::http::uri::InvalidUri| e.to_string())?;
+        let host = uri.authority().ok_or("s3 endpoint without host")?.to_string();
+
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex(&Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "PUT\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            uri.path(),
+            host,
+            payload_hash,
+            amz_date,
+            payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let key = [
+            date.as_bytes(),
+            self.config.region.as_bytes(),
+            &b"s3"[..],
+            &b"aws4_request"[..],
+        ]
+        .iter()
+        .fold(
+            format!("AWS4{}", self.config.secret_key).into_bytes(),
+            |key, part| hmac_sha256(&key, part),
+        );
+        let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+        hyper::Request::put(uri)
+            .header("host", host)
+            .header("content-type", "application/json")
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header(
+                "authorization",
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+                    self.config.access_key, scope, signature
+                ),
+            )
+            .body(body.into())
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl EventsSink for S3Sink {
+    fn export<'a>(&'a self, batch: &'a EventsBatch) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let body = serde_json::to_vec(batch).map_err(|e| e.to_string())?;
+            let request = self.request(&format!("{}.json", batch.block), body)?;
+            let response = self
+                .client
+                .request(request)
+                .await
+                .map_err(|e| e.to_string())?;
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(format!("s3 responded with {}", response.status()))
+            }
+        })
+    }
+}
+
+impl EventsSinkConfig {
+    /// Creates sink according to configuration.
+    pub fn into_sink(self) -> Box<dyn EventsSink> {
+        match self {
+            Self::None => Box::new(NoneSink),
+            Self::File(dir) => Box::new(FileSink(dir)),
+            Self::S3(config) => Box::new(S3Sink::new(config)),
+        }
+    }
+}
+
+/// Runs the archive worker: on every finality notification exports and prunes events
+/// of finalized blocks older than the retention period.
+pub async fn run_events_archive<Block, Client, BE>(
+    config: EventsArchiveConfig,
+    client: Arc<Client>,
+    backend: Arc<BE>,
+) where
+    Block: BlockT,
+    Client: BlockchainEvents<Block> + HeaderBackend<Block>,
+    BE: Backend<Block>,
+{
+    let mut storage = match backend.offchain_storage() {
+        Some(storage) => storage,
+        None => {
+            log::warn!("Offchain storage is not available, Move events archive disabled");
+            return;
+        }
+    };
+    let sink = config.sink.into_sink();
+
+    let mut finality = client.finality_notification_stream();
+    while let Some(notification) = finality.next().await {
+        let finalized: u64 = (*notification.header.number()).unique_saturated_into();
+        let until = finalized.saturating_sub(config.retention);
+
+        let mut next = storage
+            .get(STORAGE_PREFIX, LAST_ARCHIVED_KEY)
+            .and_then(|last| u64::decode(&mut &last[..]).ok())
+            .map(|last| last + 1)
+            .unwrap_or(1);
+
+        let round_end = until.min(next.saturating_add(MAX_BLOCKS_PER_ROUND));
+        while next <= round_end {
+            if let Err(err) = archive_block(&mut storage, &*client, &*sink, next).await {
+                // Keep events, export is retried on the next notification.
+                log::error!("Failed to archive Move events of block {}: {}", next, err);
+                break;
+            }
+            storage.set(STORAGE_PREFIX, LAST_ARCHIVED_KEY, &next.encode());
+            next += 1;
+        }
+    }
+}
+
+/// Exports events of the block and removes them from the offchain storage.
+async fn archive_block<Block, Client, S>(
+    storage: &mut S,
+    client: &Client,
+    sink: &dyn EventsSink,
+    block: u64,
+) -> Result<(), String>
+where
+    Block: BlockT,
+    Client: HeaderBackend<Block>,
+    S: OffchainStorage,
+{
+    let count_key = offchain::events_count_key(block);
+    let count = match storage.get(STORAGE_PREFIX, &count_key) {
+        Some(count) => u32::decode(&mut &count[..]).map_err(|e| e.to_string())?,
+        None => return Ok(()),
+    };
+
+    let events = (0..count)
+        .filter_map(|index| {
+            storage
+                .get(STORAGE_PREFIX, &offchain::event_key(block, index))
+                .and_then(|record| MoveEventRecord::decode(&mut &record[..]).ok())
+                .map(|record| ArchivedEvent::new(index, record))
+        })
+        .collect();
+
+    let hash = client
+        .hash(block.unique_saturated_into())
+        .map_err(|e| e.to_string())?
+        .map(|hash| format!("{:?}", hash))
+        .unwrap_or_default();
+
+    sink.export(&EventsBatch {
+        block,
+        hash,
+        events,
+    })
+    .await?;
+
+    for index in 0..count {
+        storage.remove(STORAGE_PREFIX, &offchain::event_key(block, index));
+    }
+    storage.remove(STORAGE_PREFIX, &count_key);
+
+    Ok(())
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,233 @@
+//! `pontem export-move-state` command: writes Move modules and resources of the block to a
+//! directory tree for audits and for seeding local dev chains.
+//!
+//! Every module is written to `<address>/modules/<Name>.mv` and every resource to
+//! `<address>/resources/<Module>.<Name>.bcs`. `manifest.json` lists all entries with their
+//! `VMStorage` trie keys: storage value of the entry is the SCALE encoded file content, so the
+//! state could be put to the raw chain spec as is, or into genesis of the dev chain with
+//! `--mvm-genesis-state` (see [`read_move_state`]).
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use clap::Parser as Clap;
+use codec::Decode;
+use serde::{Deserialize, Serialize};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::bytes::{from_hex, to_hex};
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{Block as BlockT, NumberFor, UniqueSaturatedInto};
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use sp_mvm_rpc::abi::type_tag_string;
+use sp_mvm_rpc::changes::AccountKey;
+use sp_mvm_rpc::state::state_entries;
+
+/// Name of the manifest file in the output directory.
+const MANIFEST: &str = "manifest.json";
+/// Length of the blake2-128 hash of the access key in the trie key.
+const KEY_HASH_LENGTH: usize = 16;
+
+#[derive(Debug, Clap)]
+pub struct ExportMoveStateCmd {
+    /// Block hash or number to export state at, best block by default.
+    #[clap(long, value_name = "HASH or NUMBER")]
+    pub at: Option<BlockNumberOrHash>,
+
+    /// Output directory, created if missing.
+    #[clap(short, long, parse(from_os_str))]
+    pub output: PathBuf,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub pruning_params: PruningParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub database_params: DatabaseParams,
+}
+
+/// Exported state written to `manifest.json`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub block_number: u64,
+    pub block_hash: String,
+    pub modules: Vec<ManifestEntry>,
+    pub resources: Vec<ManifestEntry>,
+}
+
+/// Exported module or resource.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Module id (`0x1::Signer`) or resource type (`0x1::Account::Balance<0x1::NOX::NOX>`).
+    pub id: String,
+    /// File path relative to the output directory.
+    pub path: String,
+    /// `VMStorage` trie key of the entry.
+    pub storage_key: String,
+}
+
+impl ExportMoveStateCmd {
+    pub async fn run<B, BA, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+    where
+        B: BlockT,
+        BA: Backend<B>,
+        C: StorageProvider<B, BA> + HeaderBackend<B>,
+        <B::Hash as FromStr>::Err: Debug,
+        <NumberFor<B> as FromStr>::Err: Debug,
+    {
+        let at = match &self.at {
+            Some(at) => at.parse::<B>()?,
+            None => BlockId::Hash(client.info().best_hash),
+        };
+        let hash = client
+            .block_hash_from_id(&at)?
+            .ok_or_else(|| format!("Unknown block {}", at))?;
+        let number = client
+            .block_number_from_id(&at)?
+            .ok_or_else(|| format!("Unknown block {}", at))?;
+
+        let prefix = sp_mvm::proof::vm_storage_prefix::<pontem_runtime::Runtime>();
+        let entries = state_entries(&*client, &BlockId::Hash(hash), &prefix)?;
+
+        let mut manifest = Manifest {
+            block_number: number.unique_saturated_into(),
+            block_hash: format!("{:?}", hash),
+            modules: vec![],
+            resources: vec![],
+        };
+        let mut paths = HashSet::new();
+        for (address, key, storage_key, value) in entries {
+            let address = address_dir(&address);
+            let (id, path) = match &key {
+                AccountKey::Module(id) => {
+                    let path = format!("{}/modules/{}.mv", address, id.name());
+                    (format!("{}::{}", address, id.name()), path)
+                }
+                AccountKey::Resource(tag) => {
+                    let stem = format!("{}/resources/{}", address, resource_file_stem(tag));
+                    let path = unique_path(&mut paths, stem, "bcs");
+                    (type_tag_string(&TypeTag::Struct(tag.clone())), path)
+                }
+            };
+            write(&self.output.join(&path), &value)?;
+
+            let entry = ManifestEntry {
+                id,
+                path,
+                storage_key: to_hex(&storage_key, false),
+            };
+            match key {
+                AccountKey::Module(_) => manifest.modules.push(entry),
+                AccountKey::Resource(_) => manifest.resources.push(entry),
+            }
+        }
+        manifest.modules.sort_by(|a, b| a.path.cmp(&b.path));
+        manifest.resources.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+        write(&self.output.join(MANIFEST), &json)?;
+
+        println!(
+            "Exported {} modules and {} resources of block #{} to {:?}",
+            manifest.modules.len(),
+            manifest.resources.len(),
+            manifest.block_number,
+            self.output
+        );
+        Ok(())
+    }
+}
+
+impl CliConfiguration for ExportMoveStateCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn pruning_params(&self) -> Option<&PruningParams> {
+        Some(&self.pruning_params)
+    }
+
+    fn database_params(&self) -> Option<&DatabaseParams> {
+        Some(&self.database_params)
+    }
+}
+
+/// Reads the exported state as `VMStorage` entries: access keys with values.
+pub fn read_move_state(dir: &Path) -> Result<Vec<(Vec<u8>, Vec<u8>)>, String> {
+    let manifest = read(&dir.join(MANIFEST))?;
+    let manifest: Manifest = serde_json::from_slice(&manifest)
+        .map_err(|e| format!("Invalid manifest {:?}: {}", dir.join(MANIFEST), e))?;
+    let prefix = sp_mvm::proof::vm_storage_prefix::<pontem_runtime::Runtime>();
+
+    manifest
+        .modules
+        .iter()
+        .chain(&manifest.resources)
+        .map(|entry| {
+            let storage_key = from_hex(&entry.storage_key)
+                .map_err(|e| format!("Invalid storage key of {}: {}", entry.id, e))?;
+            let access_key = storage_key
+                .strip_prefix(&prefix[..])
+                .and_then(|key| key.get(KEY_HASH_LENGTH..))
+                .and_then(|key| Vec::<u8>::decode(&mut &key[..]).ok())
+                .ok_or_else(|| format!("{} isn't a Move state entry", entry.id))?;
+            Ok((access_key, read(&dir.join(&entry.path))?))
+        })
+        .collect()
+}
+
+/// Directory of the address: short hex literal, e.g. `0x1`.
+fn address_dir(address: &AccountAddress) -> String {
+    address.to_hex_literal()
+}
+
+/// File name of the resource without extension: `<Module>.<Name>`, type parameters are
+/// appended with characters not allowed in file names replaced by `_`.
+fn resource_file_stem(tag: &StructTag) -> String {
+    let mut stem = format!("{}.{}", tag.module, tag.name);
+    if !tag.type_params.is_empty() {
+        let params: Vec<_> = tag.type_params.iter().map(type_tag_string).collect();
+        let params: String = params
+            .join(",")
+            .replace("::", ".")
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '_' })
+            .collect();
+        stem.push('_');
+        stem.push_str(&params);
+    }
+    stem
+}
+
+/// Path with the extension, numbered if sanitized resource names of the address collide.
+fn unique_path(paths: &mut HashSet<String>, stem: String, extension: &str) -> String {
+    let mut path = format!("{}.{}", stem, extension);
+    let mut index = 1;
+    while !paths.insert(path.clone()) {
+        path = format!("{}.{}.{}", stem, index, extension);
+        index += 1;
+    }
+    path
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))
+}
+
+fn write(path: &Path, content: &[u8]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    }
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
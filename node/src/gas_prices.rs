@@ -0,0 +1,50 @@
+//! Worker recording gas unit prices of the Move extrinsics for `mvm_gasPrice`.
+//!
+//! Every new best block is read once: gas unit prices of its `Mvm` extrinsics (submitted
+//! directly, as `ChargeGasPrice` matches them) go to the rolling window of the oracle, whether
+//! their dispatch succeeded or not, as they are included and prioritized by the price anyway.
+
+use std::sync::Arc;
+use futures::StreamExt;
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::Header as HeaderT;
+
+use pontem_runtime::{gas_unit_price_of, Block};
+use sp_mvm_rpc::gas_price::GasPriceOracle;
+
+/// Gas unit prices of the Move extrinsics of the block.
+fn block_gas_prices<C>(client: &C, at: &BlockId<Block>) -> Result<Vec<u64>, String>
+where
+    C: BlockBackend<Block>,
+{
+    let extrinsics = client
+        .block_body(at)
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    Ok(extrinsics
+        .iter()
+        .filter_map(gas_unit_price_of)
+        .collect())
+}
+
+/// Runs the oracle worker: records every new best block.
+pub async fn run_gas_price_oracle<C>(oracle: GasPriceOracle, client: Arc<C>)
+where
+    C: BlockchainEvents<Block> + BlockBackend<Block>,
+{
+    let mut imports = client.import_notification_stream();
+    while let Some(notification) = imports.next().await {
+        if !notification.is_new_best {
+            continue;
+        }
+        match block_gas_prices(&*client, &BlockId::Hash(notification.hash)) {
+            Ok(prices) => oracle.record_block((*notification.header.number()).into(), prices),
+            Err(err) => log::warn!(
+                "Failed to record gas prices of block {:?}: {}",
+                notification.hash,
+                err
+            ),
+        }
+    }
+}
@@ -1,6 +1,16 @@
 pub mod chain_spec;
 pub mod cli;
 pub mod command;
+pub mod diff_abi;
+pub mod events_archive;
+pub mod export_move_state;
+pub mod gas_prices;
+pub mod move_cmd;
+pub mod move_metrics;
+pub mod move_replay;
+pub mod replay;
 pub mod rpc;
+pub mod rpc_client;
 pub mod service;
+pub mod transaction_index;
 pub mod vm_config;
@@ -0,0 +1,218 @@
+//! `pontem move publish` and `pontem move run` commands: publish compiled Move modules and
+//! execute Move transactions on a live node without external deployment scripts.
+//!
+//! Extrinsic is built and signed locally with the `--suri` key, then submitted with
+//! `author_submitExtrinsic`. Nonce, runtime versions and genesis hash are requested from
+//! the node, the extrinsic is immortal. Gas limit is estimated by the node unless set.
+
+use std::path::{Path, PathBuf};
+
+use clap::Parser as Clap;
+use codec::Encode;
+use serde_json::{json, Value};
+use sp_core::bytes::to_hex;
+use sp_core::crypto::{AccountId32, Pair as _, Ss58Codec};
+use sp_core::sr25519;
+use sp_runtime::generic::{Era, SignedPayload};
+use sp_runtime::{MultiAddress, MultiSignature};
+
+use pontem_runtime::{Call, SignedExtra, UncheckedExtrinsic};
+use primitives::{Hash, Index};
+
+use crate::diff_abi::package_modules;
+use crate::rpc_client::RpcClient;
+
+/// Move VM status of the successful execution.
+const EXECUTED: u64 = 4001;
+/// Gas limit of the estimation, the same as the default limit of Move VM transactions.
+const ESTIMATION_GAS_LIMIT: u64 = 1_000_000;
+
+/// Publish and run Move contracts on a live node, replay them from the node database.
+#[derive(Debug, Clap)]
+pub enum MoveSubcommand {
+    /// Publish module (`.mv`) or package bundle (`.pac`).
+    Publish(MovePublishCmd),
+
+    /// Execute transaction built by `dove tx` (`.mvt`).
+    Run(MoveRunCmd),
+
+    /// Replay Move extrinsic of a historical block from the node database and compare
+    /// its write-set with the chain.
+    Replay(crate::move_replay::MoveReplayCmd),
+}
+
+/// Parameters of the submitted extrinsic.
+#[derive(Debug, Clap)]
+pub struct SubmitParams {
+    /// HTTP RPC endpoint of the node.
+    #[clap(long, default_value = "http://127.0.0.1:9933")]
+    pub rpc: String,
+
+    /// Secret URI of the sr25519 signer key, e.g. `//Alice` or a mnemonic phrase.
+    #[clap(long)]
+    pub suri: String,
+
+    /// Gas limit, estimated by the node with 20% margin by default.
+    #[clap(long)]
+    pub gas_limit: Option<u64>,
+
+    /// Gas unit price: tip per gas unit, raises priority of the extrinsic.
+    #[clap(long, default_value = "0")]
+    pub gas_unit_price: u64,
+}
+
+#[derive(Debug, Clap)]
+pub struct MovePublishCmd {
+    /// Compiled module (`.mv`) or package bundle (`.pac`).
+    #[clap(parse(from_os_str))]
+    pub path: PathBuf,
+
+    #[clap(flatten)]
+    pub params: SubmitParams,
+}
+
+impl MovePublishCmd {
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let bytecode = read(&self.path)?;
+        let is_package = self.path.extension().map_or(false, |ext| ext == "pac");
+        let estimate = if is_package {
+            let modules = package_modules(&bytecode)?;
+            let modules: Vec<_> = modules.iter().map(|module| to_hex(module, false)).collect();
+            ("mvm_estimateGasPublishPackage", json!(modules))
+        } else {
+            ("mvm_estimateGasPublish", json!(to_hex(&bytecode, false)))
+        };
+
+        submit(&self.params, estimate, |gas_limit| {
+            let call = if is_package {
+                sp_mvm::Call::publish_package {
+                    package: bytecode.clone(),
+                    gas_limit,
+                }
+            } else {
+                sp_mvm::Call::publish_module {
+                    module_bc: bytecode.clone(),
+                    gas_limit,
+                }
+            };
+            Call::Mvm(call)
+        })
+    }
+}
+
+#[derive(Debug, Clap)]
+pub struct MoveRunCmd {
+    /// Transaction built by `dove tx` (`.mvt`).
+    #[clap(parse(from_os_str))]
+    pub path: PathBuf,
+
+    #[clap(flatten)]
+    pub params: SubmitParams,
+}
+
+impl MoveRunCmd {
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let tx_bc = read(&self.path)?;
+        let estimate = ("mvm_estimateGasExecute", json!(to_hex(&tx_bc, false)));
+
+        submit(&self.params, estimate, |gas_limit| {
+            Call::Mvm(sp_mvm::Call::execute {
+                tx_bc: tx_bc.clone(),
+                gas_limit,
+            })
+        })
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))
+}
+
+/// Builds call with the gas limit, signs and submits it, prints hash of the extrinsic.
+///
+/// `estimate` is the estimation RPC method and its bytecode parameter.
+fn submit<F>(params: &SubmitParams, estimate: (&str, Value), call: F) -> sc_cli::Result<()>
+where
+    F: Fn(u64) -> Call,
+{
+    let pair = sr25519::Pair::from_string(&params.suri, None)
+        .map_err(|e| format!("Invalid secret URI: {:?}", e))?;
+    let account = AccountId32::from(pair.public());
+    let client = RpcClient::new(&params.rpc)?;
+    let runtime = sc_cli::build_runtime()?;
+
+    let hash = runtime.block_on(async {
+        let gas_limit = match params.gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => estimate_gas(&client, &account, estimate).await?,
+        };
+        let call = call(gas_limit);
+
+        let nonce = json!([account.to_ss58check()]);
+        let nonce: Index = result(client.request("system_accountNextIndex", nonce).await?)?;
+        let genesis: Hash = result(client.request("chain_getBlockHash", json!([0])).await?)?;
+        let version = client.request("state_getRuntimeVersion", json!([])).await?;
+        let spec_version = version["specVersion"].as_u64().ok_or("Invalid runtime version")?;
+        let tx_version =
+            version["transactionVersion"].as_u64().ok_or("Invalid runtime version")?;
+
+        let extra: SignedExtra = (
+            frame_system::CheckSpecVersion::new(),
+            frame_system::CheckTxVersion::new(),
+            frame_system::CheckGenesis::new(),
+            frame_system::CheckEra::from(Era::Immortal),
+            frame_system::CheckNonce::from(nonce),
+            frame_system::CheckWeight::new(),
+            pallet_transaction_payment::ChargeTransactionPayment::from(0),
+            sp_mvm::gas_price::ChargeGasPrice::from(params.gas_unit_price),
+            sp_mvm::session::CheckSessionKey::new(),
+            sp_mvm::sponsor::CheckSponsor::new(),
+        );
+        let additional = (
+            spec_version as u32,
+            tx_version as u32,
+            genesis,
+            genesis,
+            (),
+            (),
+            (),
+            (),
+            (),
+            (),
+        );
+        let payload = SignedPayload::from_raw(call.clone(), extra.clone(), additional);
+        let signature = MultiSignature::from(payload.using_encoded(|payload| pair.sign(payload)));
+        let extrinsic =
+            UncheckedExtrinsic::new_signed(call, MultiAddress::Id(account), signature, extra);
+
+        let hash = client
+            .request("author_submitExtrinsic", json!([to_hex(&extrinsic.encode(), false)]))
+            .await?;
+        Ok::<_, String>(hash)
+    })?;
+
+    println!("Submitted extrinsic {}", hash.as_str().unwrap_or_default());
+    Ok(())
+}
+
+/// Estimates gas of the call, fails if the dry run isn't executed successfully.
+async fn estimate_gas(
+    client: &RpcClient,
+    account: &AccountId32,
+    (method, bytecode): (&str, Value),
+) -> Result<u64, String> {
+    let estimation = client
+        .request(method, json!([account.to_ss58check(), bytecode, ESTIMATION_GAS_LIMIT]))
+        .await?;
+    let gas_used = estimation["gas_used"].as_u64().ok_or("Invalid gas estimation")?;
+    let status_code = estimation["status_code"].as_u64().ok_or("Invalid gas estimation")?;
+    if status_code != EXECUTED {
+        return Err(format!("Dry run failed with Move VM status {}", status_code));
+    }
+    Ok(gas_used.saturating_add(gas_used / 5).min(ESTIMATION_GAS_LIMIT))
+}
+
+/// Decodes the RPC result.
+fn result<T: serde::de::DeserializeOwned>(value: Value) -> Result<T, String> {
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
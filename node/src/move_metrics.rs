@@ -0,0 +1,213 @@
+//! Prometheus metrics of the Move VM calls in imported blocks.
+//!
+//! Outcome of every `Mvm` extrinsic of the new best block is taken from the block events:
+//! calls failed with the error of the Move VM pallet are counted as aborts, other failures
+//! (e.g. bad origin) as failed. Gas used is converted back from the actual weight of the calls.
+//! Loader cache counters are taken from `Mvm::LoaderStats` of the block.
+
+use std::sync::Arc;
+use codec::Decode;
+use futures::StreamExt;
+use frame_support::dispatch::GetCallName;
+use frame_support::storage::StorageValue;
+use frame_support::traits::PalletInfoAccess;
+use frame_system::{EventRecord, Phase};
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents, StorageProvider};
+use sp_core::hashing::twox_128;
+use sp_core::storage::StorageKey;
+use sp_runtime::generic::BlockId;
+use sp_runtime::{DispatchError, ModuleError};
+use substrate_prometheus_endpoint::{
+    exponential_buckets, register, Counter, CounterVec, Histogram, HistogramOpts, Opts,
+    PrometheusError, Registry, U64,
+};
+
+use pontem_runtime::{Block, Call, Event, Mvm, Runtime};
+use primitives::Hash;
+use sp_mvm::gas::GasWeightMapping;
+use sp_mvm::types::LoaderCacheStats;
+
+/// Metrics of the Move VM calls.
+#[derive(Clone)]
+pub struct MoveMetrics {
+    /// Move VM extrinsics by call and outcome (`success`, `abort` or `failed`).
+    calls: CounterVec<U64>,
+    /// Published modules and packages.
+    published: CounterVec<U64>,
+    /// Gas used by Move VM extrinsics of the block.
+    block_gas: Histogram,
+    /// Loader cache lookups by result (`hit` or `miss`).
+    loader_cache: CounterVec<U64>,
+    /// Modules verified by the loader or on publishing.
+    verifier_runs: Counter<U64>,
+    /// Loader cache flushes.
+    cache_flushes: Counter<U64>,
+}
+
+impl MoveMetrics {
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            calls: register(
+                CounterVec::new(
+                    Opts::new("pontem_mvm_calls_total", "Move VM extrinsics in imported blocks"),
+                    &["call", "outcome"],
+                )?,
+                registry,
+            )?,
+            published: register(
+                CounterVec::new(
+                    Opts::new(
+                        "pontem_mvm_published_total",
+                        "Published Move modules and packages",
+                    ),
+                    &["kind"],
+                )?,
+                registry,
+            )?,
+            block_gas: register(
+                Histogram::with_opts(
+                    HistogramOpts::new(
+                        "pontem_mvm_block_gas_used",
+                        "Gas used by Move VM extrinsics of the block",
+                    )
+                    .buckets(exponential_buckets(1_000.0, 4.0, 12)?),
+                )?,
+                registry,
+            )?,
+            loader_cache: register(
+                CounterVec::new(
+                    Opts::new(
+                        "pontem_mvm_loader_cache_total",
+                        "Move VM loader cache lookups in imported blocks",
+                    ),
+                    &["result"],
+                )?,
+                registry,
+            )?,
+            verifier_runs: register(
+                Counter::new(
+                    "pontem_mvm_verifier_runs_total",
+                    "Move modules verified in imported blocks",
+                )?,
+                registry,
+            )?,
+            cache_flushes: register(
+                Counter::new(
+                    "pontem_mvm_loader_cache_flushes_total",
+                    "Move VM loader cache flushes in imported blocks",
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    /// Observes Move VM extrinsics of the block.
+    fn observe_block<BA, C>(&self, client: &C, hash: Hash) -> Result<(), String>
+    where
+        BA: Backend<Block>,
+        C: BlockBackend<Block> + StorageProvider<Block, BA>,
+    {
+        let at = BlockId::Hash(hash);
+        let extrinsics = client
+            .block_body(&at)
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default();
+        let events = client
+            .storage(&at, &StorageKey(events_key()))
+            .map_err(|e| e.to_string())?
+            .map(|events| Vec::<EventRecord<Event, Hash>>::decode(&mut &events.0[..]))
+            .transpose()
+            .map_err(|e| format!("Failed to decode events: {}", e))?
+            .unwrap_or_default();
+
+        let mvm_index = <Mvm as PalletInfoAccess>::index() as u8;
+        let mut gas_used = 0u64;
+        for record in events {
+            match record.event {
+                Event::Mvm(sp_mvm::Event::ModulePublished(_)) => {
+                    self.published.with_label_values(&["module"]).inc()
+                }
+                Event::Mvm(sp_mvm::Event::PackagePublished(_)) => {
+                    self.published.with_label_values(&["package"]).inc()
+                }
+                _ => {}
+            }
+
+            let call = match record.phase {
+                Phase::ApplyExtrinsic(index) => {
+                    extrinsics.get(index as usize).map(|xt| &xt.function)
+                }
+                _ => None,
+            };
+            let call = match call {
+                Some(Call::Mvm(call)) => call,
+                _ => continue,
+            };
+            let (outcome, info) = match &record.event {
+                Event::System(frame_system::Event::ExtrinsicSuccess { dispatch_info }) => {
+                    ("success", dispatch_info)
+                }
+                Event::System(frame_system::Event::ExtrinsicFailed {
+                    dispatch_error,
+                    dispatch_info,
+                }) => match dispatch_error {
+                    DispatchError::Module(ModuleError { index, .. }) if *index == mvm_index => {
+                        ("abort", dispatch_info)
+                    }
+                    _ => ("failed", dispatch_info),
+                },
+                _ => continue,
+            };
+            self.calls
+                .with_label_values(&[call.get_call_name(), outcome])
+                .inc();
+            gas_used = gas_used.saturating_add(
+                <Runtime as sp_mvm::Config>::GasWeightMapping::weight_to_gas(info.weight),
+            );
+        }
+        self.block_gas.observe(gas_used as f64);
+
+        let stats = client
+            .storage(&at, &StorageKey(sp_mvm::LoaderStats::<Runtime>::hashed_key().to_vec()))
+            .map_err(|e| e.to_string())?
+            .map(|stats| LoaderCacheStats::decode(&mut &stats.0[..]))
+            .transpose()
+            .map_err(|e| format!("Failed to decode loader stats: {}", e))?
+            .unwrap_or_default();
+        self.loader_cache
+            .with_label_values(&["hit"])
+            .inc_by(stats.hits.into());
+        self.loader_cache
+            .with_label_values(&["miss"])
+            .inc_by(stats.misses.into());
+        self.verifier_runs.inc_by(stats.verifier_runs.into());
+        self.cache_flushes.inc_by(stats.flushes.into());
+        Ok(())
+    }
+}
+
+/// Runs the metrics worker: observes every new best block.
+pub async fn run_move_metrics<BA, C>(metrics: MoveMetrics, client: Arc<C>)
+where
+    BA: Backend<Block>,
+    C: BlockchainEvents<Block> + BlockBackend<Block> + StorageProvider<Block, BA>,
+{
+    let mut imports = client.import_notification_stream();
+    while let Some(notification) = imports.next().await {
+        if !notification.is_new_best {
+            continue;
+        }
+        if let Err(err) = metrics.observe_block::<BA, _>(&*client, notification.hash) {
+            log::warn!(
+                "Failed to collect Move VM metrics of block {:?}: {}",
+                notification.hash,
+                err
+            );
+        }
+    }
+}
+
+/// Trie key of `System::Events`.
+fn events_key() -> Vec<u8> {
+    [twox_128(b"System"), twox_128(b"Events")].concat()
+}
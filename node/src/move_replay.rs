@@ -0,0 +1,302 @@
+//! `pontem move replay` command: re-executes Move extrinsic of a historical block against
+//! the parent state from the node database and compares its write-set with the chain.
+//!
+//! Extrinsics of the block are applied up to the replayed one, so the extrinsic sees the same
+//! state as on chain. Write-set is the `VMStorage` entries changed by the extrinsic. Every entry
+//! is compared with the state of the block: entries changed again by later extrinsics are
+//! checked by replaying the whole block. Use `--execution native` or `--execution wasm` to find
+//! discrepancies between native and wasm runtimes.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use clap::Parser as Clap;
+use serde::Serialize;
+use sc_cli::{BlockNumberOrHash, CliConfiguration, ImportParams, SharedParams};
+use sc_client_api::backend::StateBackendFor;
+use sc_client_api::{Backend, BlockBackend, StorageProvider};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
+use sp_core::bytes::to_hex;
+use sp_core::storage::StorageKey;
+use sp_runtime::generic::{BlockId, DigestItem};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+use move_core_types::language_storage::TypeTag;
+use pontem_runtime::{Block, Call};
+use sp_mvm_rpc::abi::type_tag_string;
+use sp_mvm_rpc::changes::{state_key, AccountKey};
+
+/// Changed `VMStorage` entries: trie keys with new values, `None` if removed.
+type VmChanges = BTreeMap<Vec<u8>, Option<Vec<u8>>>;
+
+#[derive(Debug, Clap)]
+pub struct MoveReplayCmd {
+    /// Block hash or number with the extrinsic.
+    #[clap(long, value_name = "HASH or NUMBER")]
+    pub block: BlockNumberOrHash,
+
+    /// Index of the extrinsic in the block.
+    #[clap(long)]
+    pub index: u32,
+
+    /// Print report as JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub shared_params: SharedParams,
+
+    #[allow(missing_docs)]
+    #[clap(flatten)]
+    pub import_params: ImportParams,
+}
+
+/// Replay report.
+#[derive(Debug, Serialize)]
+pub struct ReplayReport {
+    pub block_hash: String,
+    pub index: u32,
+    /// Dispatch result of the replayed extrinsic.
+    pub outcome: String,
+    pub write_set: Vec<WriteSetEntry>,
+    /// Entries changed by the whole block replay which differ from the state of the block.
+    pub block_mismatches: Vec<String>,
+}
+
+/// `VMStorage` entry changed by the replayed extrinsic.
+#[derive(Debug, Serialize)]
+pub struct WriteSetEntry {
+    /// Module id, resource owner and type, or hex of the unknown key.
+    pub key: String,
+    pub storage_key: String,
+    /// Value written by the replayed extrinsic, `None` if removed.
+    pub replayed: Option<String>,
+    /// Value in the state of the block.
+    pub on_chain: Option<String>,
+    pub status: EntryStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryStatus {
+    /// Replayed value is the value on chain.
+    Match,
+    /// Entry is changed by later extrinsics of the block, the whole block replay matches chain.
+    OverwrittenLater,
+    /// Replayed value differs from the chain.
+    Mismatch,
+}
+
+impl ReplayReport {
+    fn diverges(&self) -> bool {
+        !self.block_mismatches.is_empty()
+            || self
+                .write_set
+                .iter()
+                .any(|entry| entry.status == EntryStatus::Mismatch)
+    }
+}
+
+impl MoveReplayCmd {
+    pub async fn run<BA, C>(&self, client: Arc<C>, backend: Arc<BA>) -> sc_cli::Result<()>
+    where
+        BA: Backend<Block>,
+        C: ProvideRuntimeApi<Block> + BlockBackend<Block> + StorageProvider<Block, BA>,
+        C::Api: BlockBuilderApi<Block> + ApiExt<Block, StateBackend = StateBackendFor<BA, Block>>,
+    {
+        let at = self.block.parse::<Block>()?;
+        let block = client
+            .block(&at)?
+            .ok_or_else(|| format!("Unknown block {}", at))?
+            .block;
+        let block_hash = block.hash();
+        let (mut header, extrinsics) = block.deconstruct();
+        let index = self.index as usize;
+        match extrinsics.get(index).map(|xt| &xt.function) {
+            Some(Call::Mvm(_)) => {}
+            Some(_) => return Err("Extrinsic is not a Move VM call".into()),
+            None => return Err(format!("Extrinsic {} not found", index).into()),
+        }
+        // Seal is added after the block is built, it isn't known to the runtime.
+        header
+            .digest_mut()
+            .logs
+            .retain(|item| !matches!(item, DigestItem::Seal(..)));
+        let parent = BlockId::Hash(*header.parent_hash());
+
+        let replay = |count: usize, finalize: bool| {
+            vm_changes(&*client, &*backend, &header, &extrinsics[..count], finalize)
+        };
+        let (before, _) = replay(index, false)?;
+        let (after, outcome) = replay(index + 1, false)?;
+        let (block_changes, _) = replay(extrinsics.len(), true)?;
+
+        let prefix = sp_mvm::proof::vm_storage_prefix::<pontem_runtime::Runtime>();
+        let storage = |at: &BlockId<Block>, key: &[u8]| {
+            client
+                .storage(at, &StorageKey(key.to_vec()))
+                .map(|value| value.map(|value| value.0))
+        };
+        let on_chain = BlockId::Hash(block_hash);
+
+        let mut write_set = Vec::new();
+        let keys: BTreeSet<_> = before.keys().chain(after.keys()).collect();
+        for key in keys {
+            let before = match before.get(key) {
+                Some(value) => value.clone(),
+                None => storage(&parent, key)?,
+            };
+            let replayed = match after.get(key) {
+                Some(value) => value.clone(),
+                None => storage(&parent, key)?,
+            };
+            if before == replayed {
+                continue;
+            }
+
+            let chain_value = storage(&on_chain, key)?;
+            let status = if replayed == chain_value {
+                EntryStatus::Match
+            } else if block_changes.get(key) == Some(&chain_value) {
+                EntryStatus::OverwrittenLater
+            } else {
+                EntryStatus::Mismatch
+            };
+            write_set.push(WriteSetEntry {
+                key: display_key(&prefix, key),
+                storage_key: to_hex(key, false),
+                replayed: replayed.as_deref().map(|value| to_hex(value, false)),
+                on_chain: chain_value.as_deref().map(|value| to_hex(value, false)),
+                status,
+            });
+        }
+
+        let mut block_mismatches = Vec::new();
+        for (key, value) in &block_changes {
+            if *value != storage(&on_chain, key)? {
+                block_mismatches.push(display_key(&prefix, key));
+            }
+        }
+
+        let report = ReplayReport {
+            block_hash: format!("{:?}", block_hash),
+            index: self.index,
+            outcome,
+            write_set,
+            block_mismatches,
+        };
+        if self.json {
+            let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+            println!("{}", json);
+        } else {
+            print_report(&report);
+        }
+
+        if report.diverges() {
+            return Err("Replay diverges from the chain".into());
+        }
+        Ok(())
+    }
+}
+
+impl CliConfiguration for MoveReplayCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+
+    fn import_params(&self) -> Option<&ImportParams> {
+        Some(&self.import_params)
+    }
+}
+
+/// Applies extrinsics on top of the parent state, returns `VMStorage` changes and dispatch
+/// result of the last extrinsic.
+fn vm_changes<BA, C>(
+    client: &C,
+    backend: &BA,
+    header: &<Block as BlockT>::Header,
+    extrinsics: &[<Block as BlockT>::Extrinsic],
+    finalize: bool,
+) -> Result<(VmChanges, String), String>
+where
+    BA: Backend<Block>,
+    C: ProvideRuntimeApi<Block>,
+    C::Api: BlockBuilderApi<Block> + ApiExt<Block, StateBackend = StateBackendFor<BA, Block>>,
+{
+    let parent_hash = *header.parent_hash();
+    let parent = BlockId::Hash(parent_hash);
+    let api = client.runtime_api();
+
+    api.initialize_block(&parent, header)
+        .map_err(|e| e.to_string())?;
+    let mut outcome = String::new();
+    for (index, xt) in extrinsics.iter().enumerate() {
+        let result = api
+            .apply_extrinsic(&parent, xt.clone())
+            .map_err(|e| e.to_string())?;
+        if let Err(e) = result {
+            return Err(format!("Extrinsic {} is invalid in replay: {:?}", index, e));
+        }
+        outcome = format!("{:?}", result);
+    }
+    if finalize {
+        api.finalize_block(&parent).map_err(|e| e.to_string())?;
+    }
+
+    let state = backend.state_at(parent).map_err(|e| e.to_string())?;
+    let changes = api.into_storage_changes(&state, parent_hash)?;
+
+    let prefix = sp_mvm::proof::vm_storage_prefix::<pontem_runtime::Runtime>();
+    let changes = changes
+        .main_storage_changes
+        .into_iter()
+        .filter(|(key, _)| key.starts_with(&prefix[..]))
+        .collect();
+    Ok((changes, outcome))
+}
+
+/// Renders `VMStorage` trie key as module id or resource owner with type.
+fn display_key(prefix: &[u8], key: &[u8]) -> String {
+    match state_key(prefix, key) {
+        Some((_, AccountKey::Module(id))) => {
+            format!("{}::{}", id.address().to_hex_literal(), id.name())
+        }
+        Some((owner, AccountKey::Resource(tag))) => format!(
+            "{} {}",
+            owner.to_hex_literal(),
+            type_tag_string(&TypeTag::Struct(tag))
+        ),
+        None => to_hex(key, false),
+    }
+}
+
+fn print_report(report: &ReplayReport) {
+    println!(
+        "Extrinsic {} of block {}: {}",
+        report.index, report.block_hash, report.outcome
+    );
+    if report.write_set.is_empty() {
+        println!("Write-set is empty");
+    }
+    for entry in &report.write_set {
+        let status = match entry.status {
+            EntryStatus::Match => "match",
+            EntryStatus::OverwrittenLater => "overwritten later",
+            EntryStatus::Mismatch => "MISMATCH",
+        };
+        let value = |value: &Option<String>| match value {
+            Some(value) => value.clone(),
+            None => "removed".to_owned(),
+        };
+        println!("  [{}] {}", status, entry.key);
+        if entry.status == EntryStatus::Mismatch {
+            println!("    replayed: {}", value(&entry.replayed));
+            println!("    on chain: {}", value(&entry.on_chain));
+        }
+    }
+    for key in &report.block_mismatches {
+        println!("  Block replay differs from the chain: {}", key);
+    }
+}
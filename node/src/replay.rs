@@ -0,0 +1,288 @@
+//! `pontem mvm capture-replay` command: captures replay bundle of the Move VM call from a live node.
+//!
+//! Bundle contains `VMStorage` slice with modules and resources of `0x1`, the signer and
+//! additional addresses, native balances of the signer and additional accounts,
+//! block number and timestamp, the call and its outcome taken from the block events.
+//! State is taken at the parent block, so the call should not depend on the earlier
+//! extrinsics of the same block. Bundles are executed by the pallet tests, see
+//! `pallets/sp-mvm/tests/replay.rs`.
+
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+use clap::Parser as Clap;
+use codec::Decode;
+use serde_json::{json, Value};
+use sp_core::bytes::{from_hex, to_hex};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use sp_core::hashing::{blake2_128, twox_128};
+use sp_runtime::MultiAddress;
+use frame_support::traits::PalletInfoAccess;
+use frame_system::{AccountInfo, EventRecord, Phase};
+use pallet_balances::AccountData;
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+use pontem_runtime::{Call, Event, Mvm, UncheckedExtrinsic};
+use primitives::{Balance, Hash, Index};
+use sp_mvm::replay::{ReplayBundle, ReplayCall, ReplayOutcome};
+use sp_mvm::storage::{is_module_key, is_resource_key};
+
+use crate::rpc_client::RpcClient;
+
+/// Amount of keys requested per `state_getKeysPaged` call.
+const KEYS_PAGE: usize = 1000;
+
+#[derive(Debug, Clap)]
+pub struct CaptureReplayCmd {
+    /// Hash of the block with the extrinsic.
+    pub block: String,
+
+    /// Index of the extrinsic in the block.
+    pub index: u32,
+
+    /// HTTP RPC endpoint of the node (archive node for old blocks).
+    #[clap(long)]
+    pub rpc: String,
+
+    /// Additional account (SS58 or hex) to capture resources, modules and balance of.
+    #[clap(long = "address")]
+    pub addresses: Vec<String>,
+
+    /// Output file.
+    #[clap(short, long, parse(from_os_str), default_value = "mvm.replay")]
+    pub output: PathBuf,
+}
+
+impl CaptureReplayCmd {
+    pub fn run(&self) -> sc_cli::Result<()> {
+        let runtime = sc_cli::build_runtime()?;
+        let bundle = runtime.block_on(self.capture())?;
+
+        std::fs::write(&self.output, bundle.to_bytes())?;
+        println!(
+            "Captured {} storage entries, outcome {:?}, saved to {:?}",
+            bundle.state.len(),
+            bundle.expected,
+            self.output
+        );
+        Ok(())
+    }
+
+    async fn capture(&self) -> Result<ReplayBundle, String> {
+        let client = RpcClient::new(&self.rpc)?;
+
+        let block = client.request("chain_getBlock", json!([self.block])).await?;
+        let header = &block["block"]["header"];
+        let parent = header["parentHash"]
+            .as_str()
+            .ok_or("Block not found")?
+            .to_string();
+        let block_number = header["number"]
+            .as_str()
+            .and_then(|number| u64::from_str_radix(number.trim_start_matches("0x"), 16).ok())
+            .ok_or("Invalid block number")?;
+        let xt = block["block"]["extrinsics"][self.index as usize]
+            .as_str()
+            .ok_or_else(|| format!("Extrinsic {} not found", self.index))?;
+        let xt = UncheckedExtrinsic::decode(&mut &hex_bytes(xt)?[..])
+            .map_err(|e| format!("Failed to decode extrinsic: {}", e))?;
+
+        let signer = match &xt.signature {
+            Some((MultiAddress::Id(account), _, _)) => account.clone(),
+            _ => return Err("Only extrinsics signed by account id are supported".into()),
+        };
+        let call = replay_call(xt.function, <[u8; 32]>::from(signer.clone()))?;
+
+        let mut accounts = vec![signer];
+        for address in &self.addresses {
+            accounts.push(parse_account(address)?);
+        }
+        let mut addresses = vec![CORE_CODE_ADDRESS];
+        addresses.extend(
+            accounts
+                .iter()
+                .map(|account| AccountAddress::new(*account.as_ref())),
+        );
+
+        let state = vm_state(&client, &parent, &addresses).await?;
+
+        let mut balances = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            let key = [
+                storage_prefix("System", "Account"),
+                blake2_128(account.as_ref()).to_vec(),
+                account.as_ref().to_vec(),
+            ]
+            .concat();
+            let free = match get_storage::<AccountInfo<Index, AccountData<Balance>>>(
+                &client, &key, &parent,
+            )
+            .await?
+            {
+                Some(info) => info.data.free,
+                None => 0,
+            };
+            balances.push((account.into(), free as u128));
+        }
+
+        let timestamp =
+            get_storage::<u64>(&client, &storage_prefix("Timestamp", "Now"), &self.block)
+                .await?
+                .ok_or("Block timestamp not found")?;
+        let expected = outcome(&client, &self.block, self.index).await?;
+
+        Ok(ReplayBundle {
+            block_number,
+            timestamp,
+            state,
+            balances,
+            call,
+            expected,
+        })
+    }
+}
+
+/// Converts runtime call to the replay call.
+fn replay_call(call: Call, signer: [u8; 32]) -> Result<ReplayCall, String> {
+    let signer = Some(signer);
+    match call {
+        Call::Mvm(sp_mvm::Call::execute { tx_bc, gas_limit }) => Ok(ReplayCall::Execute {
+            signer,
+            tx_bc,
+            gas_limit,
+        }),
+        Call::Mvm(sp_mvm::Call::publish_module {
+            module_bc,
+            gas_limit,
+        })
+        | Call::Mvm(sp_mvm::Call::publish_module_with_source_map {
+            module_bc,
+            gas_limit,
+            ..
+        }) => Ok(ReplayCall::PublishModule {
+            signer,
+            module_bc,
+            gas_limit,
+        }),
+        Call::Mvm(sp_mvm::Call::publish_package { package, gas_limit })
+        | Call::Mvm(sp_mvm::Call::publish_package_with_metadata {
+            package,
+            gas_limit,
+            ..
+        }) => Ok(ReplayCall::PublishPackage {
+            signer,
+            package,
+            gas_limit,
+        }),
+        _ => Err("Extrinsic is not a Move VM execute or publish call".into()),
+    }
+}
+
+/// Collects `VMStorage` entries of the addresses.
+async fn vm_state(
+    client: &RpcClient,
+    at: &str,
+    addresses: &[AccountAddress],
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, String> {
+    let prefix = storage_prefix("Mvm", "VMStorage");
+    let mut state = Vec::new();
+    let mut start: Option<String> = None;
+
+    loop {
+        let keys = client
+            .request(
+                "state_getKeysPaged",
+                json!([to_hex(&prefix, false), KEYS_PAGE, start, at]),
+            )
+            .await?;
+        let keys = keys.as_array().ok_or("Unexpected state_getKeysPaged result")?;
+
+        for key in keys {
+            let key = key.as_str().ok_or("Unexpected storage key")?;
+            let storage_key = hex_bytes(key)?;
+            // Map key is Blake2_128Concat hashed, raw VM key follows the hash.
+            let vm_key = Vec::<u8>::decode(&mut &storage_key[prefix.len() + 16..])
+                .map_err(|e| format!("Invalid VMStorage key {}: {}", key, e))?;
+
+            let involved = addresses.iter().any(|address| {
+                is_module_key(&vm_key, address) || is_resource_key(&vm_key, address)
+            });
+            if involved {
+                if let Some(value) = get_storage::<Vec<u8>>(client, &storage_key, at).await? {
+                    state.push((vm_key, value));
+                }
+            }
+        }
+
+        if keys.len() < KEYS_PAGE {
+            break;
+        }
+        start = keys.last().and_then(Value::as_str).map(str::to_string);
+    }
+
+    Ok(state)
+}
+
+/// Finds outcome of the extrinsic in the block events.
+async fn outcome(client: &RpcClient, at: &str, index: u32) -> Result<ReplayOutcome, String> {
+    let events = get_storage::<Vec<EventRecord<Event, Hash>>>(
+        client,
+        &storage_prefix("System", "Events"),
+        at,
+    )
+    .await?
+    .unwrap_or_default();
+
+    let mvm_index = <Mvm as PalletInfoAccess>::index() as u8;
+    events
+        .into_iter()
+        .filter(|record| record.phase == Phase::ApplyExtrinsic(index))
+        .find_map(|record| match record.event {
+            Event::System(frame_system::Event::ExtrinsicSuccess { .. }) => {
+                Some(ReplayOutcome::Success)
+            }
+            Event::System(frame_system::Event::ExtrinsicFailed { dispatch_error, .. }) => {
+                Some(ReplayOutcome::from_dispatch_error(&dispatch_error, mvm_index))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| "Extrinsic outcome not found in the block events".into())
+}
+
+async fn get_storage<T: Decode>(
+    client: &RpcClient,
+    key: &[u8],
+    at: &str,
+) -> Result<Option<T>, String> {
+    match client
+        .request("state_getStorage", json!([to_hex(key, false), at]))
+        .await?
+    {
+        Value::Null => Ok(None),
+        Value::String(value) => T::decode(&mut &hex_bytes(&value)?[..])
+            .map(Some)
+            .map_err(|e| format!("Failed to decode storage value: {}", e)),
+        result => Err(format!("Unexpected state_getStorage result: {}", result)),
+    }
+}
+
+fn storage_prefix(pallet: &str, item: &str) -> Vec<u8> {
+    [twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+fn hex_bytes(hex: &str) -> Result<Vec<u8>, String> {
+    from_hex(hex).map_err(|e| format!("Invalid hex {}: {}", hex, e))
+}
+
+fn parse_account(address: &str) -> Result<AccountId32, String> {
+    if address.starts_with("0x") {
+        let bytes = hex_bytes(address)?;
+        let bytes = <[u8; 32]>::try_from(bytes.as_slice())
+            .map_err(|_| format!("Invalid address {}", address))?;
+        Ok(AccountId32::from(bytes))
+    } else {
+        AccountId32::from_ss58check(address)
+            .map_err(|e| format!("Invalid address {}: {:?}", address, e))
+    }
+}
@@ -9,34 +9,73 @@ use std::sync::Arc;
 use primitives::{AccountId, Balance, Index};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::offchain::OffchainStorage;
 use sp_block_builder::BlockBuilder;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_mvm_rpc_runtime::MVMApiRuntime;
-use sp_mvm_rpc::{MVMApiRpc, MVMApi};
+use sp_mvm_rpc::{Estimation, MVMApiRpc, MVMApi};
+use sp_mvm_rpc::changes::{MVMChangesApi, MVMChanges};
+use sp_mvm_rpc::limits::RequestLimits;
+use sp_mvm_rpc::metrics::RpcMetrics;
+use sp_mvm_rpc::pool::EstimationPool;
+use sp_mvm_rpc::rate_limit::RateLimiter;
+use sp_mvm_rpc::cache::{EstimationCache, ModuleCache};
+use sp_mvm_rpc::gas_price::GasPriceOracle;
+use sc_rpc::SubscriptionTaskExecutor;
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, S> {
     /// The client instance to use.
     pub client: Arc<C>,
     /// Transaction pool instance.
     pub pool: Arc<P>,
     /// Whether to deny unsafe calls
     pub deny_unsafe: DenyUnsafe,
+    /// Execution deadline (in milliseconds) for Move VM RPC calls.
+    pub mvm_rpc_deadline: Option<u64>,
+    /// Size limits of Move VM RPC inputs.
+    pub mvm_rpc_limits: RequestLimits,
+    /// Whether `mvm_executeWithDebug` is enabled, dev nodes only.
+    pub mvm_debug_rpc: bool,
+    /// Metrics of Move VM RPC calls, available if Prometheus is enabled.
+    pub mvm_rpc_metrics: Option<RpcMetrics>,
+    /// Workers executing Move VM gas estimations, shared by RPC servers.
+    pub mvm_estimation_pool: EstimationPool,
+    /// Budgets of expensive Move VM RPC calls, shared by RPC servers.
+    pub mvm_rate_limiter: RateLimiter,
+    /// ABIs of the published Move modules, shared by RPC servers.
+    pub mvm_module_cache: ModuleCache,
+    /// Move VM gas estimations by state root and request, shared by RPC servers.
+    pub mvm_estimation_cache: EstimationCache<Estimation>,
+    /// Gas unit prices of the last blocks, recorded by the node.
+    pub mvm_gas_price_oracle: GasPriceOracle,
+    /// Amount of the last finalized blocks with the state kept, `None` on archive nodes.
+    pub mvm_state_pruning: Option<u32>,
+    /// Offchain storage with Move write-sets, available if offchain indexing is enabled.
+    pub offchain_storage: Option<S>,
+    /// Executor of the RPC subscriptions.
+    pub subscription_executor: SubscriptionTaskExecutor,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P, B>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
+pub fn create_full<C, P, S, B>(
+    deps: FullDeps<C, P, S>,
+) -> jsonrpc_core::IoHandler<sc_rpc::Metadata>
 where
     B: sp_api::BlockT,
     C: Send + Sync + 'static,
     C: ProvideRuntimeApi<B>,
     C: HeaderBackend<B>,
+    C: sc_client_api::ProofProvider<B>,
+    C: sc_client_api::BlockchainEvents<B>,
+    C: sc_client_api::BlockBackend<B>,
     C::Api: MVMApiRuntime<B, AccountId>,
     C::Api: BlockBuilder<B>,
     C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<B, Balance>,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<B, AccountId, Index>,
     P: TransactionPool + 'static,
+    S: OffchainStorage + 'static,
 {
     use substrate_frame_rpc_system::{FullSystem, SystemApi};
     use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApi};
@@ -46,6 +85,18 @@ where
         client,
         pool,
         deny_unsafe,
+        mvm_rpc_deadline,
+        mvm_rpc_limits,
+        mvm_debug_rpc,
+        mvm_rpc_metrics,
+        mvm_estimation_pool,
+        mvm_rate_limiter,
+        mvm_module_cache,
+        mvm_estimation_cache,
+        mvm_gas_price_oracle,
+        mvm_state_pruning,
+        offchain_storage,
+        subscription_executor,
     } = deps;
 
     io.extend_with(SystemApi::to_delegate(FullSystem::new(
@@ -63,7 +114,25 @@ where
     // to call into the runtime.
     // `io.extend_with(YourRpcTrait::to_delegate(YourRpcStruct::new(ReferenceToClient, ...)));`
 
-    io.extend_with(MVMApiRpc::to_delegate(MVMApi::new(client.clone())));
+    let mvm = MVMApi::new(client.clone(), mvm_rpc_deadline, deny_unsafe)
+        .with_limits(mvm_rpc_limits)
+        .with_estimation_pool(mvm_estimation_pool)
+        .with_rate_limiter(mvm_rate_limiter)
+        .with_module_cache(mvm_module_cache)
+        .with_estimation_cache(mvm_estimation_cache)
+        .with_gas_price_oracle(mvm_gas_price_oracle)
+        .with_state_pruning(mvm_state_pruning)
+        .with_debug(mvm_debug_rpc);
+    let mvm = match offchain_storage {
+        Some(storage) => mvm.with_offchain_storage(storage),
+        None => mvm,
+    };
+    let mvm = match mvm_rpc_metrics {
+        Some(metrics) => mvm.with_metrics(metrics),
+        None => mvm,
+    };
+    io.extend_with(MVMApiRpc::to_delegate(mvm));
+    io.extend_with(MVMChangesApi::to_delegate(MVMChanges::new(client, subscription_executor)));
 
     io
 }
@@ -0,0 +1,51 @@
+//! Minimal HTTP JSON-RPC client used by `pontem mvm` utilities.
+
+use serde_json::{json, Value};
+
+/// JSON-RPC client of the node.
+pub struct RpcClient {
+    uri: hyper::Uri,
+    client: hyper::Client<hyper_rustls::HttpsConnector<hyper::client::HttpConnector>>,
+}
+
+impl RpcClient {
+    pub fn new(url: &str) -> Result<Self, String> {
+        let uri = url
+            .parse()
+            .map_err(|e: hyper::http::uri::InvalidUri| e.to_string())?;
+        let connector = hyper_rustls::HttpsConnector::with_native_roots();
+        Ok(Self {
+            uri,
+            client: hyper::Client::builder().build(connector),
+        })
+    }
+
+    /// Calls `method` and returns its result.
+    pub async fn request(&self, method: &str, params: Value) -> Result<Value, String> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let request = hyper::Request::post(self.uri.clone())
+            .header("content-type", "application/json")
+            .body(body.to_string().into())
+            .map_err(|e| e.to_string())?;
+        let response = self
+            .client
+            .request(request)
+            .await
+            .map_err(|e| e.to_string())?;
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut response: Value = serde_json::from_slice(&body).map_err(|e| e.to_string())?;
+
+        if let Some(error) = response.get("error") {
+            return Err(format!("{} failed: {}", method, error));
+        }
+        Ok(response["result"].take())
+    }
+}
@@ -6,6 +6,10 @@ use cumulus_client_cli::CollatorOptions;
 use cumulus_relay_chain_inprocess_interface::build_inprocess_relay_chain;
 use cumulus_relay_chain_interface::{RelayChainError, RelayChainInterface};
 use crate::cli::Sealing;
+use crate::events_archive::{EventsArchiveConfig, run_events_archive};
+use crate::gas_prices::run_gas_price_oracle;
+use crate::move_metrics::{MoveMetrics, run_move_metrics};
+use crate::transaction_index::run_transaction_index;
 use cumulus_primitives_parachain_inherent::{MockValidationDataInherentDataProvider, MockXcmConfig};
 use futures::StreamExt;
 use sp_core::H256;
@@ -18,8 +22,18 @@ use sc_consensus_manual_seal::{run_manual_seal, EngineCommand, ManualSealParams}
 use cumulus_primitives_core::ParaId;
 use pontem_runtime::RuntimeApi;
 use sp_blockchain::HeaderBackend;
-use sc_service::{Configuration, PartialComponents, Role, TFullBackend, TFullClient, TaskManager};
+use sc_client_api::Backend as _;
+use sc_service::{
+    Configuration, PartialComponents, PruningMode, Role, TFullBackend, TFullClient, TaskManager,
+};
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker, TelemetryWorkerHandle};
+use std::net::SocketAddr;
+use sp_mvm_rpc::limits::RequestLimits;
+use sp_mvm_rpc::metrics::RpcMetrics;
+use sp_mvm_rpc::pool::EstimationPool;
+use sp_mvm_rpc::rate_limit::RateLimiter;
+use sp_mvm_rpc::cache::{EstimationCache, ModuleCache};
+use sp_mvm_rpc::gas_price::GasPriceOracle;
 use std::sync::Arc;
 use substrate_prometheus_endpoint::Registry;
 use sp_keystore::SyncCryptoStorePtr;
@@ -166,12 +180,20 @@ async fn start_node_impl(
     parachain_config: Configuration,
     polkadot_config: Configuration,
     id: ParaId,
+    events_archive: Option<EventsArchiveConfig>,
+    mvm_rpc_deadline: Option<u64>,
+    mvm_rpc_limits: RequestLimits,
+    mvm_rest_addr: Option<SocketAddr>,
+    mvm_graphql_addr: Option<SocketAddr>,
 ) -> sc_service::error::Result<(TaskManager, Arc<FullClient>)> {
     if matches!(parachain_config.role, Role::Light) {
         return Err("Light client not supported!".into());
     }
 
-    let parachain_config = prepare_node_config(parachain_config);
+    let mut parachain_config = prepare_node_config(parachain_config);
+    if events_archive.is_some() {
+        parachain_config.offchain_worker.indexing_enabled = true;
+    }
 
     let params = new_partial(&parachain_config, false)?;
     let (mut telemetry, telemetry_worker_handle) = params.other;
@@ -191,6 +213,35 @@ async fn start_node_impl(
     let client = params.client.clone();
     let backend = params.backend.clone();
 
+    if let Some(events_archive) = events_archive {
+        task_manager.spawn_handle().spawn(
+            "mvm-events-archive",
+            None,
+            run_events_archive(events_archive, client.clone(), backend.clone()),
+        );
+    }
+
+    if let Some(addr) = mvm_rest_addr {
+        let gateway = sp_mvm_rest::Gateway::<_, FullBackend, Block, primitives::AccountId>::new(
+            client.clone(),
+            mvm_rpc_deadline,
+        );
+        task_manager.spawn_handle().spawn("mvm-rest", None, sp_mvm_rest::serve(addr, gateway));
+    }
+
+    if let Some(addr) = mvm_graphql_addr {
+        use sp_mvm_rpc::graphql::{schema, serve, ClientMoveState};
+
+        let state =
+            ClientMoveState::<_, FullBackend, Block, primitives::AccountId>::new(client.clone());
+        let state = match backend.offchain_storage() {
+            Some(storage) => state.with_offchain_storage(storage),
+            None => state,
+        };
+        let schema = schema(Arc::new(state));
+        task_manager.spawn_handle().spawn("mvm-graphql", None, serve(addr, schema));
+    }
+
     let block_announce_validator = BlockAnnounceValidator::new(relay_chain_full_node.clone(), id);
 
     let is_validator = parachain_config.role.is_authority();
@@ -217,15 +268,71 @@ async fn start_node_impl(
             })),
         })?;
 
+    if parachain_config.offchain_worker.enabled {
+        sc_service::build_offchain_workers(
+            &parachain_config,
+            task_manager.spawn_handle(),
+            client.clone(),
+            network.clone(),
+        );
+    }
+
+    let mvm_rpc_metrics = match prometheus_registry.as_ref() {
+        Some(registry) => {
+            let metrics = MoveMetrics::register(registry)?;
+            task_manager.spawn_handle().spawn(
+                "mvm-metrics",
+                None,
+                run_move_metrics::<FullBackend, _>(metrics, client.clone()),
+            );
+            Some(RpcMetrics::register(registry)?)
+        }
+        None => None,
+    };
+
+    let mvm_estimation_pool = EstimationPool::new(
+        mvm_rpc_limits.max_estimations,
+        Duration::from_millis(mvm_rpc_limits.estimation_timeout_ms),
+    )?;
+    let mvm_rate_limiter =
+        RateLimiter::new(mvm_rpc_limits.rate_limit, mvm_rpc_limits.connection_rate_limit);
+    let mvm_module_cache = ModuleCache::default();
+    let mvm_estimation_cache = EstimationCache::default();
+    let mvm_gas_price_oracle = GasPriceOracle::default();
+    task_manager.spawn_handle().spawn(
+        "mvm-gas-price-oracle",
+        None,
+        run_gas_price_oracle(mvm_gas_price_oracle.clone(), client.clone()),
+    );
+    task_manager.spawn_handle().spawn(
+        "mvm-transaction-index",
+        None,
+        run_transaction_index(client.clone(), backend.clone()),
+    );
+    let mvm_state_pruning = state_pruning(&parachain_config);
+
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
+        let offchain_storage = backend.offchain_storage();
 
-        Box::new(move |deny_unsafe, _| {
+        Box::new(move |deny_unsafe, subscription_executor| {
             let deps = crate::rpc::FullDeps {
                 client: client.clone(),
                 pool: pool.clone(),
                 deny_unsafe,
+                mvm_rpc_deadline,
+                mvm_rpc_limits,
+                mvm_debug_rpc: false,
+                mvm_rpc_metrics: mvm_rpc_metrics.clone(),
+                mvm_estimation_pool: mvm_estimation_pool.clone(),
+                mvm_rate_limiter: mvm_rate_limiter.clone(),
+                mvm_module_cache: mvm_module_cache.clone(),
+                mvm_estimation_cache: mvm_estimation_cache.clone(),
+                mvm_gas_price_oracle: mvm_gas_price_oracle.clone(),
+                mvm_state_pruning,
+                offchain_storage: offchain_storage.clone(),
+                subscription_executor,
             };
 
             let io = crate::rpc::create_full(deps);
@@ -365,16 +472,49 @@ pub async fn start_node(
     parachain_config: Configuration,
     polkadot_config: Configuration,
     id: ParaId,
+    events_archive: Option<EventsArchiveConfig>,
+    mvm_rpc_deadline: Option<u64>,
+    mvm_rpc_limits: RequestLimits,
+    mvm_rest_addr: Option<SocketAddr>,
+    mvm_graphql_addr: Option<SocketAddr>,
 ) -> sc_service::error::Result<(TaskManager, Arc<FullClient>)> {
-    start_node_impl(parachain_config, polkadot_config, id).await
+    start_node_impl(
+        parachain_config,
+        polkadot_config,
+        id,
+        events_archive,
+        mvm_rpc_deadline,
+        mvm_rpc_limits,
+        mvm_rest_addr,
+        mvm_graphql_addr,
+    )
+    .await
+}
+
+/// Amount of the last finalized blocks with the state kept by the node, `None` on archive nodes.
+fn state_pruning(config: &Configuration) -> Option<u32> {
+    match &config.state_pruning {
+        PruningMode::Constrained(constraints) => Some(constraints.max_blocks.unwrap_or(0)),
+        PruningMode::ArchiveAll | PruningMode::ArchiveCanonical => None,
+    }
 }
 
 pub fn new_dev(
-    config: Configuration,
+    mut config: Configuration,
     author_id: nimbus_primitives::NimbusId,
     sealing: Sealing,
+    events_archive: Option<EventsArchiveConfig>,
+    mvm_rpc_deadline: Option<u64>,
+    mvm_rpc_limits: RequestLimits,
+    mvm_debug_rpc: bool,
+    mvm_rest_addr: Option<SocketAddr>,
+    mvm_graphql_addr: Option<SocketAddr>,
 ) -> Result<TaskManager, sc_service::Error> {
     use futures::Stream;
+
+    if events_archive.is_some() {
+        config.offchain_worker.indexing_enabled = true;
+    }
     let sc_service::PartialComponents {
         client,
         mut task_manager,
@@ -407,6 +547,35 @@ pub fn new_dev(
         );
     }
 
+    if let Some(events_archive) = events_archive {
+        task_manager.spawn_handle().spawn(
+            "mvm-events-archive",
+            None,
+            run_events_archive(events_archive, client.clone(), backend.clone()),
+        );
+    }
+
+    if let Some(addr) = mvm_rest_addr {
+        let gateway = sp_mvm_rest::Gateway::<_, FullBackend, Block, primitives::AccountId>::new(
+            client.clone(),
+            mvm_rpc_deadline,
+        );
+        task_manager.spawn_handle().spawn("mvm-rest", None, sp_mvm_rest::serve(addr, gateway));
+    }
+
+    if let Some(addr) = mvm_graphql_addr {
+        use sp_mvm_rpc::graphql::{schema, serve, ClientMoveState};
+
+        let state =
+            ClientMoveState::<_, FullBackend, Block, primitives::AccountId>::new(client.clone());
+        let state = match backend.offchain_storage() {
+            Some(storage) => state.with_offchain_storage(storage),
+            None => state,
+        };
+        let schema = schema(Arc::new(state));
+        task_manager.spawn_handle().spawn("mvm-graphql", None, serve(addr, schema));
+    }
+
     let prometheus_registry = config.prometheus_registry().cloned();
     let collator = config.role.is_authority();
 
@@ -508,15 +677,62 @@ pub fn new_dev(
         );
     }
 
+    let mvm_rpc_metrics = match prometheus_registry.as_ref() {
+        Some(registry) => {
+            let metrics = MoveMetrics::register(registry)?;
+            task_manager.spawn_handle().spawn(
+                "mvm-metrics",
+                None,
+                run_move_metrics::<FullBackend, _>(metrics, client.clone()),
+            );
+            Some(RpcMetrics::register(registry)?)
+        }
+        None => None,
+    };
+
+    let mvm_estimation_pool = EstimationPool::new(
+        mvm_rpc_limits.max_estimations,
+        Duration::from_millis(mvm_rpc_limits.estimation_timeout_ms),
+    )?;
+    let mvm_rate_limiter =
+        RateLimiter::new(mvm_rpc_limits.rate_limit, mvm_rpc_limits.connection_rate_limit);
+    let mvm_module_cache = ModuleCache::default();
+    let mvm_estimation_cache = EstimationCache::default();
+    let mvm_gas_price_oracle = GasPriceOracle::default();
+    task_manager.spawn_handle().spawn(
+        "mvm-gas-price-oracle",
+        None,
+        run_gas_price_oracle(mvm_gas_price_oracle.clone(), client.clone()),
+    );
+    task_manager.spawn_handle().spawn(
+        "mvm-transaction-index",
+        None,
+        run_transaction_index(client.clone(), backend.clone()),
+    );
+    let mvm_state_pruning = state_pruning(&config);
+
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
+        let offchain_storage = backend.offchain_storage();
 
-        Box::new(move |deny_unsafe, _| {
+        Box::new(move |deny_unsafe, subscription_executor| {
             let deps = crate::rpc::FullDeps {
                 client: client.clone(),
                 pool: pool.clone(),
                 deny_unsafe,
+                mvm_rpc_deadline,
+                mvm_rpc_limits,
+                mvm_debug_rpc,
+                mvm_rpc_metrics: mvm_rpc_metrics.clone(),
+                mvm_estimation_pool: mvm_estimation_pool.clone(),
+                mvm_rate_limiter: mvm_rate_limiter.clone(),
+                mvm_module_cache: mvm_module_cache.clone(),
+                mvm_estimation_cache: mvm_estimation_cache.clone(),
+                mvm_gas_price_oracle: mvm_gas_price_oracle.clone(),
+                mvm_state_pruning,
+                offchain_storage: offchain_storage.clone(),
+                subscription_executor,
             };
 
             let io = crate::rpc::create_full(deps);
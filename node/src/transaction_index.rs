@@ -0,0 +1,72 @@
+//! Worker indexing Move extrinsics by hash for `mvm_getTransactionByHash`.
+//!
+//! Every new best block is read once: locations (block and index) of its `Mvm` extrinsics are
+//! written into the offchain storage by the extrinsic hash, as the transaction pool hashes
+//! them. Extrinsics included again by the new best block of a reorg are overwritten, stale
+//! locations are ignored by the RPC.
+
+use std::sync::Arc;
+use codec::Encode;
+use futures::StreamExt;
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents};
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{BlakeTwo256, Hash, Header as HeaderT};
+
+use pontem_runtime::{Block, Call, Runtime};
+use sp_mvm::gas_price::gas_limit_of;
+use sp_mvm_rpc::transaction::{transaction_key, TransactionLocation};
+
+/// Runs the index worker: records Move extrinsics of every new best block.
+pub async fn run_transaction_index<BE, C>(client: Arc<C>, backend: Arc<BE>)
+where
+    BE: Backend<Block>,
+    C: BlockchainEvents<Block> + BlockBackend<Block>,
+{
+    let mut storage = match backend.offchain_storage() {
+        Some(storage) => storage,
+        None => {
+            log::warn!("Offchain storage is not available, Move transactions index disabled");
+            return;
+        }
+    };
+
+    let mut imports = client.import_notification_stream();
+    while let Some(notification) = imports.next().await {
+        if !notification.is_new_best {
+            continue;
+        }
+        let extrinsics = match client.block_body(&BlockId::Hash(notification.hash)) {
+            Ok(body) => body.unwrap_or_default(),
+            Err(err) => {
+                log::warn!(
+                    "Failed to index Move transactions of block {:?}: {}",
+                    notification.hash,
+                    err
+                );
+                continue;
+            }
+        };
+
+        let block: u64 = (*notification.header.number()).into();
+        for (index, xt) in extrinsics.iter().enumerate() {
+            let is_move = match &xt.function {
+                Call::Mvm(call) => gas_limit_of::<Runtime>(call).is_some(),
+                _ => false,
+            };
+            if !is_move {
+                continue;
+            }
+            let location = TransactionLocation {
+                block,
+                block_hash: notification.hash.as_ref().to_vec(),
+                index: index as u32,
+            };
+            storage.set(
+                STORAGE_PREFIX,
+                &transaction_key(BlakeTwo256::hash_of(xt).as_ref()),
+                &location.encode(),
+            );
+        }
+    }
+}
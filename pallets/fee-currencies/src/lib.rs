@@ -0,0 +1,250 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! This pallet allows to pay transaction fees in registered non-native currencies.
+//!
+//! Governance registers a currency with its fee rate: amount of the currency (in the smallest
+//! units) charged for a single unit of the native currency fee. `FeeAdapter` is used as
+//! `OnChargeTransaction` of the transaction payment pallet: fees are charged in the native
+//! currency, and if the account can't pay them, in a registered currency the account holds
+//! enough of. Non-native fees are accepted only for calls allowed by `TokenFeeCalls` (e.g. Move
+//! VM calls) and are transferred to `FeeReceiver` as a whole, including tips.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::{
+    pallet_prelude::*,
+    traits::{Contains, Currency, Imbalance, OnUnbalanced},
+};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use pallet_transaction_payment::{CurrencyAdapter, OnChargeTransaction};
+use primitives::currency::CurrencyId;
+use sp_runtime::{
+    traits::{DispatchInfoOf, PostDispatchInfoOf, Saturating, Zero},
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+    FixedPointNumber, FixedPointOperand, FixedU128,
+};
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<
+    <T as frame_system::Config>::AccountId,
+>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Non-native currencies fees are charged in.
+        type MultiCurrency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId>;
+
+        /// Calls which fees could be paid in non-native currencies.
+        type TokenFeeCalls: Contains<<Self as frame_system::Config>::Call>;
+
+        /// Account receiving fees paid in non-native currencies, e.g. treasury.
+        type FeeReceiver: Get<Self::AccountId>;
+
+        /// The origin which may register fee currencies.
+        type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Weight information for the extrinsics in this module.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Native currency can't be registered as a fee currency.
+        NativeFeeCurrency,
+        /// Fee rate should be greater than zero.
+        ZeroFeeRate,
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Fee rate of the currency set, `None` if removed. \[currency_id, rate\]
+        FeeRateSet(CurrencyId, Option<FixedU128>),
+        /// Transaction fee paid in non-native currency. \[who, currency_id, amount\]
+        FeePaid(T::AccountId, CurrencyId, BalanceOf<T>),
+    }
+
+    /// Fee rates of the registered currencies.
+    ///
+    /// map CurrencyId => amount of the currency charged per unit of the native fee
+    #[pallet::storage]
+    #[pallet::getter(fn fee_rate)]
+    pub type FeeRates<T: Config> =
+        StorageMap<_, Twox64Concat, CurrencyId, FixedU128, OptionQuery>;
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Register currency with the fee `rate`, or remove it if `rate` is `None`.
+        #[pallet::weight(T::WeightInfo::set_fee_rate())]
+        pub fn set_fee_rate(
+            origin: OriginFor<T>,
+            currency_id: CurrencyId,
+            rate: Option<FixedU128>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            ensure!(
+                currency_id != CurrencyId::NATIVE,
+                Error::<T>::NativeFeeCurrency
+            );
+            ensure!(
+                rate.map(|rate| !rate.is_zero()).unwrap_or(true),
+                Error::<T>::ZeroFeeRate
+            );
+
+            FeeRates::<T>::set(currency_id, rate);
+            Self::deposit_event(Event::FeeRateSet(currency_id, rate));
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Amount of the currency charged for the native `fee`, `None` if not registered.
+        pub fn convert_fee(currency_id: CurrencyId, fee: BalanceOf<T>) -> Option<BalanceOf<T>>
+        where
+            BalanceOf<T>: FixedPointOperand,
+        {
+            FeeRates::<T>::get(currency_id).map(|rate| rate.saturating_mul_int(fee))
+        }
+    }
+}
+
+/// Fee withdrawn from the account.
+pub enum FeeLiquidity<NegativeImbalance, Balance> {
+    /// Native fee, handled as by `CurrencyAdapter`.
+    Native(Option<NegativeImbalance>),
+    /// Fee paid in non-native currency with the rate.
+    Token {
+        currency_id: CurrencyId,
+        rate: FixedU128,
+        paid: Balance,
+    },
+}
+
+impl<NegativeImbalance, Balance> Default for FeeLiquidity<NegativeImbalance, Balance> {
+    fn default() -> Self {
+        FeeLiquidity::Native(None)
+    }
+}
+
+type NegativeImbalanceOf<C, T> =
+    <C as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+/// `OnChargeTransaction` charging fees in the native currency `C` (distributed with `OU`),
+/// or in registered currencies if native balance isn't enough.
+pub struct FeeAdapter<C, OU>(PhantomData<(C, OU)>);
+
+impl<T, C, OU> OnChargeTransaction<T> for FeeAdapter<C, OU>
+where
+    T: pallet_transaction_payment::Config + Config,
+    T::TransactionByteFee: Get<BalanceOf<T>>,
+    C: Currency<T::AccountId, Balance = BalanceOf<T>>,
+    C::PositiveImbalance: Imbalance<BalanceOf<T>, Opposite = C::NegativeImbalance>,
+    C::NegativeImbalance: Imbalance<BalanceOf<T>, Opposite = C::PositiveImbalance>,
+    OU: OnUnbalanced<NegativeImbalanceOf<C, T>>,
+    BalanceOf<T>: FixedPointOperand,
+{
+    type Balance = BalanceOf<T>;
+    type LiquidityInfo = FeeLiquidity<NegativeImbalanceOf<C, T>, BalanceOf<T>>;
+
+    fn withdraw_fee(
+        who: &T::AccountId,
+        call: &T::Call,
+        dispatch_info: &DispatchInfoOf<T::Call>,
+        fee: Self::Balance,
+        tip: Self::Balance,
+    ) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+        let native = <CurrencyAdapter<C, OU> as OnChargeTransaction<T>>::withdraw_fee(
+            who,
+            call,
+            dispatch_info,
+            fee,
+            tip,
+        );
+        let err = match native {
+            Ok(imbalance) => return Ok(FeeLiquidity::Native(imbalance)),
+            Err(err) => err,
+        };
+        if !T::TokenFeeCalls::contains(call) {
+            return Err(err);
+        }
+
+        for (currency_id, rate) in FeeRates::<T>::iter() {
+            let amount = rate.saturating_mul_int(fee);
+            if T::MultiCurrency::withdraw(currency_id, who, amount).is_ok() {
+                return Ok(FeeLiquidity::Token {
+                    currency_id,
+                    rate,
+                    paid: amount,
+                });
+            }
+        }
+        Err(InvalidTransaction::Payment.into())
+    }
+
+    fn correct_and_deposit_fee(
+        who: &T::AccountId,
+        dispatch_info: &DispatchInfoOf<T::Call>,
+        post_info: &PostDispatchInfoOf<T::Call>,
+        corrected_fee: Self::Balance,
+        tip: Self::Balance,
+        already_withdrawn: Self::LiquidityInfo,
+    ) -> Result<(), TransactionValidityError> {
+        match already_withdrawn {
+            FeeLiquidity::Native(imbalance) => {
+                <CurrencyAdapter<C, OU> as OnChargeTransaction<T>>::correct_and_deposit_fee(
+                    who,
+                    dispatch_info,
+                    post_info,
+                    corrected_fee,
+                    tip,
+                    imbalance,
+                )
+            }
+            FeeLiquidity::Token {
+                currency_id,
+                rate,
+                paid,
+            } => {
+                let fee = rate.saturating_mul_int(corrected_fee).min(paid);
+                let refund = paid.saturating_sub(fee);
+                if !refund.is_zero() {
+                    T::MultiCurrency::deposit(currency_id, who, refund)
+                        .map_err(|_| InvalidTransaction::Payment)?;
+                }
+                if !fee.is_zero() {
+                    T::MultiCurrency::deposit(currency_id, &T::FeeReceiver::get(), fee)
+                        .map_err(|_| InvalidTransaction::Payment)?;
+                }
+
+                Pallet::<T>::deposit_event(Event::FeePaid(who.clone(), currency_id, fee));
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use super::*;
+use frame_support::{
+    assert_noop, assert_ok,
+    weights::{DispatchInfo, PostDispatchInfo},
+};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+type Adapter = FeeAdapter<Balances, ()>;
+type Liquidity = <Adapter as OnChargeTransaction<Runtime>>::LiquidityInfo;
+
+const REMARK: &Call = &Call::System(frame_system::Call::remark { remark: Vec::new() });
+const TRANSFER: &Call = &Call::Balances(pallet_balances::Call::transfer {
+    dest: ALICE,
+    value: 10,
+});
+
+fn withdraw(
+    who: AccountId,
+    call: &Call,
+    fee: Balance,
+) -> Result<Liquidity, TransactionValidityError> {
+    <Adapter as OnChargeTransaction<Runtime>>::withdraw_fee(
+        &who,
+        call,
+        &DispatchInfo::default(),
+        fee,
+        0,
+    )
+}
+
+fn correct(who: AccountId, corrected_fee: Balance, withdrawn: Liquidity) {
+    assert_ok!(<Adapter as OnChargeTransaction<Runtime>>::correct_and_deposit_fee(
+        &who,
+        &DispatchInfo::default(),
+        &PostDispatchInfo::default(),
+        corrected_fee,
+        0,
+        withdrawn,
+    ));
+}
+
+#[test]
+fn set_fee_rate() {
+    ExtBuilder::default().build().execute_with(|| {
+        let rate = FixedU128::saturating_from_integer(2);
+        assert_noop!(
+            FeeCurrencies::set_fee_rate(Origin::signed(BOB), KSM, Some(rate)),
+            BadOrigin
+        );
+        assert_noop!(
+            FeeCurrencies::set_fee_rate(Origin::signed(ALICE), CurrencyId::NATIVE, Some(rate)),
+            Error::<Runtime>::NativeFeeCurrency
+        );
+        assert_noop!(
+            FeeCurrencies::set_fee_rate(Origin::signed(ALICE), KSM, Some(FixedU128::zero())),
+            Error::<Runtime>::ZeroFeeRate
+        );
+
+        assert_ok!(FeeCurrencies::set_fee_rate(Origin::signed(ALICE), KSM, Some(rate)));
+        System::assert_last_event(Event::FeeCurrencies(crate::Event::FeeRateSet(
+            KSM,
+            Some(rate),
+        )));
+        assert_eq!(FeeCurrencies::fee_rate(KSM), Some(rate));
+        assert_eq!(FeeCurrencies::convert_fee(KSM, 100), Some(200));
+
+        assert_ok!(FeeCurrencies::set_fee_rate(Origin::signed(ALICE), KSM, None));
+        assert_eq!(FeeCurrencies::fee_rate(KSM), None);
+        assert_eq!(FeeCurrencies::convert_fee(KSM, 100), None);
+    });
+}
+
+#[test]
+fn native_fee_preferred() {
+    ExtBuilder::default()
+        .balances(vec![(BOB, 1_000)])
+        .tokens(vec![(BOB, KSM, 1_000)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(FeeCurrencies::set_fee_rate(
+                Origin::signed(ALICE),
+                KSM,
+                Some(FixedU128::saturating_from_integer(2))
+            ));
+
+            let withdrawn = withdraw(BOB, REMARK, 100).unwrap();
+            assert!(matches!(withdrawn, FeeLiquidity::Native(Some(_))));
+            correct(BOB, 60, withdrawn);
+
+            assert_eq!(Balances::free_balance(&BOB), 940);
+            assert_eq!(Tokens::free_balance(KSM, &BOB), 1_000);
+        });
+}
+
+#[test]
+fn token_fee_without_native_balance() {
+    ExtBuilder::default()
+        .tokens(vec![(BOB, KSM, 1_000)])
+        .build()
+        .execute_with(|| {
+            // Currency isn't registered yet.
+            assert!(withdraw(BOB, REMARK, 100).is_err());
+
+            assert_ok!(FeeCurrencies::set_fee_rate(
+                Origin::signed(ALICE),
+                KSM,
+                Some(FixedU128::saturating_from_integer(2))
+            ));
+
+            let withdrawn = withdraw(BOB, REMARK, 100).unwrap();
+            assert!(matches!(
+                withdrawn,
+                FeeLiquidity::Token {
+                    currency_id: KSM,
+                    paid: 200,
+                    ..
+                }
+            ));
+            assert_eq!(Tokens::free_balance(KSM, &BOB), 800);
+
+            // Unused fee is refunded.
+            correct(BOB, 60, withdrawn);
+            assert_eq!(Tokens::free_balance(KSM, &BOB), 880);
+            assert_eq!(Tokens::free_balance(KSM, &TREASURY), 120);
+            System::assert_last_event(Event::FeeCurrencies(crate::Event::FeePaid(
+                BOB, KSM, 120,
+            )));
+
+            // Not enough tokens.
+            assert!(withdraw(BOB, REMARK, 1_000).is_err());
+            assert_eq!(Tokens::free_balance(KSM, &BOB), 880);
+        });
+}
+
+#[test]
+fn token_fee_only_for_allowed_calls() {
+    ExtBuilder::default()
+        .tokens(vec![(BOB, KSM, 1_000)])
+        .build()
+        .execute_with(|| {
+            assert_ok!(FeeCurrencies::set_fee_rate(
+                Origin::signed(ALICE),
+                KSM,
+                Some(FixedU128::saturating_from_integer(2))
+            ));
+
+            assert!(withdraw(BOB, TRANSFER, 100).is_err());
+            assert_eq!(Tokens::free_balance(KSM, &BOB), 1_000);
+        });
+}
@@ -0,0 +1,31 @@
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// temporary placeholder for auto generated weights
+
+/// Weight functions needed for fee currencies pallet.
+pub trait WeightInfo {
+    fn set_fee_rate() -> Weight;
+}
+
+/// Just like SubstrateWeights, but measured in Pontem.
+pub struct PontemWeights<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for PontemWeights<T> {
+    fn set_fee_rate() -> Weight {
+        (20_000_000 as Weight) // TODO: Needs benches
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn set_fee_rate() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
@@ -0,0 +1,7 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use sp_mvm_rpc::decode::decode_transaction;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_transaction(data, |id| Err(anyhow::anyhow!("Module {} is missing", id)));
+});
@@ -0,0 +1,285 @@
+//! REST gateway serving a subset of Aptos REST API on top of the Move runtime API,
+//! so Aptos SDKs and tools (explorer, TypeScript SDK) could work with Pontem nodes:
+//!
+//! `GET /accounts/{address}/resources` - resources of the account with decoded fields.
+//!
+//! `GET /accounts/{address}/modules` - modules published by the account with ABI.
+//!
+//! `POST /transactions/simulate` - dry run of the transaction (see `SimulateTransactionRequest`).
+//!
+//! State is read at the best block, or at the block number passed as `ledger_version` query
+//! parameter. `VMStorage` trie keys are hashed, so listing the account iterates all Move state
+//! entries at the block: the gateway is meant for development and indexing nodes.
+use std::convert::Infallible;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use codec::Codec;
+use hyper::body::HttpBody;
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde::Serialize;
+use sc_client_api::{Backend, StorageProvider};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, SaturatedConversion},
+};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use sp_mvm::addr::address_to_account;
+use sp_mvm_rpc::abi::{type_tag_string, MoveModuleBytecode};
+use sp_mvm_rpc::changes::AccountKey;
+use sp_mvm_rpc::decode::decode_transaction;
+use sp_mvm_rpc::resource::decode_resource;
+use sp_mvm_rpc::state::account_entries;
+use sp_mvm_rpc::version::estimate_gas_execute;
+use sp_mvm_rpc_runtime::MVMApiRuntime;
+
+pub mod routes;
+pub mod types;
+use routes::{ledger_version, parse_address, Route};
+use types::{ApiError, MoveResource, SimulateTransactionRequest, SimulatedTransaction, U64};
+
+/// Maximum size of the request body.
+pub const MAX_REQUEST_SIZE: u64 = 1024 * 1024;
+
+/// Aptos REST API gateway over the client.
+pub struct Gateway<C, B, Block, AccountId> {
+    client: Arc<C>,
+    deadline: Option<u64>,
+    _marker: PhantomData<fn() -> (B, Block, AccountId)>,
+}
+
+impl<C, B, Block, AccountId> Gateway<C, B, Block, AccountId>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    B: Backend<Block>,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, B>,
+    C::Api: MVMApiRuntime<Block, AccountId>,
+{
+    /// Creates gateway, `deadline` (in milliseconds) limits simulations as `--mvm-rpc-deadline`.
+    pub fn new(client: Arc<C>, deadline: Option<u64>) -> Self {
+        Self {
+            client,
+            deadline,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Handles the request with the body, returns JSON response.
+    pub fn handle(
+        &self,
+        method: &Method,
+        path: &str,
+        query: Option<&str>,
+        body: &[u8],
+    ) -> Result<Vec<u8>, ApiError> {
+        let route = Route::parse(method, path)?;
+        let at = self.block(ledger_version(query)?)?;
+
+        match route {
+            Route::AccountResources(address) => to_json(&self.resources(&at, &address)?),
+            Route::AccountModules(address) => to_json(&self.modules(&at, &address)?),
+            Route::SimulateTransaction => {
+                let request = serde_json::from_slice(body)
+                    .map_err(|e| ApiError::bad_request(format!("Invalid request: {}", e)))?;
+                to_json(&vec![self.simulate(&at, request)?])
+            }
+        }
+    }
+
+    fn block(&self, ledger_version: Option<u64>) -> Result<BlockId<Block>, ApiError> {
+        let hash = match ledger_version {
+            Some(number) => self
+                .client
+                .hash(number.saturated_into())
+                .map_err(|e| ApiError::internal(e.to_string()))?
+                .ok_or_else(|| {
+                    ApiError::not_found(format!("Ledger version {} not found", number))
+                })?,
+            None => self.client.info().best_hash,
+        };
+        Ok(BlockId::hash(hash))
+    }
+
+    /// Move state entries of the address with their values.
+    fn account_entries(
+        &self,
+        at: &BlockId<Block>,
+        address: &AccountAddress,
+    ) -> Result<Vec<(AccountKey, Vec<u8>)>, ApiError> {
+        let prefix = self
+            .client
+            .runtime_api()
+            .get_vm_storage_prefix(at)
+            .map_err(|e| ApiError::internal(format!("{:?}", e)))?;
+        account_entries(&*self.client, at, &prefix, address)
+            .map_err(|e| ApiError::internal(e.to_string()))
+    }
+
+    fn resources(
+        &self,
+        at: &BlockId<Block>,
+        address: &AccountAddress,
+    ) -> Result<Vec<MoveResource>, ApiError> {
+        let mut resources = vec![];
+        for (key, value) in self.account_entries(at, address)? {
+            if let AccountKey::Resource(tag) = key {
+                let data = decode_resource(&tag, &value, |id| self.module(at, id))
+                    .map_err(|e| ApiError::internal(e.to_string()))?;
+                resources.push(MoveResource {
+                    typ: type_tag_string(&TypeTag::Struct(tag)),
+                    data,
+                });
+            }
+        }
+        Ok(resources)
+    }
+
+    fn modules(
+        &self,
+        at: &BlockId<Block>,
+        address: &AccountAddress,
+    ) -> Result<Vec<MoveModuleBytecode>, ApiError> {
+        self.account_entries(at, address)?
+            .into_iter()
+            .filter_map(|(key, value)| match key {
+                AccountKey::Module(_) => Some(value),
+                AccountKey::Resource(_) => None,
+            })
+            .map(|bytecode| {
+                MoveModuleBytecode::new(bytecode)
+                    .try_parse_abi()
+                    .map_err(|e| ApiError::internal(e.to_string()))
+            })
+            .collect()
+    }
+
+    fn simulate(
+        &self,
+        at: &BlockId<Block>,
+        request: SimulateTransactionRequest,
+    ) -> Result<SimulatedTransaction, ApiError> {
+        let account = address_to_account::<AccountId>(&parse_address(&request.sender)?)
+            .map_err(|_| ApiError::bad_request(format!("Invalid sender {}", request.sender)))?;
+        let payload = request.payload.into_vec();
+
+        let estimation = estimate_gas_execute(
+            &*self.client.runtime_api(),
+            at,
+            account,
+            payload.clone(),
+            request.max_gas_amount.0,
+            self.deadline,
+        )
+        .map_err(|e| ApiError::internal(format!("{:?}", e)))?
+        .map_err(|e| ApiError::bad_request(format!("{:?}", e)))?;
+
+        Ok(SimulatedTransaction {
+            sender: request.sender,
+            max_gas_amount: request.max_gas_amount,
+            gas_unit_price: request.gas_unit_price,
+            gas_used: U64(estimation.gas_used),
+            success: types::is_executed(estimation.status_code),
+            vm_status: types::vm_status(estimation.status_code),
+            payload: decode_transaction(&payload, |id| self.module(at, id))
+                .map(|tx| tx.payload)
+                .ok(),
+        })
+    }
+
+    fn module(&self, at: &BlockId<Block>, id: &ModuleId) -> anyhow::Result<Vec<u8>> {
+        self.client
+            .runtime_api()
+            .get_module(at, bcs::to_bytes(id)?)
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?
+            .map_err(|e| anyhow::anyhow!("{}", String::from_utf8_lossy(&e)))?
+            .ok_or_else(|| anyhow::anyhow!("Module {} not found", id))
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, ApiError> {
+    serde_json::to_vec(value).map_err(|e| ApiError::internal(e.to_string()))
+}
+
+fn json_response(status: StatusCode, body: Vec<u8>) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+async fn respond<C, B, Block, AccountId>(
+    gateway: Arc<Gateway<C, B, Block, AccountId>>,
+    request: Request<Body>,
+) -> Response<Body>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    B: Backend<Block>,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, B>,
+    C::Api: MVMApiRuntime<Block, AccountId>,
+{
+    let (parts, body) = request.into_parts();
+    let body = if body.size_hint().lower() > MAX_REQUEST_SIZE {
+        Err(ApiError::payload_too_large("Request body is too large"))
+    } else {
+        hyper::body::to_bytes(body)
+            .await
+            .map_err(|e| ApiError::bad_request(e.to_string()))
+    };
+
+    let result = body.and_then(|body| {
+        gateway.handle(&parts.method, parts.uri.path(), parts.uri.query(), &body)
+    });
+    match result {
+        Ok(body) => json_response(StatusCode::OK, body),
+        Err(err) => {
+            let status =
+                StatusCode::from_u16(err.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            json_response(status, serde_json::to_vec(&err).unwrap_or_default())
+        }
+    }
+}
+
+/// Serves the gateway on `addr` until the server fails.
+pub async fn serve<C, B, Block, AccountId>(
+    addr: SocketAddr,
+    gateway: Gateway<C, B, Block, AccountId>,
+) where
+    Block: BlockT,
+    AccountId: Codec + 'static,
+    B: Backend<Block> + 'static,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, B>,
+    C: Send + Sync + 'static,
+    C::Api: MVMApiRuntime<Block, AccountId>,
+{
+    let gateway = Arc::new(gateway);
+    let make_service = make_service_fn(move |_| {
+        let gateway = gateway.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let gateway = gateway.clone();
+                async move { Ok::<_, Infallible>(respond(gateway, request).await) }
+            }))
+        }
+    });
+
+    let server = match Server::try_bind(&addr) {
+        Ok(builder) => builder.serve(make_service),
+        Err(e) => {
+            tracing::error!("Failed to start Move REST API on {}: {}", addr, e);
+            return;
+        }
+    };
+    tracing::info!("Move REST API listening on http://{}", addr);
+    if let Err(e) = server.await {
+        tracing::error!("Move REST API server error: {}", e);
+    }
+}
@@ -0,0 +1,77 @@
+//! Routes of the gateway.
+//!
+//! Paths follow Aptos REST API, with or without the `/v1` prefix.
+use hyper::Method;
+use move_core_types::account_address::AccountAddress;
+use crate::types::ApiError;
+
+/// Query parameter with the block number to read the state at (Aptos ledger version).
+const LEDGER_VERSION: &str = "ledger_version";
+
+/// Gateway route.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Route {
+    /// `GET /accounts/{address}/resources`
+    AccountResources(AccountAddress),
+    /// `GET /accounts/{address}/modules`
+    AccountModules(AccountAddress),
+    /// `POST /transactions/simulate`
+    SimulateTransaction,
+}
+
+impl Route {
+    /// Resolves the route of the request.
+    pub fn parse(method: &Method, path: &str) -> Result<Route, ApiError> {
+        let path = path.trim_end_matches('/');
+        let path = path.strip_prefix("/v1").unwrap_or(path);
+        let segments: Vec<_> = path.split('/').skip(1).collect();
+
+        let (route, allowed) = match segments.as_slice() {
+            ["accounts", address, "resources"] => {
+                (Route::AccountResources(parse_address(address)?), Method::GET)
+            }
+            ["accounts", address, "modules"] => {
+                (Route::AccountModules(parse_address(address)?), Method::GET)
+            }
+            ["transactions", "simulate"] => (Route::SimulateTransaction, Method::POST),
+            _ => return Err(ApiError::not_found(format!("Route {} not found", path))),
+        };
+
+        if *method != allowed {
+            return Err(ApiError::method_not_allowed(format!(
+                "Method {} is not allowed, expected {}",
+                method, allowed
+            )));
+        }
+        Ok(route)
+    }
+}
+
+/// Parses account address, `0x` prefix is optional and leading zeros may be omitted.
+pub fn parse_address(address: &str) -> Result<AccountAddress, ApiError> {
+    let literal = if address.starts_with("0x") {
+        address.to_owned()
+    } else {
+        format!("0x{}", address)
+    };
+    AccountAddress::from_hex_literal(&literal)
+        .map_err(|_| ApiError::bad_request(format!("Invalid address {}", address)))
+}
+
+/// Ledger version requested in the query string, `None` for the best block.
+pub fn ledger_version(query: Option<&str>) -> Result<Option<u64>, ApiError> {
+    let value = query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == LEDGER_VERSION)
+        .map(|(_, value)| value);
+
+    value
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| ApiError::bad_request(format!("Invalid ledger version {}", value)))
+        })
+        .transpose()
+}
@@ -0,0 +1,116 @@
+//! Request and response types in the JSON shape of Aptos REST API.
+use std::fmt;
+use std::str::FromStr;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use fc_rpc_core::types::Bytes;
+use move_core_types::vm_status::StatusCode;
+use sp_mvm_rpc::decode::TransactionPayload;
+
+/// Integer rendered as a string, as Aptos renders `u64` values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct U64(pub u64);
+
+impl Serialize for U64 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for U64 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        u64::from_str(&s).map(U64).map_err(de::Error::custom)
+    }
+}
+
+/// Resource published under the account.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MoveResource {
+    /// Struct tag of the resource, e.g. `0x1::Diem::Balance<0x1::NOX::NOX>`.
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub data: Value,
+}
+
+/// Request of `/transactions/simulate`.
+///
+/// Payload is the Move transaction as produced by `dove tx`, the same as `mvm.execute` accepts.
+/// Simulated transactions aren't signed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulateTransactionRequest {
+    pub sender: String,
+    pub max_gas_amount: U64,
+    #[serde(default)]
+    pub gas_unit_price: U64,
+    pub payload: Bytes,
+}
+
+/// Simulated transaction in the shape of Aptos `UserTransaction`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedTransaction {
+    pub sender: String,
+    pub max_gas_amount: U64,
+    pub gas_unit_price: U64,
+    pub gas_used: U64,
+    pub success: bool,
+    pub vm_status: String,
+    /// Decoded payload, `None` if modules of the entry function aren't published.
+    pub payload: Option<TransactionPayload>,
+}
+
+/// Whether the transaction is executed successfully.
+pub fn is_executed(status_code: u64) -> bool {
+    status_code == StatusCode::EXECUTED as u64
+}
+
+/// Renders Move VM status code as Aptos `vm_status`.
+pub fn vm_status(status_code: u64) -> String {
+    match StatusCode::try_from(status_code) {
+        Ok(StatusCode::EXECUTED) => "Executed successfully".to_owned(),
+        Ok(status) => format!("{:?}", status),
+        Err(_) => format!("Unknown status code {}", status_code),
+    }
+}
+
+/// Error response in the shape of Aptos errors.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApiError {
+    pub code: u16,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(400, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(404, message)
+    }
+
+    pub fn method_not_allowed(message: impl Into<String>) -> Self {
+        Self::new(405, message)
+    }
+
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::new(413, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(500, message)
+    }
+
+    fn new(code: u16, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
@@ -0,0 +1,80 @@
+/// Tests for the routing and JSON shapes of the REST gateway.
+use hyper::Method;
+use serde_json::json;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+use sp_mvm_rest::routes::{ledger_version, parse_address, Route};
+use sp_mvm_rest::types::{is_executed, vm_status, SimulateTransactionRequest, U64};
+
+#[test]
+/// Aptos paths are routed with or without `/v1` prefix.
+fn routes() {
+    assert_eq!(
+        Route::parse(&Method::GET, "/accounts/0x1/resources"),
+        Ok(Route::AccountResources(CORE_CODE_ADDRESS))
+    );
+    assert_eq!(
+        Route::parse(&Method::GET, "/v1/accounts/0x1/modules/"),
+        Ok(Route::AccountModules(CORE_CODE_ADDRESS))
+    );
+    assert_eq!(
+        Route::parse(&Method::POST, "/v1/transactions/simulate"),
+        Ok(Route::SimulateTransaction)
+    );
+}
+
+#[test]
+/// Unknown routes, wrong methods and invalid addresses are rejected.
+fn invalid_routes() {
+    let code = |method, path| Route::parse(&method, path).unwrap_err().code;
+    assert_eq!(code(Method::GET, "/accounts/0x1"), 404);
+    assert_eq!(code(Method::GET, "/v2/accounts/0x1/resources"), 404);
+    assert_eq!(code(Method::POST, "/accounts/0x1/resources"), 405);
+    assert_eq!(code(Method::GET, "/transactions/simulate"), 405);
+    assert_eq!(code(Method::GET, "/accounts/0xzz/modules"), 400);
+}
+
+#[test]
+/// Addresses are accepted in short and full forms, with or without `0x`.
+fn addresses() {
+    let full = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48";
+    let address = AccountAddress::from_hex_literal(&format!("0x{}", full)).unwrap();
+    assert_eq!(parse_address(full), Ok(address));
+    assert_eq!(parse_address(&format!("0x{}", full)), Ok(address));
+    assert_eq!(parse_address("1"), Ok(CORE_CODE_ADDRESS));
+}
+
+#[test]
+/// Ledger version is read from the query string.
+fn ledger_versions() {
+    assert_eq!(ledger_version(None), Ok(None));
+    assert_eq!(ledger_version(Some("start=0&ledger_version=42")), Ok(Some(42)));
+    assert_eq!(ledger_version(Some("limit=10")), Ok(None));
+    assert_eq!(ledger_version(Some("ledger_version=latest")).unwrap_err().code, 400);
+}
+
+#[test]
+/// Integers are strings and gas unit price is optional, as in Aptos requests.
+fn simulate_request() {
+    let request: SimulateTransactionRequest = serde_json::from_value(json!({
+        "sender": "0x1",
+        "max_gas_amount": "1000",
+        "payload": "0x0102",
+    }))
+    .unwrap();
+    assert_eq!(request.max_gas_amount, U64(1000));
+    assert_eq!(request.gas_unit_price, U64(0));
+    assert_eq!(request.payload.into_vec(), vec![1, 2]);
+
+    assert_eq!(serde_json::to_value(U64(u64::MAX)).unwrap(), json!(u64::MAX.to_string()));
+}
+
+#[test]
+/// VM status codes are rendered as Aptos does.
+fn vm_statuses() {
+    assert!(is_executed(4001));
+    assert_eq!(vm_status(4001), "Executed successfully");
+    assert!(!is_executed(4016));
+    assert_eq!(vm_status(4016), "ABORTED");
+    assert_eq!(vm_status(u64::MAX), format!("Unknown status code {}", u64::MAX));
+}
@@ -9,7 +9,28 @@ use frame_support::weights::Weight;
 pub mod types;
 
 // Describe Runtime API for MVM pallet.
+//
+// Version 1: gas conversion, gas estimation without deadline, modules, ABI and resources.
+// Version 2: estimation deadline, package estimation, storage keys, Move addresses,
+// allowlisted scripts and VM info.
+// Version 3: XCM transfer fee estimation.
+// Version 4: block gas usage.
+// Version 5: sequence numbers of Move accounts.
+// Version 6: fee estimation of script execution.
+// Version 7: Aptos chain id.
+// Version 8: encoding of `execute` calls.
+// Version 9: package metadata.
+// Version 10: module source maps.
+// Version 11: abort codes of estimations.
+// Version 12: state overrides of estimations.
+// Version 13: Move extrinsics of blocks with their outcome.
+// Version 14: accounts of Move addresses.
+// Version 15: gas unit price and gas tip of execution fee estimation.
+// Version 16: gas unit price moved from `execute` call into `ChargeGasPrice` extension.
+// RPC checks the version of the runtime at the block and falls back to old methods
+// or reports methods unsupported by the runtime.
 sp_api::decl_runtime_apis! {
+    #[api_version(16)]
     pub trait MVMApiRuntime<AccountId> where
         AccountId: codec::Codec,
     {
@@ -20,10 +41,39 @@ sp_api::decl_runtime_apis! {
         fn weight_to_gas(weight: Weight) -> u64;
 
         // Estimate gas for publish module.
-        fn estimate_gas_publish(account: AccountId, module_bc: Vec<u8>, gas_limit: u64) -> Result<types::MVMApiEstimation, sp_runtime::DispatchError>;
+        #[changed_in(2)]
+        fn estimate_gas_publish(account: AccountId, module_bc: Vec<u8>, gas_limit: u64) -> Result<types::MVMApiEstimationV1, sp_runtime::DispatchError>;
+
+        // Estimate gas for publish module.
+        // Deadline (in milliseconds) is converted to gas and limits execution.
+        #[changed_in(11)]
+        fn estimate_gas_publish(account: AccountId, module_bc: Vec<u8>, gas_limit: u64, deadline: Option<u64>) -> Result<types::MVMApiEstimationV1, sp_runtime::DispatchError>;
+
+        // Estimate gas for publish module.
+        // Deadline (in milliseconds) is converted to gas and limits execution.
+        fn estimate_gas_publish(account: AccountId, module_bc: Vec<u8>, gas_limit: u64, deadline: Option<u64>) -> Result<types::MVMApiEstimation, sp_runtime::DispatchError>;
+
+        // Estimate gas for publish package of modules, verified together as by `publish_package`.
+        // Deadline (in milliseconds) is converted to gas and limits execution.
+        #[changed_in(11)]
+        fn estimate_gas_publish_package(account: AccountId, modules: Vec<Vec<u8>>, gas_limit: u64, deadline: Option<u64>) -> Result<types::MVMApiEstimationV1, sp_runtime::DispatchError>;
+
+        // Estimate gas for publish package of modules, verified together as by `publish_package`.
+        // Deadline (in milliseconds) is converted to gas and limits execution.
+        fn estimate_gas_publish_package(account: AccountId, modules: Vec<Vec<u8>>, gas_limit: u64, deadline: Option<u64>) -> Result<types::MVMApiEstimation, sp_runtime::DispatchError>;
+
+        // Estimate gas for execute script.
+        #[changed_in(2)]
+        fn estimate_gas_execute(account: AccountId, tx_bc: Vec<u8>, gas_limit: u64) -> Result<types::MVMApiEstimationV1, sp_runtime::DispatchError>;
 
         // Estimate gas for execute script.
-        fn estimate_gas_execute(account: AccountId, tx_bc: Vec<u8>, gas_limit: u64) -> Result<types::MVMApiEstimation, sp_runtime::DispatchError>;
+        // Deadline (in milliseconds) is converted to gas and limits execution.
+        #[changed_in(11)]
+        fn estimate_gas_execute(account: AccountId, tx_bc: Vec<u8>, gas_limit: u64, deadline: Option<u64>) -> Result<types::MVMApiEstimationV1, sp_runtime::DispatchError>;
+
+        // Estimate gas for execute script, abort code is returned for aborted scripts.
+        // Deadline (in milliseconds) is converted to gas and limits execution.
+        fn estimate_gas_execute(account: AccountId, tx_bc: Vec<u8>, gas_limit: u64, deadline: Option<u64>) -> Result<types::MVMApiEstimation, sp_runtime::DispatchError>;
 
         // Get module binary by it's address
         fn get_module(module_id: Vec<u8>) -> Result<Option<Vec<u8>>, Vec<u8>>;
@@ -34,5 +84,71 @@ sp_api::decl_runtime_apis! {
         // Get resource
         fn get_resource(account: AccountId, tag: Vec<u8>) -> Result<Option<Vec<u8>>, Vec<u8>>;
 
+        // Get storage trie key of the resource, used to request its storage proof
+        fn get_resource_storage_key(account: AccountId, tag: Vec<u8>) -> Vec<u8>;
+
+        // Get storage trie key of the module by it's BCS encoded id
+        fn get_module_storage_key(module_id: Vec<u8>) -> Vec<u8>;
+
+        // Get prefix of the Move VM storage trie keys
+        fn get_vm_storage_prefix() -> Vec<u8>;
+
+        // Get Move address of the account
+        fn get_move_address(account: AccountId) -> [u8; 32];
+
+        // Get allowlisted scripts with metadata
+        fn get_allowed_scripts() -> Vec<types::MVMApiAllowedScript<AccountId>>;
+
+        // Get versions and supported features
+        fn get_vm_info() -> types::MVMApiVmInfo;
+
+        // Estimate fee of the XCM transfer of the currency (by ticker) to the SCALE encoded
+        // `VersionedMultiLocation`, charged by the destination and reserve chains
+        fn estimate_xcm_transfer_fee(ticker: Vec<u8>, dest: Vec<u8>) -> Result<types::MVMApiXcmFee, Vec<u8>>;
+
+        // Get Move gas used within the block and the block gas ceiling
+        fn get_block_gas_usage() -> types::MVMApiBlockGas;
+
+        // Get sequence number of Move account
+        fn get_sequence_number(account: AccountId) -> u64;
+
+        // Estimate fee (base, length and weight fees) of the signed `execute` extrinsic with the
+        // gas limit, in the native currency and in the currency (by ticker) if requested
+        #[changed_in(15)]
+        fn estimate_execute_fee(tx_bc: Vec<u8>, gas_limit: u64, ticker: Option<Vec<u8>>) -> Result<types::MVMApiFeeV1, Vec<u8>>;
+
+        // Estimate fee (base, length and weight fees) of the signed `execute` extrinsic with the
+        // gas limit and unit price, in the native currency and in the currency (by ticker) if
+        // requested, with the gas tip charged by `ChargeGasPrice` in the native currency
+        fn estimate_execute_fee(tx_bc: Vec<u8>, gas_limit: u64, gas_unit_price: u64, ticker: Option<Vec<u8>>) -> Result<types::MVMApiFee, Vec<u8>>;
+
+        // Get chain id of ed25519 signed transactions, also reported to Aptos tooling
+        fn get_aptos_chain_id() -> u8;
+
+        // Encode `execute` call of the runtime and the unsigned extrinsic with the call
+        #[changed_in(16)]
+        fn encode_execute_call(tx_bc: Vec<u8>, gas_limit: u64, gas_unit_price: u64) -> types::MVMApiSubmission;
+
+        // Encode `execute` call of the runtime and the unsigned extrinsic with the call, the gas
+        // unit price is set by the client in `ChargeGasPrice` extension
+        fn encode_execute_call(tx_bc: Vec<u8>, gas_limit: u64) -> types::MVMApiSubmission;
+
+        // Get metadata of the package published by the account
+        fn get_package(account: AccountId, name: Vec<u8>) -> Option<types::MVMApiPackage<AccountId>>;
+
+        // Get source map of the module by it's id
+        fn get_source_map(module_id: Vec<u8>) -> Option<Vec<u8>>;
+
+        // Override resources (account, BCS encoded struct tag, value or `None` to remove) in the
+        // state overlay of the calls, e.g. before the estimation; nothing is committed
+        fn override_resources(overrides: Vec<(AccountId, Vec<u8>, Option<Vec<u8>>)>) -> Result<(), Vec<u8>>;
+
+        // Get the Move extrinsic (SCALE encoded) with the index in the block with its outcome:
+        // dispatch success, `ExecutionResult` and Move events of the extrinsic; `None` for other
+        // extrinsics. Called at the block including the extrinsic
+        fn get_transaction(extrinsic: Vec<u8>, index: u32) -> Option<types::MVMApiTransaction<AccountId>>;
+
+        // Get the account of the Move address, recorded on the first Move call of the account
+        fn resolve_move_address(address: [u8; 32]) -> Option<AccountId>;
     }
 }
@@ -1,7 +1,131 @@
 use codec::{Encode, Decode};
+use sp_std::prelude::*;
 
 #[derive(Clone, PartialEq, Debug, Encode, Decode)]
 pub struct MVMApiEstimation {
     pub gas_used: u64,
     pub status_code: u64,
+    pub abort_code: Option<u64>,
+}
+
+// Estimation of the runtime API before version 11, without abort code.
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiEstimationV1 {
+    pub gas_used: u64,
+    pub status_code: u64,
+}
+
+impl From<MVMApiEstimationV1> for MVMApiEstimation {
+    fn from(e: MVMApiEstimationV1) -> Self {
+        Self {
+            gas_used: e.gas_used,
+            status_code: e.status_code,
+            abort_code: None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiAllowedScript<AccountId> {
+    pub hash: [u8; 32],
+    pub description: Option<Vec<u8>>,
+    pub proposer: Option<AccountId>,
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiVmInfo {
+    pub pallet_version: (u16, u8, u8),
+    pub move_vm_revision: Vec<u8>,
+    pub gas_schedule_version: u32,
+    pub stdlib_hash: [u8; 32],
+    pub features: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiXcmFee {
+    pub weight: u64,
+    pub fee: u128,
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiBlockGas {
+    pub used: u64,
+    pub limit: Option<u64>,
+}
+
+// Fee estimation of the runtime API before version 15, without the gas tip.
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiFeeV1 {
+    pub base_fee: u128,
+    pub length_fee: u128,
+    pub weight_fee: u128,
+    pub total_fee: u128,
+    pub total_fee_in: Option<u128>,
+}
+
+impl From<MVMApiFeeV1> for MVMApiFee {
+    fn from(fee: MVMApiFeeV1) -> Self {
+        Self {
+            base_fee: fee.base_fee,
+            length_fee: fee.length_fee,
+            weight_fee: fee.weight_fee,
+            total_fee: fee.total_fee,
+            total_fee_in: fee.total_fee_in,
+            gas_tip: 0,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiFee {
+    pub base_fee: u128,
+    pub length_fee: u128,
+    pub weight_fee: u128,
+    pub total_fee: u128,
+    pub total_fee_in: Option<u128>,
+    pub gas_tip: u128,
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiSubmission {
+    pub call: Vec<u8>,
+    pub unsigned_extrinsic: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiPackage<AccountId> {
+    pub name: Vec<u8>,
+    pub version: Vec<u8>,
+    pub upgrade_number: u64,
+    pub dependencies: Vec<(AccountId, Vec<u8>)>,
+    pub source_digest: Vec<u8>,
+    pub modules: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiExecutionResult {
+    pub gas_used: u64,
+    pub status_code: u64,
+    pub abort_code: Option<u64>,
+    pub writes: u32,
+    pub events: u32,
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiEvent {
+    pub guid: Vec<u8>,
+    pub ty_tag: Vec<u8>,
+    pub message: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Debug, Encode, Decode)]
+pub struct MVMApiTransaction<AccountId> {
+    pub signer: Option<AccountId>,
+    pub call: Vec<u8>,
+    pub payload: Vec<u8>,
+    pub gas_limit: u64,
+    pub gas_unit_price: u64,
+    pub success: bool,
+    pub result: Option<MVMApiExecutionResult>,
+    pub events: Vec<MVMApiEvent>,
 }
@@ -0,0 +1,407 @@
+//! Move module ABI in the format of Aptos REST API.
+//!
+//! Output of `mvm_getModuleABI` and `mvm_getScriptABI` follows the JSON shape of Aptos
+//! `MoveModuleBytecode` and `MoveScriptBytecode`,
+//! so existing Aptos SDKs can consume it without any changes. Modules published with source
+//! maps also list their error constants (`errors`, see [`crate::abort`]).
+use serde::{Serialize, Deserialize};
+use fc_rpc_core::types::Bytes;
+use move_binary_format::access::{ModuleAccess, ScriptAccess};
+use move_binary_format::file_format::{
+    Ability, AbilitySet, CompiledModule, CompiledScript, SignatureToken, StructFieldInformation,
+    StructHandleIndex, Visibility,
+};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::TypeTag;
+use sp_mvm::entry::is_view;
+use crate::abort::{abort_reasons, AbortReason};
+use crate::source_map::SourceMap;
+
+/// Module bytecode with ABI.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MoveModuleBytecode {
+    pub bytecode: Bytes,
+    pub abi: Option<MoveModule>,
+}
+
+impl MoveModuleBytecode {
+    /// Creates new instance from module bytecode, ABI is not parsed yet.
+    pub fn new(bytecode: Vec<u8>) -> Self {
+        Self {
+            bytecode: bytecode.into(),
+            abi: None,
+        }
+    }
+
+    /// Deserializes bytecode and fills ABI.
+    pub fn try_parse_abi(mut self) -> anyhow::Result<Self> {
+        if self.abi.is_none() {
+            let module = CompiledModule::deserialize(&self.bytecode.0)
+                .map_err(|err| anyhow::anyhow!("Failed to deserialize module: {:?}", err))?;
+            self.abi = Some(make_abi(&module));
+        }
+        Ok(self)
+    }
+}
+
+/// Entry of the batched module ABI query: ABI of the module (`None` if the module isn't
+/// published) or the error of this module only.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModuleAbiEntry {
+    pub module: Option<MoveModuleBytecode>,
+    pub error: Option<jsonrpc_core::Error>,
+}
+
+impl From<jsonrpc_core::Result<Option<MoveModuleBytecode>>> for ModuleAbiEntry {
+    fn from(result: jsonrpc_core::Result<Option<MoveModuleBytecode>>) -> Self {
+        match result {
+            Ok(module) => ModuleAbiEntry {
+                module,
+                error: None,
+            },
+            Err(error) => ModuleAbiEntry {
+                module: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Script bytecode with ABI.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MoveScriptBytecode {
+    pub bytecode: Bytes,
+    pub abi: Option<MoveFunction>,
+}
+
+impl MoveScriptBytecode {
+    /// Creates new instance from script bytecode, ABI is not parsed yet.
+    pub fn new(bytecode: Vec<u8>) -> Self {
+        Self {
+            bytecode: bytecode.into(),
+            abi: None,
+        }
+    }
+
+    /// Deserializes bytecode and fills ABI.
+    pub fn try_parse_abi(mut self) -> anyhow::Result<Self> {
+        if self.abi.is_none() {
+            let script = CompiledScript::deserialize(&self.bytecode.0)
+                .map_err(|err| anyhow::anyhow!("Failed to deserialize script: {:?}", err))?;
+            self.abi = Some(make_script_abi(&script));
+        }
+        Ok(self)
+    }
+}
+
+/// Module ABI.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct MoveModule {
+    pub address: String,
+    pub name: String,
+    pub friends: Vec<String>,
+    pub exposed_functions: Vec<MoveFunction>,
+    pub structs: Vec<MoveStruct>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<AbortReason>,
+}
+
+/// Function visibility.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum MoveFunctionVisibility {
+    Private,
+    Public,
+    Friend,
+}
+
+/// Move ability.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum MoveAbility {
+    Copy,
+    Drop,
+    Store,
+    Key,
+}
+
+/// Exposed (public, friend or entry) function.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct MoveFunction {
+    pub name: String,
+    pub visibility: MoveFunctionVisibility,
+    pub is_entry: bool,
+    // View-style function (see `sp_mvm::entry::is_view`), read-only calls of it make sense.
+    pub is_view: bool,
+    pub generic_type_params: Vec<MoveFunctionGenericTypeParam>,
+    pub params: Vec<String>,
+    #[serde(rename = "return")]
+    #[cfg_attr(feature = "graphql", graphql(name = "return"))]
+    pub return_: Vec<String>,
+}
+
+/// Function type parameter.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct MoveFunctionGenericTypeParam {
+    pub constraints: Vec<MoveAbility>,
+}
+
+/// Struct definition.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct MoveStruct {
+    pub name: String,
+    pub is_native: bool,
+    pub abilities: Vec<MoveAbility>,
+    pub generic_type_params: Vec<MoveStructGenericTypeParam>,
+    pub fields: Vec<MoveStructField>,
+}
+
+/// Struct type parameter.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct MoveStructGenericTypeParam {
+    pub constraints: Vec<MoveAbility>,
+}
+
+/// Struct field.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct MoveStructField {
+    pub name: String,
+    #[serde(rename = "type")]
+    #[cfg_attr(feature = "graphql", graphql(name = "type"))]
+    pub typ: String,
+}
+
+/// Builds ABI of the compiled module.
+pub fn make_abi(module: &CompiledModule) -> MoveModule {
+    make_abi_with_source_map(module, None)
+}
+
+/// Builds ABI of the compiled module with the error constants named by the source map.
+pub fn make_abi_with_source_map(
+    module: &CompiledModule,
+    source_map: Option<&SourceMap>,
+) -> MoveModule {
+    let id = module.self_id();
+
+    let friends = module
+        .friend_decls()
+        .iter()
+        .map(|handle| {
+            let id = module.module_id_for_handle(handle);
+            format!("{}::{}", address_literal(id.address()), id.name())
+        })
+        .collect();
+
+    let exposed_functions = module
+        .function_defs()
+        .iter()
+        .filter(|def| def.visibility != Visibility::Private)
+        .map(|def| {
+            let handle = module.function_handle_at(def.function);
+            let (visibility, is_entry) = match def.visibility {
+                Visibility::Friend => (MoveFunctionVisibility::Friend, false),
+                // `public(script)` functions are entry functions in terms of Aptos.
+                Visibility::Script => (MoveFunctionVisibility::Public, true),
+                _ => (MoveFunctionVisibility::Public, false),
+            };
+
+            MoveFunction {
+                name: module.identifier_at(handle.name).to_string(),
+                visibility,
+                is_entry,
+                is_view: is_view(module, def),
+                generic_type_params: function_type_params(&handle.type_parameters),
+                params: signature(module, module.signature_at(handle.parameters).0.iter()),
+                return_: signature(module, module.signature_at(handle.return_).0.iter()),
+            }
+        })
+        .collect();
+
+    let structs = module
+        .struct_defs()
+        .iter()
+        .map(|def| {
+            let handle = module.struct_handle_at(def.struct_handle);
+            let (is_native, fields) = match &def.field_information {
+                StructFieldInformation::Native => (true, vec![]),
+                StructFieldInformation::Declared(fields) => (
+                    false,
+                    fields
+                        .iter()
+                        .map(|field| MoveStructField {
+                            name: module.identifier_at(field.name).to_string(),
+                            typ: type_string(module, &field.signature.0),
+                        })
+                        .collect(),
+                ),
+            };
+
+            MoveStruct {
+                name: module.identifier_at(handle.name).to_string(),
+                is_native,
+                abilities: abilities(handle.abilities),
+                generic_type_params: handle
+                    .type_parameters
+                    .iter()
+                    .map(|param| MoveStructGenericTypeParam {
+                        constraints: abilities(param.constraints),
+                    })
+                    .collect(),
+                fields,
+            }
+        })
+        .collect();
+
+    MoveModule {
+        address: address_literal(id.address()),
+        name: id.name().to_string(),
+        friends,
+        exposed_functions,
+        structs,
+        errors: source_map
+            .map(|source_map| abort_reasons(module, source_map))
+            .unwrap_or_default(),
+    }
+}
+
+/// Builds ABI of the compiled script.
+///
+/// Script is rendered as a public entry function named `main`, as Aptos does.
+pub fn make_script_abi(script: &CompiledScript) -> MoveFunction {
+    MoveFunction {
+        name: "main".to_owned(),
+        visibility: MoveFunctionVisibility::Public,
+        is_entry: true,
+        is_view: false,
+        generic_type_params: function_type_params(&script.type_parameters),
+        params: signature(
+            script,
+            script.signature_at(script.parameters).0.iter(),
+        ),
+        return_: vec![],
+    }
+}
+
+/// Handles shared by compiled modules and scripts, required to render types.
+trait Handles {
+    fn struct_name(&self, idx: StructHandleIndex) -> String;
+}
+
+impl Handles for CompiledModule {
+    fn struct_name(&self, idx: StructHandleIndex) -> String {
+        let handle = self.struct_handle_at(idx);
+        let id = self.module_id_for_handle(self.module_handle_at(handle.module));
+        format!(
+            "{}::{}::{}",
+            address_literal(id.address()),
+            id.name(),
+            self.identifier_at(handle.name)
+        )
+    }
+}
+
+impl Handles for CompiledScript {
+    fn struct_name(&self, idx: StructHandleIndex) -> String {
+        let handle = self.struct_handle_at(idx);
+        let module = self.module_handle_at(handle.module);
+        format!(
+            "{}::{}::{}",
+            address_literal(self.address_identifier_at(module.address)),
+            self.identifier_at(module.name),
+            self.identifier_at(handle.name)
+        )
+    }
+}
+
+/// Short hex literal of the address (`0x1`), the same as Aptos renders addresses.
+pub(crate) fn address_literal(address: &AccountAddress) -> String {
+    address.to_hex_literal()
+}
+
+/// Renders type tag as a Move type string with short addresses, e.g. `0x1::NOX::NOX`.
+pub fn type_tag_string(tag: &TypeTag) -> String {
+    match tag {
+        TypeTag::Bool => "bool".to_owned(),
+        TypeTag::U8 => "u8".to_owned(),
+        TypeTag::U64 => "u64".to_owned(),
+        TypeTag::U128 => "u128".to_owned(),
+        TypeTag::Address => "address".to_owned(),
+        TypeTag::Signer => "signer".to_owned(),
+        TypeTag::Vector(tag) => format!("vector<{}>", type_tag_string(tag)),
+        TypeTag::Struct(tag) => {
+            let name = format!(
+                "{}::{}::{}",
+                address_literal(&tag.address),
+                tag.module,
+                tag.name
+            );
+            if tag.type_params.is_empty() {
+                name
+            } else {
+                let params: Vec<_> = tag.type_params.iter().map(type_tag_string).collect();
+                format!("{}<{}>", name, params.join(", "))
+            }
+        }
+    }
+}
+
+fn abilities(set: AbilitySet) -> Vec<MoveAbility> {
+    [
+        (Ability::Copy, MoveAbility::Copy),
+        (Ability::Drop, MoveAbility::Drop),
+        (Ability::Store, MoveAbility::Store),
+        (Ability::Key, MoveAbility::Key),
+    ]
+    .into_iter()
+    .filter(|(ability, _)| set.has_ability(*ability))
+    .map(|(_, ability)| ability)
+    .collect()
+}
+
+fn function_type_params(params: &[AbilitySet]) -> Vec<MoveFunctionGenericTypeParam> {
+    params
+        .iter()
+        .map(|constraints| MoveFunctionGenericTypeParam {
+            constraints: abilities(*constraints),
+        })
+        .collect()
+}
+
+fn signature<'a>(
+    module: &impl Handles,
+    tokens: impl Iterator<Item = &'a SignatureToken>,
+) -> Vec<String> {
+    tokens.map(|token| type_string(module, token)).collect()
+}
+
+/// Renders signature token as a Move type string, e.g. `&mut 0x1::Event::EventHandle<T0>`.
+fn type_string(module: &impl Handles, token: &SignatureToken) -> String {
+    match token {
+        SignatureToken::Bool => "bool".to_owned(),
+        SignatureToken::U8 => "u8".to_owned(),
+        SignatureToken::U64 => "u64".to_owned(),
+        SignatureToken::U128 => "u128".to_owned(),
+        SignatureToken::Address => "address".to_owned(),
+        SignatureToken::Signer => "signer".to_owned(),
+        SignatureToken::Vector(token) => format!("vector<{}>", type_string(module, token)),
+        SignatureToken::Reference(token) => format!("&{}", type_string(module, token)),
+        SignatureToken::MutableReference(token) => {
+            format!("&mut {}", type_string(module, token))
+        }
+        SignatureToken::TypeParameter(idx) => format!("T{}", idx),
+        SignatureToken::Struct(idx) => module.struct_name(*idx),
+        SignatureToken::StructInstantiation(idx, type_args) => format!(
+            "{}<{}>",
+            module.struct_name(*idx),
+            signature(module, type_args.iter()).join(", ")
+        ),
+    }
+}
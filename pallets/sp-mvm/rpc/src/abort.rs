@@ -0,0 +1,101 @@
+//! Human-readable reasons of Move aborts.
+//!
+//! Move aborts with the numeric code only, e.g. `abort E_NOT_AUTHORIZED` reports `5`. Names of
+//! the constants are kept in the source map of the module only, so abort codes of the modules
+//! published with source maps (see [`crate::source_map`]) are resolved: `u64` constants named
+//! as errors (`E_NOT_AUTHORIZED` or `ENOT_AUTHORIZED`) make the registry of the module, listed
+//! in its ABI (`errors`).
+//!
+//! Move VM doesn't report the module of the abort, so the abort code of the dry run is resolved
+//! by the abort sites of the transaction call tree (see [`crate::trace`]): the first site
+//! aborting with the code names the reason.
+use serde::{Serialize, Deserialize};
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::{CompiledModule, ConstantPoolIndex, SignatureToken};
+use crate::abi::address_literal;
+use crate::source_map::SourceMap;
+use crate::trace::TraceNode;
+
+/// Error constant of the module.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct AbortReason {
+    /// Module declaring the constant, e.g. `0x1::Account`.
+    pub module: String,
+    /// Abort code, value of the constant.
+    pub code: u64,
+    /// Name of the constant, e.g. `E_NOT_AUTHORIZED`.
+    pub name: String,
+    /// Message built from the name, e.g. `Not authorized`.
+    pub message: String,
+}
+
+/// Error constants of the module named by its source map, ordered by code.
+pub fn abort_reasons(module: &CompiledModule, source_map: &SourceMap) -> Vec<AbortReason> {
+    let id = module.self_id();
+    let module_name = format!("{}::{}", address_literal(id.address()), id.name());
+    let constants = module.constant_pool().len();
+
+    let mut reasons: Vec<_> = source_map
+        .constants()
+        .filter(|(name, idx)| is_error_name(name) && (*idx as usize) < constants)
+        .filter_map(|(name, idx)| {
+            Some(AbortReason {
+                module: module_name.clone(),
+                code: u64_constant(module, ConstantPoolIndex(idx))?,
+                name: name.to_owned(),
+                message: message(name),
+            })
+        })
+        .collect();
+    reasons.sort_by_key(|reason| reason.code);
+    reasons
+}
+
+/// Reason of the abort with the code, named by the first abort site of the call tree
+/// (depth-first) aborting with it.
+pub fn find_reason(node: &TraceNode, abort_code: u64) -> Option<AbortReason> {
+    node.aborts
+        .iter()
+        .filter(|site| site.abort_code == Some(abort_code))
+        .find_map(|site| site.reason.clone())
+        .or_else(|| {
+            node.calls
+                .iter()
+                .find_map(|call| find_reason(call, abort_code))
+        })
+}
+
+/// Message of the error constant: name without the `E` prefix in words,
+/// e.g. `Not authorized` for `E_NOT_AUTHORIZED`.
+pub fn message(name: &str) -> String {
+    let words = name
+        .strip_prefix('E')
+        .unwrap_or(name)
+        .trim_start_matches('_')
+        .replace('_', " ")
+        .to_lowercase();
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name.to_owned(),
+    }
+}
+
+/// Error constants follow the Move convention: upper case names starting with `E`.
+fn is_error_name(name: &str) -> bool {
+    name.len() > 1
+        && name.starts_with('E')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Value of the `u64` constant, `None` for constants of other types.
+pub(crate) fn u64_constant(module: &CompiledModule, idx: ConstantPoolIndex) -> Option<u64> {
+    let constant = module.constant_at(idx);
+    match constant.type_ {
+        SignatureToken::U64 => bcs::from_bytes(&constant.data).ok(),
+        _ => None,
+    }
+}
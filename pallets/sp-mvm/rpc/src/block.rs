@@ -0,0 +1,107 @@
+//! Block to call `mvm_*` method at: hash or number of the block.
+//!
+//! Numbers are resolved to the hash of the canonical chain block, so queries by number follow
+//! the best chain as seen by the node.
+//!
+//! Nodes pruning state (`--state-pruning <blocks>`) keep states of the last finalized blocks
+//! only, queries of older blocks fail with "state pruned" error, archive nodes keep all states.
+use std::convert::TryFrom;
+use jsonrpc_core::Result;
+use serde::{Serialize, Deserialize};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, UniqueSaturatedInto};
+use crate::error;
+
+/// Block hash (`"0x..."`) or block number (`42`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockNumberOrHash<Hash> {
+    Hash(Hash),
+    Number(u64),
+}
+
+impl<Hash> From<Hash> for BlockNumberOrHash<Hash> {
+    fn from(hash: Hash) -> Self {
+        Self::Hash(hash)
+    }
+}
+
+/// Hash of the block, the best block if `at` is not supplied.
+pub fn resolve<Block, C>(
+    client: &C,
+    at: Option<BlockNumberOrHash<Block::Hash>>,
+) -> Result<Block::Hash>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    let number = match at {
+        None => return Ok(client.info().best_hash),
+        Some(BlockNumberOrHash::Hash(hash)) => return Ok(hash),
+        Some(BlockNumberOrHash::Number(number)) => number,
+    };
+    let not_found = || error::not_found("Block not found", format!("#{}", number));
+
+    let number = NumberFor::<Block>::try_from(number).map_err(|_| not_found())?;
+    client
+        .hash(number)
+        .map_err(|e| error::runtime_api_error("Error while requesting block hash", e))?
+        .ok_or_else(not_found)
+}
+
+/// Number of the earliest block with the state kept by the node, `keep` is the amount of
+/// finalized blocks with the state kept, `None` for archive nodes.
+pub fn earliest_available<Block, C>(client: &C, keep: Option<u32>) -> u64
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    let finalized: u64 = client.info().finalized_number.unique_saturated_into();
+    keep.map_or(0, |keep| finalized.saturating_sub(keep.into()))
+}
+
+/// Hash of the block as by `resolve`, fails with "state pruned" error if the node has already
+/// discarded state of the block.
+pub fn resolve_state<Block, C>(
+    client: &C,
+    at: Option<BlockNumberOrHash<Block::Hash>>,
+    keep: Option<u32>,
+) -> Result<Block::Hash>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    let hash = resolve::<Block, C>(client, at)?;
+    if keep.is_none() {
+        return Ok(hash);
+    }
+
+    let number = client
+        .number(hash)
+        .map_err(|e| error::runtime_api_error("Error while requesting block number", e))?;
+    if let Some(number) = number {
+        let number: u64 = number.unique_saturated_into();
+        let earliest = earliest_available::<Block, C>(client, keep);
+        if number < earliest {
+            return Err(error::state_pruned(
+                "State of the block is pruned",
+                format!("#{} is before the earliest available block #{}", number, earliest),
+            ));
+        }
+    }
+    Ok(hash)
+}
+
+/// State root of the block.
+pub fn state_root<Block, C>(client: &C, hash: Block::Hash) -> Result<Block::Hash>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    let header = client
+        .header(BlockId::hash(hash))
+        .map_err(|e| error::runtime_api_error("Error while requesting block header", e))?
+        .ok_or_else(|| error::not_found("Block not found", format!("{:?}", hash)))?;
+    Ok(*header.state_root())
+}
@@ -0,0 +1,145 @@
+//! Content-addressed caches of the module ABIs and gas estimations.
+//!
+//! Published module is immutable, the same bytecode is returned for every block until the
+//! module is republished, so ABIs are keyed by the hash of the bytecode: requests at any block
+//! hit the cache, and republished modules get new entries. ABIs with error constants are keyed
+//! by the hash of the bytecode and the source map. Least recently used entries are evicted once
+//! the cache is full.
+//!
+//! Estimation is a dry run, its result depends on the state and the request only, so it's
+//! keyed by the state root of the block and the request: the same request at the same state
+//! (e.g. repeated by wallets and dApps before submitting the transaction) is served without
+//! executing the runtime again. Cached estimations serve RPC requests only, block building
+//! executes extrinsics in full.
+use std::sync::{Arc, Mutex};
+use codec::Encode;
+use lru::LruCache;
+use move_binary_format::file_format::CompiledModule;
+use sp_core::hashing::blake2_256;
+use crate::abi::{make_abi_with_source_map, MoveModule};
+use crate::source_map::SourceMap;
+
+/// Default number of cached ABIs.
+pub const DEFAULT_CAPACITY: usize = 1024;
+
+/// Cache of the module ABIs, shared by all RPC servers of the node.
+#[derive(Clone)]
+pub struct ModuleCache {
+    entries: Arc<Mutex<LruCache<[u8; 32], Arc<MoveModule>>>>,
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl ModuleCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// ABI of the module bytecode, the bytecode is deserialized on the first request only.
+    pub fn abi(&self, bytecode: &[u8]) -> anyhow::Result<Arc<MoveModule>> {
+        self.abi_with_source_map(bytecode, None)
+    }
+
+    /// ABI of the module bytecode with the error constants named by the source map,
+    /// source maps which can't be decoded are ignored.
+    pub fn abi_with_source_map(
+        &self,
+        bytecode: &[u8],
+        source_map: Option<&[u8]>,
+    ) -> anyhow::Result<Arc<MoveModule>> {
+        let hash = match source_map {
+            Some(source_map) => blake2_256(&(bytecode, source_map).encode()),
+            None => blake2_256(bytecode),
+        };
+        if let Some(abi) = self.lock().get(&hash) {
+            return Ok(abi.clone());
+        }
+
+        // Deserialized without the lock, concurrent requests of the same module may parse it
+        // twice, but never wait for each other.
+        let module = CompiledModule::deserialize(bytecode)
+            .map_err(|err| anyhow::anyhow!("Failed to deserialize module: {:?}", err))?;
+        let source_map = source_map.and_then(|map| SourceMap::decode(map).ok());
+        let abi = Arc::new(make_abi_with_source_map(&module, source_map.as_ref()));
+        self.lock().put(hash, abi.clone());
+        Ok(abi)
+    }
+
+    /// Number of cached ABIs.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<LruCache<[u8; 32], Arc<MoveModule>>> {
+        // Cache is never left inconsistent by the panicking thread.
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Default number of cached estimations.
+pub const DEFAULT_ESTIMATIONS_CAPACITY: usize = 256;
+
+/// Cache of the gas estimations, shared by all RPC servers of the node.
+#[derive(Clone)]
+pub struct EstimationCache<T> {
+    entries: Arc<Mutex<LruCache<[u8; 32], T>>>,
+}
+
+impl<T: Clone> Default for EstimationCache<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_ESTIMATIONS_CAPACITY)
+    }
+}
+
+impl<T: Clone> EstimationCache<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// Key of the `method` request (SCALE encoded arguments) at the state.
+    pub fn key(state_root: &[u8], method: &str, request: &[u8]) -> [u8; 32] {
+        blake2_256(&(state_root, method, request).encode())
+    }
+
+    /// Cached estimation of the request, or the result of `estimate` cached on success.
+    /// Errors (e.g. runtime API failures) are not cached.
+    pub fn get_or_estimate<E>(
+        &self,
+        key: [u8; 32],
+        estimate: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, E> {
+        if let Some(estimation) = self.lock().get(&key) {
+            return Ok(estimation.clone());
+        }
+
+        // Estimated without the lock, as module ABIs.
+        let estimation = estimate()?;
+        self.lock().put(key, estimation.clone());
+        Ok(estimation)
+    }
+
+    /// Number of cached estimations.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<LruCache<[u8; 32], T>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,241 @@
+//! Subscription to Move state changes of the account for `mvm_subscribeAccountChanges`.
+//!
+//! `VMStorage` trie keys start with the hash of the access key, so entries of the address don't
+//! share a prefix: the subscription listens to all storage changes and matches changed keys by
+//! the access key. Storage prefix and Move address of the account are requested from the
+//! runtime of the best block once subscribed.
+use std::convert::TryFrom;
+use std::sync::Arc;
+use codec::{Codec, Decode};
+use futures::{future, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::{Error as RpcError, Result};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use serde::{Serialize, Deserialize};
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use fc_rpc_core::types::Bytes;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use sp_mvm::storage::{is_module_key, resource_owner};
+use sp_mvm_rpc_runtime::MVMApiRuntime;
+use crate::abi::{address_literal, type_tag_string};
+use crate::error;
+use crate::version::{require_api_version, API_VERSION_2};
+
+/// Length of the blake2-128 hash of the access key in the trie key.
+const KEY_HASH_LENGTH: usize = 16;
+
+/// Kind of the changed Move state entry.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Resource,
+    Module,
+}
+
+/// Changed resource or module of the account.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccountChange {
+    pub kind: ChangeKind,
+    /// Struct tag of the resource (e.g. `0x1::Diem::Balance<0x1::NOX::NOX>`)
+    /// or module id (e.g. `0x1::Diem`).
+    pub id: String,
+    /// New value, `None` if removed.
+    pub value: Option<Bytes>,
+}
+
+/// Changes of the account made by the block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccountChanges<BlockHash> {
+    pub block: BlockHash,
+    pub changes: Vec<AccountChange>,
+}
+
+/// Move state entry of the account, decoded from the `VMStorage` trie key.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccountKey {
+    Resource(StructTag),
+    Module(ModuleId),
+}
+
+/// Decodes the trie key with the address it's published under, `None` for other keys.
+pub fn state_key(prefix: &[u8], key: &[u8]) -> Option<(AccountAddress, AccountKey)> {
+    let encoded_key = key.strip_prefix(prefix)?.get(KEY_HASH_LENGTH..)?;
+    let access_key = Vec::<u8>::decode(&mut &encoded_key[..]).ok()?;
+
+    if let Some(owner) = resource_owner(&access_key) {
+        let tag = bcs::from_bytes(&access_key[AccountAddress::LENGTH + 1..]).ok()?;
+        return Some((owner, AccountKey::Resource(tag)));
+    }
+    let publisher = AccountAddress::try_from(access_key.get(1..=AccountAddress::LENGTH)?).ok()?;
+    if is_module_key(&access_key, &publisher) {
+        Some((publisher, AccountKey::Module(bcs::from_bytes(&access_key[1..]).ok()?)))
+    } else {
+        None
+    }
+}
+
+/// Decodes the trie key, `None` if it's not a resource or module of `address`.
+pub fn account_key(prefix: &[u8], address: &AccountAddress, key: &[u8]) -> Option<AccountKey> {
+    state_key(prefix, key)
+        .filter(|(owner, _)| owner == address)
+        .map(|(_, key)| key)
+}
+
+/// Decodes the changed trie entry, `None` if it's not a resource or module of `address`.
+pub fn account_change(
+    prefix: &[u8],
+    address: &AccountAddress,
+    key: &[u8],
+    value: Option<&[u8]>,
+) -> Option<AccountChange> {
+    let (kind, id) = match account_key(prefix, address, key)? {
+        AccountKey::Module(id) => {
+            let id = format!("{}::{}", address_literal(id.address()), id.name());
+            (ChangeKind::Module, id)
+        }
+        AccountKey::Resource(tag) => {
+            (ChangeKind::Resource, type_tag_string(&TypeTag::Struct(tag)))
+        }
+    };
+
+    let value = match value {
+        Some(value) => Some(Vec::<u8>::decode(&mut &value[..]).ok()?.into()),
+        None => None,
+    };
+    Some(AccountChange { kind, id, value })
+}
+
+#[rpc]
+pub trait MVMChangesApi<BlockHash, AccountId> {
+    type Metadata;
+
+    #[pubsub(
+        subscription = "mvm_accountChanges",
+        subscribe,
+        name = "mvm_subscribeAccountChanges"
+    )]
+    fn subscribe_account_changes(
+        &self,
+        metadata: Self::Metadata,
+        subscriber: Subscriber<AccountChanges<BlockHash>>,
+        account: AccountId,
+    );
+
+    #[pubsub(
+        subscription = "mvm_accountChanges",
+        unsubscribe,
+        name = "mvm_unsubscribeAccountChanges"
+    )]
+    fn unsubscribe_account_changes(
+        &self,
+        metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool>;
+}
+
+pub struct MVMChanges<C, P> {
+    client: Arc<C>,
+    subscriptions: SubscriptionManager,
+    _marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> MVMChanges<C, P> {
+    pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+        Self {
+            client,
+            subscriptions: SubscriptionManager::new(Arc::new(executor)),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId> MVMChangesApi<<Block as BlockT>::Hash, AccountId>
+    for MVMChanges<C, Block>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    C: 'static + Send + Sync,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+    C::Api: MVMApiRuntime<Block, AccountId>,
+{
+    type Metadata = sc_rpc::Metadata;
+
+    fn subscribe_account_changes(
+        &self,
+        _metadata: Self::Metadata,
+        subscriber: Subscriber<AccountChanges<<Block as BlockT>::Hash>>,
+        account: AccountId,
+    ) {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(self.client.info().best_hash);
+        let version = require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_subscribeAccountChanges",
+            API_VERSION_2,
+        );
+        if let Err(e) = version {
+            let _ = subscriber.reject(e);
+            return;
+        }
+        let filter = api.get_vm_storage_prefix(&at).and_then(|prefix| {
+            let address = api.get_move_address(&at, account)?;
+            Ok((prefix, address))
+        });
+        let (prefix, address) = match filter {
+            Ok((prefix, address)) => (prefix, AccountAddress::new(address)),
+            Err(e) => {
+                let _ = subscriber.reject(error::runtime_api_error("API error", e));
+                return;
+            }
+        };
+
+        let stream = match self.client.storage_changes_notification_stream(None, None) {
+            Ok(stream) => stream,
+            Err(e) => {
+                let _ = subscriber.reject(error::runtime_api_error(
+                    "Error while subscribing to storage changes",
+                    e,
+                ));
+                return;
+            }
+        };
+
+        let stream = stream.filter_map(move |(block, changes)| {
+            let changes: Vec<_> = changes
+                .iter()
+                .filter(|(child_key, _, _)| child_key.is_none())
+                .filter_map(|(_, key, value)| {
+                    let value = value.map(|value| value.0.as_slice());
+                    account_change(&prefix, &address, &key.0, value)
+                })
+                .collect();
+            future::ready(if changes.is_empty() {
+                None
+            } else {
+                Some(Ok::<_, ()>(Ok::<_, RpcError>(AccountChanges { block, changes })))
+            })
+        });
+
+        self.subscriptions.add(subscriber, |sink| {
+            stream
+                .forward(sink.sink_map_err(|e| {
+                    tracing::warn!("Error sending account changes notification: {:?}", e)
+                }))
+                .map(|_| ())
+        });
+    }
+
+    fn unsubscribe_account_changes(
+        &self,
+        _metadata: Option<Self::Metadata>,
+        id: SubscriptionId,
+    ) -> Result<bool> {
+        Ok(self.subscriptions.cancel(id))
+    }
+}
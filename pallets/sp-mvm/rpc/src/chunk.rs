@@ -0,0 +1,43 @@
+//! Module bytecode in chunks for `mvm_getModuleChunk`.
+//!
+//! Modules uploaded in chunks (see `stage_module_chunk` of the pallet) can exceed the response
+//! size of RPC servers, so clients download them by ranges of the bytecode.
+use jsonrpc_core::Result;
+use serde::{Serialize, Deserialize};
+use fc_rpc_core::types::Bytes;
+use crate::error;
+
+/// Maximum size of the returned chunk.
+pub const MAX_CHUNK_BYTES: u32 = 256 * 1024;
+
+/// Range of the module bytecode.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModuleChunk {
+    /// Size of the whole module bytecode.
+    pub size: u32,
+    /// Offset of the chunk in the bytecode.
+    pub offset: u32,
+    /// Bytecode of the chunk, empty if `offset` is the end of the module.
+    pub chunk: Bytes,
+}
+
+/// Chunk of `bytecode` at `offset` of `len` bytes at most, capped by [`MAX_CHUNK_BYTES`] and
+/// the end of the module.
+pub fn module_chunk(bytecode: &[u8], offset: u32, len: u32) -> Result<ModuleChunk> {
+    let start = offset as usize;
+    if start > bytecode.len() {
+        return Err(error::bad_params(
+            "Invalid chunk offset",
+            format!("{} is beyond the module size {}", offset, bytecode.len()),
+        ));
+    }
+    let end = start
+        .saturating_add(len.min(MAX_CHUNK_BYTES) as usize)
+        .min(bytecode.len());
+
+    Ok(ModuleChunk {
+        size: bytecode.len() as u32,
+        offset,
+        chunk: bytecode[start..end].to_vec().into(),
+    })
+}
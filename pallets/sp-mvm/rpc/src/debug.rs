@@ -0,0 +1,80 @@
+//! Capture of the runtime logs for `mvm_executeWithDebug`.
+//!
+//! Runtime API is executed on the calling thread, and runtime logs (including Move
+//! `Debug::print` output logged by Move VM) reach the node as `log` records, converted to
+//! `tracing` events by the node logger. The capturing subscriber is installed as the thread
+//! default for the duration of the call, so logs of other threads are not captured.
+//!
+//! Runtime drops records above the node log level, so the node has to run with the debug
+//! level for the Move VM targets, e.g. `-lmvm=debug`.
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+/// Maximum amount of captured lines, the rest is dropped.
+pub const MAX_CAPTURED_LINES: usize = 10_000;
+
+/// Runs `f` and returns its result with the logs emitted on this thread, e.g.
+/// `DEBUG mvm: [debug] 42`.
+pub fn capture_logs<R, F: FnOnce() -> R>(f: F) -> (R, Vec<String>) {
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = CaptureSubscriber {
+        lines: lines.clone(),
+    };
+    let result = tracing::subscriber::with_default(subscriber, f);
+
+    let lines = match lines.lock() {
+        Ok(mut lines) => std::mem::take(&mut *lines),
+        Err(poisoned) => std::mem::take(&mut *poisoned.into_inner()),
+    };
+    (result, lines)
+}
+
+/// Subscriber collecting events as lines, spans are ignored.
+struct CaptureSubscriber {
+    lines: Arc<Mutex<Vec<String>>>,
+}
+
+impl Subscriber for CaptureSubscriber {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = MessageVisitor(String::new());
+        event.record(&mut message);
+        let metadata = event.metadata();
+        let line = format!("{} {}: {}", metadata.level(), metadata.target(), message.0);
+
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() < MAX_CAPTURED_LINES {
+                lines.push(line);
+            }
+        }
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+/// Extracts `message` field of the event.
+struct MessageVisitor(String);
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
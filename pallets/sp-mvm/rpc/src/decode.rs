@@ -0,0 +1,240 @@
+//! Decoding of Move transactions for `mvm_decodeTransaction`.
+//!
+//! Payload is rendered in the JSON shape of Aptos `TransactionPayload`,
+//! so wallets can show users what they are signing.
+use std::convert::TryFrom;
+use anyhow::{anyhow, bail, ensure, Result};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use fc_rpc_core::types::Bytes;
+use move_binary_format::access::{ModuleAccess, ScriptAccess};
+use move_binary_format::file_format::{CompiledModule, CompiledScript, SignatureToken, Visibility};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::IdentStr;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use move_vm::types::{Call, Transaction};
+use crate::abi::{address_literal, type_tag_string, MoveScriptBytecode};
+
+/// Decoded transaction.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DecodedTransaction {
+    pub signers_count: u32,
+    pub root_signer: bool,
+    pub payload: TransactionPayload,
+}
+
+/// Transaction payload: entry function call or raw script.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransactionPayload {
+    ScriptFunctionPayload {
+        function: String,
+        type_arguments: Vec<String>,
+        arguments: Vec<Value>,
+    },
+    ScriptPayload {
+        code: MoveScriptBytecode,
+        type_arguments: Vec<String>,
+        arguments: Vec<Value>,
+    },
+}
+
+/// Decodes transaction (as produced by `dove tx`).
+///
+/// Arguments are decoded with the function signature, modules of entry functions
+/// are requested with `get_module`.
+pub fn decode_transaction<F>(tx_bc: &[u8], get_module: F) -> Result<DecodedTransaction>
+where
+    F: FnOnce(&ModuleId) -> Result<Vec<u8>>,
+{
+    let tx = Transaction::try_from(tx_bc)
+        .map_err(|err| anyhow!("Failed to deserialize transaction: {:?}", err))?;
+    let signers_count = tx.signers_count();
+    let root_signer = tx.has_root_signer();
+
+    // Signers are not part of the payload, so any addresses are fine here.
+    let tx = tx
+        .into_script(vec![AccountAddress::ZERO; signers_count as usize])
+        .map_err(|err| anyhow!("Invalid transaction: {:?}", err))?;
+    let type_args = tx.type_args();
+    let type_arguments = type_args.iter().map(type_tag_string).collect();
+
+    let payload = match tx.call() {
+        Call::Script { code } => {
+            let script = CompiledScript::deserialize(code)
+                .map_err(|err| anyhow!("Failed to deserialize script: {:?}", err))?;
+            let params = &script.signature_at(script.parameters).0;
+
+            TransactionPayload::ScriptPayload {
+                code: MoveScriptBytecode::new(code.clone()).try_parse_abi()?,
+                type_arguments,
+                arguments: decode_args(params, type_args, tx.args())?,
+            }
+        }
+        Call::ScriptFunction {
+            mod_address,
+            mod_name,
+            func_name,
+        } => {
+            let module_id = ModuleId::new(*mod_address, mod_name.clone());
+            let module = CompiledModule::deserialize(&get_module(&module_id)?)
+                .map_err(|err| anyhow!("Failed to deserialize module: {:?}", err))?;
+            let params = entry_function_params(&module, func_name)?;
+
+            TransactionPayload::ScriptFunctionPayload {
+                function: format!(
+                    "{}::{}::{}",
+                    address_literal(mod_address),
+                    mod_name,
+                    func_name
+                ),
+                type_arguments,
+                arguments: decode_args(params, type_args, tx.args())?,
+            }
+        }
+    };
+
+    Ok(DecodedTransaction {
+        signers_count: signers_count as u32,
+        root_signer,
+        payload,
+    })
+}
+
+/// Returns parameters of the entry (`public(script)`) function.
+fn entry_function_params<'a>(
+    module: &'a CompiledModule,
+    name: &IdentStr,
+) -> Result<&'a [SignatureToken]> {
+    let def = module
+        .function_defs()
+        .iter()
+        .find(|def| module.identifier_at(module.function_handle_at(def.function).name) == name)
+        .ok_or_else(|| anyhow!("Function {} not found in module", name))?;
+    ensure!(
+        def.visibility == Visibility::Script,
+        "Function {} is not an entry function",
+        name
+    );
+
+    let handle = module.function_handle_at(def.function);
+    Ok(&module.signature_at(handle.parameters).0)
+}
+
+/// Decodes BCS arguments, signer parameters are provided by the chain and skipped.
+fn decode_args(
+    params: &[SignatureToken],
+    type_args: &[TypeTag],
+    args: &[Vec<u8>],
+) -> Result<Vec<Value>> {
+    let params: Vec<_> = params.iter().filter(|param| !is_signer(param)).collect();
+    ensure!(
+        params.len() == args.len(),
+        "Expected {} arguments, got {}",
+        params.len(),
+        args.len()
+    );
+
+    params
+        .into_iter()
+        .zip(args)
+        .map(|(param, arg)| {
+            let tag = resolve_type(param, type_args)?;
+            let mut bytes = arg.as_slice();
+            let value = decode_value(&tag, &mut bytes)?;
+            ensure!(
+                bytes.is_empty(),
+                "Trailing bytes in argument of type {}",
+                type_tag_string(&tag)
+            );
+            Ok(value)
+        })
+        .collect()
+}
+
+fn is_signer(token: &SignatureToken) -> bool {
+    match token {
+        SignatureToken::Signer => true,
+        SignatureToken::Reference(inner) => matches!(**inner, SignatureToken::Signer),
+        _ => false,
+    }
+}
+
+/// Substitutes type parameters, only types allowed as transaction arguments are supported.
+fn resolve_type(token: &SignatureToken, type_args: &[TypeTag]) -> Result<TypeTag> {
+    Ok(match token {
+        SignatureToken::Bool => TypeTag::Bool,
+        SignatureToken::U8 => TypeTag::U8,
+        SignatureToken::U64 => TypeTag::U64,
+        SignatureToken::U128 => TypeTag::U128,
+        SignatureToken::Address => TypeTag::Address,
+        SignatureToken::Vector(inner) => {
+            TypeTag::Vector(Box::new(resolve_type(inner, type_args)?))
+        }
+        SignatureToken::TypeParameter(idx) => type_args
+            .get(*idx as usize)
+            .cloned()
+            .ok_or_else(|| anyhow!("Missing type argument T{}", idx))?,
+        token => bail!("Unsupported argument type {:?}", token),
+    })
+}
+
+/// Decodes BCS value to JSON, following Aptos: integers wider than 32 bits
+/// and `vector<u8>` are rendered as strings.
+pub(crate) fn decode_value(tag: &TypeTag, bytes: &mut &[u8]) -> Result<Value> {
+    Ok(match tag {
+        TypeTag::Bool => match take(bytes, 1)?[0] {
+            0 => Value::Bool(false),
+            1 => Value::Bool(true),
+            b => bail!("Invalid bool value {}", b),
+        },
+        TypeTag::U8 => Value::from(take(bytes, 1)?[0]),
+        TypeTag::U64 => {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(take(bytes, 8)?);
+            Value::String(u64::from_le_bytes(buf).to_string())
+        }
+        TypeTag::U128 => {
+            let mut buf = [0; 16];
+            buf.copy_from_slice(take(bytes, 16)?);
+            Value::String(u128::from_le_bytes(buf).to_string())
+        }
+        TypeTag::Address => {
+            let address = AccountAddress::from_bytes(take(bytes, AccountAddress::LENGTH)?)
+                .map_err(|err| anyhow!("Invalid address: {:?}", err))?;
+            Value::String(address_literal(&address))
+        }
+        TypeTag::Vector(inner) => {
+            let len = read_uleb128(bytes)?;
+            if **inner == TypeTag::U8 {
+                let data = take(bytes, len)?.to_vec();
+                serde_json::to_value(Bytes(data))?
+            } else {
+                let values = (0..len)
+                    .map(|_| decode_value(inner, bytes))
+                    .collect::<Result<_>>()?;
+                Value::Array(values)
+            }
+        }
+        tag => bail!("Unsupported argument type {}", type_tag_string(tag)),
+    })
+}
+
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    ensure!(bytes.len() >= len, "Unexpected end of argument");
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+pub(crate) fn read_uleb128(bytes: &mut &[u8]) -> Result<usize> {
+    let mut value: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = take(bytes, 1)?[0];
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value as usize);
+        }
+    }
+    bail!("Invalid ULEB128 length")
+}
@@ -0,0 +1,128 @@
+//! Errors of the `mvm_*` methods.
+//!
+//! Server errors use codes of the `-32000..-32099` range reserved by JSON-RPC for
+//! implementation-defined server errors, bad params are reported with the standard
+//! `-32602` (invalid params) code. `data` of every error is a JSON object with the error
+//! `kind` (snake case name of the code) and human-readable `details`, errors of the VM also
+//! carry the dispatch `error` as JSON:
+//!
+//! ```json
+//! {
+//!     "kind": "vm_error",
+//!     "details": "Module(ModuleError { index: 70, error: 3, message: Some(\"LinkerError\") })",
+//!     "error": { "Module": { "index": 70, "error": 3, "message": "LinkerError" } }
+//! }
+//! ```
+use std::fmt::{Debug, Display};
+use jsonrpc_core::{Error as RpcError, ErrorCode};
+use serde_json::{json, Value};
+use sp_runtime::DispatchError;
+
+/// Runtime API call or client request failed.
+pub const RUNTIME_API_ERROR: i64 = -32000;
+/// Module or script is rejected by the Move VM: dispatch error of the execution.
+pub const VM_ERROR: i64 = -32001;
+/// Requested block or entry doesn't exist.
+pub const NOT_FOUND: i64 = -32002;
+/// Bytecode, write-set or runtime result can't be decoded.
+pub const DECODE_ERROR: i64 = -32003;
+/// Method requires newer runtime API version than the runtime at the block implements.
+pub const UNSUPPORTED_BY_RUNTIME: i64 = -32004;
+/// Method depends on the node feature which is turned off.
+pub const UNAVAILABLE: i64 = -32005;
+/// Request isn't finished in time.
+pub const TIMEOUT: i64 = -32006;
+/// State of the requested block is discarded by the node, see `block::earliest_available`.
+pub const STATE_PRUNED: i64 = -32007;
+/// Call exceeds the budget of expensive methods, see `rate_limit::RateLimiter`.
+pub const RATE_LIMITED: i64 = -32008;
+
+/// Error of the client reading state discarded by the state pruning.
+const STATE_DISCARDED: &str = "State already discarded";
+
+/// Runtime API call or client request failed.
+pub fn runtime_api_error(message: &str, e: impl Debug) -> RpcError {
+    let details = format!("{:?}", e);
+    if details.contains(STATE_DISCARDED) {
+        return state_pruned("State of the block is pruned", details);
+    }
+    server_error(RUNTIME_API_ERROR, "runtime_api_error", message, details)
+}
+
+/// Module or script is rejected by the Move VM.
+pub fn vm_error(message: &str, e: &DispatchError) -> RpcError {
+    let mut data = data("vm_error", format!("{:?}", e));
+    if let Ok(e) = serde_json::to_value(e) {
+        data["error"] = e;
+    }
+    RpcError {
+        code: ErrorCode::ServerError(VM_ERROR),
+        message: message.into(),
+        data: Some(data),
+    }
+}
+
+/// Error returned by the runtime method as UTF-8 bytes.
+pub fn method_error(message: &str, e: &[u8]) -> RpcError {
+    let details = std::str::from_utf8(e).unwrap_or("can't decode error");
+    server_error(VM_ERROR, "vm_error", message, details)
+}
+
+/// Requested block or entry doesn't exist.
+pub fn not_found(message: &str, details: impl Display) -> RpcError {
+    server_error(NOT_FOUND, "not_found", message, details)
+}
+
+/// Input or result can't be decoded.
+pub fn decode_error(message: &str, e: impl Display) -> RpcError {
+    server_error(DECODE_ERROR, "decode_error", message, e)
+}
+
+/// Method isn't implemented by the runtime at the block.
+pub fn unsupported_by_runtime(message: &str, details: impl Display) -> RpcError {
+    server_error(UNSUPPORTED_BY_RUNTIME, "unsupported_by_runtime", message, details)
+}
+
+/// Method depends on the node feature which is turned off.
+pub fn unavailable(message: &str, details: impl Display) -> RpcError {
+    server_error(UNAVAILABLE, "unavailable", message, details)
+}
+
+/// Request isn't finished in time.
+pub fn timeout(message: &str, details: impl Display) -> RpcError {
+    server_error(TIMEOUT, "timeout", message, details)
+}
+
+/// State of the requested block is discarded by the node.
+pub fn state_pruned(message: &str, details: impl Display) -> RpcError {
+    server_error(STATE_PRUNED, "state_pruned", message, details)
+}
+
+/// Call exceeds the budget of expensive methods.
+pub fn rate_limited(message: &str, details: impl Display) -> RpcError {
+    server_error(RATE_LIMITED, "rate_limited", message, details)
+}
+
+/// Request params are invalid.
+pub fn bad_params(message: &str, details: impl Display) -> RpcError {
+    RpcError {
+        code: ErrorCode::InvalidParams,
+        message: message.into(),
+        data: Some(data("bad_params", details)),
+    }
+}
+
+fn server_error(code: i64, kind: &str, message: &str, details: impl Display) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(code),
+        message: message.into(),
+        data: Some(data(kind, details)),
+    }
+}
+
+fn data(kind: &str, details: impl Display) -> Value {
+    json!({
+        "kind": kind,
+        "details": details.to_string(),
+    })
+}
@@ -0,0 +1,112 @@
+//! Gas unit price oracle for `mvm_gasPrice`.
+//!
+//! The node records gas unit prices of the Move extrinsics of every new best block into a
+//! rolling window of the last blocks, as `eth_feeHistory` does, and the RPC suggests prices at
+//! the requested percentiles of the window. Blocks replaced by a reorg are overwritten by the
+//! new best ones. Wallets pick a percentile by the urgency of the transaction: extrinsics
+//! paying more per gas unit have higher transaction pool priority.
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use serde::{Serialize, Deserialize};
+use jsonrpc_core::Result;
+use crate::error;
+
+/// Default number of blocks in the window.
+pub const DEFAULT_WINDOW: usize = 20;
+/// Percentiles suggested if none are requested.
+pub const DEFAULT_PERCENTILES: [u8; 3] = [25, 50, 75];
+/// Maximum number of requested percentiles.
+pub const MAX_PERCENTILES: usize = 16;
+
+/// Suggested gas unit prices.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GasPrice {
+    /// Oldest block of the window, `None` if no blocks are recorded yet.
+    pub oldest_block: Option<u64>,
+    /// Newest block of the window.
+    pub newest_block: Option<u64>,
+    /// Number of Move extrinsics in the window.
+    pub transactions: u32,
+    /// Requested percentiles.
+    pub percentiles: Vec<u8>,
+    /// Gas unit prices at the percentiles, zero if there are no Move extrinsics in the window.
+    pub prices: Vec<u64>,
+}
+
+/// Gas unit prices of the last blocks, shared by the node worker and RPC servers.
+#[derive(Clone)]
+pub struct GasPriceOracle {
+    window: usize,
+    blocks: Arc<Mutex<VecDeque<(u64, Vec<u64>)>>>,
+}
+
+impl Default for GasPriceOracle {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl GasPriceOracle {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            blocks: Default::default(),
+        }
+    }
+
+    /// Records gas unit prices of the Move extrinsics of the new best block, blocks of the
+    /// same or higher number (replaced by a reorg) are dropped.
+    pub fn record_block(&self, number: u64, prices: Vec<u64>) {
+        let mut blocks = self.lock();
+        while matches!(blocks.back(), Some((last, _)) if *last >= number) {
+            blocks.pop_back();
+        }
+        blocks.push_back((number, prices));
+        while blocks.len() > self.window {
+            blocks.pop_front();
+        }
+    }
+
+    /// Gas unit prices at the percentiles (0..=100) of the window, nearest-rank.
+    pub fn suggest(&self, percentiles: Option<Vec<u8>>) -> Result<GasPrice> {
+        let percentiles = percentiles.unwrap_or_else(|| DEFAULT_PERCENTILES.to_vec());
+        if percentiles.len() > MAX_PERCENTILES || percentiles.iter().any(|p| *p > 100) {
+            return Err(error::bad_params(
+                "Invalid percentiles",
+                format!("At most {} percentiles of 0..=100", MAX_PERCENTILES),
+            ));
+        }
+
+        let blocks = self.lock();
+        let mut prices: Vec<u64> = blocks
+            .iter()
+            .flat_map(|(_, prices)| prices.iter().copied())
+            .collect();
+        prices.sort_unstable();
+
+        Ok(GasPrice {
+            oldest_block: blocks.front().map(|(number, _)| *number),
+            newest_block: blocks.back().map(|(number, _)| *number),
+            transactions: prices.len() as u32,
+            prices: percentiles
+                .iter()
+                .map(|p| percentile(&prices, *p))
+                .collect(),
+            percentiles,
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<VecDeque<(u64, Vec<u64>)>> {
+        // Window is never left inconsistent by the panicking thread.
+        self.blocks.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Nearest-rank percentile of the sorted prices, zero for no prices.
+fn percentile(sorted: &[u64], percentile: u8) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (percentile as usize * sorted.len() + 99) / 100;
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
@@ -0,0 +1,446 @@
+//! GraphQL API of Move state, enabled with the `graphql` feature.
+//!
+//! Typed schema of modules (with ABI), resources (with decoded fields) and Move events,
+//! with filtering by type and pagination, for explorers preferring GraphQL to batched JSON-RPC.
+//! State is read at the best block or at the requested block number. Events are read from the
+//! offchain index, so they are served by nodes with offchain indexing within events retention.
+use std::convert::Infallible;
+use std::marker::PhantomData;
+use std::net::SocketAddr;
+use std::ops::Range;
+use std::sync::Arc;
+use anyhow::anyhow;
+use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Json, Object, OutputType, Schema, SimpleObject,
+};
+use codec::{Codec, Decode};
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde_json::Value;
+use sc_client_api::{Backend, StorageProvider};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::hexdisplay::HexDisplay;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, SaturatedConversion},
+};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use sp_mvm::event::{offchain::event_key, offchain::events_count_key, MoveEventRecord};
+use sp_mvm_rpc_runtime::MVMApiRuntime;
+use crate::abi::{type_tag_string, MoveModule, MoveModuleBytecode};
+use crate::changes::AccountKey;
+use crate::resource::decode_resource;
+use crate::state::account_entries;
+use crate::OffchainGet;
+
+/// Default and maximum amount of items on the page.
+pub const MAX_PAGE_SIZE: usize = 100;
+/// Maximum amount of blocks scanned for events by a query.
+pub const MAX_EVENT_BLOCKS: u64 = 1000;
+
+/// Move state served by the schema.
+pub trait MoveState: Send + Sync {
+    /// Number of the best block.
+    fn best_block(&self) -> anyhow::Result<u64>;
+
+    /// Resources and modules published under `address` at the block, with their values.
+    fn account_entries(
+        &self,
+        address: &AccountAddress,
+        block: u64,
+    ) -> anyhow::Result<Vec<(AccountKey, Vec<u8>)>>;
+
+    /// Module bytecode at the block.
+    fn module(&self, id: &ModuleId, block: u64) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// Move events emitted in the block.
+    fn events(&self, block: u64) -> anyhow::Result<Vec<MoveEventRecord>>;
+}
+
+/// Move state of the node client.
+pub struct ClientMoveState<C, B, Block, AccountId> {
+    client: Arc<C>,
+    // Offchain storage with indexed events, if offchain indexing is enabled.
+    offchain: Option<Box<OffchainGet>>,
+    _marker: PhantomData<fn() -> (B, Block, AccountId)>,
+}
+
+impl<C, B, Block, AccountId> ClientMoveState<C, B, Block, AccountId> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            offchain: None,
+            _marker: Default::default(),
+        }
+    }
+
+    // Serve events from the node offchain storage.
+    pub fn with_offchain_storage<S>(mut self, storage: S) -> Self
+    where
+        S: OffchainStorage + 'static,
+    {
+        self.offchain = Some(Box::new(move |key: &[u8]| storage.get(STORAGE_PREFIX, key)));
+        self
+    }
+}
+
+impl<C, B, Block, AccountId> ClientMoveState<C, B, Block, AccountId>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    fn at(&self, block: u64) -> anyhow::Result<BlockId<Block>> {
+        let hash = self
+            .client
+            .hash(block.saturated_into())?
+            .ok_or_else(|| anyhow!("Block {} not found", block))?;
+        Ok(BlockId::hash(hash))
+    }
+}
+
+impl<C, B, Block, AccountId> MoveState for ClientMoveState<C, B, Block, AccountId>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    B: Backend<Block>,
+    C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, B>,
+    C: Send + Sync,
+    C::Api: MVMApiRuntime<Block, AccountId>,
+{
+    fn best_block(&self) -> anyhow::Result<u64> {
+        Ok(self.client.info().best_number.saturated_into())
+    }
+
+    fn account_entries(
+        &self,
+        address: &AccountAddress,
+        block: u64,
+    ) -> anyhow::Result<Vec<(AccountKey, Vec<u8>)>> {
+        let at = self.at(block)?;
+        let prefix = self
+            .client
+            .runtime_api()
+            .get_vm_storage_prefix(&at)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        Ok(account_entries(&*self.client, &at, &prefix, address)?)
+    }
+
+    fn module(&self, id: &ModuleId, block: u64) -> anyhow::Result<Option<Vec<u8>>> {
+        self.client
+            .runtime_api()
+            .get_module(&self.at(block)?, bcs::to_bytes(id)?)
+            .map_err(|e| anyhow!("{:?}", e))?
+            .map_err(|e| anyhow!("{}", String::from_utf8_lossy(&e)))
+    }
+
+    fn events(&self, block: u64) -> anyhow::Result<Vec<MoveEventRecord>> {
+        let get = self.offchain.as_ref().ok_or_else(|| {
+            anyhow!("Offchain storage is not available, enable offchain indexing on the node")
+        })?;
+
+        let count = get(&events_count_key(block))
+            .and_then(|count| u32::decode(&mut &count[..]).ok())
+            .unwrap_or(0);
+        (0..count)
+            .filter_map(|index| get(&event_key(block, index)))
+            .map(|event| Ok(MoveEventRecord::decode(&mut &event[..])?))
+            .collect()
+    }
+}
+
+/// Published module.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct Module {
+    pub address: String,
+    pub name: String,
+    /// Hex encoded bytecode.
+    pub bytecode: String,
+    pub abi: MoveModule,
+}
+
+impl Module {
+    fn new(bytecode: Vec<u8>) -> anyhow::Result<Self> {
+        let module = MoveModuleBytecode::new(bytecode).try_parse_abi()?;
+        let abi = module.abi.ok_or_else(|| anyhow!("Module ABI is missing"))?;
+        Ok(Module {
+            address: abi.address.clone(),
+            name: abi.name.clone(),
+            bytecode: hex(&module.bytecode.0),
+            abi,
+        })
+    }
+}
+
+/// Resource with decoded fields.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct Resource {
+    /// Struct tag, e.g. `0x1::Diem::Balance<0x1::NOX::NOX>`.
+    #[graphql(name = "type")]
+    pub typ: String,
+    pub data: Json<Value>,
+}
+
+/// Move event.
+#[derive(Clone, Debug, SimpleObject)]
+pub struct Event {
+    /// Number of the block emitting the event.
+    pub block: u64,
+    /// Hex encoded event handle GUID.
+    pub guid: String,
+    /// Type of the message.
+    #[graphql(name = "type")]
+    pub typ: String,
+    /// Hex encoded BCS message.
+    pub message: String,
+}
+
+/// Page of the filtered items.
+#[derive(Clone, Debug, SimpleObject)]
+#[graphql(concrete(name = "ModulePage", params(Module)))]
+#[graphql(concrete(name = "ResourcePage", params(Resource)))]
+#[graphql(concrete(name = "EventPage", params(Event)))]
+pub struct Page<T: OutputType> {
+    /// Amount of the items matching the filter.
+    pub total_count: usize,
+    pub items: Vec<T>,
+}
+
+/// Range of the items on the page, the page is at most `MAX_PAGE_SIZE` items.
+pub fn page_range(
+    total_count: usize,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Range<usize> {
+    let start = offset.unwrap_or(0).min(total_count);
+    let limit = limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    start..total_count.min(start + limit)
+}
+
+fn paginate<T: OutputType>(
+    mut items: Vec<T>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Page<T> {
+    let total_count = items.len();
+    let range = page_range(total_count, offset, limit);
+    items.truncate(range.end);
+    Page {
+        total_count,
+        items: items.split_off(range.start),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    format!("0x{}", HexDisplay::from(&bytes))
+}
+
+fn parse_address(address: &str) -> anyhow::Result<AccountAddress> {
+    AccountAddress::from_hex_literal(address).map_err(|_| anyhow!("Invalid address {}", address))
+}
+
+fn block_or_best(state: &dyn MoveState, block: Option<u64>) -> anyhow::Result<u64> {
+    block.map(Ok).unwrap_or_else(|| state.best_block())
+}
+
+fn has_prefix(value: &str, prefix: &Option<String>) -> bool {
+    prefix.as_ref().map_or(true, |prefix| value.starts_with(prefix.as_str()))
+}
+
+/// Queries of the schema.
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// Number of the best block.
+    async fn best_block(&self, ctx: &Context<'_>) -> async_graphql::Result<u64> {
+        Ok(ctx.data::<Arc<dyn MoveState>>()?.best_block()?)
+    }
+
+    /// Module published by the account.
+    async fn module(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        name: String,
+        block: Option<u64>,
+    ) -> async_graphql::Result<Option<Module>> {
+        let state = ctx.data::<Arc<dyn MoveState>>()?;
+        let block = block_or_best(state.as_ref(), block)?;
+        let id = ModuleId::new(parse_address(&address)?, Identifier::new(name)?);
+        Ok(state.module(&id, block)?.map(Module::new).transpose()?)
+    }
+
+    /// Modules published by the account, filtered by name prefix.
+    async fn modules(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        name_prefix: Option<String>,
+        block: Option<u64>,
+        offset: Option<usize>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Page<Module>> {
+        let state = ctx.data::<Arc<dyn MoveState>>()?;
+        let block = block_or_best(state.as_ref(), block)?;
+
+        let mut modules = vec![];
+        for (key, value) in state.account_entries(&parse_address(&address)?, block)? {
+            if let AccountKey::Module(id) = key {
+                if has_prefix(id.name().as_str(), &name_prefix) {
+                    modules.push(Module::new(value)?);
+                }
+            }
+        }
+        modules.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(paginate(modules, offset, limit))
+    }
+
+    /// Resources of the account, filtered by type prefix, e.g. `0x1::Diem::Balance`.
+    async fn resources(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+        type_prefix: Option<String>,
+        block: Option<u64>,
+        offset: Option<usize>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Page<Resource>> {
+        let state = ctx.data::<Arc<dyn MoveState>>()?;
+        let block = block_or_best(state.as_ref(), block)?;
+
+        let mut resources = vec![];
+        for (key, value) in state.account_entries(&parse_address(&address)?, block)? {
+            if let AccountKey::Resource(tag) = key {
+                let typ = type_tag_string(&TypeTag::Struct(tag.clone()));
+                if !has_prefix(&typ, &type_prefix) {
+                    continue;
+                }
+                let data = decode_resource(&tag, &value, |id| {
+                    state
+                        .module(id, block)?
+                        .ok_or_else(|| anyhow!("Module {} not found", id))
+                })?;
+                resources.push(Resource {
+                    typ,
+                    data: Json(data),
+                });
+            }
+        }
+        resources.sort_by(|a, b| a.typ.cmp(&b.typ));
+        Ok(paginate(resources, offset, limit))
+    }
+
+    /// Events emitted in the blocks `from_block..=to_block` (the best block by default),
+    /// filtered by type prefix. At most `MAX_EVENT_BLOCKS` blocks are scanned.
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        from_block: u64,
+        to_block: Option<u64>,
+        type_prefix: Option<String>,
+        offset: Option<usize>,
+        limit: Option<usize>,
+    ) -> async_graphql::Result<Page<Event>> {
+        let state = ctx.data::<Arc<dyn MoveState>>()?;
+        let to_block = block_or_best(state.as_ref(), to_block)?;
+        if to_block.saturating_sub(from_block) >= MAX_EVENT_BLOCKS {
+            return Err(format!("At most {} blocks could be scanned", MAX_EVENT_BLOCKS).into());
+        }
+
+        let mut events = vec![];
+        for block in from_block..=to_block {
+            for event in state.events(block)? {
+                let typ = String::from_utf8_lossy(&event.ty_tag).into_owned();
+                if has_prefix(&typ, &type_prefix) {
+                    events.push(Event {
+                        block,
+                        guid: hex(&event.guid),
+                        typ,
+                        message: hex(&event.message),
+                    });
+                }
+            }
+        }
+        Ok(paginate(events, offset, limit))
+    }
+}
+
+/// GraphQL schema of Move state.
+pub type MoveSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// Builds the schema serving `state`.
+pub fn schema(state: Arc<dyn MoveState>) -> MoveSchema {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(state)
+        .finish()
+}
+
+async fn respond(schema: MoveSchema, request: Request<Body>) -> Response<Body> {
+    let method = request.method().clone();
+    let (status, content_type, body) = match method {
+        // Playground for exploring the schema in the browser.
+        Method::GET => (
+            StatusCode::OK,
+            "text/html",
+            playground_source(GraphQLPlaygroundConfig::new("/")).into_bytes(),
+        ),
+        Method::POST => match hyper::body::to_bytes(request.into_body()).await {
+            Ok(body) => match serde_json::from_slice::<async_graphql::Request>(&body) {
+                Ok(query) => {
+                    let response = schema.execute(query).await;
+                    let body = serde_json::to_vec(&response).unwrap_or_default();
+                    (StatusCode::OK, "application/json", body)
+                }
+                Err(e) => (
+                    StatusCode::BAD_REQUEST,
+                    "text/plain",
+                    format!("Invalid request: {}", e).into_bytes(),
+                ),
+            },
+            Err(e) => (StatusCode::BAD_REQUEST, "text/plain", e.to_string().into_bytes()),
+        },
+        _ => (
+            StatusCode::METHOD_NOT_ALLOWED,
+            "text/plain",
+            b"Use GET for playground or POST for queries".to_vec(),
+        ),
+    };
+
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response
+}
+
+/// Serves the schema on `addr` until the server fails.
+pub async fn serve(addr: SocketAddr, schema: MoveSchema) {
+    let make_service = make_service_fn(move |_| {
+        let schema = schema.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let schema = schema.clone();
+                async move { Ok::<_, Infallible>(respond(schema, request).await) }
+            }))
+        }
+    });
+
+    let server = match Server::try_bind(&addr) {
+        Ok(builder) => builder.serve(make_service),
+        Err(e) => {
+            tracing::error!("Failed to start Move GraphQL API on {}: {}", addr, e);
+            return;
+        }
+    };
+    tracing::info!("Move GraphQL API listening on http://{}", addr);
+    if let Err(e) = server.await {
+        tracing::error!("Move GraphQL API server error: {}", e);
+    }
+}
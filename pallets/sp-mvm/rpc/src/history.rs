@@ -0,0 +1,56 @@
+//! Resource history for `mvm_getResourceHistory`.
+//!
+//! The node keeps no index of resource changes, so the resource is requested at every block of
+//! the range and compared with its value at the previous block. Ranges are bounded by
+//! [`RequestLimits::max_history_blocks`](crate::limits::RequestLimits), blocks are requested
+//! lazily, so the scan stops once `limit` changes are found.
+use jsonrpc_core::Result;
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use fc_rpc_core::types::Bytes;
+
+/// Maximum (and default) number of changes returned at once.
+pub const MAX_HISTORY_CHANGES: u32 = 1_000;
+
+/// Value of the resource set by the block.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResourceChange<BlockHash> {
+    pub block_number: u64,
+    pub block_hash: BlockHash,
+    /// New value (BCS encoded struct), `None` if removed.
+    pub value: Option<Bytes>,
+    /// Value decoded to JSON, if requested.
+    pub decoded: Option<Value>,
+}
+
+/// Number of changes to return: requested `limit` capped by [`MAX_HISTORY_CHANGES`].
+pub fn changes_limit(limit: Option<u32>) -> usize {
+    limit.map_or(MAX_HISTORY_CHANGES, |limit| limit.min(MAX_HISTORY_CHANGES)) as usize
+}
+
+/// Blocks of `values` (number, hash and value at the block) where the value differs from the
+/// value at the previous block, `previous` is the value before the first block.
+///
+/// At most `limit` changes are collected, the rest of `values` isn't requested.
+pub fn collect_changes<H, V, I>(
+    mut previous: Option<V>,
+    values: I,
+    limit: usize,
+) -> Result<Vec<(u64, H, Option<V>)>>
+where
+    V: Clone + PartialEq,
+    I: IntoIterator<Item = Result<(u64, H, Option<V>)>>,
+{
+    let mut changes = Vec::new();
+    for item in values {
+        if changes.len() >= limit {
+            break;
+        }
+        let (number, hash, value) = item?;
+        if value != previous {
+            previous = value.clone();
+            changes.push((number, hash, value));
+        }
+    }
+    Ok(changes)
+}
@@ -1,24 +1,114 @@
 use std::sync::Arc;
+use std::collections::BTreeMap;
 use std::convert::From;
-use codec::{self, Codec};
-use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use futures::future;
+use codec::{self, Codec, Decode, Encode};
+use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::PubSubMetadata;
 use sp_blockchain::HeaderBackend;
+use sc_client_api::{BlockBackend, ProofProvider};
+use sc_rpc::DenyUnsafe;
+use sp_core::bytes::to_hex;
+use sp_core::offchain::{OffchainStorage, STORAGE_PREFIX};
 use sp_runtime::{
     generic::BlockId,
-    traits::{Block as BlockT},
+    traits::{Block as BlockT, UniqueSaturatedInto},
 };
-use sp_api::ProvideRuntimeApi;
-use sp_mvm_rpc_runtime::{MVMApiRuntime, types::MVMApiEstimation};
+use sp_api::{Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_mvm_rpc_runtime::{
+    MVMApiRuntime,
+    types::{
+        MVMApiEstimation, MVMApiAllowedScript, MVMApiVmInfo, MVMApiXcmFee, MVMApiBlockGas,
+        MVMApiFee, MVMApiPackage,
+    },
+};
+use sp_rpc::number::NumberOrHex;
 use frame_support::weights::Weight;
 use serde::{Serialize, Deserialize};
 use fc_rpc_core::types::Bytes;
+use substrate_prometheus_endpoint::prometheus::HistogramTimer;
+use sp_mvm::writeset::{BlockWriteSet, offchain::block_write_set_key};
+
+pub mod abi;
+pub mod abort;
+pub mod block;
+pub mod cache;
+pub mod changes;
+pub mod chunk;
+pub mod debug;
+pub mod decode;
+pub mod error;
+pub mod gas_price;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+pub mod history;
+pub mod limits;
+pub mod metrics;
+pub mod pool;
+pub mod rate_limit;
+pub mod resource;
+pub mod simulate;
+pub mod source_map;
+pub mod state;
+pub mod trace;
+pub mod transaction;
+pub mod version;
+use abi::{ModuleAbiEntry, MoveModuleBytecode, MoveScriptBytecode};
+use abort::AbortReason;
+use block::BlockNumberOrHash;
+use cache::{EstimationCache, ModuleCache};
+use chunk::ModuleChunk;
+use decode::DecodedTransaction;
+use gas_price::{GasPrice, GasPriceOracle};
+use history::ResourceChange;
+use limits::RequestLimits;
+use metrics::RpcMetrics;
+use pool::EstimationPool;
+use rate_limit::{RateLimiter, ABI_COST, ESTIMATION_COST, HISTORY_COST, TRACE_COST};
+use simulate::{PendingOutcome, Simulation, StateOverride, MAX_PENDING_EXTRINSICS};
+use trace::ExecutionTrace;
+use transaction::{Transaction, TransactionLocation};
+use version::{
+    require_api_version, API_VERSION_2, API_VERSION_3, API_VERSION_4, API_VERSION_5,
+    API_VERSION_6, API_VERSION_7, API_VERSION_8, API_VERSION_9, API_VERSION_10, API_VERSION_12,
+    API_VERSION_13, API_VERSION_14, API_VERSION_15,
+};
 
 // Estimation struct with serde.
-#[derive(Serialize, Deserialize)]
+// Fees are estimated for script execution only (by runtimes since API version 6): fees of the
+// signed `execute` extrinsic with the used gas, in the native currency and (`total_fee_in`) in
+// the requested currency. The gas tip charged by `ChargeGasPrice` for the used gas with the
+// requested gas unit price (runtimes since API version 15) is reported separately (`gas_tip`).
+// Aborted scripts report the abort code (runtimes since API version 11) and its error constant,
+// if the module is published with the source map (see `abort`).
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Estimation {
     pub gas_used: u64,
     pub status_code: u64,
+    pub abort_code: Option<u64>,
+    pub abort_reason: Option<AbortReason>,
+    pub base_fee: Option<NumberOrHex>,
+    pub length_fee: Option<NumberOrHex>,
+    pub weight_fee: Option<NumberOrHex>,
+    pub total_fee: Option<NumberOrHex>,
+    pub total_fee_in: Option<NumberOrHex>,
+    pub gas_tip: Option<NumberOrHex>,
+}
+
+impl Estimation {
+    fn with_fee(self, fee: MVMApiFee) -> Self {
+        Self {
+            base_fee: Some(fee.base_fee.into()),
+            length_fee: Some(fee.length_fee.into()),
+            weight_fee: Some(fee.weight_fee.into()),
+            total_fee: Some(fee.total_fee.into()),
+            total_fee_in: fee.total_fee_in.map(Into::into),
+            gas_tip: Some(fee.gas_tip.into()),
+            ..self
+        }
+    }
 }
 
 impl From<MVMApiEstimation> for Estimation {
@@ -26,246 +116,1906 @@ impl From<MVMApiEstimation> for Estimation {
         Self {
             gas_used: e.gas_used,
             status_code: e.status_code,
+            abort_code: e.abort_code,
+            abort_reason: None,
+            base_fee: None,
+            length_fee: None,
+            weight_fee: None,
+            total_fee: None,
+            total_fee_in: None,
+            gas_tip: None,
+        }
+    }
+}
+
+// Allowlisted script with serde.
+#[derive(Serialize, Deserialize)]
+pub struct AllowedScript {
+    pub hash: Bytes,
+    pub description: Option<String>,
+    pub proposer: Option<String>,
+}
+
+impl<AccountId: std::fmt::Display> From<MVMApiAllowedScript<AccountId>> for AllowedScript {
+    fn from(s: MVMApiAllowedScript<AccountId>) -> Self {
+        Self {
+            hash: s.hash.to_vec().into(),
+            description: s
+                .description
+                .map(|d| String::from_utf8_lossy(&d).into_owned()),
+            proposer: s.proposer.map(|p| p.to_string()),
+        }
+    }
+}
+
+// Versions and capabilities with serde.
+#[derive(Serialize, Deserialize)]
+pub struct VmInfo {
+    pub pallet_version: String,
+    pub move_vm_version: String,
+    pub language_features: Vec<String>,
+    pub stdlib_hash: Bytes,
+    pub gas_schedule_version: u32,
+    pub rpc_methods: Vec<String>,
+}
+
+impl From<MVMApiVmInfo> for VmInfo {
+    fn from(i: MVMApiVmInfo) -> Self {
+        let (major, minor, patch) = i.pallet_version;
+        Self {
+            pallet_version: format!("{}.{}.{}", major, minor, patch),
+            move_vm_version: String::from_utf8_lossy(&i.move_vm_revision).into_owned(),
+            language_features: i
+                .features
+                .iter()
+                .map(|f| String::from_utf8_lossy(f).into_owned())
+                .collect(),
+            stdlib_hash: i.stdlib_hash.to_vec().into(),
+            gas_schedule_version: i.gas_schedule_version,
+            rpc_methods: RPC_METHODS.iter().map(|m| m.to_string()).collect(),
         }
     }
 }
 
+// Move storage change with serde, `value` is `None` for removed entries.
+#[derive(Serialize, Deserialize)]
+pub struct WriteSetChange {
+    pub key: Bytes,
+    pub value: Option<Bytes>,
+}
+
+// Move event with serde.
+#[derive(Serialize, Deserialize)]
+pub struct WriteSetEvent {
+    pub guid: Bytes,
+    pub type_tag: String,
+    pub message: Bytes,
+}
+
+// Move write-set of the block with serde.
+#[derive(Serialize, Deserialize)]
+pub struct WriteSet {
+    pub block: u64,
+    pub changes: Vec<WriteSetChange>,
+    pub events: Vec<WriteSetEvent>,
+}
+
+impl From<BlockWriteSet> for WriteSet {
+    fn from(w: BlockWriteSet) -> Self {
+        Self {
+            block: w.block,
+            changes: w
+                .changes
+                .into_iter()
+                .map(|c| WriteSetChange {
+                    key: c.key.into(),
+                    value: c.value.map(Into::into),
+                })
+                .collect(),
+            events: w
+                .events
+                .into_iter()
+                .map(|e| WriteSetEvent {
+                    guid: e.guid.into(),
+                    type_tag: String::from_utf8_lossy(&e.ty_tag).into_owned(),
+                    message: e.message.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+// Dry run result with the logs captured during execution.
+#[derive(Serialize, Deserialize)]
+pub struct DebugExecution {
+    pub gas_used: u64,
+    pub status_code: u64,
+    pub abort_code: Option<u64>,
+    pub abort_reason: Option<AbortReason>,
+    pub logs: Vec<String>,
+}
+
+// Fee of the XCM transfer, `received` is the transferred amount left after the fee.
+#[derive(Serialize, Deserialize)]
+pub struct XcmTransferFee {
+    pub weight: Weight,
+    pub fee: NumberOrHex,
+    pub received: NumberOrHex,
+}
+
+impl XcmTransferFee {
+    fn new(fee: MVMApiXcmFee, amount: u128) -> Self {
+        Self {
+            weight: fee.weight,
+            fee: fee.fee.into(),
+            received: amount.saturating_sub(fee.fee).into(),
+        }
+    }
+}
+
+// Move gas used within the block, `limit` is the block gas ceiling (unlimited if not set).
+#[derive(Serialize, Deserialize)]
+pub struct BlockGasUsage {
+    pub used: u64,
+    pub limit: Option<u64>,
+}
+
+impl From<MVMApiBlockGas> for BlockGasUsage {
+    fn from(usage: MVMApiBlockGas) -> Self {
+        Self {
+            used: usage.used,
+            limit: usage.limit,
+        }
+    }
+}
+
+// Resource with the storage proof of its trie key at the block.
+// `value` is the resource (BCS encoded struct), the trie value is its SCALE encoding.
+#[derive(Serialize, Deserialize)]
+pub struct ResourceWithProof<BlockHash> {
+    pub at: BlockHash,
+    pub key: Bytes,
+    pub value: Option<Bytes>,
+    pub proof: Vec<Bytes>,
+}
+
+// Ledger info polled by Aptos SDKs on startup: the best block, the finalized block number,
+// chain id of ed25519 signed transactions and the stdlib hash as its version.
+#[derive(Serialize, Deserialize)]
+pub struct LedgerInfo<BlockHash> {
+    pub chain_id: u8,
+    pub block_height: u64,
+    pub block_hash: BlockHash,
+    pub finalized_height: u64,
+    pub stdlib_version: Bytes,
+}
+
+// `execute` call of the transaction and the unsigned extrinsic with it, both SCALE encoded.
+// The call is signed with the signed extensions of the chain, the unsigned extrinsic is
+// for the tooling decoding extrinsics without the runtime metadata.
+#[derive(Serialize, Deserialize)]
+pub struct EncodedSubmission {
+    pub call: Bytes,
+    pub unsigned_extrinsic: Bytes,
+}
+
+// Dependency of the package with serde.
+#[derive(Serialize, Deserialize)]
+pub struct PackageDependency {
+    pub account: String,
+    pub name: String,
+}
+
+// Module of the package with its bytecode, `None` if the module is removed.
+#[derive(Serialize, Deserialize)]
+pub struct PackageModule {
+    pub name: String,
+    pub bytecode: Option<Bytes>,
+}
+
+// Package metadata with serde, as Aptos `PackageMetadata` with the bytecode of its modules.
+#[derive(Serialize, Deserialize)]
+pub struct Package {
+    pub name: String,
+    pub version: String,
+    pub upgrade_number: u64,
+    pub dependencies: Vec<PackageDependency>,
+    pub source_digest: Bytes,
+    pub modules: Vec<PackageModule>,
+}
+
+// Methods served by MVM RPC, keep in sync with the trait below.
+pub const RPC_METHODS: &[&str] = &[
+    "mvm_gasToWeight",
+    "mvm_weightToGas",
+    "mvm_estimateGasPublish",
+    "mvm_estimateGasPublishPackage",
+    "mvm_estimateGasExecute",
+    "mvm_getResource",
+    "mvm_getResourceWithProof",
+    "mvm_getResourceHistory",
+    "mvm_storageKeyFor",
+    "mvm_moduleStorageKeyFor",
+    "mvm_getModuleABI",
+    "mvm_getModulesABIs",
+    "mvm_getScriptABI",
+    "mvm_decodeTransaction",
+    "mvm_getModule",
+    "mvm_getModuleChunk",
+    "mvm_getSourceMap",
+    "mvm_getPackage",
+    "mvm_getAllowedScripts",
+    "mvm_info",
+    "mvm_getBlockWriteSet",
+    "mvm_traceExecute",
+    "mvm_executeWithDebug",
+    "mvm_simulateTransaction",
+    "mvm_estimateXcmTransferFee",
+    "mvm_earliestAvailableBlock",
+    "mvm_blockGasUsage",
+    "mvm_gasPrice",
+    "mvm_getSequenceNumber",
+    "mvm_getLedgerInfo",
+    "mvm_encodeSubmissionAsExtrinsic",
+    "mvm_getTransactionByHash",
+    "mvm_resolveMoveAddress",
+    "mvm_subscribeAccountChanges",
+];
+
+// Methods denied by nodes serving safe methods only (`--rpc-methods safe`, the default for
+// external interfaces): they apply arbitrary extrinsics, return node logs or build
+// submissions bypassing the transaction pool checks.
+pub const UNSAFE_RPC_METHODS: &[&str] = &[
+    "mvm_traceExecute",
+    "mvm_executeWithDebug",
+    "mvm_simulateTransaction",
+    "mvm_encodeSubmissionAsExtrinsic",
+];
+
 // RPC calls.
+// Expensive methods take the connection metadata to charge its budget, see `rate_limit`.
 #[rpc]
 pub trait MVMApiRpc<BlockHash, AccountId> {
+    type Metadata;
+
     #[rpc(name = "mvm_gasToWeight")]
-    fn gas_to_weight(&self, gas: u64, at: Option<BlockHash>) -> Result<Weight>;
+    fn gas_to_weight(&self, gas: u64, at: Option<BlockNumberOrHash<BlockHash>>) -> Result<Weight>;
 
     #[rpc(name = "mvm_weightToGas")]
-    fn weight_to_gas(&self, weight: Weight, at: Option<BlockHash>) -> Result<u64>;
+    fn weight_to_gas(
+        &self,
+        weight: Weight,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<u64>;
 
-    #[rpc(name = "mvm_estimateGasPublish")]
+    #[rpc(meta, name = "mvm_estimateGasPublish")]
     fn estimate_gas_publish(
         &self,
+        meta: Self::Metadata,
         account: AccountId,
         module_bc: Bytes,
         gas_limit: u64,
-        at: Option<BlockHash>,
-    ) -> Result<Estimation>;
+        at: Option<BlockNumberOrHash<BlockHash>>,
+        deadline: Option<u64>,
+    ) -> BoxFuture<Result<Estimation>>;
 
-    #[rpc(name = "mvm_estimateGasExecute")]
+    #[rpc(meta, name = "mvm_estimateGasPublishPackage")]
+    fn estimate_gas_publish_package(
+        &self,
+        meta: Self::Metadata,
+        account: AccountId,
+        modules: Vec<Bytes>,
+        gas_limit: u64,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+        deadline: Option<u64>,
+    ) -> BoxFuture<Result<Estimation>>;
+
+    // Fees are estimated in the native currency and in `currency` (by ticker) if requested,
+    // the currency requires runtime API version 6. Resources are replaced by `state_overrides`
+    // before the dry run (runtime API version 12), see `simulate`. The gas tip is estimated
+    // with `gas_unit_price` (runtime API version 15).
+    #[rpc(meta, name = "mvm_estimateGasExecute")]
     fn estimate_gas_execute(
         &self,
+        meta: Self::Metadata,
         account: AccountId,
         tx_bc: Bytes,
         gas_limit: u64,
-        at: Option<BlockHash>,
-    ) -> Result<Estimation>;
+        at: Option<BlockNumberOrHash<BlockHash>>,
+        deadline: Option<u64>,
+        currency: Option<String>,
+        state_overrides: Option<Vec<StateOverride<AccountId>>>,
+        gas_unit_price: Option<u64>,
+    ) -> BoxFuture<Result<Estimation>>;
 
     #[rpc(name = "mvm_getResource")]
     fn get_resource(
         &self,
         account_id: AccountId,
         tag: Bytes,
-        at: Option<BlockHash>,
+        at: Option<BlockNumberOrHash<BlockHash>>,
     ) -> Result<Option<Bytes>>;
 
-    #[rpc(name = "mvm_getModuleABI")]
-    fn get_module_abi(&self, module_id: Bytes, at: Option<BlockHash>) -> Result<Option<Bytes>>;
+    #[rpc(name = "mvm_getResourceWithProof")]
+    fn get_resource_with_proof(
+        &self,
+        account_id: AccountId,
+        tag: Bytes,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<ResourceWithProof<BlockHash>>;
+
+    // Blocks of `from..=to` changing the resource with its new values, at most `limit` of them.
+    #[rpc(meta, name = "mvm_getResourceHistory")]
+    fn get_resource_history(
+        &self,
+        meta: Self::Metadata,
+        account_id: AccountId,
+        tag: Bytes,
+        from: u64,
+        to: u64,
+        limit: Option<u32>,
+        decode: Option<bool>,
+    ) -> Result<Vec<ResourceChange<BlockHash>>>;
+
+    #[rpc(name = "mvm_storageKeyFor")]
+    fn storage_key_for(
+        &self,
+        account_id: AccountId,
+        tag: Bytes,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Bytes>;
+
+    #[rpc(name = "mvm_moduleStorageKeyFor")]
+    fn module_storage_key_for(
+        &self,
+        module_id: Bytes,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Bytes>;
+
+    #[rpc(meta, name = "mvm_getModuleABI")]
+    fn get_module_abi(
+        &self,
+        meta: Self::Metadata,
+        module_id: Bytes,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Option<MoveModuleBytecode>>;
+
+    #[rpc(meta, name = "mvm_getModulesABIs")]
+    fn get_modules_abis(
+        &self,
+        meta: Self::Metadata,
+        module_ids: Vec<Bytes>,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<BTreeMap<String, ModuleAbiEntry>>;
+
+    #[rpc(meta, name = "mvm_getScriptABI")]
+    fn get_script_abi(
+        &self,
+        meta: Self::Metadata,
+        script_bc: Bytes,
+    ) -> Result<MoveScriptBytecode>;
+
+    #[rpc(name = "mvm_decodeTransaction")]
+    fn decode_transaction(
+        &self,
+        tx_bc: Bytes,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<DecodedTransaction>;
 
     #[rpc(name = "mvm_getModule")]
-    fn get_module(&self, module_id: Bytes, at: Option<BlockHash>) -> Result<Option<Bytes>>;
+    fn get_module(
+        &self,
+        module_id: Bytes,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Option<Bytes>>;
+
+    #[rpc(name = "mvm_getModuleChunk")]
+    fn get_module_chunk(
+        &self,
+        module_id: Bytes,
+        offset: u32,
+        len: u32,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Option<ModuleChunk>>;
+
+    #[rpc(name = "mvm_getSourceMap")]
+    fn get_source_map(
+        &self,
+        module_id: Bytes,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Option<Bytes>>;
+
+    #[rpc(name = "mvm_getPackage")]
+    fn get_package(
+        &self,
+        account: AccountId,
+        name: String,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Option<Package>>;
+
+    #[rpc(name = "mvm_getAllowedScripts")]
+    fn get_allowed_scripts(
+        &self,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Vec<AllowedScript>>;
+
+    #[rpc(name = "mvm_info")]
+    fn info(&self, at: Option<BlockNumberOrHash<BlockHash>>) -> Result<VmInfo>;
+
+    #[rpc(name = "mvm_getBlockWriteSet")]
+    fn get_block_write_set(&self, block_hash: BlockHash) -> Result<Option<WriteSet>>;
+
+    #[rpc(meta, name = "mvm_traceExecute")]
+    fn trace_execute(
+        &self,
+        meta: Self::Metadata,
+        account: AccountId,
+        tx_bc: Bytes,
+        gas_limit: u64,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<ExecutionTrace>;
+
+    #[rpc(meta, name = "mvm_executeWithDebug")]
+    fn execute_with_debug(
+        &self,
+        meta: Self::Metadata,
+        account: AccountId,
+        tx_bc: Bytes,
+        gas_limit: u64,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<DebugExecution>;
+
+    #[rpc(meta, name = "mvm_simulateTransaction")]
+    fn simulate_transaction(
+        &self,
+        meta: Self::Metadata,
+        account: AccountId,
+        tx_bc: Bytes,
+        gas_limit: u64,
+        pending: Vec<Bytes>,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+        deadline: Option<u64>,
+    ) -> Result<Simulation>;
+
+    // Fee is estimated with the trader of this chain: the destination (and the reserve chain
+    // of the currency, if the transfer goes through it) may charge differently.
+    #[rpc(name = "mvm_estimateXcmTransferFee")]
+    fn estimate_xcm_transfer_fee(
+        &self,
+        currency: String,
+        amount: NumberOrHex,
+        dest: Bytes,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<XcmTransferFee>;
+
+    // Number of the earliest block `at` param may refer to: state of older blocks is pruned.
+    #[rpc(name = "mvm_earliestAvailableBlock")]
+    fn earliest_available_block(&self) -> Result<u64>;
+
+    // Gas used by Move calls of the block, accounted against the block gas ceiling.
+    #[rpc(name = "mvm_blockGasUsage")]
+    fn block_gas_usage(&self, at: Option<BlockNumberOrHash<BlockHash>>) -> Result<BlockGasUsage>;
+
+    // Suggested gas unit prices at the percentiles (25th, 50th and 75th by default) of the Move
+    // extrinsics of the last blocks.
+    #[rpc(name = "mvm_gasPrice")]
+    fn gas_price(&self, percentiles: Option<Vec<u8>>) -> Result<GasPrice>;
+
+    // Sequence number of the Move account, expected by `execute_with_sequence`.
+    #[rpc(name = "mvm_getSequenceNumber")]
+    fn get_sequence_number(
+        &self,
+        account: AccountId,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<u64>;
+
+    // Ledger info at the best block, so Aptos SDKs can be pointed at the node with a thin shim.
+    #[rpc(name = "mvm_getLedgerInfo")]
+    fn get_ledger_info(&self) -> Result<LedgerInfo<BlockHash>>;
+
+    // Encodes `execute` call of the transaction (`dove tx` output) as the runtime at the block
+    // does, so wallets submit it without the runtime metadata. The gas unit price is set in
+    // `ChargeGasPrice` extension by the wallet signing the call.
+    #[rpc(name = "mvm_encodeSubmissionAsExtrinsic")]
+    fn encode_submission_as_extrinsic(
+        &self,
+        tx_bc: Bytes,
+        gas_limit: u64,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<EncodedSubmission>;
+
+    // Move extrinsic by hash with the decoded payload and its outcome (result, events and gas),
+    // found in the canonical chain by the index of the node.
+    #[rpc(name = "mvm_getTransactionByHash")]
+    fn get_transaction_by_hash(
+        &self,
+        tx_hash: BlockHash,
+    ) -> Result<Option<Transaction<BlockHash>>>;
+
+    // Account of the Move address (hex literal, e.g. `0x1`), if the account has made a Move call,
+    // so explorers attribute Move addresses to accounts.
+    #[rpc(name = "mvm_resolveMoveAddress")]
+    fn resolve_move_address(
+        &self,
+        move_addr: String,
+        at: Option<BlockNumberOrHash<BlockHash>>,
+    ) -> Result<Option<String>>;
 }
 
+// Reads value from the offchain storage.
+type OffchainGet = dyn Fn(&[u8]) -> Option<Vec<u8>> + Send + Sync;
+
 pub struct MVMApi<C, P> {
     client: Arc<C>,
+    // Whether unsafe methods (`UNSAFE_RPC_METHODS`) are denied.
+    deny_unsafe: DenyUnsafe,
+    // Operator configured execution deadline in milliseconds.
+    deadline: Option<u64>,
+    // Offchain storage with collected write-sets, if offchain indexing is enabled.
+    offchain: Option<Box<OffchainGet>>,
+    // Whether `mvm_executeWithDebug` is allowed, dev nodes only.
+    debug: bool,
+    // Size limits of modules and transactions.
+    limits: RequestLimits,
+    // Duration of the calls, if Prometheus is enabled on the node.
+    metrics: Option<RpcMetrics>,
+    // Workers executing gas estimations, estimations are executed inline if not set.
+    pool: Option<EstimationPool>,
+    // ABIs of the published modules by bytecode hash.
+    modules: ModuleCache,
+    // Estimations by state root and request.
+    estimations: EstimationCache<Estimation>,
+    // Amount of the last finalized blocks with the state kept, `None` on archive nodes.
+    state_pruning: Option<u32>,
+    // Budgets of the expensive methods, not limited if not set.
+    rate_limiter: Option<RateLimiter>,
+    // Gas unit prices of the last blocks, recorded by the node.
+    gas_prices: Option<GasPriceOracle>,
     _marker: std::marker::PhantomData<P>,
 }
 
 impl<C, P> MVMApi<C, P> {
-    pub fn new(client: Arc<C>) -> Self {
+    pub fn new(client: Arc<C>, deadline: Option<u64>, deny_unsafe: DenyUnsafe) -> Self {
         Self {
             client,
+            deny_unsafe,
+            deadline,
+            offchain: None,
+            debug: false,
+            limits: Default::default(),
+            metrics: None,
+            pool: None,
+            modules: Default::default(),
+            estimations: Default::default(),
+            state_pruning: None,
+            rate_limiter: None,
+            gas_prices: None,
             _marker: Default::default(),
         }
     }
+
+    // Serve write-sets from the node offchain storage.
+    pub fn with_offchain_storage<S>(mut self, storage: S) -> Self
+    where
+        S: OffchainStorage + 'static,
+    {
+        self.offchain = Some(Box::new(move |key: &[u8]| storage.get(STORAGE_PREFIX, key)));
+        self
+    }
+
+    // Allow `mvm_executeWithDebug`, it captures node logs, so enable it on dev nodes only.
+    pub fn with_debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    // Reject modules and transactions exceeding the limits before calling the runtime.
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    // Observe duration of the calls.
+    pub fn with_metrics(mut self, metrics: RpcMetrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    // Execute gas estimations on the bounded pool.
+    pub fn with_estimation_pool(mut self, pool: EstimationPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    // Serve gas unit prices recorded by the node worker.
+    pub fn with_gas_price_oracle(mut self, oracle: GasPriceOracle) -> Self {
+        self.gas_prices = Some(oracle);
+        self
+    }
+
+    // Share the module ABI cache with other RPC servers of the node.
+    pub fn with_module_cache(mut self, modules: ModuleCache) -> Self {
+        self.modules = modules;
+        self
+    }
+
+    // Share the estimation cache with other RPC servers of the node.
+    pub fn with_estimation_cache(mut self, estimations: EstimationCache<Estimation>) -> Self {
+        self.estimations = estimations;
+        self
+    }
+
+    // Report queries of blocks with pruned state, `keep` is `--state-pruning` of the node.
+    pub fn with_state_pruning(mut self, keep: Option<u32>) -> Self {
+        self.state_pruning = keep;
+        self
+    }
+
+    // Charge expensive methods against the budgets shared with other RPC servers of the node.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    // Block to call the runtime at, fails if the node has already discarded its state.
+    fn block_id<Block>(
+        &self,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<BlockId<Block>>
+    where
+        Block: BlockT,
+        C: HeaderBackend<Block>,
+    {
+        let hash = block::resolve_state::<Block, _>(&*self.client, at, self.state_pruning)?;
+        Ok(BlockId::hash(hash))
+    }
+
+    // Observes duration of the method call until the timer is dropped.
+    fn timer(&self, method: &str) -> Option<HistogramTimer> {
+        self.metrics.as_ref().map(|metrics| metrics.start_timer(method))
+    }
+
+    // Charges the call of the connection with `cost`, fails if the budget is exceeded.
+    fn charge(&self, meta: &sc_rpc::Metadata, method: &str, cost: u32) -> Result<()> {
+        match &self.rate_limiter {
+            Some(limiter) => limiter.charge(meta.session().as_ref(), method, cost),
+            None => Ok(()),
+        }
+    }
+
+    // Executes the estimation on the pool if the request passed the `checked` limits.
+    fn estimate<T, F>(
+        &self,
+        method: &'static str,
+        checked: Result<()>,
+        f: F,
+    ) -> BoxFuture<Result<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        let timer = self.timer(method);
+        let result: BoxFuture<Result<T>> = match (checked, &self.pool) {
+            (Err(e), _) => Box::pin(future::err(e)),
+            (Ok(()), Some(pool)) => pool.spawn(method, f),
+            (Ok(()), None) => Box::pin(future::ready(f())),
+        };
+        Box::pin(async move {
+            let _timer = timer;
+            result.await
+        })
+    }
+
+    // Effective deadline: the shortest of operator configured and client requested ones.
+    fn deadline(&self, requested: Option<u64>) -> Option<u64> {
+        match (self.deadline, requested) {
+            (Some(configured), Some(requested)) => Some(configured.min(requested)),
+            (configured, requested) => configured.or(requested),
+        }
+    }
+}
+
+impl<C, Block> MVMApi<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block>,
+{
+    // ABI of the module published at the block.
+    fn module_abi<AccountId>(
+        &self,
+        at: &BlockId<Block>,
+        module_id: Bytes,
+    ) -> Result<Option<MoveModuleBytecode>>
+    where
+        AccountId: Codec,
+        C::Api: MVMApiRuntime<Block, AccountId>,
+    {
+        // ABI is built from the module bytecode on the node side,
+        // so it is rendered in the same (Aptos compatible) shape for all runtime versions.
+        let api = self.client.runtime_api();
+        let f: Option<Vec<u8>> = api
+            .get_module(at, module_id.0.clone())
+            .map_err(|e| error::runtime_api_error("API error", e))?
+            .map_err(|e| error::method_error("Error from method", &e))?;
+
+        f.map(|bytecode| {
+            // Error constants are named by the source map, older runtimes have none.
+            let version = version::api_version::<Block, AccountId, _>(&*api, at).map_err(|e| {
+                error::runtime_api_error("Error during requesting Runtime API version", e)
+            })?;
+            let source_map = if version < Some(API_VERSION_10) {
+                None
+            } else {
+                api.get_source_map(at, module_id.into_vec())
+                    .map_err(|e| error::runtime_api_error("API error", e))?
+            };
+            let abi = self
+                .modules
+                .abi_with_source_map(&bytecode, source_map.as_deref())
+                .map_err(|e| error::decode_error("Error while building module ABI", e))?;
+            Ok(MoveModuleBytecode {
+                bytecode: bytecode.into(),
+                abi: Some(abi.as_ref().clone()),
+            })
+        })
+        .transpose()
+    }
+}
+
+// Key of the estimation request at the block, see `EstimationCache`.
+fn estimation_key<Block, C>(
+    client: &C,
+    hash: Block::Hash,
+    method: &str,
+    request: &[u8],
+) -> Result<[u8; 32]>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    let state_root = block::state_root::<Block, C>(client, hash)?;
+    Ok(EstimationCache::<Estimation>::key(state_root.as_ref(), method, request))
+}
+
+// Module published at the block, for the call tree of the transaction.
+fn published_module<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    module_id: &move_core_types::language_storage::ModuleId,
+) -> anyhow::Result<Vec<u8>>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: MVMApiRuntime<Block, AccountId>,
+{
+    api.get_module(at, bcs::to_bytes(module_id)?)
+        .map_err(|e| anyhow::anyhow!("API error: {}", e))?
+        .map_err(|e| anyhow::anyhow!("{}", String::from_utf8_lossy(&e)))?
+        .ok_or_else(|| anyhow::anyhow!("Module {} not found", module_id))
+}
+
+// Error constant of the abort code of the transaction dry run at the block, resolved by the
+// abort sites of its call tree. Abort codes are returned by runtimes with source maps only.
+fn abort_reason<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    tx_bc: &[u8],
+    abort_code: Option<u64>,
+) -> Option<AbortReason>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: MVMApiRuntime<Block, AccountId>,
+{
+    let abort_code = abort_code?;
+    let call_tree = trace::trace_transaction_with_source_maps(
+        tx_bc,
+        |module_id| published_module::<Block, AccountId, _>(api, at, module_id),
+        |module_id| {
+            let module_id = bcs::to_bytes(module_id).ok()?;
+            api.get_source_map(at, module_id).ok().flatten()
+        },
+    )
+    .ok()?;
+    abort::find_reason(&call_tree, abort_code)
 }
 
 impl<C, Block, AccountId> MVMApiRpc<<Block as BlockT>::Hash, AccountId> for MVMApi<C, Block>
 where
     Block: BlockT,
-    AccountId: Clone + std::fmt::Display + Codec,
-    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    AccountId: Clone + std::fmt::Display + Codec + Send + 'static,
+    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + ProofProvider<Block>,
+    C: BlockBackend<Block>,
+    C: Send + Sync,
     C::Api: MVMApiRuntime<Block, AccountId>,
+    C::Api: BlockBuilder<Block>,
 {
-    fn gas_to_weight(&self, gas: u64, at: Option<<Block as BlockT>::Hash>) -> Result<Weight> {
+    type Metadata = sc_rpc::Metadata;
+
+    fn gas_to_weight(
+        &self,
+        gas: u64,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Weight> {
+        let _timer = self.timer("mvm_gasToWeight");
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash));
+        let at = self.block_id::<Block>(at)?;
 
         let res = api.gas_to_weight(&at, gas);
 
-        res.map_err(|e| RpcError {
-            code: ErrorCode::ServerError(500),
-            message: "Error during requesting Runtime API".into(),
-            data: Some(format!("{:?}", e).into()),
-        })
+        res.map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))
     }
 
-    fn weight_to_gas(&self, weight: Weight, at: Option<<Block as BlockT>::Hash>) -> Result<u64> {
+    fn weight_to_gas(
+        &self,
+        weight: Weight,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<u64> {
+        let _timer = self.timer("mvm_weightToGas");
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash));
+        let at = self.block_id::<Block>(at)?;
 
         let res = api.weight_to_gas(&at, weight);
 
-        res.map_err(|e| RpcError {
-            code: ErrorCode::ServerError(500),
-            message: "Error during requesting Runtime API".into(),
-            data: Some(format!("{:?}", e).into()),
-        })
+        res.map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))
     }
 
     fn estimate_gas_publish(
         &self,
+        meta: Self::Metadata,
         account: AccountId,
         module_bc: Bytes,
         gas_limit: u64,
-        at: Option<<Block as BlockT>::Hash>,
-    ) -> Result<Estimation> {
-        let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash));
-
-        let res = api
-            .estimate_gas_publish(&at, account, module_bc.into_vec(), gas_limit)
-            .map_err(|e| RpcError {
-                code: ErrorCode::ServerError(500),
-                message: "Error during requesting Runtime API".into(),
-                data: Some(format!("{:?}", e).into()),
-            })?;
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+        deadline: Option<u64>,
+    ) -> BoxFuture<Result<Estimation>> {
+        let method = "mvm_estimateGasPublish";
+        let checked = self
+            .limits
+            .check_module(&module_bc)
+            .and_then(|_| self.charge(&meta, method, ESTIMATION_COST));
+        let client = self.client.clone();
+        let deadline = self.deadline(deadline);
+        let state_pruning = self.state_pruning;
+        let estimations = self.estimations.clone();
+        self.estimate(method, checked, move || {
+            let hash = block::resolve_state::<Block, _>(&*client, at, state_pruning)?;
+            let request = (&account, &module_bc.0, gas_limit, deadline).encode();
+            let key = estimation_key::<Block, _>(&*client, hash, method, &request)?;
+            estimations.get_or_estimate(key, || {
+                let api = client.runtime_api();
+                let at = BlockId::hash(hash);
 
-        let mvm_estimation = res.map_err(|e| RpcError {
-            code: ErrorCode::ServerError(500),
-            message: "Error during publishing module for estimation".into(),
-            data: Some(format!("{:?}", e).into()),
-        })?;
+                let res = version::estimate_gas_publish(
+                    &*api,
+                    &at,
+                    account,
+                    module_bc.into_vec(),
+                    gas_limit,
+                    deadline,
+                )
+                .map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))?;
+
+                let mvm_estimation = res.map_err(|e| {
+                    error::vm_error("Error during publishing module for estimation", &e)
+                })?;
+
+                Ok(Estimation::from(mvm_estimation))
+            })
+        })
+    }
+
+    fn estimate_gas_publish_package(
+        &self,
+        meta: Self::Metadata,
+        account: AccountId,
+        modules: Vec<Bytes>,
+        gas_limit: u64,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+        deadline: Option<u64>,
+    ) -> BoxFuture<Result<Estimation>> {
+        let method = "mvm_estimateGasPublishPackage";
+        let checked = self
+            .limits
+            .check_package(&modules)
+            .and_then(|_| self.charge(&meta, method, ESTIMATION_COST));
+        let client = self.client.clone();
+        let deadline = self.deadline(deadline);
+        let state_pruning = self.state_pruning;
+        let estimations = self.estimations.clone();
+        self.estimate(method, checked, move || {
+            let hash = block::resolve_state::<Block, _>(&*client, at, state_pruning)?;
+            let code: Vec<&Vec<u8>> = modules.iter().map(|module| &module.0).collect();
+            let request = (&account, code, gas_limit, deadline).encode();
+            let key = estimation_key::<Block, _>(&*client, hash, method, &request)?;
+            estimations.get_or_estimate(key, || {
+                let api = client.runtime_api();
+                let at = BlockId::hash(hash);
+                require_api_version::<Block, AccountId, _>(
+                    &*api,
+                    &at,
+                    "mvm_estimateGasPublishPackage",
+                    API_VERSION_2,
+                )?;
+
+                let res = version::estimate_gas_publish_package(
+                    &*api,
+                    &at,
+                    account,
+                    modules.into_iter().map(Bytes::into_vec).collect(),
+                    gas_limit,
+                    deadline,
+                )
+                .map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))?;
 
-        Ok(Estimation::from(mvm_estimation))
+                let mvm_estimation = res.map_err(|e| {
+                    error::vm_error("Error during publishing package for estimation", &e)
+                })?;
+
+                Ok(Estimation::from(mvm_estimation))
+            })
+        })
     }
 
     fn estimate_gas_execute(
         &self,
+        meta: Self::Metadata,
         account: AccountId,
         tx_bc: Bytes,
         gas_limit: u64,
-        at: Option<<Block as BlockT>::Hash>,
-    ) -> Result<Estimation> {
-        let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash));
-
-        let res = api
-            .estimate_gas_execute(&at, account, tx_bc.into_vec(), gas_limit)
-            .map_err(|e| RpcError {
-                code: ErrorCode::ServerError(500),
-                message: "Error during requesting Runtime API".into(),
-                data: Some(format!("{:?}", e).into()),
-            })?;
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+        deadline: Option<u64>,
+        currency: Option<String>,
+        state_overrides: Option<Vec<StateOverride<AccountId>>>,
+        gas_unit_price: Option<u64>,
+    ) -> BoxFuture<Result<Estimation>> {
+        let method = "mvm_estimateGasExecute";
+        let overrides = state_overrides.unwrap_or_default();
+        let checked = self
+            .limits
+            .check_transaction(&tx_bc)
+            .and_then(|_| simulate::check_state_overrides(&overrides))
+            .and_then(|_| self.charge(&meta, method, ESTIMATION_COST));
+        let overrides: Vec<_> = overrides.into_iter().map(StateOverride::into_parts).collect();
+        let client = self.client.clone();
+        let deadline = self.deadline(deadline);
+        let state_pruning = self.state_pruning;
+        let estimations = self.estimations.clone();
+        self.estimate(method, checked, move || {
+            let hash = block::resolve_state::<Block, _>(&*client, at, state_pruning)?;
+            let request = (
+                &account,
+                &tx_bc.0,
+                gas_limit,
+                deadline,
+                &currency,
+                &overrides,
+                gas_unit_price,
+            )
+                .encode();
+            let key = estimation_key::<Block, _>(&*client, hash, method, &request)?;
+            estimations.get_or_estimate(key, || {
+                let api = client.runtime_api();
+                let at = BlockId::hash(hash);
 
-        let mvm_estimation = res.map_err(|e| RpcError {
-            code: ErrorCode::ServerError(500),
-            message: "Error during script execution for estimation".into(),
-            data: Some(format!("{:?}", e).into()),
-        })?;
+                // Overrides are written to the state overlay shared by the calls below.
+                if !overrides.is_empty() {
+                    require_api_version::<Block, AccountId, _>(
+                        &*api,
+                        &at,
+                        "mvm_estimateGasExecute with state overrides",
+                        API_VERSION_12,
+                    )?;
+                    api.override_resources(&at, overrides)
+                        .map_err(|e| {
+                            error::runtime_api_error("Error during requesting Runtime API", e)
+                        })?
+                        .map_err(|e| {
+                            error::bad_params(
+                                "Invalid state override",
+                                String::from_utf8_lossy(&e),
+                            )
+                        })?;
+                }
+
+                let res = version::estimate_gas_execute(
+                    &*api,
+                    &at,
+                    account,
+                    tx_bc.0.clone(),
+                    gas_limit,
+                    deadline,
+                )
+                .map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))?;
+
+                let mvm_estimation = res.map_err(|e| {
+                    error::vm_error("Error during script execution for estimation", &e)
+                })?;
+                let estimation = Estimation {
+                    abort_reason: abort_reason::<Block, AccountId, _>(
+                        &*api,
+                        &at,
+                        &tx_bc.0,
+                        mvm_estimation.abort_code,
+                    ),
+                    ..Estimation::from(mvm_estimation)
+                };
 
-        Ok(Estimation::from(mvm_estimation))
+                // Older runtimes estimate gas only.
+                let version =
+                    version::api_version::<Block, AccountId, _>(&*api, &at).map_err(|e| {
+                        error::runtime_api_error("Error during requesting Runtime API version", e)
+                    })?;
+                if gas_unit_price.is_some() && version < Some(API_VERSION_15) {
+                    return Err(version::unsupported_by_runtime(
+                        "mvm_estimateGasExecute with gas unit price",
+                        version,
+                        API_VERSION_15,
+                    ));
+                }
+                if version < Some(API_VERSION_6) {
+                    return match currency {
+                        Some(_) => Err(version::unsupported_by_runtime(
+                            "mvm_estimateGasExecute with currency",
+                            version,
+                            API_VERSION_6,
+                        )),
+                        None => Ok(estimation),
+                    };
+                }
+
+                // Weight fee is refunded down to the used gas, so the fee is of the used gas.
+                let fee = version::estimate_execute_fee(
+                    &*api,
+                    &at,
+                    tx_bc.into_vec(),
+                    estimation.gas_used,
+                    gas_unit_price.unwrap_or_default(),
+                    currency.map(String::into_bytes),
+                )
+                .map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))?
+                .map_err(|e| error::method_error("Error during fee estimation", &e))?;
+
+                Ok(estimation.with_fee(fee))
+            })
+        })
     }
 
     fn get_resource(
         &self,
         account_id: AccountId,
         tag: Bytes,
-        at: Option<<Block as BlockT>::Hash>,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
     ) -> Result<Option<Bytes>> {
+        let _timer = self.timer("mvm_getResource");
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash));
+        let at = self.block_id::<Block>(at)?;
 
         let f: Option<Vec<u8>> = api
             .get_resource(&at, account_id, tag.into_vec())
-            .map_err(|e| RpcError {
-                code: ErrorCode::ServerError(500),
-                message: "ABI error".into(),
-                data: Some(e.to_string().into()),
-            })?
-            .map_err(|e| RpcError {
-                code: ErrorCode::ServerError(500),
-                message: "Error from method".into(),
-                data: Some(
-                    std::str::from_utf8(e.as_slice())
-                        .unwrap_or("can't decode error")
-                        .into(),
-                ),
-            })?;
+            .map_err(|e| error::runtime_api_error("API error", e))?
+            .map_err(|e| error::method_error("Error from method", &e))?;
         Ok(f.map(Into::into))
     }
 
+    fn get_resource_with_proof(
+        &self,
+        account_id: AccountId,
+        tag: Bytes,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<ResourceWithProof<<Block as BlockT>::Hash>> {
+        let _timer = self.timer("mvm_getResourceWithProof");
+        let api = self.client.runtime_api();
+        let hash = block::resolve_state::<Block, _>(&*self.client, at, self.state_pruning)?;
+        let at = BlockId::hash(hash);
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_getResourceWithProof",
+            API_VERSION_2,
+        )?;
+
+        let key = api
+            .get_resource_storage_key(&at, account_id.clone(), tag.0.clone())
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+        let value = api
+            .get_resource(&at, account_id, tag.into_vec())
+            .map_err(|e| error::runtime_api_error("API error", e))?
+            .map_err(|e| error::method_error("Error from method", &e))?;
+        let proof = self
+            .client
+            .read_proof(&at, &mut std::iter::once(key.as_slice()))
+            .map_err(|e| error::runtime_api_error("Error while reading storage proof", e))?;
+
+        Ok(ResourceWithProof {
+            at: hash,
+            key: key.into(),
+            value: value.map(Into::into),
+            proof: proof.into_iter_nodes().map(Into::into).collect(),
+        })
+    }
+
+    fn get_resource_history(
+        &self,
+        meta: Self::Metadata,
+        account_id: AccountId,
+        tag: Bytes,
+        from: u64,
+        to: u64,
+        limit: Option<u32>,
+        decode: Option<bool>,
+    ) -> Result<Vec<ResourceChange<<Block as BlockT>::Hash>>> {
+        let _timer = self.timer("mvm_getResourceHistory");
+        self.limits.check_history_range(from, to)?;
+        self.charge(&meta, "mvm_getResourceHistory", HISTORY_COST)?;
+        let struct_tag: Option<move_core_types::language_storage::StructTag> = match decode {
+            Some(true) => Some(
+                bcs::from_bytes(&tag.0)
+                    .map_err(|e| error::decode_error("Error while decoding struct tag", e))?,
+            ),
+            _ => None,
+        };
+
+        let api = self.client.runtime_api();
+        let resource = |number: u64| -> Result<(u64, <Block as BlockT>::Hash, Option<Vec<u8>>)> {
+            let at = Some(BlockNumberOrHash::Number(number));
+            let hash = block::resolve_state::<Block, _>(&*self.client, at, self.state_pruning)?;
+            let value = api
+                .get_resource(&BlockId::hash(hash), account_id.clone(), tag.0.clone())
+                .map_err(|e| error::runtime_api_error("API error", e))?
+                .map_err(|e| error::method_error("Error from method", &e))?;
+            Ok((number, hash, value))
+        };
+
+        // The value before the range, there are no resources before genesis.
+        let previous = match from.checked_sub(1) {
+            Some(number) => resource(number)?.2,
+            None => None,
+        };
+        let best: u64 = self.client.info().best_number.unique_saturated_into();
+        let changes = history::collect_changes(
+            previous,
+            (from..=to.min(best)).map(resource),
+            history::changes_limit(limit),
+        )?;
+
+        // Resources are decoded with the modules published at the changing block.
+        changes
+            .into_iter()
+            .map(|(block_number, block_hash, value)| {
+                let decoded = match (&struct_tag, &value) {
+                    (Some(struct_tag), Some(value)) => {
+                        let at = BlockId::hash(block_hash);
+                        let get_module =
+                            |module_id: &move_core_types::language_storage::ModuleId| {
+                                api.get_module(&at, bcs::to_bytes(module_id)?)
+                                    .map_err(|e| anyhow::anyhow!("API error: {}", e))?
+                                    .map_err(|e| {
+                                        anyhow::anyhow!("{}", String::from_utf8_lossy(&e))
+                                    })?
+                                    .ok_or_else(|| {
+                                        anyhow::anyhow!("Module {} not found", module_id)
+                                    })
+                            };
+                        let decoded = resource::decode_resource(struct_tag, value, get_module);
+                        Some(decoded.map_err(|e| {
+                            error::decode_error("Error while decoding resource", e)
+                        })?)
+                    }
+                    _ => None,
+                };
+                Ok(ResourceChange {
+                    block_number,
+                    block_hash,
+                    value: value.map(Into::into),
+                    decoded,
+                })
+            })
+            .collect()
+    }
+
+    fn storage_key_for(
+        &self,
+        account_id: AccountId,
+        tag: Bytes,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Bytes> {
+        let _timer = self.timer("mvm_storageKeyFor");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_storageKeyFor",
+            API_VERSION_2,
+        )?;
+
+        let key = api
+            .get_resource_storage_key(&at, account_id, tag.into_vec())
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+        Ok(key.into())
+    }
+
+    fn module_storage_key_for(
+        &self,
+        module_id: Bytes,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Bytes> {
+        let _timer = self.timer("mvm_moduleStorageKeyFor");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_moduleStorageKeyFor",
+            API_VERSION_2,
+        )?;
+
+        let key = api
+            .get_module_storage_key(&at, module_id.into_vec())
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+        Ok(key.into())
+    }
+
     fn get_module_abi(
         &self,
+        meta: Self::Metadata,
         module_id: Bytes,
-        at: Option<<Block as BlockT>::Hash>,
-    ) -> Result<Option<Bytes>> {
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Option<MoveModuleBytecode>> {
+        let _timer = self.timer("mvm_getModuleABI");
+        self.charge(&meta, "mvm_getModuleABI", ABI_COST)?;
+        let at = self.block_id::<Block>(at)?;
+        self.module_abi::<AccountId>(&at, module_id)
+    }
+
+    fn get_modules_abis(
+        &self,
+        meta: Self::Metadata,
+        module_ids: Vec<Bytes>,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<BTreeMap<String, ModuleAbiEntry>> {
+        let _timer = self.timer("mvm_getModulesABIs");
+        self.limits.check_module_ids(&module_ids)?;
+        let cost = ABI_COST.saturating_mul(module_ids.len() as u32);
+        self.charge(&meta, "mvm_getModulesABIs", cost)?;
+        let at = self.block_id::<Block>(at)?;
+
+        // Failure of one module doesn't fail the others, it is reported in its entry.
+        Ok(module_ids
+            .into_iter()
+            .map(|module_id| {
+                let key = to_hex(&module_id, false);
+                (key, self.module_abi::<AccountId>(&at, module_id).into())
+            })
+            .collect())
+    }
+
+    fn get_script_abi(
+        &self,
+        meta: Self::Metadata,
+        script_bc: Bytes,
+    ) -> Result<MoveScriptBytecode> {
+        let _timer = self.timer("mvm_getScriptABI");
+        self.limits.check_script(&script_bc)?;
+        self.charge(&meta, "mvm_getScriptABI", ABI_COST)?;
+
+        // Script is not stored on chain, so ABI doesn't depend on the block state.
+        MoveScriptBytecode::new(script_bc.into_vec())
+            .try_parse_abi()
+            .map_err(|e| error::decode_error("Error while building script ABI", e))
+    }
+
+    fn decode_transaction(
+        &self,
+        tx_bc: Bytes,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<DecodedTransaction> {
+        let _timer = self.timer("mvm_decodeTransaction");
+        self.limits.check_transaction(&tx_bc)?;
+
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash));
+        let at = self.block_id::<Block>(at)?;
 
-        let f: Option<Vec<u8>> = api
-            .get_module_abi(&at, module_id.into_vec())
-            .map_err(|e| RpcError {
-                code: ErrorCode::ServerError(500),
-                message: "API error".into(),
-                data: Some(e.to_string().into()),
-            })?
-            .map_err(|e| RpcError {
-                code: ErrorCode::ServerError(500),
-                message: "Error from method".into(),
-                data: Some(
-                    std::str::from_utf8(e.as_slice())
-                        .unwrap_or("can't decode error")
-                        .into(),
-                ),
-            })?;
-        Ok(f.map(Into::into))
+        // Modules are requested only to decode arguments of entry functions.
+        let get_module = |module_id: &move_core_types::language_storage::ModuleId| {
+            api
+                .get_module(&at, bcs::to_bytes(module_id)?)
+                .map_err(|e| anyhow::anyhow!("API error: {}", e))?
+                .map_err(|e| anyhow::anyhow!("{}", String::from_utf8_lossy(&e)))?
+                .ok_or_else(|| anyhow::anyhow!("Module {} not found", module_id))
+        };
+
+        decode::decode_transaction(&tx_bc.0, get_module)
+            .map_err(|e| error::decode_error("Error while decoding transaction", e))
     }
 
     fn get_module(
         &self,
         module_id: Bytes,
-        at: Option<<Block as BlockT>::Hash>,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
     ) -> Result<Option<Bytes>> {
+        let _timer = self.timer("mvm_getModule");
         let api = self.client.runtime_api();
-        let at = BlockId::hash(at.unwrap_or_else(||
-			// If the block hash is not supplied assume the best block.
-			self.client.info().best_hash));
+        let at = self.block_id::<Block>(at)?;
 
         let f: Option<Vec<u8>> = api
             .get_module(&at, module_id.into_vec())
-            .map_err(|e| RpcError {
-                code: ErrorCode::ServerError(500),
-                message: "API error.".into(),
-                data: Some(e.to_string().into()),
-            })?
-            .map_err(|e| RpcError {
-                code: ErrorCode::ServerError(500),
-                message: "Nope, error.".into(),
-                data: Some(
-                    std::str::from_utf8(e.as_slice())
-                        .unwrap_or("can't decode error")
-                        .into(),
-                ),
-            })?;
+            .map_err(|e| error::runtime_api_error("API error", e))?
+            .map_err(|e| error::method_error("Error from method", &e))?;
         Ok(f.map(Into::into))
     }
+
+    fn get_module_chunk(
+        &self,
+        module_id: Bytes,
+        offset: u32,
+        len: u32,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Option<ModuleChunk>> {
+        let _timer = self.timer("mvm_getModuleChunk");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+
+        let module: Option<Vec<u8>> = api
+            .get_module(&at, module_id.into_vec())
+            .map_err(|e| error::runtime_api_error("API error", e))?
+            .map_err(|e| error::method_error("Error from method", &e))?;
+        module
+            .map(|module| chunk::module_chunk(&module, offset, len))
+            .transpose()
+    }
+
+    fn get_source_map(
+        &self,
+        module_id: Bytes,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Option<Bytes>> {
+        let _timer = self.timer("mvm_getSourceMap");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_getSourceMap",
+            API_VERSION_10,
+        )?;
+
+        let source_map = api
+            .get_source_map(&at, module_id.into_vec())
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+        Ok(source_map.map(Into::into))
+    }
+
+    fn get_package(
+        &self,
+        account: AccountId,
+        name: String,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Option<Package>> {
+        let _timer = self.timer("mvm_getPackage");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(&*api, &at, "mvm_getPackage", API_VERSION_9)?;
+
+        let package: Option<MVMApiPackage<AccountId>> = api
+            .get_package(&at, account.clone(), name.into_bytes())
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+        let package = match package {
+            Some(package) => package,
+            None => return Ok(None),
+        };
+        let address = api
+            .get_move_address(&at, account)
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+        let address = move_core_types::account_address::AccountAddress::new(address);
+
+        let modules = package
+            .modules
+            .into_iter()
+            .map(|name| {
+                let name = String::from_utf8_lossy(&name).into_owned();
+                let module_id = move_core_types::identifier::Identifier::new(name.clone())
+                    .map(|id| move_core_types::language_storage::ModuleId::new(address, id))
+                    .map_err(|e| error::decode_error("Invalid module name", e))?;
+                let module_id = bcs::to_bytes(&module_id)
+                    .map_err(|e| error::decode_error("Error while encoding module id", e))?;
+                let bytecode = api
+                    .get_module(&at, module_id)
+                    .map_err(|e| error::runtime_api_error("API error", e))?
+                    .map_err(|e| error::method_error("Error from method", &e))?;
+                Ok(PackageModule {
+                    name,
+                    bytecode: bytecode.map(Into::into),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Some(Package {
+            name: String::from_utf8_lossy(&package.name).into_owned(),
+            version: String::from_utf8_lossy(&package.version).into_owned(),
+            upgrade_number: package.upgrade_number,
+            dependencies: package
+                .dependencies
+                .into_iter()
+                .map(|(account, name)| PackageDependency {
+                    account: account.to_string(),
+                    name: String::from_utf8_lossy(&name).into_owned(),
+                })
+                .collect(),
+            source_digest: package.source_digest.into(),
+            modules,
+        }))
+    }
+
+    fn get_allowed_scripts(
+        &self,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Vec<AllowedScript>> {
+        let _timer = self.timer("mvm_getAllowedScripts");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_getAllowedScripts",
+            API_VERSION_2,
+        )?;
+
+        let scripts = api
+            .get_allowed_scripts(&at)
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+
+        Ok(scripts.into_iter().map(AllowedScript::from).collect())
+    }
+
+    fn info(&self, at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>) -> Result<VmInfo> {
+        let _timer = self.timer("mvm_info");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(&*api, &at, "mvm_info", API_VERSION_2)?;
+
+        let info = api.get_vm_info(&at).map_err(|e| error::runtime_api_error("API error", e))?;
+
+        Ok(VmInfo::from(info))
+    }
+
+    fn get_block_write_set(
+        &self,
+        block_hash: <Block as BlockT>::Hash,
+    ) -> Result<Option<WriteSet>> {
+        let _timer = self.timer("mvm_getBlockWriteSet");
+        let get = self.offchain.as_ref().ok_or_else(|| {
+            error::unavailable(
+                "Offchain storage is not available",
+                "Enable offchain indexing and offchain workers on the node",
+            )
+        })?;
+
+        get(&block_write_set_key(block_hash.as_ref()))
+            .map(|encoded| {
+                BlockWriteSet::decode(&mut &encoded[..])
+                    .map(WriteSet::from)
+                    .map_err(|e| error::decode_error("Error while decoding write-set", e))
+            })
+            .transpose()
+    }
+
+    fn trace_execute(
+        &self,
+        meta: Self::Metadata,
+        account: AccountId,
+        tx_bc: Bytes,
+        gas_limit: u64,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<ExecutionTrace> {
+        let _timer = self.timer("mvm_traceExecute");
+        self.deny_unsafe.check_if_safe()?;
+        self.limits.check_transaction(&tx_bc)?;
+        self.charge(&meta, "mvm_traceExecute", TRACE_COST)?;
+
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+
+        let estimation = version::estimate_gas_execute(
+            &*api,
+            &at,
+            account,
+            tx_bc.0.clone(),
+            gas_limit,
+            self.deadline(None),
+        )
+        .map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))?
+        .map_err(|e| error::vm_error("Error during script execution for estimation", &e))?;
+
+        let get_module = |module_id: &move_core_types::language_storage::ModuleId| {
+            published_module::<Block, AccountId, _>(&*api, &at, module_id)
+        };
+        // Older runtimes have no source maps, abort sites aren't located then.
+        let version = version::api_version::<Block, AccountId, _>(&*api, &at).map_err(|e| {
+            error::runtime_api_error("Error during requesting Runtime API version", e)
+        })?;
+        let get_source_map = |module_id: &move_core_types::language_storage::ModuleId| {
+            if version < Some(API_VERSION_10) {
+                return None;
+            }
+            let module_id = bcs::to_bytes(module_id).ok()?;
+            api.get_source_map(&at, module_id).ok().flatten()
+        };
+        let call_tree =
+            trace::trace_transaction_with_source_maps(&tx_bc.0, get_module, get_source_map)
+                .map_err(|e| error::decode_error("Error while building call tree", e))?;
+
+        Ok(ExecutionTrace {
+            gas_used: estimation.gas_used,
+            status_code: estimation.status_code,
+            abort_code: estimation.abort_code,
+            abort_reason: estimation
+                .abort_code
+                .and_then(|abort_code| abort::find_reason(&call_tree, abort_code)),
+            call_tree,
+        })
+    }
+
+    fn execute_with_debug(
+        &self,
+        meta: Self::Metadata,
+        account: AccountId,
+        tx_bc: Bytes,
+        gas_limit: u64,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<DebugExecution> {
+        let _timer = self.timer("mvm_executeWithDebug");
+        self.deny_unsafe.check_if_safe()?;
+        if !self.debug {
+            return Err(error::unavailable(
+                "Debug execution is disabled",
+                "Run dev node with --mvm-debug-rpc",
+            ));
+        }
+        self.limits.check_transaction(&tx_bc)?;
+        self.charge(&meta, "mvm_executeWithDebug", TRACE_COST)?;
+
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+
+        let (res, logs) = debug::capture_logs(|| {
+            version::estimate_gas_execute(
+                &*api,
+                &at,
+                account,
+                tx_bc.0.clone(),
+                gas_limit,
+                self.deadline(None),
+            )
+        });
+
+        let estimation = res
+            .map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))?
+            .map_err(|e| error::vm_error("Error during script execution", &e))?;
+
+        Ok(DebugExecution {
+            gas_used: estimation.gas_used,
+            status_code: estimation.status_code,
+            abort_code: estimation.abort_code,
+            abort_reason: abort_reason::<Block, AccountId, _>(
+                &*api,
+                &at,
+                &tx_bc.0,
+                estimation.abort_code,
+            ),
+            logs,
+        })
+    }
+    fn simulate_transaction(
+        &self,
+        meta: Self::Metadata,
+        account: AccountId,
+        tx_bc: Bytes,
+        gas_limit: u64,
+        pending: Vec<Bytes>,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+        deadline: Option<u64>,
+    ) -> Result<Simulation> {
+        let _timer = self.timer("mvm_simulateTransaction");
+        self.deny_unsafe.check_if_safe()?;
+        self.limits.check_transaction(&tx_bc)?;
+        if pending.len() > MAX_PENDING_EXTRINSICS {
+            return Err(error::bad_params(
+                "Too many pending extrinsics",
+                format!("At most {} extrinsics", MAX_PENDING_EXTRINSICS),
+            ));
+        }
+        self.charge(&meta, "mvm_simulateTransaction", ESTIMATION_COST)?;
+        let pending = pending
+            .into_iter()
+            .map(|xt| {
+                Block::Extrinsic::decode(&mut &xt[..])
+                    .map_err(|e| error::bad_params("Error while decoding pending extrinsic", e))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let api = self.client.runtime_api();
+        let hash = block::resolve_state::<Block, _>(&*self.client, at, self.state_pruning)?;
+        let at = BlockId::hash(hash);
+
+        let parent = self
+            .client
+            .header(BlockId::hash(hash))
+            .map_err(|e| error::runtime_api_error("Error while requesting block header", e))?
+            .ok_or_else(|| error::not_found("Block not found", format!("{:?}", hash)))?;
+
+        // Runtime API calls share the state overlay, so extrinsics are applied to the new block
+        // and the script is executed on top of them.
+        api.initialize_block(&at, &simulate::next_header(&parent, hash))
+            .map_err(|e| {
+                error::runtime_api_error("Error while initializing block for simulation", e)
+            })?;
+        let pending = pending
+            .into_iter()
+            .map(|xt| {
+                api.apply_extrinsic(&at, xt)
+                    .map(PendingOutcome::from)
+                    .map_err(|e| {
+                        error::runtime_api_error("Error while applying pending extrinsic", e)
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let estimation = version::estimate_gas_execute(
+            &*api,
+            &at,
+            account,
+            tx_bc.0.clone(),
+            gas_limit,
+            self.deadline(deadline),
+        )
+        .map_err(|e| error::runtime_api_error("Error during requesting Runtime API", e))?
+        .map_err(|e| error::vm_error("Error during script simulation", &e))?;
+
+        Ok(Simulation {
+            gas_used: estimation.gas_used,
+            status_code: estimation.status_code,
+            abort_code: estimation.abort_code,
+            abort_reason: abort_reason::<Block, AccountId, _>(
+                &*api,
+                &at,
+                &tx_bc.0,
+                estimation.abort_code,
+            ),
+            pending,
+        })
+    }
+
+    fn estimate_xcm_transfer_fee(
+        &self,
+        currency: String,
+        amount: NumberOrHex,
+        dest: Bytes,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<XcmTransferFee> {
+        let _timer = self.timer("mvm_estimateXcmTransferFee");
+        let amount = u128::try_from(amount)
+            .map_err(|e| error::bad_params("Amount doesn't fit u128", format!("{:?}", e)))?;
+
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_estimateXcmTransferFee",
+            API_VERSION_3,
+        )?;
+
+        let fee = api
+            .estimate_xcm_transfer_fee(&at, currency.into_bytes(), dest.into_vec())
+            .map_err(|e| error::runtime_api_error("API error", e))?
+            .map_err(|e| error::method_error("Error from method", &e))?;
+
+        Ok(XcmTransferFee::new(fee, amount))
+    }
+
+    fn earliest_available_block(&self) -> Result<u64> {
+        let _timer = self.timer("mvm_earliestAvailableBlock");
+        Ok(block::earliest_available::<Block, _>(&*self.client, self.state_pruning))
+    }
+
+    fn block_gas_usage(
+        &self,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<BlockGasUsage> {
+        let _timer = self.timer("mvm_blockGasUsage");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_blockGasUsage",
+            API_VERSION_4,
+        )?;
+
+        let usage = api
+            .get_block_gas_usage(&at)
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+
+        Ok(usage.into())
+    }
+
+    fn gas_price(&self, percentiles: Option<Vec<u8>>) -> Result<GasPrice> {
+        let _timer = self.timer("mvm_gasPrice");
+        let oracle = self.gas_prices.as_ref().ok_or_else(|| {
+            error::unavailable(
+                "Gas price oracle is disabled",
+                "Gas unit prices of the blocks are recorded by full nodes only",
+            )
+        })?;
+        oracle.suggest(percentiles)
+    }
+
+    fn get_sequence_number(
+        &self,
+        account: AccountId,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<u64> {
+        let _timer = self.timer("mvm_getSequenceNumber");
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_getSequenceNumber",
+            API_VERSION_5,
+        )?;
+
+        api.get_sequence_number(&at, account)
+            .map_err(|e| error::runtime_api_error("API error", e))
+    }
+
+    fn get_ledger_info(&self) -> Result<LedgerInfo<<Block as BlockT>::Hash>> {
+        let _timer = self.timer("mvm_getLedgerInfo");
+        let info = self.client.info();
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(info.best_hash);
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_getLedgerInfo",
+            API_VERSION_7,
+        )?;
+
+        let chain_id = api
+            .get_aptos_chain_id(&at)
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+        let vm_info = api.get_vm_info(&at).map_err(|e| error::runtime_api_error("API error", e))?;
+
+        Ok(LedgerInfo {
+            chain_id,
+            block_height: info.best_number.unique_saturated_into(),
+            block_hash: info.best_hash,
+            finalized_height: info.finalized_number.unique_saturated_into(),
+            stdlib_version: vm_info.stdlib_hash.to_vec().into(),
+        })
+    }
+
+    fn encode_submission_as_extrinsic(
+        &self,
+        tx_bc: Bytes,
+        gas_limit: u64,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<EncodedSubmission> {
+        let _timer = self.timer("mvm_encodeSubmissionAsExtrinsic");
+        self.deny_unsafe.check_if_safe()?;
+        self.limits.check_transaction(&tx_bc)?;
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_encodeSubmissionAsExtrinsic",
+            API_VERSION_8,
+        )?;
+
+        let submission = version::encode_execute_call::<Block, AccountId, _>(
+            &*api,
+            &at,
+            tx_bc.into_vec(),
+            gas_limit,
+        )
+        .map_err(|e| error::runtime_api_error("API error", e))?;
+        Ok(EncodedSubmission {
+            call: submission.call.into(),
+            unsigned_extrinsic: submission.unsigned_extrinsic.into(),
+        })
+    }
+
+    fn get_transaction_by_hash(
+        &self,
+        tx_hash: <Block as BlockT>::Hash,
+    ) -> Result<Option<Transaction<<Block as BlockT>::Hash>>> {
+        let _timer = self.timer("mvm_getTransactionByHash");
+        let get = self.offchain.as_ref().ok_or_else(|| {
+            error::unavailable(
+                "Offchain storage is not available",
+                "Transactions are indexed by full nodes with offchain storage only",
+            )
+        })?;
+        let location = match get(&transaction::transaction_key(tx_hash.as_ref())) {
+            Some(encoded) => TransactionLocation::decode(&mut &encoded[..]).map_err(|e| {
+                error::decode_error("Error while decoding transaction location", e)
+            })?,
+            None => return Ok(None),
+        };
+
+        // Location is left by the block replaced by a reorg.
+        let block_hash = match self
+            .client
+            .hash(location.block.unique_saturated_into())
+            .map_err(|e| error::runtime_api_error("Blockchain error", e))?
+        {
+            Some(hash) if hash.as_ref() == &location.block_hash[..] => hash,
+            _ => return Ok(None),
+        };
+        let extrinsic = match self
+            .client
+            .block_body(&BlockId::hash(block_hash))
+            .map_err(|e| error::runtime_api_error("Blockchain error", e))?
+            .and_then(|mut body| {
+                let index = location.index as usize;
+                (index < body.len()).then(|| body.swap_remove(index))
+            }) {
+            Some(extrinsic) => extrinsic,
+            None => return Ok(None),
+        };
+
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(Some(block_hash.into()))?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_getTransactionByHash",
+            API_VERSION_13,
+        )?;
+        let tx = match api
+            .get_transaction(&at, extrinsic.encode(), location.index)
+            .map_err(|e| error::runtime_api_error("API error", e))?
+        {
+            Some(tx) => tx,
+            None => return Ok(None),
+        };
+
+        // Modules are requested only to decode arguments of entry functions.
+        let get_module = |module_id: &move_core_types::language_storage::ModuleId| {
+            api
+                .get_module(&at, bcs::to_bytes(module_id)?)
+                .map_err(|e| anyhow::anyhow!("API error: {}", e))?
+                .map_err(|e| anyhow::anyhow!("{}", String::from_utf8_lossy(&e)))?
+                .ok_or_else(|| anyhow::anyhow!("Module {} not found", module_id))
+        };
+        Ok(Some(Transaction::new(tx_hash, block_hash, &location, tx, get_module)))
+    }
+
+    fn resolve_move_address(
+        &self,
+        move_addr: String,
+        at: Option<BlockNumberOrHash<<Block as BlockT>::Hash>>,
+    ) -> Result<Option<String>> {
+        use move_core_types::account_address::AccountAddress;
+
+        let _timer = self.timer("mvm_resolveMoveAddress");
+        let address = AccountAddress::from_hex_literal(&move_addr)
+            .map_err(|e| error::bad_params("Invalid Move address", e))?;
+        let api = self.client.runtime_api();
+        let at = self.block_id::<Block>(at)?;
+        require_api_version::<Block, AccountId, _>(
+            &*api,
+            &at,
+            "mvm_resolveMoveAddress",
+            API_VERSION_14,
+        )?;
+
+        let account = api
+            .resolve_move_address(&at, address.into_bytes())
+            .map_err(|e| error::runtime_api_error("API error", e))?;
+        Ok(account.map(|account| account.to_string()))
+    }
 }
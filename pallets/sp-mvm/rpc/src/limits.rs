@@ -0,0 +1,137 @@
+//! Limits of Move VM RPC requests.
+//!
+//! Inputs are checked before they are copied into the runtime API call, so oversized modules
+//! and transactions are rejected with `InvalidParams` without reaching the runtime. Gas
+//! estimations are executed by the bounded pool (see [`crate::pool`]), expensive methods are
+//! charged against the rate limits (see [`crate::rate_limit`]).
+use std::convert::TryFrom;
+use jsonrpc_core::{Error as RpcError, Result};
+use fc_rpc_core::types::Bytes;
+use move_core_types::account_address::AccountAddress;
+use move_vm::types::Transaction;
+use crate::error;
+
+/// Limits of the Move VM RPC requests.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RequestLimits {
+    /// Size of the module bytecode.
+    pub max_module_bytes: usize,
+    /// Number of modules in the package.
+    pub max_package_modules: usize,
+    /// Size of the script or transaction bytecode.
+    pub max_script_bytes: usize,
+    /// Number of the transaction arguments.
+    pub max_args: usize,
+    /// Number of gas estimations executed at once.
+    pub max_estimations: usize,
+    /// Time of the gas estimation in milliseconds, including the wait for a free worker.
+    pub estimation_timeout_ms: u64,
+    /// Number of blocks scanned by resource history requests.
+    pub max_history_blocks: u64,
+    /// Budget of the expensive methods of the node, in cost units per second
+    /// (see [`crate::rate_limit`]).
+    pub rate_limit: Option<u32>,
+    /// Budget of the expensive methods of a single connection, in cost units per second.
+    pub connection_rate_limit: Option<u32>,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_module_bytes: 256 * 1024,
+            max_package_modules: 64,
+            max_script_bytes: 64 * 1024,
+            max_args: 64,
+            max_estimations: 4,
+            estimation_timeout_ms: 10_000,
+            max_history_blocks: 10_000,
+            rate_limit: None,
+            connection_rate_limit: None,
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Checks size of the module bytecode.
+    pub fn check_module(&self, module_bc: &[u8]) -> Result<()> {
+        check_size("Module", module_bc.len(), self.max_module_bytes)
+    }
+
+    /// Checks number of modules in the package and size of every module.
+    pub fn check_package(&self, modules: &[Bytes]) -> Result<()> {
+        if modules.len() > self.max_package_modules {
+            return Err(invalid_params(
+                "Too many modules in the package",
+                modules.len(),
+                self.max_package_modules,
+            ));
+        }
+        modules.iter().try_for_each(|module| self.check_module(module))
+    }
+
+    /// Checks number of modules queried at once, it is limited as the number of package modules.
+    pub fn check_module_ids(&self, module_ids: &[Bytes]) -> Result<()> {
+        if module_ids.len() > self.max_package_modules {
+            return Err(invalid_params(
+                "Too many modules requested",
+                module_ids.len(),
+                self.max_package_modules,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks size of the script bytecode.
+    pub fn check_script(&self, script_bc: &[u8]) -> Result<()> {
+        check_size("Script", script_bc.len(), self.max_script_bytes)
+    }
+
+    /// Checks size of the transaction bytecode and number of its arguments.
+    ///
+    /// Malformed transactions pass the check, they are rejected by the runtime.
+    pub fn check_transaction(&self, tx_bc: &[u8]) -> Result<()> {
+        check_size("Transaction", tx_bc.len(), self.max_script_bytes)?;
+
+        let args = Transaction::try_from(tx_bc).ok().and_then(|tx| {
+            let signers = vec![AccountAddress::ZERO; tx.signers_count() as usize];
+            tx.into_script(signers).ok().map(|tx| tx.args().len())
+        });
+        match args {
+            Some(args) if args > self.max_args => Err(invalid_params(
+                "Too many transaction arguments",
+                args,
+                self.max_args,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the block range `from..=to` of the resource history request.
+    pub fn check_history_range(&self, from: u64, to: u64) -> Result<()> {
+        if to < from {
+            return Err(error::bad_params(
+                "Invalid block range",
+                format!("#{} is after #{}", from, to),
+            ));
+        }
+        let blocks = (to - from).saturating_add(1);
+        if blocks > self.max_history_blocks {
+            return Err(error::bad_params(
+                "Block range is too large",
+                format!("{}, at most {} allowed", blocks, self.max_history_blocks),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn check_size(input: &str, size: usize, max: usize) -> Result<()> {
+    if size > max {
+        return Err(invalid_params(&format!("{} is too large", input), size, max));
+    }
+    Ok(())
+}
+
+fn invalid_params(message: &str, value: usize, max: usize) -> RpcError {
+    error::bad_params(message, format!("{}, at most {} allowed", value, max))
+}
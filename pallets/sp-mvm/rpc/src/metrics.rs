@@ -0,0 +1,36 @@
+//! Prometheus metrics of the `mvm_*` methods.
+//!
+//! Metrics are registered once per node and shared by all RPC servers (HTTP, WS), the number
+//! of requests per method is the `_count` of the duration histogram.
+use substrate_prometheus_endpoint::{
+    prometheus::HistogramTimer, register, HistogramOpts, HistogramVec, PrometheusError, Registry,
+};
+
+/// Duration of the `mvm_*` method calls, labeled by method.
+#[derive(Clone)]
+pub struct RpcMetrics {
+    duration: HistogramVec,
+}
+
+impl RpcMetrics {
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            duration: register(
+                HistogramVec::new(
+                    HistogramOpts::new(
+                        "pontem_mvm_rpc_call_duration_seconds",
+                        "Duration of the Move VM RPC calls",
+                    )
+                    .buckets(vec![0.001, 0.005, 0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]),
+                    &["method"],
+                )?,
+                registry,
+            )?,
+        })
+    }
+
+    /// Starts the call of the method, duration is observed when the timer is dropped.
+    pub fn start_timer(&self, method: &str) -> HistogramTimer {
+        self.duration.with_label_values(&[method]).start_timer()
+    }
+}
@@ -0,0 +1,95 @@
+//! Bounded pool executing gas estimations off the RPC server threads.
+//!
+//! Estimation executes Move VM in the runtime and may take up to the execution deadline, so it
+//! runs on blocking threads of the dedicated runtime: at most `workers` estimations run at once,
+//! others wait for a free worker. Request fails if the estimation isn't finished in `timeout`
+//! including the wait, started estimation keeps its worker until it finishes anyway.
+use std::sync::Arc;
+use std::time::Duration;
+use jsonrpc_core::{BoxFuture, Result};
+use tokio::runtime::{Handle, Runtime};
+use tokio::sync::Semaphore;
+use crate::error;
+
+/// Pool of the estimation workers, shared by all RPC servers of the node.
+#[derive(Clone)]
+pub struct EstimationPool {
+    runtime: Arc<PoolRuntime>,
+    workers: Arc<Semaphore>,
+    timeout: Duration,
+}
+
+impl EstimationPool {
+    pub fn new(workers: usize, timeout: Duration) -> std::io::Result<Self> {
+        let workers = workers.max(1);
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .max_blocking_threads(workers)
+            .thread_name("mvm-rpc-estimation")
+            .enable_time()
+            .build()?;
+        Ok(Self {
+            runtime: Arc::new(PoolRuntime(Some(runtime))),
+            workers: Arc::new(Semaphore::new(workers)),
+            timeout,
+        })
+    }
+
+    /// Executes `f` on a free worker.
+    pub fn spawn<T, F>(&self, method: &'static str, f: F) -> BoxFuture<Result<T>>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        let workers = self.workers.clone();
+        let timeout = self.timeout;
+        let estimation = async move {
+            let permit = workers
+                .acquire_owned()
+                .await
+                .map_err(|e| error::runtime_api_error("Estimation pool is closed", e))?;
+            tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                f()
+            })
+            .await
+            .map_err(|e| error::runtime_api_error("Estimation failed", e))?
+        };
+        let task = self.runtime.handle().spawn(async move {
+            tokio::time::timeout(timeout, estimation)
+                .await
+                .map_err(|_| {
+                    error::timeout(
+                        "Estimation timed out",
+                        format!("{} isn't finished in {} ms", method, timeout.as_millis()),
+                    )
+                })?
+        });
+
+        Box::pin(async move {
+            task.await
+                .map_err(|e| error::runtime_api_error("Estimation failed", e))?
+        })
+    }
+}
+
+/// Runtime of the pool, shut down without blocking: the pool may be dropped by the RPC server
+/// thread, where blocking shutdown panics.
+struct PoolRuntime(Option<Runtime>);
+
+impl PoolRuntime {
+    fn handle(&self) -> &Handle {
+        self.0
+            .as_ref()
+            .expect("Runtime is taken on drop only; qed")
+            .handle()
+    }
+}
+
+impl Drop for PoolRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Cost budgets of expensive Move VM RPC methods.
+//!
+//! Gas estimations, traces, resource history and ABI requests execute Move VM or deserialize
+//! modules, so every call is charged with the cost of the method against token buckets: the
+//! global one, shared by all RPC servers of the node, and the one of the connection. Buckets
+//! hold one second of the budget and refill continuously, calls exceeding any of the budgets
+//! fail with "rate limited" error before reaching the runtime.
+//!
+//! Connections are told apart by the pubsub session, so only WebSocket (and IPC) connections
+//! have their own budget, HTTP requests are charged against the global budget only.
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use jsonrpc_core::Result;
+use jsonrpc_pubsub::Session;
+use crate::error;
+
+/// Cost of gas estimations and simulations.
+pub const ESTIMATION_COST: u32 = 10;
+/// Cost of traced and debug executions.
+pub const TRACE_COST: u32 = 20;
+/// Cost of the resource history request.
+pub const HISTORY_COST: u32 = 10;
+/// Cost of the ABI of a single module or script.
+pub const ABI_COST: u32 = 1;
+
+/// Token buckets of the node and its connections, shared by all RPC servers of the node.
+#[derive(Clone)]
+pub struct RateLimiter {
+    global: Option<Arc<Mutex<Bucket>>>,
+    per_connection: Option<u32>,
+    connections: Arc<Mutex<HashMap<usize, Bucket>>>,
+}
+
+impl RateLimiter {
+    /// Limiter with the budgets (in cost units per second) of the node and of a connection,
+    /// not limited if not set.
+    pub fn new(global: Option<u32>, per_connection: Option<u32>) -> Self {
+        Self {
+            global: global.map(|budget| Arc::new(Mutex::new(Bucket::new(budget)))),
+            per_connection,
+            connections: Default::default(),
+        }
+    }
+
+    /// Charges the call of `method` made by the `session` connection with `cost`.
+    ///
+    /// Nothing is taken from the budgets if any of them is exceeded.
+    pub fn charge(&self, session: Option<&Arc<Session>>, method: &str, cost: u32) -> Result<()> {
+        let now = Instant::now();
+        let mut global = self
+            .global
+            .as_ref()
+            .map(|bucket| bucket.lock().expect("Rate limiter lock is never poisoned; qed"));
+        let mut connections = self
+            .connections
+            .lock()
+            .expect("Rate limiter lock is never poisoned; qed");
+        let mut connection = match (session, self.per_connection) {
+            (Some(session), Some(budget)) => {
+                Some(self.connection(&mut connections, session, budget))
+            }
+            _ => None,
+        };
+
+        let fits = |bucket: Option<&mut Bucket>| bucket.map_or(true, |b| b.has(now, cost));
+        if !fits(global.as_deref_mut()) || !fits(connection.as_deref_mut()) {
+            return Err(error::rate_limited(
+                "Rate limit exceeded",
+                format!("{} costs {}, retry later", method, cost),
+            ));
+        }
+
+        if let Some(bucket) = global.as_deref_mut() {
+            bucket.take(cost);
+        }
+        if let Some(bucket) = connection {
+            bucket.take(cost);
+        }
+        Ok(())
+    }
+
+    /// Bucket of the connection, created on the first call and removed once the connection
+    /// is closed.
+    fn connection<'a>(
+        &self,
+        connections: &'a mut HashMap<usize, Bucket>,
+        session: &Arc<Session>,
+        budget: u32,
+    ) -> &'a mut Bucket {
+        let key = Arc::as_ptr(session) as usize;
+        connections.entry(key).or_insert_with(|| {
+            let registry = Arc::downgrade(&self.connections);
+            session.on_drop(move || {
+                if let Some(connections) = registry.upgrade() {
+                    if let Ok(mut connections) = connections.lock() {
+                        connections.remove(&key);
+                    }
+                }
+            });
+            Bucket::new(budget)
+        })
+    }
+}
+
+/// Token bucket holding one second of the budget.
+struct Bucket {
+    budget: u32,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(budget: u32) -> Self {
+        Self {
+            budget,
+            tokens: f64::from(budget),
+            updated: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let budget = f64::from(self.budget);
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * budget).min(budget);
+        self.updated = now;
+    }
+
+    /// Whether `cost` fits into the bucket refilled to `now`.
+    fn has(&mut self, now: Instant, cost: u32) -> bool {
+        self.refill(now);
+        self.tokens >= f64::from(cost)
+    }
+
+    fn take(&mut self, cost: u32) {
+        self.tokens -= f64::from(cost);
+    }
+}
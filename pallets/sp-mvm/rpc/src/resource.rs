@@ -0,0 +1,151 @@
+//! Decoding of Move resources to JSON.
+//!
+//! Resources are rendered in the JSON shape of Aptos `MoveResource` data: struct fields become
+//! object fields, values follow the rules of transaction arguments (see `decode`).
+//! Struct layouts are taken from the modules declaring them, each module is requested once.
+use std::collections::HashMap;
+use anyhow::{anyhow, bail, ensure, Result};
+use serde_json::{Map, Value};
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::{
+    CompiledModule, SignatureToken, StructFieldInformation, StructHandleIndex,
+};
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
+use crate::abi::type_tag_string;
+use crate::decode::{decode_value, read_uleb128};
+
+/// Decodes BCS encoded resource of type `tag`, modules are requested with `get_module`.
+pub fn decode_resource<F>(tag: &StructTag, bytes: &[u8], get_module: F) -> Result<Value>
+where
+    F: FnMut(&ModuleId) -> Result<Vec<u8>>,
+{
+    let mut decoder = ResourceDecoder {
+        get_module,
+        modules: HashMap::new(),
+    };
+    let mut bytes = bytes;
+    let value = decoder.decode_struct(tag, &mut bytes)?;
+    ensure!(
+        bytes.is_empty(),
+        "Trailing bytes in resource {}",
+        type_tag_string(&TypeTag::Struct(tag.clone()))
+    );
+    Ok(value)
+}
+
+struct ResourceDecoder<F> {
+    get_module: F,
+    modules: HashMap<ModuleId, CompiledModule>,
+}
+
+impl<F> ResourceDecoder<F>
+where
+    F: FnMut(&ModuleId) -> Result<Vec<u8>>,
+{
+    fn decode(&mut self, tag: &TypeTag, bytes: &mut &[u8]) -> Result<Value> {
+        match tag {
+            TypeTag::Struct(tag) => self.decode_struct(tag, bytes),
+            TypeTag::Vector(inner) if **inner != TypeTag::U8 => {
+                let len = read_uleb128(bytes)?;
+                let values = (0..len)
+                    .map(|_| self.decode(inner, bytes))
+                    .collect::<Result<_>>()?;
+                Ok(Value::Array(values))
+            }
+            tag => decode_value(tag, bytes),
+        }
+    }
+
+    fn decode_struct(&mut self, tag: &StructTag, bytes: &mut &[u8]) -> Result<Value> {
+        let mut object = Map::new();
+        for (name, field) in self.fields(tag)? {
+            let value = self.decode(&field, bytes)?;
+            object.insert(name, value);
+        }
+        Ok(Value::Object(object))
+    }
+
+    /// Names and types of the struct fields, with type parameters substituted.
+    fn fields(&mut self, tag: &StructTag) -> Result<Vec<(String, TypeTag)>> {
+        let module = self.module(&tag.module_id())?;
+        let def = module
+            .struct_defs()
+            .iter()
+            .find(|def| {
+                module.identifier_at(module.struct_handle_at(def.struct_handle).name)
+                    == tag.name.as_ident_str()
+            })
+            .ok_or_else(|| anyhow!("Struct {} not found in module", tag.name))?;
+
+        let fields = match &def.field_information {
+            StructFieldInformation::Native => {
+                bail!("Native struct {} can't be decoded", tag.name)
+            }
+            StructFieldInformation::Declared(fields) => fields,
+        };
+        fields
+            .iter()
+            .map(|field| {
+                let name = module.identifier_at(field.name).to_string();
+                let field_tag = resolve_type(module, &field.signature.0, &tag.type_params)?;
+                Ok((name, field_tag))
+            })
+            .collect()
+    }
+
+    fn module(&mut self, id: &ModuleId) -> Result<&CompiledModule> {
+        if !self.modules.contains_key(id) {
+            let module = CompiledModule::deserialize(&(self.get_module)(id)?)
+                .map_err(|err| anyhow!("Failed to deserialize module: {:?}", err))?;
+            self.modules.insert(id.clone(), module);
+        }
+        Ok(&self.modules[id])
+    }
+}
+
+/// Substitutes type parameters of the field type declared in `module`.
+fn resolve_type(
+    module: &CompiledModule,
+    token: &SignatureToken,
+    type_args: &[TypeTag],
+) -> Result<TypeTag> {
+    Ok(match token {
+        SignatureToken::Bool => TypeTag::Bool,
+        SignatureToken::U8 => TypeTag::U8,
+        SignatureToken::U64 => TypeTag::U64,
+        SignatureToken::U128 => TypeTag::U128,
+        SignatureToken::Address => TypeTag::Address,
+        SignatureToken::Signer => TypeTag::Signer,
+        SignatureToken::Vector(inner) => {
+            TypeTag::Vector(Box::new(resolve_type(module, inner, type_args)?))
+        }
+        SignatureToken::Struct(idx) => TypeTag::Struct(struct_tag(module, *idx, vec![])),
+        SignatureToken::StructInstantiation(idx, params) => {
+            let params = params
+                .iter()
+                .map(|param| resolve_type(module, param, type_args))
+                .collect::<Result<_>>()?;
+            TypeTag::Struct(struct_tag(module, *idx, params))
+        }
+        SignatureToken::TypeParameter(idx) => type_args
+            .get(*idx as usize)
+            .cloned()
+            .ok_or_else(|| anyhow!("Missing type argument T{}", idx))?,
+        token => bail!("Unsupported field type {:?}", token),
+    })
+}
+
+fn struct_tag(
+    module: &CompiledModule,
+    idx: StructHandleIndex,
+    type_params: Vec<TypeTag>,
+) -> StructTag {
+    let handle = module.struct_handle_at(idx);
+    let module_handle = module.module_handle_at(handle.module);
+    StructTag {
+        address: *module.address_identifier_at(module_handle.address),
+        module: module.identifier_at(module_handle.name).to_owned(),
+        name: module.identifier_at(handle.name).to_owned(),
+        type_params,
+    }
+}
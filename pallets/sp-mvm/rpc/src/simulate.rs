@@ -0,0 +1,107 @@
+//! Simulation of Move transactions on top of pending extrinsics for `mvm_simulateTransaction`.
+//!
+//! Pending extrinsics (e.g. in-flight transactions of the user) are applied to a new block built
+//! on top of the requested one, as the block builder does, then the script is dry run in the
+//! same state. Nothing is written: runtime API calls share the state overlay, which is dropped
+//! with the call. The new block reuses pre-runtime digests (author) of its parent.
+//!
+//! Estimations take state overrides the same way, as `eth_call` does: resources of the accounts
+//! are replaced in the overlay before the dry run, e.g. to estimate a transaction as if the
+//! approval was granted, without sending setup transactions.
+use serde::{Serialize, Deserialize};
+use fc_rpc_core::types::Bytes;
+use jsonrpc_core::Result;
+use sp_runtime::traits::{Header as HeaderT, One};
+use sp_runtime::{ApplyExtrinsicResult, Digest, DigestItem};
+use crate::abort::AbortReason;
+use crate::error;
+
+/// Maximum amount of pending extrinsics applied before the simulation.
+pub const MAX_PENDING_EXTRINSICS: usize = 64;
+
+/// Maximum amount of state overrides of the estimation.
+pub const MAX_STATE_OVERRIDES: usize = 64;
+
+/// Resource override: `value` (BCS encoded resource) replaces the resource of the account
+/// with `tag` (BCS encoded `StructTag`), `None` removes it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StateOverride<AccountId> {
+    pub account: AccountId,
+    pub tag: Bytes,
+    pub value: Option<Bytes>,
+}
+
+impl<AccountId> StateOverride<AccountId> {
+    /// Override in the form of the runtime API.
+    pub fn into_parts(self) -> (AccountId, Vec<u8>, Option<Vec<u8>>) {
+        (self.account, self.tag.into_vec(), self.value.map(Bytes::into_vec))
+    }
+}
+
+/// Fails with "invalid params" error if there are too many overrides.
+pub fn check_state_overrides<AccountId>(overrides: &[StateOverride<AccountId>]) -> Result<()> {
+    if overrides.len() > MAX_STATE_OVERRIDES {
+        return Err(error::bad_params(
+            "Too many state overrides",
+            format!("At most {} overrides", MAX_STATE_OVERRIDES),
+        ));
+    }
+    Ok(())
+}
+
+/// Outcome of the pending extrinsic.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingOutcome {
+    /// Extrinsic is included into the block, even if its dispatch failed.
+    pub applied: bool,
+    /// Dispatch error or the reason the extrinsic is invalid.
+    pub error: Option<String>,
+}
+
+impl From<ApplyExtrinsicResult> for PendingOutcome {
+    fn from(result: ApplyExtrinsicResult) -> Self {
+        match result {
+            Ok(Ok(())) => PendingOutcome {
+                applied: true,
+                error: None,
+            },
+            Ok(Err(err)) => PendingOutcome {
+                applied: true,
+                error: Some(format!("{:?}", err)),
+            },
+            Err(err) => PendingOutcome {
+                applied: false,
+                error: Some(format!("{:?}", err)),
+            },
+        }
+    }
+}
+
+/// Simulation result: dry run of the script and outcomes of the pending extrinsics in order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Simulation {
+    pub gas_used: u64,
+    pub status_code: u64,
+    pub abort_code: Option<u64>,
+    pub abort_reason: Option<AbortReason>,
+    pub pending: Vec<PendingOutcome>,
+}
+
+/// Header of the block built on top of `parent` with hash `parent_hash`.
+pub fn next_header<H: HeaderT>(parent: &H, parent_hash: H::Hash) -> H {
+    let logs = parent
+        .digest()
+        .logs()
+        .iter()
+        .filter(|item| matches!(item, DigestItem::PreRuntime(..)))
+        .cloned()
+        .collect();
+
+    H::new(
+        *parent.number() + One::one(),
+        Default::default(),
+        Default::default(),
+        parent_hash,
+        Digest { logs },
+    )
+}
@@ -0,0 +1,96 @@
+//! Source maps of the published modules for `mvm_getSourceMap` and abort locations of
+//! `mvm_traceExecute`.
+//!
+//! Source maps are stored as produced by the Move compiler (`dove build` writes them to
+//! `build/<package>/source_maps/*.mvsm`): BCS encoded `SourceMap` of Move 1.5 layout. Only
+//! the code map of functions (locations of the instructions in the source file, identified by
+//! its hash, as byte ranges) and names of the constants (see [`crate::abort`]) are used.
+//! Source maps of other layouts are served as is, but don't resolve locations.
+use std::collections::BTreeMap;
+use anyhow::{anyhow, Result};
+use serde::{Serialize, Deserialize};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use sp_core::bytes::to_hex;
+
+/// Location in the source file: byte range of the file with the hash.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    /// Hash (SHA-256) of the source file, hex encoded.
+    pub file_hash: String,
+    /// Byte offset of the start of the location.
+    pub start: u32,
+    /// Byte offset of the end of the location.
+    pub end: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+struct Loc {
+    file_hash: [u8; 32],
+    start: u32,
+    end: u32,
+}
+
+type SourceName = (String, Loc);
+
+// Fields are decoded to reach the code map only.
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+struct StructSourceMap {
+    definition_location: Loc,
+    type_parameters: Vec<SourceName>,
+    fields: Vec<Loc>,
+}
+
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+struct FunctionSourceMap {
+    definition_location: Loc,
+    type_parameters: Vec<SourceName>,
+    parameters: Vec<SourceName>,
+    locals: Vec<SourceName>,
+    code_map: BTreeMap<u16, Loc>,
+    is_native: bool,
+}
+
+/// Decoded source map of the module.
+#[allow(dead_code)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct SourceMap {
+    definition_location: Loc,
+    module_name_opt: Option<(AccountAddress, Identifier)>,
+    struct_map: BTreeMap<u16, StructSourceMap>,
+    function_map: BTreeMap<u16, FunctionSourceMap>,
+    constant_map: BTreeMap<String, u16>,
+}
+
+impl SourceMap {
+    /// Decodes BCS encoded source map.
+    pub fn decode(source_map: &[u8]) -> Result<Self> {
+        bcs::from_bytes(source_map).map_err(|e| anyhow!("Failed to decode source map: {}", e))
+    }
+
+    /// Location of the instruction at `offset` of the function by its definition index.
+    ///
+    /// Code map holds locations of the first instructions of the ranges, so the instruction
+    /// takes the location of the closest preceding entry.
+    pub fn location(&self, function: u16, offset: u16) -> Option<SourceLocation> {
+        let function = self.function_map.get(&function)?;
+        let (_, loc) = function.code_map.range(..=offset).next_back()?;
+        Some(SourceLocation {
+            file_hash: to_hex(&loc.file_hash, false),
+            start: loc.start,
+            end: loc.end,
+        })
+    }
+
+    /// Amount of functions with the source map.
+    pub fn functions(&self) -> usize {
+        self.function_map.len()
+    }
+
+    /// Names of the module constants with their constant pool indexes.
+    pub fn constants(&self) -> impl Iterator<Item = (&str, u16)> {
+        self.constant_map.iter().map(|(name, idx)| (name.as_str(), *idx))
+    }
+}
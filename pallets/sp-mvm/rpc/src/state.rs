@@ -0,0 +1,62 @@
+//! Move state of the account read from the client storage.
+//!
+//! `VMStorage` trie keys start with the hash of the access key, so entries of the address don't
+//! share a prefix: listing the account iterates all Move state entries at the block.
+use codec::Decode;
+use sc_client_api::{Backend, StorageProvider};
+use sp_core::storage::StorageKey;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use move_core_types::account_address::AccountAddress;
+use crate::changes::{account_key, state_key, AccountKey};
+
+/// Move state entry: address it's published under, decoded key, trie key and value.
+pub type StateEntry = (AccountAddress, AccountKey, Vec<u8>, Vec<u8>);
+
+/// All resources and modules of the Move state at the block.
+///
+/// `prefix` is the `VMStorage` prefix served by the runtime API (`get_vm_storage_prefix`).
+pub fn state_entries<C, B, Block>(
+    client: &C,
+    at: &BlockId<Block>,
+    prefix: &[u8],
+) -> sp_blockchain::Result<Vec<StateEntry>>
+where
+    Block: BlockT,
+    B: Backend<Block>,
+    C: StorageProvider<Block, B>,
+{
+    let pairs = client.storage_pairs(at, &StorageKey(prefix.to_vec()))?;
+    Ok(pairs
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let (address, state_key) = state_key(prefix, &key.0)?;
+            let value = Vec::<u8>::decode(&mut &value.0[..]).ok()?;
+            Some((address, state_key, key.0, value))
+        })
+        .collect())
+}
+
+/// Resources and modules published under `address` with their values.
+///
+/// `prefix` is the `VMStorage` prefix served by the runtime API (`get_vm_storage_prefix`).
+pub fn account_entries<C, B, Block>(
+    client: &C,
+    at: &BlockId<Block>,
+    prefix: &[u8],
+    address: &AccountAddress,
+) -> sp_blockchain::Result<Vec<(AccountKey, Vec<u8>)>>
+where
+    Block: BlockT,
+    B: Backend<Block>,
+    C: StorageProvider<Block, B>,
+{
+    let pairs = client.storage_pairs(at, &StorageKey(prefix.to_vec()))?;
+    Ok(pairs
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = account_key(prefix, address, &key.0)?;
+            let value = Vec::<u8>::decode(&mut &value.0[..]).ok()?;
+            Some((key, value))
+        })
+        .collect())
+}
@@ -0,0 +1,325 @@
+//! Execution profile of Move transactions for `mvm_traceExecute`.
+//!
+//! Gas is measured by the dry run of the transaction. Move VM doesn't expose its gas meter,
+//! so per-function profile is built from the bytecode: call tree of the functions reachable
+//! from the entry point with their instruction counts by opcode. Opcode counts are static
+//! (every instruction once), loops and branches are not unrolled.
+//!
+//! Move VM doesn't report the abort location, so every function lists its abort sites with the
+//! abort codes loaded right before them, located in the source file and named by the error
+//! constants of the source map of the module if it's published with one (see
+//! [`crate::source_map`] and [`crate::abort`]).
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
+use anyhow::{anyhow, ensure, Result};
+use serde::{Serialize, Deserialize};
+use move_binary_format::access::{ModuleAccess, ScriptAccess};
+use move_binary_format::file_format::{
+    Bytecode, CompiledModule, CompiledScript, ConstantPoolIndex, FunctionHandleIndex,
+    FunctionInstantiationIndex,
+};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::{IdentStr, Identifier};
+use move_core_types::language_storage::ModuleId;
+use move_vm::types::{Call, Transaction};
+use crate::abi::address_literal;
+use crate::abort::{abort_reasons, u64_constant, AbortReason};
+use crate::source_map::{SourceLocation, SourceMap};
+
+/// Maximum depth of the call tree.
+pub const MAX_TRACE_DEPTH: usize = 16;
+/// Maximum amount of the call tree nodes.
+pub const MAX_TRACE_NODES: usize = 1024;
+
+/// Dry run result with the call tree.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    pub gas_used: u64,
+    pub status_code: u64,
+    pub abort_code: Option<u64>,
+    pub abort_reason: Option<AbortReason>,
+    pub call_tree: TraceNode,
+}
+
+/// Function of the call tree.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TraceNode {
+    /// Function name, `script` for scripts, e.g. `0x1::Coin::transfer`.
+    pub function: String,
+    /// Native function, its cost is defined by Move VM.
+    pub native: bool,
+    /// Amount of instructions in the function body.
+    pub instructions: u64,
+    /// Amount of instructions by opcode, e.g. `{"Call": 2, "Ret": 1}`.
+    pub opcodes: BTreeMap<String, u64>,
+    /// Called functions in order of the first call.
+    /// Recursive calls and calls deeper than `MAX_TRACE_DEPTH` are not expanded.
+    pub calls: Vec<TraceNode>,
+    /// Abort instructions of the function body.
+    pub aborts: Vec<AbortSite>,
+}
+
+/// Abort instruction of the function.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AbortSite {
+    /// Offset of the instruction in the function body.
+    pub code_offset: u16,
+    /// Abort code if it's a constant loaded right before the instruction.
+    pub abort_code: Option<u64>,
+    /// Location in the source file, if the module is published with the source map.
+    pub location: Option<SourceLocation>,
+    /// Error constant of the abort code, if the module is published with the source map.
+    pub reason: Option<AbortReason>,
+}
+
+/// Builds call tree of the transaction (as produced by `dove tx`),
+/// modules are requested with `get_module`.
+pub fn trace_transaction<F>(tx_bc: &[u8], get_module: F) -> Result<TraceNode>
+where
+    F: FnMut(&ModuleId) -> Result<Vec<u8>>,
+{
+    trace_transaction_with_source_maps(tx_bc, get_module, |_| None)
+}
+
+/// Builds call tree of the transaction as [`trace_transaction`], abort sites are located with
+/// the source maps of the modules requested with `get_source_map`.
+pub fn trace_transaction_with_source_maps<F, S>(
+    tx_bc: &[u8],
+    get_module: F,
+    get_source_map: S,
+) -> Result<TraceNode>
+where
+    F: FnMut(&ModuleId) -> Result<Vec<u8>>,
+    S: FnMut(&ModuleId) -> Option<Vec<u8>>,
+{
+    let tx = Transaction::try_from(tx_bc)
+        .map_err(|err| anyhow!("Failed to deserialize transaction: {:?}", err))?;
+    let signers_count = tx.signers_count();
+
+    // Signers don't change the code, so any addresses are fine here.
+    let tx = tx
+        .into_script(vec![AccountAddress::ZERO; signers_count as usize])
+        .map_err(|err| anyhow!("Invalid transaction: {:?}", err))?;
+
+    let mut tracer = Tracer {
+        get_module,
+        get_source_map,
+        modules: BTreeMap::new(),
+        source_maps: BTreeMap::new(),
+        nodes: 0,
+    };
+    let mut stack = Vec::new();
+    match tx.call() {
+        Call::Script { code } => {
+            let script = CompiledScript::deserialize(code)
+                .map_err(|err| anyhow!("Failed to deserialize script: {:?}", err))?;
+            let callees = callees(
+                &script.code.code,
+                |idx| script_callee(&script, idx),
+                |idx| script.function_instantiation_at(idx).handle,
+            );
+            // Scripts aren't published, so they have no source maps.
+            let aborts = abort_sites(&script.code.code, |_| None);
+            tracer.trace(
+                "script".to_owned(),
+                false,
+                &script.code.code,
+                callees,
+                aborts,
+                &mut stack,
+            )
+        }
+        Call::ScriptFunction {
+            mod_address,
+            mod_name,
+            func_name,
+        } => {
+            let module_id = ModuleId::new(*mod_address, mod_name.clone());
+            tracer.trace_function(&module_id, func_name, &mut stack)
+        }
+    }
+}
+
+/// Call tree builder with cache of the requested modules and source maps.
+struct Tracer<F, S> {
+    get_module: F,
+    get_source_map: S,
+    modules: BTreeMap<ModuleId, CompiledModule>,
+    source_maps: BTreeMap<ModuleId, Option<SourceMap>>,
+    nodes: usize,
+}
+
+impl<F, S> Tracer<F, S>
+where
+    F: FnMut(&ModuleId) -> Result<Vec<u8>>,
+    S: FnMut(&ModuleId) -> Option<Vec<u8>>,
+{
+    fn module(&mut self, id: &ModuleId) -> Result<&CompiledModule> {
+        if !self.modules.contains_key(id) {
+            let module = CompiledModule::deserialize(&(self.get_module)(id)?)
+                .map_err(|err| anyhow!("Failed to deserialize module: {:?}", err))?;
+            self.modules.insert(id.clone(), module);
+        }
+        Ok(&self.modules[id])
+    }
+
+    /// Caches source map of the module, `None` if it's missing or can't be decoded.
+    fn load_source_map(&mut self, id: &ModuleId) {
+        if !self.source_maps.contains_key(id) {
+            let source_map =
+                (self.get_source_map)(id).and_then(|map| SourceMap::decode(&map).ok());
+            self.source_maps.insert(id.clone(), source_map);
+        }
+    }
+
+    fn trace_function(
+        &mut self,
+        id: &ModuleId,
+        name: &IdentStr,
+        stack: &mut Vec<String>,
+    ) -> Result<TraceNode> {
+        let function = format!("{}::{}::{}", address_literal(id.address()), id.name(), name);
+        let module = self.module(id)?;
+        let (index, def) = module
+            .function_defs()
+            .iter()
+            .enumerate()
+            .find(|(_, def)| {
+                let handle = module.function_handle_at(def.function);
+                module.identifier_at(handle.name) == name
+            })
+            .ok_or_else(|| anyhow!("Function {} not found", function))?;
+
+        let native = def.code.is_none();
+        let code = def.code.as_ref().map(|code| code.code.clone()).unwrap_or_default();
+        let callees = callees(
+            &code,
+            |idx| module_callee(module, idx),
+            |idx| module.function_instantiation_at(idx).handle,
+        );
+        let mut aborts = abort_sites(&code, |idx| u64_constant(module, idx));
+        if !aborts.is_empty() {
+            // Source map is borrowed together with the module.
+            self.load_source_map(id);
+            if let (module, Some(source_map)) = (&self.modules[id], &self.source_maps[id]) {
+                let reasons = abort_reasons(module, source_map);
+                for site in &mut aborts {
+                    site.location = source_map.location(index as u16, site.code_offset);
+                    site.reason = reasons
+                        .iter()
+                        .find(|reason| Some(reason.code) == site.abort_code)
+                        .cloned();
+                }
+            }
+        }
+        self.trace(function, native, &code, callees, aborts, stack)
+    }
+
+    fn trace(
+        &mut self,
+        function: String,
+        native: bool,
+        code: &[Bytecode],
+        callees: Vec<(ModuleId, Identifier)>,
+        aborts: Vec<AbortSite>,
+        stack: &mut Vec<String>,
+    ) -> Result<TraceNode> {
+        self.nodes += 1;
+        ensure!(
+            self.nodes <= MAX_TRACE_NODES,
+            "Call tree has more than {} functions",
+            MAX_TRACE_NODES
+        );
+
+        let mut opcodes = BTreeMap::new();
+        for op in code {
+            *opcodes.entry(opcode_name(op)).or_insert(0) += 1;
+        }
+
+        let mut calls = Vec::new();
+        if stack.len() < MAX_TRACE_DEPTH && !stack.contains(&function) {
+            stack.push(function.clone());
+            let mut traced = BTreeSet::new();
+            for (id, name) in callees {
+                if traced.insert((id.clone(), name.clone())) {
+                    calls.push(self.trace_function(&id, &name, stack)?);
+                }
+            }
+            stack.pop();
+        }
+
+        Ok(TraceNode {
+            function,
+            native,
+            instructions: code.len() as u64,
+            opcodes,
+            calls,
+            aborts,
+        })
+    }
+}
+
+/// Abort instructions of the code with the abort codes loaded right before them,
+/// `constant` decodes `u64` constants of the pool.
+fn abort_sites<C>(code: &[Bytecode], constant: C) -> Vec<AbortSite>
+where
+    C: Fn(ConstantPoolIndex) -> Option<u64>,
+{
+    code.iter()
+        .enumerate()
+        .filter(|(_, op)| matches!(op, Bytecode::Abort))
+        .map(|(offset, _)| {
+            let abort_code = match offset.checked_sub(1).map(|prev| &code[prev]) {
+                Some(Bytecode::LdU64(abort_code)) => Some(*abort_code),
+                Some(Bytecode::LdConst(idx)) => constant(*idx),
+                _ => None,
+            };
+            AbortSite {
+                code_offset: offset as u16,
+                abort_code,
+                location: None,
+                reason: None,
+            }
+        })
+        .collect()
+}
+
+/// Functions called by the code in order of calls.
+fn callees<C, G>(code: &[Bytecode], callee: C, generic: G) -> Vec<(ModuleId, Identifier)>
+where
+    C: Fn(FunctionHandleIndex) -> (ModuleId, Identifier),
+    G: Fn(FunctionInstantiationIndex) -> FunctionHandleIndex,
+{
+    code.iter()
+        .filter_map(|op| match op {
+            Bytecode::Call(idx) => Some(callee(*idx)),
+            Bytecode::CallGeneric(idx) => Some(callee(generic(*idx))),
+            _ => None,
+        })
+        .collect()
+}
+
+fn script_callee(script: &CompiledScript, idx: FunctionHandleIndex) -> (ModuleId, Identifier) {
+    let handle = script.function_handle_at(idx);
+    let module = script.module_handle_at(handle.module);
+    let id = ModuleId::new(
+        *script.address_identifier_at(module.address),
+        script.identifier_at(module.name).to_owned(),
+    );
+    (id, script.identifier_at(handle.name).to_owned())
+}
+
+fn module_callee(module: &CompiledModule, idx: FunctionHandleIndex) -> (ModuleId, Identifier) {
+    let handle = module.function_handle_at(idx);
+    let id = module.module_id_for_handle(module.module_handle_at(handle.module));
+    (id, module.identifier_at(handle.name).to_owned())
+}
+
+/// Opcode without operands, e.g. `LdU64` for `LdU64(42)`.
+fn opcode_name(op: &Bytecode) -> String {
+    let name = format!("{:?}", op);
+    match name.find('(') {
+        Some(end) => name[..end].to_owned(),
+        None => name,
+    }
+}
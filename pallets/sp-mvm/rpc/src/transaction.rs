@@ -0,0 +1,154 @@
+//! Move extrinsics by hash for `mvm_getTransactionByHash`.
+//!
+//! Substrate doesn't index extrinsics by hash, so the node records the location (block and
+//! index) of the Move extrinsics of every new best block into the offchain storage. The RPC
+//! reads the extrinsic from the block body and the runtime at the block reports its outcome
+//! from the events of the block: dispatch success, `ExecutionResult` and Move events, so
+//! explorers get the transaction in one call. Locations left by the blocks replaced by a reorg
+//! are ignored: the block of the location has to be in the canonical chain.
+use std::convert::TryFrom;
+use codec::{Decode, Encode};
+use serde::{Serialize, Deserialize};
+use fc_rpc_core::types::Bytes;
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::ModuleId;
+use move_vm::types::ModulePackage;
+use sp_mvm_rpc_runtime::types::{MVMApiExecutionResult, MVMApiTransaction};
+use crate::abi::address_literal;
+use crate::decode::{decode_transaction, DecodedTransaction};
+use crate::WriteSetEvent;
+
+/// Prefix of the offchain key of the transaction location.
+pub const TRANSACTION_PREFIX: &[u8] = b"mvm::tx::";
+
+/// Offchain key of the location of the extrinsic with the hash.
+pub fn transaction_key(hash: &[u8]) -> Vec<u8> {
+    let mut key = TRANSACTION_PREFIX.to_vec();
+    key.extend_from_slice(hash);
+    key
+}
+
+/// Location of the extrinsic: block number, block hash and index in the block.
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub struct TransactionLocation {
+    pub block: u64,
+    pub block_hash: Vec<u8>,
+    pub index: u32,
+}
+
+/// Outcome of the Move call of the extrinsic, as `ExecutionResult` event reports it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransactionResult {
+    pub gas_used: u64,
+    pub status_code: u64,
+    pub abort_code: Option<u64>,
+    pub writes: u32,
+    pub events: u32,
+}
+
+impl From<MVMApiExecutionResult> for TransactionResult {
+    fn from(r: MVMApiExecutionResult) -> Self {
+        Self {
+            gas_used: r.gas_used,
+            status_code: r.status_code,
+            abort_code: r.abort_code,
+            writes: r.writes,
+            events: r.events,
+        }
+    }
+}
+
+/// Move extrinsic with its outcome.
+///
+/// `payload` is the transaction of the script execution (`decoded` as `mvm_decodeTransaction`
+/// does) or the published module or package (ids in `modules`). `result` is `None` for calls
+/// rejected before reaching Move VM, Move events are emitted by succeeded calls only.
+#[derive(Serialize, Deserialize)]
+pub struct Transaction<BlockHash> {
+    pub hash: BlockHash,
+    pub block_hash: BlockHash,
+    pub block_number: u64,
+    pub index: u32,
+    pub signer: Option<String>,
+    pub call: String,
+    pub gas_limit: u64,
+    pub gas_unit_price: u64,
+    pub success: bool,
+    pub payload: Bytes,
+    pub decoded: Option<DecodedTransaction>,
+    pub modules: Vec<String>,
+    pub result: Option<TransactionResult>,
+    pub events: Vec<WriteSetEvent>,
+}
+
+impl<BlockHash> Transaction<BlockHash> {
+    /// Builds the transaction reported by the runtime, modules of entry functions are
+    /// requested with `get_module` to decode the arguments.
+    pub fn new<AccountId, F>(
+        hash: BlockHash,
+        block_hash: BlockHash,
+        location: &TransactionLocation,
+        tx: MVMApiTransaction<AccountId>,
+        get_module: F,
+    ) -> Self
+    where
+        AccountId: std::fmt::Display,
+        F: FnOnce(&ModuleId) -> anyhow::Result<Vec<u8>>,
+    {
+        let call = String::from_utf8_lossy(&tx.call).into_owned();
+        let (decoded, modules) = if call.starts_with("execute") {
+            (decode_transaction(&tx.payload, get_module).ok(), Vec::new())
+        } else if call.starts_with("publish_package") {
+            (None, package_modules(&tx.payload))
+        } else {
+            (None, module_ids(std::iter::once(&tx.payload)))
+        };
+
+        Self {
+            hash,
+            block_hash,
+            block_number: location.block,
+            index: location.index,
+            signer: tx.signer.map(|s| s.to_string()),
+            call,
+            gas_limit: tx.gas_limit,
+            gas_unit_price: tx.gas_unit_price,
+            success: tx.success,
+            payload: tx.payload.into(),
+            decoded,
+            modules,
+            result: tx.result.map(Into::into),
+            events: tx
+                .events
+                .into_iter()
+                .map(|e| WriteSetEvent {
+                    guid: e.guid.into(),
+                    type_tag: String::from_utf8_lossy(&e.ty_tag).into_owned(),
+                    message: e.message.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Ids of the modules of the package, e.g. `0x1::Signer`.
+fn package_modules(package: &[u8]) -> Vec<String> {
+    match ModulePackage::try_from(package) {
+        // Sender doesn't change the module ids.
+        Ok(package) => module_ids(package.into_tx(AccountAddress::ZERO).into_inner().0.iter()),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Ids of the modules, bytecode failed to deserialize is skipped.
+fn module_ids<'a>(modules: impl Iterator<Item = &'a Vec<u8>>) -> Vec<String> {
+    modules
+        .filter_map(|bytecode| CompiledModule::deserialize(bytecode).ok())
+        .map(|module| {
+            let id = module.self_id();
+            format!("{}::{}", address_literal(id.address()), id.name())
+        })
+        .collect()
+}
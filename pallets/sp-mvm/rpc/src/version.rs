@@ -0,0 +1,237 @@
+//! Version of `MVMApiRuntime` implemented by the runtime at the requested block.
+//!
+//! Older runtimes (e.g. blocks before the runtime upgrade) implement older API versions:
+//! methods changed since are called in the old form, and methods added since are reported as
+//! unsupported by the runtime instead of failing to decode the result.
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, Result};
+use sp_api::{ApiError, ApiExt};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT, DispatchError};
+use sp_mvm_rpc_runtime::{
+    types::{MVMApiEstimation, MVMApiFee, MVMApiSubmission},
+    MVMApiRuntime,
+};
+use crate::error;
+
+/// Runtime API version with estimation deadline, package estimation, storage keys,
+/// Move addresses, allowlisted scripts and VM info.
+pub const API_VERSION_2: u32 = 2;
+
+/// Runtime API version with XCM transfer fee estimation.
+pub const API_VERSION_3: u32 = 3;
+
+/// Runtime API version with block gas usage.
+pub const API_VERSION_4: u32 = 4;
+
+/// Runtime API version with sequence numbers of Move accounts.
+pub const API_VERSION_5: u32 = 5;
+
+/// Runtime API version with fee estimation of script execution.
+pub const API_VERSION_6: u32 = 6;
+
+/// Runtime API version with Aptos chain id.
+pub const API_VERSION_7: u32 = 7;
+
+/// Runtime API version with encoding of `execute` calls.
+pub const API_VERSION_8: u32 = 8;
+
+/// Runtime API version with package metadata.
+pub const API_VERSION_9: u32 = 9;
+
+/// Runtime API version with module source maps.
+pub const API_VERSION_10: u32 = 10;
+
+/// Runtime API version with abort codes of estimations.
+pub const API_VERSION_11: u32 = 11;
+
+/// Runtime API version with state overrides of estimations.
+pub const API_VERSION_12: u32 = 12;
+
+/// Runtime API version with Move extrinsics of blocks.
+pub const API_VERSION_13: u32 = 13;
+
+/// Runtime API version with accounts of Move addresses.
+pub const API_VERSION_14: u32 = 14;
+
+/// Runtime API version with gas unit price of execution fee estimation.
+pub const API_VERSION_15: u32 = 15;
+
+/// Runtime API version with gas unit price in `ChargeGasPrice` extension.
+pub const API_VERSION_16: u32 = 16;
+
+/// Result of the gas estimation runtime API.
+pub type EstimationResult = std::result::Result<MVMApiEstimation, DispatchError>;
+
+/// Version of `MVMApiRuntime` at the block, `None` if the runtime doesn't implement it.
+pub fn api_version<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+) -> std::result::Result<Option<u32>, ApiError>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: ApiExt<Block>,
+{
+    api.api_version::<dyn MVMApiRuntime<Block, AccountId>>(at)
+}
+
+/// Fails with "unsupported by runtime" error if the runtime API at the block is older
+/// than `required`.
+pub fn require_api_version<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    method: &str,
+    required: u32,
+) -> Result<()>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: ApiExt<Block>,
+{
+    let version = api_version::<Block, AccountId, Api>(api, at)
+        .map_err(|e| error::runtime_api_error("Error during requesting Runtime API version", e))?;
+    match version {
+        Some(version) if version >= required => Ok(()),
+        version => Err(unsupported_by_runtime(method, version, required)),
+    }
+}
+
+/// Error of the method requiring `required` runtime API version.
+pub fn unsupported_by_runtime(method: &str, version: Option<u32>, required: u32) -> RpcError {
+    let implemented = match version {
+        Some(version) => format!("version {}", version),
+        None => "no Move VM runtime API".to_owned(),
+    };
+    error::unsupported_by_runtime(
+        "Unsupported by runtime",
+        format!(
+            "{} requires runtime API version {}, runtime at the block implements {}",
+            method, required, implemented
+        ),
+    )
+}
+
+/// Estimates gas of the module publishing, deadline is ignored by the runtime API version 1.
+pub fn estimate_gas_publish<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    account: AccountId,
+    module_bc: Vec<u8>,
+    gas_limit: u64,
+    deadline: Option<u64>,
+) -> std::result::Result<EstimationResult, ApiError>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: ApiExt<Block> + MVMApiRuntime<Block, AccountId>,
+{
+    let version = api_version::<Block, AccountId, Api>(api, at)?;
+    #[allow(deprecated)]
+    let res = if version < Some(API_VERSION_2) {
+        api.estimate_gas_publish_before_version_2(at, account, module_bc, gas_limit)
+    } else if version < Some(API_VERSION_11) {
+        api.estimate_gas_publish_before_version_11(at, account, module_bc, gas_limit, deadline)
+    } else {
+        return api.estimate_gas_publish(at, account, module_bc, gas_limit, deadline);
+    };
+    res.map(|res| res.map(Into::into))
+}
+
+/// Estimates gas of the package publishing, requires the runtime API version 2.
+pub fn estimate_gas_publish_package<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    account: AccountId,
+    modules: Vec<Vec<u8>>,
+    gas_limit: u64,
+    deadline: Option<u64>,
+) -> std::result::Result<EstimationResult, ApiError>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: ApiExt<Block> + MVMApiRuntime<Block, AccountId>,
+{
+    if api_version::<Block, AccountId, Api>(api, at)? < Some(API_VERSION_11) {
+        #[allow(deprecated)]
+        return api
+            .estimate_gas_publish_package_before_version_11(
+                at, account, modules, gas_limit, deadline,
+            )
+            .map(|res| res.map(Into::into));
+    }
+    api.estimate_gas_publish_package(at, account, modules, gas_limit, deadline)
+}
+
+/// Estimates gas of the script execution, deadline is ignored by the runtime API version 1,
+/// abort code is returned since the version 11.
+pub fn estimate_gas_execute<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    account: AccountId,
+    tx_bc: Vec<u8>,
+    gas_limit: u64,
+    deadline: Option<u64>,
+) -> std::result::Result<EstimationResult, ApiError>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: ApiExt<Block> + MVMApiRuntime<Block, AccountId>,
+{
+    let version = api_version::<Block, AccountId, Api>(api, at)?;
+    #[allow(deprecated)]
+    let res = if version < Some(API_VERSION_2) {
+        api.estimate_gas_execute_before_version_2(at, account, tx_bc, gas_limit)
+    } else if version < Some(API_VERSION_11) {
+        api.estimate_gas_execute_before_version_11(at, account, tx_bc, gas_limit, deadline)
+    } else {
+        return api.estimate_gas_execute(at, account, tx_bc, gas_limit, deadline);
+    };
+    res.map(|res| res.map(Into::into))
+}
+
+/// Estimates fee of the script execution, gas unit price is ignored and the gas tip isn't
+/// estimated before the runtime API version 15.
+pub fn estimate_execute_fee<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    tx_bc: Vec<u8>,
+    gas_limit: u64,
+    gas_unit_price: u64,
+    ticker: Option<Vec<u8>>,
+) -> std::result::Result<std::result::Result<MVMApiFee, Vec<u8>>, ApiError>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: ApiExt<Block> + MVMApiRuntime<Block, AccountId>,
+{
+    let version = api_version::<Block, AccountId, Api>(api, at)?;
+    #[allow(deprecated)]
+    let res = if version < Some(API_VERSION_15) {
+        api.estimate_execute_fee_before_version_15(at, tx_bc, gas_limit, ticker)
+    } else {
+        return api.estimate_execute_fee(at, tx_bc, gas_limit, gas_unit_price, ticker);
+    };
+    res.map(|res| res.map(Into::into))
+}
+
+/// Encodes `execute` call of the script, calls of the runtime API before version 16 have
+/// the gas unit price field, encoded as zero (no tip).
+pub fn encode_execute_call<Block, AccountId, Api>(
+    api: &Api,
+    at: &BlockId<Block>,
+    tx_bc: Vec<u8>,
+    gas_limit: u64,
+) -> std::result::Result<MVMApiSubmission, ApiError>
+where
+    Block: BlockT,
+    AccountId: Codec,
+    Api: ApiExt<Block> + MVMApiRuntime<Block, AccountId>,
+{
+    let version = api_version::<Block, AccountId, Api>(api, at)?;
+    #[allow(deprecated)]
+    if version < Some(API_VERSION_16) {
+        api.encode_execute_call_before_version_16(at, tx_bc, gas_limit, 0)
+    } else {
+        api.encode_execute_call(at, tx_bc, gas_limit)
+    }
+}
@@ -0,0 +1,147 @@
+/// Tests for Aptos compatible module and script ABI.
+///
+/// Expected ABIs are stored in `tests/assets/abi/<Module>.json`.
+/// Run tests with `UPDATE_GOLDEN=1` to regenerate them.
+use std::path::PathBuf;
+use sp_mvm_rpc::abi::{ModuleAbiEntry, MoveModuleBytecode, MoveScriptBytecode};
+use sp_mvm_rpc::error;
+
+/// Standard library modules corpus.
+const STDLIB: &[u8] = include_bytes!("../../tests/common/move-stdlib.pac");
+
+/// Hand-assembled script `fun main<T: copy + drop>(_s: signer, _x: u64, _v: vector<T>) {}`.
+const GENERIC_SCRIPT: &[u8] = &[
+    0xa1, 0x1c, 0xeb, 0x0b, 0x02, 0x00, 0x00, 0x00, 0x04, 0x01, 0x00, 0x02,
+    0x05, 0x02, 0x07, 0x07, 0x09, 0x07, 0x08, 0x10, 0x20, 0x00, 0x00, 0x00,
+    0x03, 0x0c, 0x03, 0x0a, 0x09, 0x00, 0x06, 0x53, 0x69, 0x67, 0x6e, 0x65,
+    0x72, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x03, 0x01,
+    0x00, 0x01, 0x02,
+];
+
+fn stdlib_modules() -> Vec<Vec<u8>> {
+    bcs::from_bytes(STDLIB).expect("Stdlib package should be a list of modules")
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/assets/abi")
+        .join(format!("{}.json", name))
+}
+
+#[test]
+/// Compare ABI of every stdlib module with the golden file.
+fn stdlib_abi_matches_golden_files() {
+    let update = std::env::var("UPDATE_GOLDEN").is_ok();
+
+    for bytecode in stdlib_modules() {
+        let module = MoveModuleBytecode::new(bytecode)
+            .try_parse_abi()
+            .expect("Stdlib module should be deserialized");
+        let abi = module.abi.expect("ABI should be parsed");
+        let actual = serde_json::to_value(&abi).unwrap();
+        let path = golden_path(&abi.name);
+
+        if update {
+            let json = serde_json::to_string_pretty(&actual).unwrap();
+            std::fs::write(&path, json + "\n").unwrap();
+            continue;
+        }
+
+        let golden = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("Golden file {:?} should exist", path));
+        let expected: serde_json::Value = serde_json::from_str(&golden).unwrap();
+        assert_eq!(expected, actual, "ABI of module {} mismatch", abi.name);
+    }
+}
+
+#[test]
+/// Check JSON shape of `MoveModuleBytecode`, including hex-encoded bytecode.
+fn module_bytecode_json_shape() {
+    let bytecode = stdlib_modules()
+        .into_iter()
+        .next()
+        .expect("Stdlib should contain modules");
+    let module = MoveModuleBytecode::new(bytecode.clone())
+        .try_parse_abi()
+        .unwrap();
+    let json = serde_json::to_value(&module).unwrap();
+
+    let hex = json["bytecode"].as_str().unwrap();
+    assert!(hex.starts_with("0x"));
+    assert_eq!(hex.len(), 2 + bytecode.len() * 2);
+
+    let abi = &json["abi"];
+    assert_eq!(abi["address"], "0x1");
+    assert_eq!(abi["name"], "Signer");
+    let function = &abi["exposed_functions"][0];
+    for field in &[
+        "name",
+        "visibility",
+        "is_entry",
+        "is_view",
+        "generic_type_params",
+        "params",
+        "return",
+    ] {
+        assert!(!function[field].is_null(), "missing field {}", field);
+    }
+}
+
+#[test]
+/// Broken bytecode should not produce ABI.
+fn invalid_bytecode() {
+    assert!(MoveModuleBytecode::new(vec![0, 1, 2])
+        .try_parse_abi()
+        .is_err());
+}
+
+#[test]
+/// Script ABI is rendered as a public entry function `main`.
+fn script_abi() {
+    let script = MoveScriptBytecode::new(GENERIC_SCRIPT.to_vec())
+        .try_parse_abi()
+        .expect("Script should be deserialized");
+    let json = serde_json::to_value(&script).unwrap();
+
+    assert_eq!(
+        json["abi"],
+        serde_json::json!({
+            "name": "main",
+            "visibility": "public",
+            "is_entry": true,
+            "is_view": false,
+            "generic_type_params": [{ "constraints": ["copy", "drop"] }],
+            "params": ["signer", "u64", "vector<T0>"],
+            "return": [],
+        })
+    );
+}
+
+#[test]
+/// Module bytecode is not a script.
+fn module_is_not_script() {
+    let module = stdlib_modules()
+        .into_iter()
+        .next()
+        .expect("Stdlib should contain modules");
+    assert!(MoveScriptBytecode::new(module).try_parse_abi().is_err());
+}
+
+#[test]
+/// Entry of the batched query holds either the module or the error of this module.
+fn module_abi_entry() {
+    let entry = ModuleAbiEntry::from(Ok(None));
+    assert_eq!(
+        serde_json::to_value(&entry).unwrap(),
+        serde_json::json!({ "module": null, "error": null })
+    );
+
+    let failure = error::decode_error("Error while building module ABI", "bad bytecode");
+    let entry = ModuleAbiEntry::from(Err(failure));
+    let json = serde_json::to_value(&entry).unwrap();
+    assert_eq!(json["module"], serde_json::Value::Null);
+    assert_eq!(json["error"]["code"], error::DECODE_ERROR);
+    assert_eq!(json["error"]["message"], "Error while building module ABI");
+}
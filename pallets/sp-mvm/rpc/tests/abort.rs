@@ -0,0 +1,63 @@
+/// Tests for abort reasons of the dry runs.
+use std::collections::BTreeMap;
+use sp_mvm_rpc::abort::{find_reason, message, AbortReason};
+use sp_mvm_rpc::trace::{AbortSite, TraceNode};
+
+fn reason(code: u64, name: &str) -> AbortReason {
+    AbortReason {
+        module: "0x1::Account".to_owned(),
+        code,
+        name: name.to_owned(),
+        message: message(name),
+    }
+}
+
+fn node(function: &str, aborts: Vec<AbortSite>, calls: Vec<TraceNode>) -> TraceNode {
+    TraceNode {
+        function: function.to_owned(),
+        native: false,
+        instructions: 0,
+        opcodes: BTreeMap::new(),
+        calls,
+        aborts,
+    }
+}
+
+fn site(abort_code: u64, reason: Option<AbortReason>) -> AbortSite {
+    AbortSite {
+        code_offset: 0,
+        abort_code: Some(abort_code),
+        location: None,
+        reason,
+    }
+}
+
+#[test]
+/// Messages are built from the names of the error constants.
+fn error_messages() {
+    assert_eq!(message("E_NOT_AUTHORIZED"), "Not authorized");
+    assert_eq!(message("ENOT_AUTHORIZED"), "Not authorized");
+    assert_eq!(message("EINSUFFICIENT_BALANCE"), "Insufficient balance");
+    assert_eq!(message("E"), "E");
+}
+
+#[test]
+/// Abort code is resolved by the first abort site of the call tree aborting with it.
+fn find_abort_reason() {
+    let tree = node(
+        "script",
+        vec![site(1, None)],
+        vec![
+            node("0x1::Account::check", vec![site(5, None)], vec![]),
+            node(
+                "0x1::Account::withdraw",
+                vec![site(5, Some(reason(5, "E_NOT_AUTHORIZED")))],
+                vec![],
+            ),
+        ],
+    );
+
+    assert_eq!(find_reason(&tree, 5), Some(reason(5, "E_NOT_AUTHORIZED")));
+    assert_eq!(find_reason(&tree, 1), None);
+    assert_eq!(find_reason(&tree, 7), None);
+}
@@ -0,0 +1,26 @@
+/// Tests for `at` param of `mvm_*` methods.
+use serde_json::json;
+use sp_core::H256;
+use sp_mvm_rpc::block::BlockNumberOrHash;
+
+#[test]
+/// Block is given by hash or by number.
+fn hash_or_number() {
+    let hash = H256::repeat_byte(0xab);
+    let at: BlockNumberOrHash<H256> = serde_json::from_value(json!(hash)).unwrap();
+    assert_eq!(at, BlockNumberOrHash::Hash(hash));
+
+    let at: BlockNumberOrHash<H256> = serde_json::from_value(json!(42)).unwrap();
+    assert_eq!(at, BlockNumberOrHash::Number(42));
+
+    assert!(serde_json::from_value::<BlockNumberOrHash<H256>>(json!("0x2a")).is_err());
+    assert!(serde_json::from_value::<BlockNumberOrHash<H256>>(json!(-1)).is_err());
+}
+
+#[test]
+/// Missing `at` is the best block.
+fn optional() {
+    let at: Option<BlockNumberOrHash<H256>> = serde_json::from_value(json!(null)).unwrap();
+    assert_eq!(at, None);
+    assert_eq!(json!(BlockNumberOrHash::<H256>::Number(7)), json!(7));
+}
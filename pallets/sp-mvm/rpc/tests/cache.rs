@@ -0,0 +1,91 @@
+/// Tests for the module ABI and estimation caches.
+use std::sync::Arc;
+use sp_mvm_rpc::abi::MoveModuleBytecode;
+use sp_mvm_rpc::cache::{EstimationCache, ModuleCache};
+
+/// Standard library modules corpus.
+const STDLIB: &[u8] = include_bytes!("../../tests/common/move-stdlib.pac");
+
+fn stdlib_modules() -> Vec<Vec<u8>> {
+    bcs::from_bytes(STDLIB).expect("Stdlib package should be a list of modules")
+}
+
+#[test]
+/// Cached ABI is the same as parsed from the bytecode.
+fn same_abi() {
+    let cache = ModuleCache::default();
+    for bytecode in stdlib_modules() {
+        let expected = MoveModuleBytecode::new(bytecode.clone())
+            .try_parse_abi()
+            .unwrap()
+            .abi
+            .unwrap();
+        assert_eq!(*cache.abi(&bytecode).unwrap(), expected);
+    }
+}
+
+#[test]
+/// Bytecode is parsed once, copies of the cache share entries.
+fn shared_entries() {
+    let cache = ModuleCache::default();
+    let copy = cache.clone();
+    let bytecode = stdlib_modules().remove(0);
+
+    let first = cache.abi(&bytecode).unwrap();
+    let second = copy.abi(&bytecode).unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+/// Least recently used ABIs are evicted.
+fn eviction() {
+    let cache = ModuleCache::new(2);
+    let modules = stdlib_modules();
+    for bytecode in &modules[..3] {
+        cache.abi(bytecode).unwrap();
+    }
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+/// Malformed bytecode is rejected and not cached.
+fn malformed() {
+    let cache = ModuleCache::default();
+    assert!(cache.abi(&[0, 1, 2]).is_err());
+    assert!(cache.is_empty());
+}
+
+#[test]
+/// Estimation is executed once per state root and request.
+fn estimation_cache() {
+    let cache = EstimationCache::<u64>::default();
+    let key = EstimationCache::<u64>::key(&[1; 32], "mvm_estimateGasExecute", b"request");
+
+    let estimated = cache.get_or_estimate::<()>(key, || Ok(42));
+    assert_eq!(estimated, Ok(42));
+    let cached = cache.get_or_estimate::<()>(key, || panic!("Estimation should be cached"));
+    assert_eq!(cached, Ok(42));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+/// Keys differ by state root, method and request.
+fn estimation_keys() {
+    let key = EstimationCache::<u64>::key;
+    let base = key(&[1; 32], "mvm_estimateGasExecute", b"request");
+    assert_ne!(base, key(&[2; 32], "mvm_estimateGasExecute", b"request"));
+    assert_ne!(base, key(&[1; 32], "mvm_estimateGasPublish", b"request"));
+    assert_ne!(base, key(&[1; 32], "mvm_estimateGasExecute", b"other"));
+}
+
+#[test]
+/// Failed estimations are not cached.
+fn estimation_errors() {
+    let cache = EstimationCache::<u64>::new(2);
+    let key = EstimationCache::<u64>::key(&[1; 32], "mvm_estimateGasExecute", b"request");
+
+    assert_eq!(cache.get_or_estimate(key, || Err("runtime error")), Err("runtime error"));
+    assert!(cache.is_empty());
+    assert_eq!(cache.get_or_estimate::<()>(key, || Ok(1)), Ok(1));
+}
@@ -0,0 +1,87 @@
+/// Tests for the decoding of changed entries of `mvm_subscribeAccountChanges`.
+use codec::Encode;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag, CORE_CODE_ADDRESS};
+use sp_mvm::storage::resource_key;
+use sp_mvm_rpc::changes::{account_change, state_key, AccountChange, AccountKey, ChangeKind};
+
+const PREFIX: [u8; 32] = [7; 32];
+
+/// Trie key of the `VMStorage` entry: prefix, key hash (not checked) and SCALE encoded key.
+fn trie_key(access_key: Vec<u8>) -> Vec<u8> {
+    let mut key = PREFIX.to_vec();
+    key.extend_from_slice(&[0; 16]);
+    key.extend(access_key.encode());
+    key
+}
+
+fn store_tag() -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("Store").unwrap(),
+        name: Identifier::new("U64").unwrap(),
+        type_params: vec![],
+    }
+}
+
+#[test]
+/// Resource of the address is decoded with its tag and new value.
+fn resource_change() {
+    let address = AccountAddress::new([1; 32]);
+    let key = trie_key(resource_key(&address, &bcs::to_bytes(&store_tag()).unwrap()));
+    let value = vec![42, 0, 0, 0, 0, 0, 0, 0];
+
+    assert_eq!(
+        account_change(&PREFIX, &address, &key, Some(&value.encode())),
+        Some(AccountChange {
+            kind: ChangeKind::Resource,
+            id: "0x1::Store::U64".to_owned(),
+            value: Some(value.into()),
+        })
+    );
+
+    // Removed resource.
+    let change = account_change(&PREFIX, &address, &key, None).unwrap();
+    assert_eq!(change.value, None);
+
+    // Resource of another address.
+    assert_eq!(account_change(&PREFIX, &CORE_CODE_ADDRESS, &key, None), None);
+}
+
+#[test]
+/// Module published under the address is decoded with its id.
+fn module_change() {
+    let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Store").unwrap());
+    let key = trie_key(module_id.access_vector());
+
+    let change = account_change(&PREFIX, &CORE_CODE_ADDRESS, &key, Some(&vec![1].encode()));
+    let change = change.unwrap();
+    assert_eq!(change.kind, ChangeKind::Module);
+    assert_eq!(change.id, "0x1::Store");
+}
+
+#[test]
+/// Keys of any address are decoded with the address they're published under.
+fn state_keys() {
+    let address = AccountAddress::new([1; 32]);
+    let key = trie_key(resource_key(&address, &bcs::to_bytes(&store_tag()).unwrap()));
+    assert_eq!(state_key(&PREFIX, &key), Some((address, AccountKey::Resource(store_tag()))));
+
+    let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("Store").unwrap());
+    let key = trie_key(module_id.access_vector());
+    assert_eq!(
+        state_key(&PREFIX, &key),
+        Some((CORE_CODE_ADDRESS, AccountKey::Module(module_id)))
+    );
+}
+
+#[test]
+/// Keys of other storage maps are skipped.
+fn other_storage_skipped() {
+    let address = AccountAddress::new([1; 32]);
+    let mut key = trie_key(resource_key(&address, &bcs::to_bytes(&store_tag()).unwrap()));
+    key[0] = 0;
+
+    assert_eq!(account_change(&PREFIX, &address, &key, None), None);
+}
@@ -0,0 +1,26 @@
+/// Tests for module bytecode chunks of `mvm_getModuleChunk`.
+use sp_mvm_rpc::chunk::{module_chunk, MAX_CHUNK_BYTES};
+
+#[test]
+/// Chunks are cut at the end of the module.
+fn chunks() {
+    let bytecode: Vec<u8> = (0..10).collect();
+
+    let chunk = module_chunk(&bytecode, 0, 4).unwrap();
+    assert_eq!((chunk.size, chunk.offset), (10, 0));
+    assert_eq!(chunk.chunk.into_vec(), vec![0, 1, 2, 3]);
+
+    let chunk = module_chunk(&bytecode, 8, 4).unwrap();
+    assert_eq!(chunk.chunk.into_vec(), vec![8, 9]);
+
+    assert!(module_chunk(&bytecode, 10, 4).unwrap().chunk.is_empty());
+    assert!(module_chunk(&bytecode, 11, 4).is_err());
+}
+
+#[test]
+/// Chunk length is capped.
+fn max_chunk_len() {
+    let bytecode = vec![0; MAX_CHUNK_BYTES as usize * 2];
+    let chunk = module_chunk(&bytecode, 1, u32::MAX).unwrap();
+    assert_eq!(chunk.chunk.len(), MAX_CHUNK_BYTES as usize);
+}
@@ -0,0 +1,27 @@
+/// Tests for the logs capture of `mvm_executeWithDebug`.
+use sp_mvm_rpc::debug::capture_logs;
+
+#[test]
+/// Events emitted on the calling thread are captured with level and target.
+fn capture_thread_logs() {
+    let (result, logs) = capture_logs(|| {
+        tracing::debug!(target: "mvm", "[debug] {}", 42);
+        tracing::info!(target: "runtime", "done");
+        7
+    });
+
+    assert_eq!(result, 7);
+    assert_eq!(logs, vec!["DEBUG mvm: [debug] 42", "INFO runtime: done"]);
+}
+
+#[test]
+/// Logs of other threads are not captured.
+fn skip_other_threads() {
+    let (_, logs) = capture_logs(|| {
+        std::thread::spawn(|| tracing::info!(target: "mvm", "other thread"))
+            .join()
+            .unwrap();
+    });
+
+    assert!(logs.is_empty());
+}
@@ -0,0 +1,78 @@
+/// Tests for transaction decoding.
+///
+/// Transactions are built by `pallets/sp-mvm/tests/assets/build_assets.sh`.
+use std::path::PathBuf;
+use serde_json::json;
+use sp_mvm_rpc::decode::{decode_transaction, TransactionPayload};
+
+fn transaction(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/assets/user/build/assets/transaction")
+        .join(format!("{}.mvt", name));
+    std::fs::read(&path).unwrap_or_else(|_| panic!("Transaction {:?} should be built", path))
+}
+
+fn no_modules(id: &move_core_types::language_storage::ModuleId) -> anyhow::Result<Vec<u8>> {
+    panic!("Scripts should not request module {}", id)
+}
+
+#[test]
+/// Decode script with signer and u64 argument.
+fn decode_script() {
+    let decoded = decode_transaction(&transaction("store_u64"), no_modules).unwrap();
+    assert_eq!(decoded.signers_count, 1);
+    assert!(!decoded.root_signer);
+
+    match decoded.payload {
+        TransactionPayload::ScriptPayload {
+            code,
+            type_arguments,
+            arguments,
+        } => {
+            let abi = code.abi.expect("Script ABI should be parsed");
+            assert_eq!(abi.params, vec!["signer", "u64"]);
+            assert!(type_arguments.is_empty());
+            assert_eq!(arguments, vec![json!("42")]);
+        }
+        payload => panic!("Unexpected payload {:?}", payload),
+    }
+}
+
+#[test]
+/// Decode generic script with type and address arguments.
+fn decode_generic_script() {
+    let decoded = decode_transaction(&transaction("transfer"), no_modules).unwrap();
+    let json = serde_json::to_value(&decoded.payload).unwrap();
+
+    assert_eq!(json["type"], "script_payload");
+    assert_eq!(json["type_arguments"], json!(["0x1::NOX::NOX"]));
+    assert_eq!(
+        json["arguments"],
+        json!([
+            "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+            "2000"
+        ])
+    );
+}
+
+#[test]
+/// Broken transaction can't be decoded.
+fn decode_invalid_transaction() {
+    assert!(decode_transaction(&[0, 1, 2], no_modules).is_err());
+}
+
+#[test]
+/// Crashers found by fuzzing (`pallets/sp-mvm/fuzz`) are decoded without panics.
+fn decode_fuzz_crashers() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/assets/fuzz/decode_transaction");
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries {
+        let path = entry.unwrap().path();
+        let tx_bc = std::fs::read(&path).unwrap();
+        let _ = decode_transaction(&tx_bc, |id| Err(anyhow::anyhow!("Module {} is missing", id)));
+    }
+}
@@ -0,0 +1,76 @@
+/// Tests for error codes and data of `mvm_*` methods.
+use jsonrpc_core::ErrorCode;
+use sp_mvm_rpc::error::{self, DECODE_ERROR, NOT_FOUND, RUNTIME_API_ERROR, STATE_PRUNED, VM_ERROR};
+use sp_runtime::{DispatchError, ModuleError};
+
+#[test]
+/// Server errors use codes of the range reserved for implementation-defined errors.
+fn codes() {
+    let codes = [
+        error::runtime_api_error("API error", "failed").code,
+        error::vm_error("Error during script execution", &DispatchError::BadOrigin).code,
+        error::method_error("Error from method", b"failed").code,
+        error::not_found("Block not found", "0x00").code,
+        error::decode_error("Error while decoding write-set", "failed").code,
+        error::unavailable("Debug execution is disabled", "failed").code,
+    ];
+    for code in codes {
+        match code {
+            ErrorCode::ServerError(code) => assert!((-32099..=-32000).contains(&code)),
+            code => panic!("Unexpected error code {:?}", code),
+        }
+    }
+    assert_eq!(codes[0], ErrorCode::ServerError(RUNTIME_API_ERROR));
+    assert_eq!(codes[1], ErrorCode::ServerError(VM_ERROR));
+    assert_eq!(codes[3], ErrorCode::ServerError(NOT_FOUND));
+    assert_eq!(codes[4], ErrorCode::ServerError(DECODE_ERROR));
+
+    let error = error::bad_params("Too many pending extrinsics", "At most 64 extrinsics");
+    assert_eq!(error.code, ErrorCode::InvalidParams);
+    assert_eq!(error.data.unwrap()["kind"], "bad_params");
+}
+
+#[test]
+/// Data of VM errors contains the dispatch error.
+fn vm_error_data() {
+    let e = DispatchError::Module(ModuleError {
+        index: 70,
+        error: 3,
+        message: Some("LinkerError"),
+    });
+    let error = error::vm_error("Error during script execution", &e);
+    assert_eq!(error.message, "Error during script execution");
+
+    let data = error.data.unwrap();
+    assert_eq!(data["kind"], "vm_error");
+    assert_eq!(data["error"]["Module"]["index"], 70);
+    assert_eq!(data["error"]["Module"]["error"], 3);
+    assert!(data["details"].as_str().unwrap().contains("LinkerError"));
+}
+
+#[test]
+/// Errors of runtime methods are decoded from UTF-8.
+fn method_error_data() {
+    let error = error::method_error("Error from method", b"error in get_module");
+    assert_eq!(error.data.unwrap()["details"], "error in get_module");
+
+    let error = error::method_error("Error from method", &[0xff]);
+    assert_eq!(error.data.unwrap()["details"], "can't decode error");
+}
+
+#[test]
+/// Client errors reading discarded state are reported as pruned state.
+fn state_pruned() {
+    let error = error::state_pruned("State of the block is pruned", "#1 is before #100");
+    assert_eq!(error.code, ErrorCode::ServerError(STATE_PRUNED));
+    assert_eq!(error.data.unwrap()["kind"], "state_pruned");
+
+    let discarded = "UnknownBlock(\"State already discarded for BlockId::Hash(0x00)\")";
+    let error = error::runtime_api_error("Error during requesting Runtime API", discarded);
+    assert_eq!(error.code, ErrorCode::ServerError(STATE_PRUNED));
+    assert_eq!(error.message, "State of the block is pruned");
+    assert!(error.data.unwrap()["details"].as_str().unwrap().contains("0x00"));
+
+    let error = error::runtime_api_error("Error during requesting Runtime API", "failed");
+    assert_eq!(error.code, ErrorCode::ServerError(RUNTIME_API_ERROR));
+}
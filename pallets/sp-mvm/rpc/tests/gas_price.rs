@@ -0,0 +1,69 @@
+/// Tests for the gas unit price oracle.
+use sp_mvm_rpc::gas_price::{GasPriceOracle, DEFAULT_PERCENTILES};
+
+#[test]
+/// Prices at the percentiles of all the Move extrinsics of the window.
+fn percentiles() {
+    let oracle = GasPriceOracle::new(3);
+    oracle.record_block(1, vec![5, 1]);
+    oracle.record_block(2, vec![]);
+    oracle.record_block(3, vec![3, 4, 2]);
+
+    let price = oracle.suggest(Some(vec![0, 20, 50, 100])).unwrap();
+    assert_eq!(price.oldest_block, Some(1));
+    assert_eq!(price.newest_block, Some(3));
+    assert_eq!(price.transactions, 5);
+    assert_eq!(price.percentiles, vec![0, 20, 50, 100]);
+    assert_eq!(price.prices, vec![1, 1, 3, 5]);
+
+    let price = oracle.suggest(None).unwrap();
+    assert_eq!(price.percentiles, DEFAULT_PERCENTILES.to_vec());
+    assert_eq!(price.prices, vec![2, 3, 4]);
+}
+
+#[test]
+/// Only the last blocks are kept.
+fn window() {
+    let oracle = GasPriceOracle::new(2);
+    oracle.record_block(1, vec![100]);
+    oracle.record_block(2, vec![1]);
+    oracle.record_block(3, vec![2]);
+
+    let price = oracle.suggest(Some(vec![100])).unwrap();
+    assert_eq!(price.oldest_block, Some(2));
+    assert_eq!(price.transactions, 2);
+    assert_eq!(price.prices, vec![2]);
+}
+
+#[test]
+/// Blocks replaced by a reorg are overwritten by the new best ones.
+fn reorg() {
+    let oracle = GasPriceOracle::new(10);
+    oracle.record_block(1, vec![1]);
+    oracle.record_block(2, vec![2]);
+    oracle.record_block(3, vec![3]);
+    oracle.record_block(2, vec![7]);
+
+    let price = oracle.suggest(Some(vec![100])).unwrap();
+    assert_eq!(price.newest_block, Some(2));
+    assert_eq!(price.transactions, 2);
+    assert_eq!(price.prices, vec![7]);
+}
+
+#[test]
+/// No recorded prices suggest zeros.
+fn empty() {
+    let price = GasPriceOracle::default().suggest(None).unwrap();
+    assert_eq!(price.oldest_block, None);
+    assert_eq!(price.newest_block, None);
+    assert_eq!(price.transactions, 0);
+    assert_eq!(price.prices, vec![0; DEFAULT_PERCENTILES.len()]);
+}
+
+#[test]
+/// Percentiles out of range or too many of them are rejected.
+fn invalid_percentiles() {
+    let oracle = GasPriceOracle::default();
+    assert!(oracle.suggest(Some(vec![101])).is_err());
+    assert!(oracle.suggest(Some(vec![50; 17])).is_err());
+}
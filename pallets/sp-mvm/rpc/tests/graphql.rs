@@ -0,0 +1,114 @@
+/// Tests for the GraphQL schema of Move state.
+///
+/// Modules are built by `pallets/sp-mvm/tests/assets/build_assets.sh`.
+#![cfg(feature = "graphql")]
+use std::path::PathBuf;
+use std::sync::Arc;
+use serde_json::json;
+use move_binary_format::file_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use sp_mvm::event::MoveEventRecord;
+use sp_mvm_rpc::changes::AccountKey;
+use sp_mvm_rpc::graphql::{page_range, schema, MoveState, MAX_PAGE_SIZE};
+
+fn store_module() -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/assets/user/build/assets/bytecode_modules/Store.mv");
+    std::fs::read(&path).unwrap_or_else(|_| panic!("Module {:?} should be built", path))
+}
+
+fn store_id() -> ModuleId {
+    CompiledModule::deserialize(&store_module()).unwrap().self_id()
+}
+
+/// State with the `Store` module, `Store::U64` resource and an event in every block.
+struct TestState;
+
+impl MoveState for TestState {
+    fn best_block(&self) -> anyhow::Result<u64> {
+        Ok(10)
+    }
+
+    fn account_entries(
+        &self,
+        address: &AccountAddress,
+        _block: u64,
+    ) -> anyhow::Result<Vec<(AccountKey, Vec<u8>)>> {
+        if address != store_id().address() {
+            return Ok(vec![]);
+        }
+        let tag = StructTag {
+            address: *address,
+            module: store_id().name().to_owned(),
+            name: Identifier::new("U64").unwrap(),
+            type_params: vec![],
+        };
+        Ok(vec![
+            (AccountKey::Module(store_id()), store_module()),
+            (AccountKey::Resource(tag), bcs::to_bytes(&42u64).unwrap()),
+        ])
+    }
+
+    fn module(&self, id: &ModuleId, _block: u64) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(Some(store_module()).filter(|_| *id == store_id()))
+    }
+
+    fn events(&self, _block: u64) -> anyhow::Result<Vec<MoveEventRecord>> {
+        Ok(vec![MoveEventRecord {
+            guid: vec![1],
+            ty_tag: b"u64".to_vec(),
+            message: vec![2],
+        }])
+    }
+}
+
+fn query(query: &str) -> serde_json::Value {
+    let response = futures::executor::block_on(schema(Arc::new(TestState)).execute(query));
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    response.data.into_json().unwrap()
+}
+
+#[test]
+/// Modules are served with ABI and resources with decoded fields.
+fn account_state() {
+    let address = store_id().address().to_hex_literal();
+    let data = query(&format!(
+        r#"{{
+            modules(address: "{0}") {{ totalCount items {{ name abi {{ structs {{ name }} }} }} }}
+            resources(address: "{0}", typePrefix: "{0}::Store::") {{ items {{ data }} }}
+            missing: resources(address: "0x1") {{ totalCount }}
+        }}"#,
+        address
+    ));
+
+    assert_eq!(data["modules"]["totalCount"], json!(1));
+    assert_eq!(data["modules"]["items"][0]["name"], json!("Store"));
+    let structs = &data["modules"]["items"][0]["abi"]["structs"];
+    assert!(structs.as_array().unwrap().contains(&json!({ "name": "U64" })));
+    assert_eq!(data["resources"]["items"], json!([{ "data": { "val": "42" } }]));
+    assert_eq!(data["missing"]["totalCount"], json!(0));
+}
+
+#[test]
+/// Events are read from the blocks range up to the best block.
+fn events() {
+    let data = query(r#"{ events(fromBlock: 8, limit: 2) { totalCount items { block type } } }"#);
+    assert_eq!(
+        data["events"],
+        json!({
+            "totalCount": 3,
+            "items": [{ "block": 8, "type": "u64" }, { "block": 9, "type": "u64" }],
+        })
+    );
+}
+
+#[test]
+/// Pages are limited by the maximum size and clamped to the items.
+fn pages() {
+    assert_eq!(page_range(10, None, None), 0..10);
+    assert_eq!(page_range(10, Some(8), Some(5)), 8..10);
+    assert_eq!(page_range(10, Some(20), None), 10..10);
+    assert_eq!(page_range(1000, None, Some(1000)), 0..MAX_PAGE_SIZE);
+}
@@ -0,0 +1,43 @@
+/// Tests for resource history of `mvm_getResourceHistory`.
+use jsonrpc_core::Result;
+use sp_mvm_rpc::history::{changes_limit, collect_changes, MAX_HISTORY_CHANGES};
+
+/// Values of the resource at blocks `from..`, the block hash is its number.
+fn blocks(from: u64, values: &[Option<u64>]) -> Vec<Result<(u64, u64, Option<u64>)>> {
+    values
+        .iter()
+        .zip(from..)
+        .map(|(value, number)| Ok((number, number, *value)))
+        .collect()
+}
+
+#[test]
+/// Only blocks changing the value are collected, including removal of the resource.
+fn changed_blocks() {
+    let values = blocks(10, &[Some(1), Some(1), Some(2), None, None, Some(2)]);
+    let changes = collect_changes(None, values, 10).unwrap();
+    assert_eq!(
+        changes,
+        vec![(10, 10, Some(1)), (12, 12, Some(2)), (13, 13, None), (15, 15, Some(2))]
+    );
+
+    // The value before the range is not a change.
+    let values = blocks(10, &[Some(1), Some(2)]);
+    let changes = collect_changes(Some(1), values, 10).unwrap();
+    assert_eq!(changes, vec![(11, 11, Some(2))]);
+}
+
+#[test]
+/// Blocks after `limit` changes are not requested.
+fn limited_changes() {
+    let mut values = blocks(0, &[Some(1), Some(2), Some(3)]);
+    values.push(Err(jsonrpc_core::Error::internal_error()));
+
+    let changes = collect_changes(None, values.clone(), 3).unwrap();
+    assert_eq!(changes.len(), 3);
+    assert!(collect_changes(None, values, 4).is_err());
+
+    assert_eq!(changes_limit(Some(5)), 5);
+    assert_eq!(changes_limit(None), MAX_HISTORY_CHANGES as usize);
+    assert_eq!(changes_limit(Some(u32::MAX)), MAX_HISTORY_CHANGES as usize);
+}
@@ -0,0 +1,82 @@
+/// Tests for size limits of RPC inputs.
+///
+/// Transactions are built by `pallets/sp-mvm/tests/assets/build_assets.sh`.
+use std::path::PathBuf;
+use jsonrpc_core::ErrorCode;
+use sp_mvm_rpc::limits::RequestLimits;
+
+fn transaction(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/assets/user/build/assets/transaction")
+        .join(format!("{}.mvt", name));
+    std::fs::read(&path).unwrap_or_else(|_| panic!("Transaction {:?} should be built", path))
+}
+
+#[test]
+/// Modules and packages are limited by size and number of modules.
+fn modules() {
+    let limits = RequestLimits {
+        max_module_bytes: 4,
+        max_package_modules: 2,
+        ..Default::default()
+    };
+    assert!(limits.check_module(&[0; 4]).is_ok());
+
+    let error = limits.check_module(&[0; 5]).unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidParams);
+    assert_eq!(error.message, "Module is too large");
+
+    assert!(limits.check_package(&[vec![0; 4].into(), vec![0; 4].into()]).is_ok());
+    assert!(limits.check_package(&[vec![0; 4].into(), vec![0; 5].into()]).is_err());
+
+    assert!(limits.check_module_ids(&[vec![0].into(), vec![1].into()]).is_ok());
+    let error = limits
+        .check_module_ids(&[vec![0].into(), vec![1].into(), vec![2].into()])
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidParams);
+    let error = limits.check_package(&vec![vec![0].into(); 3]).unwrap_err();
+    assert_eq!(error.message, "Too many modules in the package");
+}
+
+#[test]
+/// Transactions are limited by size and number of arguments.
+fn transactions() {
+    let tx = transaction("store_u64");
+    assert!(RequestLimits::default().check_transaction(&tx).is_ok());
+
+    let limits = RequestLimits {
+        max_args: 0,
+        ..Default::default()
+    };
+    let error = limits.check_transaction(&tx).unwrap_err();
+    assert_eq!(error.message, "Too many transaction arguments");
+
+    let limits = RequestLimits {
+        max_script_bytes: tx.len() - 1,
+        ..Default::default()
+    };
+    let error = limits.check_transaction(&tx).unwrap_err();
+    assert_eq!(error.message, "Transaction is too large");
+
+    // Malformed transactions are left to the runtime.
+    assert!(RequestLimits::default().check_transaction(&[1, 2, 3]).is_ok());
+}
+
+#[test]
+/// Resource history is requested for bounded block ranges.
+fn history_range() {
+    let limits = RequestLimits {
+        max_history_blocks: 10,
+        ..Default::default()
+    };
+    assert!(limits.check_history_range(5, 5).is_ok());
+    assert!(limits.check_history_range(0, 9).is_ok());
+
+    let error = limits.check_history_range(0, 10).unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidParams);
+    assert_eq!(error.message, "Block range is too large");
+    assert!(limits.check_history_range(0, u64::MAX).is_err());
+
+    let error = limits.check_history_range(6, 5).unwrap_err();
+    assert_eq!(error.message, "Invalid block range");
+}
@@ -0,0 +1,32 @@
+/// Tests for Prometheus metrics of RPC methods.
+use sp_mvm_rpc::metrics::RpcMetrics;
+use substrate_prometheus_endpoint::Registry;
+
+#[test]
+/// Calls are counted per method by the duration histogram.
+fn call_duration() {
+    let registry = Registry::new();
+    let metrics = RpcMetrics::register(&registry).unwrap();
+    drop(metrics.start_timer("mvm_getModule"));
+    drop(metrics.start_timer("mvm_getModule"));
+    drop(metrics.start_timer("mvm_info"));
+
+    let families = registry.gather();
+    let family = families
+        .iter()
+        .find(|family| family.get_name() == "pontem_mvm_rpc_call_duration_seconds")
+        .unwrap();
+    let count = |method: &str| {
+        family
+            .get_metric()
+            .iter()
+            .find(|metric| metric.get_label()[0].get_value() == method)
+            .map(|metric| metric.get_histogram().get_sample_count())
+    };
+    assert_eq!(count("mvm_getModule"), Some(2));
+    assert_eq!(count("mvm_info"), Some(1));
+    assert_eq!(count("mvm_getResource"), None);
+
+    // Metrics are registered once and shared by RPC servers.
+    assert!(RpcMetrics::register(&registry).is_err());
+}
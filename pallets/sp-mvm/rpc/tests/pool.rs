@@ -0,0 +1,53 @@
+/// Tests for the pool executing gas estimations.
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use futures::executor::block_on;
+use futures::future::join_all;
+use jsonrpc_core::ErrorCode;
+use sp_mvm_rpc::error::TIMEOUT;
+use sp_mvm_rpc::pool::EstimationPool;
+
+#[test]
+/// Result of the estimation is returned to the caller.
+fn result() {
+    let pool = EstimationPool::new(1, Duration::from_secs(5)).unwrap();
+    assert_eq!(block_on(pool.spawn("mvm_test", || Ok(42))).unwrap(), 42);
+}
+
+#[test]
+/// At most `workers` estimations are executed at once.
+fn concurrency() {
+    let pool = EstimationPool::new(2, Duration::from_secs(5)).unwrap();
+    let active = Arc::new(AtomicUsize::new(0));
+    let max = Arc::new(AtomicUsize::new(0));
+
+    let estimations = (0..6).map(|_| {
+        let active = active.clone();
+        let max = max.clone();
+        pool.spawn("mvm_test", move || {
+            let running = active.fetch_add(1, Ordering::SeqCst) + 1;
+            max.fetch_max(running, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(50));
+            active.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        })
+    });
+    for result in block_on(join_all(estimations)) {
+        result.unwrap();
+    }
+    assert_eq!(max.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+/// Slow estimation fails with timeout.
+fn timeout() {
+    let pool = EstimationPool::new(1, Duration::from_millis(50)).unwrap();
+    let error = block_on(pool.spawn("mvm_test", || {
+        std::thread::sleep(Duration::from_millis(500));
+        Ok(())
+    }))
+    .unwrap_err();
+    assert_eq!(error.code, ErrorCode::ServerError(TIMEOUT));
+    assert_eq!(error.data.unwrap()["kind"], "timeout");
+}
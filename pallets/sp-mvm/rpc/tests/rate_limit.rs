@@ -0,0 +1,59 @@
+/// Tests for budgets of expensive RPC methods.
+use std::sync::Arc;
+use std::time::Duration;
+use futures::channel::mpsc;
+use jsonrpc_core::ErrorCode;
+use jsonrpc_pubsub::Session;
+use sp_mvm_rpc::error::RATE_LIMITED;
+use sp_mvm_rpc::rate_limit::{RateLimiter, ESTIMATION_COST};
+
+fn session() -> Arc<Session> {
+    let (sender, _) = mpsc::unbounded();
+    Arc::new(Session::new(sender))
+}
+
+#[test]
+/// Calls are limited by the global budget, which refills over time.
+fn global_budget() {
+    let limiter = RateLimiter::new(Some(2 * ESTIMATION_COST), None);
+    assert!(limiter.charge(None, "mvm_test", ESTIMATION_COST).is_ok());
+    assert!(limiter.charge(Some(&session()), "mvm_test", ESTIMATION_COST).is_ok());
+
+    let error = limiter.charge(None, "mvm_test", ESTIMATION_COST).unwrap_err();
+    assert_eq!(error.code, ErrorCode::ServerError(RATE_LIMITED));
+    assert_eq!(error.data.unwrap()["kind"], "rate_limited");
+
+    std::thread::sleep(Duration::from_millis(600));
+    assert!(limiter.charge(None, "mvm_test", ESTIMATION_COST).is_ok());
+}
+
+#[test]
+/// Every connection has its own budget, calls without connection aren't limited by it.
+fn connection_budget() {
+    let limiter = RateLimiter::new(None, Some(ESTIMATION_COST));
+    let (first, second) = (session(), session());
+
+    assert!(limiter.charge(Some(&first), "mvm_test", ESTIMATION_COST).is_ok());
+    assert!(limiter.charge(Some(&first), "mvm_test", 1).is_err());
+    assert!(limiter.charge(Some(&second), "mvm_test", ESTIMATION_COST).is_ok());
+    assert!(limiter.charge(None, "mvm_test", ESTIMATION_COST).is_ok());
+}
+
+#[test]
+/// Rejected calls don't take from any budget.
+fn rejected_calls_are_free() {
+    let limiter = RateLimiter::new(Some(ESTIMATION_COST), Some(ESTIMATION_COST / 2));
+    let connection = session();
+
+    assert!(limiter.charge(Some(&connection), "mvm_test", ESTIMATION_COST).is_err());
+    assert!(limiter.charge(None, "mvm_test", ESTIMATION_COST).is_ok());
+}
+
+#[test]
+/// Calls aren't limited without budgets.
+fn unlimited() {
+    let limiter = RateLimiter::new(None, None);
+    for _ in 0..100 {
+        assert!(limiter.charge(Some(&session()), "mvm_test", u32::MAX).is_ok());
+    }
+}
@@ -0,0 +1,56 @@
+/// Tests for the decoding of Move resources.
+///
+/// Modules are built by `pallets/sp-mvm/tests/assets/build_assets.sh`.
+use std::path::PathBuf;
+use serde_json::json;
+use move_binary_format::file_format::CompiledModule;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use sp_mvm_rpc::resource::decode_resource;
+
+fn store_module() -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/assets/user/build/assets/bytecode_modules/Store.mv");
+    std::fs::read(&path).unwrap_or_else(|_| panic!("Module {:?} should be built", path))
+}
+
+fn store_tag(name: &str) -> StructTag {
+    let id = CompiledModule::deserialize(&store_module()).unwrap().self_id();
+    StructTag {
+        address: *id.address(),
+        module: id.name().to_owned(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn get_store(id: &ModuleId) -> anyhow::Result<Vec<u8>> {
+    assert_eq!(id.name().as_str(), "Store");
+    Ok(store_module())
+}
+
+#[test]
+/// Struct fields are decoded by the layout from the module.
+fn decode_store_u64() {
+    let tag = store_tag("U64");
+    let value = decode_resource(&tag, &bcs::to_bytes(&42u64).unwrap(), get_store).unwrap();
+    assert_eq!(value, json!({ "val": "42" }));
+}
+
+#[test]
+/// Vector of bytes is rendered as hex.
+fn decode_store_vector_u8() {
+    let tag = store_tag("VectorU8");
+    let bytes = bcs::to_bytes(&vec![1u8, 2, 3]).unwrap();
+    let value = decode_resource(&tag, &bytes, get_store).unwrap();
+    assert_eq!(value, json!({ "val": "0x010203" }));
+}
+
+#[test]
+/// Truncated and oversized values are rejected.
+fn decode_invalid_resource() {
+    let tag = store_tag("U64");
+    assert!(decode_resource(&tag, &[1, 2, 3], get_store).is_err());
+    assert!(decode_resource(&tag, &[0; 9], get_store).is_err());
+    assert!(decode_resource(&store_tag("Missing"), &[0; 8], get_store).is_err());
+}
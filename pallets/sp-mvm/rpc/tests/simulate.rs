@@ -0,0 +1,70 @@
+/// Tests for the block building helpers of `mvm_simulateTransaction` and state overrides.
+use sp_mvm_rpc::simulate::{
+    check_state_overrides, next_header, PendingOutcome, StateOverride, MAX_STATE_OVERRIDES,
+};
+use sp_runtime::testing::{Header, H256};
+use sp_runtime::traits::Header as HeaderT;
+use sp_runtime::transaction_validity::InvalidTransaction;
+use sp_runtime::{Digest, DigestItem, DispatchError};
+
+#[test]
+/// New block follows the parent and keeps only its pre-runtime digests.
+fn next_block_header() {
+    let author = DigestItem::PreRuntime(*b"nmbs", vec![1, 2, 3]);
+    let parent = Header::new(
+        41,
+        H256::repeat_byte(1),
+        H256::repeat_byte(2),
+        H256::repeat_byte(3),
+        Digest {
+            logs: vec![author.clone(), DigestItem::Seal(*b"nmbs", vec![4, 5, 6])],
+        },
+    );
+    let parent_hash = H256::repeat_byte(4);
+
+    let header: Header = next_header(&parent, parent_hash);
+    assert_eq!(header.number, 42);
+    assert_eq!(header.parent_hash, parent_hash);
+    assert_eq!(header.digest.logs, vec![author]);
+}
+
+#[test]
+/// Failed dispatch is applied, invalid extrinsic is not.
+fn pending_outcomes() {
+    assert_eq!(
+        PendingOutcome::from(Ok(Ok(()))),
+        PendingOutcome {
+            applied: true,
+            error: None,
+        }
+    );
+
+    let failed = PendingOutcome::from(Ok(Err(DispatchError::Other("failed"))));
+    assert!(failed.applied);
+    assert!(failed.error.is_some());
+
+    let invalid = PendingOutcome::from(Err(InvalidTransaction::Stale.into()));
+    assert!(!invalid.applied);
+    assert!(invalid.error.unwrap().contains("Stale"));
+}
+
+#[test]
+/// Overrides are passed as JSON objects, `null` value removes the resource.
+fn state_overrides() {
+    let overrides: Vec<StateOverride<u64>> = serde_json::from_str(
+        r#"[
+            { "account": 1, "tag": "0x0102", "value": "0x2a00000000000000" },
+            { "account": 2, "tag": "0x0304", "value": null }
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        overrides[0].clone().into_parts(),
+        (1, vec![1, 2], Some(42u64.to_le_bytes().to_vec()))
+    );
+    assert_eq!(overrides[1].clone().into_parts(), (2, vec![3, 4], None));
+
+    assert!(check_state_overrides(&overrides).is_ok());
+    let too_many = vec![overrides[1].clone(); MAX_STATE_OVERRIDES + 1];
+    assert!(check_state_overrides(&too_many).is_err());
+}
@@ -0,0 +1,56 @@
+/// Tests for source maps of `mvm_getSourceMap` and abort locations of `mvm_traceExecute`.
+///
+/// Source maps are built with modules by `pallets/sp-mvm/tests/assets/build_assets.sh`.
+use std::path::PathBuf;
+use move_core_types::language_storage::ModuleId;
+use sp_mvm_rpc::source_map::SourceMap;
+use sp_mvm_rpc::trace::{trace_transaction, trace_transaction_with_source_maps};
+
+fn asset(path: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/assets/user/build/assets")
+        .join(path);
+    std::fs::read(&path).unwrap_or_else(|_| panic!("Asset {:?} should be built", path))
+}
+
+fn user_modules(id: &ModuleId) -> anyhow::Result<Vec<u8>> {
+    Ok(asset(&format!("bytecode_modules/{}.mv", id.name())))
+}
+
+fn user_source_maps(id: &ModuleId) -> Option<Vec<u8>> {
+    Some(asset(&format!("source_maps/{}.mvsm", id.name())))
+}
+
+#[test]
+/// Source maps of the compiler are decoded, instructions are located by their offsets.
+fn decode_source_map() {
+    let source_map = SourceMap::decode(&asset("source_maps/Store.mvsm")).unwrap();
+    assert!(source_map.functions() > 0);
+
+    let location = source_map.location(0, 0).unwrap();
+    assert_eq!(location.file_hash.len(), 64);
+    assert!(location.start <= location.end);
+    // Instructions after the first one take the closest preceding location.
+    assert!(source_map.location(0, u16::MAX).is_some());
+    assert!(source_map.location(u16::MAX, 0).is_none());
+
+    assert!(SourceMap::decode(&[1, 2, 3]).is_err());
+}
+
+#[test]
+/// Trace is the same with source maps, abort sites are located if the module has them.
+fn trace_with_source_maps() {
+    let tx = asset("transaction/store_u64.mvt");
+    let plain = trace_transaction(&tx, user_modules).unwrap();
+    let located =
+        trace_transaction_with_source_maps(&tx, user_modules, user_source_maps).unwrap();
+    assert_eq!(plain.calls.len(), located.calls.len());
+    assert_eq!(plain.calls[0].aborts.len(), located.calls[0].aborts.len());
+
+    for site in plain.calls.iter().flat_map(|node| &node.aborts) {
+        assert!(site.location.is_none());
+    }
+    for site in located.calls.iter().flat_map(|node| &node.aborts) {
+        assert!(site.location.is_some());
+    }
+}
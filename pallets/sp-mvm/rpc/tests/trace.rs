@@ -0,0 +1,40 @@
+/// Tests for the call tree of `mvm_traceExecute`.
+///
+/// Transactions and modules are built by `pallets/sp-mvm/tests/assets/build_assets.sh`.
+use std::path::PathBuf;
+use move_core_types::language_storage::ModuleId;
+use sp_mvm_rpc::trace::trace_transaction;
+
+fn asset(path: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/assets/user/build/assets")
+        .join(path);
+    std::fs::read(&path).unwrap_or_else(|_| panic!("Asset {:?} should be built", path))
+}
+
+fn user_modules(id: &ModuleId) -> anyhow::Result<Vec<u8>> {
+    Ok(asset(&format!("bytecode_modules/{}.mv", id.name())))
+}
+
+#[test]
+/// Script calling module function.
+fn trace_script() {
+    let tree = trace_transaction(&asset("transaction/store_u64.mvt"), user_modules).unwrap();
+    assert_eq!(tree.function, "script");
+    assert!(!tree.native);
+    assert_eq!(tree.opcodes.get("Call"), Some(&1));
+    assert_eq!(tree.instructions, tree.opcodes.values().sum::<u64>());
+
+    assert_eq!(tree.calls.len(), 1);
+    let store = &tree.calls[0];
+    assert!(store.function.ends_with("::Store::store_u64"));
+    assert_eq!(store.opcodes.get("MoveTo"), Some(&1));
+    assert!(store.calls.is_empty());
+}
+
+#[test]
+/// Missing modules fail the trace.
+fn trace_missing_module() {
+    let no_modules = |id: &ModuleId| Err(anyhow::anyhow!("Module {} not found", id));
+    assert!(trace_transaction(&asset("transaction/store_u64.mvt"), no_modules).is_err());
+}
@@ -0,0 +1,111 @@
+/// Tests for Move extrinsics by hash.
+///
+/// Transactions are built by `pallets/sp-mvm/tests/assets/build_assets.sh`.
+use std::path::PathBuf;
+use codec::{Decode, Encode};
+use sp_mvm_rpc::transaction::{transaction_key, Transaction, TransactionLocation};
+use sp_mvm_rpc_runtime::types::{MVMApiEvent, MVMApiExecutionResult, MVMApiTransaction};
+
+/// Standard library modules corpus.
+const STDLIB: &[u8] = include_bytes!("../../tests/common/move-stdlib.pac");
+
+fn transaction(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests/assets/user/build/assets/transaction")
+        .join(format!("{}.mvt", name));
+    std::fs::read(&path).unwrap_or_else(|_| panic!("Transaction {:?} should be built", path))
+}
+
+fn no_modules(id: &move_core_types::language_storage::ModuleId) -> anyhow::Result<Vec<u8>> {
+    panic!("Scripts should not request module {}", id)
+}
+
+fn location() -> TransactionLocation {
+    TransactionLocation {
+        block: 7,
+        block_hash: vec![1; 32],
+        index: 2,
+    }
+}
+
+fn api_transaction(call: &str, payload: Vec<u8>) -> MVMApiTransaction<u64> {
+    MVMApiTransaction {
+        signer: Some(42),
+        call: call.as_bytes().to_vec(),
+        payload,
+        gas_limit: 1_000_000,
+        gas_unit_price: 2,
+        success: true,
+        result: Some(MVMApiExecutionResult {
+            gas_used: 100,
+            status_code: 4001,
+            abort_code: None,
+            writes: 1,
+            events: 1,
+        }),
+        events: vec![MVMApiEvent {
+            guid: vec![0; 40],
+            ty_tag: b"0x1::Event::Test".to_vec(),
+            message: vec![42],
+        }],
+    }
+}
+
+#[test]
+/// Locations are keyed by the extrinsic hash and SCALE encoded.
+fn location_key() {
+    let key = transaction_key(&[7; 32]);
+    assert!(key.starts_with(b"mvm::tx::"));
+    assert!(key.ends_with(&[7; 32]));
+
+    let encoded = location().encode();
+    assert_eq!(TransactionLocation::decode(&mut &encoded[..]).unwrap(), location());
+}
+
+#[test]
+/// Transaction of the script execution is decoded, the outcome is reported as is.
+fn execute() {
+    let tx = api_transaction("execute", transaction("store_u64"));
+    let tx = Transaction::new(0u8, 1u8, &location(), tx, no_modules);
+
+    assert_eq!(tx.block_number, 7);
+    assert_eq!(tx.index, 2);
+    assert_eq!(tx.signer.as_deref(), Some("42"));
+    assert_eq!(tx.call, "execute");
+    assert!(tx.success);
+    assert!(tx.modules.is_empty());
+    assert_eq!(tx.decoded.expect("Transaction should be decoded").signers_count, 1);
+    assert_eq!(tx.result.unwrap().gas_used, 100);
+    assert_eq!(tx.events.len(), 1);
+    assert_eq!(tx.events[0].type_tag, "0x1::Event::Test");
+}
+
+#[test]
+/// Ids of the published modules are listed.
+fn publish() {
+    let modules: Vec<Vec<u8>> = bcs::from_bytes(STDLIB).unwrap();
+
+    let tx = api_transaction("publish_module", modules[0].clone());
+    let tx = Transaction::new(0u8, 1u8, &location(), tx, no_modules);
+    assert!(tx.decoded.is_none());
+    assert_eq!(tx.modules.len(), 1);
+    assert!(tx.modules[0].starts_with("0x1::"));
+
+    let tx = api_transaction("publish_package", STDLIB.to_vec());
+    let tx = Transaction::new(0u8, 1u8, &location(), tx, no_modules);
+    assert_eq!(tx.modules.len(), modules.len());
+}
+
+#[test]
+/// Malformed payload is returned without the decoded form.
+fn malformed_payload() {
+    let mut tx = api_transaction("execute", vec![1, 2, 3]);
+    tx.success = false;
+    tx.result = None;
+    let tx = Transaction::new(0u8, 1u8, &location(), tx, no_modules);
+
+    assert!(!tx.success);
+    assert!(tx.decoded.is_none());
+    assert!(tx.result.is_none());
+    assert_eq!(tx.payload.0, vec![1, 2, 3]);
+}
@@ -0,0 +1,21 @@
+/// Tests for errors of methods unsupported by older runtimes.
+use jsonrpc_core::ErrorCode;
+use sp_mvm_rpc::error::UNSUPPORTED_BY_RUNTIME;
+use sp_mvm_rpc::version::{unsupported_by_runtime, API_VERSION_2};
+
+#[test]
+/// Error names the method with required and implemented runtime API versions.
+fn unsupported_method() {
+    let error = unsupported_by_runtime("mvm_info", Some(1), API_VERSION_2);
+    assert_eq!(error.code, ErrorCode::ServerError(UNSUPPORTED_BY_RUNTIME));
+    assert_eq!(error.message, "Unsupported by runtime");
+    let data = error.data.unwrap();
+    assert_eq!(data["kind"], "unsupported_by_runtime");
+    let details = data["details"].as_str().unwrap();
+    assert!(details.starts_with("mvm_info requires runtime API version 2"));
+    assert!(details.ends_with("implements version 1"));
+
+    let error = unsupported_by_runtime("mvm_info", None, API_VERSION_2);
+    let details = error.data.unwrap()["details"].as_str().unwrap().to_owned();
+    assert!(details.ends_with("no Move VM runtime API"));
+}
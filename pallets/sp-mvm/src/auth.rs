@@ -0,0 +1,74 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Public key of the transaction sender for Move account models.
+//!
+//! Before the script of the signed transaction runs, the pallet stores the public key checked
+//! by the signature of the transaction (`Config::SenderKeys`) as
+//! `0x1::Auth::SenderKey { scheme: u8, public_key: vector<u8> }` resource under the sender and
+//! removes it after the script, so framework `Auth` module reads it with `borrow_global` and
+//! no native is needed. Multisig and rotating-key account models are built on it in Move:
+//! accounts compare the key to the ones they authorize instead of trusting the address.
+//!
+//! Scheme is the id of `crypto::SignatureScheme`. Transactions of `ed25519_tx` are signed with
+//! ed25519 keys of the transaction authenticator.
+use sp_std::prelude::*;
+use move_core_types::account_address::AccountAddress;
+
+use crate::crypto::SignatureScheme;
+use crate::storage::{core_struct_tag, resource_key};
+
+/// Module of the sender key struct, published under `0x1`.
+pub const AUTH_MODULE: &str = "Auth";
+/// Name of the sender key struct.
+pub const SENDER_KEY_STRUCT: &str = "SenderKey";
+
+/// Public key signed the transaction.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SenderKey {
+    pub scheme: SignatureScheme,
+    pub public_key: Vec<u8>,
+}
+
+impl SenderKey {
+    pub fn new(scheme: SignatureScheme, public_key: &[u8]) -> Self {
+        Self {
+            scheme,
+            public_key: public_key.to_vec(),
+        }
+    }
+
+    /// BCS encoded sender key resource, keys are shorter than 128 bytes, so their length fits
+    /// one byte of ULEB128.
+    pub fn to_resource(&self) -> Vec<u8> {
+        let mut resource = Vec::with_capacity(self.public_key.len() + 2);
+        resource.push(self.scheme.id());
+        resource.push(self.public_key.len() as u8);
+        resource.extend_from_slice(&self.public_key);
+        resource
+    }
+}
+
+/// Source of the public keys of the transaction senders.
+pub trait SenderKeyProvider<AccountId> {
+    /// Public key signed the current extrinsic, if it is signed by the account.
+    fn sender_key(who: &AccountId) -> Option<SenderKey>;
+}
+
+/// No sender keys: `0x1::Auth::SenderKey` is never stored.
+impl<AccountId> SenderKeyProvider<AccountId> for () {
+    fn sender_key(_: &AccountId) -> Option<SenderKey> {
+        None
+    }
+}
+
+/// BCS encoded `0x1::Auth::SenderKey` struct tag.
+pub fn sender_key_tag() -> Vec<u8> {
+    core_struct_tag(AUTH_MODULE, SENDER_KEY_STRUCT)
+}
+
+/// Storage key of the sender key resource of the account.
+pub fn sender_key_key(address: &AccountAddress) -> Vec<u8> {
+    resource_key(address, &sender_key_tag())
+}
@@ -16,6 +16,7 @@ use core::convert::TryInto;
 use move_vm::io::traits::{Balance as VmBalance, BalanceAccess};
 
 use crate::addr::address_to_account;
+use crate::currency::{self, NativeError};
 use frame_support::pallet_prelude::MaybeSerializeDeserialize;
 use frame_support::dispatch::fmt::Debug;
 use frame_support::PalletId;
@@ -136,30 +137,14 @@ where
         ticker: &[u8],
         amount: VmBalance,
     ) {
-        let currency_id = CurrencyId::try_from(ticker.to_vec());
-
         trace!("deposit native balance '{}'", PrintedTicker(ticker));
-        match currency_id {
-            Ok(id) => {
-                address_to_account::<AccountId>(address)
-                    .map_err(|_err| error!("Can't convert address from Move to Substrate."))
-                    .and_then(|address| {
-                        let amount: <Currencies as MultiCurrency<AccountId>>::Balance =
-                            amount.try_into().map_err(|_err| {
-                                error!("Can't convert VM balance to native balance type.")
-                            })?;
-                        Currencies::withdraw(id, &self.get_pallet_account(), amount)
-                            .map_err(|_err| error!("Can't withdraw from pallet"))?;
-                        Currencies::deposit(id, &address, amount)
-                            .map_err(|_err| error!("Can't deposit native balance."))
-                    })
-                    .ok();
-            }
-            Err(_) => trace!(
-                "native balance ticker '{}' not supported",
-                PrintedTicker(ticker)
-            ),
-        }
+        let result = currency::deposit::<AccountId, Currencies>(
+            &self.get_pallet_account(),
+            address,
+            ticker,
+            amount,
+        );
+        log_native_error(result, ticker, "Can't deposit native balance");
     }
 
     /// Reduce native coin balance of account.
@@ -171,34 +156,18 @@ where
         ticker: &[u8],
         amount: VmBalance,
     ) {
-        let currency_id = CurrencyId::try_from(ticker.to_vec());
-
-        match currency_id {
-            Ok(id) => {
-                trace!(
-                    "withdraw balance {} requested, amount: {}",
-                    PrintedTicker(ticker),
-                    amount
-                );
-                address_to_account::<AccountId>(address)
-                    .map_err(|_| error!("Can't convert address from Move to Substrate."))
-                    .and_then(|address| {
-                        let amount: <Currencies as MultiCurrency<AccountId>>::Balance =
-                            amount.try_into().map_err(|_err| {
-                                error!("Can't convert VM balance to native balance type.")
-                            })?;
-                        Currencies::withdraw(id, &address, amount)
-                            .map_err(|_err| error!("Can't deposit native balance."))?;
-                        Currencies::deposit(id, &self.get_pallet_account(), amount)
-                            .map_err(|_err| error!("Can't deposit to pallet"))
-                    })
-                    .ok();
-            }
-            Err(_) => trace!(
-                "native balance ticker '{}' not supported",
-                PrintedTicker(ticker)
-            ),
-        }
+        trace!(
+            "withdraw balance {} requested, amount: {}",
+            PrintedTicker(ticker),
+            amount
+        );
+        let result = currency::withdraw::<AccountId, Currencies>(
+            &self.get_pallet_account(),
+            address,
+            ticker,
+            amount,
+        );
+        log_native_error(result, ticker, "Can't withdraw native balance");
     }
 
     // Get currency total issuance using ticker.
@@ -220,6 +189,18 @@ where
     }
 }
 
+/// Log failed currency native, unsupported tickers are expected and traced only.
+fn log_native_error(result: Result<(), NativeError>, ticker: &[u8], message: &str) {
+    match result {
+        Ok(()) => {}
+        Err(NativeError::UnknownTicker) => trace!(
+            "native balance ticker '{}' not supported",
+            PrintedTicker(ticker)
+        ),
+        Err(err) => error!("{} '{}': {:?}", message, PrintedTicker(ticker), err),
+    }
+}
+
 pub mod boxed {
     use move_vm::io::{
         traits::{Balance as VmBalance, BalanceAccess},
@@ -16,6 +16,8 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{CORE_CODE_ADDRESS, ModuleId, StructTag};
 use move_vm::io::key::AccessKey;
 use sp_std::prelude::*;
+use sp_runtime::traits::Bounded;
+use frame_support::traits::Currency;
 use groupsign;
 
 use crate::benchmarking::store::container;
@@ -31,7 +33,7 @@ benchmarks! {
     where_clause { where Result<groupsign::Origin<T>, <T as frame_system::Config>::Origin>: From<<T as frame_system::Config>::Origin> }
 
     publish_empty_module {
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
         let module = include_bytes!("../tests/benchmark_assets/artifacts/modules/2_Empty.mv").to_vec();
     }: publish_module(RawOrigin::Signed(caller), module, 100_000_000)
     verify {
@@ -41,33 +43,41 @@ benchmarks! {
         for (name, module) in stdlib() {
             VMStorage::<T>::insert(module_access_core(name), module);
         }
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
         let module = include_bytes!("../tests/benchmark_assets/artifacts/modules/53_StdImport.mv").to_vec();
     }: publish_module(RawOrigin::Signed(caller), module, 100_000_000)
     verify {
         assert!(VMStorage::<T>::contains_key(module_access("StdImport")));
     }
     publish_s_module {
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
         let module = include_bytes!("../tests/benchmark_assets/artifacts/modules/6_S.mv").to_vec();
     }: publish_module(RawOrigin::Signed(caller), module, 100_000_000)
     verify {
         assert!(VMStorage::<T>::contains_key(module_access("S")));
     }
     publish_m_module {
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
         let module = include_bytes!("../tests/benchmark_assets/artifacts/modules/5_M.mv").to_vec();
     }: publish_module(RawOrigin::Signed(caller), module, 100_000_000)
     verify {
         assert!(VMStorage::<T>::contains_key(module_access("M")));
     }
     publish_l_module {
-        let caller: T::AccountId = whitelisted_caller();
+        let caller: T::AccountId = funded_caller::<T>();
         let module = include_bytes!("../tests/benchmark_assets/artifacts/modules/4_L.mv").to_vec();
     }: publish_module(RawOrigin::Signed(caller), module, 100_000_000)
     verify {
         assert!(VMStorage::<T>::contains_key(module_access("L")));
     }
+    remove_module {
+        let caller: T::AccountId = funded_caller::<T>();
+        let module = include_bytes!("../tests/benchmark_assets/artifacts/modules/4_L.mv").to_vec();
+        Mvm::<T>::publish_module(RawOrigin::Signed(caller.clone()).into(), module, 100_000_000)?;
+    }: _(RawOrigin::Signed(caller), b"L".to_vec())
+    verify {
+        assert!(!VMStorage::<T>::contains_key(module_access("L")));
+    }
     execute_many_params {
         let caller: T::AccountId = whitelisted_caller();
         let tx = include_bytes!("../tests/benchmark_assets/artifacts/transactions/many_params.mvt").to_vec();
@@ -143,6 +153,13 @@ benchmarks! {
 
 impl_benchmark_test_suite!(Mvm, crate::mock::new_test_ext(), crate::mock::Test,);
 
+/// Caller able to reserve module deposits.
+pub fn funded_caller<T: Config>() -> T::AccountId {
+    let caller: T::AccountId = whitelisted_caller();
+    T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+    caller
+}
+
 pub fn module_access_core(name: &str) -> Vec<u8> {
     ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(name).unwrap()).access_vector()
 }
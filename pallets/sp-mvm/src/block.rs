@@ -0,0 +1,39 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Block metadata for Move contracts.
+//!
+//! Block height and timestamp are passed to Move VM with the execution context of every call
+//! and read by the framework natives (`PontBlock::get_current_block_height`,
+//! `PontTimestamp::now_microseconds`). Parent hash isn't part of the context, so every block
+//! the pallet stores it as `0x1::BlockMetadata::ParentHash { hash: vector<u8>, block: u64 }`
+//! resource under `0x1`, readable with `borrow_global`.
+use sp_std::prelude::*;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+
+use crate::storage::{core_struct_tag, resource_key};
+
+/// Module of the block metadata structs, published under `0x1`.
+pub const BLOCK_METADATA_MODULE: &str = "BlockMetadata";
+/// Name of the parent hash struct.
+pub const PARENT_HASH_STRUCT: &str = "ParentHash";
+
+/// BCS encoded `0x1::BlockMetadata::ParentHash` struct tag.
+pub fn parent_hash_tag() -> Vec<u8> {
+    core_struct_tag(BLOCK_METADATA_MODULE, PARENT_HASH_STRUCT)
+}
+
+/// Storage key of the parent hash resource.
+pub fn parent_hash_key() -> Vec<u8> {
+    resource_key(&CORE_CODE_ADDRESS, &parent_hash_tag())
+}
+
+/// BCS encoded parent hash resource, its length fits one byte of ULEB128.
+pub fn parent_hash_resource(hash: &[u8], block: u64) -> Vec<u8> {
+    let mut resource = Vec::with_capacity(hash.len() + 9);
+    resource.push(hash.len() as u8);
+    resource.extend_from_slice(hash);
+    resource.extend_from_slice(&block.to_le_bytes());
+    resource
+}
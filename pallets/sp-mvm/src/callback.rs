@@ -0,0 +1,182 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Callbacks of Move code executed by the runtime.
+//!
+//! Reactive contracts (e.g. auto-compounding vaults) subscribe a callback script by emitting
+//! `0x1::Callback::SubscribeRequest` event with the handle created for the subscriber:
+//! ```move
+//! struct SubscribeRequest { trigger: u8, callback: vector<u8>, gas_limit: u64 }
+//! struct UnsubscribeRequest { trigger: u8 }
+//! ```
+//! Only `0x1` modules construct the requests, so the framework `Callback` module gates them
+//! with its subscriber capability. One callback is subscribed per trigger (`CallbackTrigger`):
+//!     * 0 - every block initialization.
+//!     * 1 - deposit: free native balance of the subscriber grew since the last check.
+//!
+//! Triggers are checked at the block initialization and the callback scripts (built by
+//! `dove tx`, e.g. an entry function call) are executed signed by the subscriber while their
+//! gas limits fit the `MaxCallbackGas` budget of the block. Callbacks not fitting the budget
+//! are skipped, deposits stay pending until their callback is executed.
+//!
+//! Subscriptions reserve `CallbackDeposit` from the subscriber (released on unsubscribe) and
+//! every execution withdraws the fee of the gas limit priced by `CallbackGasUnitPrice`, so
+//! callbacks of subscribers unable to pay are skipped.
+use sp_std::prelude::*;
+use move_core_types::account_address::AccountAddress;
+
+use crate::event::{MessageReader, MoveEventArguments};
+use crate::types::CallbackTrigger;
+
+/// Module of the subscription request structs, published under `0x1`.
+pub const CALLBACK_MODULE: &str = "Callback";
+/// Name of the subscribe request struct.
+pub const SUBSCRIBE_REQUEST: &str = "SubscribeRequest";
+/// Name of the unsubscribe request struct.
+pub const UNSUBSCRIBE_REQUEST: &str = "UnsubscribeRequest";
+
+/// Trigger of the Move id, `None` for unknown ids.
+fn trigger(id: u8) -> Option<CallbackTrigger> {
+    match id {
+        0 => Some(CallbackTrigger::Block),
+        1 => Some(CallbackTrigger::Deposit),
+        _ => None,
+    }
+}
+
+/// Subscribe request emitted by Move code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SubscribeRequest {
+    /// Subscriber address, owner of the event handle.
+    pub address: AccountAddress,
+    /// Trigger of the callback.
+    pub trigger: CallbackTrigger,
+    /// Script executed signed by the subscriber once triggered.
+    pub callback: Vec<u8>,
+    /// Gas limit of the callback.
+    pub gas_limit: u64,
+}
+
+impl SubscribeRequest {
+    /// Converts Move event into the subscribe request.
+    ///
+    /// Returns `None` if event isn't `0x1::Callback::SubscribeRequest` or can't be decoded.
+    pub fn from_event(e: &MoveEventArguments) -> Option<Self> {
+        let address = e.core_request(CALLBACK_MODULE, SUBSCRIBE_REQUEST)?;
+
+        // BCS encoded struct: u8, vector<u8>, u64.
+        let mut message = MessageReader::new(&e.message);
+        let request = Self {
+            address,
+            trigger: trigger(message.read_u8()?)?,
+            callback: message.read_bytes()?,
+            gas_limit: message.read_u64()?,
+        };
+        if !message.is_empty() {
+            return None;
+        }
+        Some(request)
+    }
+}
+
+/// Unsubscribe request emitted by Move code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnsubscribeRequest {
+    /// Subscriber address, owner of the event handle.
+    pub address: AccountAddress,
+    /// Trigger of the removed callback.
+    pub trigger: CallbackTrigger,
+}
+
+impl UnsubscribeRequest {
+    /// Converts Move event into the unsubscribe request.
+    ///
+    /// Returns `None` if event isn't `0x1::Callback::UnsubscribeRequest` or can't be decoded.
+    pub fn from_event(e: &MoveEventArguments) -> Option<Self> {
+        let address = e.core_request(CALLBACK_MODULE, UNSUBSCRIBE_REQUEST)?;
+
+        let mut message = MessageReader::new(&e.message);
+        let trigger = trigger(message.read_u8()?)?;
+        if !message.is_empty() {
+            return None;
+        }
+        Some(Self { address, trigger })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS};
+
+    use super::*;
+
+    fn request_event(
+        address: AccountAddress,
+        name: &str,
+        message: Vec<u8>,
+    ) -> MoveEventArguments {
+        MoveEventArguments {
+            guid: [0u64.to_le_bytes().to_vec(), address.to_vec()].concat(),
+            ty_tag: TypeTag::Struct(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(CALLBACK_MODULE).unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_params: vec![],
+            }),
+            message,
+        }
+    }
+
+    fn subscribe_message(trigger: u8, callback: &[u8], gas: u64) -> Vec<u8> {
+        let mut message = vec![trigger, callback.len() as u8];
+        message.extend_from_slice(callback);
+        message.extend_from_slice(&gas.to_le_bytes());
+        message
+    }
+
+    #[test]
+    fn decode_subscribe_request() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let message = subscribe_message(1, &[4, 5], 50_000);
+
+        assert_eq!(
+            SubscribeRequest::from_event(&request_event(address, SUBSCRIBE_REQUEST, message)),
+            Some(SubscribeRequest {
+                address,
+                trigger: CallbackTrigger::Deposit,
+                callback: vec![4, 5],
+                gas_limit: 50_000,
+            })
+        );
+    }
+
+    #[test]
+    fn decode_unsubscribe_request() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+
+        assert_eq!(
+            UnsubscribeRequest::from_event(&request_event(address, UNSUBSCRIBE_REQUEST, vec![0])),
+            Some(UnsubscribeRequest {
+                address,
+                trigger: CallbackTrigger::Block,
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_trigger() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let message = subscribe_message(2, &[], 0);
+
+        assert_eq!(
+            SubscribeRequest::from_event(&request_event(address, SUBSCRIBE_REQUEST, message)),
+            None
+        );
+        assert_eq!(
+            UnsubscribeRequest::from_event(&request_event(address, UNSUBSCRIBE_REQUEST, vec![2])),
+            None
+        );
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Signature schemes of the keys signing Move transactions.
+//!
+//! Move code sees the scheme as `u8` id, e.g. in `0x1::Auth::SenderKey` (see `auth`).
+
+/// Signature scheme, Move code passes it as `u8`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SignatureScheme {
+    Sr25519,
+    Ed25519,
+    /// Secp256k1 ecdsa with blake2b-256 hashed message, 65 bytes signature, 33 bytes public key.
+    Ecdsa,
+}
+
+impl SignatureScheme {
+    /// Converts Move scheme id.
+    pub fn from_u8(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Sr25519),
+            1 => Some(Self::Ed25519),
+            2 => Some(Self::Ecdsa),
+            _ => None,
+        }
+    }
+
+    /// Move scheme id.
+    pub fn id(self) -> u8 {
+        match self {
+            Self::Sr25519 => 0,
+            Self::Ed25519 => 1,
+            Self::Ecdsa => 2,
+        }
+    }
+}
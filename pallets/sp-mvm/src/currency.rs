@@ -0,0 +1,104 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Currency operations behind `BalancesAdapter`.
+//!
+//! Move framework moves PONT and registered tokens with the balance natives of Move VM
+//! (`sp-move-vm`), which call `BalanceAccess` of the `BalancesAdapter`, backed by the functions
+//! below. Coins deposited into Move are held by the pallet account, so total issuance doesn't
+//! change.
+//!
+//! Currency is selected by Move ticker (e.g. `NOX`, `KSM`), see `CurrencyId::try_from`:
+//!     * deposit - move coins from the pallet account to the account (withdrawn from Move).
+//!     * withdraw - move coins from the account to the pallet account (deposited to Move).
+use core::convert::TryFrom;
+use core::convert::TryInto;
+use move_core_types::account_address::AccountAddress;
+use move_vm::io::traits::Balance as VmBalance;
+use orml_traits::MultiCurrency;
+use parity_scale_codec::Decode;
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+use crate::addr::address_to_account;
+
+/// Errors of the currency operations.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum NativeError {
+    /// Ticker doesn't match any currency.
+    UnknownTicker,
+    /// Move address can't be converted to the account.
+    InvalidAddress,
+    /// Amount doesn't fit the currency balance type.
+    InvalidAmount,
+    /// Currency operation failed, e.g. insufficient or locked balance.
+    Currency(DispatchError),
+}
+
+/// Currency id of the Move ticker.
+pub fn currency_id<CurrencyId>(ticker: &[u8]) -> Result<CurrencyId, NativeError>
+where
+    CurrencyId: TryFrom<Vec<u8>>,
+{
+    CurrencyId::try_from(ticker.to_vec()).map_err(|_| NativeError::UnknownTicker)
+}
+
+/// Deposit `amount` of the currency to the account from the pallet account.
+pub fn deposit<AccountId, Currencies>(
+    pallet_account: &AccountId,
+    address: &AccountAddress,
+    ticker: &[u8],
+    amount: VmBalance,
+) -> Result<(), NativeError>
+where
+    AccountId: Decode,
+    Currencies: MultiCurrency<AccountId>,
+    Currencies::CurrencyId: TryFrom<Vec<u8>>,
+    Currencies::Balance: TryFrom<VmBalance>,
+{
+    let (id, account, amount) = convert::<AccountId, Currencies>(address, ticker, amount)?;
+
+    Currencies::withdraw(id, pallet_account, amount).map_err(NativeError::Currency)?;
+    Currencies::deposit(id, &account, amount).map_err(NativeError::Currency)
+}
+
+/// Withdraw `amount` of the currency from the account to the pallet account.
+pub fn withdraw<AccountId, Currencies>(
+    pallet_account: &AccountId,
+    address: &AccountAddress,
+    ticker: &[u8],
+    amount: VmBalance,
+) -> Result<(), NativeError>
+where
+    AccountId: Decode,
+    Currencies: MultiCurrency<AccountId>,
+    Currencies::CurrencyId: TryFrom<Vec<u8>>,
+    Currencies::Balance: TryFrom<VmBalance>,
+{
+    let (id, account, amount) = convert::<AccountId, Currencies>(address, ticker, amount)?;
+
+    Currencies::withdraw(id, &account, amount).map_err(NativeError::Currency)?;
+    Currencies::deposit(id, pallet_account, amount).map_err(NativeError::Currency)
+}
+
+/// Convert Move arguments of the operation to the currency id, account and amount.
+#[allow(clippy::type_complexity)]
+fn convert<AccountId, Currencies>(
+    address: &AccountAddress,
+    ticker: &[u8],
+    amount: VmBalance,
+) -> Result<(Currencies::CurrencyId, AccountId, Currencies::Balance), NativeError>
+where
+    AccountId: Decode,
+    Currencies: MultiCurrency<AccountId>,
+    Currencies::CurrencyId: TryFrom<Vec<u8>>,
+    Currencies::Balance: TryFrom<VmBalance>,
+{
+    let id = currency_id::<Currencies::CurrencyId>(ticker)?;
+    let account =
+        address_to_account::<AccountId>(address).map_err(|_| NativeError::InvalidAddress)?;
+    let amount = amount.try_into().map_err(|_| NativeError::InvalidAmount)?;
+
+    Ok((id, account, amount))
+}
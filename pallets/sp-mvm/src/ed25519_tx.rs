@@ -0,0 +1,186 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Move transactions signed with ed25519 keys.
+//!
+//! Users with ed25519 keys submit BCS encoded signed transaction with
+//! `submit_ed25519_transaction` unsigned extrinsic, without Substrate keys. The envelope borrows
+//! the layout and signing scheme of Aptos `SignedTransaction`, but the payload is a `dove tx`
+//! transaction, not Aptos `TransactionPayload`, so Aptos wallets can't produce these transactions:
+//!     * raw transaction is sender, sequence number, payload, max gas amount, gas unit price,
+//!       expiration timestamp in seconds and chain id, payload is BCS `vector<u8>` of the script
+//!       transaction built by `dove tx`.
+//!     * authenticator is ed25519 one (variant `0`, public key and signature),
+//!       the signed message is `sha3_256("APTOS::RawTransaction") | raw transaction`.
+//!     * sender is the authentication key of the public key, `sha3_256(public key | 0x00)`,
+//!       the script is executed signed by the sender address.
+use sp_std::prelude::*;
+use sp_core::ed25519;
+use sha3::{Digest, Sha3_256};
+
+/// Salt of the signed raw transaction.
+pub const RAW_TRANSACTION_SALT: &[u8] = b"APTOS::RawTransaction";
+/// Ed25519 authentication key scheme.
+pub const ED25519_SCHEME: u8 = 0;
+/// Ed25519 variant of the transaction authenticator.
+const ED25519_AUTHENTICATOR: u8 = 0;
+
+/// Raw transaction with Move script transaction as payload.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RawTransaction {
+    pub sender: [u8; 32],
+    pub sequence_number: u64,
+    pub payload: Vec<u8>,
+    pub max_gas_amount: u64,
+    pub gas_unit_price: u64,
+    pub expiration_timestamp_secs: u64,
+    pub chain_id: u8,
+}
+
+impl RawTransaction {
+    /// BCS encoded raw transaction.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.payload.len() + 70);
+        bytes.extend_from_slice(&self.sender);
+        bytes.extend_from_slice(&self.sequence_number.to_le_bytes());
+        write_bytes(&mut bytes, &self.payload);
+        bytes.extend_from_slice(&self.max_gas_amount.to_le_bytes());
+        bytes.extend_from_slice(&self.gas_unit_price.to_le_bytes());
+        bytes.extend_from_slice(&self.expiration_timestamp_secs.to_le_bytes());
+        bytes.push(self.chain_id);
+        bytes
+    }
+
+    /// Message signed by the sender.
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut message = Sha3_256::digest(RAW_TRANSACTION_SALT).to_vec();
+        message.extend_from_slice(&self.encode());
+        message
+    }
+
+    fn decode(reader: &mut Reader) -> Option<Self> {
+        Some(Self {
+            sender: reader.array()?,
+            sequence_number: reader.u64()?,
+            payload: reader.bytes()?.to_vec(),
+            max_gas_amount: reader.u64()?,
+            gas_unit_price: reader.u64()?,
+            expiration_timestamp_secs: reader.u64()?,
+            chain_id: reader.u8()?,
+        })
+    }
+}
+
+/// Raw transaction signed with ed25519 key.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SignedTransaction {
+    pub raw: RawTransaction,
+    pub public_key: [u8; 32],
+    pub signature: [u8; 64],
+}
+
+impl SignedTransaction {
+    /// Decodes BCS encoded signed transaction, `None` if malformed or not ed25519 signed.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        let raw = RawTransaction::decode(&mut reader)?;
+        if reader.u8()? != ED25519_AUTHENTICATOR {
+            return None;
+        }
+        let public_key = reader.bytes()?.try_into().ok()?;
+        let signature = reader.bytes()?.try_into().ok()?;
+
+        if !reader.0.is_empty() {
+            return None;
+        }
+        Some(Self {
+            raw,
+            public_key,
+            signature,
+        })
+    }
+
+    /// BCS encoded signed transaction.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = self.raw.encode();
+        bytes.push(ED25519_AUTHENTICATOR);
+        write_bytes(&mut bytes, &self.public_key);
+        write_bytes(&mut bytes, &self.signature);
+        bytes
+    }
+
+    /// Checks the transaction is signed by the sender.
+    pub fn verify(&self) -> bool {
+        authentication_key(&self.public_key) == self.raw.sender
+            && sp_io::crypto::ed25519_verify(
+                &ed25519::Signature(self.signature),
+                &self.raw.signing_message(),
+                &ed25519::Public(self.public_key),
+            )
+    }
+}
+
+/// Authentication key (sender address) of the ed25519 public key.
+pub fn authentication_key(public_key: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    hasher.update(public_key);
+    hasher.update([ED25519_SCHEME]);
+    hasher.finalize().into()
+}
+
+/// Gas limit of the signed transaction, zero if malformed.
+pub fn max_gas_amount(signed_tx: &[u8]) -> u64 {
+    SignedTransaction::decode(signed_tx)
+        .map(|tx| tx.raw.max_gas_amount)
+        .unwrap_or_default()
+}
+
+/// Writes ULEB128 length prefixed bytes.
+fn write_bytes(bytes: &mut Vec<u8>, value: &[u8]) {
+    let mut len = value.len();
+    while len >= 0x80 {
+        bytes.push((len as u8 & 0x7f) | 0x80);
+        len >>= 7;
+    }
+    bytes.push(len as u8);
+    bytes.extend_from_slice(value);
+}
+
+/// Reader of BCS encoded values.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (value, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(value)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|value| value[0])
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let mut len = 0usize;
+        for shift in (0..32).step_by(7) {
+            let byte = self.u8()?;
+            len |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return self.take(len);
+            }
+        }
+        None
+    }
+}
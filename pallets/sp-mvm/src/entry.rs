@@ -0,0 +1,204 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Checks of script and entry function calls against their signatures before execution.
+//!
+//! Amount of type arguments, arguments and signers and BCS layout of primitive arguments are
+//! checked with the script or the stored module, so common mistakes fail with the same status
+//! as Move VM would return, but without loading the VM and burning gas.
+//! Arguments of other types (structs) are left to Move VM.
+use sp_std::prelude::*;
+use move_binary_format::access::{ModuleAccess, ScriptAccess};
+use move_binary_format::file_format::{
+    CompiledModule, CompiledScript, FunctionDefinition, SignatureToken, Visibility,
+};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::IdentStr;
+use move_core_types::vm_status::StatusCode;
+
+/// Checks the script call: `type_args` and `signers` are amounts of type arguments and signers,
+/// `args` are BCS encoded arguments.
+pub fn check_script(
+    code: &[u8],
+    type_args: usize,
+    args: &[Vec<u8>],
+    signers: Option<usize>,
+) -> Result<(), StatusCode> {
+    let script =
+        CompiledScript::deserialize(code).map_err(|_| StatusCode::CODE_DESERIALIZATION_ERROR)?;
+    check_call(
+        &script.signature_at(script.parameters).0,
+        script.type_parameters.len(),
+        type_args,
+        args,
+        signers,
+    )
+}
+
+/// Checks the call of the entry (`public(script)`) function of the module bytecode.
+pub fn check_entry_function(
+    module: &[u8],
+    name: &IdentStr,
+    type_args: usize,
+    args: &[Vec<u8>],
+    signers: Option<usize>,
+) -> Result<(), StatusCode> {
+    let module =
+        CompiledModule::deserialize(module).map_err(|_| StatusCode::CODE_DESERIALIZATION_ERROR)?;
+    let def = function_def(&module, name)?;
+    if def.visibility != Visibility::Script {
+        return Err(StatusCode::EXECUTE_SCRIPT_FUNCTION_CALLED_ON_NON_SCRIPT_VISIBLE);
+    }
+
+    let handle = module.function_handle_at(def.function);
+    check_call(
+        &module.signature_at(handle.parameters).0,
+        handle.type_parameters.len(),
+        type_args,
+        args,
+        signers,
+    )
+}
+
+/// Checks the call of the view-style function of the module bytecode (see `is_view`).
+pub fn check_view_function(
+    module: &[u8],
+    name: &IdentStr,
+    type_args: usize,
+    args: &[Vec<u8>],
+) -> Result<(), StatusCode> {
+    let module =
+        CompiledModule::deserialize(module).map_err(|_| StatusCode::CODE_DESERIALIZATION_ERROR)?;
+    let def = function_def(&module, name)?;
+    if !is_view(&module, def) {
+        return Err(StatusCode::FUNCTION_RESOLUTION_FAILURE);
+    }
+
+    let handle = module.function_handle_at(def.function);
+    check_call(
+        &module.signature_at(handle.parameters).0,
+        handle.type_parameters.len(),
+        type_args,
+        args,
+        Some(0),
+    )
+}
+
+/// Checks if the function is view-style: public, without signer or reference parameters and
+/// returning values (not references). Bytecode has no attributes, so `#[view]` is inferred
+/// from the signature.
+pub fn is_view(module: &CompiledModule, def: &FunctionDefinition) -> bool {
+    let handle = module.function_handle_at(def.function);
+    let params = &module.signature_at(handle.parameters).0;
+    let returns = &module.signature_at(handle.return_).0;
+    def.visibility == Visibility::Public
+        && !params.iter().any(|param| is_signer(param) || is_reference(param))
+        && !returns.is_empty()
+        && !returns.iter().any(is_reference)
+}
+
+fn function_def<'a>(
+    module: &'a CompiledModule,
+    name: &IdentStr,
+) -> Result<&'a FunctionDefinition, StatusCode> {
+    module
+        .function_defs()
+        .iter()
+        .find(|def| module.identifier_at(module.function_handle_at(def.function).name) == name)
+        .ok_or(StatusCode::FUNCTION_RESOLUTION_FAILURE)
+}
+
+/// Checks arguments against the parameters, signers are the leading parameters.
+/// Signers are not checked if `signers` is `None` (e.g. root signed transactions).
+fn check_call(
+    params: &[SignatureToken],
+    type_params: usize,
+    type_args: usize,
+    args: &[Vec<u8>],
+    signers: Option<usize>,
+) -> Result<(), StatusCode> {
+    if type_params != type_args {
+        return Err(StatusCode::NUMBER_OF_TYPE_ARGUMENTS_MISMATCH);
+    }
+
+    let signer_params = params.iter().take_while(|param| is_signer(param)).count();
+    if signers.map_or(false, |signers| signers != signer_params) {
+        return Err(StatusCode::NUMBER_OF_SIGNER_ARGUMENTS_MISMATCH);
+    }
+
+    let params = &params[signer_params..];
+    if params.len() != args.len() {
+        return Err(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH);
+    }
+    for (param, arg) in params.iter().zip(args) {
+        let valid = match skip_value(param, arg) {
+            Ok(Some(rest)) => rest.is_empty(),
+            Ok(None) => true,
+            Err(()) => false,
+        };
+        if !valid {
+            return Err(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT);
+        }
+    }
+    Ok(())
+}
+
+fn is_signer(param: &SignatureToken) -> bool {
+    match param {
+        SignatureToken::Signer => true,
+        SignatureToken::Reference(inner) => matches!(**inner, SignatureToken::Signer),
+        _ => false,
+    }
+}
+
+fn is_reference(param: &SignatureToken) -> bool {
+    matches!(param, SignatureToken::Reference(_) | SignatureToken::MutableReference(_))
+}
+
+/// Skips BCS encoded value of the type, returns the rest of the bytes,
+/// `None` if the type isn't checked here.
+fn skip_value<'a>(param: &SignatureToken, bytes: &'a [u8]) -> Result<Option<&'a [u8]>, ()> {
+    let size = match param {
+        SignatureToken::Bool => {
+            return match bytes.split_first() {
+                Some((0 | 1, rest)) => Ok(Some(rest)),
+                _ => Err(()),
+            }
+        }
+        SignatureToken::U8 => 1,
+        SignatureToken::U64 => 8,
+        SignatureToken::U128 => 16,
+        SignatureToken::Address => AccountAddress::LENGTH,
+        SignatureToken::Vector(inner) => {
+            let (len, mut rest) = read_uleb128(bytes)?;
+            for _ in 0..len {
+                rest = match skip_value(inner, rest)? {
+                    Some(rest) => rest,
+                    None => return Ok(None),
+                };
+            }
+            return Ok(Some(rest));
+        }
+        _ => return Ok(None),
+    };
+    if bytes.len() < size {
+        return Err(());
+    }
+    Ok(Some(&bytes[size..]))
+}
+
+/// Reads ULEB128 encoded length of BCS sequence (at most `u32::MAX`).
+fn read_uleb128(bytes: &[u8]) -> Result<(u64, &[u8]), ()> {
+    let mut value = 0u64;
+    for (index, byte) in bytes.iter().enumerate().take(5) {
+        value |= ((byte & 0x7f) as u64) << (7 * index);
+        if byte & 0x80 == 0 {
+            if value > u32::MAX as u64 {
+                return Err(());
+            }
+            return Ok((value, &bytes[index + 1..]));
+        }
+    }
+    Err(())
+}
@@ -3,10 +3,13 @@
 // Apache 2.0
 
 //! Implement support of Move VM events inside Substrate.
-use core::convert::TryInto;
+use core::convert::{TryFrom, TryInto};
 use move_vm::io::traits::EventHandler;
 use sp_std::prelude::*;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{TypeTag, CORE_CODE_ADDRESS};
+use parity_scale_codec_derive::{Encode, Decode};
+use sp_runtime::traits::Hash;
 use crate::{Event, Config};
 
 #[cfg(not(feature = "std"))]
@@ -32,12 +35,151 @@ pub struct MoveEventArguments {
     pub message: Vec<u8>,
 }
 
+/// Move VM event record written into the offchain index.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub struct MoveEventRecord {
+    /// Event GUID.
+    pub guid: Vec<u8>,
+    /// Move VM type stored into event, encoded as String.
+    pub ty_tag: Vec<u8>,
+    /// Event message.
+    pub message: Vec<u8>,
+}
+
+impl From<&MoveEventArguments> for MoveEventRecord {
+    fn from(e: &MoveEventArguments) -> Self {
+        Self {
+            guid: e.guid.clone(),
+            ty_tag: e.ty_tag_string(),
+            message: e.message.clone(),
+        }
+    }
+}
+
+/// Keys of the Move events in the offchain index.
+///
+/// Events are indexed per block: `events_count_key(block)` contains amount of events (u32),
+/// each event is stored under `event_key(block, index)`.
+/// Node reads, archives and prunes them according to retention policy.
+pub mod offchain {
+    use sp_std::prelude::*;
+
+    /// Prefix of the indexed event key.
+    pub const EVENT_PREFIX: &[u8] = b"mvm::event::";
+    /// Prefix of the indexed events count key.
+    pub const EVENTS_COUNT_PREFIX: &[u8] = b"mvm::events_count::";
+
+    /// Key of the event with `index` emitted in `block`.
+    pub fn event_key(block: u64, index: u32) -> Vec<u8> {
+        let mut key = EVENT_PREFIX.to_vec();
+        key.extend_from_slice(&block.to_be_bytes());
+        key.extend_from_slice(&index.to_be_bytes());
+        key
+    }
+
+    /// Key of the events count in `block`.
+    pub fn events_count_key(block: u64) -> Vec<u8> {
+        let mut key = EVENTS_COUNT_PREFIX.to_vec();
+        key.extend_from_slice(&block.to_be_bytes());
+        key
+    }
+}
+
+impl MoveEventArguments {
+    /// Struct tag of the event, encoded as String, the same as in `Event::Event`.
+    pub fn ty_tag_string(&self) -> Vec<u8> {
+        format!("{}", self.ty_tag).as_bytes().to_vec()
+    }
+
+    /// Topic of the event: hash of the type tag string.
+    ///
+    /// Clients select Move events by type with `frame_system::EventTopics`,
+    /// e.g. `blake2_256(b"0x1::Coin::DepositEvent")`, without decoding every event.
+    pub fn topic<H: Hash>(&self) -> H::Output {
+        H::hash(&self.ty_tag_string())
+    }
+
+    /// Address of the event handle creator, if the event is `0x1::<module>::<name>` struct.
+    ///
+    /// Only `0x1` modules can construct such events, and the handle is created with the signer,
+    /// so the pallet can treat the event as a request signed by the creator.
+    pub fn core_request(&self, module: &str, name: &str) -> Option<AccountAddress> {
+        match &self.ty_tag {
+            TypeTag::Struct(tag)
+                if tag.address == CORE_CODE_ADDRESS
+                    && tag.module.as_str() == module
+                    && tag.name.as_str() == name => {}
+            _ => return None,
+        }
+
+        // Event GUID ends with the address of the handle creator.
+        let creator = self.guid.len().checked_sub(AccountAddress::LENGTH)?;
+        AccountAddress::from_bytes(&self.guid[creator..]).ok()
+    }
+}
+
+/// Reader of the BCS encoded Move event message.
+pub struct MessageReader<'a>(&'a [u8]);
+
+impl<'a> MessageReader<'a> {
+    /// New reader of the message.
+    pub fn new(message: &'a [u8]) -> Self {
+        Self(message)
+    }
+
+    /// Whether the whole message is read.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Read `len` bytes.
+    pub fn read_raw(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    /// Read `u8`.
+    pub fn read_u8(&mut self) -> Option<u8> {
+        self.read_raw(1).map(|bytes| bytes[0])
+    }
+
+    /// Read `u64`.
+    pub fn read_u64(&mut self) -> Option<u64> {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(self.read_raw(8)?);
+        Some(u64::from_le_bytes(buf))
+    }
+
+    /// Read ULEB128 encoded length of the vector.
+    pub fn read_len(&mut self) -> Option<usize> {
+        let mut value: u64 = 0;
+        for shift in (0..32).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return u32::try_from(value).ok().map(|len| len as usize);
+            }
+        }
+        None
+    }
+
+    /// Read `vector<u8>`.
+    pub fn read_bytes(&mut self) -> Option<Vec<u8>> {
+        let len = self.read_len()?;
+        self.read_raw(len).map(<[u8]>::to_vec)
+    }
+}
+
 impl<T: Config> TryInto<Event<T>> for MoveEventArguments {
     type Error = parity_scale_codec::Error;
 
     /// Convert Move VM event into pallet one.
     fn try_into(self) -> Result<Event<T>, Self::Error> {
-        let ty_tag_enc = format!("{}", self.ty_tag).as_bytes().to_vec();
+        let ty_tag_enc = self.ty_tag_string();
         Ok(Event::Event(self.guid, ty_tag_enc, self.message))
     }
 }
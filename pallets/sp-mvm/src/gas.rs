@@ -6,7 +6,18 @@
 //!
 //! Move VM uses a similar gas model to EVM.
 //! As we are using Substrate we should allow us to convert gas to weight, and weight to gas.
-use frame_support::weights::Weight;
+use frame_support::weights::{Weight, constants::WEIGHT_PER_MILLIS};
+use parity_scale_codec_derive::{Encode, Decode};
+use scale_info::TypeInfo;
+
+/// Version of the gas schedule, bump it once gas mapping or Move VM cost table is changed.
+pub const GAS_SCHEDULE_VERSION: u32 = 2;
+
+/// Default gas of the first access to a storage item within a VM call.
+pub const COLD_STORAGE_ACCESS_GAS: u64 = 200;
+
+/// Default gas of the next accesses to the storage item within the same VM call.
+pub const WARM_STORAGE_ACCESS_GAS: u64 = 10;
 
 /// A mapping function that converts Move VM gas to Substrate weight.
 pub trait GasWeightMapping {
@@ -16,3 +27,44 @@ pub trait GasWeightMapping {
     /// Convert weight to gas.
     fn weight_to_gas(weight: Weight) -> u64;
 }
+
+/// Limits gas by the execution deadline (in milliseconds), so execution stops with out of gas
+/// once the deadline is reached. Deadline is converted to gas with the provided mapping.
+pub fn deadline_gas_limit<M: GasWeightMapping>(gas_limit: u64, deadline_ms: Option<u64>) -> u64 {
+    match deadline_ms {
+        Some(ms) => gas_limit.min(M::weight_to_gas(WEIGHT_PER_MILLIS.saturating_mul(ms))),
+        None => gas_limit,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Gas of the storage accesses charged on top of the Move VM gas, EIP-2929 style: the first
+/// access to a storage item within a VM call is cold (loaded from the trie), the next ones are
+/// warm (served by the overlay).
+pub struct StorageGasSchedule {
+    /// Gas of the first access to the storage item.
+    pub cold_access: u64,
+    /// Gas of every next access to the storage item.
+    pub warm_access: u64,
+}
+
+impl Default for StorageGasSchedule {
+    fn default() -> Self {
+        StorageGasSchedule {
+            cold_access: COLD_STORAGE_ACCESS_GAS,
+            warm_access: WARM_STORAGE_ACCESS_GAS,
+        }
+    }
+}
+
+impl StorageGasSchedule {
+    /// Gas of `accesses` to one storage item, zero accesses are free.
+    pub fn item_gas(&self, accesses: u32) -> u64 {
+        match accesses {
+            0 => 0,
+            n => self
+                .cold_access
+                .saturating_add(self.warm_access.saturating_mul(n as u64 - 1)),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Gas price of Move extrinsics.
+//!
+//! The gas unit price is the field of `ChargeGasPrice` signed extension (as the tip of the
+//! transaction payment), Move extrinsics take only `gas_limit`. The extension raises the
+//! transaction pool priority of the Move extrinsic by its gas unit price, so Move transactions
+//! paying more per gas unit are included first, and charges the tip of
+//! `gas_limit * gas_unit_price` before the dispatch. The price is ignored for other calls.
+//! The tip is charged for the whole gas limit (as the transaction payment tip, it's not
+//! refunded), so gas limit should be estimated with `mvm_estimateGas*` RPC.
+//! Tips are handled by `OnGasTip`, e.g. given to the block author.
+//!
+//! Only Move calls submitted directly are matched, calls wrapped into other calls
+//! (e.g. groupsign) have the default priority and pay no tip.
+use core::marker::PhantomData;
+use frame_support::traits::{Currency, ExistenceRequirement, IsSubType, OnUnbalanced};
+use frame_support::traits::WithdrawReasons;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{DispatchInfoOf, SignedExtension, UniqueSaturatedInto, Saturating};
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+};
+use frame_system::pallet_prelude::OriginFor;
+
+use crate::types::GasUnitPrice;
+use crate::{BalanceOf, Call, Config};
+
+/// Signed extension prioritizing Move extrinsics by their gas unit price and charging the tip.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeGasPrice<T: Config + Send + Sync>(
+    #[codec(compact)] GasUnitPrice,
+    PhantomData<T>,
+);
+
+impl<T: Config + Send + Sync> ChargeGasPrice<T> {
+    /// Utility constructor, used only in client/factory code.
+    pub fn from(gas_unit_price: GasUnitPrice) -> Self {
+        ChargeGasPrice(gas_unit_price, PhantomData)
+    }
+
+    /// Gas unit price of the extrinsic.
+    pub fn gas_unit_price(&self) -> GasUnitPrice {
+        self.0
+    }
+}
+
+impl<T: Config + Send + Sync> Default for ChargeGasPrice<T> {
+    fn default() -> Self {
+        Self::from(0)
+    }
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for ChargeGasPrice<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "ChargeGasPrice<{:?}>", self.0)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+/// Gas limit of the Move extrinsic, `None` for other calls.
+pub fn gas_limit_of<T: Config>(call: &Call<T>) -> Option<u64>
+where
+    OriginFor<T>: Into<Result<groupsign::Origin<T>, OriginFor<T>>>,
+{
+    match call {
+        Call::execute { gas_limit, .. }
+        | Call::execute_with_sequence { gas_limit, .. }
+        | Call::publish_module { gas_limit, .. }
+        | Call::publish_package { gas_limit, .. }
+        | Call::publish_package_with_metadata { gas_limit, .. }
+        | Call::publish_staged_module { gas_limit }
+        | Call::publish_module_with_source_map { gas_limit, .. }
+        | Call::execute_with_session_key { gas_limit, .. }
+        | Call::execute_sponsored { gas_limit, .. } => Some(*gas_limit),
+        _ => None,
+    }
+}
+
+/// Tip paid for the gas limit with the gas unit price.
+pub fn gas_tip<T: Config>(gas_limit: u64, gas_unit_price: u64) -> BalanceOf<T> {
+    gas_limit.saturating_mul(gas_unit_price).unique_saturated_into()
+}
+
+impl<T: Config + Send + Sync> ChargeGasPrice<T>
+where
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+    OriginFor<T>: Into<Result<groupsign::Origin<T>, OriginFor<T>>>,
+{
+    fn gas_limit(call: &<T as frame_system::Config>::Call) -> Option<u64> {
+        call.is_sub_type().and_then(gas_limit_of::<T>)
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for ChargeGasPrice<T>
+where
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+    OriginFor<T>: Into<Result<groupsign::Origin<T>, OriginFor<T>>>,
+{
+    const IDENTIFIER: &'static str = "ChargeGasPrice";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        let gas_limit = match Self::gas_limit(call) {
+            Some(gas_limit) => gas_limit,
+            None => return Ok(ValidTransaction::default()),
+        };
+
+        let tip = gas_tip::<T>(gas_limit, self.0);
+        let balance = T::Currency::free_balance(who);
+        if balance.saturating_sub(T::Currency::minimum_balance()) < tip {
+            return Err(InvalidTransaction::Payment.into());
+        }
+
+        Ok(ValidTransaction {
+            priority: self.0,
+            ..Default::default()
+        })
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<(), TransactionValidityError> {
+        self.validate(who, call, info, len)?;
+
+        if let Some(gas_limit) = Self::gas_limit(call) {
+            let tip = gas_tip::<T>(gas_limit, self.0);
+            let imbalance = T::Currency::withdraw(
+                who,
+                tip,
+                WithdrawReasons::TIP,
+                ExistenceRequirement::KeepAlive,
+            )
+            .map_err(|_| InvalidTransaction::Payment)?;
+            T::OnGasTip::on_unbalanced(imbalance);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Runtime governance proposals submitted by Move code.
+//!
+//! DAO logic written in Move proposes runtime calls by emitting
+//! `0x1::Governance::ProposalRequest` event with the handle created for the proposer:
+//! ```move
+//! struct ProposalRequest {
+//!     call: vector<u8>,
+//!     deposit: u128,
+//!     callback: vector<u8>,
+//!     callback_gas: u64,
+//! }
+//! ```
+//! `call` is the SCALE encoded runtime call. Only `0x1` modules construct the request, so the
+//! framework `Governance` module gates it with its proposer capability.
+//!
+//! The pallet stores the proposal and the runtime (`GovernanceBridge`, e.g. with
+//! `pallet-democracy`) proposes `enact_governance_proposal` with the deposit of the proposer.
+//! Once enacted, the call is dispatched with the root origin and the `callback` script (built
+//! by `dove tx`, e.g. an entry function call) is executed signed by the proposer, so Move code
+//! follows the outcome. Rejected proposals are discarded by the proposer or governance.
+use sp_std::prelude::*;
+use sp_runtime::{DispatchError, DispatchResult};
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::weights::Weight;
+use move_core_types::account_address::AccountAddress;
+
+use crate::event::{MessageReader, MoveEventArguments};
+
+/// Module of the proposal request struct, published under `0x1`.
+pub const GOVERNANCE_MODULE: &str = "Governance";
+/// Name of the proposal request struct.
+pub const PROPOSAL_REQUEST: &str = "ProposalRequest";
+
+/// Submits proposals to the runtime governance and dispatches enacted calls.
+pub trait GovernanceBridge<AccountId> {
+    /// Propose `enact_governance_proposal` of the proposal `id` on behalf of `proposer` with
+    /// the deposit. `call` is the SCALE encoded runtime call of the proposal, enactment weight
+    /// covers it and the callback.
+    fn propose(
+        proposer: AccountId,
+        id: u64,
+        call: &[u8],
+        callback_weight: Weight,
+        deposit: u128,
+    ) -> DispatchResult;
+
+    /// Weight of SCALE encoded runtime call, `None` if the call can't be decoded.
+    fn call_weight(call: &[u8]) -> Option<Weight>;
+
+    /// Dispatch SCALE encoded runtime call of the enacted proposal with the root origin.
+    fn dispatch(call: Vec<u8>) -> DispatchResultWithPostInfo;
+}
+
+/// Governance proposals are not supported.
+impl<AccountId> GovernanceBridge<AccountId> for () {
+    fn propose(_: AccountId, _: u64, _: &[u8], _: Weight, _: u128) -> DispatchResult {
+        Err(DispatchError::Other("Governance proposals are not supported"))
+    }
+
+    fn call_weight(_: &[u8]) -> Option<Weight> {
+        None
+    }
+
+    fn dispatch(_: Vec<u8>) -> DispatchResultWithPostInfo {
+        Err(DispatchError::Other("Governance proposals are not supported").into())
+    }
+}
+
+/// Proposal request emitted by Move code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ProposalRequest {
+    /// Proposer address, owner of the event handle.
+    pub address: AccountAddress,
+    /// SCALE encoded runtime call.
+    pub call: Vec<u8>,
+    /// Deposit of the proposal.
+    pub deposit: u128,
+    /// Script executed signed by the proposer once the proposal is enacted, empty for none.
+    pub callback: Vec<u8>,
+    /// Gas limit of the callback.
+    pub callback_gas: u64,
+}
+
+impl ProposalRequest {
+    /// Converts Move event into the proposal request.
+    ///
+    /// Returns `None` if event isn't `0x1::Governance::ProposalRequest` or can't be decoded.
+    pub fn from_event(e: &MoveEventArguments) -> Option<Self> {
+        let address = e.core_request(GOVERNANCE_MODULE, PROPOSAL_REQUEST)?;
+
+        // BCS encoded struct: vector<u8>, u128, vector<u8>, u64.
+        let mut message = MessageReader::new(&e.message);
+        let call = message.read_bytes()?;
+        let mut deposit = [0; 16];
+        deposit.copy_from_slice(message.read_raw(16)?);
+        let request = Self {
+            address,
+            call,
+            deposit: u128::from_le_bytes(deposit),
+            callback: message.read_bytes()?,
+            callback_gas: message.read_u64()?,
+        };
+        if !message.is_empty() {
+            return None;
+        }
+        Some(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS};
+
+    use super::*;
+
+    fn request_event(address: AccountAddress, message: Vec<u8>) -> MoveEventArguments {
+        MoveEventArguments {
+            guid: [0u64.to_le_bytes().to_vec(), address.to_vec()].concat(),
+            ty_tag: TypeTag::Struct(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(GOVERNANCE_MODULE).unwrap(),
+                name: Identifier::new(PROPOSAL_REQUEST).unwrap(),
+                type_params: vec![],
+            }),
+            message,
+        }
+    }
+
+    fn request_message(call: &[u8], deposit: u128, callback: &[u8], gas: u64) -> Vec<u8> {
+        let mut message = vec![call.len() as u8];
+        message.extend_from_slice(call);
+        message.extend_from_slice(&deposit.to_le_bytes());
+        message.push(callback.len() as u8);
+        message.extend_from_slice(callback);
+        message.extend_from_slice(&gas.to_le_bytes());
+        message
+    }
+
+    #[test]
+    fn decode_request() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let message = request_message(&[1, 2, 3], 1000, &[4, 5], 50_000);
+
+        assert_eq!(
+            ProposalRequest::from_event(&request_event(address, message)),
+            Some(ProposalRequest {
+                address,
+                call: vec![1, 2, 3],
+                deposit: 1000,
+                callback: vec![4, 5],
+                callback_gas: 50_000,
+            })
+        );
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let mut message = request_message(&[1], 1000, &[], 0);
+        message.push(0);
+
+        assert_eq!(ProposalRequest::from_event(&request_event(address, message)), None);
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Chain info for Move contracts.
+//!
+//! Parachain id, relay chain block number and chain name are stored by the pallet as
+//! `0x1::PontemInfo::ChainInfo { parachain_id: u64, relay_block: u64, chain_name: vector<u8> }`
+//! resource under `0x1`, readable with `borrow_global`, so contracts can tell testnet from
+//! mainnet without hardcoded addresses. The runtime updates it with the validation data of
+//! every parachain block (`Pallet::store_chain_info`), chain name is set in genesis.
+use sp_std::prelude::*;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+
+use crate::storage::{core_struct_tag, resource_key};
+
+/// Module of the chain info struct, published under `0x1`.
+pub const CHAIN_INFO_MODULE: &str = "PontemInfo";
+/// Name of the chain info struct.
+pub const CHAIN_INFO_STRUCT: &str = "ChainInfo";
+
+/// BCS encoded `0x1::PontemInfo::ChainInfo` struct tag.
+pub fn chain_info_tag() -> Vec<u8> {
+    core_struct_tag(CHAIN_INFO_MODULE, CHAIN_INFO_STRUCT)
+}
+
+/// Storage key of the chain info resource.
+pub fn chain_info_key() -> Vec<u8> {
+    resource_key(&CORE_CODE_ADDRESS, &chain_info_tag())
+}
+
+/// BCS encoded chain info resource.
+pub fn chain_info_resource(parachain_id: u32, relay_block: u32, chain_name: &[u8]) -> Vec<u8> {
+    let mut resource = Vec::with_capacity(chain_name.len() + 21);
+    resource.extend_from_slice(&u64::from(parachain_id).to_le_bytes());
+    resource.extend_from_slice(&u64::from(relay_block).to_le_bytes());
+
+    // ULEB128 encoded length of the name.
+    let mut len = chain_name.len();
+    while len >= 0x80 {
+        resource.push((len as u8 & 0x7f) | 0x80);
+        len >>= 7;
+    }
+    resource.push(len as u8);
+    resource.extend_from_slice(chain_name);
+    resource
+}
@@ -18,6 +18,98 @@
 //! execute(tx_bc: Vec<u8>, gas_limit: u64) - execute Move script with bytecode `tx_bc`.
 //! publish_module(module_bc: Vec<u8>, gas_limit: u64) - publish Move module with bytecode `module_bc`.
 //! publish_package(package: Vec<u8>, gas_limit: u64) - publish package (a set of Move modules) from binary `package`.
+//! publish_package_with_metadata(package: Vec<u8>, metadata: PackageInfo, gas_limit: u64) - publish package
+//! and store its metadata (name, version, dependencies, source digest), see `Packages`.
+//! stage_module_chunk(offset: u32, chunk: Vec<u8>) - upload module bytecode larger than a single extrinsic in chunks,
+//! published with publish_staged_module(gas_limit: u64), see `StagedModules`.
+//! Gas unit price of `ChargeGasPrice` signed extension is charged as a tip for the gas limit and prioritizes
+//! the extrinsic, see `gas_price`.
+//!
+//! Move accounts have sequence numbers, bumped for the signers of every executed script, so tooling relying on
+//! sequence numbers (e.g. Aptos SDKs) gets replay protection independent of the Substrate nonce:
+//! execute_with_sequence(tx_bc: Vec<u8>, gas_limit: u64, sequence_number: u64) - execute Move
+//! script if `sequence_number` is the current sequence number of the sender.
+//! Users with ed25519 keys submit signed Move transactions without Substrate keys, see `ed25519_tx`:
+//! submit_ed25519_transaction(signed_tx: Vec<u8>) - unsigned, execute Move script signed by the key owner.
+
+//! Governance (`UpdateOrigin`) can restrict execution of raw scripts to the allowlist:
+//! set_script_allowlist_mode(enabled: bool) - enable or disable script allowlist mode.
+//! allow_script(script_hash: [u8; 32], description: Option<Vec<u8>>, proposer: Option<AccountId>) - add script hash to the allowlist.
+//! disallow_script(script_hash: [u8; 32]) - remove script hash from the allowlist.
+
+//! Governance (`UpdateOrigin`) can restrict module publishing to the allowlist of accounts:
+//! set_open_publishing(open: bool) - allow or disallow publishing by any account.
+//! allow_publisher(account: AccountId) - allow account to publish modules once publishing is closed.
+//! disallow_publisher(account: AccountId) - remove account from the publishers allowlist.
+
+//! Publishing a module reserves a deposit proportional to its bytecode size (`ModuleDepositPerByte`),
+//! republishing adjusts the deposit to the new size:
+//! remove_module(name: Vec<u8>) - remove module published under the account and release its deposit.
+//! Module can't be removed while other modules depend on it.
+
+//! Resources written by Move VM reserve a deposit from their owner, `ResourceDepositPerByte` for every byte
+//! of the resource key and value. The deposit is released once resources shrink or are removed.
+
+//! Scripts with multiple signers can be approved on chain, by each signer with own extrinsic:
+//! propose_multisig_script(tx_bc: Vec<u8>, gas_limit: u64, signers: Vec<AccountId>) - propose script, proposer approves it and reserves `MultisigDeposit`.
+//! approve_multisig_script(hash: [u8; 32], max_gas: u64) - approve script, executed once approved by all signers.
+//! cancel_multisig_script(hash: [u8; 32]) - cancel proposed script, allowed to proposer only.
+
+//! Root can execute maintenance scripts signed by any Move address, e.g. `0x1`:
+//! execute_as(address: [u8; 32], tx_bc: Vec<u8>, gas_limit: u64) - execute script with one signer as the address.
+
+//! Scripts can be scheduled for execution in the future block (`ScriptScheduler`, e.g. `pallet-scheduler`):
+//! schedule_execute(when: BlockNumber, tx_bc: Vec<u8>, gas_limit: u64) - schedule script signed by the caller.
+//! The caller pays the fee of the gas limit at the scheduling, pending scripts per account are limited.
+//! Move code can schedule a follow-up script by emitting `0x1::Schedule::ScheduleRequest` event, see `schedule`.
+
+//! Move code can send cross-chain transfers (`XcmTransfer`, e.g. `orml-xtokens`) by emitting
+//! `0x1::XTransfer::TransferRequest` event, see `xtransfer`.
+//! claim_trapped_assets(origin_location: Location, ticker: Vec<u8>, amount: u128, beneficiary: AccountId) - root
+//! claims assets trapped by a failed incoming transfer (`AssetClaims`) into the account.
+//! Other chains run scripts with XCM `Transact` of `transact_execute(tx_bc: Vec<u8>, gas_limit: u64)`
+//! (`XcmTransactOrigin`), failed scripts are queued into `FailedTransacts` instead of being dropped:
+//! retry_failed_transact(id: u64, max_gas: u64) - anyone retries the failed script.
+//! discard_failed_transact(id: u64) - the account of the script or governance removes it from the queue.
+
+//! Runtime randomness is stored every block as `0x1::Randomness::Seed` resource, see `randomness`.
+//! Parent block hash is stored every block as `0x1::BlockMetadata::ParentHash` resource, see `block`,
+//! block height and timestamp come with the execution context.
+//! Parachain id, relay block number and chain name are stored as `0x1::PontemInfo::ChainInfo`
+//! resource, see `info`.
+//! Public key signed the transaction is stored as `0x1::Auth::SenderKey` resource under the sender
+//! while its script runs (`SenderKeys`), see `auth`.
+//! Vesting of the signers (`Lockups`, e.g. `pallet-vesting`) is stored as `0x1::Vesting::Lockup` resource
+//! while their script runs, Move code unlocks vested coins by emitting `0x1::Vesting::VestRequest` event,
+//! see `vesting`.
+//! Current round of the runtime staking (`Staking`, e.g. `parachain-staking`) is stored every block as
+//! `0x1::Staking::Round` resource and stake of the signers as `0x1::Staking::Stake` resource while their
+//! script runs, see `staking`.
+//! Move code proposes runtime calls to the governance (`Governance`, e.g. `pallet-democracy`) by emitting
+//! `0x1::Governance::ProposalRequest` event, see `governance`:
+//! enact_governance_proposal(id: u64, max_weight: Weight) - root dispatches the call and runs the callback script.
+//! discard_governance_proposal(id: u64) - the proposer or governance removes the proposal.
+//! Allowed accounts spend the treasury (`TreasurySpend`, up to `MaxTreasurySpend` per `TreasurySpendPeriod`) by emitting
+//! `0x1::Treasury::SpendRequest` event, see `treasury`:
+//! allow_treasury_spender(account: AccountId) - governance allows the account to request spends.
+//! disallow_treasury_spender(account: AccountId) - governance disallows the account.
+//! Move code subscribes callbacks executed at the block initialization (every block or on deposits) within
+//! `MaxCallbackGas` budget by emitting `0x1::Callback::SubscribeRequest` event, see `callback`.
+//! Subscribers reserve `CallbackDeposit` per subscription and pay the gas of their callbacks:
+//! remove_callback_subscription(subscriber: AccountId, trigger: CallbackTrigger) - the subscriber or governance removes it.
+//!
+//! Governance (`UpdateOrigin`) can limit cumulative Move gas of all calls within a block, so Move heavy
+//! blocks don't starve other pallets:
+//! set_max_block_gas(max_gas: Option<u64>) - set the ceiling, `None` removes it.
+//! set_storage_gas_schedule(schedule: StorageGasSchedule) - set gas of cold and warm storage
+//! accesses charged on top of the Move VM gas, see `gas::StorageGasSchedule`.
+
+//! Account owner can migrate Move resources to another account (e.g. after key rotation):
+//! request_account_migration(to: AccountId) - request migration, enactable after `AccountMigrationDelay` blocks.
+//! cancel_account_migration() - cancel requested migration.
+//! migrate_account(resources: Vec<Vec<u8>>, skip_embedded: bool) - move listed resources and retire the account.
+//! Accounts are recorded by their Move addresses on the first Move call (`MoveAccounts`), so Move addresses
+//! are resolved back to accounts.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -28,19 +120,103 @@ extern crate log;
 extern crate serde_alt as serde;
 #[cfg(feature = "runtime-benchmarks")]
 extern crate bcs_alt as bcs;
+
+use sp_std::collections::btree_map::BTreeMap;
+
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
 pub use pallet::*;
 pub mod addr;
+pub mod ed25519_tx;
+pub mod auth;
 pub mod balance;
+pub mod block;
+pub mod callback;
+pub mod crypto;
+pub mod currency;
+pub mod entry;
 pub mod event;
 pub mod gas;
+pub mod gas_price;
+pub mod governance;
+pub mod info;
+pub mod migrations;
 pub mod mvm;
+pub mod proof;
+pub mod randomness;
+pub mod replay;
 pub mod result;
+pub mod schedule;
+pub mod session;
+pub mod sponsor;
+pub mod staking;
 pub mod storage;
+pub mod treasury;
 pub mod types;
+pub mod vesting;
 pub mod weights;
+pub mod writeset;
+pub mod xtransfer;
+
+/// Revision of Move VM (`sp-move-vm` repository) used by the pallet, keep in sync with Cargo.toml.
+pub const MOVE_VM_REVISION: &str = "c922f60b747bfba0d0f01ab77bd3091d3e43454c";
+
+/// Move language and pallet features supported by the runtime.
+pub const FEATURES: &[&str] = &[
+    "scripts",
+    "script_functions",
+    "module_packages",
+    "multisig_signers",
+    "root_signer",
+    "native_balances",
+    "script_allowlist",
+    "account_migration",
+    "offchain_events_index",
+    "module_deposits",
+    "resource_deposits",
+    "multisig_scripts",
+    "scheduled_scripts",
+    "xcm_transfers",
+    "randomness_seed",
+    "block_metadata",
+    "offchain_write_sets",
+    "gas_unit_price",
+    "chain_info",
+    "block_gas_limit",
+    "sequence_numbers",
+    "ed25519_transactions",
+    "resource_groups",
+    "storage_gas",
+    "session_keys",
+    "sponsored_transactions",
+    "execution_result_events",
+    "failed_transacts_queue",
+    "package_metadata",
+    "staged_modules",
+    "loader_cache_stats",
+    "source_maps",
+    "move_address_lookup",
+    "sender_keys",
+    "vesting_lockups",
+    "governance_proposals",
+    "treasury_spends",
+    "staking_info",
+    "callbacks",
+];
+
+// First failed request of Move code (e.g. `0x1::Schedule::ScheduleRequest` event) during the
+// VM session run by `with_execution_result`. Kept in memory, so VM sessions outside of it
+// (e.g. runtime API estimations) don't leave it behind.
+environmental::environmental!(failed_move_request: Option<sp_runtime::DispatchError>);
+
+// Storage items accessed by the VM call run by `charge_storage_accesses` and count of the
+// accesses. Kept in memory for the call only, they are charged with `StorageGas` once it's done.
+environmental::environmental!(pending_storage_accesses: BTreeMap<sp_std::vec::Vec<u8>, u32>);
+
+// Module of the entry function called by `record_cache_hit` and whether Move VM loader has read
+// it from the storage during the call.
+environmental::environmental!(called_module: (sp_std::vec::Vec<u8>, bool));
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -48,9 +224,23 @@ pub mod pallet {
     #![allow(clippy::unused_unit)]
 
     use super::*;
-    use super::storage::MoveVmStorage;
     use gas::GasWeightMapping;
     use event::*;
+    use schedule::{ScheduleRequest, ScheduleScript};
+    use xtransfer::{AssetClaims, Location, TransferRequest, XcmTransfer};
+    use vesting::VestRequest;
+    use governance::ProposalRequest;
+    use treasury::SpendRequest;
+    use callback::{SubscribeRequest, UnsubscribeRequest};
+    use result::ExecutionResult;
+    use types::{
+        Ed25519SignedTransaction, EncodedStructTag, GasAmount, ModuleBytecode,
+        ModuleDeposit, ModuleName, MultisigScript, PackageBytecode, PendingMigration, ScriptHash,
+        ScriptMetadata, SessionKey, SessionScope, StorageDeposit, TransactionBytecode, VmInfo,
+        FailedTransact, PackageInfo, PackageMetadata, MAX_PACKAGE_METADATA_SIZE, StagedModule,
+        LoaderCacheStats, SourceMapEntry, GovernanceProposal, CallbackSubscription,
+        CallbackTrigger,
+    };
     use groupsign::utils::ensure_groupsign;
     use mvm::*;
     use weights::WeightInfo;
@@ -66,11 +256,16 @@ pub mod pallet {
     use frame_support as support;
     use support::dispatch::fmt::Debug;
     use support::pallet_prelude::*;
-    use support::traits::{UnixTime, tokens::fungibles};
+    use support::traits::{UnixTime, PalletInfoAccess, tokens::fungibles};
+    use support::traits::{Currency, OnUnbalanced, Randomness, ReservableCurrency};
+    use support::traits::{ExistenceRequirement, WithdrawReasons};
     use support::PalletId;
     use support::dispatch::DispatchResultWithPostInfo;
-    use sp_runtime::traits::{UniqueSaturatedInto, AccountIdConversion};
-    use parity_scale_codec::{FullCodec, FullEncode};
+    use support::transactional;
+    use support::storage::TransactionOutcome;
+    use sp_runtime::traits::{UniqueSaturatedInto, AccountIdConversion, Saturating, Zero, One};
+    use sp_runtime::DispatchError;
+    use parity_scale_codec::{Encode, FullCodec, FullEncode};
 
     use move_vm::{Vm, StateAccess};
     use move_vm::mvm::Mvm;
@@ -80,15 +275,26 @@ pub mod pallet {
     use move_vm::types::Transaction;
     use move_vm::types::VmResult;
     use move_vm::types::ModulePackage;
+    use move_vm::types::PackageTx;
+    use move_vm::types::{Call, ScriptTx};
 
     use move_core_types::account_address::AccountAddress;
-    use move_core_types::language_storage::CORE_CODE_ADDRESS;
+    use move_core_types::language_storage::{ModuleId, StructTag, CORE_CODE_ADDRESS};
+    use move_core_types::identifier::Identifier;
+    use move_core_types::vm_status::StatusCode;
+    use move_binary_format::file_format::CompiledModule;
 
     #[cfg(not(feature = "std"))]
     extern crate alloc;
     #[cfg(not(feature = "std"))]
     use alloc::format;
 
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
     pub trait Config:
@@ -110,6 +316,148 @@ pub mod pallet {
         #[pallet::constant]
         type PalletId: Get<PalletId>;
 
+        /// Delay (in blocks) between request and enactment of account migration.
+        /// Zero allows to migrate in the same block.
+        #[pallet::constant]
+        type AccountMigrationDelay: Get<Self::BlockNumber>;
+
+        /// Maximum size (in bytes) of the module bytecode, checked before verification.
+        /// Applies to every module of the package.
+        #[pallet::constant]
+        type MaxModuleSize: Get<u32>;
+
+        /// Maximum size (in bytes) of the module bytecode uploaded in chunks with
+        /// `stage_module_chunk`, so modules larger than a single extrinsic can be published.
+        #[pallet::constant]
+        type MaxStagedModuleSize: Get<u32>;
+
+        /// Maximum size (in bytes) of the source map attached to the module.
+        #[pallet::constant]
+        type MaxSourceMapSize: Get<u32>;
+
+        /// Maximum size (in bytes) of the script transaction, checked before deserialization.
+        #[pallet::constant]
+        type MaxScriptSize: Get<u32>;
+
+        /// Maximum nesting depth of the script type arguments, e.g. `vector<u8>` has depth 2.
+        #[pallet::constant]
+        type MaxTypeArgDepth: Get<u32>;
+
+        /// Maximum amount of modules (or functions) a session key is allowed to call.
+        #[pallet::constant]
+        type MaxSessionScope: Get<u32>;
+
+        /// Currency to reserve module deposits.
+        type Currency: ReservableCurrency<Self::AccountId>;
+
+        /// Handler of the gas tips charged by `ChargeGasPrice` and of the callback fees,
+        /// e.g. block author.
+        type OnGasTip: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// Deposit reserved per byte of the published module bytecode.
+        #[pallet::constant]
+        type ModuleDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Deposit reserved per byte of the resources stored under the account.
+        #[pallet::constant]
+        type ResourceDepositPerByte: Get<BalanceOf<Self>>;
+
+        /// Deposit reserved from the proposer of the multisig script, released once the script
+        /// is executed or cancelled.
+        #[pallet::constant]
+        type MultisigDeposit: Get<BalanceOf<Self>>;
+
+        /// Maximum amount of the open multisig scripts proposed by one account.
+        #[pallet::constant]
+        type MaxMultisigProposals: Get<u32>;
+
+        /// Scheduler of the delayed scripts execution.
+        type ScriptScheduler: ScheduleScript<Self::AccountId, Self::BlockNumber>;
+
+        /// Maximum gas limit of the scheduled script.
+        #[pallet::constant]
+        type MaxScheduledGas: Get<u64>;
+
+        /// Maximum amount of the pending scripts scheduled by one account.
+        #[pallet::constant]
+        type MaxScheduledScripts: Get<u32>;
+
+        /// Price of the scheduled script gas unit, the fee of the whole gas limit is withdrawn
+        /// from the account at the scheduling and handled by `OnGasTip`.
+        #[pallet::constant]
+        type ScheduledGasUnitPrice: Get<u64>;
+
+        /// Sender of the cross-chain transfers requested by Move code.
+        type XcmTransfer: XcmTransfer<Self::AccountId>;
+
+        /// Claims of the assets trapped by failed incoming cross-chain transfers.
+        type AssetClaims: AssetClaims<Self::AccountId>;
+
+        /// Origin of the scripts dispatched by XCM `Transact` with `transact_execute`,
+        /// resolved to the account signing the script.
+        type XcmTransactOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+
+        /// Maximum amount of failed XCM-dispatched scripts queued for retries.
+        #[pallet::constant]
+        type MaxFailedTransacts: Get<u32>;
+
+        /// Source of the randomness seed stored for Move contracts every block.
+        type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+        /// Resource groups: resources of the member structs are packed into one storage item,
+        /// see `storage::ResourceGroups`.
+        type ResourceGroups: storage::ResourceGroups;
+
+        /// Public keys signed the transactions, stored for Move code as `0x1::Auth::SenderKey`
+        /// while scripts run, see `auth`.
+        type SenderKeys: auth::SenderKeyProvider<Self::AccountId>;
+
+        /// Token lockups of the accounts, stored for Move code as `0x1::Vesting::Lockup` while
+        /// scripts run, see `vesting`.
+        type Lockups: vesting::Lockups<Self::AccountId>;
+
+        /// Staking state of the runtime, stored for Move code as `0x1::Staking::Round` every
+        /// block and `0x1::Staking::Stake` while scripts run, see `staking`.
+        type Staking: staking::StakingInfo<Self::AccountId>;
+
+        /// Runtime governance receiving proposals of Move code, see `governance`.
+        type Governance: governance::GovernanceBridge<Self::AccountId>;
+
+        /// Spend origin of the treasury spends requested by Move code, see `treasury`.
+        type TreasurySpend: treasury::TreasurySpend<Self::AccountId>;
+
+        /// Maximum amount of native coins spent by one spender of Move code within the
+        /// `TreasurySpendPeriod`.
+        #[pallet::constant]
+        type MaxTreasurySpend: Get<u128>;
+
+        /// Period of the `MaxTreasurySpend` budget of the Move spenders, in blocks.
+        #[pallet::constant]
+        type TreasurySpendPeriod: Get<Self::BlockNumber>;
+
+        /// Gas budget of the Move callbacks executed at the block initialization, also the
+        /// maximum gas limit of one callback, see `callback`.
+        #[pallet::constant]
+        type MaxCallbackGas: Get<u64>;
+
+        /// Maximum amount of the callbacks subscribed by Move code.
+        #[pallet::constant]
+        type MaxCallbackSubscriptions: Get<u32>;
+
+        /// Maximum amount of the callbacks subscribed by one account.
+        #[pallet::constant]
+        type MaxAccountCallbackSubscriptions: Get<u32>;
+
+        /// Deposit reserved from the subscriber per callback subscription, released once the
+        /// callback is unsubscribed.
+        #[pallet::constant]
+        type CallbackDeposit: Get<BalanceOf<Self>>;
+
+        /// Price of the callback gas unit, the fee of the whole gas limit is withdrawn from the
+        /// subscriber before every callback execution and handled by `OnGasTip`.
+        #[pallet::constant]
+        type CallbackGasUnitPrice: Get<u64>;
+
         /// Currency id indetifier.
         type CurrencyId: FullCodec
             + Eq
@@ -131,8 +479,12 @@ pub mod pallet {
             >;
     }
 
+    /// Current version of the pallet storage, see `migrations`.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -146,12 +498,306 @@ pub mod pallet {
     #[pallet::storage]
     pub type VMStorage<T> = StorageMap<_, Blake2_128Concat, Vec<u8>, Vec<u8>>;
 
+    /// Script allowlist mode.
+    /// If enabled, signed origins can execute only raw scripts from `AllowedScripts`.
+    #[pallet::storage]
+    #[pallet::getter(fn script_allowlist_enabled)]
+    pub type ScriptAllowlistEnabled<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// Allowlisted raw scripts.
+    ///
+    /// map ScriptHash => ScriptMetadata
+    #[pallet::storage]
+    pub type AllowedScripts<T: Config> =
+        StorageMap<_, Identity, ScriptHash, ScriptMetadata<T::AccountId>, OptionQuery>;
+
+    /// Amount of Move events emitted in the current block.
+    /// Used as index of events in the offchain index, cleared on finalize.
+    #[pallet::storage]
+    pub type MoveEventsCount<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Amount of Move VM storage changes made in the current block.
+    /// Used as index of changes in the offchain index, cleared on finalize.
+    #[pallet::storage]
+    pub type MoveWriteSetCount<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Version of the stored Move resources layout, bumped by resource migrations.
+    #[pallet::storage]
+    pub type ResourcesVersion<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Requested account migrations.
+    ///
+    /// map AccountId => PendingMigration
+    #[pallet::storage]
+    pub type PendingMigrations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        PendingMigration<T::AccountId, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Migrated accounts, the old accounts can't be signers of Move calls anymore.
+    ///
+    /// map old AccountId => new AccountId
+    #[pallet::storage]
+    #[pallet::getter(fn migrated_to)]
+    pub type MigratedAccounts<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// Amount of modules published under the Move address, accounts with published modules
+    /// can't be migrated.
+    ///
+    /// map Move address => u32
+    #[pallet::storage]
+    #[pallet::getter(fn published_modules)]
+    pub type PublishedModules<T> = StorageMap<_, Blake2_128Concat, [u8; 32], u32, ValueQuery>;
+
+    #[pallet::type_value]
+    pub fn DefaultOpenPublishing() -> bool {
+        true
+    }
+
+    /// Open publishing mode, enabled by default.
+    /// If disabled, signed origins can publish modules and packages only if allowlisted in `AllowedPublishers`.
+    #[pallet::storage]
+    #[pallet::getter(fn open_publishing)]
+    pub type OpenPublishing<T> = StorageValue<_, bool, ValueQuery, DefaultOpenPublishing>;
+
+    /// Accounts allowed to publish modules when open publishing is disabled.
+    ///
+    /// map AccountId => ()
+    #[pallet::storage]
+    pub type AllowedPublishers<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Deposits of published modules.
+    /// Modules published by root and before deposits were introduced have no deposit.
+    ///
+    /// map module storage key => ModuleDeposit
+    #[pallet::storage]
+    pub type ModuleDeposits<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Vec<u8>,
+        ModuleDeposit<T::AccountId, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// Amount of modules depending on the module, listed in `ModuleDeposits`.
+    ///
+    /// map module storage key => u32
+    #[pallet::storage]
+    pub type ModuleDependents<T> = StorageMap<_, Blake2_128Concat, Vec<u8>, u32, ValueQuery>;
+
+    /// Name of the chain (e.g. chain spec id) published to Move contracts, set in genesis.
+    #[pallet::storage]
+    #[pallet::getter(fn chain_name)]
+    pub type ChainName<T> = StorageValue<_, Vec<u8>, ValueQuery>;
+
+    /// Sequence numbers of Move accounts, bumped for the signers of every executed script.
+    ///
+    /// map AccountId => u64
+    #[pallet::storage]
+    #[pallet::getter(fn sequence_number)]
+    pub type SequenceNumbers<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+    /// Chain id of ed25519 signed transactions (see `ed25519_tx`), reported to Aptos tooling,
+    /// set in genesis.
+    #[pallet::storage]
+    #[pallet::getter(fn aptos_chain_id)]
+    pub type AptosChainId<T> = StorageValue<_, u8, ValueQuery>;
+
+    /// Max cumulative Move gas of all calls within a block, unlimited if not set.
+    #[pallet::storage]
+    #[pallet::getter(fn max_block_gas)]
+    pub type MaxBlockGas<T> = StorageValue<_, u64, OptionQuery>;
+
+    /// Move gas used by calls within the current block, see `MaxBlockGas`.
+    #[pallet::storage]
+    #[pallet::getter(fn block_gas_used)]
+    pub type BlockGasUsed<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// Move VM loader cache counters of the current block, exported by the node metrics.
+    /// Counters of the reverted calls are reverted too.
+    #[pallet::storage]
+    #[pallet::getter(fn loader_stats)]
+    pub type LoaderStats<T> = StorageValue<_, LoaderCacheStats, ValueQuery>;
+
+    /// Gas of cold and warm storage accesses charged on top of the Move VM gas.
+    #[pallet::storage]
+    #[pallet::getter(fn storage_gas_schedule)]
+    pub type StorageGas<T> = StorageValue<_, gas::StorageGasSchedule, ValueQuery>;
+
+    /// Storage used by resources of accounts and reserved deposits.
+    /// Resources stored before deposits were introduced are not counted.
+    ///
+    /// map AccountId => StorageDeposit
+    #[pallet::storage]
+    pub type StorageDeposits<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, StorageDeposit<BalanceOf<T>>, OptionQuery>;
+
+    /// Change of the resources size (in bytes) of accounts during the current VM session.
+    /// Settled into `StorageDeposits` once the session is finished.
+    ///
+    /// map AccountId => i64
+    #[pallet::storage]
+    pub type PendingStorageDeltas<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, i64, ValueQuery>;
+
+    /// Scripts with multiple signers waiting for approvals.
+    ///
+    /// map proposal hash => MultisigScript
+    #[pallet::storage]
+    pub type MultisigScripts<T: Config> = StorageMap<
+        _,
+        Identity,
+        ScriptHash,
+        MultisigScript<T::AccountId, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// Amount of the open multisig scripts proposed by the account.
+    ///
+    /// map AccountId => u32
+    #[pallet::storage]
+    pub type MultisigProposals<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// Session keys authorized by accounts, see `session`.
+    ///
+    /// double map owner, session key => SessionKey
+    #[pallet::storage]
+    pub type SessionKeys<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        SessionKey<T::AccountId, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Failed scripts dispatched by XCM `Transact` (dead-letter queue), see `transact_execute`.
+    ///
+    /// map id => FailedTransact
+    #[pallet::storage]
+    pub type FailedTransacts<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        FailedTransact<T::AccountId, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Id of the next failed transact.
+    #[pallet::storage]
+    pub type NextFailedTransactId<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// Amount of queued failed transacts, bounded by `MaxFailedTransacts`.
+    #[pallet::storage]
+    pub type FailedTransactsCount<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Metadata of the packages published with `publish_package_with_metadata`.
+    ///
+    /// double map account, package name => PackageMetadata
+    #[pallet::storage]
+    pub type Packages<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        Vec<u8>,
+        PackageMetadata<T::AccountId>,
+        OptionQuery,
+    >;
+
+    /// Source maps of the modules published with `publish_module_with_source_map`.
+    ///
+    /// map module key => SourceMapEntry
+    #[pallet::storage]
+    pub type SourceMaps<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Vec<u8>,
+        SourceMapEntry<T::AccountId, BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// Module bytecode staged by the account, see `stage_module_chunk`.
+    ///
+    /// map AccountId => StagedModule
+    #[pallet::storage]
+    pub type StagedModules<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, StagedModule<BalanceOf<T>>, OptionQuery>;
+
+    /// Accounts by their Move addresses, recorded on the first Move call of the account
+    /// (executed script or published module), so Move addresses are attributed to accounts.
+    ///
+    /// map Move address => AccountId
+    #[pallet::storage]
+    #[pallet::getter(fn move_account)]
+    pub type MoveAccounts<T: Config> =
+        StorageMap<_, Blake2_128Concat, [u8; 32], T::AccountId, OptionQuery>;
+
+    /// Runtime governance proposals submitted by Move code, until enacted or discarded.
+    ///
+    /// map id => GovernanceProposal
+    #[pallet::storage]
+    pub type GovernanceProposals<T: Config> =
+        StorageMap<_, Twox64Concat, u64, GovernanceProposal<T::AccountId>, OptionQuery>;
+
+    /// Id of the next governance proposal.
+    #[pallet::storage]
+    pub type NextGovernanceProposalId<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// Callbacks subscribed by Move code, see `callback`.
+    ///
+    /// double_map AccountId, CallbackTrigger => CallbackSubscription
+    #[pallet::storage]
+    pub type CallbackSubscriptions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        CallbackTrigger,
+        CallbackSubscription<BalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// Amount of the subscribed callbacks.
+    #[pallet::storage]
+    pub type CallbackSubscriptionsCount<T> = StorageValue<_, u32, ValueQuery>;
+
+    /// Blocks of the scripts scheduled by the account, past blocks are pruned at the next
+    /// scheduling.
+    ///
+    /// map AccountId => Vec<BlockNumber>
+    #[pallet::storage]
+    pub type ScheduledScripts<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<T::BlockNumber>, ValueQuery>;
+
+    /// Accounts allowed to request treasury spends from Move code, see `treasury`.
+    ///
+    /// map AccountId => ()
+    #[pallet::storage]
+    pub type TreasurySpenders<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// Treasury spends of the spender within the spend period, see `TreasurySpendPeriod`.
+    ///
+    /// map AccountId => (first block of the period, spent amount)
+    #[pallet::storage]
+    pub type TreasurySpent<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, u128), OptionQuery>;
+
     // Pallets use events to inform users when important changes are made.
     // https://substrate.dev/docs/en/knowledgebase/runtime/events
     #[pallet::event]
     #[pallet::generate_deposit(pub fn deposit_event)]
     pub enum Event<T: Config> {
-        /// Event provided by Move VM
+        /// Event provided by Move VM, indexed by the hash of the typetag
         /// [guid, typetag, message]
         Event(
             Vec<u8>, // Event guid
@@ -166,6 +812,186 @@ pub mod pallet {
         /// Event about successful move-package published
         /// [account]
         PackagePublished(T::AccountId),
+
+        /// Script allowlist mode changed
+        /// [enabled]
+        ScriptAllowlistModeSet(bool),
+
+        /// Script added to the allowlist
+        /// [script_hash]
+        ScriptAllowed(ScriptHash),
+
+        /// Script removed from the allowlist
+        /// [script_hash]
+        ScriptDisallowed(ScriptHash),
+
+        /// Account migration requested
+        /// [from, to, enactable_at]
+        AccountMigrationRequested(T::AccountId, T::AccountId, T::BlockNumber),
+
+        /// Account migration cancelled
+        /// [from]
+        AccountMigrationCancelled(T::AccountId),
+
+        /// Resource moved to the new account
+        /// [from, to, struct_tag]
+        ResourceMigrated(T::AccountId, T::AccountId, EncodedStructTag),
+
+        /// Resource embeds the old address, so it is left under the old account
+        /// [from, struct_tag]
+        ResourceMigrationSkipped(T::AccountId, EncodedStructTag),
+
+        /// Account migrated, modules under the old address are owned by the new account
+        /// [from, to, migrated, skipped]
+        AccountMigrated(T::AccountId, T::AccountId, u32, u32),
+
+        /// Open publishing mode changed
+        /// [open]
+        OpenPublishingSet(bool),
+
+        /// Account added to the publishers allowlist
+        /// [account]
+        PublisherAllowed(T::AccountId),
+
+        /// Account removed from the publishers allowlist
+        /// [account]
+        PublisherDisallowed(T::AccountId),
+
+        /// Module removed and its deposit released
+        /// [account, module, deposit]
+        ModuleRemoved(T::AccountId, ModuleName, BalanceOf<T>),
+
+        /// Resources storage deposit changed
+        /// [account, bytes, deposit]
+        StorageDepositUpdated(T::AccountId, u64, BalanceOf<T>),
+
+        /// Multisig script proposed
+        /// [hash, proposer]
+        MultisigScriptProposed(ScriptHash, T::AccountId),
+
+        /// Multisig script approved by the signer
+        /// [hash, signer]
+        MultisigScriptApproved(ScriptHash, T::AccountId),
+
+        /// Multisig script approved by all signers and executed
+        /// [hash]
+        MultisigScriptExecuted(ScriptHash),
+
+        /// Multisig script cancelled
+        /// [hash]
+        MultisigScriptCancelled(ScriptHash),
+
+        /// Script executed by root as the Move address
+        /// [address, script_hash]
+        ScriptExecutedAs([u8; 32], ScriptHash),
+
+        /// Script scheduled for execution
+        /// [account, when, script_hash]
+        ScriptScheduled(T::AccountId, T::BlockNumber, ScriptHash),
+
+        /// Cross-chain transfer requested by Move code is sent
+        /// [account, ticker, amount]
+        XcmTransferSent(T::AccountId, Vec<u8>, u64),
+
+        /// Trapped assets claimed into the account
+        /// [account, ticker, amount]
+        TrappedAssetsClaimed(T::AccountId, Vec<u8>, u128),
+
+        /// Max cumulative Move gas per block is changed
+        /// [max_gas]
+        MaxBlockGasSet(Option<u64>),
+
+        /// Ed25519 signed transaction is executed
+        /// [account, sequence_number]
+        Ed25519TransactionExecuted(T::AccountId, u64),
+
+        /// Gas of storage accesses is changed
+        /// [schedule]
+        StorageGasScheduleSet(gas::StorageGasSchedule),
+
+        /// Session key authorized by the account
+        /// [owner, session_key, expires_at]
+        SessionKeyAuthorized(T::AccountId, T::AccountId, T::BlockNumber),
+
+        /// Session key revoked by the account
+        /// [owner, session_key]
+        SessionKeyRevoked(T::AccountId, T::AccountId),
+
+        /// Transaction of the sender executed with fees paid by the sponsor
+        /// [sponsor, sender, sequence_number]
+        SponsoredTransactionExecuted(T::AccountId, T::AccountId, u64),
+
+        /// Move call of the extrinsic executed (or failed) by Move VM
+        /// [result]
+        ExecutionResult(ExecutionResult),
+
+        /// Script dispatched by XCM `Transact` failed and is queued for retries
+        /// [id, account, error]
+        TransactFailed(u64, T::AccountId, DispatchError),
+
+        /// Failed transact is retried and executed
+        /// [id]
+        FailedTransactRetried(u64),
+
+        /// Failed transact is removed from the queue
+        /// [id]
+        FailedTransactDiscarded(u64),
+
+        /// Package is published with metadata
+        /// [account, name, upgrade_number]
+        PackageMetadataStored(T::AccountId, Vec<u8>, u64),
+
+        /// Chunk of the module bytecode is staged
+        /// [account, staged_size]
+        ModuleChunkStaged(T::AccountId, u32),
+
+        /// Staged module bytecode is discarded
+        /// [account]
+        StagedModuleDiscarded(T::AccountId),
+
+        /// Move VM loader cache is flushed as published modules are republished
+        /// [account, republished_modules]
+        LoaderCacheFlushed(T::AccountId, u32),
+
+        /// Source map of the module is stored
+        /// [account, module_name]
+        SourceMapStored(T::AccountId, Vec<u8>),
+
+        /// Move code submitted the runtime governance proposal
+        /// [proposer, id]
+        GovernanceProposalSubmitted(T::AccountId, u64),
+
+        /// Governance proposal of Move code is enacted, the call is dispatched
+        /// [id, dispatched]
+        GovernanceProposalEnacted(u64, bool),
+
+        /// Governance proposal of Move code is discarded
+        /// [id]
+        GovernanceProposalDiscarded(u64),
+
+        /// Account is allowed to request treasury spends from Move code
+        /// [account]
+        TreasurySpenderAllowed(T::AccountId),
+
+        /// Account is disallowed to request treasury spends from Move code
+        /// [account]
+        TreasurySpenderDisallowed(T::AccountId),
+
+        /// Treasury spend requested by Move code is submitted
+        /// [spender, beneficiary, amount]
+        TreasurySpendRequested(T::AccountId, T::AccountId, u128),
+
+        /// Callback is subscribed by Move code
+        /// [subscriber, trigger]
+        CallbackSubscribed(T::AccountId, CallbackTrigger),
+
+        /// Callback subscription is removed
+        /// [subscriber, trigger]
+        CallbackUnsubscribed(T::AccountId, CallbackTrigger),
+
+        /// Triggered callback is executed
+        /// [subscriber, trigger, succeeded]
+        CallbackExecuted(T::AccountId, CallbackTrigger, bool),
     }
 
     // Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -187,8 +1013,8 @@ pub mod pallet {
         )]
         pub fn execute(
             origin: OriginFor<T>,
-            tx_bc: Vec<u8>,
-            gas_limit: u64,
+            tx_bc: TransactionBytecode,
+            gas_limit: GasAmount,
         ) -> DispatchResultWithPostInfo {
             let groupsign_origin = ensure_groupsign(origin.clone());
 
@@ -201,11 +1027,60 @@ pub mod pallet {
                 },
             };
 
-            let vm_result = Self::raw_execute_script(&signers, tx_bc, gas_limit, root, false)?;
+            let resources = Self::signer_resources(&signers);
+            Self::with_execution_result(|execution| {
+                let vm_result = Self::with_signer_resources(&resources, || {
+                    Self::raw_execute_script(&signers, tx_bc, gas_limit, root, false)
+                })
+                .map_err(|e| result::rejected::<T>(e, T::WeightInfo::execute()))?;
+                *execution = Some(ExecutionResult::from(&vm_result));
 
-            // produce result with spended gas:
-            let result = result::from_vm_result::<T>(vm_result)?;
-            Ok(result)
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::settle_move_requests()?;
+                Self::settle_storage_deposits()?;
+                Self::bump_sequence_numbers(&signers);
+                Ok(result)
+            })
+        }
+
+        /// Execute Move script if `sequence_number` is the current sequence number of the sender.
+        ///
+        /// Same as `execute` signed by the account, the sequence number protects the script
+        /// from replays independently of the Substrate nonce, as Aptos tooling expects.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*gas_limit)
+            )
+        )]
+        pub fn execute_with_sequence(
+            origin: OriginFor<T>,
+            tx_bc: TransactionBytecode,
+            gas_limit: GasAmount,
+            sequence_number: u64,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            debug!("executing `execute_with_sequence` with sequence number {}", sequence_number);
+            Self::ensure_sequence_number(&who, sequence_number)?;
+
+            let signers = vec![who];
+            let resources = Self::signer_resources(&signers);
+            Self::with_execution_result(|execution| {
+                let vm_result = Self::with_signer_resources(&resources, || {
+                    Self::raw_execute_script(&signers, tx_bc, gas_limit, false, false)
+                })
+                .map_err(|e| result::rejected::<T>(e, T::WeightInfo::execute()))?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::settle_move_requests()?;
+                Self::settle_storage_deposits()?;
+                Self::bump_sequence_numbers(&signers);
+                Ok(result)
+            })
         }
 
         /// Publish Move module.
@@ -219,23 +1094,10 @@ pub mod pallet {
         )]
         pub fn publish_module(
             origin: OriginFor<T>,
-            module_bc: Vec<u8>,
-            gas_limit: u64,
+            module_bc: ModuleBytecode,
+            gas_limit: GasAmount,
         ) -> DispatchResultWithPostInfo {
-            // Allows to update Standard Library if root.
-            let (sender, signer) = Self::ensure_and_convert(origin)?;
-            debug!("executing `publish module` with signed {:?}", sender);
-
-            // Publish module.
-            let vm_result = Self::raw_publish_module(&signer, module_bc, gas_limit, false)?;
-
-            // produce result with spended gas:
-            let result = result::from_vm_result::<T>(vm_result)?;
-
-            // Emit an event:
-            Self::deposit_event(Event::ModulePublished(signer));
-
-            Ok(result)
+            Self::do_publish_module(origin, module_bc, None, gas_limit)
         }
 
         /// Publish module package (could be generated using 'dove build -b'), e.g.: several modules in one transaction.
@@ -251,233 +1113,2521 @@ pub mod pallet {
         )]
         pub fn publish_package(
             origin: OriginFor<T>,
-            package: Vec<u8>,
+            package: PackageBytecode,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_publish_package(origin, package, None, gas_limit)
+        }
+
+        /// Publish module package with the package metadata, as Aptos `code::publish_package`.
+        ///
+        /// Metadata is stored in `Packages` under the account and the package name, served by
+        /// `mvm_getPackage` RPC. Republishing the package under the same name is an upgrade:
+        /// it bumps the upgrade number and must keep all modules of the published package.
+        /// Dependencies must be published with metadata before.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::publish_module().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*gas_limit)
+            )
+        )]
+        pub fn publish_package_with_metadata(
+            origin: OriginFor<T>,
+            package: PackageBytecode,
+            metadata: PackageInfo<T::AccountId>,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_publish_package(origin, package, Some(metadata), gas_limit)
+        }
+
+        /// Enable or disable script allowlist mode.
+        ///
+        /// Once enabled, signed origins can execute only raw scripts which hashes are allowlisted.
+        /// Script functions and root execution are not restricted.
+        #[pallet::weight(<T as Config>::WeightInfo::set_script_allowlist_mode())]
+        pub fn set_script_allowlist_mode(
+            origin: OriginFor<T>,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            ScriptAllowlistEnabled::<T>::put(enabled);
+            Self::deposit_event(Event::ScriptAllowlistModeSet(enabled));
+
+            Ok(().into())
+        }
+
+        /// Add script hash to the allowlist.
+        ///
+        /// Script hash is blake2-256 hash of the script bytecode (see `types::script_hash`).
+        /// Description and proposer are optional and served by `mvm_getAllowedScripts` RPC.
+        #[pallet::weight(<T as Config>::WeightInfo::allow_script())]
+        pub fn allow_script(
+            origin: OriginFor<T>,
+            script_hash: ScriptHash,
+            description: Option<Vec<u8>>,
+            proposer: Option<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !AllowedScripts::<T>::contains_key(script_hash),
+                Error::<T>::ScriptAlreadyAllowed
+            );
+
+            AllowedScripts::<T>::insert(
+                script_hash,
+                ScriptMetadata {
+                    description,
+                    proposer,
+                },
+            );
+            Self::deposit_event(Event::ScriptAllowed(script_hash));
+
+            Ok(().into())
+        }
+
+        /// Remove script hash from the allowlist.
+        #[pallet::weight(<T as Config>::WeightInfo::disallow_script())]
+        pub fn disallow_script(
+            origin: OriginFor<T>,
+            script_hash: ScriptHash,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            AllowedScripts::<T>::take(script_hash).ok_or(Error::<T>::UnknownScript)?;
+            Self::deposit_event(Event::ScriptDisallowed(script_hash));
+
+            Ok(().into())
+        }
+
+        /// Request migration of Move state to another account (e.g. after key rotation).
+        ///
+        /// Migration can be enacted by `migrate_account` after `AccountMigrationDelay` blocks.
+        #[pallet::weight(<T as Config>::WeightInfo::request_account_migration())]
+        pub fn request_account_migration(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+
+            ensure!(
+                from != to && !MigratedAccounts::<T>::contains_key(&to),
+                Error::<T>::InvalidMigrationTarget
+            );
+            Self::ensure_not_migrated(&from)?;
+            Self::ensure_no_modules(&from)?;
+            ensure!(
+                !PendingMigrations::<T>::contains_key(&from),
+                Error::<T>::AccountMigrationAlreadyRequested
+            );
+
+            let enactable_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::AccountMigrationDelay::get());
+            PendingMigrations::<T>::insert(
+                &from,
+                PendingMigration {
+                    to: to.clone(),
+                    enactable_at,
+                },
+            );
+            Self::deposit_event(Event::AccountMigrationRequested(from, to, enactable_at));
+
+            Ok(().into())
+        }
+
+        /// Cancel requested account migration.
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_account_migration())]
+        pub fn cancel_account_migration(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+
+            PendingMigrations::<T>::take(&from).ok_or(Error::<T>::AccountMigrationNotRequested)?;
+            Self::deposit_event(Event::AccountMigrationCancelled(from));
+
+            Ok(().into())
+        }
+
+        /// Enact requested account migration.
+        ///
+        /// Moves listed resources (BCS-encoded `StructTag`s) to the new account address.
+        /// Resources which embed the old address (e.g. event handles) can't be moved safely:
+        /// migration fails on them, or leaves them under the old address if `skip_embedded` is set.
+        /// Accounts with published modules can't be migrated, as module bytecode is bound to
+        /// the address. Once migrated, the old account can't be a signer of Move calls anymore.
+        #[pallet::weight(<T as Config>::WeightInfo::migrate_account(resources.len() as u32))]
+        #[transactional]
+        pub fn migrate_account(
+            origin: OriginFor<T>,
+            resources: Vec<EncodedStructTag>,
+            skip_embedded: bool,
+        ) -> DispatchResultWithPostInfo {
+            let from = ensure_signed(origin)?;
+
+            let migration =
+                PendingMigrations::<T>::get(&from).ok_or(Error::<T>::AccountMigrationNotRequested)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= migration.enactable_at,
+                Error::<T>::AccountMigrationTimelocked
+            );
+            // Modules could be published after the migration request.
+            Self::ensure_no_modules(&from)?;
+
+            let from_address = addr::account_to_account_address(&from);
+            let to_address = addr::account_to_account_address(&migration.to);
+
+            // Validate all resources before any change in storage.
+            let mut moves = Vec::with_capacity(resources.len());
+            let mut skipped = Vec::new();
+            for tag in resources {
+                let key = storage::resource_key(&from_address, &tag);
+                let blob =
+                    Self::vm_storage_get(&key).ok_or(Error::<T>::MigrationResourceNotFound)?;
+
+                if storage::embeds_address(&blob, &from_address) {
+                    ensure!(skip_embedded, Error::<T>::MigrationResourceEmbedsAddress);
+                    skipped.push(tag);
+                    continue;
+                }
+
+                let target = storage::resource_key(&to_address, &tag);
+                ensure!(
+                    Self::vm_storage_get(&target).is_none(),
+                    Error::<T>::MigrationTargetOccupied
+                );
+                moves.push((key, target, blob, tag));
+            }
+
+            let (migrated, skipped_count) = (moves.len() as u32, skipped.len() as u32);
+            for (key, target, blob, tag) in moves {
+                Self::vm_storage_remove(&key);
+                Self::vm_storage_insert(&target, &blob);
+                Self::deposit_event(Event::ResourceMigrated(
+                    from.clone(),
+                    migration.to.clone(),
+                    tag,
+                ));
+            }
+            for tag in skipped {
+                Self::deposit_event(Event::ResourceMigrationSkipped(from.clone(), tag));
+            }
+
+            // Deposit for moved resources is reserved from the new account.
+            Self::settle_storage_deposits()?;
+
+            PendingMigrations::<T>::remove(&from);
+            MigratedAccounts::<T>::insert(&from, &migration.to);
+            Self::deposit_event(Event::AccountMigrated(
+                from,
+                migration.to,
+                migrated,
+                skipped_count,
+            ));
+
+            Ok(().into())
+        }
+
+        /// Enable or disable open publishing mode.
+        ///
+        /// Once disabled, signed origins can publish modules and packages only if allowlisted.
+        /// Root publishing is not restricted.
+        #[pallet::weight(<T as Config>::WeightInfo::set_open_publishing())]
+        pub fn set_open_publishing(origin: OriginFor<T>, open: bool) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            OpenPublishing::<T>::put(open);
+            Self::deposit_event(Event::OpenPublishingSet(open));
+
+            Ok(().into())
+        }
+
+        /// Add account to the publishers allowlist.
+        #[pallet::weight(<T as Config>::WeightInfo::allow_publisher())]
+        pub fn allow_publisher(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !AllowedPublishers::<T>::contains_key(&account),
+                Error::<T>::PublisherAlreadyAllowed
+            );
+
+            AllowedPublishers::<T>::insert(&account, ());
+            Self::deposit_event(Event::PublisherAllowed(account));
+
+            Ok(().into())
+        }
+
+        /// Remove account from the publishers allowlist.
+        #[pallet::weight(<T as Config>::WeightInfo::disallow_publisher())]
+        pub fn disallow_publisher(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            AllowedPublishers::<T>::take(&account).ok_or(Error::<T>::UnknownPublisher)?;
+            Self::deposit_event(Event::PublisherDisallowed(account));
+
+            Ok(().into())
+        }
+
+        /// Remove module published under the account and release its deposit.
+        ///
+        /// Module can't be removed while other modules depend on it.
+        /// Resources of the module types are left in storage.
+        #[pallet::weight(<T as Config>::WeightInfo::remove_module())]
+        pub fn remove_module(
+            origin: OriginFor<T>,
+            name: ModuleName,
+        ) -> DispatchResultWithPostInfo {
+            let signer = ensure_signed(origin)?;
+
+            let name_id = Identifier::from_utf8(name.clone())
+                .map_err(|_| Error::<T>::ModuleDepositNotFound)?;
+            let address = addr::account_to_account_address(&signer);
+            let key = ModuleId::new(address, name_id).access_vector();
+
+            let deposit =
+                ModuleDeposits::<T>::get(&key).ok_or(Error::<T>::ModuleDepositNotFound)?;
+            ensure!(
+                deposit.depositor == signer,
+                Error::<T>::ModuleDepositNotFound
+            );
+            ensure!(
+                ModuleDependents::<T>::get(&key) == 0,
+                Error::<T>::ModuleHasDependents
+            );
+
+            let amount = deposit.amount;
+            Self::write_untracked(&key, None);
+            Self::release_module_deposit(&key, deposit);
+            Self::remove_source_map(&key);
+            Self::clear_vm_cache();
+
+            Self::deposit_event(Event::ModuleRemoved(signer, name, amount));
+
+            Ok(().into())
+        }
+
+        /// Propose script with multiple signers, proposal is approved by the proposer.
+        ///
+        /// Signers are listed in the order of the script `signer` parameters, proposer must be one of them.
+        /// Script is executed once approved by every signer, see `approve_multisig_script`.
+        /// `MultisigDeposit` is reserved from the proposer until the script is executed or
+        /// cancelled, up to `MaxMultisigProposals` scripts of the proposer are open at once.
+        #[pallet::weight(<T as Config>::WeightInfo::propose_multisig_script())]
+        #[transactional]
+        pub fn propose_multisig_script(
+            origin: OriginFor<T>,
+            tx_bc: TransactionBytecode,
+            gas_limit: GasAmount,
+            signers: Vec<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let proposer = ensure_signed(origin)?;
+
+            ensure!(
+                tx_bc.len() <= T::MaxScriptSize::get() as usize,
+                Error::<T>::ScriptTooLarge
+            );
+            let transaction = Transaction::try_from(&tx_bc[..])
+                .map_err(|_| Error::<T>::TransactionValidationError)?;
+            ensure!(
+                !transaction.has_root_signer()
+                    && transaction.signers_count() as usize == signers.len(),
+                Error::<T>::TransactionSignersNumError
+            );
+            let distinct = signers
+                .iter()
+                .enumerate()
+                .all(|(i, signer)| !signers[..i].contains(signer));
+            ensure!(
+                signers.len() > 1 && distinct && signers.contains(&proposer),
+                Error::<T>::InvalidMultisigSigners
+            );
+
+            let hash = types::multisig_script_hash(&tx_bc, gas_limit, &signers);
+            ensure!(
+                !MultisigScripts::<T>::contains_key(hash),
+                Error::<T>::MultisigScriptAlreadyProposed
+            );
+            let proposals = MultisigProposals::<T>::get(&proposer);
+            ensure!(
+                proposals < T::MaxMultisigProposals::get(),
+                Error::<T>::TooManyMultisigProposals
+            );
+
+            let deposit = T::MultisigDeposit::get();
+            T::Currency::reserve(&proposer, deposit)
+                .map_err(|_| Error::<T>::InsufficientMultisigDeposit)?;
+            MultisigProposals::<T>::insert(&proposer, proposals + 1);
+            MultisigScripts::<T>::insert(
+                hash,
+                MultisigScript {
+                    proposer: proposer.clone(),
+                    tx_bc,
+                    gas_limit,
+                    signers,
+                    approvals: vec![proposer.clone()],
+                    deposit,
+                },
+            );
+            Self::deposit_event(Event::MultisigScriptProposed(hash, proposer));
+
+            Ok(().into())
+        }
+
+        /// Approve multisig script.
+        ///
+        /// Once all signers approved, the script is executed within this call, so `max_gas`
+        /// should cover the script gas limit. If the execution fails, the approval is not recorded.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::approve_multisig_script().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*max_gas)
+            )
+        )]
+        pub fn approve_multisig_script(
+            origin: OriginFor<T>,
+            hash: ScriptHash,
+            max_gas: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            let signer = ensure_signed(origin)?;
+            Self::with_execution_result(|execution| {
+                let mut script =
+                    MultisigScripts::<T>::get(hash).ok_or(Error::<T>::UnknownMultisigScript)?;
+                ensure!(
+                    script.signers.contains(&signer),
+                    Error::<T>::NotMultisigSigner
+                );
+                ensure!(
+                    !script.approvals.contains(&signer),
+                    Error::<T>::MultisigScriptAlreadyApproved
+                );
+
+                script.approvals.push(signer.clone());
+                Self::deposit_event(Event::MultisigScriptApproved(hash, signer));
+
+                if script.approvals.len() < script.signers.len() {
+                    MultisigScripts::<T>::insert(hash, script);
+                    let weight = <T as Config>::WeightInfo::approve_multisig_script();
+                    return Ok(Some(weight).into());
+                }
+
+                ensure!(
+                    max_gas >= script.gas_limit,
+                    Error::<T>::MultisigGasLimitTooLow
+                );
+                MultisigScripts::<T>::remove(hash);
+                Self::release_multisig_deposit(&script.proposer, script.deposit);
+
+                let vm_result = Self::raw_execute_script(
+                    &script.signers,
+                    script.tx_bc,
+                    script.gas_limit,
+                    false,
+                    false,
+                )?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::settle_move_requests()?;
+                Self::settle_storage_deposits()?;
+                Self::deposit_event(Event::MultisigScriptExecuted(hash));
+
+                Ok(result)
+            })
+        }
+
+        /// Cancel multisig script, allowed to proposer only.
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_multisig_script())]
+        pub fn cancel_multisig_script(
+            origin: OriginFor<T>,
+            hash: ScriptHash,
+        ) -> DispatchResultWithPostInfo {
+            let proposer = ensure_signed(origin)?;
+
+            let script =
+                MultisigScripts::<T>::get(hash).ok_or(Error::<T>::UnknownMultisigScript)?;
+            ensure!(script.proposer == proposer, Error::<T>::NotMultisigProposer);
+
+            MultisigScripts::<T>::remove(hash);
+            Self::release_multisig_deposit(&proposer, script.deposit);
+            Self::deposit_event(Event::MultisigScriptCancelled(hash));
+
+            Ok(().into())
+        }
+
+        /// Execute Move script signed by arbitrary Move address, root only.
+        ///
+        /// Allows governance to run maintenance scripts as the standard library address (`0x1`)
+        /// or any account, e.g. to fix stuck resources or update framework configuration.
+        /// Script must have exactly one `signer` parameter, script allowlist is not applied.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*gas_limit)
+            )
+        )]
+        pub fn execute_as(
+            origin: OriginFor<T>,
+            address: [u8; 32],
+            tx_bc: TransactionBytecode,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            let script_hash = types::script_hash(&tx_bc);
+            let move_address = AccountAddress::new(address);
+            Self::with_execution_result(|execution| {
+                let vm_result =
+                    Self::raw_execute_script_as(move_address, tx_bc, gas_limit, false)?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::settle_move_requests()?;
+                Self::settle_storage_deposits()?;
+                Self::deposit_event(Event::ScriptExecutedAs(address, script_hash));
+
+                Ok(result)
+            })
+        }
+
+        /// Schedule Move script execution at block `when`.
+        ///
+        /// Script is executed as `execute` call signed by the caller, so it must have exactly
+        /// one `signer` parameter. Script allowlist is checked at the execution.
+        /// The fee of `gas_limit` (`ScheduledGasUnitPrice`) is paid at the scheduling, up to
+        /// `MaxScheduledScripts` scripts of the caller are pending.
+        #[pallet::weight(<T as Config>::WeightInfo::schedule_execute())]
+        pub fn schedule_execute(
+            origin: OriginFor<T>,
+            when: T::BlockNumber,
+            tx_bc: TransactionBytecode,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            Self::schedule_script(who, when, tx_bc, gas_limit)?;
+
+            Ok(().into())
+        }
+
+        /// Claim assets trapped by a failed incoming cross-chain transfer into `beneficiary`.
+        ///
+        /// Assets are trapped under the location of the sender (`origin_location`, e.g. the
+        /// relay chain) and claimed only with the exact currency and amount. Root only.
+        #[pallet::weight(<T as Config>::WeightInfo::claim_trapped_assets())]
+        #[transactional]
+        pub fn claim_trapped_assets(
+            origin: OriginFor<T>,
+            origin_location: Location,
+            ticker: Vec<u8>,
+            amount: u128,
+            beneficiary: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            T::AssetClaims::claim(origin_location, ticker.clone(), amount, beneficiary.clone())?;
+            Self::deposit_event(Event::TrappedAssetsClaimed(beneficiary, ticker, amount));
+
+            Ok(().into())
+        }
+
+        /// Set max cumulative Move gas of all calls within a block, `None` removes the limit.
+        ///
+        /// Calls which gas limit doesn't fit into the rest of the block gas fail
+        /// with `BlockGasLimitExceeded`.
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_block_gas())]
+        pub fn set_max_block_gas(
+            origin: OriginFor<T>,
+            max_gas: Option<GasAmount>,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            MaxBlockGas::<T>::set(max_gas);
+            Self::deposit_event(Event::MaxBlockGasSet(max_gas));
+
+            Ok(().into())
+        }
+
+        /// Execute Move script of ed25519 signed transaction (BCS encoded), see `ed25519_tx`.
+        ///
+        /// Signature, chain id, expiration and sequence number are checked by `ValidateUnsigned`.
+        /// Gas limit is paid with the gas unit price (as the tip of `execute`) and the sequence
+        /// number is bumped even if the script fails, as the transaction is included anyway.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute().saturating_add(
+                T::GasWeightMapping::gas_to_weight(ed25519_tx::max_gas_amount(signed_tx))
+            )
+        )]
+        pub fn submit_ed25519_transaction(
+            origin: OriginFor<T>,
+            signed_tx: Ed25519SignedTransaction,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            let signed_tx = ed25519_tx::SignedTransaction::decode(&signed_tx)
+                .ok_or(Error::<T>::InvalidEd25519Transaction)?;
+            let key =
+                auth::SenderKey::new(crypto::SignatureScheme::Ed25519, &signed_tx.public_key);
+            let raw = signed_tx.raw;
+            let who = Self::ed25519_sender(&raw)?;
+            debug!(
+                "executing ed25519 transaction of {:?} with sequence number {}",
+                who, raw.sequence_number
+            );
+
+            let tip = gas_price::gas_tip::<T>(raw.max_gas_amount, raw.gas_unit_price);
+            let imbalance = T::Currency::withdraw(
+                &who,
+                tip,
+                WithdrawReasons::TIP,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            T::OnGasTip::on_unbalanced(imbalance);
+            SequenceNumbers::<T>::mutate(&who, |seq| *seq = seq.saturating_add(1));
+            Self::record_move_account(&who);
+
+            let result =
+                Self::execute_ed25519_transaction(&who, key, raw.payload, raw.max_gas_amount)?;
+            Self::deposit_event(Event::Ed25519TransactionExecuted(who, raw.sequence_number));
+
+            Ok(result)
+        }
+
+        /// Set gas of cold (first within a VM call) and warm storage accesses, charged on top
+        /// of the Move VM gas.
+        #[pallet::weight(<T as Config>::WeightInfo::set_storage_gas_schedule())]
+        pub fn set_storage_gas_schedule(
+            origin: OriginFor<T>,
+            schedule: gas::StorageGasSchedule,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            StorageGas::<T>::put(schedule);
+            Self::deposit_event(Event::StorageGasScheduleSet(schedule));
+
+            Ok(().into())
+        }
+
+        /// Authorize session key to call entry functions of the `scope` on behalf of the caller
+        /// until block `expires_at`, within the gas allowance. Replaces the previous
+        /// authorization of the key, see `session`.
+        #[pallet::weight(<T as Config>::WeightInfo::authorize_session_key(scope.len() as u32))]
+        pub fn authorize_session_key(
+            origin: OriginFor<T>,
+            session_key: T::AccountId,
+            scope: Vec<SessionScope<T::AccountId>>,
+            gas_allowance: GasAmount,
+            expires_at: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            ensure!(
+                !scope.is_empty() && scope.len() <= T::MaxSessionScope::get() as usize,
+                Error::<T>::InvalidSessionScope
+            );
+            ensure!(
+                expires_at > frame_system::Pallet::<T>::block_number(),
+                Error::<T>::SessionKeyExpired
+            );
+
+            let key = SessionKey {
+                scope,
+                gas_allowance,
+                expires_at,
+            };
+            SessionKeys::<T>::insert(&owner, &session_key, key);
+            Self::deposit_event(Event::SessionKeyAuthorized(owner, session_key, expires_at));
+
+            Ok(().into())
+        }
+
+        /// Revoke session key of the caller.
+        #[pallet::weight(<T as Config>::WeightInfo::revoke_session_key())]
+        pub fn revoke_session_key(
+            origin: OriginFor<T>,
+            session_key: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            SessionKeys::<T>::take(&owner, &session_key).ok_or(Error::<T>::UnknownSessionKey)?;
+            Self::deposit_event(Event::SessionKeyRevoked(owner, session_key));
+
+            Ok(().into())
+        }
+
+        /// Execute Move entry function call on behalf of `owner`, signed by its session key.
+        ///
+        /// The key, its expiry, gas allowance and the called function are checked by
+        /// `CheckSessionKey` signed extension and here, gas used is deducted from the allowance.
+        /// The gas tip of `ChargeGasPrice` is charged from the session key.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*gas_limit)
+            )
+        )]
+        pub fn execute_with_session_key(
+            origin: OriginFor<T>,
+            owner: T::AccountId,
+            tx_bc: TransactionBytecode,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            let session_key = ensure_signed(origin)?;
+            session::check::<T>(&owner, &session_key, &tx_bc, gas_limit)?;
+
+            let signers = vec![owner.clone()];
+            Self::with_execution_result(|execution| {
+                let vm_result =
+                    Self::raw_execute_script(&signers, tx_bc, gas_limit, false, false)
+                        .map_err(|e| result::rejected::<T>(e, T::WeightInfo::execute()))?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+                let gas_used = vm_result.gas_used;
+
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                SessionKeys::<T>::mutate(&owner, &session_key, |key| {
+                    if let Some(key) = key {
+                        key.gas_allowance = key.gas_allowance.saturating_sub(gas_used);
+                    }
+                });
+                Self::settle_move_requests()?;
+                Self::settle_storage_deposits()?;
+                Self::bump_sequence_numbers(&signers);
+                Ok(result)
+            })
+        }
+
+        /// Execute Move script signed by `sender`, submitted and paid by the caller (sponsor).
+        ///
+        /// The sender signs the sponsored payload (see `sponsor::payload`) for the sponsor with
+        /// its current sequence number, the signature is checked by `CheckSponsor` signed
+        /// extension and here. The gas tip of `ChargeGasPrice` is charged from the sponsor.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*gas_limit)
+            )
+        )]
+        pub fn execute_sponsored(
+            origin: OriginFor<T>,
+            sender: T::AccountId,
+            tx_bc: TransactionBytecode,
+            gas_limit: GasAmount,
+            valid_thru: T::BlockNumber,
+            signature: <T as groupsign::Config>::Signature,
+        ) -> DispatchResultWithPostInfo {
+            let sponsor = ensure_signed(origin)?;
+            sponsor::check::<T>(
+                &sponsor,
+                &sender,
+                &tx_bc,
+                gas_limit,
+                valid_thru,
+                &signature,
+            )?;
+            let sequence_number = SequenceNumbers::<T>::get(&sender);
+
+            let signers = vec![sender.clone()];
+            Self::with_execution_result(|execution| {
+                let vm_result =
+                    Self::raw_execute_script(&signers, tx_bc, gas_limit, false, false)
+                        .map_err(|e| result::rejected::<T>(e, T::WeightInfo::execute()))?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::settle_move_requests()?;
+                Self::settle_storage_deposits()?;
+                Self::bump_sequence_numbers(&signers);
+                Self::deposit_event(Event::SponsoredTransactionExecuted(
+                    sponsor,
+                    sender,
+                    sequence_number,
+                ));
+                Ok(result)
+            })
+        }
+
+        /// Execute Move script dispatched by XCM `Transact`, signed by the account of the origin
+        /// (`XcmTransactOrigin`).
+        ///
+        /// The XCM executor drops errors of the dispatched calls, so failed scripts are reverted
+        /// and queued into `FailedTransacts` with the error, to be retried with
+        /// `retry_failed_transact`. The call fails only if the queue is full.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute()
+                .saturating_add(T::GasWeightMapping::gas_to_weight(*gas_limit))
+                .saturating_add(T::DbWeight::get().reads_writes(2, 3))
+        )]
+        pub fn transact_execute(
+            origin: OriginFor<T>,
+            tx_bc: TransactionBytecode,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            let who = T::XcmTransactOrigin::ensure_origin(origin)?;
+
+            let signers = vec![who.clone()];
+            match Self::execute_signed(&signers, tx_bc.clone(), gas_limit) {
+                Ok(result) => Ok(result),
+                Err(err) => {
+                    Self::queue_failed_transact(who, tx_bc, gas_limit, err.error)?;
+                    Ok(err.post_info)
+                }
+            }
+        }
+
+        /// Retry failed script dispatched by XCM `Transact`, allowed to anyone.
+        ///
+        /// The script is executed signed by its account with the original gas limit, so `max_gas`
+        /// should cover it. Executed script is removed from the queue, failed one stays there.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::execute().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*max_gas)
+            )
+        )]
+        pub fn retry_failed_transact(
+            origin: OriginFor<T>,
+            id: u64,
+            max_gas: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let transact =
+                FailedTransacts::<T>::get(id).ok_or(Error::<T>::UnknownFailedTransact)?;
+            ensure!(
+                max_gas >= transact.gas_limit,
+                Error::<T>::FailedTransactGasLimitTooLow
+            );
+
+            let signers = vec![transact.account];
+            let result = Self::execute_signed(&signers, transact.tx_bc, transact.gas_limit)?;
+
+            Self::remove_failed_transact(id);
+            Self::deposit_event(Event::FailedTransactRetried(id));
+            Ok(result)
+        }
+
+        /// Remove failed script dispatched by XCM `Transact` from the queue, allowed to the
+        /// account of the script or governance (`UpdateOrigin`).
+        #[pallet::weight(<T as Config>::WeightInfo::discard_failed_transact())]
+        pub fn discard_failed_transact(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResultWithPostInfo {
+            let transact =
+                FailedTransacts::<T>::get(id).ok_or(Error::<T>::UnknownFailedTransact)?;
+            if T::UpdateOrigin::ensure_origin(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                ensure!(
+                    who == transact.account,
+                    Error::<T>::NotFailedTransactAccount
+                );
+            }
+
+            Self::remove_failed_transact(id);
+            Self::deposit_event(Event::FailedTransactDiscarded(id));
+            Ok(().into())
+        }
+
+        /// Stage a chunk of the module bytecode, so modules not fitting into a single extrinsic
+        /// (up to `MaxStagedModuleSize`, e.g. framework updates) are uploaded in several ones.
+        ///
+        /// `offset` must be the size of the bytecode staged so far, so chunks are appended in
+        /// order, zero starts a new module discarding the staged one. Deposit is reserved per
+        /// byte of the staged bytecode until the module is published with
+        /// `publish_staged_module` or discarded with `discard_staged_module`.
+        #[pallet::weight(<T as Config>::WeightInfo::stage_module_chunk(chunk.len() as u32))]
+        #[transactional]
+        pub fn stage_module_chunk(
+            origin: OriginFor<T>,
+            offset: u32,
+            chunk: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            // Allows to stage Standard Library modules if root.
+            let (sender, signer) = Self::ensure_and_convert(origin)?;
+            Self::ensure_publisher_allowed(&sender, &signer)?;
+            ensure!(
+                !chunk.is_empty() && chunk.len() <= T::MaxModuleSize::get() as usize,
+                Error::<T>::InvalidModuleChunk
+            );
+
+            let mut staged = match StagedModules::<T>::get(&signer) {
+                Some(staged) if offset != 0 => staged,
+                Some(staged) => {
+                    T::Currency::unreserve(&signer, staged.deposit);
+                    StagedModule::default()
+                }
+                None => StagedModule::default(),
+            };
+            ensure!(
+                staged.bytecode.len() == offset as usize,
+                Error::<T>::ModuleChunkOffsetMismatch
+            );
+            let size = staged.bytecode.len().saturating_add(chunk.len());
+            ensure!(
+                size <= T::MaxStagedModuleSize::get() as usize,
+                Error::<T>::StagedModuleTooLarge
+            );
+
+            // Modules staged under `0x1` (root) have no deposit, as published ones.
+            if sender != CORE_CODE_ADDRESS {
+                let amount = T::ModuleDepositPerByte::get()
+                    .saturating_mul((chunk.len() as u32).into());
+                T::Currency::reserve(&signer, amount)
+                    .map_err(|_| Error::<T>::InsufficientModuleDeposit)?;
+                staged.deposit = staged.deposit.saturating_add(amount);
+            }
+            staged.bytecode.extend(chunk);
+            StagedModules::<T>::insert(&signer, staged);
+
+            Self::deposit_event(Event::ModuleChunkStaged(signer, size as u32));
+            Ok(().into())
+        }
+
+        /// Publish the module staged by `stage_module_chunk`.
+        ///
+        /// The assembled bytecode is verified and published as by `publish_module`, the deposit
+        /// of the staged bytecode is replaced with the module deposit. Failed publishing keeps
+        /// the staged module, so it can be published again or discarded.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::publish_module().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*gas_limit)
+            )
+        )]
+        pub fn publish_staged_module(
+            origin: OriginFor<T>,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            let (sender, signer) = Self::ensure_and_convert(origin)?;
+            debug!("executing `publish staged module` with signed {:?}", sender);
+            Self::ensure_publisher_allowed(&sender, &signer)?;
+            let staged = StagedModules::<T>::get(&signer).ok_or(Error::<T>::NoStagedModule)?;
+            let republished = Self::republished_modules(&[&staged.bytecode]);
+
+            Self::with_publish_result(|execution| {
+                StagedModules::<T>::remove(&signer);
+                T::Currency::unreserve(&signer, staged.deposit);
+
+                let vm_result = Self::publish_module_bytecode(
+                    &signer,
+                    staged.bytecode.clone(),
+                    gas_limit,
+                    false,
+                )?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::update_module_deposits(&sender, &signer, &[staged.bytecode.clone()])?;
+                Self::update_source_maps(&sender, &signer, &[staged.bytecode], None)?;
+                Self::flush_republished(&signer, republished);
+
+                // Emit an event:
+                Self::deposit_event(Event::ModulePublished(signer));
+
+                Ok(result)
+            })
+        }
+
+        /// Discard the module staged by the caller, the deposit is released.
+        #[pallet::weight(<T as Config>::WeightInfo::discard_staged_module())]
+        pub fn discard_staged_module(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let (_, signer) = Self::ensure_and_convert(origin)?;
+            let staged = StagedModules::<T>::take(&signer).ok_or(Error::<T>::NoStagedModule)?;
+            T::Currency::unreserve(&signer, staged.deposit);
+
+            Self::deposit_event(Event::StagedModuleDiscarded(signer));
+            Ok(().into())
+        }
+
+        /// Publish Move module with its source map (as produced by the Move compiler).
+        ///
+        /// Source map is stored separately from the module with the deposit per byte and is
+        /// served by `mvm_getSourceMap` RPC, e.g. to locate aborts. Republishing the module
+        /// replaces or removes its source map, as offsets of the previous bytecode are stale.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::publish_module().saturating_add(
+                T::GasWeightMapping::gas_to_weight(*gas_limit)
+            )
+        )]
+        pub fn publish_module_with_source_map(
+            origin: OriginFor<T>,
+            module_bc: ModuleBytecode,
+            source_map: Vec<u8>,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            Self::do_publish_module(origin, module_bc, Some(source_map), gas_limit)
+        }
+
+        /// Enact governance proposal submitted by Move code, see `governance`.
+        ///
+        /// Proposed by the runtime governance (`Governance`), the call of the proposal is
+        /// dispatched with the root origin, then its callback script is executed signed by the
+        /// proposer. `max_weight` covers both, heavier proposals and undecodable calls are
+        /// rejected (discard them instead). Failures of the call or the callback don't fail the
+        /// enactment, `GovernanceProposalEnacted` event reports the dispatch result. Returns the
+        /// actual weight of the enactment.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::enact_governance_proposal().saturating_add(*max_weight)
+        )]
+        pub fn enact_governance_proposal(
+            origin: OriginFor<T>,
+            id: u64,
+            max_weight: Weight,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            let proposal = GovernanceProposals::<T>::get(id)
+                .ok_or(Error::<T>::UnknownGovernanceProposal)?;
+
+            let call_weight = T::Governance::call_weight(&proposal.call)
+                .ok_or(Error::<T>::InvalidGovernanceProposalCall)?;
+            let callback_weight = if proposal.callback.is_empty() {
+                0
+            } else {
+                T::GasWeightMapping::gas_to_weight(proposal.callback_gas)
+            };
+            ensure!(
+                call_weight.saturating_add(callback_weight) <= max_weight,
+                Error::<T>::GovernanceProposalTooHeavy
+            );
+            GovernanceProposals::<T>::remove(id);
+
+            let mut weight = <T as Config>::WeightInfo::enact_governance_proposal();
+            let dispatched = match T::Governance::dispatch(proposal.call) {
+                Ok(info) => {
+                    weight = weight.saturating_add(info.actual_weight.unwrap_or(call_weight));
+                    true
+                }
+                Err(err) => {
+                    error!("Governance proposal {} failed: {:?}", id, err.error);
+                    let actual_weight = err.post_info.actual_weight.unwrap_or(call_weight);
+                    weight = weight.saturating_add(actual_weight);
+                    false
+                }
+            };
+            Self::deposit_event(Event::GovernanceProposalEnacted(id, dispatched));
+
+            if dispatched && !proposal.callback.is_empty() {
+                let signers = vec![proposal.proposer];
+                let result =
+                    Self::execute_signed(&signers, proposal.callback, proposal.callback_gas);
+                let info = result.unwrap_or_else(|err| {
+                    error!("Callback of governance proposal {} failed: {:?}", id, err.error);
+                    err.post_info
+                });
+                weight = weight.saturating_add(info.actual_weight.unwrap_or(callback_weight));
+            }
+
+            Ok(Some(weight).into())
+        }
+
+        /// Discard governance proposal submitted by Move code (e.g. rejected by the
+        /// referendum), allowed to the proposer or governance (`UpdateOrigin`).
+        #[pallet::weight(<T as Config>::WeightInfo::discard_governance_proposal())]
+        pub fn discard_governance_proposal(
+            origin: OriginFor<T>,
+            id: u64,
+        ) -> DispatchResultWithPostInfo {
+            let proposal = GovernanceProposals::<T>::get(id)
+                .ok_or(Error::<T>::UnknownGovernanceProposal)?;
+            if T::UpdateOrigin::ensure_origin(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                ensure!(
+                    who == proposal.proposer,
+                    Error::<T>::NotGovernanceProposer
+                );
+            }
+
+            GovernanceProposals::<T>::remove(id);
+            Self::deposit_event(Event::GovernanceProposalDiscarded(id));
+            Ok(().into())
+        }
+
+        /// Allow the account to request treasury spends from Move code, as the capability of
+        /// the governance-published module running the grants.
+        #[pallet::weight(<T as Config>::WeightInfo::allow_treasury_spender())]
+        pub fn allow_treasury_spender(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !TreasurySpenders::<T>::contains_key(&account),
+                Error::<T>::TreasurySpenderAlreadyAllowed
+            );
+
+            TreasurySpenders::<T>::insert(&account, ());
+            Self::deposit_event(Event::TreasurySpenderAllowed(account));
+
+            Ok(().into())
+        }
+
+        /// Disallow the account to request treasury spends from Move code.
+        #[pallet::weight(<T as Config>::WeightInfo::disallow_treasury_spender())]
+        pub fn disallow_treasury_spender(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            T::UpdateOrigin::ensure_origin(origin)?;
+
+            TreasurySpenders::<T>::take(&account).ok_or(Error::<T>::UnknownTreasurySpender)?;
+            TreasurySpent::<T>::remove(&account);
+            Self::deposit_event(Event::TreasurySpenderDisallowed(account));
+
+            Ok(().into())
+        }
+
+        /// Remove callback subscribed by Move code (e.g. failing every time), allowed to the
+        /// subscriber or governance (`UpdateOrigin`).
+        #[pallet::weight(<T as Config>::WeightInfo::remove_callback_subscription())]
+        pub fn remove_callback_subscription(
+            origin: OriginFor<T>,
+            subscriber: T::AccountId,
+            trigger: CallbackTrigger,
+        ) -> DispatchResultWithPostInfo {
+            if T::UpdateOrigin::ensure_origin(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                ensure!(who == subscriber, Error::<T>::NotCallbackSubscriber);
+            }
+
+            Self::unsubscribe_callback(subscriber, trigger)?;
+            Ok(().into())
+        }
+    }
+
+    /// Ed25519 signed transactions are submitted unsigned, validated by the embedded signature.
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T>
+    where
+        OriginFor<T>: Into<Result<groupsign::Origin<T>, OriginFor<T>>>,
+    {
+        type Call = Call<T>;
+
+        fn validate_unsigned(
+            _source: TransactionSource,
+            call: &Self::Call,
+        ) -> TransactionValidity {
+            let (raw, who) = Self::check_ed25519_call(call)?;
+
+            // Transactions with the next sequence numbers wait in the pool.
+            let current = SequenceNumbers::<T>::get(&who);
+            ensure!(raw.sequence_number >= current, InvalidTransaction::Stale);
+
+            let mut valid = ValidTransaction::with_tag_prefix("MvmEd25519Transaction")
+                .priority(raw.gas_unit_price)
+                .and_provides((who.clone(), raw.sequence_number))
+                .propagate(true);
+            if raw.sequence_number > current {
+                valid = valid.and_requires((who, raw.sequence_number - 1));
+            }
+            valid.build()
+        }
+
+        fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+            let (raw, who) = Self::check_ed25519_call(call)?;
+
+            let current = SequenceNumbers::<T>::get(&who);
+            ensure!(raw.sequence_number >= current, InvalidTransaction::Stale);
+            ensure!(raw.sequence_number == current, InvalidTransaction::Future);
+            Ok(())
+        }
+    }
+
+    /// Genesis configuration.
+    ///
+    /// Allows to configure Move VM in the genesis block.
+    /// Accepts Standard Library modules list and initialize state by calling initialize function with arguments.
+    #[pallet::genesis_config]
+    pub struct GenesisConfig<T: Config> {
+        pub _phantom: std::marker::PhantomData<T>,
+        /// Move Standard library bytes.
+        pub move_stdlib: Vec<u8>,
+        /// Pontem Framework library bytes.
+        pub pont_framework: Vec<u8>,
+        /// Runtime framework library bytes: modules of the pallet requests and resources,
+        /// e.g. `0x1::Schedule`.
+        #[serde(default)]
+        pub runtime_framework: Vec<u8>,
+        /// Module name for genesis init.
+        pub init_module: Vec<u8>,
+        // Init function name.
+        pub init_func: Vec<u8>,
+        // Init function arguments.
+        pub init_args: Vec<Vec<u8>>,
+        /// Move state entries (access keys with values) stored after the initialization,
+        /// e.g. state snapshot exported from another chain.
+        #[serde(default)]
+        pub move_state: Vec<(Vec<u8>, Vec<u8>)>,
+        /// Name of the chain published to Move contracts, see `info`.
+        #[serde(default)]
+        pub chain_name: Vec<u8>,
+        /// Chain id of ed25519 signed transactions, see `ed25519_tx`.
+        #[serde(default)]
+        pub aptos_chain_id: u8,
+    }
+
+    /// Default genesis configuration.
+    #[cfg(feature = "std")]
+    impl<T: Config> Default for GenesisConfig<T> {
+        fn default() -> Self {
+            GenesisConfig {
+                _phantom: Default::default(),
+                move_stdlib: vec![],
+                pont_framework: vec![],
+                runtime_framework: vec![],
+                init_module: vec![],
+                init_func: vec![],
+                init_args: vec![],
+                move_state: vec![],
+                chain_name: vec![],
+                aptos_chain_id: 0,
+            }
+        }
+    }
+
+    /// Initialize Move VM during genesis block.
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+        fn build(&self) {
+            let mut stdlib_package = ModulePackage::try_from(&self.move_stdlib[..])
+                .expect("Failed to parse move stdlib");
+
+            // Pontem Framework could be omitted, e.g. if stdlib is replaced by the mock in tests.
+            if !self.pont_framework.is_empty() {
+                let pont_framework_package = ModulePackage::try_from(&self.pont_framework[..])
+                    .expect("Failed to parse pont framework lib");
+
+                stdlib_package.join(pont_framework_package);
+            }
+            if !self.runtime_framework.is_empty() {
+                let runtime_framework_package =
+                    ModulePackage::try_from(&self.runtime_framework[..])
+                        .expect("Failed to parse runtime framework lib");
+
+                stdlib_package.join(runtime_framework_package);
+            }
+
+            let genesis_config = move_vm::genesis::build_genesis_config(
+                stdlib_package.into_tx(CORE_CODE_ADDRESS),
+                Some(move_vm::genesis::InitFuncConfig {
+                    module: self.init_module.clone(),
+                    func: self.init_func.clone(),
+                    args: self.init_args.clone(),
+                }),
+            );
+
+            let storage = Pallet::<T>::untracked_move_vm_storage();
+            move_vm::genesis::init_storage(storage, genesis_config)
+                .expect("Unable to initialize storage");
+
+            // Snapshot entries override the initialized state, e.g. standard library modules.
+            for (key, value) in &self.move_state {
+                Pallet::<T>::write_untracked(key, Some(value.as_slice()));
+            }
+
+            ChainName::<T>::put(&self.chain_name);
+            AptosChainId::<T>::put(self.aptos_chain_id);
+        }
+    }
+
+    /// Clearing Move VM cache once block processed.
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
+    // TODO: make it configurable:  where <T as Config>::ClearMvmCachePolicy = ...
+    {
+        fn on_initialize(block: BlockNumberFor<T>) -> Weight {
+            // Randomness seed for Move contracts, the same within the block.
+            let (seed, _) = T::Randomness::random(randomness::RANDOMNESS_SUBJECT);
+            Self::write_pallet_resource(
+                &randomness::seed_key(),
+                &randomness::seed_resource(seed.as_ref(), block.unique_saturated_into()),
+            );
+
+            // Parent hash, block height and timestamp are in the execution context.
+            let parent_hash = frame_system::Pallet::<T>::parent_hash();
+            Self::write_pallet_resource(
+                &block::parent_hash_key(),
+                &block::parent_hash_resource(parent_hash.as_ref(), block.unique_saturated_into()),
+            );
+
+            // Staking round, collators and total stake of the runtime staking.
+            if let Some(round) = T::Staking::round() {
+                Self::write_pallet_resource(&staking::round_key(), &round.to_resource());
+            }
+
+            // Move gas is metered per block.
+            BlockGasUsed::<T>::kill();
+            LoaderStats::<T>::kill();
+
+            // Callbacks run on the fresh pallet resources and the block gas.
+            let callbacks = Self::execute_callbacks();
+
+            <T as Config>::WeightInfo::on_initialize()
+                .saturating_add(<T as Config>::WeightInfo::on_finalize())
+                .saturating_add(callbacks)
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<(), &'static str> {
+            let modules = migrations::check_state::<T>()?;
+            info!("sp-mvm pre-upgrade: {} modules checked", modules);
+            Ok(())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade() -> Result<(), &'static str> {
+            if Self::on_chain_storage_version() != STORAGE_VERSION {
+                return Err("sp-mvm storage version is not migrated");
+            }
+            let modules = migrations::check_state::<T>()?;
+            info!("sp-mvm post-upgrade: {} modules checked", modules);
+            Ok(())
+        }
+
+        fn on_finalize(block: BlockNumberFor<T>) {
+            let events_count = MoveEventsCount::<T>::take();
+            if events_count > 0 {
+                sp_io::offchain_index::set(
+                    &offchain::events_count_key(block.unique_saturated_into()),
+                    &events_count.encode(),
+                );
+            }
+            let changes_count = MoveWriteSetCount::<T>::take();
+            if changes_count > 0 {
+                sp_io::offchain_index::set(
+                    &writeset::offchain::changes_count_key(block.unique_saturated_into()),
+                    &changes_count.encode(),
+                );
+            }
+
+            if Self::is_move_vm_used() {
+                if let Some(vm) = Self::get_move_vm_cell().get() {
+                    vm.clear();
+                    Self::set_move_vm_clean();
+                    trace!("VM cache cleared on finalize block");
+                }
+            }
+            // Otherwise we are not requesting VM.
+        }
+
+        fn offchain_worker(block: BlockNumberFor<T>) {
+            Self::collect_write_sets(block.unique_saturated_into());
+        }
+    }
+
+    /// Get VM methods unification.
+    impl<T: Config> Pallet<T> {
+        fn get_vm() -> Result<&'static VmWrapperTy, Error<T>> {
+            let vm = Self::try_get_or_create_move_vm()?;
+            Ok(vm)
+        }
+    }
+
+    /// Move VM allows us to configure Gas Price, but we use constant for gas price, as we follow general Substrate approach with weight and tips.
+    const GAS_UNIT_PRICE: u64 = 1;
+
+    impl<T: Config> Pallet<T> {
+        #![allow(clippy::useless_conversion)]
+        /// Returns gas limit object requires for execute/publish functions.
+        fn get_move_gas_limit(gas_limit: u64) -> Result<Gas, Error<T>> {
+            Gas::new(gas_limit, GAS_UNIT_PRICE).map_err(|_| Error::InvalidGasAmountMaxValue)
+        }
+
+        /// Returns gas limit bounded by the execution deadline (in milliseconds), see `gas::deadline_gas_limit`.
+        pub fn deadline_gas_limit(gas_limit: u64, deadline_ms: Option<u64>) -> u64 {
+            gas::deadline_gas_limit::<T::GasWeightMapping>(gas_limit, deadline_ms)
+        }
+
+        /// Get pallet account id.
+        pub fn get_account_id() -> T::AccountId {
+            T::PalletId::get().into_account()
+        }
+
+        /// Execute Move VM script with provided signers, script byte code, gas limit, and dry run configuration.
+        /// In case of dry run nothing would be written to storage after execution (required mostly by RPC calls, e.g. estimate gas etc).
+        /// Multiple signers supported by utilizing multisig pallet.
+        pub fn raw_execute_script(
+            signers: &[T::AccountId],
+            tx_bc: Vec<u8>,
+            gas_limit: u64,
+            root_signed: bool,
+            dry_run: bool,
+        ) -> Result<VmResult, Error<T>>
+        where
+            <T as timestamp::Config>::Moment: UniqueSaturatedInto<u64>,
+            // T::BlockNumber: BaseArithmetic,
+            // T::BlockNumber: UniqueSaturatedInto<u64>,
+            T::BlockNumber: TryInto<u64>,
+        {
+            // TODO: some minimum gas for processing transaction from bytes?
+            ensure!(
+                tx_bc.len() <= T::MaxScriptSize::get() as usize,
+                Error::<T>::ScriptTooLarge
+            );
+            let transaction = Transaction::try_from(&tx_bc[..])
+                .map_err(|_| Error::<T>::TransactionValidationError)?;
+
+            // TODO: think about in future change fn-parameters
+            //       from `signers` & `root_signed` to just `origin: OriginFor`
+            //       and so ensure root here instead of trust `root_signed`.
+            ensure!(
+                root_signed == transaction.has_root_signer(),
+                Error::<T>::TransactionIsNotAllowedError
+            );
+
+            for signer in signers {
+                Self::ensure_not_migrated(signer)?;
+            }
+
+            // Root signer is provided by the VM, so only the other signers are checked.
+            let signers_count = (!root_signed).then(|| transaction.signers_count() as usize);
+            let tx = {
+                let signers = if transaction.signers_count() == 0 {
+                    &[]
+                } else {
+                    signers
+                };
+
+                // Is it really necessary? VM will throw an error if it caused.
+                if transaction.signers_count() as usize != signers.len() {
+                    error!(
+                        "Transaction signers num isn't eq signers: {} != {}",
+                        transaction.signers_count(),
+                        signers.len()
+                    );
+                    return Err(Error::<T>::TransactionSignersNumError.into());
+                }
+
+                let signers = signers
+                    .iter()
+                    .map(addr::account_to_bytes)
+                    .map(AccountAddress::new)
+                    .collect();
+
+                transaction
+                    .into_script(signers)
+                    .map_err(|_| Error::<T>::TransactionValidationError)?
+            };
+
+            if !root_signed {
+                Self::ensure_script_allowed(&tx)?;
+            }
+
+            Self::execute_script_tx(tx, signers_count, gas_limit, dry_run)
+        }
+
+        /// Execute Move VM script signed by arbitrary Move address, e.g. `0x1`.
+        /// Script must have exactly one signer, script allowlist is not applied.
+        pub fn raw_execute_script_as(
+            address: AccountAddress,
+            tx_bc: Vec<u8>,
+            gas_limit: u64,
+            dry_run: bool,
+        ) -> Result<VmResult, Error<T>>
+        where
+            <T as timestamp::Config>::Moment: UniqueSaturatedInto<u64>,
+            T::BlockNumber: TryInto<u64>,
+        {
+            ensure!(
+                tx_bc.len() <= T::MaxScriptSize::get() as usize,
+                Error::<T>::ScriptTooLarge
+            );
+            let transaction = Transaction::try_from(&tx_bc[..])
+                .map_err(|_| Error::<T>::TransactionValidationError)?;
+
+            ensure!(
+                !transaction.has_root_signer(),
+                Error::<T>::TransactionIsNotAllowedError
+            );
+            ensure!(
+                transaction.signers_count() == 1,
+                Error::<T>::TransactionSignersNumError
+            );
+
+            let tx = transaction
+                .into_script(vec![address])
+                .map_err(|_| Error::<T>::TransactionValidationError)?;
+
+            Self::execute_script_tx(tx, Some(1), gas_limit, dry_run)
+        }
+
+        /// Execute script transaction with already resolved signers, `signers` is the amount
+        /// of signers checked against the script parameters (see `check_call`).
+        fn execute_script_tx(
+            tx: ScriptTx,
+            signers: Option<usize>,
+            gas_limit: u64,
+            dry_run: bool,
+        ) -> Result<VmResult, Error<T>>
+        where
+            <T as timestamp::Config>::Moment: UniqueSaturatedInto<u64>,
+            T::BlockNumber: TryInto<u64>,
+        {
+            ensure!(
+                tx.type_args()
+                    .iter()
+                    .all(|tag| types::type_tag_depth(tag) <= T::MaxTypeArgDepth::get()),
+                Error::<T>::TypeArgsTooDeep
+            );
+            Self::check_call(&tx, signers)?;
+            let called_module = Self::called_module_key(&tx).filter(|_| !dry_run);
+
+            let vm = Self::get_vm()?;
+            let gas = Self::get_move_gas_limit(gas_limit)?;
+
+            let ctx = {
+                let height = frame_system::Pallet::<T>::block_number()
+                    .try_into()
+                    .map_err(|_| Error::<T>::NumConversionError)?
+                    as u64;
+
+                // Because if we call now().as_millis() during genesis it returns error.
+                // And stdlib initializing during genesis.
+                let time = match height {
+                    0 => 0,
+                    _ => <timestamp::Pallet<T> as UnixTime>::now().as_millis() as u64,
+                };
+
+                ExecutionContext::new(time, height)
+            };
+
+            let res = Self::meter_block_gas(gas_limit, dry_run, || {
+                Self::record_cache_hit(called_module, || vm.execute_script(gas, ctx, tx, dry_run))
+            })?;
+            debug!("execution result: {:?}", res);
+
+            Ok(res)
+        }
+
+        /// Ensures `sequence_number` is the current sequence number of the account.
+        fn ensure_sequence_number(
+            who: &T::AccountId,
+            sequence_number: u64,
+        ) -> Result<(), Error<T>> {
+            let current = SequenceNumbers::<T>::get(who);
+            ensure!(sequence_number >= current, Error::<T>::SequenceNumberTooOld);
+            ensure!(sequence_number == current, Error::<T>::SequenceNumberTooNew);
+            Ok(())
+        }
+
+        /// Checks ed25519 signed transaction of the call: signature, chain id, expiration,
+        /// script size and balance of the sender for the gas. Returns the raw transaction
+        /// and the sender.
+        fn check_ed25519_call(
+            call: &Call<T>,
+        ) -> Result<(ed25519_tx::RawTransaction, T::AccountId), TransactionValidityError> {
+            let signed_tx = match call {
+                Call::submit_ed25519_transaction { signed_tx } => signed_tx,
+                _ => return Err(InvalidTransaction::Call.into()),
+            };
+            let tx = ed25519_tx::SignedTransaction::decode(signed_tx)
+                .ok_or(InvalidTransaction::Call)?;
+            ensure!(tx.verify(), InvalidTransaction::BadProof);
+
+            let raw = tx.raw;
+            ensure!(raw.chain_id == AptosChainId::<T>::get(), InvalidTransaction::BadProof);
+            let now = <timestamp::Pallet<T> as UnixTime>::now().as_secs();
+            ensure!(raw.expiration_timestamp_secs > now, InvalidTransaction::Stale);
+            ensure!(
+                raw.payload.len() <= T::MaxScriptSize::get() as usize,
+                InvalidTransaction::ExhaustsResources
+            );
+
+            // Unsigned transactions pay no fees, so the gas must be paid.
+            ensure!(raw.gas_unit_price > 0, InvalidTransaction::Payment);
+            let who = Self::ed25519_sender(&raw).map_err(|_| InvalidTransaction::BadSigner)?;
+            let tip = gas_price::gas_tip::<T>(raw.max_gas_amount, raw.gas_unit_price);
+            let balance = T::Currency::free_balance(&who);
+            ensure!(
+                balance.saturating_sub(T::Currency::minimum_balance()) >= tip,
+                InvalidTransaction::Payment
+            );
+
+            Ok((raw, who))
+        }
+
+        /// Account of the ed25519 transaction sender, Move address of the account is the sender.
+        fn ed25519_sender(raw: &ed25519_tx::RawTransaction) -> Result<T::AccountId, Error<T>> {
+            addr::address_to_account(&AccountAddress::new(raw.sender))
+                .map_err(|_| Error::<T>::InvalidEd25519Transaction)
+        }
+
+        /// Executes script of the ed25519 transaction signed by the sender with the key.
+        fn execute_ed25519_transaction(
+            who: &T::AccountId,
+            key: auth::SenderKey,
+            tx_bc: Vec<u8>,
+            gas_limit: u64,
+        ) -> DispatchResultWithPostInfo {
+            let signers = vec![who.clone()];
+            let mut resources = Self::signer_resources(&signers);
+            resources.push((
+                auth::sender_key_key(&addr::account_to_account_address(who)),
+                key.to_resource(),
+            ));
+            Self::with_execution_result(|execution| {
+                let vm_result = Self::with_signer_resources(&resources, || {
+                    Self::raw_execute_script(&signers, tx_bc, gas_limit, false, false)
+                })?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::settle_move_requests()?;
+                Self::settle_storage_deposits()?;
+                Ok(result)
+            })
+        }
+
+        /// Runs the Move call in a storage transaction, reverted if the call fails, and emits
+        /// `ExecutionResult` event with the VM result the call reports, so the outcome of
+        /// failed calls is visible to indexers too. Calls rejected before reaching the VM
+        /// (e.g. malformed transactions) report nothing and emit no event.
+        /// Failed requests of Move code are collected for `settle_move_requests` of the call.
+        fn with_execution_result(
+            call: impl FnOnce(&mut Option<ExecutionResult>) -> DispatchResultWithPostInfo,
+        ) -> DispatchResultWithPostInfo {
+            let writes = MoveWriteSetCount::<T>::get();
+            let events = MoveEventsCount::<T>::get();
+
+            let mut execution = None;
+            let mut failed_request = None;
+            let result = support::storage::with_transaction(|| {
+                let result =
+                    failed_move_request::using(&mut failed_request, || call(&mut execution));
+                // Counters are reverted with the call, so changes and events are counted here.
+                if let Some(execution) = execution.as_mut() {
+                    execution.writes = MoveWriteSetCount::<T>::get().saturating_sub(writes);
+                    execution.events = MoveEventsCount::<T>::get().saturating_sub(events);
+                }
+                match result {
+                    Ok(_) => TransactionOutcome::Commit(result),
+                    Err(_) => TransactionOutcome::Rollback(result),
+                }
+            });
+
+            if let Some(execution) = execution {
+                Self::deposit_event(Event::ExecutionResult(execution));
+            }
+            result
+        }
+
+        /// Publishes modules within `with_execution_result`.
+        ///
+        /// Move VM caches the published modules, so the cache is cleared if the publish succeeded
+        /// but the call is rolled back (e.g. the module deposit can't be reserved): otherwise
+        /// the loader keeps serving modules missing in the storage.
+        fn with_publish_result(
+            call: impl FnOnce(&mut Option<ExecutionResult>) -> DispatchResultWithPostInfo,
+        ) -> DispatchResultWithPostInfo {
+            let mut published = false;
+            let result = Self::with_execution_result(|execution| {
+                let result = call(execution);
+                published = execution
+                    .as_ref()
+                    .map_or(false, |e| e.status_code == StatusCode::EXECUTED as u64);
+                result
+            });
+
+            if published && result.is_err() {
+                Self::clear_vm_cache();
+            }
+            result
+        }
+
+        /// Executes Move script signed by the accounts, reverted if it fails.
+        fn execute_signed(
+            signers: &[T::AccountId],
+            tx_bc: Vec<u8>,
+            gas_limit: u64,
+        ) -> DispatchResultWithPostInfo {
+            Self::with_execution_result(|execution| {
+                let vm_result = Self::raw_execute_script(signers, tx_bc, gas_limit, false, false)
+                    .map_err(|e| result::rejected::<T>(e, T::WeightInfo::execute()))?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::settle_move_requests()?;
+                Self::settle_storage_deposits()?;
+                Self::bump_sequence_numbers(signers);
+                Ok(result)
+            })
+        }
+
+        /// Queues failed script dispatched by XCM `Transact` into `FailedTransacts`.
+        fn queue_failed_transact(
+            account: T::AccountId,
+            tx_bc: Vec<u8>,
+            gas_limit: u64,
+            error: DispatchError,
+        ) -> Result<(), Error<T>> {
+            let count = FailedTransactsCount::<T>::get();
+            ensure!(
+                count < T::MaxFailedTransacts::get(),
+                Error::<T>::FailedTransactsQueueFull
+            );
+
+            let id = NextFailedTransactId::<T>::mutate(|next| {
+                let id = *next;
+                *next = next.saturating_add(1);
+                id
+            });
+            let transact = FailedTransact {
+                account: account.clone(),
+                tx_bc,
+                gas_limit,
+                error,
+                failed_at: frame_system::Pallet::<T>::block_number(),
+            };
+            FailedTransacts::<T>::insert(id, transact);
+            FailedTransactsCount::<T>::put(count.saturating_add(1));
+            Self::deposit_event(Event::TransactFailed(id, account, error));
+            Ok(())
+        }
+
+        /// Removes failed transact from the queue.
+        fn remove_failed_transact(id: u64) {
+            FailedTransacts::<T>::remove(id);
+            FailedTransactsCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+        }
+
+        /// Bumps sequence numbers of the script signers once the script is executed.
+        fn bump_sequence_numbers(signers: &[T::AccountId]) {
+            for signer in signers {
+                SequenceNumbers::<T>::mutate(signer, |seq| *seq = seq.saturating_add(1));
+                Self::record_move_account(signer);
+            }
+        }
+
+        /// Runs the VM call within the block gas ceiling (`MaxBlockGas`) and accounts used gas,
+        /// including gas of the storage accesses (see `charge_storage_accesses`).
+        ///
+        /// The call must fit with its whole gas limit, so the ceiling can't be exceeded.
+        /// Gas of dry runs isn't accounted, as well as gas of calls reverted afterwards.
+        fn meter_block_gas(
             gas_limit: u64,
+            dry_run: bool,
+            call: impl FnOnce() -> VmResult,
+        ) -> Result<VmResult, Error<T>> {
+            if dry_run {
+                return Ok(Self::charge_storage_accesses(gas_limit, call));
+            }
+
+            let used = BlockGasUsed::<T>::get();
+            if let Some(max_gas) = MaxBlockGas::<T>::get() {
+                ensure!(
+                    used.saturating_add(gas_limit) <= max_gas,
+                    Error::<T>::BlockGasLimitExceeded
+                );
+            }
+
+            let res = Self::charge_storage_accesses(gas_limit, call);
+            BlockGasUsed::<T>::put(used.saturating_add(res.gas_used));
+            Ok(res)
+        }
+
+        /// Runs the VM call and adds gas of the storage items accessed by it (recorded in memory
+        /// by `record_storage_access`) priced with `StorageGas`. The call runs out of gas if the
+        /// total exceeds the limit, its changes are reverted with the call
+        /// (see `with_execution_result`).
+        fn charge_storage_accesses(gas_limit: u64, call: impl FnOnce() -> VmResult) -> VmResult {
+            let mut accesses = BTreeMap::new();
+            let mut res = pending_storage_accesses::using(&mut accesses, call);
+
+            let schedule = StorageGas::<T>::get();
+            let storage_gas = accesses
+                .into_values()
+                .fold(0u64, |gas, accesses| gas.saturating_add(schedule.item_gas(accesses)));
+
+            let total = res.gas_used.saturating_add(storage_gas);
+            if total > gas_limit {
+                res.status_code = StatusCode::OUT_OF_GAS;
+                res.gas_used = gas_limit;
+            } else {
+                res.gas_used = total;
+            }
+            res
+        }
+
+        /// Ensures module bytecode fits `MaxModuleSize`, so oversized modules don't reach the verifier.
+        fn ensure_module_size(module_bc: &[u8]) -> Result<(), Error<T>> {
+            ensure!(
+                module_bc.len() <= T::MaxModuleSize::get() as usize,
+                Error::<T>::ModuleTooLarge
+            );
+            Ok(())
+        }
+
+        /// Checks amounts of type arguments, arguments and signers and primitive arguments
+        /// against the script or the entry function signature (see `entry`), so mistakes fail
+        /// before execution. Calls of missing modules are left to Move VM.
+        fn check_call(tx: &ScriptTx, signers: Option<usize>) -> Result<(), Error<T>> {
+            let type_args = tx.type_args().len();
+            let checked = match tx.call() {
+                Call::Script { code } => entry::check_script(code, type_args, tx.args(), signers),
+                Call::ScriptFunction {
+                    mod_address,
+                    mod_name,
+                    func_name,
+                } => {
+                    let module_id = ModuleId::new(*mod_address, mod_name.clone());
+                    match VMStorage::<T>::get(module_id.access_vector()) {
+                        Some(module) => entry::check_entry_function(
+                            &module,
+                            func_name,
+                            type_args,
+                            tx.args(),
+                            signers,
+                        ),
+                        None => Ok(()),
+                    }
+                }
+            };
+            checked.map_err(Error::<T>::from)
+        }
+
+        /// Ensures raw script is allowlisted if script allowlist mode is enabled.
+        fn ensure_script_allowed(tx: &ScriptTx) -> Result<(), Error<T>> {
+            if !Self::script_allowlist_enabled() {
+                return Ok(());
+            }
+
+            match tx.call() {
+                Call::Script { code } => {
+                    ensure!(
+                        AllowedScripts::<T>::contains_key(types::script_hash(code)),
+                        Error::<T>::UnknownScript
+                    );
+                    Ok(())
+                }
+                Call::ScriptFunction { .. } => Ok(()),
+            }
+        }
+
+        /// Returns versions and features of the pallet.
+        ///
+        /// Stdlib hash is calculated over all stored modules, so it's intended for RPC only.
+        pub fn get_vm_info() -> VmInfo {
+            let version = <Self as PalletInfoAccess>::crate_version();
+
+            let mut stdlib: Vec<_> = VMStorage::<T>::iter()
+                .filter(|(key, _)| storage::is_module_key(key, &CORE_CODE_ADDRESS))
+                .collect();
+            stdlib.sort();
+
+            VmInfo {
+                pallet_version: (version.major, version.minor, version.patch),
+                move_vm_revision: MOVE_VM_REVISION.as_bytes().to_vec(),
+                gas_schedule_version: gas::GAS_SCHEDULE_VERSION,
+                stdlib_hash: sp_io::hashing::blake2_256(&stdlib.encode()),
+                features: FEATURES.iter().map(|f| f.as_bytes().to_vec()).collect(),
+            }
+        }
+
+        /// Stores chain info resource for Move contracts, see `info`.
+        ///
+        /// Called by the runtime once the relay chain block the current block is built on is
+        /// known, e.g. with the validation data of the parachain block.
+        pub fn store_chain_info(parachain_id: u32, relay_block: u32) {
+            Self::write_pallet_resource(
+                &info::chain_info_key(),
+                &info::chain_info_resource(parachain_id, relay_block, &ChainName::<T>::get()),
+            );
+        }
+
+        /// Publishes module, stores its source map if supplied.
+        fn do_publish_module(
+            origin: OriginFor<T>,
+            module_bc: ModuleBytecode,
+            source_map: Option<Vec<u8>>,
+            gas_limit: GasAmount,
+        ) -> DispatchResultWithPostInfo {
+            // Allows to update Standard Library if root.
+            let (sender, signer) = Self::ensure_and_convert(origin)?;
+            debug!("executing `publish module` with signed {:?}", sender);
+            Self::ensure_publisher_allowed(&sender, &signer)?;
+            if let Some(source_map) = &source_map {
+                ensure!(
+                    source_map.len() <= T::MaxSourceMapSize::get() as usize,
+                    Error::<T>::SourceMapTooLarge
+                );
+            }
+            let republished = Self::republished_modules(&[&module_bc]);
+
+            Self::with_publish_result(|execution| {
+                // Publish module.
+                let vm_result =
+                    Self::raw_publish_module(&signer, module_bc.clone(), gas_limit, false)?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::update_module_deposits(&sender, &signer, &[module_bc.clone()])?;
+                Self::update_source_maps(&sender, &signer, &[module_bc], source_map)?;
+                Self::flush_republished(&signer, republished);
+
+                // Emit an event:
+                Self::deposit_event(Event::ModulePublished(signer));
+
+                Ok(result)
+            })
+        }
+
+        /// Publishes module package, stores its metadata if supplied.
+        fn do_publish_package(
+            origin: OriginFor<T>,
+            package: PackageBytecode,
+            info: Option<PackageInfo<T::AccountId>>,
+            gas_limit: GasAmount,
         ) -> DispatchResultWithPostInfo {
             // Allows to update Standard Library if root.
             let (sender, signer) = Self::ensure_and_convert(origin)?;
             debug!("executing `publish package` with signed {:?}", sender);
+            Self::ensure_publisher_allowed(&sender, &signer)?;
 
             let vm = Self::get_vm()?;
             let gas = Self::get_move_gas_limit(gas_limit)?;
 
-            let package = {
-                ModulePackage::try_from(&package[..])
-                    .map_err(|_| Error::<T>::TransactionValidationError)?
-                    .into_tx(sender)
+            let (modules, address) = ModulePackage::try_from(&package[..])
+                .map_err(|_| Error::<T>::TransactionValidationError)?
+                .into_tx(sender)
+                .into_inner();
+            for module in &modules {
+                Self::ensure_module_size(module)?;
+            }
+            let metadata = info
+                .map(|info| Self::package_metadata(&signer, info, &modules))
+                .transpose()?;
+            let republished =
+                Self::republished_modules(&modules.iter().map(Vec::as_slice).collect::<Vec<_>>());
+            let package = PackageTx::new(modules.clone(), address);
+
+            Self::with_publish_result(|execution| {
+                Self::record_verifier_runs(modules.len());
+                let vm_result = Self::meter_block_gas(gas_limit, false, || {
+                    vm.publish_module_package(gas, package, false)
+                })?;
+                *execution = Some(ExecutionResult::from(&vm_result));
+
+                // produce result with spended gas:
+                let result = result::from_vm_result::<T>(vm_result)?;
+
+                Self::update_module_deposits(&sender, &signer, &modules)?;
+                Self::update_source_maps(&sender, &signer, &modules, None)?;
+                Self::flush_republished(&signer, republished);
+
+                if let Some(metadata) = metadata {
+                    let (name, upgrade_number) = (metadata.name.clone(), metadata.upgrade_number);
+                    Packages::<T>::insert(&signer, &name, metadata);
+                    Self::deposit_event(Event::PackageMetadataStored(
+                        signer.clone(),
+                        name,
+                        upgrade_number,
+                    ));
+                }
+
+                // Emit an event:
+                Self::deposit_event(Event::PackagePublished(signer));
+
+                Ok(result)
+            })
+        }
+
+        /// Validates metadata supplied for the package `modules` published by `account`
+        /// and builds its stored metadata, upgrading the published package of the same name.
+        fn package_metadata(
+            account: &T::AccountId,
+            info: PackageInfo<T::AccountId>,
+            modules: &[Vec<u8>],
+        ) -> Result<PackageMetadata<T::AccountId>, Error<T>> {
+            ensure!(
+                !info.name.is_empty() && info.encoded_size() <= MAX_PACKAGE_METADATA_SIZE,
+                Error::<T>::InvalidPackageMetadata
+            );
+            for dependency in &info.dependencies {
+                ensure!(
+                    Packages::<T>::contains_key(&dependency.account, &dependency.name),
+                    Error::<T>::UnknownPackageDependency
+                );
+            }
+
+            let names = modules
+                .iter()
+                .map(|module_bc| {
+                    let module = CompiledModule::deserialize(module_bc)
+                        .map_err(|_| Error::<T>::TransactionValidationError)?;
+                    Ok(module.self_id().name().as_bytes().to_vec())
+                })
+                .collect::<Result<Vec<_>, Error<T>>>()?;
+
+            let upgrade_number = match Packages::<T>::get(account, &info.name) {
+                Some(published) => {
+                    ensure!(
+                        published.modules.iter().all(|name| names.contains(name)),
+                        Error::<T>::PackageModulesRemoved
+                    );
+                    published.upgrade_number.saturating_add(1)
+                }
+                None => 0,
             };
 
-            let vm_result = vm.publish_module_package(gas, package, false);
+            Ok(PackageMetadata {
+                name: info.name,
+                version: info.version,
+                upgrade_number,
+                dependencies: info.dependencies,
+                source_digest: info.source_digest,
+                modules: names,
+            })
+        }
 
-            // produce result with spended gas:
-            let result = result::from_vm_result::<T>(vm_result)?;
+        /// Returns metadata of the package published by the account.
+        pub fn get_package(
+            account: &T::AccountId,
+            name: &[u8],
+        ) -> Option<PackageMetadata<T::AccountId>> {
+            Packages::<T>::get(account, name)
+        }
 
-            // Emit an event:
-            Self::deposit_event(Event::PackagePublished(signer));
+        /// Returns allowlisted scripts hashes with metadata.
+        pub fn get_allowed_scripts() -> Vec<(ScriptHash, ScriptMetadata<T::AccountId>)> {
+            AllowedScripts::<T>::iter().collect()
+        }
 
-            Ok(result)
+        /// Ensures origin is root or signed and returns account id with associated move-address.
+        /// Returns error if si not signed or root/sudo.
+        pub fn ensure_and_convert(
+            origin: OriginFor<T>,
+        ) -> Result<(AccountAddress, T::AccountId), Error<T>> {
+            // Allows to update Standard Library if root.
+            match T::UpdateOrigin::ensure_origin(origin.clone()) {
+                Ok(_) => {
+                    let signer = addr::address_to_account(&CORE_CODE_ADDRESS)
+                        .map_err(|_| Error::<T>::AccountAddressConversionError)?;
+                    Ok((CORE_CODE_ADDRESS, signer))
+                }
+                Err(_) => {
+                    let signer =
+                        ensure_signed(origin).map_err(|_| Error::<T>::InvalidSignature)?;
+                    Self::ensure_not_migrated(&signer)?;
+                    Self::record_move_account(&signer);
+                    Ok((addr::account_to_account_address(&signer), signer))
+                }
+            }
         }
-    }
 
-    /// Genesis configuration.
-    ///
-    /// Allows to configure Move VM in the genesis block.
-    /// Accepts Standard Library modules list and initialize state by calling initialize function with arguments.
-    #[pallet::genesis_config]
-    pub struct GenesisConfig<T: Config> {
-        pub _phantom: std::marker::PhantomData<T>,
-        /// Move Standard library bytes.
-        pub move_stdlib: Vec<u8>,
-        /// Pontem Framework library bytes.
-        pub pont_framework: Vec<u8>,
-        /// Module name for genesis init.
-        pub init_module: Vec<u8>,
-        // Init function name.
-        pub init_func: Vec<u8>,
-        // Init function arguments.
-        pub init_args: Vec<Vec<u8>>,
-    }
+        /// Ensures the account isn't migrated: migrated account is retired, its key could be
+        /// compromised.
+        fn ensure_not_migrated(who: &T::AccountId) -> Result<(), Error<T>> {
+            ensure!(
+                !MigratedAccounts::<T>::contains_key(who),
+                Error::<T>::AccountAlreadyMigrated
+            );
+            Ok(())
+        }
 
-    /// Default genesis configuration.
-    #[cfg(feature = "std")]
-    impl<T: Config> Default for GenesisConfig<T> {
-        fn default() -> Self {
-            GenesisConfig {
-                _phantom: Default::default(),
-                move_stdlib: vec![],
-                pont_framework: vec![],
-                init_module: vec![],
-                init_func: vec![],
-                init_args: vec![],
+        /// Ensures no modules are published under the account address, see `PublishedModules`.
+        fn ensure_no_modules(who: &T::AccountId) -> Result<(), Error<T>> {
+            ensure!(
+                PublishedModules::<T>::get(addr::account_to_bytes(who)) == 0,
+                Error::<T>::MigrationOfPublisher
+            );
+            Ok(())
+        }
+
+        /// Ensures account can publish modules if open publishing is disabled.
+        /// Publishing under `0x1` (root) is not restricted.
+        fn ensure_publisher_allowed(
+            sender: &AccountAddress,
+            signer: &T::AccountId,
+        ) -> Result<(), Error<T>> {
+            if *sender == CORE_CODE_ADDRESS || Self::open_publishing() {
+                return Ok(());
             }
+
+            ensure!(
+                AllowedPublishers::<T>::contains_key(signer),
+                Error::<T>::PublisherNotAllowed
+            );
+            Ok(())
         }
-    }
 
-    /// Initialize Move VM during genesis block.
-    #[pallet::genesis_build]
-    impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
-        fn build(&self) {
-            let mut stdlib_package = ModulePackage::try_from(&self.move_stdlib[..])
-                .expect("Failed to parse move stdlib");
+        /// Reserves deposits for the published modules and tracks their dependencies.
+        /// Deposit of the republished module is released first, so only the size difference is reserved.
+        /// Modules published under `0x1` (root) have no deposit.
+        fn update_module_deposits(
+            sender: &AccountAddress,
+            signer: &T::AccountId,
+            modules: &[Vec<u8>],
+        ) -> Result<(), Error<T>> {
+            if *sender == CORE_CODE_ADDRESS {
+                return Ok(());
+            }
 
-            let pont_framework_package = ModulePackage::try_from(&self.pont_framework[..])
-                .expect("Failed to parse pont framework lib");
+            for module_bc in modules {
+                let module = CompiledModule::deserialize(module_bc)
+                    .map_err(|_| Error::<T>::TransactionValidationError)?;
+                let key = module.self_id().access_vector();
 
-            stdlib_package.join(pont_framework_package);
+                if let Some(deposit) = ModuleDeposits::<T>::get(&key) {
+                    Self::release_module_deposit(&key, deposit);
+                }
 
-            let genesis_config = move_vm::genesis::build_genesis_config(
-                stdlib_package.into_tx(CORE_CODE_ADDRESS),
-                Some(move_vm::genesis::InitFuncConfig {
-                    module: self.init_module.clone(),
-                    func: self.init_func.clone(),
-                    args: self.init_args.clone(),
+                let amount = T::ModuleDepositPerByte::get()
+                    .saturating_mul((module_bc.len() as u32).into());
+                T::Currency::reserve(signer, amount)
+                    .map_err(|_| Error::<T>::InsufficientModuleDeposit)?;
+
+                let dependencies: Vec<_> = module
+                    .immediate_dependencies()
+                    .iter()
+                    .filter(|id| *id.address() != CORE_CODE_ADDRESS)
+                    .map(ModuleId::access_vector)
+                    .collect();
+                for dependency in &dependencies {
+                    ModuleDependents::<T>::mutate(dependency, |count| {
+                        *count = count.saturating_add(1)
+                    });
+                }
+
+                ModuleDeposits::<T>::insert(
+                    &key,
+                    ModuleDeposit {
+                        depositor: signer.clone(),
+                        amount,
+                        dependencies,
+                    },
+                );
+            }
+
+            Ok(())
+        }
+
+        /// Releases module deposit and stops tracking its dependencies.
+        fn release_module_deposit(
+            key: &[u8],
+            deposit: ModuleDeposit<T::AccountId, BalanceOf<T>>,
+        ) {
+            T::Currency::unreserve(&deposit.depositor, deposit.amount);
+            for dependency in &deposit.dependencies {
+                ModuleDependents::<T>::mutate_exists(dependency, |count| {
+                    *count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0)
+                });
+            }
+            ModuleDeposits::<T>::remove(key);
+        }
+
+        /// Replaces source maps of the published modules: stale source maps of the republished
+        /// modules are removed, `source_map` of the single module is stored with the deposit.
+        /// Source maps of modules published under `0x1` (root) have no deposit.
+        fn update_source_maps(
+            sender: &AccountAddress,
+            signer: &T::AccountId,
+            modules: &[Vec<u8>],
+            source_map: Option<Vec<u8>>,
+        ) -> Result<(), Error<T>> {
+            let mut source_map = source_map;
+            for module_bc in modules {
+                let module = CompiledModule::deserialize(module_bc)
+                    .map_err(|_| Error::<T>::TransactionValidationError)?;
+                let key = module.self_id().access_vector();
+                Self::remove_source_map(&key);
+
+                let source_map = match source_map.take() {
+                    Some(source_map) => source_map,
+                    None => continue,
+                };
+                let deposit = if *sender == CORE_CODE_ADDRESS {
+                    Zero::zero()
+                } else {
+                    let amount = T::ModuleDepositPerByte::get()
+                        .saturating_mul((source_map.len() as u32).into());
+                    T::Currency::reserve(signer, amount)
+                        .map_err(|_| Error::<T>::InsufficientModuleDeposit)?;
+                    amount
+                };
+                SourceMaps::<T>::insert(
+                    &key,
+                    SourceMapEntry {
+                        source_map,
+                        depositor: signer.clone(),
+                        deposit,
+                    },
+                );
+                Self::deposit_event(Event::SourceMapStored(
+                    signer.clone(),
+                    module.self_id().name().as_bytes().to_vec(),
+                ));
+            }
+            Ok(())
+        }
+
+        /// Removes source map of the module and releases its deposit.
+        fn remove_source_map(key: &[u8]) {
+            if let Some(entry) = SourceMaps::<T>::take(key) {
+                T::Currency::unreserve(&entry.depositor, entry.deposit);
+            }
+        }
+
+        /// Move VM storage adapter tracking size of the resources written by the VM
+        /// and storage accesses charged with `StorageGas`.
+        fn tracked_move_vm_storage() -> StorageAdapter {
+            StorageAdapter::new(
+                Box::new(|key: &[u8]| {
+                    Self::record_storage_access(key);
+                    if storage::is_code_key(key) {
+                        Self::record_module_load(key);
+                    }
+                    Self::vm_storage_get(key)
                 }),
-            );
+                Box::new(|key: &[u8], value: &[u8]| {
+                    Self::record_storage_access(key);
+                    Self::vm_storage_insert(key, value)
+                }),
+                Box::new(|key: &[u8]| {
+                    Self::record_storage_access(key);
+                    Self::vm_storage_remove(key)
+                }),
+            )
+        }
 
-            move_vm::genesis::init_storage(Pallet::<T>::move_vm_storage(), genesis_config)
-                .expect("Unable to initialize storage");
+        /// Records access to the storage item holding the entry (group item for grouped
+        /// resources) for the VM call run by `charge_storage_accesses`.
+        fn record_storage_access(key: &[u8]) {
+            let item = storage::group_member_key::<T::ResourceGroups>(key)
+                .map(|(group_key, _)| group_key)
+                .unwrap_or_else(|| key.to_vec());
+            pending_storage_accesses::with(|pending| {
+                let accesses = pending.entry(item).or_default();
+                *accesses = accesses.saturating_add(1);
+            });
         }
-    }
 
-    /// Clearing Move VM cache once block processed.
-    #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T>
-    // TODO: make it configurable:  where <T as Config>::ClearMvmCachePolicy = ...
-    {
-        fn on_finalize(_: BlockNumberFor<T>) {
-            if Self::is_move_vm_used() {
-                if let Some(vm) = Self::get_move_vm_cell().get() {
-                    vm.clear();
-                    Self::set_move_vm_clean();
-                    trace!("VM cache cleared on finalize block");
+        /// Move VM storage adapter packing resource groups without tracking, used in genesis.
+        fn untracked_move_vm_storage() -> StorageAdapter {
+            StorageAdapter::new(
+                Box::new(|key: &[u8]| Self::vm_storage_get(key)),
+                Box::new(|key: &[u8], value: &[u8]| {
+                    Self::change_vm_storage(key, Some(value), Self::write_untracked)
+                }),
+                Box::new(|key: &[u8]| Self::change_vm_storage(key, None, Self::write_untracked)),
+            )
+        }
+
+        /// Reads VM storage entry, resources of group members are read from their group item.
+        fn vm_storage_get(key: &[u8]) -> Option<Vec<u8>> {
+            match storage::group_member_key::<T::ResourceGroups>(key) {
+                Some((group_key, member)) => {
+                    storage::decode_group(VMStorage::<T>::get(&group_key)).remove(&member)
                 }
+                None => VMStorage::<T>::get(key),
             }
-            // Otherwise we are not requesting VM.
         }
-    }
 
-    /// Get VM methods unification.
-    impl<T: Config> Pallet<T> {
-        fn get_vm() -> Result<&'static VmWrapperTy, Error<T>> {
-            let vm = Self::try_get_or_create_move_vm()?;
-            Ok(vm)
+        /// Writes VM storage entry and tracks the change of the resource owner storage usage.
+        fn vm_storage_insert(key: &[u8], value: &[u8]) {
+            Self::change_vm_storage(key, Some(value), Self::write_tracked);
+        }
+
+        /// Removes VM storage entry and tracks the change of the resource owner storage usage.
+        fn vm_storage_remove(key: &[u8]) {
+            Self::change_vm_storage(key, None, Self::write_tracked);
+        }
+
+        /// Resources stored under the signers while their script runs: public keys signed the
+        /// transaction (see `auth`), lockups (see `vesting`) and stakes (see `staking`), as
+        /// storage keys and values.
+        fn signer_resources(signers: &[T::AccountId]) -> Vec<(Vec<u8>, Vec<u8>)> {
+            let mut resources = Vec::new();
+            for who in signers {
+                let address = addr::account_to_account_address(who);
+                if let Some(key) = T::SenderKeys::sender_key(who) {
+                    resources.push((auth::sender_key_key(&address), key.to_resource()));
+                }
+                if let Some(lockup) = T::Lockups::lockup(who) {
+                    resources.push((vesting::lockup_key(&address), lockup.to_resource()));
+                }
+                if let Some(stake) = T::Staking::stake(who) {
+                    resources.push((staking::stake_key(&address), stake.to_resource()));
+                }
+            }
+            resources
+        }
+
+        /// Runs the script call with the signer resources stored, they are removed after the
+        /// call, whatever its result.
+        fn with_signer_resources<R>(
+            resources: &[(Vec<u8>, Vec<u8>)],
+            call: impl FnOnce() -> R,
+        ) -> R {
+            for (key, value) in resources {
+                Self::write_pallet_resource(key, value);
+            }
+            let result = call();
+            for (key, _) in resources {
+                Self::change_vm_storage(key, None, Self::write_untracked);
+            }
+            result
+        }
+
+        /// Stores resource managed by the pallet (e.g. block metadata) without tracking.
+        fn write_pallet_resource(key: &[u8], value: &[u8]) {
+            Self::change_vm_storage(key, Some(value), Self::write_untracked);
+        }
+
+        /// Changes VM storage entry with `write` (`None` removes it), resources of group members
+        /// are changed within their group item, removed with the last member.
+        fn change_vm_storage(key: &[u8], value: Option<&[u8]>, write: fn(&[u8], Option<&[u8]>)) {
+            let (group_key, member) = match storage::group_member_key::<T::ResourceGroups>(key) {
+                Some(group) => group,
+                None => return write(key, value),
+            };
+
+            let mut group = storage::decode_group(VMStorage::<T>::get(&group_key));
+            match value {
+                Some(value) => group.insert(member, value.to_vec()),
+                None => group.remove(&member),
+            };
+            if group.is_empty() {
+                write(&group_key, None);
+            } else {
+                write(&group_key, Some(&group.encode()));
+            }
+        }
+
+        /// Writes VM storage item, tracks the change of the owner storage usage and indexes it.
+        fn write_tracked(key: &[u8], value: Option<&[u8]>) {
+            Self::track_storage_usage(key, value.map_or(0, |value| key.len() + value.len()));
+            Self::index_storage_change(key, value);
+            Self::write_untracked(key, value);
+        }
+
+        /// Writes VM storage item as is, counts published and removed modules.
+        fn write_untracked(key: &[u8], value: Option<&[u8]>) {
+            if storage::is_code_key(key) && VMStorage::<T>::contains_key(key) != value.is_some() {
+                let mut publisher = [0; 32];
+                publisher.copy_from_slice(&key[1..=AccountAddress::LENGTH]);
+                PublishedModules::<T>::mutate_exists(publisher, |count| {
+                    let modules = count.unwrap_or_default();
+                    *count = match value {
+                        Some(_) => Some(modules.saturating_add(1)),
+                        None => Some(modules.saturating_sub(1)).filter(|c| *c > 0),
+                    };
+                });
+            }
+            match value {
+                Some(value) => VMStorage::<T>::insert(key, value),
+                None => VMStorage::<T>::remove(key),
+            }
+        }
+
+        /// Writes VM storage change into offchain index (no-op if offchain indexing disabled).
+        fn index_storage_change(key: &[u8], value: Option<&[u8]>) {
+            let index = MoveWriteSetCount::<T>::mutate(|count| {
+                let index = *count;
+                *count = count.saturating_add(1);
+                index
+            });
+            let block = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+            let change = writeset::WriteSetChange {
+                key: key.to_vec(),
+                value: value.map(<[u8]>::to_vec),
+            };
+            sp_io::offchain_index::set(
+                &writeset::offchain::change_key(block, index),
+                &change.encode(),
+            );
         }
-    }
 
-    /// Move VM allows us to configure Gas Price, but we use constant for gas price, as we follow general Substrate approach with weight and tips.
-    const GAS_UNIT_PRICE: u64 = 1;
+        /// Collects write-sets of the blocks before `now` into the offchain storage,
+        /// keyed by block hash, at most `MAX_WRITE_SETS_PER_RUN` blocks per run.
+        fn collect_write_sets(now: u64) {
+            use sp_core::offchain::StorageKind;
+            use sp_io::offchain::{local_storage_clear, local_storage_get, local_storage_set};
+            use writeset::offchain::{
+                LAST_COLLECTED_KEY, block_write_set_key, change_key, changes_count_key,
+            };
+            const MAX_WRITE_SETS_PER_RUN: u64 = 16;
 
-    impl<T: Config> Pallet<T> {
-        #![allow(clippy::useless_conversion)]
-        /// Returns gas limit object requires for execute/publish functions.
-        fn get_move_gas_limit(gas_limit: u64) -> Result<Gas, Error<T>> {
-            Gas::new(gas_limit, GAS_UNIT_PRICE).map_err(|_| Error::InvalidGasAmountMaxValue)
+            let get = |key: &[u8]| local_storage_get(StorageKind::PERSISTENT, key);
+            let last = get(LAST_COLLECTED_KEY)
+                .and_then(|last| u64::decode(&mut &last[..]).ok())
+                .unwrap_or(0);
+            let from = last.max(now.saturating_sub(MAX_WRITE_SETS_PER_RUN)) + 1;
+
+            for block in from..now {
+                let number: T::BlockNumber = block.unique_saturated_into();
+                let hash = frame_system::Pallet::<T>::block_hash(number);
+                let write_set = writeset::collect_block_write_set(block, get);
+                local_storage_set(
+                    StorageKind::PERSISTENT,
+                    &block_write_set_key(hash.as_ref()),
+                    &write_set.encode(),
+                );
+                // Events are pruned by the node events archive.
+                for index in 0..writeset::changes_count(block, get) {
+                    local_storage_clear(StorageKind::PERSISTENT, &change_key(block, index));
+                }
+                local_storage_clear(StorageKind::PERSISTENT, &changes_count_key(block));
+                local_storage_set(StorageKind::PERSISTENT, LAST_COLLECTED_KEY, &block.encode());
+            }
         }
 
-        /// Get pallet account id.
-        pub fn get_account_id() -> T::AccountId {
-            T::PalletId::get().into_account()
+        /// Records the first failed request of Move code for the VM session of
+        /// `with_execution_result`, failures outside of it are only logged.
+        fn record_move_request(request: &str, result: DispatchResult) {
+            if let Err(err) = result {
+                error!("Can't {} requested by Move event: {:?}", request, err);
+                failed_move_request::with(|failed| {
+                    failed.get_or_insert(err);
+                });
+            }
         }
 
-        /// Execute Move VM script with provided signers, script byte code, gas limit, and dry run configuration.
-        /// In case of dry run nothing would be written to storage after execution (required mostly by RPC calls, e.g. estimate gas etc).
-        /// Multiple signers supported by utilizing multisig pallet.
-        pub fn raw_execute_script(
-            signers: &[T::AccountId],
-            tx_bc: Vec<u8>,
-            gas_limit: u64,
-            root_signed: bool,
-            dry_run: bool,
-        ) -> Result<VmResult, Error<T>>
-        where
-            <T as timestamp::Config>::Moment: UniqueSaturatedInto<u64>,
-            // T::BlockNumber: BaseArithmetic,
-            // T::BlockNumber: UniqueSaturatedInto<u64>,
-            T::BlockNumber: TryInto<u64>,
-        {
-            // TODO: some minimum gas for processing transaction from bytes?
-            let transaction = Transaction::try_from(&tx_bc[..])
-                .map_err(|_| Error::<T>::TransactionValidationError)?;
+        /// Fails the finished VM session if a request of Move code failed.
+        fn settle_move_requests() -> DispatchResult {
+            match failed_move_request::with(Option::take).flatten() {
+                Some(err) => Err(err),
+                None => Ok(()),
+            }
+        }
 
-            // TODO: think about in future change fn-parameters
-            //       from `signers` & `root_signed` to just `origin: OriginFor`
-            //       and so ensure root here instead of trust `root_signed`.
-            ensure!(
-                root_signed == transaction.has_root_signer(),
-                Error::<T>::TransactionIsNotAllowedError
-            );
+        /// Records change of the resource size in `PendingStorageDeltas`.
+        /// Modules (covered by module deposits) and resources under `0x1` are not tracked.
+        fn track_storage_usage(key: &[u8], new_size: usize) {
+            let owner = match storage::resource_owner(key) {
+                Some(owner) if owner != CORE_CODE_ADDRESS => owner,
+                _ => return,
+            };
+            let account = match addr::address_to_account::<T::AccountId>(&owner) {
+                Ok(account) => account,
+                Err(_) => return,
+            };
 
-            let vm = Self::get_vm()?;
-            let gas = Self::get_move_gas_limit(gas_limit)?;
+            let old_size = VMStorage::<T>::decode_len(key)
+                .map(|len| len + key.len())
+                .unwrap_or(0);
+            if old_size != new_size {
+                PendingStorageDeltas::<T>::mutate(account, |delta| {
+                    *delta = delta.saturating_add(new_size as i64 - old_size as i64)
+                });
+            }
+        }
 
-            let tx = {
-                let signers = if transaction.signers_count() == 0 {
-                    &[]
+        /// Reserves or releases resource deposits according to `PendingStorageDeltas`.
+        fn settle_storage_deposits() -> Result<(), Error<T>> {
+            for (account, delta) in PendingStorageDeltas::<T>::drain() {
+                let deposit = StorageDeposits::<T>::get(&account).unwrap_or_default();
+                let bytes = if delta >= 0 {
+                    deposit.bytes.saturating_add(delta as u64)
                 } else {
-                    signers
+                    deposit.bytes.saturating_sub(delta.unsigned_abs())
                 };
+                let amount = T::ResourceDepositPerByte::get()
+                    .saturating_mul(bytes.unique_saturated_into());
 
-                // Is it really necessary? VM will throw an error if it caused.
-                if transaction.signers_count() as usize != signers.len() {
-                    error!(
-                        "Transaction signers num isn't eq signers: {} != {}",
-                        transaction.signers_count(),
-                        signers.len()
-                    );
-                    return Err(Error::<T>::TransactionSignersNumError.into());
+                if amount > deposit.amount {
+                    T::Currency::reserve(&account, amount - deposit.amount)
+                        .map_err(|_| Error::<T>::InsufficientStorageDeposit)?;
+                } else {
+                    T::Currency::unreserve(&account, deposit.amount - amount);
                 }
 
-                let signers = signers
-                    .iter()
-                    .map(addr::account_to_bytes)
-                    .map(AccountAddress::new)
-                    .collect();
+                if bytes == 0 {
+                    StorageDeposits::<T>::remove(&account);
+                } else {
+                    StorageDeposits::<T>::insert(&account, StorageDeposit { bytes, amount });
+                }
+                if amount != deposit.amount {
+                    Self::deposit_event(Event::StorageDepositUpdated(account, bytes, amount));
+                }
+            }
 
-                transaction
-                    .into_script(signers)
-                    .map_err(|_| Error::<T>::TransactionValidationError)?
-            };
+            Ok(())
+        }
 
-            let ctx = {
-                let height = frame_system::Pallet::<T>::block_number()
-                    .try_into()
-                    .map_err(|_| Error::<T>::NumConversionError)?
-                    as u64;
+        /// Clears Move VM cache, e.g. once module is removed from storage.
+        fn clear_vm_cache() {
+            if let Some(vm) = Self::get_move_vm_cell().get() {
+                vm.clear();
+                LoaderStats::<T>::mutate(|stats| stats.flushes = stats.flushes.saturating_add(1));
+            }
+        }
 
-                // Because if we call now().as_millis() during genesis it returns error.
-                // And stdlib initializing during genesis.
-                let time = match height {
-                    0 => 0,
-                    _ => <timestamp::Pallet<T> as UnixTime>::now().as_millis() as u64,
-                };
+        /// Amount of `modules` already published, so the cached versions are outdated.
+        fn republished_modules(modules: &[&[u8]]) -> u32 {
+            modules
+                .iter()
+                .filter_map(|module_bc| CompiledModule::deserialize(module_bc).ok())
+                .filter(|module| VMStorage::<T>::contains_key(module.self_id().access_vector()))
+                .count() as u32
+        }
 
-                ExecutionContext::new(time, height)
-            };
+        /// Flushes Move VM cache once modules are republished, so the loader doesn't serve the
+        /// previous versions of the modules or of their dependents.
+        fn flush_republished(account: &T::AccountId, republished: u32) {
+            if republished > 0 {
+                Self::clear_vm_cache();
+                Self::deposit_event(Event::LoaderCacheFlushed(account.clone(), republished));
+            }
+        }
 
-            let res = vm.execute_script(gas, ctx, tx, dry_run);
-            debug!("execution result: {:?}", res);
+        /// Records the module loaded from the storage by Move VM loader, which verifies it.
+        fn record_module_load(key: &[u8]) {
+            LoaderStats::<T>::mutate(|stats| {
+                stats.misses = stats.misses.saturating_add(1);
+                stats.verifier_runs = stats.verifier_runs.saturating_add(1);
+            });
+            called_module::with(|(module_key, loaded)| {
+                if module_key.as_slice() == key {
+                    *loaded = true;
+                }
+            });
+        }
 
-            Ok(res)
+        /// Records modules verified on publishing.
+        fn record_verifier_runs(modules: usize) {
+            LoaderStats::<T>::mutate(|stats| {
+                stats.verifier_runs = stats.verifier_runs.saturating_add(modules as u32)
+            });
         }
 
-        /// Ensures origin is root or signed and returns account id with associated move-address.
-        /// Returns error if si not signed or root/sudo.
-        pub fn ensure_and_convert(
-            origin: OriginFor<T>,
-        ) -> Result<(AccountAddress, T::AccountId), Error<T>> {
-            // Allows to update Standard Library if root.
-            match T::UpdateOrigin::ensure_origin(origin.clone()) {
-                Ok(_) => {
-                    let signer = addr::address_to_account(&CORE_CODE_ADDRESS)
-                        .map_err(|_| Error::<T>::AccountAddressConversionError)?;
-                    Ok((CORE_CODE_ADDRESS, signer))
-                }
-                Err(_) => {
-                    let signer =
-                        ensure_signed(origin).map_err(|_| Error::<T>::InvalidSignature)?;
-                    Ok((addr::account_to_account_address(&signer), signer))
-                }
+        /// Storage key of the module of the called entry function, `None` for scripts.
+        fn called_module_key(tx: &ScriptTx) -> Option<Vec<u8>> {
+            match tx.call() {
+                Call::ScriptFunction {
+                    mod_address,
+                    mod_name,
+                    ..
+                } => Some(ModuleId::new(*mod_address, mod_name.clone()).access_vector()),
+                Call::Script { .. } => None,
+            }
+        }
+
+        /// Runs the VM call and records the cache hit if Move VM loader hasn't read the module
+        /// of the called entry function (`module_key`) from the storage, so it was cached.
+        /// Other modules are loaded by Move VM without the storage access, so only the called
+        /// module can be checked.
+        fn record_cache_hit(
+            module_key: Option<Vec<u8>>,
+            call: impl FnOnce() -> VmResult,
+        ) -> VmResult {
+            let mut called = match module_key {
+                Some(key) => (key, false),
+                None => return call(),
+            };
+            let res = called_module::using(&mut called, call);
+            if !called.1 {
+                LoaderStats::<T>::mutate(|stats| stats.hits = stats.hits.saturating_add(1));
             }
+            res
         }
 
         /// Publish Move module script with provided account, module bytecode, gas limit, and dry run configuration.
@@ -488,8 +3638,23 @@ pub mod pallet {
             gas_limit: u64,
             dry_run: bool,
         ) -> Result<VmResult, Error<T>> {
+            Self::ensure_module_size(&module_bc)?;
+            Self::publish_module_bytecode(account, module_bc, gas_limit, dry_run)
+        }
+
+        /// Publishes module bytecode of any size, bounded by the caller.
+        fn publish_module_bytecode(
+            account: &T::AccountId,
+            module_bc: Vec<u8>,
+            gas_limit: u64,
+            dry_run: bool,
+        ) -> Result<VmResult, Error<T>> {
+            Self::ensure_not_migrated(account)?;
             let vm = Self::get_vm()?;
             let gas = Self::get_move_gas_limit(gas_limit)?;
+            if !dry_run {
+                Self::record_verifier_runs(1);
+            }
 
             let tx = {
                 let sender = addr::account_to_bytes(account);
@@ -497,12 +3662,44 @@ pub mod pallet {
                 ModuleTx::new(module_bc, AccountAddress::new(sender))
             };
 
-            let res = vm.publish_module(gas, tx, dry_run);
+            let res = Self::meter_block_gas(gas_limit, dry_run, || {
+                vm.publish_module(gas, tx, dry_run)
+            })?;
             debug!("publication result: {:?}", res);
 
             Ok(res)
         }
 
+        /// Publish package of Move modules with provided account, gas limit and dry run configuration.
+        /// Modules are published atomically and verified together, as by `publish_package`.
+        pub fn raw_publish_package(
+            account: &T::AccountId,
+            modules: Vec<Vec<u8>>,
+            gas_limit: u64,
+            dry_run: bool,
+        ) -> Result<VmResult, Error<T>> {
+            for module in &modules {
+                Self::ensure_module_size(module)?;
+            }
+            Self::ensure_not_migrated(account)?;
+
+            let vm = Self::get_vm()?;
+            let gas = Self::get_move_gas_limit(gas_limit)?;
+
+            let sender = AccountAddress::new(addr::account_to_bytes(account));
+            if !dry_run {
+                Self::record_verifier_runs(modules.len());
+            }
+            let package = PackageTx::new(modules, sender);
+
+            let res = Self::meter_block_gas(gas_limit, dry_run, || {
+                vm.publish_module_package(gas, package, dry_run)
+            })?;
+            debug!("package publication result: {:?}", res);
+
+            Ok(res)
+        }
+
         pub fn get_module_abi(module_id: &[u8]) -> Result<Option<Vec<u8>>, Vec<u8>> {
             let vm = Self::get_vm()
                 .map_err::<Vec<u8>, _>(|e| format!("error while getting vm {:?}", e).into())?;
@@ -517,6 +3714,25 @@ pub mod pallet {
                 .map_err(|e| format!("error in get_module: {:?}", e).into())
         }
 
+        /// Returns the account of the Move address, if the account has made a Move call.
+        pub fn resolve_move_address(address: [u8; 32]) -> Option<T::AccountId> {
+            MoveAccounts::<T>::get(address)
+        }
+
+        /// Records the account by its Move address once, see `MoveAccounts`.
+        fn record_move_account(account: &T::AccountId) {
+            let address = addr::account_to_bytes(account);
+            if !MoveAccounts::<T>::contains_key(address) {
+                MoveAccounts::<T>::insert(address, account);
+            }
+        }
+
+        /// Returns source map of the module by BCS encoded module id.
+        pub fn get_source_map(module_id: &[u8]) -> Option<Vec<u8>> {
+            let module_id: ModuleId = bcs::from_bytes(module_id).ok()?;
+            SourceMaps::<T>::get(module_id.access_vector()).map(|entry| entry.source_map)
+        }
+
         pub fn get_resource(
             account: &T::AccountId,
             tag: &[u8],
@@ -526,6 +3742,25 @@ pub mod pallet {
             vm.get_resource(&AccountAddress::new(addr::account_to_bytes(account)), tag)
                 .map_err(|e| format!("error in get_resource: {:?}", e).into())
         }
+
+        /// Overrides resources of the accounts: `(account, tag, value)`, where `tag` is BCS
+        /// encoded `StructTag`, `None` value removes the resource.
+        ///
+        /// Used by the runtime API to estimate transactions in the state with the overrides
+        /// (e.g. a granted approval) within the state overlay of the call: nothing is committed.
+        /// Values aren't checked against the struct layout, malformed ones fail the execution.
+        pub fn override_resources(
+            overrides: Vec<(T::AccountId, Vec<u8>, Option<Vec<u8>>)>,
+        ) -> Result<(), Vec<u8>> {
+            for (account, tag, value) in overrides {
+                bcs::from_bytes::<StructTag>(&tag)
+                    .map_err::<Vec<u8>, _>(|e| format!("invalid resource tag: {:?}", e).into())?;
+                let address = addr::account_to_account_address(&account);
+                let key = storage::resource_key(&address, &tag);
+                Self::change_vm_storage(&key, value.as_deref(), Self::write_untracked);
+            }
+            Ok(())
+        }
     }
 
     /// Get storage adapter ready for the VM.
@@ -541,9 +3776,383 @@ pub mod pallet {
         fn deposit_move_event(e: MoveEventArguments) {
             debug!("MoveVM Event: {:?} {:?} {:?}", e.guid, e.ty_tag, e.message);
 
-            // Emit an event:
+            // Write event into offchain index (no-op if offchain indexing disabled on the node):
+            let index = MoveEventsCount::<T>::mutate(|count| {
+                let index = *count;
+                *count = count.saturating_add(1);
+                index
+            });
+            let block = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+            sp_io::offchain_index::set(
+                &offchain::event_key(block, index),
+                &MoveEventRecord::from(&e).encode(),
+            );
+
+            // Schedule follow-up script requested by Move code:
+            if let Some(request) = ScheduleRequest::from_event(&e) {
+                let result = Self::schedule_requested_script(request);
+                Self::record_move_request("schedule script", result);
+            }
+
+            // Send cross-chain transfer requested by Move code:
+            if let Some(request) = TransferRequest::from_event(&e) {
+                let result = Self::send_requested_transfer(request);
+                Self::record_move_request("send transfer", result);
+            }
+
+            // Unlock vested coins requested by Move code:
+            if let Some(request) = VestRequest::from_event(&e) {
+                let result = Self::vest_requested(request);
+                Self::record_move_request("vest coins", result);
+            }
+
+            // Submit governance proposal requested by Move code:
+            if let Some(request) = ProposalRequest::from_event(&e) {
+                let result = Self::submit_requested_proposal(request);
+                Self::record_move_request("submit proposal", result);
+            }
+
+            // Spend treasury as requested by Move code:
+            if let Some(request) = SpendRequest::from_event(&e) {
+                let result = Self::spend_requested(request);
+                Self::record_move_request("spend treasury", result);
+            }
+
+            // Subscribe and unsubscribe callbacks as requested by Move code:
+            if let Some(request) = SubscribeRequest::from_event(&e) {
+                let result = Self::subscribe_requested(request);
+                Self::record_move_request("subscribe callback", result);
+            }
+            if let Some(request) = UnsubscribeRequest::from_event(&e) {
+                let result = addr::address_to_account::<T::AccountId>(&request.address)
+                    .map_err(|_| Error::<T>::AccountAddressConversionError)
+                    .and_then(|who| Self::unsubscribe_callback(who, request.trigger))
+                    .map_err(Into::into);
+                Self::record_move_request("unsubscribe callback", result);
+            }
+
+            // Emit an event indexed by the hash of its type:
+            let topic = e.topic::<T::Hashing>();
             // TODO: dispatch up the error by TryInto. Error is almost impossible but who knows..
-            Self::deposit_event(e.try_into().expect("Cannot back-convert address"));
+            let event: Event<T> = e.try_into().expect("Cannot back-convert address");
+            let event: <T as Config>::Event = event.into();
+            frame_system::Pallet::<T>::deposit_event_indexed(&[topic], event.into());
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Validate script and schedule its execution on behalf of the account, the fee of the
+        /// gas limit is withdrawn from the account.
+        #[transactional]
+        fn schedule_script(
+            who: T::AccountId,
+            when: T::BlockNumber,
+            tx_bc: Vec<u8>,
+            gas_limit: u64,
+        ) -> DispatchResult {
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(when > now, Error::<T>::ScheduleInPast);
+            ensure!(
+                tx_bc.len() <= T::MaxScriptSize::get() as usize,
+                Error::<T>::ScriptTooLarge
+            );
+            ensure!(
+                gas_limit <= T::MaxScheduledGas::get(),
+                Error::<T>::ScheduledGasTooLarge
+            );
+            let transaction = Transaction::try_from(&tx_bc[..])
+                .map_err(|_| Error::<T>::TransactionValidationError)?;
+            ensure!(
+                !transaction.has_root_signer(),
+                Error::<T>::TransactionIsNotAllowedError
+            );
+            ensure!(
+                transaction.signers_count() == 1,
+                Error::<T>::TransactionSignersNumError
+            );
+
+            let mut pending = ScheduledScripts::<T>::get(&who);
+            pending.retain(|block| *block >= now);
+            ensure!(
+                pending.len() < T::MaxScheduledScripts::get() as usize,
+                Error::<T>::TooManyScheduledScripts
+            );
+            pending.push(when);
+
+            let fee = gas_price::gas_tip::<T>(gas_limit, T::ScheduledGasUnitPrice::get());
+            let imbalance = T::Currency::withdraw(
+                &who,
+                fee,
+                WithdrawReasons::FEE,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            T::OnGasTip::on_unbalanced(imbalance);
+
+            let script_hash = types::script_hash(&tx_bc);
+            T::ScriptScheduler::schedule_script(who.clone(), when, tx_bc, gas_limit)?;
+            ScheduledScripts::<T>::insert(&who, pending);
+            Self::deposit_event(Event::ScriptScheduled(who, when, script_hash));
+
+            Ok(())
+        }
+
+        /// Send cross-chain transfer requested by `0x1::XTransfer::TransferRequest` event.
+        fn send_requested_transfer(request: TransferRequest) -> DispatchResult {
+            let who = addr::address_to_account::<T::AccountId>(&request.address)
+                .map_err(|_| Error::<T>::AccountAddressConversionError)?;
+
+            T::XcmTransfer::transfer(
+                who.clone(),
+                request.ticker.clone(),
+                request.amount,
+                request.dest,
+                request.dest_weight,
+            )?;
+            Self::deposit_event(Event::XcmTransferSent(who, request.ticker, request.amount));
+
+            Ok(())
+        }
+
+        /// Unlock vested coins requested by `0x1::Vesting::VestRequest` event.
+        fn vest_requested(request: VestRequest) -> DispatchResult {
+            let target = addr::address_to_account::<T::AccountId>(&request.target)
+                .map_err(|_| Error::<T>::AccountAddressConversionError)?;
+            T::Lockups::vest(target)
+        }
+
+        /// Submit governance proposal requested by `0x1::Governance::ProposalRequest` event.
+        fn submit_requested_proposal(request: ProposalRequest) -> DispatchResult {
+            let proposer = addr::address_to_account::<T::AccountId>(&request.address)
+                .map_err(|_| Error::<T>::AccountAddressConversionError)?;
+            ensure!(
+                request.callback.len() <= T::MaxScriptSize::get() as usize,
+                Error::<T>::GovernanceCallbackTooLarge
+            );
+
+            let id = NextGovernanceProposalId::<T>::mutate(|next| {
+                let id = *next;
+                *next = next.saturating_add(1);
+                id
+            });
+            T::Governance::propose(
+                proposer.clone(),
+                id,
+                &request.call,
+                T::GasWeightMapping::gas_to_weight(request.callback_gas),
+                request.deposit,
+            )?;
+            GovernanceProposals::<T>::insert(
+                id,
+                GovernanceProposal {
+                    proposer: proposer.clone(),
+                    call: request.call,
+                    callback: request.callback,
+                    callback_gas: request.callback_gas,
+                },
+            );
+            Self::deposit_event(Event::GovernanceProposalSubmitted(proposer, id));
+
+            Ok(())
+        }
+
+        /// Spend treasury as requested by `0x1::Treasury::SpendRequest` event.
+        fn spend_requested(request: SpendRequest) -> DispatchResult {
+            let spender = addr::address_to_account::<T::AccountId>(&request.address)
+                .map_err(|_| Error::<T>::AccountAddressConversionError)?;
+            let beneficiary = addr::address_to_account::<T::AccountId>(&request.beneficiary)
+                .map_err(|_| Error::<T>::AccountAddressConversionError)?;
+            ensure!(
+                TreasurySpenders::<T>::contains_key(&spender),
+                Error::<T>::UnknownTreasurySpender
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let period_start = now - now % T::TreasurySpendPeriod::get().max(One::one());
+            let spent = match TreasurySpent::<T>::get(&spender) {
+                Some((start, spent)) if start == period_start => spent,
+                _ => 0,
+            };
+            let spent = spent.saturating_add(request.amount);
+            ensure!(
+                spent <= T::MaxTreasurySpend::get(),
+                Error::<T>::TreasurySpendTooLarge
+            );
+
+            T::TreasurySpend::spend(spender.clone(), beneficiary.clone(), request.amount)?;
+            TreasurySpent::<T>::insert(&spender, (period_start, spent));
+            Self::deposit_event(Event::TreasurySpendRequested(
+                spender,
+                beneficiary,
+                request.amount,
+            ));
+
+            Ok(())
+        }
+
+        /// Subscribe callback requested by `0x1::Callback::SubscribeRequest` event, replaces
+        /// the callback of the subscriber with the same trigger.
+        fn subscribe_requested(request: SubscribeRequest) -> DispatchResult {
+            let who = addr::address_to_account::<T::AccountId>(&request.address)
+                .map_err(|_| Error::<T>::AccountAddressConversionError)?;
+            ensure!(
+                request.callback.len() <= T::MaxScriptSize::get() as usize,
+                Error::<T>::CallbackTooLarge
+            );
+            ensure!(
+                request.gas_limit <= T::MaxCallbackGas::get(),
+                Error::<T>::CallbackGasTooLarge
+            );
+
+            let deposit = match CallbackSubscriptions::<T>::get(&who, request.trigger) {
+                Some(subscription) => subscription.deposit,
+                None => {
+                    let count = CallbackSubscriptionsCount::<T>::get();
+                    ensure!(
+                        count < T::MaxCallbackSubscriptions::get(),
+                        Error::<T>::TooManyCallbackSubscriptions
+                    );
+                    // Bounded by the amount of triggers.
+                    let account_count = CallbackSubscriptions::<T>::iter_key_prefix(&who).count();
+                    ensure!(
+                        account_count < T::MaxAccountCallbackSubscriptions::get() as usize,
+                        Error::<T>::TooManyAccountCallbackSubscriptions
+                    );
+                    let deposit = T::CallbackDeposit::get();
+                    T::Currency::reserve(&who, deposit)
+                        .map_err(|_| Error::<T>::InsufficientCallbackDeposit)?;
+                    CallbackSubscriptionsCount::<T>::put(count.saturating_add(1));
+                    deposit
+                }
+            };
+
+            let subscription = CallbackSubscription {
+                callback: request.callback,
+                gas_limit: request.gas_limit,
+                deposit,
+                balance: T::Currency::free_balance(&who),
+            };
+            CallbackSubscriptions::<T>::insert(&who, request.trigger, subscription);
+            Self::deposit_event(Event::CallbackSubscribed(who, request.trigger));
+
+            Ok(())
+        }
+
+        /// Removes the callback subscription.
+        fn unsubscribe_callback(
+            who: T::AccountId,
+            trigger: CallbackTrigger,
+        ) -> Result<(), Error<T>> {
+            let subscription = CallbackSubscriptions::<T>::take(&who, trigger)
+                .ok_or(Error::<T>::UnknownCallbackSubscription)?;
+            T::Currency::unreserve(&who, subscription.deposit);
+            CallbackSubscriptionsCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+            Self::deposit_event(Event::CallbackUnsubscribed(who, trigger));
+            Ok(())
+        }
+
+        /// Executes callbacks triggered at the block initialization while their gas limits fit
+        /// the `MaxCallbackGas` budget, returns the weight of the checks and executions.
+        ///
+        /// The fee of the gas limit is withdrawn from the subscriber before the execution,
+        /// callbacks of subscribers unable to pay it are skipped.
+        ///
+        /// Balance of the `Deposit` subscription is updated after its callback, so deposits of
+        /// the skipped callbacks stay pending and the callback doesn't trigger itself.
+        fn execute_callbacks() -> Weight {
+            // Keys are collected first: callbacks subscribe and unsubscribe.
+            let keys: Vec<_> = CallbackSubscriptions::<T>::iter_keys().collect();
+            let mut budget = T::MaxCallbackGas::get();
+            let mut reads = keys.len() as Weight;
+            let mut writes = 0;
+
+            for (who, trigger) in keys {
+                let mut subscription = match CallbackSubscriptions::<T>::get(&who, trigger) {
+                    Some(subscription) => subscription,
+                    None => continue,
+                };
+                reads += 1;
+                if trigger == CallbackTrigger::Deposit {
+                    let balance = T::Currency::free_balance(&who);
+                    reads += 1;
+                    if balance < subscription.balance {
+                        // Spent coins: deposits are counted from the lower balance.
+                        subscription.balance = balance;
+                        Self::update_callback_balance(&who, balance);
+                        writes += 1;
+                    }
+                    if balance <= subscription.balance {
+                        continue;
+                    }
+                }
+                if subscription.gas_limit > budget {
+                    continue;
+                }
+                let fee = gas_price::gas_tip::<T>(
+                    subscription.gas_limit,
+                    T::CallbackGasUnitPrice::get(),
+                );
+                reads += 1;
+                writes += 1;
+                match T::Currency::withdraw(
+                    &who,
+                    fee,
+                    WithdrawReasons::FEE,
+                    ExistenceRequirement::KeepAlive,
+                ) {
+                    Ok(imbalance) => T::OnGasTip::on_unbalanced(imbalance),
+                    Err(err) => {
+                        error!("Can't pay callback fee of {:?}: {:?}", who, err);
+                        continue;
+                    }
+                }
+                budget -= subscription.gas_limit;
+
+                let signers = vec![who.clone()];
+                let result =
+                    Self::execute_signed(&signers, subscription.callback, subscription.gas_limit);
+                if let Err(err) = &result {
+                    error!("Callback of {:?} failed: {:?}", who, err.error);
+                }
+                let succeeded = result.is_ok();
+                Self::deposit_event(Event::CallbackExecuted(who.clone(), trigger, succeeded));
+
+                if trigger == CallbackTrigger::Deposit {
+                    Self::update_callback_balance(&who, T::Currency::free_balance(&who));
+                    reads += 1;
+                    writes += 1;
+                }
+            }
+
+            let gas_used = T::MaxCallbackGas::get() - budget;
+            T::GasWeightMapping::gas_to_weight(gas_used)
+                .saturating_add(T::DbWeight::get().reads_writes(reads, writes))
+        }
+
+        /// Releases the deposit of the closed multisig script and its proposal slot.
+        fn release_multisig_deposit(proposer: &T::AccountId, deposit: BalanceOf<T>) {
+            T::Currency::unreserve(proposer, deposit);
+            MultisigProposals::<T>::mutate_exists(proposer, |proposals| {
+                *proposals = proposals.and_then(|n| n.checked_sub(1)).filter(|n| *n > 0);
+            });
+        }
+
+        /// Updates balance of the `Deposit` subscription, if it's still subscribed.
+        fn update_callback_balance(who: &T::AccountId, balance: BalanceOf<T>) {
+            CallbackSubscriptions::<T>::mutate(who, CallbackTrigger::Deposit, |subscription| {
+                if let Some(subscription) = subscription {
+                    subscription.balance = balance;
+                }
+            });
+        }
+
+        /// Schedule script requested by `0x1::Schedule::ScheduleRequest` event.
+        fn schedule_requested_script(request: ScheduleRequest) -> DispatchResult {
+            let who = addr::address_to_account::<T::AccountId>(&request.address)
+                .map_err(|_| Error::<T>::AccountAddressConversionError)?;
+            let delay: T::BlockNumber = request.delay.max(1).unique_saturated_into();
+            let when = frame_system::Pallet::<T>::block_number().saturating_add(delay);
+
+            Self::schedule_script(who, when, request.script, request.gas_limit)
         }
     }
 
@@ -560,7 +4169,7 @@ pub mod pallet {
         fn try_create_move_vm() -> Result<Self::Vm, Self::Error> {
             trace!("MoveVM created");
             Mvm::new(
-                Self::move_vm_storage().into(),
+                Self::tracked_move_vm_storage(),
                 Self::create_move_event_handler(),
                 balance::BalancesAdapter::<
                     <T as frame_system::Config>::AccountId,
@@ -1009,6 +4618,144 @@ pub mod pallet {
         VecBorrowElementExistsMutableBorrowError,
         // Found duplicate of native function
         DuplicateNativeFunction,
+
+        /// Script is already in the allowlist.
+        ScriptAlreadyAllowed,
+        /// Account can't be migrated to itself or to already migrated account.
+        InvalidMigrationTarget,
+        /// Account is already migrated.
+        AccountAlreadyMigrated,
+        /// Account migration is already requested.
+        AccountMigrationAlreadyRequested,
+        /// Account migration is not requested.
+        AccountMigrationNotRequested,
+        /// Account migration delay is not passed yet.
+        AccountMigrationTimelocked,
+        /// Resource to migrate is not found under the account.
+        MigrationResourceNotFound,
+        /// Resource of the same type already exists under the new account.
+        MigrationTargetOccupied,
+        /// Resource embeds the old account address.
+        MigrationResourceEmbedsAddress,
+        /// Module bytecode exceeds `MaxModuleSize`.
+        ModuleTooLarge,
+        /// Script transaction exceeds `MaxScriptSize`.
+        ScriptTooLarge,
+        /// Type argument nesting exceeds `MaxTypeArgDepth`.
+        TypeArgsTooDeep,
+        /// Account is not allowed to publish modules.
+        PublisherNotAllowed,
+        /// Account is already in the publishers allowlist.
+        PublisherAlreadyAllowed,
+        /// Account is not in the publishers allowlist.
+        UnknownPublisher,
+        /// Not enough free balance to reserve the module deposit.
+        InsufficientModuleDeposit,
+        /// Module with deposit is not found under the account.
+        ModuleDepositNotFound,
+        /// Other modules depend on the module.
+        ModuleHasDependents,
+        /// Not enough free balance to reserve the resources storage deposit.
+        InsufficientStorageDeposit,
+        /// Multisig signers must be distinct, more than one and include the proposer.
+        InvalidMultisigSigners,
+        /// Multisig script with the same signers and gas limit is already proposed.
+        MultisigScriptAlreadyProposed,
+        /// Multisig script is not found.
+        UnknownMultisigScript,
+        /// Account is not a signer of the multisig script.
+        NotMultisigSigner,
+        /// Signer already approved the multisig script.
+        MultisigScriptAlreadyApproved,
+        /// Account is not the proposer of the multisig script.
+        NotMultisigProposer,
+        /// Approval gas limit doesn't cover the multisig script gas limit.
+        MultisigGasLimitTooLow,
+        /// Script can be scheduled for the future blocks only.
+        ScheduleInPast,
+        /// Gas limit of the call exceeds the rest of the block gas, see `MaxBlockGas`.
+        BlockGasLimitExceeded,
+        /// Ed25519 signed transaction is malformed or its sender isn't an account.
+        InvalidEd25519Transaction,
+        /// Session key isn't authorized by the account.
+        UnknownSessionKey,
+        /// Session key is expired (or the expiry block is in the past).
+        SessionKeyExpired,
+        /// Gas limit of the call exceeds the gas allowance of the session key.
+        SessionGasAllowanceExceeded,
+        /// Session key isn't allowed to call the function, only entry functions of its scope.
+        SessionCallNotAllowed,
+        /// Session key scope is empty or exceeds `MaxSessionScope`.
+        InvalidSessionScope,
+        /// Sponsored transaction isn't signed by the sender for the sponsor and its current
+        /// sequence number.
+        InvalidSponsoredSignature,
+        /// Signature of the sponsored transaction is expired.
+        SponsoredTransactionExpired,
+        /// Failed transact isn't queued.
+        UnknownFailedTransact,
+        /// Failed transacts queue is full (`MaxFailedTransacts`).
+        FailedTransactsQueueFull,
+        /// Gas limit of the retry doesn't cover the gas limit of the failed transact.
+        FailedTransactGasLimitTooLow,
+        /// Failed transact can be discarded only by its account or governance.
+        NotFailedTransactAccount,
+        /// Package name is empty or package metadata exceeds `MAX_PACKAGE_METADATA_SIZE`.
+        InvalidPackageMetadata,
+        /// Package dependency isn't published with metadata.
+        UnknownPackageDependency,
+        /// Package upgrade doesn't include all modules of the published package.
+        PackageModulesRemoved,
+        /// Module chunk is empty or exceeds `MaxModuleSize`.
+        InvalidModuleChunk,
+        /// Chunk offset isn't the size of the staged module bytecode.
+        ModuleChunkOffsetMismatch,
+        /// Staged module bytecode exceeds `MaxStagedModuleSize`.
+        StagedModuleTooLarge,
+        /// No module bytecode is staged by the account.
+        NoStagedModule,
+        /// Source map exceeds `MaxSourceMapSize`.
+        SourceMapTooLarge,
+        /// Governance proposal isn't submitted or is already enacted.
+        UnknownGovernanceProposal,
+        /// Governance proposal can be discarded only by its proposer or governance.
+        NotGovernanceProposer,
+        /// Callback of the governance proposal exceeds `MaxScriptSize`.
+        GovernanceCallbackTooLarge,
+        /// Account is already allowed to request treasury spends.
+        TreasurySpenderAlreadyAllowed,
+        /// Account isn't allowed to request treasury spends.
+        UnknownTreasurySpender,
+        /// Treasury spend exceeds the rest of `MaxTreasurySpend` budget of the spend period.
+        TreasurySpendTooLarge,
+        /// Callback exceeds `MaxScriptSize`.
+        CallbackTooLarge,
+        /// Gas limit of the callback exceeds `MaxCallbackGas`.
+        CallbackGasTooLarge,
+        /// `MaxCallbackSubscriptions` callbacks are already subscribed.
+        TooManyCallbackSubscriptions,
+        /// Callback subscription doesn't exist.
+        UnknownCallbackSubscription,
+        /// Only the subscriber or governance can remove the callback subscription.
+        NotCallbackSubscriber,
+        /// `MaxAccountCallbackSubscriptions` callbacks are already subscribed by the account.
+        TooManyAccountCallbackSubscriptions,
+        /// Not enough free balance to reserve the callback deposit.
+        InsufficientCallbackDeposit,
+        /// Gas limit of the scheduled script exceeds `MaxScheduledGas`.
+        ScheduledGasTooLarge,
+        /// `MaxScheduledScripts` scripts of the account are already pending.
+        TooManyScheduledScripts,
+        /// Weight of the governance proposal call and callback exceeds `max_weight`.
+        GovernanceProposalTooHeavy,
+        /// Proposer has too many open multisig scripts.
+        TooManyMultisigProposals,
+        /// Proposer can't reserve the multisig script deposit.
+        InsufficientMultisigDeposit,
+        /// Call of the governance proposal can't be decoded by the runtime.
+        InvalidGovernanceProposalCall,
+        /// Accounts with published modules can't be migrated.
+        MigrationOfPublisher,
     }
 }
 
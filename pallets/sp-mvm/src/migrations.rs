@@ -0,0 +1,229 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Migrations of the pallet storage and stored Move resources.
+//!
+//! Pallet storage is versioned with `StorageVersion`, its migrations run in the pallet
+//! `on_runtime_upgrade` hook.
+//!
+//! Move resources are BCS encoded, so once a framework module changes the layout of its struct,
+//! resources stored with the old layout can't be read anymore. Runtime upgrade publishing such
+//! a module has to rewrite them with `MigrateResources` added to the runtime migrations
+//! (`Executive`). Resource migrations are versioned with `ResourcesVersion` and applied once.
+//! Resource deposits are not changed by the migrations.
+//!
+//! With `try-runtime` feature the pallet checks the state before and after the upgrade with
+//! `check_state`, so the upgrade could be dry-run against the live state snapshot.
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use sp_std::prelude::*;
+use sp_std::collections::btree_map::BTreeMap;
+use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use frame_support::weights::Weight;
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+use crate::{Config, ModuleDependents, ModuleDeposits, Pallet, PublishedModules};
+use crate::{ResourcesVersion, VMStorage};
+use crate::storage::{is_module_key, resource_owner};
+use move_binary_format::file_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+
+/// Rewrites resources of the struct on runtime upgrade.
+pub trait ResourceMigration {
+    /// Resources version after the migration, the migration is skipped if already reached.
+    const VERSION: u32;
+
+    /// BCS encoded `StructTag` of the migrated resources, e.g. `storage::core_struct_tag`.
+    fn struct_tag() -> Vec<u8>;
+
+    /// Converts the resource to the new layout, `None` removes it.
+    fn migrate(owner: &AccountAddress, resource: Vec<u8>) -> Option<Vec<u8>>;
+}
+
+/// `OnRuntimeUpgrade` applying the resource migration `M`.
+pub struct MigrateResources<T, M>(PhantomData<(T, M)>);
+
+impl<T: Config, M: ResourceMigration> OnRuntimeUpgrade for MigrateResources<T, M> {
+    fn on_runtime_upgrade() -> Weight {
+        let version = ResourcesVersion::<T>::get();
+        if version >= M::VERSION {
+            info!(
+                "Move resources version {} reached, migration to {} skipped",
+                version,
+                M::VERSION
+            );
+            return T::DbWeight::get().reads(1);
+        }
+
+        let (reads, writes) = rewrite_resources::<T, _>(&M::struct_tag(), M::migrate);
+        ResourcesVersion::<T>::put(M::VERSION);
+        info!(
+            "Move resources migrated to version {}, {} resources rewritten",
+            M::VERSION,
+            writes
+        );
+
+        T::DbWeight::get().reads_writes(reads + 1, writes + 1)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<(), &'static str> {
+        Self::set_temp_storage(ResourcesVersion::<T>::get(), "ResourcesVersion");
+        Ok(())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade() -> Result<(), &'static str> {
+        let before: u32 = Self::get_temp_storage("ResourcesVersion")
+            .ok_or("Resources version before the upgrade not found")?;
+        if ResourcesVersion::<T>::get() != before.max(M::VERSION) {
+            return Err("Unexpected resources version after the migration");
+        }
+        Ok(())
+    }
+}
+
+/// Rewrites all resources with BCS encoded struct tag `tag` with `f`, `None` removes resource.
+///
+/// Iterates the whole Move VM storage, returns amount of reads and writes.
+pub fn rewrite_resources<T, F>(tag: &[u8], mut f: F) -> (u64, u64)
+where
+    T: Config,
+    F: FnMut(&AccountAddress, Vec<u8>) -> Option<Vec<u8>>,
+{
+    let mut reads = 0;
+    let matched: Vec<_> = VMStorage::<T>::iter()
+        .filter_map(|(key, value)| {
+            reads += 1;
+            let owner = resource_owner(&key)?;
+            if key[AccountAddress::LENGTH + 1..] == *tag {
+                Some((owner, key, value))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let mut writes = 0;
+    for (owner, key, value) in matched {
+        match f(&owner, value.clone()) {
+            Some(migrated) if migrated == value => {}
+            Some(migrated) => {
+                VMStorage::<T>::insert(&key, migrated);
+                writes += 1;
+            }
+            None => {
+                VMStorage::<T>::remove(&key);
+                writes += 1;
+            }
+        }
+    }
+    (reads, writes)
+}
+
+/// Runs migrations of the pallet storage up to the current `StorageVersion`.
+pub fn migrate<T: Config>() -> Weight {
+    let on_chain = Pallet::<T>::on_chain_storage_version();
+    let mut weight = T::DbWeight::get().reads(1);
+
+    if on_chain < 1 {
+        // Version 1: storage version introduced, no layout changes.
+        StorageVersion::new(1).put::<Pallet<T>>();
+        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+        info!("sp-mvm storage migrated to version 1");
+    }
+
+    if on_chain < 2 {
+        // Version 2: modules published under every address are counted in `PublishedModules`.
+        let (reads, writes) = count_published_modules::<T>();
+        StorageVersion::new(2).put::<Pallet<T>>();
+        weight = weight.saturating_add(T::DbWeight::get().reads_writes(reads, writes + 1));
+        info!("sp-mvm storage migrated to version 2, {} publishers counted", writes);
+    }
+
+    weight
+}
+
+/// Counts modules stored under every address into `PublishedModules`.
+///
+/// Iterates the whole Move VM storage, returns amount of reads and writes.
+fn count_published_modules<T: Config>() -> (u64, u64) {
+    let mut reads = 0;
+    let mut publishers = BTreeMap::<[u8; 32], u32>::new();
+    for key in VMStorage::<T>::iter_keys() {
+        reads += 1;
+        if is_stored_module_key(&key) {
+            let mut publisher = [0; 32];
+            publisher.copy_from_slice(&key[1..=AccountAddress::LENGTH]);
+            *publishers.entry(publisher).or_default() += 1;
+        }
+    }
+
+    let writes = publishers.len() as u64;
+    for (publisher, modules) in publishers {
+        PublishedModules::<T>::insert(publisher, modules);
+    }
+    (reads, writes)
+}
+
+/// Checks invariants of the pallet state:
+///     * every stored module is deserialized, stored under its own id and passes the bytecode
+///       verifier;
+///     * every module deposit and dependents counter refers to a stored module;
+///     * modules published under every address are counted in `PublishedModules`.
+///
+/// Linking of the modules is not checked. Returns amount of checked modules.
+pub fn check_state<T: Config>() -> Result<u32, &'static str> {
+    let mut modules = 0;
+    let mut publishers = BTreeMap::<[u8; 32], u32>::new();
+    for (key, code) in VMStorage::<T>::iter() {
+        if !is_stored_module_key(&key) {
+            continue;
+        }
+        modules += 1;
+        let mut publisher = [0; 32];
+        publisher.copy_from_slice(&key[1..=AccountAddress::LENGTH]);
+        *publishers.entry(publisher).or_default() += 1;
+
+        let module = CompiledModule::deserialize(&code).map_err(|err| {
+            error!("Failed to deserialize module {:?}: {:?}", key, err);
+            "Stored module can't be deserialized"
+        })?;
+        if module.self_id().access_vector() != key {
+            error!("Module {} stored under {:?}", module.self_id(), key);
+            return Err("Module is stored under the key of another module");
+        }
+        bytecode_verifier::verify_module(&module).map_err(|err| {
+            error!("Module {} is not verified: {:?}", module.self_id(), err);
+            "Stored module doesn't pass the bytecode verifier"
+        })?;
+    }
+
+    for key in ModuleDeposits::<T>::iter_keys() {
+        if !VMStorage::<T>::contains_key(&key) {
+            error!("Deposit of the missing module {:?}", key);
+            return Err("Module deposit refers to the missing module");
+        }
+    }
+    for key in ModuleDependents::<T>::iter_keys() {
+        if !VMStorage::<T>::contains_key(&key) {
+            error!("Dependents of the missing module {:?}", key);
+            return Err("Module dependents refer to the missing module");
+        }
+    }
+    if PublishedModules::<T>::iter().collect::<BTreeMap<_, _>>() != publishers {
+        return Err("Published modules are miscounted");
+    }
+
+    Ok(modules)
+}
+
+/// Checks if the key is a storage key of module published under any address.
+fn is_stored_module_key(key: &[u8]) -> bool {
+    key.get(1..=AccountAddress::LENGTH)
+        .and_then(|address| AccountAddress::try_from(address).ok())
+        .map(|address| is_module_key(key, &address))
+        .unwrap_or_default()
+}
@@ -0,0 +1,61 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Storage keys of Move state for verifiable reads.
+//!
+//! Move VM state is stored in `VMStorage` map under access keys (see `storage::resource_key`),
+//! so every resource and module is a single trie entry. Light clients and bridges verify it
+//! with the Substrate storage proof of the trie key, e.g. served by `mvm_getResourceWithProof`
+//! RPC. Trie value is SCALE encoded bytes of the resource (BCS encoded Move struct).
+//!
+//! Keys are also served by the runtime API (`mvm_storageKeyFor` and `mvm_moduleStorageKeyFor`
+//! RPC), so clients could read and subscribe to Move state with the standard `state_*` RPC.
+use sp_std::prelude::*;
+use frame_support::storage::StoragePrefixedMap;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, CODE_TAG};
+
+use crate::storage::stored_resource_key;
+use crate::{addr, Config, VMStorage};
+
+/// Prefix of all `VMStorage` trie keys.
+///
+/// Trie key is the prefix, blake2-128 hash of the SCALE encoded access key and the encoded key
+/// itself, so entries of the address don't share a prefix and are matched by the access key.
+pub fn vm_storage_prefix<T: Config>() -> [u8; 32] {
+    VMStorage::<T>::final_prefix()
+}
+
+/// Trie key of the `VMStorage` entry with access key `key`.
+pub fn vm_storage_key<T: Config>(key: &[u8]) -> Vec<u8> {
+    VMStorage::<T>::hashed_key_for(key)
+}
+
+/// Trie key of the resource published under `address`, `tag` is BCS-encoded `StructTag`.
+///
+/// Resources of grouped structs are stored in the group item, its value is SCALE encoded
+/// `storage::GroupItem` with the resource under its struct tag.
+pub fn resource_storage_key<T: Config>(address: &AccountAddress, tag: &[u8]) -> Vec<u8> {
+    vm_storage_key::<T>(&stored_resource_key::<T::ResourceGroups>(address, tag))
+}
+
+/// Trie key of the resource published under the account, `tag` is BCS-encoded `StructTag`.
+pub fn account_resource_storage_key<T: Config>(account: &T::AccountId, tag: &[u8]) -> Vec<u8> {
+    resource_storage_key::<T>(&addr::account_to_account_address(account), tag)
+}
+
+/// Trie key of the module.
+pub fn module_storage_key<T: Config>(module_id: &ModuleId) -> Vec<u8> {
+    vm_storage_key::<T>(&module_id.access_vector())
+}
+
+/// Trie key of the module with BCS-encoded `ModuleId` (as accepted by `mvm_getModule` RPC).
+///
+/// Module access key is code tag followed by the encoded id, so the id isn't decoded.
+pub fn encoded_module_storage_key<T: Config>(module_id: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(module_id.len() + 1);
+    key.push(CODE_TAG);
+    key.extend_from_slice(module_id);
+    vm_storage_key::<T>(&key)
+}
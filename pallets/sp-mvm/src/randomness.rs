@@ -0,0 +1,42 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Randomness seed for Move contracts.
+//!
+//! Every block the pallet stores the runtime randomness (`Config::Randomness`) as
+//! `0x1::Randomness::Seed { seed: vector<u8>, block: u64 }` resource under `0x1`,
+//! so Move code reads it with `borrow_global` and no native is needed.
+//!
+//! Seed is the same for all calls within the block and known to the block author in advance,
+//! so it fits games and lotteries with small stakes, but not high value draws.
+use sp_std::prelude::*;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+
+use crate::storage::{core_struct_tag, resource_key};
+
+/// Module of the seed struct, published under `0x1`.
+pub const RANDOMNESS_MODULE: &str = "Randomness";
+/// Name of the seed struct.
+pub const SEED_STRUCT: &str = "Seed";
+/// Subject of the randomness requested from the runtime.
+pub const RANDOMNESS_SUBJECT: &[u8] = b"mvm/randomness";
+
+/// BCS encoded `0x1::Randomness::Seed` struct tag.
+pub fn seed_tag() -> Vec<u8> {
+    core_struct_tag(RANDOMNESS_MODULE, SEED_STRUCT)
+}
+
+/// Storage key of the seed resource.
+pub fn seed_key() -> Vec<u8> {
+    resource_key(&CORE_CODE_ADDRESS, &seed_tag())
+}
+
+/// BCS encoded seed resource, seed is a hash, so its length fits one byte of ULEB128.
+pub fn seed_resource(seed: &[u8], block: u64) -> Vec<u8> {
+    let mut resource = Vec::with_capacity(seed.len() + 9);
+    resource.push(seed.len() as u8);
+    resource.extend_from_slice(seed);
+    resource.extend_from_slice(&block.to_le_bytes());
+    resource
+}
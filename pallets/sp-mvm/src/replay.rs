@@ -0,0 +1,124 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Replay bundle: everything needed to reproduce a Move VM call outside of the chain.
+//!
+//! Bundle contains a slice of `VMStorage` (modules and resources of the involved addresses),
+//! native balances of signers, block context, the call itself and its outcome on chain.
+//! Bundles are captured from a live node with `pontem mvm capture-replay` and executed
+//! by the pallet tests harness, see `tests/replay.rs`.
+use sp_std::prelude::*;
+use sp_runtime::{DispatchError, ModuleError};
+use parity_scale_codec::{Decode as DecodeT, Encode as EncodeT};
+use parity_scale_codec_derive::{Encode, Decode};
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::format;
+
+/// Magic bytes the bundle file starts with.
+pub const REPLAY_MAGIC: [u8; 4] = *b"MVMR";
+
+/// Current version of the bundle format.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// Move VM call to replay, signer `None` means root origin.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub enum ReplayCall {
+    Execute {
+        signer: Option<[u8; 32]>,
+        tx_bc: Vec<u8>,
+        gas_limit: u64,
+    },
+    PublishModule {
+        signer: Option<[u8; 32]>,
+        module_bc: Vec<u8>,
+        gas_limit: u64,
+    },
+    PublishPackage {
+        signer: Option<[u8; 32]>,
+        package: Vec<u8>,
+        gas_limit: u64,
+    },
+}
+
+/// Outcome of the call.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub enum ReplayOutcome {
+    Success,
+    /// Pallet error index, the same in any runtime.
+    MvmError(u8),
+    /// Any other dispatch error, rendered with `Debug`.
+    OtherError(Vec<u8>),
+}
+
+impl ReplayOutcome {
+    /// Converts dispatch error, `mvm_index` is the index of the pallet in the runtime.
+    pub fn from_dispatch_error(error: &DispatchError, mvm_index: u8) -> Self {
+        match error {
+            DispatchError::Module(ModuleError { index, error, .. }) if *index == mvm_index => {
+                Self::MvmError(*error)
+            }
+            error => Self::OtherError(format!("{:?}", error).into_bytes()),
+        }
+    }
+}
+
+/// Replay bundle.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub struct ReplayBundle {
+    /// Block the call was executed in.
+    pub block_number: u64,
+    /// Block timestamp (milliseconds).
+    pub timestamp: u64,
+    /// `VMStorage` key-value pairs before the block.
+    pub state: Vec<(Vec<u8>, Vec<u8>)>,
+    /// Free native balances of accounts before the block.
+    pub balances: Vec<([u8; 32], u128)>,
+    /// The call.
+    pub call: ReplayCall,
+    /// Outcome on chain.
+    pub expected: ReplayOutcome,
+}
+
+/// Bundle decoding errors.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ReplayError {
+    /// File doesn't start with `REPLAY_MAGIC`.
+    InvalidMagic,
+    /// Bundle format version isn't supported.
+    UnsupportedVersion(u32),
+    /// Bundle can't be decoded.
+    InvalidBundle,
+}
+
+impl ReplayBundle {
+    /// Encodes bundle with the magic and version header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = REPLAY_MAGIC.to_vec();
+        REPLAY_VERSION.encode_to(&mut bytes);
+        self.encode_to(&mut bytes);
+        bytes
+    }
+
+    /// Decodes bundle, checking the magic and version header.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, ReplayError> {
+        if !bytes.starts_with(&REPLAY_MAGIC) {
+            return Err(ReplayError::InvalidMagic);
+        }
+        bytes = &bytes[REPLAY_MAGIC.len()..];
+
+        let version = u32::decode(&mut bytes).map_err(|_| ReplayError::InvalidBundle)?;
+        if version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let bundle = Self::decode(&mut bytes).map_err(|_| ReplayError::InvalidBundle)?;
+        if !bytes.is_empty() {
+            return Err(ReplayError::InvalidBundle);
+        }
+        Ok(bundle)
+    }
+}
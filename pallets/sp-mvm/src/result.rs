@@ -16,6 +16,41 @@ use frame_support::dispatch::Weight;
 use frame_support::weights::Pays;
 use move_vm::types::VmResult;
 use move_core_types::vm_status::StatusCode;
+use parity_scale_codec_derive::{Encode, Decode};
+use scale_info::TypeInfo;
+
+/// Outcome of the Move VM call of the extrinsic, emitted as `ExecutionResult` event.
+///
+/// Move VM result doesn't carry the abort location, so only the abort code is reported.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+pub struct ExecutionResult {
+    /// Gas used, including gas of the storage accesses.
+    pub gas_used: u64,
+    /// Move VM status code, `EXECUTED` (4001) on success.
+    pub status_code: u64,
+    /// Abort code of the `ABORTED` calls.
+    pub abort_code: Option<u64>,
+    /// Amount of storage changes made by the call.
+    pub writes: u32,
+    /// Amount of Move events emitted by the call.
+    pub events: u32,
+}
+
+impl From<&VmResult> for ExecutionResult {
+    /// Writes and events are counted by the caller, see `Pallet::with_execution_result`.
+    fn from(vm_result: &VmResult) -> Self {
+        ExecutionResult {
+            gas_used: vm_result.gas_used,
+            status_code: vm_result.status_code as u64,
+            abort_code: match vm_result.status_code {
+                StatusCode::ABORTED => vm_result.sub_status,
+                _ => None,
+            },
+            writes: 0,
+            events: 0,
+        }
+    }
+}
 
 /// Check if the VM result contains successful execution code.
 pub fn is_ok(vm_result: &VmResult) -> bool {
@@ -71,6 +106,18 @@ pub fn from_vm_result<T: Config>(vm_result: VmResult) -> DispatchResultWithPostI
     }
 }
 
+/// Converts error of the call rejected before execution (e.g. arguments not matching the
+/// entry function) to dispatch error with the base `weight` of the call, the gas isn't charged.
+pub fn rejected<T: Config>(error: Error<T>, weight: Weight) -> DispatchErrorWithPostInfo {
+    DispatchErrorWithPostInfo {
+        post_info: PostDispatchInfo {
+            actual_weight: Some(weight),
+            pays_fee: Pays::Yes,
+        },
+        error: error.into(),
+    }
+}
+
 /// Converts multiple VM results to one dispatch result.
 pub fn from_vm_results<T: Config>(vm_results: &[VmResult]) -> DispatchResultWithPostInfo {
     let mut gas_total = 0;
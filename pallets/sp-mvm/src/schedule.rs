@@ -0,0 +1,129 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Scheduled execution of Move scripts.
+//!
+//! Scripts are scheduled by the runtime scheduler (usually `pallet-scheduler`) as `execute` calls
+//! dispatched with the signed origin, see `ScheduleScript`.
+//! Besides `schedule_execute` extrinsic, Move code can request a follow-up call by emitting
+//! `0x1::Schedule::ScheduleRequest { delay: u64, gas_limit: u64, script: vector<u8> }` event
+//! with the handle created for the signer, the script is scheduled on behalf of the handle owner.
+use sp_std::prelude::*;
+use sp_runtime::DispatchResult;
+use move_core_types::account_address::AccountAddress;
+
+use crate::event::{MessageReader, MoveEventArguments};
+
+/// Module of the schedule request struct, published under `0x1`.
+pub const SCHEDULE_MODULE: &str = "Schedule";
+/// Name of the schedule request struct.
+pub const SCHEDULE_REQUEST: &str = "ScheduleRequest";
+
+/// Schedules execution of the Move script on behalf of the account.
+pub trait ScheduleScript<AccountId, BlockNumber> {
+    /// Schedule `execute` call with the signed origin of `who` at block `when`.
+    fn schedule_script(
+        who: AccountId,
+        when: BlockNumber,
+        tx_bc: Vec<u8>,
+        gas_limit: u64,
+    ) -> DispatchResult;
+}
+
+/// Schedule request emitted by Move code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ScheduleRequest {
+    /// Address the script is executed on behalf of, owner of the event handle.
+    pub address: AccountAddress,
+    /// Delay in blocks, zero is treated as the next block.
+    pub delay: u64,
+    /// Gas limit of the scheduled call.
+    pub gas_limit: u64,
+    /// Script transaction bytecode.
+    pub script: Vec<u8>,
+}
+
+impl ScheduleRequest {
+    /// Converts Move event into the schedule request.
+    ///
+    /// Returns `None` if event isn't `0x1::Schedule::ScheduleRequest` or can't be decoded.
+    pub fn from_event(e: &MoveEventArguments) -> Option<Self> {
+        let address = e.core_request(SCHEDULE_MODULE, SCHEDULE_REQUEST)?;
+
+        // BCS encoded struct: u64, u64, vector<u8>.
+        let mut message = MessageReader::new(&e.message);
+        let request = Self {
+            address,
+            delay: message.read_u64()?,
+            gas_limit: message.read_u64()?,
+            script: message.read_bytes()?,
+        };
+        if !message.is_empty() {
+            return None;
+        }
+        Some(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS};
+
+    use super::*;
+
+    fn request_event(
+        address: AccountAddress,
+        module: &str,
+        message: Vec<u8>,
+    ) -> MoveEventArguments {
+        MoveEventArguments {
+            guid: [0u64.to_le_bytes().to_vec(), address.to_vec()].concat(),
+            ty_tag: TypeTag::Struct(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(module).unwrap(),
+                name: Identifier::new(SCHEDULE_REQUEST).unwrap(),
+                type_params: vec![],
+            }),
+            message,
+        }
+    }
+
+    fn request_message(delay: u64, gas_limit: u64, script: &[u8]) -> Vec<u8> {
+        let mut message = delay.to_le_bytes().to_vec();
+        message.extend_from_slice(&gas_limit.to_le_bytes());
+        message.push(script.len() as u8);
+        message.extend_from_slice(script);
+        message
+    }
+
+    #[test]
+    fn decode_request() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let e = request_event(address, SCHEDULE_MODULE, request_message(5, 1000, &[1, 2, 3]));
+
+        assert_eq!(
+            ScheduleRequest::from_event(&e),
+            Some(ScheduleRequest {
+                address,
+                delay: 5,
+                gas_limit: 1000,
+                script: vec![1, 2, 3],
+            })
+        );
+    }
+
+    #[test]
+    fn ignore_other_events() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+
+        let e = request_event(address, "Coin", request_message(5, 1000, &[1, 2, 3]));
+        assert_eq!(ScheduleRequest::from_event(&e), None);
+
+        let mut message = request_message(5, 1000, &[1, 2, 3]);
+        message.pop();
+        let e = request_event(address, SCHEDULE_MODULE, message);
+        assert_eq!(ScheduleRequest::from_event(&e), None);
+    }
+}
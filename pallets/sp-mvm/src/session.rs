@@ -0,0 +1,165 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Session keys of Move dApps.
+//!
+//! An account authorizes a secondary key (`authorize_session_key`) to call the listed entry
+//! functions on its behalf until the expiry block, within the gas allowance. The key submits
+//! `execute_with_session_key` signed by itself (and pays the fees), the transaction is executed
+//! signed by the account, so games can send frequent transactions without wallet pop-ups.
+//!
+//! `CheckSessionKey` signed extension rejects calls the key isn't allowed to make before they
+//! get into the transaction pool, the same checks are repeated by the dispatchable.
+//! Gas used by every call is deducted from the allowance. Expired keys stay in the storage
+//! until revoked by the account.
+use core::marker::PhantomData;
+use frame_support::traits::IsSubType;
+use frame_system::pallet_prelude::OriginFor;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_vm::types::{Call as VmCall, Transaction};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{DispatchInfoOf, SignedExtension};
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+};
+use sp_std::prelude::*;
+
+use crate::addr::account_to_account_address;
+use crate::types::{SessionKey, SessionScope};
+use crate::{Call, Config, Error, SessionKeys};
+
+/// Entry function called by the transaction: module address, module and function names.
+type EntryFunction = (AccountAddress, Identifier, Identifier);
+
+/// Checks the session key of `owner` allows the call of the transaction (`dove tx` output)
+/// with the gas limit, returns the key.
+pub fn check<T: Config>(
+    owner: &T::AccountId,
+    session: &T::AccountId,
+    tx_bc: &[u8],
+    gas_limit: u64,
+) -> Result<SessionKey<T::AccountId, T::BlockNumber>, Error<T>> {
+    let key = SessionKeys::<T>::get(owner, session).ok_or(Error::<T>::UnknownSessionKey)?;
+    if frame_system::Pallet::<T>::block_number() >= key.expires_at {
+        return Err(Error::<T>::SessionKeyExpired);
+    }
+    if gas_limit > key.gas_allowance {
+        return Err(Error::<T>::SessionGasAllowanceExceeded);
+    }
+
+    let function = entry_function(tx_bc).ok_or(Error::<T>::SessionCallNotAllowed)?;
+    if !key.scope.iter().any(|scope| allows(scope, &function)) {
+        return Err(Error::<T>::SessionCallNotAllowed);
+    }
+    Ok(key)
+}
+
+/// Entry function called by the transaction, `None` for raw scripts and malformed transactions.
+fn entry_function(tx_bc: &[u8]) -> Option<EntryFunction> {
+    let tx = Transaction::try_from(tx_bc).ok()?;
+    // Signers are resolved at the execution, any addresses are fine here.
+    let signers = vec![AccountAddress::ZERO; tx.signers_count() as usize];
+    let tx = tx.into_script(signers).ok()?;
+    match tx.call() {
+        VmCall::ScriptFunction {
+            mod_address,
+            mod_name,
+            func_name,
+        } => Some((*mod_address, mod_name.clone(), func_name.clone())),
+        VmCall::Script { .. } => None,
+    }
+}
+
+fn allows<AccountId: Encode>(
+    scope: &SessionScope<AccountId>,
+    (address, module, function): &EntryFunction,
+) -> bool {
+    account_to_account_address(&scope.owner) == *address
+        && scope.module == module.as_bytes()
+        && scope
+            .function
+            .as_ref()
+            .map_or(true, |name| name.as_slice() == function.as_bytes())
+}
+
+/// Signed extension checking session keys of `execute_with_session_key` calls.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckSessionKey<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckSessionKey<T> {
+    pub fn new() -> Self {
+        CheckSessionKey(PhantomData)
+    }
+}
+
+impl<T: Config + Send + Sync> Default for CheckSessionKey<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckSessionKey<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckSessionKey")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckSessionKey<T>
+where
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+    OriginFor<T>: Into<Result<groupsign::Origin<T>, OriginFor<T>>>,
+{
+    const IDENTIFIER: &'static str = "CheckSessionKey";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if let Some(Call::execute_with_session_key {
+            owner,
+            tx_bc,
+            gas_limit,
+            ..
+        }) = call.is_sub_type()
+        {
+            check::<T>(owner, who, tx_bc, *gas_limit).map_err(|err| match err {
+                Error::<T>::UnknownSessionKey => InvalidTransaction::BadSigner,
+                Error::<T>::SessionKeyExpired => InvalidTransaction::Stale,
+                Error::<T>::SessionGasAllowanceExceeded => InvalidTransaction::Payment,
+                _ => InvalidTransaction::Call,
+            })?;
+        }
+        Ok(ValidTransaction::default())
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<(), TransactionValidityError> {
+        self.validate(who, call, info, len).map(|_| ())
+    }
+}
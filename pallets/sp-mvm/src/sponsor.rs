@@ -0,0 +1,153 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Sponsored Move transactions: a third party (sponsor) pays fees of the account's transaction.
+//!
+//! The sender signs the sponsored payload (see `payload`) off chain and passes it to the sponsor,
+//! the sponsor submits `execute_sponsored` with the signature signed by itself, so it pays the
+//! transaction fee and the gas tip. The script is executed signed by the sender, so accounts
+//! without tokens can be onboarded.
+//!
+//! The payload includes the sponsor, so only the chosen sponsor can submit it, and the sequence
+//! number of the sender (bumped by the execution), so it can't be replayed.
+//! `CheckSponsor` signed extension rejects sponsored calls with invalid or expired signatures
+//! before they reach the transaction pool, the same checks are repeated by the dispatchable.
+use core::marker::PhantomData;
+use frame_support::traits::IsSubType;
+use frame_system::pallet_prelude::OriginFor;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{DispatchInfoOf, SignedExtension};
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+};
+use sp_runtime::verify_encoded_lazy;
+
+use crate::{Call, Config, Error, SequenceNumbers};
+
+/// Domain separator of the sponsored payload, so other signed data can't be reused.
+pub const SPONSORED_PAYLOAD_PREFIX: &[u8] = b"mvm:sponsored";
+
+/// Payload signed by the sender: blake2-256 hash of the SCALE encoded prefix, sponsor,
+/// transaction, gas limit, expiry block and sequence number of the sender. The gas unit price is
+/// chosen by the sponsor paying the tip.
+pub fn payload<T: Config>(
+    sponsor: &T::AccountId,
+    tx_bc: &[u8],
+    gas_limit: u64,
+    valid_thru: T::BlockNumber,
+    sequence_number: u64,
+) -> [u8; 32] {
+    let payload = (
+        SPONSORED_PAYLOAD_PREFIX,
+        sponsor,
+        tx_bc,
+        gas_limit,
+        valid_thru,
+        sequence_number,
+    );
+    sp_io::hashing::blake2_256(&payload.encode())
+}
+
+/// Checks the sponsored call: the sender signed the payload for the sponsor with its current
+/// sequence number and the signature isn't expired.
+pub fn check<T: Config>(
+    sponsor: &T::AccountId,
+    sender: &T::AccountId,
+    tx_bc: &[u8],
+    gas_limit: u64,
+    valid_thru: T::BlockNumber,
+    signature: &<T as groupsign::Config>::Signature,
+) -> Result<(), Error<T>> {
+    if frame_system::Pallet::<T>::block_number() >= valid_thru {
+        return Err(Error::<T>::SponsoredTransactionExpired);
+    }
+
+    let sequence_number = SequenceNumbers::<T>::get(sender);
+    let payload = payload::<T>(sponsor, tx_bc, gas_limit, valid_thru, sequence_number);
+    if !verify_encoded_lazy(signature, &payload, sender) {
+        return Err(Error::<T>::InvalidSponsoredSignature);
+    }
+    Ok(())
+}
+
+/// Signed extension checking signatures of `execute_sponsored` calls.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckSponsor<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckSponsor<T> {
+    pub fn new() -> Self {
+        CheckSponsor(PhantomData)
+    }
+}
+
+impl<T: Config + Send + Sync> Default for CheckSponsor<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckSponsor<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckSponsor")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckSponsor<T>
+where
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+    OriginFor<T>: Into<Result<groupsign::Origin<T>, OriginFor<T>>>,
+{
+    const IDENTIFIER: &'static str = "CheckSponsor";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if let Some(Call::execute_sponsored {
+            sender,
+            tx_bc,
+            gas_limit,
+            valid_thru,
+            signature,
+        }) = call.is_sub_type()
+        {
+            check::<T>(who, sender, tx_bc, *gas_limit, *valid_thru, signature).map_err(
+                |err| match err {
+                    Error::<T>::SponsoredTransactionExpired => InvalidTransaction::Stale,
+                    _ => InvalidTransaction::BadProof,
+                },
+            )?;
+        }
+        Ok(ValidTransaction::default())
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<(), TransactionValidityError> {
+        self.validate(who, call, info, len).map(|_| ())
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Staking state of the runtime (e.g. `parachain-staking`) for Move contracts.
+//!
+//! Liquid staking protocols written in Move track the chain staking without oracles by reading
+//! resources stored by the pallet, no native is needed:
+//! ```move
+//! struct Round {
+//!     index: u64,
+//!     first_block: u64,
+//!     length: u64,
+//!     collators: vector<address>,
+//!     total_staked: u128,
+//! }
+//! struct Stake { delegated: u128, collator_bond: u128 }
+//! ```
+//! Every block the current round (era) of the runtime staking (`Config::Staking`) is stored as
+//! `0x1::Staking::Round` resource under `0x1`. While the script runs, the stake of every signer
+//! is stored as `0x1::Staking::Stake` resource under the signer and removed after the script,
+//! as `vesting` lockups are. Both are read-only: Move code can't change them.
+use sp_std::prelude::*;
+use parity_scale_codec::Encode;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::CORE_CODE_ADDRESS;
+
+use crate::addr::account_to_bytes;
+use crate::storage::{core_struct_tag, resource_key};
+
+/// Module of the staking structs, published under `0x1`.
+pub const STAKING_MODULE: &str = "Staking";
+/// Name of the round struct.
+pub const ROUND_STRUCT: &str = "Round";
+/// Name of the stake struct.
+pub const STAKE_STRUCT: &str = "Stake";
+/// Maximum amount of the stored collators, fits one byte of ULEB128.
+pub const MAX_COLLATORS: usize = 127;
+
+/// Current staking round of the runtime.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Round<AccountId> {
+    /// Round (era) index.
+    pub index: u64,
+    /// First block of the round.
+    pub first_block: u64,
+    /// Length of the round in blocks.
+    pub length: u64,
+    /// Collators selected for the round.
+    pub collators: Vec<AccountId>,
+    /// Total amount staked by collators and delegators.
+    pub total_staked: u128,
+}
+
+impl<AccountId: Encode> Round<AccountId> {
+    /// BCS encoded round resource, collators over `MAX_COLLATORS` are omitted.
+    pub fn to_resource(&self) -> Vec<u8> {
+        let collators = self.collators.len().min(MAX_COLLATORS);
+        let mut resource = Vec::with_capacity(41 + collators * AccountAddress::LENGTH);
+        resource.extend_from_slice(&self.index.to_le_bytes());
+        resource.extend_from_slice(&self.first_block.to_le_bytes());
+        resource.extend_from_slice(&self.length.to_le_bytes());
+        resource.push(collators as u8);
+        for collator in &self.collators[..collators] {
+            resource.extend_from_slice(&account_to_bytes(collator));
+        }
+        resource.extend_from_slice(&self.total_staked.to_le_bytes());
+        resource
+    }
+}
+
+/// Stake of the account.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Stake {
+    /// Total amount delegated to collators.
+    pub delegated: u128,
+    /// Self bond of the collator candidate, zero for other accounts.
+    pub collator_bond: u128,
+}
+
+impl Stake {
+    /// BCS encoded stake resource.
+    pub fn to_resource(&self) -> Vec<u8> {
+        [self.delegated.to_le_bytes(), self.collator_bond.to_le_bytes()].concat()
+    }
+}
+
+/// Read-only staking state of the runtime.
+pub trait StakingInfo<AccountId> {
+    /// Current round, `None` if the runtime doesn't stake.
+    fn round() -> Option<Round<AccountId>>;
+
+    /// Stake of the account, `None` if nothing is staked.
+    fn stake(who: &AccountId) -> Option<Stake>;
+}
+
+/// No staking: staking resources are never stored.
+impl<AccountId> StakingInfo<AccountId> for () {
+    fn round() -> Option<Round<AccountId>> {
+        None
+    }
+
+    fn stake(_: &AccountId) -> Option<Stake> {
+        None
+    }
+}
+
+/// BCS encoded `0x1::Staking::Round` struct tag.
+pub fn round_tag() -> Vec<u8> {
+    core_struct_tag(STAKING_MODULE, ROUND_STRUCT)
+}
+
+/// Storage key of the round resource.
+pub fn round_key() -> Vec<u8> {
+    resource_key(&CORE_CODE_ADDRESS, &round_tag())
+}
+
+/// BCS encoded `0x1::Staking::Stake` struct tag.
+pub fn stake_tag() -> Vec<u8> {
+    core_struct_tag(STAKING_MODULE, STAKE_STRUCT)
+}
+
+/// Storage key of the stake resource of the account.
+pub fn stake_key(address: &AccountAddress) -> Vec<u8> {
+    resource_key(address, &stake_tag())
+}
@@ -2,12 +2,15 @@
 // This file is part of Pontem Network.
 // Apache 2.0
 
+use core::convert::TryFrom;
 use core::marker::PhantomData;
 use sp_std::prelude::*;
-use parity_scale_codec::FullCodec;
-use parity_scale_codec::FullEncode;
+use sp_std::collections::btree_map::BTreeMap;
+use parity_scale_codec::{Decode, FullCodec, FullEncode};
 use frame_support::storage::StorageMap;
 use move_vm::io::traits::Storage;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{CODE_TAG, CORE_CODE_ADDRESS, RESOURCE_TAG};
 
 pub trait MoveVmStorage<T, K: FullEncode, V: FullCodec> {
     type VmStorage;
@@ -49,6 +52,123 @@ impl<T: StorageMap<Vec<u8>, Vec<u8>, Query = Option<Vec<u8>>>> Storage
     }
 }
 
+/// Storage key of the resource published under `address`, `tag` is BCS-encoded `StructTag`.
+///
+/// Same layout as `AccessKey` built by Move VM: address, resource tag, struct tag.
+pub fn resource_key(address: &AccountAddress, tag: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(AccountAddress::LENGTH + 1 + tag.len());
+    key.extend_from_slice(address.as_ref());
+    key.push(RESOURCE_TAG);
+    key.extend_from_slice(tag);
+    key
+}
+
+/// Resource groups, as Aptos ones: resources of the member structs are packed into one storage
+/// item under the group struct tag (`resource_key(owner, group)`), so accounts holding several
+/// framework resources take one trie node. The item is SCALE encoded `GroupItem`.
+///
+/// Changing groups of stored resources requires a migration of their storage items.
+pub trait ResourceGroups {
+    /// BCS encoded struct tag of the group of the member struct (BCS encoded `StructTag`),
+    /// `None` if resources of the struct are stored as separate items.
+    fn group_of(member: &[u8]) -> Option<Vec<u8>>;
+}
+
+/// No resource groups.
+impl ResourceGroups for () {
+    fn group_of(_: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// Resources of the group by BCS encoded struct tags of the members.
+pub type GroupItem = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Storage key of the group item with the resource stored under `key` and the member struct
+/// tag, `None` for resources of ungrouped structs, modules and other keys.
+pub fn group_member_key<G: ResourceGroups>(key: &[u8]) -> Option<(Vec<u8>, Vec<u8>)> {
+    let owner = resource_owner(key)?;
+    let member = &key[AccountAddress::LENGTH + 1..];
+    let group = G::group_of(member)?;
+    Some((resource_key(&owner, &group), member.to_vec()))
+}
+
+/// Storage key of the item holding the resource: its group item or its own one.
+pub fn stored_resource_key<G: ResourceGroups>(address: &AccountAddress, tag: &[u8]) -> Vec<u8> {
+    let key = resource_key(address, tag);
+    group_member_key::<G>(&key)
+        .map(|(group_key, _)| group_key)
+        .unwrap_or(key)
+}
+
+/// Decodes the group item, missing and malformed items are empty.
+pub fn decode_group(item: Option<Vec<u8>>) -> GroupItem {
+    item.and_then(|item| GroupItem::decode(&mut &item[..]).ok())
+        .unwrap_or_default()
+}
+
+/// BCS encoded tag of the `0x1::<module>::<name>` struct without type parameters.
+///
+/// Identifiers are shorter than 128 bytes, so their length fits one byte of ULEB128.
+pub fn core_struct_tag(module: &str, name: &str) -> Vec<u8> {
+    let mut tag = CORE_CODE_ADDRESS.to_vec();
+    for identifier in [module, name] {
+        tag.push(identifier.len() as u8);
+        tag.extend_from_slice(identifier.as_bytes());
+    }
+    // No type parameters.
+    tag.push(0);
+    tag
+}
+
+/// Checks if the resource blob contains the address (e.g. stored `GUID` or address field).
+pub fn embeds_address(blob: &[u8], address: &AccountAddress) -> bool {
+    blob.windows(AccountAddress::LENGTH)
+        .any(|window| window == address.as_ref())
+}
+
+/// Checks if the key is a storage key of module published under `address`.
+///
+/// Module key is code tag followed by BCS-encoded `ModuleId`: address and module name.
+pub fn is_module_key(key: &[u8], address: &AccountAddress) -> bool {
+    const NAME_OFFSET: usize = 1 + AccountAddress::LENGTH;
+    key.len() > NAME_OFFSET
+        && key[0] == CODE_TAG
+        && &key[1..NAME_OFFSET] == address.as_ref()
+        // Module names are shorter than 128 bytes, so name length takes one byte.
+        && key[NAME_OFFSET] as usize + NAME_OFFSET + 1 == key.len()
+}
+
+/// Checks if the key is a storage key of module published under any address.
+pub fn is_code_key(key: &[u8]) -> bool {
+    key.get(1..=AccountAddress::LENGTH)
+        .and_then(|publisher| AccountAddress::try_from(publisher).ok())
+        .map_or(false, |publisher| is_module_key(key, &publisher))
+}
+
+/// Checks if the key is a storage key of resource published under `address`.
+pub fn is_resource_key(key: &[u8], address: &AccountAddress) -> bool {
+    key.len() > AccountAddress::LENGTH + 1
+        && &key[..AccountAddress::LENGTH] == address.as_ref()
+        && key[AccountAddress::LENGTH] == RESOURCE_TAG
+}
+
+/// Returns owner address of the resource storage key, `None` for module and other keys.
+pub fn resource_owner(key: &[u8]) -> Option<AccountAddress> {
+    let owner = AccountAddress::try_from(key.get(..AccountAddress::LENGTH)?).ok()?;
+    if !is_resource_key(key, &owner) {
+        return None;
+    }
+
+    // Module key could look like resource one if the publisher address ends with the resource tag.
+    let publisher = AccountAddress::try_from(key.get(1..=AccountAddress::LENGTH)?).ok()?;
+    if is_module_key(key, &publisher) {
+        return None;
+    }
+
+    Some(owner)
+}
+
 pub mod boxed {
     use sp_std::prelude::*;
     pub type VmStorageAdapter = VmStorageBoxAdapter;
@@ -76,6 +196,17 @@ pub mod boxed {
         }
     }
 
+    impl VmStorageBoxAdapter {
+        /// Creates adapter from custom storage functions, e.g. to track writes.
+        pub fn new(f_get: Box<Get>, f_insert: Box<Insert>, f_remove: Box<Remove>) -> Self {
+            VmStorageBoxAdapter {
+                f_get,
+                f_insert,
+                f_remove,
+            }
+        }
+    }
+
     impl<T> From<super::StorageAdapter<T, Vec<u8>, Vec<u8>>> for VmStorageBoxAdapter
     where
         T: super::StorageMap<Vec<u8>, Vec<u8>, Query = Option<Vec<u8>>>,
@@ -0,0 +1,116 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Treasury spends proposed by Move code.
+//!
+//! Grant distribution logic written in Move proposes spends by emitting
+//! `0x1::Treasury::SpendRequest { amount: u128, beneficiary: address }` event with the handle
+//! created for the spender. The request is accepted only from the accounts allowed by
+//! governance (`TreasurySpenders`, see `allow_treasury_spender`), as the capability of the
+//! governance-published module running the grants, and up to `MaxTreasurySpend` per spender
+//! within every `TreasurySpendPeriod` blocks.
+//!
+//! The runtime spends with its spend origin (`TreasurySpend`, e.g. the proposal of
+//! `pallet-treasury` approved on behalf of Move code), so the spend is paid out of the treasury
+//! budget at the next spend period.
+use sp_std::prelude::*;
+use sp_runtime::{DispatchError, DispatchResult};
+use move_core_types::account_address::AccountAddress;
+
+use crate::event::{MessageReader, MoveEventArguments};
+
+/// Module of the spend request struct, published under `0x1`.
+pub const TREASURY_MODULE: &str = "Treasury";
+/// Name of the spend request struct.
+pub const SPEND_REQUEST: &str = "SpendRequest";
+
+/// Spends the runtime treasury on behalf of Move code.
+pub trait TreasurySpend<AccountId> {
+    /// Spend `amount` of native coins to `beneficiary`, requested by `spender`.
+    fn spend(spender: AccountId, beneficiary: AccountId, amount: u128) -> DispatchResult;
+}
+
+/// Treasury spends are not supported.
+impl<AccountId> TreasurySpend<AccountId> for () {
+    fn spend(_: AccountId, _: AccountId, _: u128) -> DispatchResult {
+        Err(DispatchError::Other("Treasury spends are not supported"))
+    }
+}
+
+/// Spend request emitted by Move code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SpendRequest {
+    /// Spender address, owner of the event handle.
+    pub address: AccountAddress,
+    /// Amount of native coins to spend.
+    pub amount: u128,
+    /// Address of the beneficiary.
+    pub beneficiary: AccountAddress,
+}
+
+impl SpendRequest {
+    /// Converts Move event into the spend request.
+    ///
+    /// Returns `None` if event isn't `0x1::Treasury::SpendRequest` or can't be decoded.
+    pub fn from_event(e: &MoveEventArguments) -> Option<Self> {
+        let address = e.core_request(TREASURY_MODULE, SPEND_REQUEST)?;
+
+        // BCS encoded struct: u128, address.
+        let mut message = MessageReader::new(&e.message);
+        let mut amount = [0; 16];
+        amount.copy_from_slice(message.read_raw(16)?);
+        let beneficiary = message.read_raw(AccountAddress::LENGTH)?;
+        if !message.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            address,
+            amount: u128::from_le_bytes(amount),
+            beneficiary: AccountAddress::from_bytes(beneficiary).ok()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS};
+
+    use super::*;
+
+    fn request_event(address: AccountAddress, name: &str, message: Vec<u8>) -> MoveEventArguments {
+        MoveEventArguments {
+            guid: [0u64.to_le_bytes().to_vec(), address.to_vec()].concat(),
+            ty_tag: TypeTag::Struct(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(TREASURY_MODULE).unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_params: vec![],
+            }),
+            message,
+        }
+    }
+
+    #[test]
+    fn decode_request() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let beneficiary = AccountAddress::new([9; AccountAddress::LENGTH]);
+        let mut message = 1000u128.to_le_bytes().to_vec();
+        message.extend_from_slice(beneficiary.as_ref());
+
+        assert_eq!(
+            SpendRequest::from_event(&request_event(address, SPEND_REQUEST, message.clone())),
+            Some(SpendRequest {
+                address,
+                amount: 1000,
+                beneficiary,
+            })
+        );
+        assert_eq!(
+            SpendRequest::from_event(&request_event(address, "Spend", message)),
+            None
+        );
+    }
+}
@@ -7,8 +7,10 @@
 use core::convert::{TryInto, TryFrom};
 use move_core_types::identifier::Identifier;
 use sp_std::prelude::*;
-use parity_scale_codec::{Decode as DecodeT};
+use parity_scale_codec::{Decode as DecodeT, Encode as EncodeT};
 use parity_scale_codec_derive::{Encode, Decode};
+use scale_info::TypeInfo;
+use sp_runtime::DispatchError;
 use crate::addr::address_to_account;
 use move_core_types::language_storage::ModuleId as InternalModuleId;
 use move_core_types::language_storage::StructTag as InternalStructTag;
@@ -134,3 +136,278 @@ impl<AccountId: DecodeT> TryFrom<InternalStructTag> for MoveStructTag<AccountId>
         })
     }
 }
+
+/// Hash of the raw Move script bytecode (blake2-256).
+pub type ScriptHash = [u8; 32];
+
+/// Move transaction (script or entry function call) built by `dove tx`.
+/// Aliases below name `Vec<u8>` and `u64` arguments of the calls in the metadata,
+/// so generated clients (e.g. `subxt`) get meaningful types.
+pub type TransactionBytecode = Vec<u8>;
+
+/// Bytecode of the compiled Move module.
+pub type ModuleBytecode = Vec<u8>;
+
+/// Package bundle (`.pac`) of Move modules built by `dove`.
+pub type PackageBytecode = Vec<u8>;
+
+/// Name of the Move module.
+pub type ModuleName = Vec<u8>;
+
+/// BCS encoded `StructTag` of the Move resource.
+pub type EncodedStructTag = Vec<u8>;
+
+/// BCS encoded ed25519 signed transaction, see `ed25519_tx`.
+pub type Ed25519SignedTransaction = Vec<u8>;
+
+/// Amount of Move VM gas.
+pub type GasAmount = u64;
+
+/// Price of the gas unit in the native currency.
+pub type GasUnitPrice = u64;
+
+/// Calculates hash of the raw Move script bytecode, used as a key in the scripts allowlist.
+pub fn script_hash(code: &[u8]) -> ScriptHash {
+    sp_io::hashing::blake2_256(code)
+}
+
+/// Calculates hash of the multisig script proposal: script bytecode, gas limit and signers.
+pub fn multisig_script_hash<AccountId: EncodeT>(
+    tx_bc: &[u8],
+    gas_limit: u64,
+    signers: &[AccountId],
+) -> ScriptHash {
+    sp_io::hashing::blake2_256(&(tx_bc, gas_limit, signers).encode())
+}
+
+/// Calculates nesting depth of the type tag: primitive types have depth 1.
+pub fn type_tag_depth(tag: &InternalTypeTag) -> u32 {
+    match tag {
+        InternalTypeTag::Vector(inner) => type_tag_depth(inner).saturating_add(1),
+        InternalTypeTag::Struct(tag) => tag
+            .type_params
+            .iter()
+            .map(type_tag_depth)
+            .max()
+            .unwrap_or(0)
+            .saturating_add(1),
+        _ => 1,
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Optional metadata attached to the allowlisted script.
+pub struct ScriptMetadata<AccountId> {
+    /// Human-readable description of the script.
+    pub description: Option<Vec<u8>>,
+    /// Account proposed the script.
+    pub proposer: Option<AccountId>,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Requested migration of Move state to another account.
+pub struct PendingMigration<AccountId, BlockNumber> {
+    /// Account receiving the resources.
+    pub to: AccountId,
+    /// Block since which the migration can be enacted.
+    pub enactable_at: BlockNumber,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Deposit reserved for the published module.
+pub struct ModuleDeposit<AccountId, Balance> {
+    /// Account the deposit is reserved from.
+    pub depositor: AccountId,
+    /// Reserved amount.
+    pub amount: Balance,
+    /// Storage keys of the modules the module depends on (except `0x1`).
+    pub dependencies: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Script with multiple signers waiting for approvals.
+pub struct MultisigScript<AccountId, Balance> {
+    /// Account proposed the script, one of the signers.
+    pub proposer: AccountId,
+    /// Script transaction bytecode.
+    pub tx_bc: Vec<u8>,
+    /// Gas limit of the execution.
+    pub gas_limit: u64,
+    /// Signers in the order of the script `signer` parameters.
+    pub signers: Vec<AccountId>,
+    /// Signers approved the script.
+    pub approvals: Vec<AccountId>,
+    /// Deposit reserved from the proposer.
+    pub deposit: Balance,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Entry functions of the module a session key is allowed to call.
+pub struct SessionScope<AccountId> {
+    /// Publisher of the module.
+    pub owner: AccountId,
+    /// Module name.
+    pub module: ModuleName,
+    /// Entry function name, any entry function of the module if not set.
+    pub function: Option<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Secondary key calling Move entry functions on behalf of the account, see `session`.
+pub struct SessionKey<AccountId, BlockNumber> {
+    /// Entry functions the key is allowed to call.
+    pub scope: Vec<SessionScope<AccountId>>,
+    /// Gas left for the calls, gas used by every call is deducted.
+    pub gas_allowance: u64,
+    /// Block since which the key can't be used.
+    pub expires_at: BlockNumber,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Failed script dispatched by XCM `Transact`, queued for retries.
+pub struct FailedTransact<AccountId, BlockNumber> {
+    /// Account signing the script.
+    pub account: AccountId,
+    /// Script transaction (`dove tx` output).
+    pub tx_bc: Vec<u8>,
+    /// Gas limit of the script.
+    pub gas_limit: u64,
+    /// Error of the last execution.
+    pub error: DispatchError,
+    /// Block the script failed at.
+    pub failed_at: BlockNumber,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Runtime governance proposal submitted by Move code, see `governance`.
+pub struct GovernanceProposal<AccountId> {
+    /// Account of the Move proposer.
+    pub proposer: AccountId,
+    /// SCALE encoded runtime call.
+    pub call: Vec<u8>,
+    /// Script executed signed by the proposer once the proposal is enacted, empty for none.
+    pub callback: Vec<u8>,
+    /// Gas limit of the callback.
+    pub callback_gas: u64,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Trigger of the Move callback, see `callback`.
+pub enum CallbackTrigger {
+    /// Every block initialization.
+    Block,
+    /// Free native balance of the subscriber grew since the last check.
+    Deposit,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Callback subscribed by Move code, see `callback`.
+pub struct CallbackSubscription<Balance> {
+    /// Script executed signed by the subscriber once triggered.
+    pub callback: Vec<u8>,
+    /// Gas limit of the callback, charged to the block budget.
+    pub gas_limit: u64,
+    /// Deposit reserved from the subscriber.
+    pub deposit: Balance,
+    /// Free native balance of the subscriber at the last check of the `Deposit` trigger.
+    pub balance: Balance,
+}
+
+/// Maximum size of the SCALE encoded package metadata supplied by the publisher.
+pub const MAX_PACKAGE_METADATA_SIZE: usize = 4096;
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Package the published package depends on.
+pub struct PackageDependency<AccountId> {
+    /// Account the package is published under.
+    pub account: AccountId,
+    /// Name of the package.
+    pub name: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Package metadata supplied by the publisher, see `publish_package_with_metadata`.
+pub struct PackageInfo<AccountId> {
+    /// Name of the package, unique for the account.
+    pub name: Vec<u8>,
+    /// Version of the package declared in its manifest, e.g. `1.0.0`.
+    pub version: Vec<u8>,
+    /// Packages the package depends on, published before.
+    pub dependencies: Vec<PackageDependency<AccountId>>,
+    /// Digest of the package sources, e.g. SHA-256 of the sources as by `aptos move`.
+    pub source_digest: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Metadata of the published package, as Aptos `code::PackageMetadata`.
+pub struct PackageMetadata<AccountId> {
+    /// Name of the package, unique for the account.
+    pub name: Vec<u8>,
+    /// Version of the package declared in its manifest.
+    pub version: Vec<u8>,
+    /// Number of the package upgrades, 0 once published for the first time.
+    pub upgrade_number: u64,
+    /// Packages the package depends on.
+    pub dependencies: Vec<PackageDependency<AccountId>>,
+    /// Digest of the package sources.
+    pub source_digest: Vec<u8>,
+    /// Names of the package modules.
+    pub modules: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Default, Encode, Decode, Debug, TypeInfo)]
+/// Module bytecode uploaded in chunks before publishing, see `stage_module_chunk`.
+pub struct StagedModule<Balance> {
+    /// Bytecode staged so far.
+    pub bytecode: Vec<u8>,
+    /// Deposit reserved for the staged bytecode.
+    pub deposit: Balance,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Source map attached to the published module and deposit reserved for it.
+pub struct SourceMapEntry<AccountId, Balance> {
+    /// Source map as produced by the Move compiler (BCS encoded `SourceMap`).
+    pub source_map: Vec<u8>,
+    /// Account the deposit is reserved from.
+    pub depositor: AccountId,
+    /// Reserved amount.
+    pub deposit: Balance,
+}
+
+#[derive(Clone, PartialEq, Eq, Default, Encode, Decode, Debug, TypeInfo)]
+/// Counters of the Move VM loader cache within the block.
+pub struct LoaderCacheStats {
+    /// Called entry function modules found in the cache.
+    pub hits: u32,
+    /// Modules loaded from the storage.
+    pub misses: u32,
+    /// Modules verified by the loader or on publishing.
+    pub verifier_runs: u32,
+    /// Cache flushes on republishing or removal of modules.
+    pub flushes: u32,
+}
+
+#[derive(Clone, PartialEq, Eq, Default, Encode, Decode, Debug, TypeInfo)]
+/// Storage used by account resources and deposit reserved for it.
+pub struct StorageDeposit<Balance> {
+    /// Size of resources (keys and values) in bytes.
+    pub bytes: u64,
+    /// Reserved amount.
+    pub amount: Balance,
+}
+
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+/// Versions and features of the Move VM pallet.
+pub struct VmInfo {
+    /// Pallet crate version: major, minor, patch.
+    pub pallet_version: (u16, u8, u8),
+    /// Move VM revision.
+    pub move_vm_revision: Vec<u8>,
+    /// Gas schedule version.
+    pub gas_schedule_version: u32,
+    /// Hash of the standard library modules (published under `0x1`).
+    pub stdlib_hash: [u8; 32],
+    /// Supported features.
+    pub features: Vec<Vec<u8>>,
+}
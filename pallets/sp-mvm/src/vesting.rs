@@ -0,0 +1,160 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Token lockups of the runtime vesting (e.g. `pallet-vesting`) for Move contracts.
+//!
+//! While the script runs, the vesting of every signer (`Config::Lockups`) is stored as
+//! `0x1::Vesting::Lockup` resource under the signer and removed after the script, so framework
+//! `Vesting` module reads it with `borrow_global` and no native is needed:
+//! ```move
+//! struct Schedule { locked: u128, per_block: u128, starting_block: u64 }
+//! struct Lockup { locked: u128, schedules: vector<Schedule> }
+//! ```
+//! `locked` of the lockup is the amount still locked at the current block.
+//!
+//! Move code unlocks vested coins of any account (as `vest_other` does) by emitting
+//! `0x1::Vesting::VestRequest { target: address }` event, the lockup stored for the running
+//! script isn't updated.
+use sp_std::prelude::*;
+use sp_runtime::{DispatchError, DispatchResult};
+use move_core_types::account_address::AccountAddress;
+
+use crate::event::{MessageReader, MoveEventArguments};
+use crate::storage::{core_struct_tag, resource_key};
+
+/// Module of the lockup structs, published under `0x1`.
+pub const VESTING_MODULE: &str = "Vesting";
+/// Name of the lockup struct.
+pub const LOCKUP_STRUCT: &str = "Lockup";
+/// Name of the vest request struct.
+pub const VEST_REQUEST: &str = "VestRequest";
+/// Maximum amount of the stored schedules, fits one byte of ULEB128.
+pub const MAX_SCHEDULES: usize = 127;
+
+/// Vesting schedule of the account.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Schedule {
+    /// Amount locked at the start.
+    pub locked: u128,
+    /// Amount unlocked every block.
+    pub per_block: u128,
+    /// Block unlocking starts at.
+    pub starting_block: u64,
+}
+
+/// Vesting of the account.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Lockup {
+    /// Amount still locked at the current block.
+    pub locked: u128,
+    /// Vesting schedules.
+    pub schedules: Vec<Schedule>,
+}
+
+impl Lockup {
+    /// BCS encoded lockup resource, schedules over `MAX_SCHEDULES` are omitted.
+    pub fn to_resource(&self) -> Vec<u8> {
+        let schedules = self.schedules.len().min(MAX_SCHEDULES);
+        let mut resource = Vec::with_capacity(17 + schedules * 40);
+        resource.extend_from_slice(&self.locked.to_le_bytes());
+        resource.push(schedules as u8);
+        for schedule in &self.schedules[..schedules] {
+            resource.extend_from_slice(&schedule.locked.to_le_bytes());
+            resource.extend_from_slice(&schedule.per_block.to_le_bytes());
+            resource.extend_from_slice(&schedule.starting_block.to_le_bytes());
+        }
+        resource
+    }
+}
+
+/// Runtime vesting of the accounts.
+pub trait Lockups<AccountId> {
+    /// Vesting of the account, `None` if nothing is vested.
+    fn lockup(who: &AccountId) -> Option<Lockup>;
+
+    /// Unlock vested coins of the account.
+    fn vest(who: AccountId) -> DispatchResult;
+}
+
+/// No vesting: `0x1::Vesting::Lockup` is never stored.
+impl<AccountId> Lockups<AccountId> for () {
+    fn lockup(_: &AccountId) -> Option<Lockup> {
+        None
+    }
+
+    fn vest(_: AccountId) -> DispatchResult {
+        Err(DispatchError::Other("Vesting is not supported"))
+    }
+}
+
+/// BCS encoded `0x1::Vesting::Lockup` struct tag.
+pub fn lockup_tag() -> Vec<u8> {
+    core_struct_tag(VESTING_MODULE, LOCKUP_STRUCT)
+}
+
+/// Storage key of the lockup resource of the account.
+pub fn lockup_key(address: &AccountAddress) -> Vec<u8> {
+    resource_key(address, &lockup_tag())
+}
+
+/// Vest request emitted by Move code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct VestRequest {
+    /// Address requested the vest, owner of the event handle.
+    pub address: AccountAddress,
+    /// Address of the account to unlock vested coins of.
+    pub target: AccountAddress,
+}
+
+impl VestRequest {
+    /// Converts Move event into the vest request.
+    ///
+    /// Returns `None` if event isn't `0x1::Vesting::VestRequest` or can't be decoded.
+    pub fn from_event(e: &MoveEventArguments) -> Option<Self> {
+        let address = e.core_request(VESTING_MODULE, VEST_REQUEST)?;
+
+        let mut message = MessageReader::new(&e.message);
+        let target = AccountAddress::from_bytes(message.read_raw(AccountAddress::LENGTH)?).ok()?;
+        if !message.is_empty() {
+            return None;
+        }
+        Some(Self { address, target })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS};
+
+    use super::*;
+
+    fn request_event(address: AccountAddress, message: Vec<u8>) -> MoveEventArguments {
+        MoveEventArguments {
+            guid: [0u64.to_le_bytes().to_vec(), address.to_vec()].concat(),
+            ty_tag: TypeTag::Struct(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(VESTING_MODULE).unwrap(),
+                name: Identifier::new(VEST_REQUEST).unwrap(),
+                type_params: vec![],
+            }),
+            message,
+        }
+    }
+
+    #[test]
+    fn decode_request() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let target = AccountAddress::new([9; AccountAddress::LENGTH]);
+
+        assert_eq!(
+            VestRequest::from_event(&request_event(address, target.to_vec())),
+            Some(VestRequest { address, target })
+        );
+        assert_eq!(
+            VestRequest::from_event(&request_event(address, vec![9; 20])),
+            None
+        );
+    }
+}
@@ -47,6 +47,35 @@ pub trait WeightInfo {
 	fn execute_store_event() -> Weight;
 	fn execute_vec_input() -> Weight;
 	fn execute_loop() -> Weight;
+	fn set_script_allowlist_mode() -> Weight;
+	fn allow_script() -> Weight;
+	fn disallow_script() -> Weight;
+	fn request_account_migration() -> Weight;
+	fn cancel_account_migration() -> Weight;
+	fn migrate_account(r: u32, ) -> Weight;
+	fn set_open_publishing() -> Weight;
+	fn allow_publisher() -> Weight;
+	fn disallow_publisher() -> Weight;
+	fn remove_module() -> Weight;
+	fn propose_multisig_script() -> Weight;
+	fn approve_multisig_script() -> Weight;
+	fn cancel_multisig_script() -> Weight;
+	fn schedule_execute() -> Weight;
+	fn claim_trapped_assets() -> Weight;
+	fn set_max_block_gas() -> Weight;
+	fn set_storage_gas_schedule() -> Weight;
+	fn authorize_session_key(s: u32, ) -> Weight;
+	fn revoke_session_key() -> Weight;
+	fn discard_failed_transact() -> Weight;
+	fn stage_module_chunk(b: u32, ) -> Weight;
+	fn discard_staged_module() -> Weight;
+	fn enact_governance_proposal() -> Weight;
+	fn discard_governance_proposal() -> Weight;
+	fn allow_treasury_spender() -> Weight;
+	fn disallow_treasury_spender() -> Weight;
+	fn remove_callback_subscription() -> Weight;
+	fn on_initialize() -> Weight;
+	fn on_finalize() -> Weight;
 	
 }
 
@@ -60,10 +89,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: Mvm VMStorage (r:1 w:0)
+	// Storage: Mvm SequenceNumbers (r:1 w:1)
 	// Storage: Timestamp Now (r:1 w:0)
 	fn execute() -> Weight {
 		(19_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(1 as Weight))		
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: Mvm VMStorage (r:2 w:1)
 	fn publish_empty_module() -> Weight {
@@ -140,6 +171,211 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		(179_783_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))		
 	}
+	// Storage: Mvm ScriptAllowlistEnabled (r:0 w:1)
+	fn set_script_allowlist_mode() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm AllowedScripts (r:1 w:1)
+	fn allow_script() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm AllowedScripts (r:1 w:1)
+	fn disallow_script() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm MigratedAccounts (r:2 w:0)
+	// Storage: Mvm PendingMigrations (r:1 w:1)
+	fn request_account_migration() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm PendingMigrations (r:1 w:1)
+	fn cancel_account_migration() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm PendingMigrations (r:1 w:1)
+	// Storage: Mvm VMStorage (r:2 w:2)
+	// Storage: Mvm MigratedAccounts (r:0 w:1)
+	fn migrate_account(r: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			// Standard Error: 5_000
+			.saturating_add((9_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: Mvm OpenPublishing (r:0 w:1)
+	fn set_open_publishing() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm AllowedPublishers (r:1 w:1)
+	fn allow_publisher() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm AllowedPublishers (r:1 w:1)
+	fn disallow_publisher() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm ModuleDeposits (r:1 w:1)
+	// Storage: Mvm ModuleDependents (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Mvm VMStorage (r:0 w:1)
+	fn remove_module() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Mvm MultisigScripts (r:1 w:1)
+	// Storage: Mvm MultisigProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn propose_multisig_script() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Mvm MultisigScripts (r:1 w:1)
+	fn approve_multisig_script() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm MultisigScripts (r:1 w:1)
+	// Storage: Mvm MultisigProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_multisig_script() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Mvm ScheduledScripts (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn schedule_execute() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: PolkadotXcm AssetTraps (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	fn claim_trapped_assets() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Mvm MaxBlockGas (r:0 w:1)
+	fn set_max_block_gas() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm StorageGas (r:0 w:1)
+	fn set_storage_gas_schedule() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm SessionKeys (r:0 w:1)
+	fn authorize_session_key(s: u32, ) -> Weight {
+		(16_000_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((300_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm SessionKeys (r:1 w:1)
+	fn revoke_session_key() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm FailedTransacts (r:1 w:1)
+	// Storage: Mvm FailedTransactsCount (r:1 w:1)
+	fn discard_failed_transact() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Mvm AllowedPublishers (r:1 w:0)
+	// Storage: Mvm StagedModules (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn stage_module_chunk(b: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			// Standard Error: 0
+			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Mvm StagedModules (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn discard_staged_module() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Mvm GovernanceProposals (r:1 w:1)
+	fn enact_governance_proposal() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm GovernanceProposals (r:1 w:1)
+	fn discard_governance_proposal() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm TreasurySpenders (r:1 w:1)
+	fn allow_treasury_spender() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm TreasurySpenders (r:1 w:1)
+	// Storage: Mvm TreasurySpent (r:0 w:1)
+	fn disallow_treasury_spender() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Mvm CallbackSubscriptions (r:1 w:1)
+	// Storage: Mvm CallbackSubscriptionsCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove_callback_subscription() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System ParentHash (r:1 w:0)
+	// Storage: ParachainStaking Round (r:1 w:0)
+	// Storage: ParachainStaking SelectedCandidates (r:1 w:0)
+	// Storage: ParachainStaking Total (r:1 w:0)
+	// Storage: Mvm VMStorage (r:3 w:3)
+	// Storage: Mvm BlockGasUsed (r:0 w:1)
+	// Storage: Mvm LoaderStats (r:0 w:1)
+	fn on_initialize() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: Mvm MoveEventsCount (r:1 w:1)
+	// Storage: Mvm MoveWriteSetCount (r:1 w:1)
+	fn on_finalize() -> Weight {
+		(6_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
 	
 }
 
@@ -152,10 +388,12 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	// Storage: Mvm VMStorage (r:1 w:0)
+	// Storage: Mvm SequenceNumbers (r:1 w:1)
 	// Storage: Timestamp Now (r:1 w:0)
 	fn execute() -> Weight {
 		(19_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	// Storage: Mvm VMStorage (r:2 w:1)
 	fn publish_empty_module() -> Weight {
@@ -225,4 +463,174 @@ impl WeightInfo for () {
 		(179_783_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 	}
+	// Storage: Mvm ScriptAllowlistEnabled (r:0 w:1)
+	fn set_script_allowlist_mode() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm AllowedScripts (r:1 w:1)
+	fn allow_script() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm AllowedScripts (r:1 w:1)
+	fn disallow_script() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm MigratedAccounts (r:2 w:0)
+	// Storage: Mvm PendingMigrations (r:1 w:1)
+	fn request_account_migration() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm PendingMigrations (r:1 w:1)
+	fn cancel_account_migration() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm PendingMigrations (r:1 w:1)
+	// Storage: Mvm VMStorage (r:2 w:2)
+	// Storage: Mvm MigratedAccounts (r:0 w:1)
+	fn migrate_account(r: u32, ) -> Weight {
+		(25_000_000 as Weight)
+			// Standard Error: 5_000
+			.saturating_add((9_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(r as Weight)))
+	}
+	// Storage: Mvm OpenPublishing (r:0 w:1)
+	fn set_open_publishing() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm AllowedPublishers (r:1 w:1)
+	fn allow_publisher() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm AllowedPublishers (r:1 w:1)
+	fn disallow_publisher() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm ModuleDeposits (r:1 w:1)
+	// Storage: Mvm ModuleDependents (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Mvm VMStorage (r:0 w:1)
+	fn remove_module() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Mvm MultisigScripts (r:1 w:1)
+	// Storage: Mvm MultisigProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn propose_multisig_script() -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Mvm MultisigScripts (r:1 w:1)
+	fn approve_multisig_script() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Mvm MultisigScripts (r:1 w:1)
+	// Storage: Mvm MultisigProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_multisig_script() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Scheduler Agenda (r:1 w:1)
+	fn schedule_execute() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn claim_trapped_assets() -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_block_gas() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_storage_gas_schedule() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn authorize_session_key(s: u32, ) -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add((300_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_session_key() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn discard_failed_transact() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn stage_module_chunk(b: u32, ) -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn discard_staged_module() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn enact_governance_proposal() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn discard_governance_proposal() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn allow_treasury_spender() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn disallow_treasury_spender() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_callback_subscription() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn on_initialize() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn on_finalize() -> Weight {
+		(6_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
 }
@@ -0,0 +1,183 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Per block Move write-sets for explorers.
+//!
+//! Every storage change made by Move VM is written into the offchain index by block number,
+//! the same way as Move events (see `event::offchain`). Block hash isn't known while the block
+//! is executed, so the pallet offchain worker collects changes and events of the previous
+//! blocks into `BlockWriteSet` stored under `block_write_set_key(block_hash)`, served by
+//! `mvm_getBlockWriteSet` RPC.
+//!
+//! Requires offchain indexing (`--enable-offchain-indexing true`) and offchain workers.
+//! Indexed changes are keyed by block number, so on forks the last imported block wins.
+use sp_std::prelude::*;
+use parity_scale_codec::Decode;
+use parity_scale_codec_derive::{Encode, Decode};
+
+use crate::event::{offchain as events, MoveEventRecord};
+
+/// Storage change made by Move VM.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub struct WriteSetChange {
+    /// Storage key: module or resource access path.
+    pub key: Vec<u8>,
+    /// New value, `None` if the entry is removed.
+    pub value: Option<Vec<u8>>,
+}
+
+/// Move storage changes and events of the block.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, Default)]
+pub struct BlockWriteSet {
+    /// Block number.
+    pub block: u64,
+    /// Storage changes in the order they were written.
+    pub changes: Vec<WriteSetChange>,
+    /// Move events in the order they were emitted.
+    pub events: Vec<MoveEventRecord>,
+}
+
+/// Keys of the Move write-sets in the offchain storage.
+///
+/// Changes are indexed per block: `changes_count_key(block)` contains amount of changes (u32),
+/// each change is stored under `change_key(block, index)` until collected by offchain worker.
+pub mod offchain {
+    use sp_std::prelude::*;
+
+    /// Prefix of the indexed change key.
+    pub const CHANGE_PREFIX: &[u8] = b"mvm::change::";
+    /// Prefix of the indexed changes count key.
+    pub const CHANGES_COUNT_PREFIX: &[u8] = b"mvm::changes_count::";
+    /// Prefix of the collected block write-set key.
+    pub const BLOCK_WRITE_SET_PREFIX: &[u8] = b"mvm::block_write_set::";
+    /// Key of the last block collected by offchain worker (u64).
+    pub const LAST_COLLECTED_KEY: &[u8] = b"mvm::block_write_set_last";
+
+    /// Key of the change with `index` made in `block`.
+    pub fn change_key(block: u64, index: u32) -> Vec<u8> {
+        let mut key = CHANGE_PREFIX.to_vec();
+        key.extend_from_slice(&block.to_be_bytes());
+        key.extend_from_slice(&index.to_be_bytes());
+        key
+    }
+
+    /// Key of the changes count in `block`.
+    pub fn changes_count_key(block: u64) -> Vec<u8> {
+        let mut key = CHANGES_COUNT_PREFIX.to_vec();
+        key.extend_from_slice(&block.to_be_bytes());
+        key
+    }
+
+    /// Key of the write-set of the block with `hash`.
+    pub fn block_write_set_key(hash: &[u8]) -> Vec<u8> {
+        let mut key = BLOCK_WRITE_SET_PREFIX.to_vec();
+        key.extend_from_slice(hash);
+        key
+    }
+}
+
+/// Collects indexed changes and events of the `block` with `get` from the offchain storage.
+///
+/// Missing or malformed entries are skipped.
+pub fn collect_block_write_set<F>(block: u64, mut get: F) -> BlockWriteSet
+where
+    F: FnMut(&[u8]) -> Option<Vec<u8>>,
+{
+    let changes = read_indexed(&mut get, &offchain::changes_count_key(block), |index| {
+        offchain::change_key(block, index)
+    });
+    let events = read_indexed(&mut get, &events::events_count_key(block), |index| {
+        events::event_key(block, index)
+    });
+
+    BlockWriteSet {
+        block,
+        changes,
+        events,
+    }
+}
+
+/// Amount of changes indexed in the `block`.
+pub fn changes_count<F>(block: u64, mut get: F) -> u32
+where
+    F: FnMut(&[u8]) -> Option<Vec<u8>>,
+{
+    read_count(&mut get, &offchain::changes_count_key(block))
+}
+
+/// Reads count stored under `count_key`, zero if missing.
+fn read_count<F>(get: &mut F, count_key: &[u8]) -> u32
+where
+    F: FnMut(&[u8]) -> Option<Vec<u8>>,
+{
+    get(count_key)
+        .and_then(|count| u32::decode(&mut &count[..]).ok())
+        .unwrap_or(0)
+}
+
+/// Reads `count_key` entries with keys built by `key`.
+fn read_indexed<F, K, V>(get: &mut F, count_key: &[u8], key: K) -> Vec<V>
+where
+    F: FnMut(&[u8]) -> Option<Vec<u8>>,
+    K: Fn(u32) -> Vec<u8>,
+    V: Decode,
+{
+    (0..read_count(get, count_key))
+        .filter_map(|index| get(&key(index)))
+        .filter_map(|value| V::decode(&mut &value[..]).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use parity_scale_codec::Encode;
+
+    use super::*;
+
+    #[test]
+    fn collect() {
+        let change = WriteSetChange {
+            key: vec![1, 2, 3],
+            value: Some(vec![4]),
+        };
+        let removed = WriteSetChange {
+            key: vec![5],
+            value: None,
+        };
+        let event = MoveEventRecord {
+            guid: vec![0; 40],
+            ty_tag: b"0x1::Coin::DepositEvent".to_vec(),
+            message: vec![7],
+        };
+
+        let mut storage = BTreeMap::new();
+        storage.insert(offchain::changes_count_key(5), 2u32.encode());
+        storage.insert(offchain::change_key(5, 0), change.encode());
+        storage.insert(offchain::change_key(5, 1), removed.encode());
+        storage.insert(events::events_count_key(5), 1u32.encode());
+        storage.insert(events::event_key(5, 0), event.encode());
+        // Another block.
+        storage.insert(offchain::changes_count_key(6), 1u32.encode());
+        storage.insert(offchain::change_key(6, 0), change.encode());
+
+        let get = |key: &[u8]| storage.get(key).cloned();
+        assert_eq!(changes_count(5, get), 2);
+        assert_eq!(
+            collect_block_write_set(5, get),
+            BlockWriteSet {
+                block: 5,
+                changes: vec![change, removed],
+                events: vec![event],
+            }
+        );
+        assert_eq!(
+            collect_block_write_set(7, get),
+            BlockWriteSet {
+                block: 7,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright 2020-2021 Pontem Foundation LTD.
+// This file is part of Pontem Network.
+// Apache 2.0
+
+//! Cross-chain transfers initiated by Move code.
+//!
+//! Move code requests a transfer by emitting `0x1::XTransfer::TransferRequest` event with
+//! the handle created for the signer, the transfer is sent from the handle owner account by
+//! the runtime (`XcmTransfer`, e.g. with `orml-xtokens`), currency is converted by the runtime
+//! `CurrencyIdConvert`. Coins must be in the native balance of the account, so the framework
+//! module withdraws them from Move before emitting the request.
+//!
+//! Request layout (BCS):
+//! ```move
+//! struct Junction { kind: u8, parachain: u64, key: vector<u8> }
+//! struct Location { parents: u8, junctions: vector<Junction> }
+//! struct TransferRequest { ticker: vector<u8>, amount: u64, dest: Location, dest_weight: u64 }
+//! ```
+//!
+//! Assets of the failed incoming transfers are trapped by the XCM executor under the sender
+//! location. Governance claims them into the account (`AssetClaims`), e.g. the owner of the Move
+//! address the transfer was sent to.
+use sp_std::prelude::*;
+use sp_runtime::{DispatchError, DispatchResult};
+use move_core_types::account_address::AccountAddress;
+use parity_scale_codec_derive::{Encode, Decode};
+use scale_info::TypeInfo;
+
+use crate::event::{MessageReader, MoveEventArguments};
+
+/// Module of the transfer request struct, published under `0x1`.
+pub const XTRANSFER_MODULE: &str = "XTransfer";
+/// Name of the transfer request struct.
+pub const TRANSFER_REQUEST: &str = "TransferRequest";
+/// Maximum amount of the location junctions, the same as in XCM `MultiLocation`.
+pub const MAX_JUNCTIONS: usize = 8;
+
+/// Junction of the destination location.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+pub enum Junction {
+    /// Parachain id.
+    Parachain(u32),
+    /// 32 bytes account id.
+    AccountId32([u8; 32]),
+    /// 20 bytes account key.
+    AccountKey20([u8; 20]),
+    /// General key, e.g. token symbol.
+    GeneralKey(Vec<u8>),
+}
+
+impl Junction {
+    /// Move junction kinds.
+    pub const PARACHAIN: u8 = 0;
+    pub const ACCOUNT_ID_32: u8 = 1;
+    pub const ACCOUNT_KEY_20: u8 = 2;
+    pub const GENERAL_KEY: u8 = 3;
+
+    fn read(message: &mut MessageReader) -> Option<Self> {
+        let kind = message.read_u8()?;
+        let parachain = message.read_u64()?;
+        let key = message.read_bytes()?;
+
+        match kind {
+            Self::PARACHAIN => u32::try_from(parachain).ok().map(Self::Parachain),
+            Self::ACCOUNT_ID_32 => key.try_into().ok().map(Self::AccountId32),
+            Self::ACCOUNT_KEY_20 => key.try_into().ok().map(Self::AccountKey20),
+            Self::GENERAL_KEY => Some(Self::GeneralKey(key)),
+            _ => None,
+        }
+    }
+}
+
+/// Destination location, converted to XCM `MultiLocation` by the runtime.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo)]
+pub struct Location {
+    /// Amount of parent hops, e.g. 1 for the relay chain.
+    pub parents: u8,
+    /// Interior junctions.
+    pub junctions: Vec<Junction>,
+}
+
+/// Sends cross-chain transfers on behalf of the account.
+pub trait XcmTransfer<AccountId> {
+    /// Transfer `amount` of the currency with Move `ticker` from `who` to `dest`.
+    fn transfer(
+        who: AccountId,
+        ticker: Vec<u8>,
+        amount: u64,
+        dest: Location,
+        dest_weight: u64,
+    ) -> DispatchResult;
+}
+
+/// Cross-chain transfers are not supported.
+impl<AccountId> XcmTransfer<AccountId> for () {
+    fn transfer(_: AccountId, _: Vec<u8>, _: u64, _: Location, _: u64) -> DispatchResult {
+        Err(DispatchError::Other("Cross-chain transfers are not supported"))
+    }
+}
+
+/// Claims assets trapped by the XCM executor.
+pub trait AssetClaims<AccountId> {
+    /// Claim `amount` of the currency with Move `ticker` trapped under `origin` to `beneficiary`.
+    fn claim(origin: Location, ticker: Vec<u8>, amount: u128, beneficiary: AccountId)
+        -> DispatchResult;
+}
+
+/// Claims of the trapped assets are not supported.
+impl<AccountId> AssetClaims<AccountId> for () {
+    fn claim(_: Location, _: Vec<u8>, _: u128, _: AccountId) -> DispatchResult {
+        Err(DispatchError::Other("Claims of the trapped assets are not supported"))
+    }
+}
+
+/// Transfer request emitted by Move code.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TransferRequest {
+    /// Sender address, owner of the event handle.
+    pub address: AccountAddress,
+    /// Ticker of the currency.
+    pub ticker: Vec<u8>,
+    /// Amount to transfer.
+    pub amount: u64,
+    /// Destination, including the beneficiary.
+    pub dest: Location,
+    /// Weight to pay for the execution on the destination.
+    pub dest_weight: u64,
+}
+
+impl TransferRequest {
+    /// Converts Move event into the transfer request.
+    ///
+    /// Returns `None` if event isn't `0x1::XTransfer::TransferRequest` or can't be decoded.
+    pub fn from_event(e: &MoveEventArguments) -> Option<Self> {
+        let address = e.core_request(XTRANSFER_MODULE, TRANSFER_REQUEST)?;
+
+        let mut message = MessageReader::new(&e.message);
+        let ticker = message.read_bytes()?;
+        let amount = message.read_u64()?;
+        let parents = message.read_u8()?;
+        let len = message.read_len()?;
+        if len > MAX_JUNCTIONS {
+            return None;
+        }
+        let junctions = (0..len)
+            .map(|_| Junction::read(&mut message))
+            .collect::<Option<Vec<_>>>()?;
+        let dest_weight = message.read_u64()?;
+        if !message.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            address,
+            ticker,
+            amount,
+            dest: Location { parents, junctions },
+            dest_weight,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use move_core_types::identifier::Identifier;
+    use move_core_types::language_storage::{StructTag, TypeTag, CORE_CODE_ADDRESS};
+
+    use super::*;
+
+    fn request_event(address: AccountAddress, message: Vec<u8>) -> MoveEventArguments {
+        MoveEventArguments {
+            guid: [0u64.to_le_bytes().to_vec(), address.to_vec()].concat(),
+            ty_tag: TypeTag::Struct(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new(XTRANSFER_MODULE).unwrap(),
+                name: Identifier::new(TRANSFER_REQUEST).unwrap(),
+                type_params: vec![],
+            }),
+            message,
+        }
+    }
+
+    fn junction(kind: u8, parachain: u64, key: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![kind];
+        bytes.extend_from_slice(&parachain.to_le_bytes());
+        bytes.push(key.len() as u8);
+        bytes.extend_from_slice(key);
+        bytes
+    }
+
+    fn request_message(junctions: &[Vec<u8>]) -> Vec<u8> {
+        let mut message = vec![3];
+        message.extend_from_slice(b"KSM");
+        message.extend_from_slice(&1000u64.to_le_bytes());
+        message.push(1);
+        message.push(junctions.len() as u8);
+        for junction in junctions {
+            message.extend_from_slice(junction);
+        }
+        message.extend_from_slice(&5000u64.to_le_bytes());
+        message
+    }
+
+    #[test]
+    fn decode_request() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+        let message = request_message(&[
+            junction(Junction::PARACHAIN, 2000, &[]),
+            junction(Junction::ACCOUNT_ID_32, 0, &[9; 32]),
+        ]);
+
+        assert_eq!(
+            TransferRequest::from_event(&request_event(address, message)),
+            Some(TransferRequest {
+                address,
+                ticker: b"KSM".to_vec(),
+                amount: 1000,
+                dest: Location {
+                    parents: 1,
+                    junctions: vec![Junction::Parachain(2000), Junction::AccountId32([9; 32])],
+                },
+                dest_weight: 5000,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_junction() {
+        let address = AccountAddress::new([7; AccountAddress::LENGTH]);
+
+        let message = request_message(&[junction(Junction::ACCOUNT_ID_32, 0, &[9; 20])]);
+        assert_eq!(TransferRequest::from_event(&request_event(address, message)), None);
+
+        let message = request_message(&[junction(42, 0, &[])]);
+        assert_eq!(TransferRequest::from_event(&request_event(address, message)), None);
+    }
+}
@@ -0,0 +1,106 @@
+/// Tests related to raw scripts allowlist.
+use std::convert::TryFrom;
+use frame_support::{assert_noop, assert_ok};
+use frame_support::assert_err_ignore_postinfo;
+use move_vm::types::{Call, Transaction};
+use sp_runtime::DispatchError;
+use sp_mvm::types::{script_hash, ScriptHash, ScriptMetadata};
+use sp_mvm::Error;
+
+mod common;
+use common::assets::{modules, transactions, Asset};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Returns hash of the script code packed into transaction asset.
+fn tx_script_hash(tx: &Asset) -> ScriptHash {
+    let tx = Transaction::try_from(tx.bytes())
+        .unwrap()
+        .into_script(vec![origin_move_addr()])
+        .unwrap();
+    match tx.call() {
+        Call::Script { code } => script_hash(code),
+        _ => panic!("Transaction should contain raw script"),
+    }
+}
+
+#[test]
+/// Only `UpdateOrigin` can manage the allowlist.
+fn manage_allowlist_requires_update_origin() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = Origin::signed(bob_public_key());
+
+        assert_noop!(
+            Mvm::set_script_allowlist_mode(origin.clone(), true),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Mvm::allow_script(origin.clone(), [0; 32], None, None),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Mvm::disallow_script(origin, [0; 32]),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+/// Add and remove script hash, check metadata returned by getter.
+fn allow_and_disallow_script() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let hash = [1; 32];
+        let metadata = ScriptMetadata {
+            description: Some(b"store u64".to_vec()),
+            proposer: Some(bob_public_key()),
+        };
+
+        assert_ok!(Mvm::allow_script(
+            Origin::root(),
+            hash,
+            metadata.description.clone(),
+            metadata.proposer
+        ));
+        assert_eq!(Mvm::get_allowed_scripts(), vec![(hash, metadata)]);
+
+        assert_noop!(
+            Mvm::allow_script(Origin::root(), hash, None, None),
+            Error::<Test>::ScriptAlreadyAllowed
+        );
+
+        assert_ok!(Mvm::disallow_script(Origin::root(), hash));
+        assert!(Mvm::get_allowed_scripts().is_empty());
+
+        assert_noop!(
+            Mvm::disallow_script(Origin::root(), hash),
+            Error::<Test>::UnknownScript
+        );
+    });
+}
+
+#[test]
+/// Once allowlist mode is enabled, only allowlisted scripts can be executed.
+fn execute_with_allowlist_mode() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = bob_public_key();
+        utils::publish_module(origin, &modules::user::STORE, None).unwrap();
+
+        assert_ok!(Mvm::set_script_allowlist_mode(Origin::root(), true));
+        assert_err_ignore_postinfo!(
+            utils::execute_tx(origin, &transactions::STORE_U64, None),
+            Error::<Test>::UnknownScript
+        );
+
+        assert_ok!(Mvm::allow_script(
+            Origin::root(),
+            tx_script_hash(&transactions::STORE_U64),
+            None,
+            None
+        ));
+        utils::execute_tx(origin, &transactions::STORE_U64, None).unwrap();
+
+        assert_ok!(Mvm::set_script_allowlist_mode(Origin::root(), false));
+        utils::execute_tx(origin, &transactions::ONE_SIGNER_USER, None).unwrap();
+    });
+}
@@ -14,6 +14,12 @@ git checkout release-v1.0.0
 dove build -b
 popd
 
+# Mock framework for fast tests
+pushd ./mock-framework
+dove clean
+dove build -b
+popd
+
 pushd ./user
 dove clean
 dove build
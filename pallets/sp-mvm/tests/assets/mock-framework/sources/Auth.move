@@ -0,0 +1,67 @@
+/// Public key signed the transaction, stored by the Move VM pallet while the script runs.
+module Std::Auth {
+    use Std::Signer;
+
+    const E_NO_SENDER_KEY: u64 = 1;
+    const E_NOT_AUTHORIZED: u64 = 2;
+
+    struct SenderKey has key {
+        scheme: u8,
+        public_key: vector<u8>,
+    }
+
+    /// Key the account authorizes instead of any key of the address.
+    struct AuthKey has key {
+        scheme: u8,
+        public_key: vector<u8>,
+    }
+
+    /// Whether the transaction of the account is signed with a known key.
+    public fun has_sender_key(account: &signer): bool {
+        exists<SenderKey>(Signer::address_of(account))
+    }
+
+    /// Signature scheme of the transaction: 0 - sr25519, 1 - ed25519, 2 - ecdsa.
+    public fun sender_scheme(account: &signer): u8 acquires SenderKey {
+        let addr = Signer::address_of(account);
+        assert!(exists<SenderKey>(addr), E_NO_SENDER_KEY);
+        borrow_global<SenderKey>(addr).scheme
+    }
+
+    /// Public key signed the transaction.
+    public fun sender_public_key(account: &signer): vector<u8> acquires SenderKey {
+        let addr = Signer::address_of(account);
+        assert!(exists<SenderKey>(addr), E_NO_SENDER_KEY);
+        *&borrow_global<SenderKey>(addr).public_key
+    }
+
+    /// Aborts unless the transaction is signed with the authorized key of the account,
+    /// accounts without authorized keys accept any key.
+    public fun assert_authorized(account: &signer) acquires SenderKey, AuthKey {
+        let addr = Signer::address_of(account);
+        if (!exists<AuthKey>(addr)) {
+            return
+        };
+        assert!(exists<SenderKey>(addr), E_NO_SENDER_KEY);
+        let sender = borrow_global<SenderKey>(addr);
+        let auth = borrow_global<AuthKey>(addr);
+        assert!(
+            sender.scheme == auth.scheme && *&sender.public_key == *&auth.public_key,
+            E_NOT_AUTHORIZED
+        );
+    }
+
+    /// Authorizes the new key of the account, signed with the current authorized key.
+    public fun rotate_key(account: &signer, scheme: u8, public_key: vector<u8>)
+    acquires SenderKey, AuthKey {
+        assert_authorized(account);
+        let addr = Signer::address_of(account);
+        if (exists<AuthKey>(addr)) {
+            let auth = borrow_global_mut<AuthKey>(addr);
+            auth.scheme = scheme;
+            auth.public_key = public_key;
+        } else {
+            move_to(account, AuthKey { scheme, public_key });
+        }
+    }
+}
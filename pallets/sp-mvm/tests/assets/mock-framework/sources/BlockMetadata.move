@@ -0,0 +1,12 @@
+/// Block metadata, stored by the Move VM pallet every block.
+module Std::BlockMetadata {
+    struct ParentHash has key {
+        hash: vector<u8>,
+        block: u64,
+    }
+
+    /// Hash of the parent block.
+    public fun parent_hash(): vector<u8> acquires ParentHash {
+        *&borrow_global<ParentHash>(@Std).hash
+    }
+}
@@ -0,0 +1,58 @@
+/// Tiny coin without supply tracking, anyone can mint.
+module Std::Coin {
+    use Std::Event::{Self, EventHandle};
+    use Std::Signer;
+
+    const ERR_INSUFFICIENT_BALANCE: u64 = 1;
+
+    struct Coin<phantom T> has store { value: u64 }
+
+    struct Balance<phantom T> has key {
+        coin: Coin<T>,
+        transfers: EventHandle<TransferEvent>,
+    }
+
+    struct TransferEvent has drop, store {
+        from: address,
+        to: address,
+        amount: u64,
+    }
+
+    public fun register<T>(account: &signer) {
+        move_to(account, Balance<T> {
+            coin: Coin { value: 0 },
+            transfers: Event::new_event_handle<TransferEvent>(account),
+        });
+    }
+
+    public fun mint<T>(account: &signer, amount: u64) acquires Balance {
+        let addr = Signer::address_of(account);
+        if (!exists<Balance<T>>(addr)) {
+            register<T>(account);
+        };
+        let balance = borrow_global_mut<Balance<T>>(addr);
+        balance.coin.value = balance.coin.value + amount;
+    }
+
+    public fun transfer<T>(from: &signer, to: address, amount: u64) acquires Balance {
+        let from_addr = Signer::address_of(from);
+        let from_balance = borrow_global_mut<Balance<T>>(from_addr);
+        assert!(from_balance.coin.value >= amount, ERR_INSUFFICIENT_BALANCE);
+        from_balance.coin.value = from_balance.coin.value - amount;
+        Event::emit_event(
+            &mut from_balance.transfers,
+            TransferEvent { from: from_addr, to, amount },
+        );
+
+        let to_balance = borrow_global_mut<Balance<T>>(to);
+        to_balance.coin.value = to_balance.coin.value + amount;
+    }
+
+    public fun balance<T>(addr: address): u64 acquires Balance {
+        if (exists<Balance<T>>(addr)) {
+            borrow_global<Balance<T>>(addr).coin.value
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,24 @@
+/// Minimal events: handle counts emitted events and writes them to the event store.
+module Std::Event {
+    use Std::Signer;
+
+    struct EventHandle<phantom T: drop + store> has store {
+        counter: u64,
+        guid: address,
+    }
+
+    public fun new_event_handle<T: drop + store>(account: &signer): EventHandle<T> {
+        EventHandle<T> { counter: 0, guid: Signer::address_of(account) }
+    }
+
+    public fun emit_event<T: drop + store>(handle: &mut EventHandle<T>, msg: T) {
+        write_to_event_store<T>(handle.guid, handle.counter, msg);
+        handle.counter = handle.counter + 1;
+    }
+
+    public fun destroy_handle<T: drop + store>(handle: EventHandle<T>) {
+        EventHandle<T> { counter: _, guid: _ } = handle;
+    }
+
+    native fun write_to_event_store<T: drop + store>(guid: address, count: u64, msg: T);
+}
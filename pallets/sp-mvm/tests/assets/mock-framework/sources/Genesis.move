@@ -0,0 +1,4 @@
+/// Genesis of the mock framework, nothing to initialize.
+module Std::Genesis {
+    public fun initialize() {}
+}
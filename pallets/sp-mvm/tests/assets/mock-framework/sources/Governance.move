@@ -0,0 +1,62 @@
+/// Runtime governance proposals: the Move VM pallet submits the request of the proposer.
+module Std::Governance {
+    use Std::Event;
+    use Std::Signer;
+    use Std::Vector;
+
+    const E_NOT_STD: u64 = 1;
+    const E_NOT_PROPOSER: u64 = 2;
+
+    /// Addresses holding the proposer capability, granted by `0x1`.
+    struct Proposers has key {
+        addresses: vector<address>,
+    }
+
+    struct ProposalRequest has drop, store {
+        call: vector<u8>,
+        deposit: u128,
+        callback: vector<u8>,
+        callback_gas: u64,
+    }
+
+    /// Grant the proposer capability to the address.
+    public fun add_proposer(std: &signer, proposer: address) acquires Proposers {
+        assert!(Signer::address_of(std) == @Std, E_NOT_STD);
+        if (!exists<Proposers>(@Std)) {
+            move_to(std, Proposers { addresses: Vector::empty<address>() });
+        };
+        Vector::push_back(&mut borrow_global_mut<Proposers>(@Std).addresses, proposer);
+    }
+
+    /// Whether the address holds the proposer capability.
+    public fun is_proposer(addr: address): bool acquires Proposers {
+        if (!exists<Proposers>(@Std)) {
+            return false
+        };
+        let addresses = &borrow_global<Proposers>(@Std).addresses;
+        let i = 0;
+        let len = Vector::length(addresses);
+        while (i < len) {
+            if (*Vector::borrow(addresses, i) == addr) {
+                return true
+            };
+            i = i + 1;
+        };
+        false
+    }
+
+    /// Propose SCALE encoded runtime `call`, `callback` script is executed signed by
+    /// the proposer once the proposal is enacted.
+    public fun propose(
+        account: &signer,
+        call: vector<u8>,
+        deposit: u128,
+        callback: vector<u8>,
+        callback_gas: u64,
+    ) acquires Proposers {
+        assert!(is_proposer(Signer::address_of(account)), E_NOT_PROPOSER);
+        let handle = Event::new_event_handle<ProposalRequest>(account);
+        Event::emit_event(&mut handle, ProposalRequest { call, deposit, callback, callback_gas });
+        Event::destroy_handle(handle);
+    }
+}
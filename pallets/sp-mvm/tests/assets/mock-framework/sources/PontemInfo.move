@@ -0,0 +1,23 @@
+/// Chain info, stored by the Move VM pallet every parachain block.
+module Std::PontemInfo {
+    struct ChainInfo has key {
+        parachain_id: u64,
+        relay_block: u64,
+        chain_name: vector<u8>,
+    }
+
+    /// Parachain id of the chain.
+    public fun parachain_id(): u64 acquires ChainInfo {
+        borrow_global<ChainInfo>(@Std).parachain_id
+    }
+
+    /// Number of the relay chain block the current block is built on.
+    public fun relay_block_number(): u64 acquires ChainInfo {
+        borrow_global<ChainInfo>(@Std).relay_block
+    }
+
+    /// Name of the chain, e.g. `nox_mainnet`.
+    public fun chain_name(): vector<u8> acquires ChainInfo {
+        *&borrow_global<ChainInfo>(@Std).chain_name
+    }
+}
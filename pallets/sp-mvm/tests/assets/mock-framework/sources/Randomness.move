@@ -0,0 +1,12 @@
+/// Randomness seed, stored by the Move VM pallet every block.
+module Std::Randomness {
+    struct Seed has key {
+        seed: vector<u8>,
+        block: u64,
+    }
+
+    /// Seed of the current block, the same for all calls within the block.
+    public fun seed(): vector<u8> acquires Seed {
+        *&borrow_global<Seed>(@Std).seed
+    }
+}
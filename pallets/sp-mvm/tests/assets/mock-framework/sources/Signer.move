@@ -0,0 +1,8 @@
+/// Signer natives, the same as in Move stdlib.
+module Std::Signer {
+    native public fun borrow_address(s: &signer): &address;
+
+    public fun address_of(s: &signer): address {
+        *borrow_address(s)
+    }
+}
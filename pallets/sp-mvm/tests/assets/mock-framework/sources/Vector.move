@@ -0,0 +1,10 @@
+/// Vector natives used by the mock framework.
+module Std::Vector {
+    native public fun empty<Element>(): vector<Element>;
+
+    native public fun length<Element>(v: &vector<Element>): u64;
+
+    native public fun borrow<Element>(v: &vector<Element>, i: u64): &Element;
+
+    native public fun push_back<Element>(v: &mut vector<Element>, e: Element);
+}
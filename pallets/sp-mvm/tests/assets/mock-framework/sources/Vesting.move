@@ -0,0 +1,36 @@
+/// Token lockups, stored by the Move VM pallet under the signers while the script runs.
+module Std::Vesting {
+    use Std::Event;
+    use Std::Signer;
+
+    struct Schedule has store {
+        locked: u128,
+        per_block: u128,
+        starting_block: u64,
+    }
+
+    struct Lockup has key {
+        locked: u128,
+        schedules: vector<Schedule>,
+    }
+
+    struct VestRequest has drop, store {
+        target: address,
+    }
+
+    /// Amount of the account coins still locked at the current block.
+    public fun locked(account: &signer): u128 acquires Lockup {
+        let addr = Signer::address_of(account);
+        if (!exists<Lockup>(addr)) {
+            return 0
+        };
+        borrow_global<Lockup>(addr).locked
+    }
+
+    /// Unlock vested coins of `target`, the Move VM pallet does it after the request.
+    public fun vest(account: &signer, target: address) {
+        let handle = Event::new_event_handle<VestRequest>(account);
+        Event::emit_event(&mut handle, VestRequest { target });
+        Event::destroy_handle(handle);
+    }
+}
@@ -0,0 +1,8 @@
+/// Module depending on `Store`, used to test module dependents.
+module UserTests::StoreProxy {
+    use UserTests::Store;
+
+    public fun store_u64(account: &signer, val: u64) {
+        Store::store_u64(account, val);
+    }
+}
@@ -0,0 +1,63 @@
+/// Tests related to the sender keys stored for Move code.
+use serde::Deserialize;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use sp_mvm::auth::{self, SenderKey};
+use sp_mvm::crypto::SignatureScheme;
+use sp_mvm::VMStorage;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct MoveSenderKey {
+    scheme: u8,
+    public_key: Vec<u8>,
+}
+
+fn sender_key_tag() -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(auth::AUTH_MODULE).unwrap(),
+        name: Identifier::new(auth::SENDER_KEY_STRUCT).unwrap(),
+        type_params: vec![],
+    }
+}
+
+#[test]
+/// Struct tag of the sender key is encoded the same way as by Move VM.
+fn sender_key_tag_encoding() {
+    assert_eq!(
+        auth::sender_key_tag(),
+        bcs::to_bytes(&sender_key_tag()).unwrap()
+    );
+}
+
+#[test]
+/// Sender key resource is BCS encoded `0x1::Auth::SenderKey`.
+fn sender_key_resource_encoding() {
+    let key = SenderKey::new(SignatureScheme::Ecdsa, &[7; 33]);
+    let resource: MoveSenderKey = bcs::from_bytes(&key.to_resource()).unwrap();
+    assert_eq!(
+        resource,
+        MoveSenderKey {
+            scheme: 2,
+            public_key: vec![7; 33],
+        }
+    );
+}
+
+#[test]
+/// Sender key is removed after the script.
+fn sender_key_removed_after_script() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        utils::execute_tx(bob_public_key(), &transactions::STORE_U64, None).unwrap();
+
+        let key = auth::sender_key_key(&to_move_addr(bob_public_key()));
+        assert!(!VMStorage::<Test>::contains_key(key));
+    });
+}
@@ -624,3 +624,73 @@ mod adapter {
         test_token_add_with(&adapter);
     }
 }
+
+mod currency {
+    use sp_mvm::balance::BalancesAdapter;
+    use sp_mvm::currency::{self, NativeError};
+
+    use super::*;
+    use test_log::test;
+
+    pub type AccountId = <Test as frame_system::Config>::AccountId;
+
+    fn pallet_account() -> AccountId {
+        BalancesAdapter::<AccountId, Currencies, CurrencyId>::new(MVMPalletId::get())
+            .get_pallet_account()
+    }
+
+    #[test]
+    fn withdraw_deposit() {
+        RuntimeBuilder::new().build().execute_with(|| {
+            let bob = bob_public_key();
+            let currency_id = CurrencyId::KSM;
+            let ticker = currency_id.symbol();
+            assert_ok!(orml_tokens::Pallet::<Test>::deposit(currency_id, &bob, 5000));
+            let total_issuance = Currencies::total_issuance(currency_id);
+
+            assert_ok!(currency::withdraw::<AccountId, Currencies>(
+                &pallet_account(),
+                &to_move_addr(bob),
+                ticker.as_ref(),
+                2000,
+            ));
+            assert_eq!(Currencies::free_balance(currency_id, &bob), 3000);
+            assert_eq!(Currencies::free_balance(currency_id, &pallet_account()), 2000);
+
+            assert_ok!(currency::deposit::<AccountId, Currencies>(
+                &pallet_account(),
+                &to_move_addr(bob),
+                ticker.as_ref(),
+                500,
+            ));
+            assert_eq!(Currencies::free_balance(currency_id, &bob), 3500);
+            assert_eq!(Currencies::total_issuance(currency_id), total_issuance);
+        });
+    }
+
+    #[test]
+    fn withdraw_fails() {
+        RuntimeBuilder::new().build().execute_with(|| {
+            let bob = to_move_addr(bob_public_key());
+
+            assert_eq!(
+                currency::withdraw::<AccountId, Currencies>(
+                    &pallet_account(),
+                    &bob,
+                    b"UNKNOWN",
+                    1
+                ),
+                Err(NativeError::UnknownTicker)
+            );
+            assert!(matches!(
+                currency::withdraw::<AccountId, Currencies>(
+                    &pallet_account(),
+                    &bob,
+                    CurrencyId::KSM.symbol().as_ref(),
+                    1
+                ),
+                Err(NativeError::Currency(_))
+            ));
+        });
+    }
+}
@@ -0,0 +1,56 @@
+/// Tests related to the block metadata for Move contracts.
+use serde::Deserialize;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use sp_core::H256;
+use frame_support::traits::Hooks;
+use sp_mvm::block;
+
+mod common;
+use common::mock::*;
+use common::utils;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct ParentHash {
+    hash: Vec<u8>,
+    block: u64,
+}
+
+fn parent_hash_tag() -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(block::BLOCK_METADATA_MODULE).unwrap(),
+        name: Identifier::new(block::PARENT_HASH_STRUCT).unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn current_parent_hash() -> ParentHash {
+    ParentHash {
+        hash: Sys::parent_hash().as_ref().to_vec(),
+        block: Sys::block_number(),
+    }
+}
+
+#[test]
+/// Struct tag of the parent hash is encoded the same way as by Move VM.
+fn parent_hash_tag_encoding() {
+    assert_eq!(block::parent_hash_tag(), bcs::to_bytes(&parent_hash_tag()).unwrap());
+}
+
+#[test]
+/// Parent hash is stored on block initialization.
+fn parent_hash_stored_every_block() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        utils::check_storage_res(CORE_CODE_ADDRESS, parent_hash_tag(), current_parent_hash());
+
+        Sys::set_parent_hash(H256::repeat_byte(7));
+        Mvm::on_initialize(Sys::block_number());
+        let expected = ParentHash {
+            hash: vec![7; 32],
+            block: Sys::block_number(),
+        };
+        utils::check_storage_res(CORE_CODE_ADDRESS, parent_hash_tag(), expected);
+    });
+}
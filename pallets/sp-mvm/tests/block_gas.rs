@@ -0,0 +1,70 @@
+/// Tests related to the per-block Move gas ceiling.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::assert_err_ignore_postinfo;
+use sp_runtime::DispatchError;
+use sp_mvm::{Error, Event as MvmEvent};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the calls in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+#[test]
+/// Only `UpdateOrigin` can set the block gas ceiling.
+fn set_max_block_gas_requires_update_origin() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        assert_noop!(
+            Mvm::set_max_block_gas(Origin::signed(bob_public_key()), Some(GAS_LIMIT)),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Mvm::set_max_block_gas(Origin::root(), Some(GAS_LIMIT)));
+        assert_eq!(Mvm::max_block_gas(), Some(GAS_LIMIT));
+        assert_eq!(last_event(), Event::Mvm(MvmEvent::MaxBlockGasSet(Some(GAS_LIMIT))));
+
+        assert_ok!(Mvm::set_max_block_gas(Origin::root(), None));
+        assert_eq!(Mvm::max_block_gas(), None);
+    });
+}
+
+#[test]
+/// Gas used by calls is accounted within the block and reset in the next one.
+fn block_gas_used() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        assert_eq!(Mvm::block_gas_used(), 0);
+
+        utils::publish_module(bob_public_key(), &modules::user::STORE, Some(GAS_LIMIT)).unwrap();
+        let published = Mvm::block_gas_used();
+        assert!(published > 0);
+
+        utils::execute_tx(bob_public_key(), &transactions::STORE_U64, Some(GAS_LIMIT)).unwrap();
+        assert!(Mvm::block_gas_used() > published);
+
+        roll_next_block();
+        assert_eq!(Mvm::block_gas_used(), 0);
+    });
+}
+
+#[test]
+/// Calls which gas limit doesn't fit into the rest of the block gas fail.
+fn block_gas_limit_exceeded() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        assert_ok!(Mvm::set_max_block_gas(Origin::root(), Some(GAS_LIMIT + 1)));
+
+        utils::publish_module(bob_public_key(), &modules::user::STORE, Some(GAS_LIMIT)).unwrap();
+        assert_err_ignore_postinfo!(
+            utils::execute_tx(bob_public_key(), &transactions::STORE_U64, Some(GAS_LIMIT)),
+            Error::<Test>::BlockGasLimitExceeded
+        );
+
+        roll_next_block();
+        utils::execute_tx(bob_public_key(), &transactions::STORE_U64, Some(GAS_LIMIT)).unwrap();
+    });
+}
@@ -0,0 +1,200 @@
+/// Tests related to the callbacks subscribed by Move code.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::traits::{Currency, ReservableCurrency};
+use sp_mvm::types::{CallbackSubscription, CallbackTrigger};
+use sp_mvm::{CallbackSubscriptions, CallbackSubscriptionsCount, Error, Event as MvmEvent};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the callbacks in tests, only one fits the block budget.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Subscribes `STORE_U64` callback of the account, as requested by Move code.
+fn subscribe(who: AccountId, trigger: CallbackTrigger) {
+    let deposit = CallbackDeposit::get();
+    Balances::reserve(&who, deposit).unwrap();
+    CallbackSubscriptions::<Test>::insert(
+        who,
+        trigger,
+        CallbackSubscription {
+            callback: transactions::STORE_U64.bytes().to_vec(),
+            gas_limit: GAS_LIMIT,
+            deposit,
+            balance: Balances::free_balance(who),
+        },
+    );
+    CallbackSubscriptionsCount::<Test>::mutate(|count| *count += 1);
+}
+
+/// Callback executions of the current block.
+fn executed_callbacks() -> Vec<(AccountId, CallbackTrigger, bool)> {
+    Sys::events()
+        .into_iter()
+        .filter_map(|rec| match rec.event {
+            Event::Mvm(MvmEvent::CallbackExecuted(who, trigger, succeeded)) => {
+                Some((who, trigger, succeeded))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn next_block() {
+    Sys::reset_events();
+    roll_next_block();
+}
+
+#[test]
+/// Block callback is executed signed by the subscriber every block.
+fn block_callback() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        subscribe(bob_public_key(), CallbackTrigger::Block);
+
+        next_block();
+        assert_eq!(
+            executed_callbacks(),
+            vec![(bob_public_key(), CallbackTrigger::Block, true)]
+        );
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 1);
+
+        // The value is already stored, so the callback fails now.
+        next_block();
+        assert_eq!(
+            executed_callbacks(),
+            vec![(bob_public_key(), CallbackTrigger::Block, false)]
+        );
+    });
+}
+
+#[test]
+/// Deposit callback is executed once the balance of the subscriber grows.
+fn deposit_callback() {
+    RuntimeBuilder::new()
+        .set_balances(vec![(bob_public_key(), CurrencyId::NATIVE, UNIT)])
+        .build()
+        .execute_with(|| {
+            roll_next_block();
+            utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+            subscribe(bob_public_key(), CallbackTrigger::Deposit);
+
+            next_block();
+            assert!(executed_callbacks().is_empty());
+
+            Balances::make_free_balance_be(&bob_public_key(), 2 * UNIT);
+            next_block();
+            assert_eq!(
+                executed_callbacks(),
+                vec![(bob_public_key(), CallbackTrigger::Deposit, true)]
+            );
+
+            // The deposit is noticed once.
+            next_block();
+            assert!(executed_callbacks().is_empty());
+        });
+}
+
+#[test]
+/// Callbacks not fitting the block gas budget are skipped, failed ones are charged too.
+fn callback_gas_budget() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        subscribe(bob_public_key(), CallbackTrigger::Block);
+        subscribe(alice_public_key(), CallbackTrigger::Block);
+
+        next_block();
+        assert_eq!(executed_callbacks().len(), 1);
+    });
+}
+
+#[test]
+/// Subscriptions are removed by their subscriber or governance.
+fn remove_subscription() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        subscribe(bob_public_key(), CallbackTrigger::Block);
+        subscribe(bob_public_key(), CallbackTrigger::Deposit);
+
+        assert_noop!(
+            Mvm::remove_callback_subscription(
+                Origin::signed(alice_public_key()),
+                bob_public_key(),
+                CallbackTrigger::Block
+            ),
+            Error::<Test>::NotCallbackSubscriber
+        );
+        assert_ok!(Mvm::remove_callback_subscription(
+            Origin::signed(bob_public_key()),
+            bob_public_key(),
+            CallbackTrigger::Block
+        ));
+        assert_eq!(
+            last_event(),
+            Event::Mvm(MvmEvent::CallbackUnsubscribed(
+                bob_public_key(),
+                CallbackTrigger::Block
+            ))
+        );
+        assert_ok!(Mvm::remove_callback_subscription(
+            Origin::root(),
+            bob_public_key(),
+            CallbackTrigger::Deposit
+        ));
+        assert_eq!(CallbackSubscriptionsCount::<Test>::get(), 0);
+
+        assert_noop!(
+            Mvm::remove_callback_subscription(
+                Origin::root(),
+                bob_public_key(),
+                CallbackTrigger::Deposit
+            ),
+            Error::<Test>::UnknownCallbackSubscription
+        );
+    });
+}
+
+#[test]
+/// Subscribers pay the gas limit of their callbacks, the deposit is released on removal.
+fn callback_fee_and_deposit() {
+    CallbackDeposit::set(1_000);
+    CallbackGasUnitPrice::set(2);
+    RuntimeBuilder::new()
+        .set_balances(vec![
+            (bob_public_key(), CurrencyId::NATIVE, UNIT),
+            (alice_public_key(), CurrencyId::NATIVE, 1_500),
+        ])
+        .build()
+        .execute_with(|| {
+            roll_next_block();
+            utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+            subscribe(bob_public_key(), CallbackTrigger::Block);
+            // Alice can't pay the fee.
+            subscribe(alice_public_key(), CallbackTrigger::Block);
+            assert_eq!(Balances::reserved_balance(bob_public_key()), 1_000);
+
+            next_block();
+            assert_eq!(
+                executed_callbacks(),
+                vec![(bob_public_key(), CallbackTrigger::Block, true)]
+            );
+            assert_eq!(
+                Balances::free_balance(bob_public_key()),
+                UNIT - 1_000 - 2 * GAS_LIMIT
+            );
+
+            assert_ok!(Mvm::remove_callback_subscription(
+                Origin::signed(bob_public_key()),
+                bob_public_key(),
+                CallbackTrigger::Block
+            ));
+            assert_eq!(Balances::reserved_balance(bob_public_key()), 0);
+        });
+    CallbackDeposit::set(0);
+    CallbackGasUnitPrice::set(0);
+}
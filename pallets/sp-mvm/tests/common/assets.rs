@@ -9,10 +9,35 @@ pub static ROOT_PACKAGE: Package = Package::new(
 );
 /// Package built using user account.
 pub static USER_PACKAGE: Package = Package::new(
-    &["Bank", "EventProxy", "Store"],
+    &["Bank", "EventProxy", "Store", "StoreProxy"],
     Asset::new("", "tests/assets/user/build/assets/bundles/assets.pac"),
 );
 
+/// Mock framework package: tiny Coin and Event modules under `0x1`, see `mock::MockVmBuilder`.
+pub static MOCK_FRAMEWORK: Package = Package::new(
+    &[
+        "Auth",
+        "BlockMetadata",
+        "Callback",
+        "Coin",
+        "Event",
+        "Genesis",
+        "Governance",
+        "Randomness",
+        "Schedule",
+        "Signer",
+        "Staking",
+        "Treasury",
+        "Vector",
+        "Vesting",
+        "XTransfer",
+    ],
+    Asset::new(
+        "",
+        "tests/assets/mock-framework/build/MockFramework/bundles/MockFramework.pac",
+    ),
+);
+
 /// Modules assets (root/user).
 pub mod modules {
     pub mod root {
@@ -41,6 +66,10 @@ pub mod modules {
             "Bank",
             "tests/assets/user/build/assets/bytecode_modules/Bank.mv",
         );
+        pub static STORE_PROXY: Asset = Asset::new(
+            "StoreProxy",
+            "tests/assets/user/build/assets/bytecode_modules/StoreProxy.mv",
+        );
     }
 }
 
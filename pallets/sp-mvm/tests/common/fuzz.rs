@@ -0,0 +1,83 @@
+#![allow(dead_code)]
+/// Fuzzing entry points, shared by `fuzz` targets and the regression tests of found crashers.
+use std::cell::RefCell;
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::traits::OnFinalize;
+use frame_support::unsigned::ValidateUnsigned;
+use sp_runtime::transaction_validity::TransactionSource;
+use sp_mvm::Call as MvmCall;
+
+use super::mock::*;
+use super::addr::*;
+
+/// Gas limit of the fuzzed calls.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Fuzz targets by name, as in `fuzz/fuzz_targets`.
+pub const TARGETS: &[(&str, fn(&[u8]))] = &[
+    ("publish_module", publish_module),
+    ("publish_package", publish_package),
+    ("execute", execute),
+    ("ed25519_transaction", ed25519_transaction),
+];
+
+thread_local! {
+    /// Genesis with the stdlib is built once per thread, as it takes a while.
+    static EXT: RefCell<sp_io::TestExternalities> = RefCell::new(
+        RuntimeBuilder::new()
+            .set_balances(vec![(bob_public_key(), CurrencyId::NATIVE, INITIAL_BALANCE)])
+            .build()
+    );
+}
+
+/// Runs the call on top of the genesis state, changes are reverted afterwards.
+fn run(call: impl FnOnce()) {
+    EXT.with(|ext| {
+        ext.borrow_mut().execute_with(|| {
+            with_transaction(|| {
+                call();
+                // Clean VM cache, so modules of the input don't leak into the next one.
+                Mvm::on_finalize(Sys::block_number());
+                TransactionOutcome::Rollback(())
+            })
+        })
+    });
+}
+
+/// Publishes the input as a module of Bob.
+pub fn publish_module(data: &[u8]) {
+    run(|| {
+        let origin = Origin::signed(bob_public_key());
+        let _ = Mvm::publish_module(origin, data.to_vec(), GAS_LIMIT);
+    });
+}
+
+/// Publishes the input as a package of Bob.
+pub fn publish_package(data: &[u8]) {
+    run(|| {
+        let origin = Origin::signed(bob_public_key());
+        let _ = Mvm::publish_package(origin, data.to_vec(), GAS_LIMIT);
+    });
+}
+
+/// Executes the input as a script transaction signed by Bob and by root.
+pub fn execute(data: &[u8]) {
+    run(|| {
+        let _ = Mvm::execute(Origin::signed(bob_public_key()), data.to_vec(), GAS_LIMIT);
+    });
+    run(|| {
+        let _ = Mvm::execute(Origin::root(), data.to_vec(), GAS_LIMIT);
+    });
+}
+
+/// Validates the input as an ed25519 signed transaction and submits it if it's valid.
+pub fn ed25519_transaction(data: &[u8]) {
+    run(|| {
+        let call = MvmCall::submit_ed25519_transaction {
+            signed_tx: data.to_vec(),
+        };
+        if Mvm::validate_unsigned(TransactionSource::External, &call).is_ok() {
+            let _ = Mvm::submit_ed25519_transaction(Origin::none(), data.to_vec());
+        }
+    });
+}
@@ -0,0 +1,106 @@
+#![allow(dead_code)]
+/// Gas corpus harness: compares gas used by Move VM calls with the recorded fixture.
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use frame_support::dispatch::DispatchResultWithPostInfo as PsResult;
+use sp_mvm::gas::{GasWeightMapping, GAS_SCHEDULE_VERSION};
+
+use super::mock::*;
+
+/// Environment variable to record measured gas into the fixture instead of comparing.
+pub const BLESS_VAR: &str = "MVM_GAS_BLESS";
+
+/// Key of the gas schedule version in the fixture.
+const SCHEDULE_VERSION_KEY: &str = "schedule_version";
+
+/// Fixture with the gas recorded for the corpus.
+pub fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/assets/gas/corpus.txt")
+}
+
+/// Gas used by the call, `None` if it failed before reaching the VM (e.g. bad origin).
+pub fn gas_used(result: PsResult) -> Option<u64> {
+    let post_info = match result {
+        Ok(post_info) => post_info,
+        Err(err) => err.post_info,
+    };
+    post_info
+        .actual_weight
+        .map(MoveVMGasWeightMapping::weight_to_gas)
+}
+
+/// Loads `name gas` lines of the fixture, `#` starts comments.
+pub fn load(path: &Path) -> BTreeMap<String, u64> {
+    let content = std::fs::read_to_string(path)
+        .unwrap_or_else(|_| panic!("Failed to read gas corpus fixture: {:?}", path.display()));
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [name, gas] => {
+                let gas = gas
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Invalid gas in the fixture line: {:?}", line));
+                (name.to_string(), gas)
+            }
+            _ => panic!("Invalid gas corpus fixture line: {:?}", line),
+        })
+        .collect()
+}
+
+/// Writes measured gas into the fixture.
+pub fn bless(path: &Path, measured: &BTreeMap<String, u64>) {
+    let mut content = String::from(
+        "# Gas used by the Move VM calls of `tests/gas_corpus.rs`, see README.md.\n",
+    );
+    content.push_str(&format!("{} {}\n", SCHEDULE_VERSION_KEY, GAS_SCHEDULE_VERSION));
+    for (name, gas) in measured {
+        content.push_str(&format!("{} {}\n", name, gas));
+    }
+    std::fs::write(path, content)
+        .unwrap_or_else(|_| panic!("Failed to write gas corpus fixture: {:?}", path.display()));
+}
+
+/// Compares measured gas with the fixture (or records it with `MVM_GAS_BLESS=1`),
+/// panics with every difference.
+pub fn check(measured: BTreeMap<String, u64>) {
+    let path = fixture_path();
+    if std::env::var_os(BLESS_VAR).is_some() {
+        bless(&path, &measured);
+        return;
+    }
+
+    let mut expected = load(&path);
+    let mut diffs = Vec::new();
+
+    let version = expected.remove(SCHEDULE_VERSION_KEY);
+    if version != Some(GAS_SCHEDULE_VERSION as u64) {
+        diffs.push(format!(
+            "{}: recorded {:?}, current {}",
+            SCHEDULE_VERSION_KEY, version, GAS_SCHEDULE_VERSION
+        ));
+    }
+    for (name, gas) in &measured {
+        match expected.get(name) {
+            Some(recorded) if recorded == gas => {}
+            Some(recorded) => {
+                diffs.push(format!("{}: recorded {}, used {}", name, recorded, gas))
+            }
+            None => diffs.push(format!("{}: not recorded, used {}", name, gas)),
+        }
+    }
+    for name in expected.keys().filter(|name| !measured.contains_key(*name)) {
+        diffs.push(format!("{}: recorded, but missing in the corpus", name));
+    }
+
+    assert!(
+        diffs.is_empty(),
+        "Gas used by Move VM calls differs from {:?}:\n{}\n\
+         Changed costs break consensus with nodes of previous releases. If the change is \
+         intended, bump `GAS_SCHEDULE_VERSION` and record the corpus with `{}=1`.",
+        path.display(),
+        diffs.join("\n"),
+        BLESS_VAR,
+    );
+}
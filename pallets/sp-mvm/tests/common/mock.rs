@@ -6,17 +6,18 @@ use sp_core::{H256, sr25519};
 use sp_std::{convert::TryFrom, fmt::Debug};
 use frame_system as system;
 use parity_scale_codec::{Decode, Encode};
-use system::EnsureRoot;
+use system::{EnsureRoot, EnsureSigned};
 use frame_support::{
     PalletId, parameter_types,
-    traits::{Everything, ConstU32},
+    traits::{Everything, ConstU32, EqualPrivilegeOnly, Randomness},
+    traits::schedule::{Anon as ScheduleAnon, DispatchTime, LOWEST_PRIORITY},
     weights::{Weight, constants::WEIGHT_PER_SECOND},
 };
 use sp_std::vec;
 use std::include_bytes;
 use frame_support::traits::{OnInitialize, OnFinalize};
-use sp_runtime::traits::{Verify, Lazy, BlakeTwo256, IdentityLookup, ConvertInto};
-use sp_runtime::{testing::Header};
+use sp_runtime::traits::{Verify, Lazy, BlakeTwo256, IdentityLookup, ConvertInto, Hash};
+use sp_runtime::{testing::Header, DispatchResult};
 use orml_traits::parameter_type_with_key;
 use constants::SS58_PREFIX;
 use scale_info::TypeInfo;
@@ -25,6 +26,8 @@ pub use primitives::currency::CurrencyId;
 use module_currencies::BasicCurrencyAdapter;
 
 use super::vm_config::build as build_vm_config;
+use super::assets::MOCK_FRAMEWORK;
+use super::addr::{alice_public_key, bob_public_key};
 
 type UncheckedExtrinsic = system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = system::mocking::MockBlock<Test>;
@@ -68,8 +71,9 @@ frame_support::construct_runtime!(
         Vesting: pallet_vesting::{Pallet, Call, Storage, Config<T>, Event<T>},
         Tokens: orml_tokens::{Pallet, Storage, Event<T>},
         Currencies: module_currencies::{Pallet, Call, Storage, Event<T>},
-        Mvm: sp_mvm::{Pallet, Call, Config<T>, Storage, Event<T>},
+        Mvm: sp_mvm::{Pallet, Call, Config<T>, Storage, Event<T>, ValidateUnsigned},
         Groupsign: groupsign::{Pallet, Call, Origin<T>, Event<T>},
+        Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
     }
 );
 
@@ -221,12 +225,71 @@ impl module_currencies::Config for Test {
 // -------- move vm pallet --------- //
 parameter_types! {
     pub const MVMPalletId: PalletId = PalletId(*b"_nox/mvm");
+    pub const AccountMigrationDelay: u64 = 5;
+    pub const MaxModuleSize: u32 = 64 * 1024;
+    pub const MaxStagedModuleSize: u32 = 1024 * 1024;
+    pub const MaxSourceMapSize: u32 = 16 * 1024;
+    pub const MaxScriptSize: u32 = 32 * 1024;
+    pub const MaxTypeArgDepth: u32 = 8;
+    pub const MaxSessionScope: u32 = 4;
+    pub const MaxFailedTransacts: u32 = 2;
+    // Zero by default, so tests don't need to fund publishers.
+    pub static ModuleDepositPerByte: Balance = 0;
+    pub static ResourceDepositPerByte: Balance = 0;
+    pub static MultisigDeposit: Balance = 0;
+    pub const MaxMultisigProposals: u32 = 2;
+    pub const MaxTreasurySpend: u128 = 1_000;
+    pub const TreasurySpendPeriod: u64 = 10;
+    pub const MaxCallbackGas: u64 = 1_500_000;
+    pub const MaxCallbackSubscriptions: u32 = 2;
+    pub const MaxScheduledGas: u64 = 2_000_000;
+    pub const MaxScheduledScripts: u32 = 2;
+    pub static ScheduledGasUnitPrice: u64 = 0;
+    pub const MaxAccountCallbackSubscriptions: u32 = 2;
+    pub static CallbackDeposit: Balance = 0;
+    pub static CallbackGasUnitPrice: u64 = 0;
 }
 impl sp_mvm::Config for Test {
     type Event = Event;
     type GasWeightMapping = MoveVMGasWeightMapping;
     type UpdateOrigin = EnsureRoot<AccountId>;
     type PalletId = MVMPalletId;
+    type AccountMigrationDelay = AccountMigrationDelay;
+    type MaxModuleSize = MaxModuleSize;
+    type MaxStagedModuleSize = MaxStagedModuleSize;
+    type MaxSourceMapSize = MaxSourceMapSize;
+    type MaxScriptSize = MaxScriptSize;
+    type MaxTypeArgDepth = MaxTypeArgDepth;
+    type MaxSessionScope = MaxSessionScope;
+    type Currency = Balances;
+    type ModuleDepositPerByte = ModuleDepositPerByte;
+    type ResourceDepositPerByte = ResourceDepositPerByte;
+    type MultisigDeposit = MultisigDeposit;
+    type MaxMultisigProposals = MaxMultisigProposals;
+    type OnGasTip = ();
+    type ScriptScheduler = MockScriptScheduler;
+    type MaxScheduledGas = MaxScheduledGas;
+    type MaxScheduledScripts = MaxScheduledScripts;
+    type ScheduledGasUnitPrice = ScheduledGasUnitPrice;
+    type XcmTransfer = ();
+    type AssetClaims = ();
+    // Signed accounts stand for XCM `Transact` origins.
+    type XcmTransactOrigin = EnsureSigned<AccountId>;
+    type MaxFailedTransacts = MaxFailedTransacts;
+    type Randomness = MockRandomness;
+    type ResourceGroups = MockResourceGroups;
+    type SenderKeys = MockSenderKeys;
+    type Lockups = MockLockups;
+    type Staking = MockStaking;
+    type Governance = MockGovernance;
+    type TreasurySpend = ();
+    type MaxTreasurySpend = MaxTreasurySpend;
+    type TreasurySpendPeriod = TreasurySpendPeriod;
+    type MaxCallbackGas = MaxCallbackGas;
+    type MaxCallbackSubscriptions = MaxCallbackSubscriptions;
+    type MaxAccountCallbackSubscriptions = MaxAccountCallbackSubscriptions;
+    type CallbackDeposit = CallbackDeposit;
+    type CallbackGasUnitPrice = CallbackGasUnitPrice;
     type CurrencyId = CurrencyId;
     type Currencies = Currencies;
     type WeightInfo = ();
@@ -247,25 +310,269 @@ impl groupsign::Config for Test {
     type WeightInfo = PontemWeights<Self>;
 }
 
+parameter_types! {
+    pub const MaximumSchedulerWeight: Weight = 2_000_000_000_000;
+    pub const MaxScheduledPerBlock: u32 = 50;
+    pub const NoPreimagePostponement: Option<u64> = None;
+}
+
+impl pallet_scheduler::Config for Test {
+    type Event = Event;
+    type Origin = Origin;
+    type PalletsOrigin = OriginCaller;
+    type Call = Call;
+    type MaximumWeight = MaximumSchedulerWeight;
+    type ScheduleOrigin = EnsureRoot<AccountId>;
+    type OriginPrivilegeCmp = EqualPrivilegeOnly;
+    type MaxScheduledPerBlock = MaxScheduledPerBlock;
+    type WeightInfo = ();
+    type PreimageProvider = ();
+    type NoPreimagePostponement = NoPreimagePostponement;
+}
+
+/// Randomness derived from the subject and block number.
+pub struct MockRandomness;
+
+impl Randomness<H256, BlockNumber> for MockRandomness {
+    fn random(subject: &[u8]) -> (H256, BlockNumber) {
+        let block = Sys::block_number();
+        (BlakeTwo256::hash_of(&(subject, block)), block)
+    }
+}
+
+thread_local! {
+    static RESOURCE_GROUPS: std::cell::RefCell<Vec<(Vec<u8>, Vec<u8>)>> = Default::default();
+}
+
+/// Resource groups configured by the test with `set_resource_groups`, none by default.
+pub struct MockResourceGroups;
+
+impl sp_mvm::storage::ResourceGroups for MockResourceGroups {
+    fn group_of(member: &[u8]) -> Option<Vec<u8>> {
+        RESOURCE_GROUPS.with(|groups| {
+            groups
+                .borrow()
+                .iter()
+                .find(|(tag, _)| tag == member)
+                .map(|(_, group)| group.clone())
+        })
+    }
+}
+
+/// Packs resources of the member struct tags into the group struct tags (both BCS encoded).
+pub fn set_resource_groups(groups: Vec<(Vec<u8>, Vec<u8>)>) {
+    RESOURCE_GROUPS.with(|cell| *cell.borrow_mut() = groups);
+}
+
+/// Test accounts sign with sr25519 keys, the key is the account id.
+pub struct MockSenderKeys;
+
+impl sp_mvm::auth::SenderKeyProvider<AccountId> for MockSenderKeys {
+    fn sender_key(who: &AccountId) -> Option<sp_mvm::auth::SenderKey> {
+        Some(sp_mvm::auth::SenderKey::new(
+            sp_mvm::crypto::SignatureScheme::Sr25519,
+            who.as_ref(),
+        ))
+    }
+}
+
+/// Lockups of the vesting pallet.
+pub struct MockLockups;
+
+impl sp_mvm::vesting::Lockups<AccountId> for MockLockups {
+    fn lockup(who: &AccountId) -> Option<sp_mvm::vesting::Lockup> {
+        use frame_support::traits::VestingSchedule;
+
+        let locked = <Vesting as VestingSchedule<AccountId>>::vesting_balance(who)?;
+        let schedules = Vesting::vesting(who)?
+            .iter()
+            .map(|schedule| sp_mvm::vesting::Schedule {
+                locked: schedule.locked().into(),
+                per_block: schedule.per_block().into(),
+                starting_block: schedule.starting_block(),
+            })
+            .collect();
+        Some(sp_mvm::vesting::Lockup {
+            locked: locked.into(),
+            schedules,
+        })
+    }
+
+    fn vest(who: AccountId) -> DispatchResult {
+        Vesting::vest(Origin::signed(who))
+    }
+}
+
+/// Length of the mock staking rounds in blocks.
+pub const STAKING_ROUND_LENGTH: u64 = 10;
+
+/// Staking of rounds of `STAKING_ROUND_LENGTH` blocks: Alice is the collator with `UNIT` bond,
+/// Bob delegates `UNIT / 2` to her.
+pub struct MockStaking;
+
+impl sp_mvm::staking::StakingInfo<AccountId> for MockStaking {
+    fn round() -> Option<sp_mvm::staking::Round<AccountId>> {
+        let block = Sys::block_number();
+        Some(sp_mvm::staking::Round {
+            index: block / STAKING_ROUND_LENGTH,
+            first_block: block - block % STAKING_ROUND_LENGTH,
+            length: STAKING_ROUND_LENGTH,
+            collators: vec![alice_public_key()],
+            total_staked: (UNIT + UNIT / 2) as u128,
+        })
+    }
+
+    fn stake(who: &AccountId) -> Option<sp_mvm::staking::Stake> {
+        let (delegated, collator_bond) = if *who == alice_public_key() {
+            (0, UNIT)
+        } else if *who == bob_public_key() {
+            (UNIT / 2, 0)
+        } else {
+            return None;
+        };
+        Some(sp_mvm::staking::Stake {
+            delegated: delegated as u128,
+            collator_bond: collator_bond as u128,
+        })
+    }
+}
+
+/// Governance accepting proposals of valid calls, enacted calls are dispatched as root.
+pub struct MockGovernance;
+
+impl sp_mvm::governance::GovernanceBridge<AccountId> for MockGovernance {
+    fn propose(_: AccountId, _: u64, call: &[u8], _: Weight, _: u128) -> DispatchResult {
+        Call::decode(&mut &call[..]).map_err(|_| "Invalid proposal call")?;
+        Ok(())
+    }
+
+    fn call_weight(call: &[u8]) -> Option<Weight> {
+        use frame_support::weights::GetDispatchInfo;
+
+        Call::decode(&mut &call[..]).ok().map(|call| call.get_dispatch_info().weight)
+    }
+
+    fn dispatch(call: Vec<u8>) -> frame_support::dispatch::DispatchResultWithPostInfo {
+        use sp_runtime::traits::Dispatchable;
+
+        let call = Call::decode(&mut &call[..]).map_err(|_| "Invalid proposal call")?;
+        call.dispatch(Origin::root())
+    }
+}
+
+/// Schedules Move scripts as signed `execute` calls with the scheduler pallet.
+pub struct MockScriptScheduler;
+
+impl sp_mvm::schedule::ScheduleScript<AccountId, BlockNumber> for MockScriptScheduler {
+    fn schedule_script(
+        who: AccountId,
+        when: BlockNumber,
+        tx_bc: Vec<u8>,
+        gas_limit: u64,
+    ) -> DispatchResult {
+        let call = Call::Mvm(sp_mvm::Call::execute { tx_bc, gas_limit });
+        <Scheduler as ScheduleAnon<BlockNumber, Call, OriginCaller>>::schedule(
+            DispatchTime::At(when),
+            None,
+            LOWEST_PRIORITY,
+            system::RawOrigin::Signed(who).into(),
+            call.into(),
+        )
+        .map(|_| ())
+    }
+}
+
 pub type Sys = system::Pallet<Test>;
 pub type Time = timestamp::Pallet<Test>;
 pub type MoveEvent = sp_mvm::Event<Test>;
 
+/// Move VM genesis builder, used by `RuntimeBuilder` to seed the VM storage.
+pub struct MockVmBuilder {
+    move_stdlib: Vec<u8>,
+    pont_framework: Vec<u8>,
+    init_module: Vec<u8>,
+    init_func: Vec<u8>,
+    init_args: Vec<Vec<u8>>,
+    move_state: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl MockVmBuilder {
+    /// Move Standard Library and Pontem Framework, initialized with default genesis arguments.
+    pub fn stdlib() -> Self {
+        let (init_module, init_func, init_args) = build_vm_config();
+
+        Self {
+            move_stdlib: include_bytes!(
+                "../assets/move-stdlib/build/MoveStdlib/bundles/MoveStdlib.pac"
+            )
+            .to_vec(),
+            pont_framework: include_bytes!(
+                "../assets/pont-stdlib/build/PontStdlib/bundles/PontStdlib.pac"
+            )
+            .to_vec(),
+            init_module,
+            init_func,
+            init_args,
+            move_state: vec![],
+        }
+    }
+
+    /// Mock framework with tiny Coin and Event modules, for tests not depending on the stdlib.
+    /// Genesis is built in milliseconds, as there are only a few modules to load.
+    pub fn mock_framework() -> Self {
+        Self {
+            move_stdlib: MOCK_FRAMEWORK.bytes().to_vec(),
+            pont_framework: vec![],
+            init_module: b"Genesis".to_vec(),
+            init_func: b"initialize".to_vec(),
+            init_args: vec![],
+            move_state: vec![],
+        }
+    }
+
+    /// Build genesis config of the Move VM pallet.
+    pub fn build(self) -> sp_mvm::GenesisConfig<Test> {
+        sp_mvm::GenesisConfig::<Test> {
+            move_stdlib: self.move_stdlib,
+            pont_framework: self.pont_framework,
+            init_module: self.init_module,
+            init_func: self.init_func,
+            init_args: self.init_args,
+            move_state: self.move_state,
+            ..Default::default()
+        }
+    }
+}
+
 /// Runtime builder.
 pub struct RuntimeBuilder {
     balances: Vec<(AccountId, CurrencyId, Balance)>,
     vesting: Vec<(AccountId, BlockNumber, u32, Balance)>,
+    vm: MockVmBuilder,
 }
 
 impl RuntimeBuilder {
-    /// Create new Runtime builder instance.
+    /// Create new Runtime builder instance, VM is seeded with the stdlib.
     pub fn new() -> Self {
         Self {
             balances: vec![],
             vesting: vec![],
+            vm: MockVmBuilder::stdlib(),
         }
     }
 
+    /// Seed VM with the mock framework instead of the stdlib.
+    pub fn with_mock_framework(mut self) -> Self {
+        self.vm = MockVmBuilder::mock_framework();
+        self
+    }
+
+    /// Store Move state entries (access keys with values) in genesis.
+    pub fn with_move_state(mut self, move_state: Vec<(Vec<u8>, Vec<u8>)>) -> Self {
+        self.vm.move_state = move_state;
+        self
+    }
+
     /// Set balances.
     pub fn set_balances(mut self, balances: Vec<(AccountId, CurrencyId, Balance)>) -> Self {
         self.balances = balances;
@@ -298,25 +605,10 @@ impl RuntimeBuilder {
         .assimilate_storage(&mut sys)
         .expect("Pallet balances storage can't be assimilated");
 
-        let vm_config = build_vm_config();
-
-        let move_stdlib =
-            include_bytes!("../assets/move-stdlib/build/MoveStdlib/bundles/MoveStdlib.pac")
-                .to_vec();
-        let pont_framework =
-            include_bytes!("../assets/pont-stdlib/build/PontStdlib/bundles/PontStdlib.pac")
-                .to_vec();
-
-        sp_mvm::GenesisConfig::<Test> {
-            move_stdlib,
-            pont_framework,
-            init_module: vm_config.0.clone(),
-            init_func: vm_config.1.clone(),
-            init_args: vm_config.2.clone(),
-            ..Default::default()
-        }
-        .assimilate_storage(&mut sys)
-        .expect("Pallet mvm storage can't be assimilated");
+        self.vm
+            .build()
+            .assimilate_storage(&mut sys)
+            .expect("Pallet mvm storage can't be assimilated");
 
         sys.into()
     }
@@ -333,6 +625,7 @@ pub fn roll_next_block() {
     Sys::set_block_number(Sys::block_number() + 1);
     Sys::on_initialize(Sys::block_number());
     Mvm::on_initialize(Sys::block_number());
+    Scheduler::on_initialize(Sys::block_number());
     Balances::on_initialize(Sys::block_number());
 
     // set time with multiplier `*MULTIPLIER` by block:
@@ -1,5 +1,8 @@
 pub mod addr;
 pub mod assets;
+pub mod fuzz;
+pub mod gas_corpus;
 pub mod mock;
+pub mod replay;
 pub mod utils;
 pub mod vm_config;
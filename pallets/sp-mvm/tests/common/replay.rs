@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+/// Replay bundles harness: executes captured Move VM calls on the mock runtime.
+use std::convert::TryInto;
+use std::path::Path;
+use frame_support::traits::{Currency, OnFinalize, PalletInfoAccess};
+use sp_core::sr25519::Public;
+use sp_mvm::replay::{ReplayBundle, ReplayCall, ReplayOutcome};
+use sp_mvm::VMStorage;
+
+use super::mock::*;
+
+/// Loads replay bundle from file.
+pub fn load(path: &Path) -> ReplayBundle {
+    let bytes = std::fs::read(path)
+        .unwrap_or_else(|_| panic!("Failed to read replay bundle: {:?}", path.display()));
+    ReplayBundle::from_bytes(&bytes)
+        .unwrap_or_else(|err| panic!("Invalid replay bundle {:?}: {:?}", path.display(), err))
+}
+
+/// Executes the call of the bundle on top of its state and returns the outcome.
+pub fn replay(bundle: &ReplayBundle) -> ReplayOutcome {
+    RuntimeBuilder::new().build().execute_with(|| {
+        // State of the bundle replaces the genesis one.
+        let _ = VMStorage::<Test>::remove_all(None);
+        for (key, value) in &bundle.state {
+            VMStorage::<Test>::insert(key, value);
+        }
+        for (account, balance) in &bundle.balances {
+            let balance = (*balance)
+                .try_into()
+                .expect("Balance should fit into the mock runtime balance");
+            Balances::make_free_balance_be(&Public::from_raw(*account), balance);
+        }
+        Sys::set_block_number(bundle.block_number);
+        Time::set_timestamp(bundle.timestamp);
+
+        let origin = |signer: &Option<[u8; 32]>| match signer {
+            Some(signer) => Origin::signed(Public::from_raw(*signer)),
+            None => Origin::root(),
+        };
+        let result = match &bundle.call {
+            ReplayCall::Execute {
+                signer,
+                tx_bc,
+                gas_limit,
+            } => Mvm::execute(origin(signer), tx_bc.clone(), *gas_limit),
+            ReplayCall::PublishModule {
+                signer,
+                module_bc,
+                gas_limit,
+            } => Mvm::publish_module(origin(signer), module_bc.clone(), *gas_limit),
+            ReplayCall::PublishPackage {
+                signer,
+                package,
+                gas_limit,
+            } => Mvm::publish_package(origin(signer), package.clone(), *gas_limit),
+        };
+        // Clean VM cache, so modules of the bundle don't leak into the next replay.
+        Mvm::on_finalize(bundle.block_number);
+
+        match result {
+            Ok(_) => ReplayOutcome::Success,
+            Err(err) => ReplayOutcome::from_dispatch_error(&err.error, Mvm::index() as u8),
+        }
+    })
+}
@@ -0,0 +1,185 @@
+/// Tests related to module and resource deposits.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::assert_err_ignore_postinfo;
+use frame_support::traits::ReservableCurrency;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use sp_mvm::{Error, ModuleDeposits, StorageDeposits, VMStorage};
+
+mod common;
+use common::assets::{modules, transactions, USER_PACKAGE};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Storage key of the module published by Bob.
+fn bob_module_key(name: &str) -> Vec<u8> {
+    let name = Identifier::new(name).unwrap();
+    ModuleId::new(to_move_addr(bob_public_key()), name).access_vector()
+}
+
+fn funded_runtime() -> RuntimeBuilder {
+    RuntimeBuilder::new().set_balances(vec![(
+        bob_public_key(),
+        CurrencyId::NATIVE,
+        INITIAL_BALANCE,
+    )])
+}
+
+#[test]
+/// Deposit is proportional to the module size.
+fn publish_module_reserves_deposit() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+        let size = modules::user::STORE.bytes().len() as Balance;
+
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        assert_eq!(Balances::reserved_balance(&bob), size);
+
+        let deposit = ModuleDeposits::<Test>::get(bob_module_key("Store")).unwrap();
+        assert_eq!(deposit.depositor, bob);
+        assert_eq!(deposit.amount, size);
+
+        // Republishing reserves only the difference.
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        assert_eq!(Balances::reserved_balance(&bob), size);
+    });
+}
+
+#[test]
+/// Every module of the package has its own deposit.
+fn publish_package_reserves_deposit() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+
+        utils::publish_package(bob, &USER_PACKAGE, None).unwrap();
+
+        let total: Balance = USER_PACKAGE
+            .modules()
+            .iter()
+            .map(|name| ModuleDeposits::<Test>::get(bob_module_key(name)).unwrap().amount)
+            .sum();
+        assert!(total > 0);
+        assert_eq!(Balances::reserved_balance(&bob), total);
+    });
+}
+
+#[test]
+/// Module isn't published if the deposit can't be reserved.
+fn publish_without_deposit() {
+    ModuleDepositPerByte::set(1);
+    RuntimeBuilder::new().build().execute_with(|| {
+        assert_err_ignore_postinfo!(
+            utils::publish_module(bob_public_key(), &modules::user::STORE, None),
+            Error::<Test>::InsufficientModuleDeposit
+        );
+        assert!(!VMStorage::<Test>::contains_key(bob_module_key("Store")));
+    });
+}
+
+#[test]
+/// Root publishes modules without deposit.
+fn publish_as_root_without_deposit() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        utils::publish_module_as_root(&modules::root::EVENT_PROXY, None).unwrap();
+        assert_eq!(ModuleDeposits::<Test>::iter().count(), 0);
+    });
+}
+
+#[test]
+/// Removing module releases the deposit.
+fn remove_module() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+
+        assert_ok!(Mvm::remove_module(Origin::signed(bob), b"Store".to_vec()));
+        assert_eq!(Balances::reserved_balance(&bob), 0);
+        assert!(!VMStorage::<Test>::contains_key(bob_module_key("Store")));
+        assert!(!ModuleDeposits::<Test>::contains_key(bob_module_key("Store")));
+
+        assert_noop!(
+            Mvm::remove_module(Origin::signed(bob), b"Store".to_vec()),
+            Error::<Test>::ModuleDepositNotFound
+        );
+    });
+}
+
+#[test]
+/// Module can't be removed while other modules depend on it.
+fn remove_module_with_dependents() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        utils::publish_module(bob, &modules::user::STORE_PROXY, None).unwrap();
+
+        assert_noop!(
+            Mvm::remove_module(Origin::signed(bob), b"Store".to_vec()),
+            Error::<Test>::ModuleHasDependents
+        );
+
+        assert_ok!(Mvm::remove_module(Origin::signed(bob), b"StoreProxy".to_vec()));
+        assert_ok!(Mvm::remove_module(Origin::signed(bob), b"Store".to_vec()));
+        assert_eq!(Balances::reserved_balance(&bob), 0);
+    });
+}
+
+#[test]
+/// Only modules published under the signer address can be removed.
+fn remove_foreign_module() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+
+        assert_noop!(
+            Mvm::remove_module(Origin::signed(alice_public_key()), b"Store".to_vec()),
+            Error::<Test>::ModuleDepositNotFound
+        );
+    });
+}
+
+#[test]
+/// Resources written by the script reserve deposit from the owner.
+fn execute_reserves_resource_deposit() {
+    ResourceDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        assert_eq!(Balances::reserved_balance(&bob), 0);
+
+        assert_ok!(utils::execute_tx(bob, &transactions::STORE_U64, None));
+
+        let deposit = StorageDeposits::<Test>::get(&bob).unwrap();
+        // Resource key and `u64` value.
+        assert!(deposit.bytes > 8);
+        assert_eq!(deposit.amount, deposit.bytes);
+        assert_eq!(Balances::reserved_balance(&bob), deposit.amount);
+    });
+}
+
+#[test]
+/// Resources aren't written if the deposit can't be reserved.
+fn execute_without_resource_deposit() {
+    ResourceDepositPerByte::set(1);
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bob = bob_public_key();
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+
+        assert_err_ignore_postinfo!(
+            utils::execute_tx(bob, &transactions::STORE_U64, None),
+            Error::<Test>::InsufficientStorageDeposit
+        );
+        assert!(StorageDeposits::<Test>::get(&bob).is_none());
+        assert_eq!(
+            VMStorage::<Test>::iter_keys()
+                .filter(|key| sp_mvm::storage::resource_owner(key) == Some(to_move_addr(bob)))
+                .count(),
+            0
+        );
+    });
+}
@@ -0,0 +1,155 @@
+/// Tests related to Move transactions signed with ed25519 keys.
+use serde::Deserialize;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use frame_support::assert_ok;
+use frame_support::unsigned::ValidateUnsigned;
+use sp_core::{ed25519, Pair};
+use sp_runtime::transaction_validity::{
+    InvalidTransaction, TransactionSource, TransactionValidity,
+};
+use sp_mvm::ed25519_tx::{authentication_key, RawTransaction, SignedTransaction};
+use sp_mvm::{Call as MvmCall, Event as MvmEvent};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the transactions in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Ed25519 key of the sender.
+fn sender_pair() -> ed25519::Pair {
+    ed25519::Pair::from_seed(&[7; 32])
+}
+
+/// Account of the sender.
+fn sender_account() -> AccountId {
+    AccountId::from_raw(authentication_key(&sender_pair().public().0))
+}
+
+/// Signs `STORE_U64` script with the sequence number.
+fn signed_tx(sequence_number: u64, chain_id: u8) -> Vec<u8> {
+    let pair = sender_pair();
+    let raw = RawTransaction {
+        sender: authentication_key(&pair.public().0),
+        sequence_number,
+        payload: transactions::STORE_U64.bytes().to_vec(),
+        max_gas_amount: GAS_LIMIT,
+        gas_unit_price: 1,
+        expiration_timestamp_secs: u64::MAX,
+        chain_id,
+    };
+    let signature = pair.sign(&raw.signing_message());
+
+    SignedTransaction {
+        raw,
+        public_key: pair.public().0,
+        signature: signature.0,
+    }
+    .encode()
+}
+
+/// Validates unsigned call with the signed transaction.
+fn validate(signed_tx: Vec<u8>) -> TransactionValidity {
+    Mvm::validate_unsigned(
+        TransactionSource::External,
+        &MvmCall::submit_ed25519_transaction { signed_tx },
+    )
+}
+
+/// Runtime with the sender funded.
+fn runtime() -> sp_io::TestExternalities {
+    RuntimeBuilder::new()
+        .set_balances(vec![(sender_account(), CurrencyId::NATIVE, UNIT)])
+        .build()
+}
+
+#[test]
+/// Signed transaction is decoded back and verified.
+fn decode_signed_transaction() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bytes = signed_tx(0, 0);
+        let tx = SignedTransaction::decode(&bytes).unwrap();
+        assert_eq!(tx.encode(), bytes);
+        assert!(tx.verify());
+
+        // Sender isn't the authentication key of the signer.
+        let mut tx = tx;
+        tx.raw.sender = [1; 32];
+        assert!(!tx.verify());
+
+        assert!(SignedTransaction::decode(&bytes[..bytes.len() - 1]).is_none());
+    });
+}
+
+#[test]
+/// Transactions are validated by the signature, chain id, balance and sequence number.
+fn validate_ed25519_transaction() {
+    runtime().execute_with(|| {
+        assert_ok!(validate(signed_tx(0, 0)));
+        assert_eq!(validate(signed_tx(0, 1)), Err(InvalidTransaction::BadProof.into()));
+
+        let mut bytes = signed_tx(0, 0);
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        assert_eq!(validate(bytes), Err(InvalidTransaction::BadProof.into()));
+
+        // Next sequence numbers wait for the previous ones.
+        let valid = validate(signed_tx(1, 0)).unwrap();
+        assert_eq!(valid.requires.len(), 1);
+        assert_eq!(
+            Mvm::pre_dispatch(&MvmCall::submit_ed25519_transaction {
+                signed_tx: signed_tx(1, 0)
+            }),
+            Err(InvalidTransaction::Future.into())
+        );
+    });
+}
+
+#[test]
+/// Sender without balance for the gas can't submit transactions.
+fn validate_ed25519_transaction_without_balance() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        assert_eq!(validate(signed_tx(0, 0)), Err(InvalidTransaction::Payment.into()));
+    });
+}
+
+#[test]
+/// Script is executed signed by the sender, sequence number is bumped.
+fn submit_ed25519_transaction() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct StoreU64 {
+        pub val: u64,
+    }
+
+    runtime().execute_with(|| {
+        roll_next_block();
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+
+        let call = MvmCall::submit_ed25519_transaction {
+            signed_tx: signed_tx(0, 0),
+        };
+        assert_ok!(Mvm::pre_dispatch(&call));
+        assert_ok!(Mvm::submit_ed25519_transaction(Origin::none(), signed_tx(0, 0)));
+
+        assert_eq!(
+            last_event(),
+            Event::Mvm(MvmEvent::Ed25519TransactionExecuted(sender_account(), 0))
+        );
+        assert_eq!(Mvm::sequence_number(sender_account()), 1);
+        assert_eq!(validate(signed_tx(0, 0)), Err(InvalidTransaction::Stale.into()));
+
+        let tag = StructTag {
+            address: origin_move_addr(),
+            module: Identifier::new(modules::user::STORE.name()).unwrap(),
+            name: Identifier::new("U64").unwrap(),
+            type_params: vec![],
+        };
+        let owner = AccountAddress::new(authentication_key(&sender_pair().public().0));
+        utils::check_storage_res(owner, tag, StoreU64 { val: 42 });
+    });
+}
@@ -0,0 +1,115 @@
+/// Tests related to checks of the call arguments before execution.
+use std::convert::TryFrom;
+use move_core_types::identifier::Identifier;
+use move_core_types::vm_status::StatusCode;
+use move_vm::types::{Call, Transaction};
+use sp_mvm::entry::{check_entry_function, check_script, check_view_function};
+use sp_mvm::weights::WeightInfo;
+use sp_mvm::Error;
+use frame_support::traits::Get;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+
+/// Script code of `store_u64(account: signer, val: u64)` transaction.
+fn store_u64_script() -> Vec<u8> {
+    let tx = Transaction::try_from(transactions::STORE_U64.bytes())
+        .unwrap()
+        .into_script(vec![origin_move_addr()])
+        .unwrap();
+    match tx.call() {
+        Call::Script { code } => code.clone(),
+        _ => panic!("Transaction should contain raw script"),
+    }
+}
+
+#[test]
+/// Script arguments are checked against its parameters.
+fn script_arguments() {
+    let code = store_u64_script();
+    let val = 42u64.to_le_bytes().to_vec();
+
+    assert_eq!(check_script(&code, 0, &[val.clone()], Some(1)), Ok(()));
+    // Root signed transactions don't check signers.
+    assert_eq!(check_script(&code, 0, &[val.clone()], None), Ok(()));
+
+    assert_eq!(
+        check_script(&code, 1, &[val.clone()], Some(1)),
+        Err(StatusCode::NUMBER_OF_TYPE_ARGUMENTS_MISMATCH)
+    );
+    assert_eq!(
+        check_script(&code, 0, &[val.clone()], Some(2)),
+        Err(StatusCode::NUMBER_OF_SIGNER_ARGUMENTS_MISMATCH)
+    );
+    assert_eq!(
+        check_script(&code, 0, &[], Some(1)),
+        Err(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH)
+    );
+    assert_eq!(
+        check_script(&code, 0, &[val.clone(), val], Some(1)),
+        Err(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH)
+    );
+    // `u128` instead of `u64`.
+    assert_eq!(
+        check_script(&code, 0, &[42u128.to_le_bytes().to_vec()], Some(1)),
+        Err(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT)
+    );
+    assert_eq!(
+        check_script(&[0, 1, 2], 0, &[], Some(1)),
+        Err(StatusCode::CODE_DESERIALIZATION_ERROR)
+    );
+}
+
+#[test]
+/// Only entry functions of the module can be called.
+fn entry_function() {
+    let store = modules::user::STORE.bytes();
+    let val = 42u64.to_le_bytes().to_vec();
+
+    let name = Identifier::new("store_u64").unwrap();
+    assert_eq!(
+        check_entry_function(store, &name, 0, &[val.clone()], Some(1)),
+        Err(StatusCode::EXECUTE_SCRIPT_FUNCTION_CALLED_ON_NON_SCRIPT_VISIBLE)
+    );
+
+    let name = Identifier::new("missing").unwrap();
+    assert_eq!(
+        check_entry_function(store, &name, 0, &[val], Some(1)),
+        Err(StatusCode::FUNCTION_RESOLUTION_FAILURE)
+    );
+}
+
+#[test]
+/// Only public functions without signers returning values are view-style.
+fn view_function() {
+    let store = modules::user::STORE.bytes();
+    let val = 42u64.to_le_bytes().to_vec();
+
+    // Takes a signer and returns nothing.
+    let name = Identifier::new("store_u64").unwrap();
+    assert_eq!(
+        check_view_function(store, &name, 0, &[val.clone()]),
+        Err(StatusCode::FUNCTION_RESOLUTION_FAILURE)
+    );
+
+    let name = Identifier::new("missing").unwrap();
+    assert_eq!(
+        check_view_function(store, &name, 0, &[val]),
+        Err(StatusCode::FUNCTION_RESOLUTION_FAILURE)
+    );
+}
+
+#[test]
+/// Calls rejected before execution are charged the base weight, not the gas limit.
+fn rejected_call_weight() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let tx_bc = vec![0; MaxScriptSize::get() as usize + 1];
+        let res = Mvm::execute(Origin::signed(bob_public_key()), tx_bc, 1_000_000);
+
+        let err = res.unwrap_err();
+        assert_eq!(err.error, Error::<Test>::ScriptTooLarge.into());
+        assert_eq!(err.post_info.actual_weight, Some(<() as WeightInfo>::execute()));
+    });
+}
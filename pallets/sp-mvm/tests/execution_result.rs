@@ -0,0 +1,80 @@
+/// Tests related to `ExecutionResult` events of Move extrinsics.
+use move_core_types::vm_status::StatusCode;
+use sp_mvm::Event as MvmEvent;
+use sp_mvm::result::ExecutionResult;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the scripts in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Execution results emitted since the beginning of the block.
+fn execution_results() -> Vec<ExecutionResult> {
+    Sys::events()
+        .into_iter()
+        .filter_map(|rec| match rec.event {
+            Event::Mvm(MvmEvent::ExecutionResult(result)) => Some(result),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+/// Successful calls report used gas, storage changes and emitted events.
+fn execution_result_of_executed_call() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let origin = bob_public_key();
+
+        utils::publish_module(origin, &modules::user::EVENT_PROXY, Some(GAS_LIMIT)).unwrap();
+        let published = execution_results();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].status_code, StatusCode::EXECUTED as u64);
+        assert!(published[0].writes > 0);
+        assert_eq!(published[0].events, 0);
+
+        utils::execute_tx(origin, &transactions::EMIT_EVENT, Some(GAS_LIMIT)).unwrap();
+        let executed = execution_results()[1];
+        assert!(executed.gas_used > 0);
+        assert_eq!(executed.status_code, StatusCode::EXECUTED as u64);
+        assert_eq!(executed.abort_code, None);
+        assert_eq!(executed.events, 1);
+    });
+}
+
+#[test]
+/// Failed calls are reverted, but their execution result is kept.
+fn execution_result_of_failed_call() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+
+        let res = utils::execute_tx(bob_public_key(), &transactions::INF_LOOP, Some(GAS_LIMIT));
+        assert!(res.is_err());
+        assert_eq!(
+            execution_results(),
+            vec![ExecutionResult {
+                gas_used: GAS_LIMIT,
+                status_code: StatusCode::OUT_OF_GAS as u64,
+                abort_code: None,
+                writes: 0,
+                events: 0,
+            }]
+        );
+    });
+}
+
+#[test]
+/// Calls rejected before reaching Move VM emit no execution result.
+fn no_execution_result_of_rejected_call() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+
+        let res = Mvm::execute(Origin::signed(bob_public_key()), vec![0; 8], GAS_LIMIT);
+        assert!(res.is_err());
+        assert!(execution_results().is_empty());
+    });
+}
@@ -0,0 +1,47 @@
+/// Regression tests of inputs found by fuzzing, see `fuzz/`.
+use std::path::PathBuf;
+
+mod common;
+use common::assets::{modules, transactions, USER_PACKAGE};
+use common::fuzz::TARGETS;
+
+#[test]
+/// Crashers put into `tests/assets/fuzz/<target>` don't panic the pallet anymore.
+fn fuzz_crashers() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/assets/fuzz");
+    for (name, target) in TARGETS {
+        let entries = match std::fs::read_dir(dir.join(name)) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries {
+            let path = entry.unwrap().path();
+            let data = std::fs::read(&path)
+                .unwrap_or_else(|_| panic!("Failed to read crasher: {:?}", path.display()));
+            eprintln!("{}: {:?}", name, path.display());
+            target(&data);
+        }
+    }
+}
+
+#[test]
+/// Truncated and corrupted assets are handled by every target.
+fn fuzz_malformed_assets() {
+    let assets = [
+        modules::user::STORE.bytes(),
+        USER_PACKAGE.bytes(),
+        transactions::STORE_U64.bytes(),
+    ];
+    for bytes in assets {
+        for (_, target) in TARGETS {
+            target(&[]);
+            target(bytes);
+            target(&bytes[..bytes.len() / 2]);
+
+            let mut corrupted = bytes.to_vec();
+            let middle = corrupted.len() / 2;
+            corrupted[middle] ^= 0xff;
+            target(&corrupted);
+        }
+    }
+}
@@ -1,4 +1,8 @@
 /// Tests related to gas and out of gas cases.
+use frame_support::weights::DispatchInfo;
+use sp_mvm::gas_price::ChargeGasPrice;
+use sp_runtime::traits::SignedExtension;
+use sp_runtime::transaction_validity::InvalidTransaction;
 use sp_runtime::{DispatchError, ModuleError};
 
 mod common;
@@ -62,3 +66,91 @@ fn execute_gas_limit() {
         check_out_of_gas(error);
     });
 }
+
+#[test]
+/// Check deadline bounds gas limit, so long execution goes out of gas.
+fn deadline_gas_limit() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        const GAS_LIMIT: u64 = 1_000_000;
+        let gas_per_millis = GAS_PER_SECOND / 1000;
+
+        assert_eq!(Mvm::deadline_gas_limit(GAS_LIMIT, None), GAS_LIMIT);
+        assert_eq!(Mvm::deadline_gas_limit(GAS_LIMIT, Some(1000)), GAS_LIMIT);
+        assert_eq!(Mvm::deadline_gas_limit(GAS_LIMIT, Some(10)), 10 * gas_per_millis);
+
+        let gas_limit = Mvm::deadline_gas_limit(u64::MAX, Some(10));
+        let res = utils::execute_tx(bob_public_key(), &transactions::INF_LOOP, Some(gas_limit));
+
+        let error = res.unwrap_err().error;
+        check_out_of_gas(error);
+    });
+}
+
+/// Move script call with the gas limit.
+fn execute_call(gas_limit: u64) -> Call {
+    Call::Mvm(sp_mvm::Call::execute {
+        tx_bc: transactions::STORE_U64.bytes().to_vec(),
+        gas_limit,
+    })
+}
+
+#[test]
+/// Gas unit price sets the priority of Move extrinsics.
+fn gas_price_priority() {
+    RuntimeBuilder::new()
+        .set_balances(vec![(bob_public_key(), CurrencyId::NATIVE, INITIAL_BALANCE)])
+        .build()
+        .execute_with(|| {
+            let bob = bob_public_key();
+            let info = DispatchInfo::default();
+            let priority = |call: &Call, gas_unit_price| {
+                ChargeGasPrice::<Test>::from(gas_unit_price)
+                    .validate(&bob, call, &info, 0)
+                    .unwrap()
+                    .priority
+            };
+
+            assert_eq!(priority(&execute_call(1_000), 0), 0);
+            assert_eq!(priority(&execute_call(1_000), 2), 2);
+            assert!(priority(&execute_call(1_000), 10) > priority(&execute_call(1_000), 2));
+
+            // Not a Move call.
+            let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+            assert_eq!(priority(&remark, 10), 0);
+
+            // Tip can't be paid.
+            assert_eq!(
+                ChargeGasPrice::<Test>::from(42).validate(&bob, &execute_call(1_000), &info, 0),
+                Err(InvalidTransaction::Payment.into())
+            );
+        });
+}
+
+#[test]
+/// Tip for the gas limit is charged before the dispatch.
+fn gas_price_tip_charged() {
+    RuntimeBuilder::new()
+        .set_balances(vec![(bob_public_key(), CurrencyId::NATIVE, INITIAL_BALANCE)])
+        .build()
+        .execute_with(|| {
+            let bob = bob_public_key();
+            let info = DispatchInfo::default();
+
+            ChargeGasPrice::<Test>::from(3)
+                .pre_dispatch(&bob, &execute_call(1_000), &info, 0)
+                .unwrap();
+            assert_eq!(Balances::free_balance(&bob), INITIAL_BALANCE - 3_000);
+
+            ChargeGasPrice::<Test>::from(0)
+                .pre_dispatch(&bob, &execute_call(1_000), &info, 0)
+                .unwrap();
+            assert_eq!(Balances::free_balance(&bob), INITIAL_BALANCE - 3_000);
+
+            // Other calls pay no tip.
+            let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+            ChargeGasPrice::<Test>::from(3)
+                .pre_dispatch(&bob, &remark, &info, 0)
+                .unwrap();
+            assert_eq!(Balances::free_balance(&bob), INITIAL_BALANCE - 3_000);
+        });
+}
@@ -0,0 +1,136 @@
+/// Gas regression corpus: exact gas used by Move VM calls, recorded in
+/// `tests/assets/gas/corpus.txt`.
+use std::collections::BTreeMap;
+use frame_support::dispatch::DispatchResultWithPostInfo as PsResult;
+
+mod common;
+use common::assets::{modules, transactions, USER_PACKAGE};
+use common::gas_corpus::{check, gas_used};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the corpus calls.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Gas limit of the calls running out of gas.
+const SMALL_GAS_LIMIT: u64 = 100_000;
+
+/// Corpus case: setup (its gas isn't measured) and the measured call.
+struct Case {
+    name: &'static str,
+    setup: fn(),
+    call: fn() -> PsResult,
+}
+
+fn no_setup() {}
+
+fn publish_store() {
+    utils::publish_module(bob_public_key(), &modules::user::STORE, Some(GAS_LIMIT)).unwrap();
+}
+
+fn publish_event_proxy() {
+    utils::publish_module(bob_public_key(), &modules::user::EVENT_PROXY, Some(GAS_LIMIT))
+        .unwrap();
+}
+
+/// Calls of the corpus, add new cases here and record them with `MVM_GAS_BLESS=1`.
+const CORPUS: &[Case] = &[
+    Case {
+        name: "publish_module_store",
+        setup: no_setup,
+        call: || utils::publish_module(bob_public_key(), &modules::user::STORE, Some(GAS_LIMIT)),
+    },
+    Case {
+        name: "publish_module_event_proxy",
+        setup: no_setup,
+        call: || {
+            utils::publish_module(bob_public_key(), &modules::user::EVENT_PROXY, Some(GAS_LIMIT))
+        },
+    },
+    Case {
+        name: "publish_module_root_store",
+        setup: no_setup,
+        call: || utils::publish_module_as_root(&modules::root::STORE, Some(GAS_LIMIT)),
+    },
+    Case {
+        name: "publish_package_user",
+        setup: no_setup,
+        call: || utils::publish_package(bob_public_key(), &USER_PACKAGE, Some(GAS_LIMIT)),
+    },
+    Case {
+        name: "execute_store_u64",
+        setup: publish_store,
+        call: || utils::execute_tx(bob_public_key(), &transactions::STORE_U64, Some(GAS_LIMIT)),
+    },
+    Case {
+        name: "execute_store_system_block",
+        setup: publish_store,
+        call: || {
+            utils::execute_tx(
+                bob_public_key(),
+                &transactions::STORE_SYSTEM_BLOCK,
+                Some(GAS_LIMIT),
+            )
+        },
+    },
+    Case {
+        name: "execute_store_system_timestamp",
+        setup: publish_store,
+        call: || {
+            utils::execute_tx(
+                bob_public_key(),
+                &transactions::STORE_SYSTEM_TIMESTAMP,
+                Some(GAS_LIMIT),
+            )
+        },
+    },
+    Case {
+        name: "execute_emit_event",
+        setup: publish_event_proxy,
+        call: || utils::execute_tx(bob_public_key(), &transactions::EMIT_EVENT, Some(GAS_LIMIT)),
+    },
+    Case {
+        name: "execute_missing_module",
+        setup: no_setup,
+        call: || utils::execute_tx(bob_public_key(), &transactions::STORE_U64, Some(GAS_LIMIT)),
+    },
+    Case {
+        name: "execute_out_of_gas",
+        setup: no_setup,
+        call: || {
+            utils::execute_tx(bob_public_key(), &transactions::INF_LOOP, Some(SMALL_GAS_LIMIT))
+        },
+    },
+];
+
+#[test]
+/// Gas used by the corpus calls matches the recorded one exactly.
+fn gas_corpus() {
+    let measured = CORPUS
+        .iter()
+        .map(|case| {
+            // Every case starts from the genesis state at the same block and time.
+            let gas = RuntimeBuilder::new().build().execute_with(|| {
+                roll_next_block();
+                (case.setup)();
+                gas_used((case.call)())
+            });
+            let gas = gas.unwrap_or_else(|| panic!("{}: call didn't reach the VM", case.name));
+            (case.name.to_owned(), gas)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    check(measured);
+}
+
+#[test]
+/// Gas is measured for failed calls too.
+fn gas_of_failed_call() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let res =
+            utils::execute_tx(bob_public_key(), &transactions::INF_LOOP, Some(SMALL_GAS_LIMIT));
+        assert!(res.is_err());
+        assert_eq!(gas_used(res), Some(SMALL_GAS_LIMIT));
+    });
+}
@@ -0,0 +1,33 @@
+/// Tests for the Move state snapshot imported in genesis.
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, CORE_CODE_ADDRESS};
+use sp_mvm::storage::{core_struct_tag, resource_key};
+use sp_mvm::VMStorage;
+
+mod common;
+use common::mock::*;
+
+fn module_key(name: &str) -> Vec<u8> {
+    ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(name).unwrap()).access_vector()
+}
+
+#[test]
+/// Snapshot entries are stored after the framework initialization and override its state.
+fn genesis_with_move_state() {
+    let resource = resource_key(&AccountAddress::new([1; 32]), &core_struct_tag("Store", "U64"));
+    let coin = module_key("Coin");
+
+    RuntimeBuilder::new()
+        .with_mock_framework()
+        .with_move_state(vec![
+            (resource.clone(), vec![42, 0, 0, 0, 0, 0, 0, 0]),
+            (coin.clone(), vec![1, 2, 3]),
+        ])
+        .build()
+        .execute_with(|| {
+            assert_eq!(VMStorage::<Test>::get(&resource), Some(vec![42, 0, 0, 0, 0, 0, 0, 0]));
+            assert_eq!(VMStorage::<Test>::get(&coin), Some(vec![1, 2, 3]));
+            assert!(VMStorage::<Test>::contains_key(module_key("Event")));
+        });
+}
@@ -0,0 +1,154 @@
+/// Tests related to the runtime governance proposals of Move code.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::weights::{Weight, constants::WEIGHT_PER_SECOND};
+use parity_scale_codec::Encode;
+use sp_mvm::types::GovernanceProposal;
+use sp_mvm::{Error, Event as MvmEvent, GovernanceProposals};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the callbacks in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Enactment weight covering the proposals in tests.
+const MAX_WEIGHT: Weight = WEIGHT_PER_SECOND;
+
+/// Storage item set by the proposal call.
+const KEY: &[u8] = b"governance";
+
+/// Stores the proposal of Bob setting `KEY`, as submitted by Move code.
+fn submit_proposal(id: u64, callback: Vec<u8>) {
+    let call = Call::System(frame_system::Call::set_storage {
+        items: vec![(KEY.to_vec(), b"enacted".to_vec())],
+    });
+    GovernanceProposals::<Test>::insert(
+        id,
+        GovernanceProposal {
+            proposer: bob_public_key(),
+            call: call.encode(),
+            callback,
+            callback_gas: GAS_LIMIT,
+        },
+    );
+}
+
+#[test]
+/// Enacted call is dispatched with the root origin, then the callback is executed.
+fn enact_proposal() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        submit_proposal(0, transactions::STORE_U64.bytes().to_vec());
+
+        assert_noop!(
+            Mvm::enact_governance_proposal(Origin::signed(bob_public_key()), 0, MAX_WEIGHT),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Mvm::enact_governance_proposal(Origin::root(), 0, MAX_WEIGHT));
+
+        assert_eq!(sp_io::storage::get(KEY), Some(b"enacted".to_vec()));
+        assert!(Sys::events().iter().any(
+            |rec| rec.event == Event::Mvm(MvmEvent::GovernanceProposalEnacted(0, true))
+        ));
+        // The callback is signed by the proposer.
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 1);
+        assert!(!GovernanceProposals::<Test>::contains_key(0));
+
+        assert_noop!(
+            Mvm::enact_governance_proposal(Origin::root(), 0, MAX_WEIGHT),
+            Error::<Test>::UnknownGovernanceProposal
+        );
+    });
+}
+
+#[test]
+/// Proposals heavier than the enactment weight are rejected and kept.
+fn enact_too_heavy_proposal() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        submit_proposal(0, transactions::STORE_U64.bytes().to_vec());
+
+        assert_noop!(
+            Mvm::enact_governance_proposal(Origin::root(), 0, 0),
+            Error::<Test>::GovernanceProposalTooHeavy
+        );
+        assert!(GovernanceProposals::<Test>::contains_key(0));
+    });
+}
+
+#[test]
+/// Failed call skips the callback, the proposal is removed anyway.
+fn enact_failed_proposal() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        let call = Call::Mvm(sp_mvm::Call::discard_governance_proposal { id: 1 });
+        GovernanceProposals::<Test>::insert(
+            0,
+            GovernanceProposal {
+                proposer: bob_public_key(),
+                call: call.encode(),
+                callback: transactions::STORE_U64.bytes().to_vec(),
+                callback_gas: GAS_LIMIT,
+            },
+        );
+
+        assert_ok!(Mvm::enact_governance_proposal(Origin::root(), 0, MAX_WEIGHT));
+        assert_eq!(
+            last_event(),
+            Event::Mvm(MvmEvent::GovernanceProposalEnacted(0, false))
+        );
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 0);
+        assert!(!GovernanceProposals::<Test>::contains_key(0));
+    });
+}
+
+#[test]
+/// Proposals with undecodable calls aren't enacted and are kept for discarding.
+fn enact_undecodable_proposal() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        GovernanceProposals::<Test>::insert(
+            0,
+            GovernanceProposal {
+                proposer: bob_public_key(),
+                call: vec![0xff, 0xff],
+                callback: vec![],
+                callback_gas: GAS_LIMIT,
+            },
+        );
+
+        assert_noop!(
+            Mvm::enact_governance_proposal(Origin::root(), 0, MAX_WEIGHT),
+            Error::<Test>::InvalidGovernanceProposalCall
+        );
+        assert!(GovernanceProposals::<Test>::contains_key(0));
+    });
+}
+
+#[test]
+/// Proposals are discarded by their proposer or governance.
+fn discard_proposal() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        submit_proposal(0, vec![]);
+        submit_proposal(1, vec![]);
+
+        assert_noop!(
+            Mvm::discard_governance_proposal(Origin::signed(alice_public_key()), 0),
+            Error::<Test>::NotGovernanceProposer
+        );
+        assert_ok!(Mvm::discard_governance_proposal(Origin::signed(bob_public_key()), 0));
+        assert_eq!(last_event(), Event::Mvm(MvmEvent::GovernanceProposalDiscarded(0)));
+        assert_ok!(Mvm::discard_governance_proposal(Origin::root(), 1));
+
+        assert_noop!(
+            Mvm::discard_governance_proposal(Origin::root(), 1),
+            Error::<Test>::UnknownGovernanceProposal
+        );
+    });
+}
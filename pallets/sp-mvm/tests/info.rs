@@ -0,0 +1,71 @@
+/// Tests related to the chain info for Move contracts.
+use serde::{Deserialize, Serialize};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use sp_mvm::info;
+
+mod common;
+use common::mock::*;
+use common::utils;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct ChainInfo {
+    parachain_id: u64,
+    relay_block: u64,
+    chain_name: Vec<u8>,
+}
+
+fn chain_info_tag() -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(info::CHAIN_INFO_MODULE).unwrap(),
+        name: Identifier::new(info::CHAIN_INFO_STRUCT).unwrap(),
+        type_params: vec![],
+    }
+}
+
+#[test]
+/// Struct tag of the chain info is encoded the same way as by Move VM.
+fn chain_info_tag_encoding() {
+    assert_eq!(info::chain_info_tag(), bcs::to_bytes(&chain_info_tag()).unwrap());
+}
+
+#[test]
+/// Chain info is encoded as BCS, including names longer than one byte of ULEB128 length.
+fn chain_info_resource_encoding() {
+    for name in [vec![], b"nox_mainnet".to_vec(), vec![b'a'; 300]] {
+        let expected = ChainInfo {
+            parachain_id: 2105,
+            relay_block: 12_345_678,
+            chain_name: name.clone(),
+        };
+        assert_eq!(
+            info::chain_info_resource(2105, 12_345_678, &name),
+            bcs::to_bytes(&expected).unwrap()
+        );
+    }
+}
+
+#[test]
+/// Chain info is stored with the chain name from genesis.
+fn chain_info_stored() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        sp_mvm::ChainName::<Test>::put(b"pontem_dev".to_vec());
+
+        Mvm::store_chain_info(2000, 42);
+        let expected = ChainInfo {
+            parachain_id: 2000,
+            relay_block: 42,
+            chain_name: b"pontem_dev".to_vec(),
+        };
+        utils::check_storage_res(CORE_CODE_ADDRESS, chain_info_tag(), expected);
+
+        Mvm::store_chain_info(2000, 43);
+        let expected = ChainInfo {
+            parachain_id: 2000,
+            relay_block: 43,
+            chain_name: b"pontem_dev".to_vec(),
+        };
+        utils::check_storage_res(CORE_CODE_ADDRESS, chain_info_tag(), expected);
+    });
+}
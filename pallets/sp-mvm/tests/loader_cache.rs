@@ -0,0 +1,75 @@
+/// Tests related to Move VM loader cache counters and flushes.
+use sp_mvm::{Event as MvmEvent, LoaderStats};
+
+mod common;
+use common::assets::{modules, USER_PACKAGE};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Checks `LoaderCacheFlushed` event is emitted with the amount of republished modules.
+fn assert_flushed(account: AccountId, modules: u32) {
+    let expected = Event::Mvm(MvmEvent::LoaderCacheFlushed(account, modules));
+    assert!(Sys::events().iter().any(|rec| rec.event == expected));
+}
+
+fn flushed() -> bool {
+    Sys::events()
+        .iter()
+        .any(|rec| matches!(rec.event, Event::Mvm(MvmEvent::LoaderCacheFlushed(..))))
+}
+
+#[test]
+/// Published modules are verified, republishing flushes the cache.
+fn republish_flushes_cache() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let bob = bob_public_key();
+
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        assert!(!flushed());
+        let stats = LoaderStats::<Test>::get();
+        // Dependencies loaded by the VM are verified too.
+        assert!(stats.verifier_runs >= 1);
+        assert_eq!(stats.flushes, 0);
+
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        assert_flushed(bob, 1);
+        let republished = LoaderStats::<Test>::get();
+        assert!(republished.verifier_runs > stats.verifier_runs);
+        assert_eq!(republished.flushes, 1);
+
+        // Counters are kept per block.
+        roll_next_block();
+        assert_eq!(LoaderStats::<Test>::get(), Default::default());
+    });
+}
+
+#[test]
+/// Package republishing flushes the cache once, with the amount of republished modules.
+fn republish_package_flushes_cache() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let bob = bob_public_key();
+
+        utils::publish_package(bob, &USER_PACKAGE, None).unwrap();
+        assert!(!flushed());
+
+        utils::publish_package(bob, &USER_PACKAGE, None).unwrap();
+        assert_flushed(bob, USER_PACKAGE.modules().len() as u32);
+        assert_eq!(LoaderStats::<Test>::get().flushes, 1);
+    });
+}
+
+#[test]
+/// Module published by the VM but rolled back by the pallet is flushed from the cache.
+fn rolled_back_publish_flushes_cache() {
+    ModuleDepositPerByte::set(1);
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+
+        assert!(utils::publish_module(bob_public_key(), &modules::user::STORE, None).is_err());
+        assert_eq!(LoaderStats::<Test>::get().flushes, 1);
+    });
+    ModuleDepositPerByte::set(0);
+}
@@ -0,0 +1,68 @@
+/// Tests related to type names of the calls in the metadata, used by generated clients.
+use scale_info::{TypeDef, TypeInfo};
+
+mod common;
+use common::mock::*;
+
+/// Type names of the call arguments, as recorded in the metadata.
+fn call_arguments(call: &str) -> Vec<(String, String)> {
+    let info = sp_mvm::Call::<Test>::type_info();
+    let variants = match info.type_def() {
+        TypeDef::Variant(def) => def.variants(),
+        _ => panic!("Call should be an enum"),
+    };
+    let variant = variants
+        .iter()
+        .find(|variant| variant.name() == call)
+        .unwrap_or_else(|| panic!("Call {} should exist", call));
+    variant
+        .fields()
+        .iter()
+        .map(|field| {
+            (
+                field.name().cloned().unwrap_or_default(),
+                field.type_name().cloned().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+fn args(args: &[(&str, &str)]) -> Vec<(String, String)> {
+    args.iter()
+        .map(|(name, ty)| (name.to_string(), ty.to_string()))
+        .collect()
+}
+
+#[test]
+/// Bytecode and gas arguments are named after their meaning, not `Vec<u8>` and `u64`.
+fn call_argument_type_names() {
+    assert_eq!(
+        call_arguments("execute"),
+        args(&[
+            ("tx_bc", "TransactionBytecode"),
+            ("gas_limit", "GasAmount"),
+        ])
+    );
+    assert_eq!(
+        call_arguments("publish_module"),
+        args(&[
+            ("module_bc", "ModuleBytecode"),
+            ("gas_limit", "GasAmount"),
+        ])
+    );
+    assert_eq!(
+        call_arguments("publish_package"),
+        args(&[
+            ("package", "PackageBytecode"),
+            ("gas_limit", "GasAmount"),
+        ])
+    );
+    assert_eq!(
+        call_arguments("remove_module"),
+        args(&[("name", "ModuleName")])
+    );
+    assert_eq!(
+        call_arguments("submit_ed25519_transaction"),
+        args(&[("signed_tx", "Ed25519SignedTransaction")])
+    );
+}
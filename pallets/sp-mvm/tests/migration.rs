@@ -0,0 +1,260 @@
+/// Tests related to migration of Move state between accounts.
+use serde::Deserialize;
+use frame_support::{assert_noop, assert_ok};
+use frame_support::assert_err_ignore_postinfo;
+use frame_support::traits::Get;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use move_vm::io::key::AccessKey;
+use sp_mvm::{Error, Event as MvmEvent, VMStorage};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct StoreU64 {
+    pub val: u64,
+}
+
+/// Tag of `Store::U64` resource published by Bob.
+fn store_u64_tag() -> StructTag {
+    StructTag {
+        address: origin_move_addr(),
+        module: Identifier::new(modules::user::STORE.name()).unwrap(),
+        name: Identifier::new("U64").unwrap(),
+        type_params: vec![],
+    }
+}
+
+/// Tag of resource which value embeds owner address.
+fn embedded_tag() -> StructTag {
+    StructTag {
+        address: origin_move_addr(),
+        module: Identifier::new(modules::user::STORE.name()).unwrap(),
+        name: Identifier::new("Owner").unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn resource_key(address: &AccountAddress, tag: &StructTag) -> Vec<u8> {
+    AccessKey::from((address, tag)).as_ref().to_vec()
+}
+
+/// Stores `Store::U64` resource under Bob's account. It's written to the storage directly, as
+/// Bob can't be migrated once `Store` module is published under his address.
+fn store_u64() {
+    VMStorage::<Test>::insert(
+        resource_key(&origin_move_addr(), &store_u64_tag()),
+        bcs::to_bytes(&42u64).unwrap(),
+    );
+}
+
+/// Requests migration from Bob to Alice and waits for the delay.
+fn request_and_wait() {
+    let now = Sys::block_number();
+    assert_ok!(Mvm::request_account_migration(
+        Origin::signed(bob_public_key()),
+        alice_public_key()
+    ));
+    roll_block_to(now + AccountMigrationDelay::get());
+}
+
+#[test]
+/// Migration moves resources only after the delay, and retires the old account.
+fn migrate_resources_after_delay() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        store_u64();
+        let tag = bcs::to_bytes(&store_u64_tag()).unwrap();
+
+        assert_ok!(Mvm::request_account_migration(
+            Origin::signed(bob_public_key()),
+            alice_public_key()
+        ));
+        assert_noop!(
+            Mvm::migrate_account(Origin::signed(bob_public_key()), vec![tag.clone()], false),
+            Error::<Test>::AccountMigrationTimelocked
+        );
+
+        roll_block_to(Sys::block_number() + AccountMigrationDelay::get());
+        assert_ok!(Mvm::migrate_account(
+            Origin::signed(bob_public_key()),
+            vec![tag.clone()],
+            false
+        ));
+
+        utils::check_storage_res(alice_move_addr(), store_u64_tag(), StoreU64 { val: 42 });
+        assert!(!VMStorage::<Test>::contains_key(resource_key(
+            &origin_move_addr(),
+            &store_u64_tag()
+        )));
+        assert_eq!(Mvm::migrated_to(bob_public_key()), Some(alice_public_key()));
+
+        let expected = [
+            MvmEvent::ResourceMigrated(bob_public_key(), alice_public_key(), tag),
+            MvmEvent::AccountMigrated(bob_public_key(), alice_public_key(), 1, 0),
+        ];
+        for event in expected {
+            let event: Event = event.into();
+            assert!(Sys::events().iter().any(|rec| rec.event == event));
+        }
+
+        // Old account can't publish modules or sign scripts anymore.
+        assert_err_ignore_postinfo!(
+            utils::publish_module(bob_public_key(), &modules::user::EVENT_PROXY, None),
+            Error::<Test>::AccountAlreadyMigrated
+        );
+        assert_err_ignore_postinfo!(
+            utils::execute_tx(bob_public_key(), &transactions::STORE_U64, None),
+            Error::<Test>::AccountAlreadyMigrated
+        );
+        assert_noop!(
+            Mvm::request_account_migration(Origin::signed(bob_public_key()), alice_public_key()),
+            Error::<Test>::AccountAlreadyMigrated
+        );
+    });
+}
+
+#[test]
+/// Migration can't target the same account, be requested twice or enacted after cancel.
+fn request_and_cancel_migration() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = Origin::signed(bob_public_key());
+
+        assert_noop!(
+            Mvm::request_account_migration(origin.clone(), bob_public_key()),
+            Error::<Test>::InvalidMigrationTarget
+        );
+
+        assert_ok!(Mvm::request_account_migration(origin.clone(), alice_public_key()));
+        assert_noop!(
+            Mvm::request_account_migration(origin.clone(), alice_public_key()),
+            Error::<Test>::AccountMigrationAlreadyRequested
+        );
+
+        assert_ok!(Mvm::cancel_account_migration(origin.clone()));
+        assert_noop!(
+            Mvm::cancel_account_migration(origin.clone()),
+            Error::<Test>::AccountMigrationNotRequested
+        );
+        assert_noop!(
+            Mvm::migrate_account(origin, vec![], false),
+            Error::<Test>::AccountMigrationNotRequested
+        );
+    });
+}
+
+#[test]
+/// Resources which embed the old address are rejected or skipped.
+fn migrate_embedded_resources() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        store_u64();
+        VMStorage::<Test>::insert(
+            resource_key(&origin_move_addr(), &embedded_tag()),
+            origin_move_addr().to_vec(),
+        );
+        request_and_wait();
+
+        let origin = Origin::signed(bob_public_key());
+        let tag = bcs::to_bytes(&store_u64_tag()).unwrap();
+        let embedded = bcs::to_bytes(&embedded_tag()).unwrap();
+
+        assert_noop!(
+            Mvm::migrate_account(origin.clone(), vec![tag.clone(), vec![0; 4]], false),
+            Error::<Test>::MigrationResourceNotFound
+        );
+        assert_noop!(
+            Mvm::migrate_account(origin.clone(), vec![tag.clone(), embedded.clone()], false),
+            Error::<Test>::MigrationResourceEmbedsAddress
+        );
+
+        assert_ok!(Mvm::migrate_account(origin, vec![tag, embedded.clone()], true));
+        assert!(VMStorage::<Test>::contains_key(resource_key(
+            &origin_move_addr(),
+            &embedded_tag()
+        )));
+        assert!(VMStorage::<Test>::contains_key(resource_key(
+            &alice_move_addr(),
+            &store_u64_tag()
+        )));
+
+        let expected: Event =
+            MvmEvent::ResourceMigrationSkipped(bob_public_key(), embedded).into();
+        assert!(Sys::events().iter().any(|rec| rec.event == expected));
+    });
+}
+
+#[test]
+/// Migration doesn't overwrite resources of the new account.
+fn migrate_to_occupied_account() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        store_u64();
+        VMStorage::<Test>::insert(
+            resource_key(&alice_move_addr(), &store_u64_tag()),
+            bcs::to_bytes(&1u64).unwrap(),
+        );
+        request_and_wait();
+
+        assert_noop!(
+            Mvm::migrate_account(
+                Origin::signed(bob_public_key()),
+                vec![bcs::to_bytes(&store_u64_tag()).unwrap()],
+                false
+            ),
+            Error::<Test>::MigrationTargetOccupied
+        );
+    });
+}
+
+#[test]
+/// Migrated account can't be a signer in the execution paths resolving signers without the
+/// origin, e.g. of multisig scripts, session keys or scheduled scripts.
+fn migrated_account_is_not_signer() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        request_and_wait();
+        assert_ok!(Mvm::migrate_account(Origin::signed(bob_public_key()), vec![], false));
+
+        let tx_bc = transactions::STORE_U64.bytes().to_vec();
+        assert!(matches!(
+            Mvm::raw_execute_script(&[bob_public_key()], tx_bc, 1_000_000, false, true),
+            Err(Error::<Test>::AccountAlreadyMigrated)
+        ));
+        let module_bc = modules::user::STORE.bytes().to_vec();
+        assert!(matches!(
+            Mvm::raw_publish_module(&bob_public_key(), module_bc, 1_000_000, true),
+            Err(Error::<Test>::AccountAlreadyMigrated)
+        ));
+    });
+}
+
+#[test]
+/// Accounts with published modules can't be migrated, as modules are bound to the address.
+fn publisher_is_not_migrated() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let origin = Origin::signed(bob_public_key());
+
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        assert_noop!(
+            Mvm::request_account_migration(origin.clone(), alice_public_key()),
+            Error::<Test>::MigrationOfPublisher
+        );
+
+        assert_ok!(Mvm::remove_module(origin.clone(), b"Store".to_vec()));
+        assert_eq!(Mvm::published_modules(origin_move_addr().to_u8()), 0);
+        request_and_wait();
+
+        // Module published after the request.
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        assert_noop!(
+            Mvm::migrate_account(origin, vec![], false),
+            Error::<Test>::MigrationOfPublisher
+        );
+    });
+}
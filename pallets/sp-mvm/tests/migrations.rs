@@ -0,0 +1,139 @@
+/// Tests related to the storage and Move resources migrations.
+use serde::Deserialize;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use sp_mvm::migrations::{check_state, MigrateResources, ResourceMigration};
+use sp_mvm::{ModuleDeposits, PublishedModules, ResourcesVersion, VMStorage};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct StoreU64 {
+    pub val: u64,
+}
+
+fn store_u64_tag() -> StructTag {
+    StructTag {
+        address: origin_move_addr(),
+        module: Identifier::new(modules::user::STORE.name()).unwrap(),
+        name: Identifier::new("U64").unwrap(),
+        type_params: vec![],
+    }
+}
+
+/// Doubles stored `Store::U64` value.
+struct DoubleU64;
+
+impl ResourceMigration for DoubleU64 {
+    const VERSION: u32 = 1;
+
+    fn struct_tag() -> Vec<u8> {
+        bcs::to_bytes(&store_u64_tag()).unwrap()
+    }
+
+    fn migrate(owner: &AccountAddress, resource: Vec<u8>) -> Option<Vec<u8>> {
+        assert_eq!(*owner, origin_move_addr());
+        let val: u64 = bcs::from_bytes(&resource).ok()?;
+        bcs::to_bytes(&(val * 2)).ok()
+    }
+}
+
+#[test]
+/// Resources of the struct are rewritten once.
+fn migrate_resources() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = bob_public_key();
+        utils::publish_module(origin, &modules::user::STORE, None).unwrap();
+        utils::execute_tx(origin, &transactions::STORE_U64, None).unwrap();
+        utils::check_storage_res(origin_move_addr(), store_u64_tag(), StoreU64 { val: 42 });
+
+        MigrateResources::<Test, DoubleU64>::on_runtime_upgrade();
+        utils::check_storage_res(origin_move_addr(), store_u64_tag(), StoreU64 { val: 84 });
+        assert_eq!(ResourcesVersion::<Test>::get(), 1);
+
+        // Already applied.
+        MigrateResources::<Test, DoubleU64>::on_runtime_upgrade();
+        utils::check_storage_res(origin_move_addr(), store_u64_tag(), StoreU64 { val: 84 });
+    });
+}
+
+#[test]
+/// Pallet storage is migrated to the current version on the first upgrade.
+fn migrate_storage_version() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        StorageVersion::new(0).put::<Mvm>();
+
+        Mvm::on_runtime_upgrade();
+        assert_eq!(Mvm::on_chain_storage_version(), StorageVersion::new(2));
+        assert_eq!(Mvm::current_storage_version(), StorageVersion::new(2));
+    });
+}
+
+#[test]
+/// Modules published before the upgrade are counted by the publishers.
+fn migrate_published_modules() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        assert_eq!(Mvm::published_modules(origin_move_addr().to_u8()), 1);
+
+        let _ = PublishedModules::<Test>::remove_all(None);
+        assert!(check_state::<Test>().is_err());
+        StorageVersion::new(1).put::<Mvm>();
+
+        Mvm::on_runtime_upgrade();
+        assert_eq!(Mvm::published_modules(origin_move_addr().to_u8()), 1);
+        check_state::<Test>().unwrap();
+    });
+}
+
+fn store_module_key() -> Vec<u8> {
+    ModuleId::new(
+        origin_move_addr(),
+        Identifier::new(modules::user::STORE.name()).unwrap(),
+    )
+    .access_vector()
+}
+
+#[test]
+/// Published modules pass the state checks.
+fn check_state_valid() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let genesis_modules = check_state::<Test>().unwrap();
+        assert!(genesis_modules > 0);
+
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        assert_eq!(check_state::<Test>().unwrap(), genesis_modules + 1);
+    });
+}
+
+#[test]
+/// Corrupted module bytecode fails the state checks.
+fn check_state_corrupted_module() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+
+        VMStorage::<Test>::insert(store_module_key(), vec![0xa1, 0x1c, 0xeb, 0x0b, 0xff]);
+        assert!(check_state::<Test>().is_err());
+    });
+}
+
+#[test]
+/// Deposit of the removed module fails the state checks.
+fn check_state_dangling_deposit() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        assert!(ModuleDeposits::<Test>::contains_key(store_module_key()));
+
+        VMStorage::<Test>::remove(store_module_key());
+        assert_eq!(
+            check_state::<Test>(),
+            Err("Module deposit refers to the missing module")
+        );
+    });
+}
@@ -0,0 +1,28 @@
+/// Tests related to the mock Move framework.
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, CORE_CODE_ADDRESS};
+
+mod common;
+use common::assets::MOCK_FRAMEWORK;
+use common::mock::*;
+
+fn is_published(name: &str) -> bool {
+    let module_id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(name).unwrap());
+    Mvm::get_module(&bcs::to_bytes(&module_id).unwrap())
+        .unwrap()
+        .is_some()
+}
+
+#[test]
+/// Mock framework replaces the stdlib in genesis.
+fn genesis_with_mock_framework() {
+    RuntimeBuilder::new()
+        .with_mock_framework()
+        .build()
+        .execute_with(|| {
+            for name in MOCK_FRAMEWORK.modules() {
+                assert!(is_published(name), "Module {} should be published", name);
+            }
+            assert!(!is_published("NOX"));
+        });
+}
@@ -1,7 +1,14 @@
 /// Tests related to modules/packages publishing.
+use std::convert::TryFrom;
 use frame_support::assert_err_ignore_postinfo;
 use frame_support::dispatch::DispatchError;
+use frame_support::traits::Get;
 use sp_runtime::ModuleError;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::vm_status::StatusCode;
+use move_vm::types::ModulePackage;
+use sp_mvm::VMStorage;
 
 mod common;
 use common::assets::{modules, ROOT_PACKAGE, USER_PACKAGE};
@@ -62,3 +69,38 @@ fn publish_package_as_root() {
         utils::publish_package_as_root(package, None).unwrap();
     });
 }
+
+#[test]
+/// Estimate package publishing with dry run, nothing is published.
+fn estimate_package_publish() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = bob_public_key();
+        let (modules, _) = ModulePackage::try_from(USER_PACKAGE.bytes())
+            .unwrap()
+            .into_tx(origin_move_addr())
+            .into_inner();
+
+        let vm_result = Mvm::raw_publish_package(&origin, modules, 1_000_000, true).unwrap();
+        assert_eq!(vm_result.status_code, StatusCode::EXECUTED);
+        assert!(vm_result.gas_used > 0);
+
+        let store_key = ModuleId::new(origin_move_addr(), Identifier::new("Store").unwrap())
+            .access_vector();
+        assert!(!VMStorage::<Test>::contains_key(&store_key));
+
+        utils::publish_package(origin, &USER_PACKAGE, None).unwrap();
+        assert!(VMStorage::<Test>::contains_key(&store_key));
+    });
+}
+
+#[test]
+/// Oversized module is rejected before verification.
+fn publish_too_large_module() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let module_bc = vec![0; MaxModuleSize::get() as usize + 1];
+        assert_err_ignore_postinfo!(
+            Mvm::publish_module(Origin::signed(bob_public_key()), module_bc, 1_000_000),
+            sp_mvm::Error::<Test>::ModuleTooLarge
+        );
+    });
+}
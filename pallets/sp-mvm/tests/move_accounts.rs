@@ -0,0 +1,36 @@
+/// Tests related to the accounts of Move addresses.
+use sp_mvm::addr::account_to_bytes;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+#[test]
+/// Accounts are recorded by the first executed script or published module.
+fn accounts_recorded_on_first_call() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bob = account_to_bytes(&bob_public_key());
+        let alice = account_to_bytes(&alice_public_key());
+        assert_eq!(Mvm::resolve_move_address(bob), None);
+
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        assert_eq!(Mvm::resolve_move_address(bob), Some(bob_public_key()));
+        assert_eq!(Mvm::resolve_move_address(alice), None);
+
+        utils::execute_tx(alice_public_key(), &transactions::STORE_U64, None).unwrap();
+        assert_eq!(Mvm::resolve_move_address(alice), Some(alice_public_key()));
+        assert_eq!(Mvm::move_account(bob), Some(bob_public_key()));
+    });
+}
+
+#[test]
+/// Failed scripts don't record the signer.
+fn failed_script_not_recorded() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        // Module used by the script is not published.
+        assert!(utils::execute_tx(alice_public_key(), &transactions::STORE_U64, None).is_err());
+        assert_eq!(Mvm::resolve_move_address(account_to_bytes(&alice_public_key())), None);
+    });
+}
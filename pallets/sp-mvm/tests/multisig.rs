@@ -6,7 +6,8 @@ use common::addr::{alice_public_key, bob_public_key};
 use common::assets::transactions;
 
 use sp_mvm::Call as MvmCall;
-use frame_support::{assert_ok};
+use sp_mvm::{Error, MultisigProposals, MultisigScripts};
+use frame_support::{assert_err_ignore_postinfo, assert_noop, assert_ok};
 use sp_runtime::codec::Encode;
 use sp_core::Pair;
 use sp_std::vec;
@@ -68,3 +69,172 @@ fn execute_groupsign() {
         ));
     });
 }
+
+/// Account which isn't a signer of `MULTISIG_TEST`.
+fn eve_public_key() -> AccountId {
+    sp_core::sr25519::Public::from_raw([7; 32])
+}
+
+/// Hash of the `MULTISIG_TEST` proposal signed by Alice and Bob.
+fn multisig_test_hash(gas_limit: u64) -> [u8; 32] {
+    sp_mvm::types::multisig_script_hash(
+        transactions::MULTISIG_TEST.bytes(),
+        gas_limit,
+        &[alice_public_key(), bob_public_key()],
+    )
+}
+
+fn propose_multisig_test(gas_limit: u64) -> [u8; 32] {
+    assert_ok!(Mvm::propose_multisig_script(
+        Origin::signed(alice_public_key()),
+        transactions::MULTISIG_TEST.bytes().to_vec(),
+        gas_limit,
+        vec![alice_public_key(), bob_public_key()],
+    ));
+    multisig_test_hash(gas_limit)
+}
+
+#[test]
+/// Script with two signers is executed once approved by both of them.
+fn execute_multisig_script() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+
+        let hash = propose_multisig_test(1_000_000);
+        assert_noop!(
+            Mvm::approve_multisig_script(Origin::signed(alice_public_key()), hash, 1_000_000),
+            Error::<Test>::MultisigScriptAlreadyApproved
+        );
+
+        assert_ok!(Mvm::approve_multisig_script(
+            Origin::signed(bob_public_key()),
+            hash,
+            1_000_000
+        ));
+        assert!(!MultisigScripts::<Test>::contains_key(hash));
+        let expected = Event::Mvm(MoveEvent::MultisigScriptExecuted(hash));
+        assert!(Sys::events().iter().any(|rec| rec.event == expected));
+    });
+}
+
+#[test]
+/// Final approval must cover the script gas limit.
+fn approve_multisig_script_gas_limit() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let hash = propose_multisig_test(1_000_000);
+
+        assert_err_ignore_postinfo!(
+            Mvm::approve_multisig_script(Origin::signed(bob_public_key()), hash, 1_000),
+            Error::<Test>::MultisigGasLimitTooLow
+        );
+        assert_eq!(
+            MultisigScripts::<Test>::get(hash).unwrap().approvals,
+            vec![alice_public_key()]
+        );
+    });
+}
+
+#[test]
+/// Signers must match the script signers count and include the proposer.
+fn propose_multisig_script_signers() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bytecode = transactions::MULTISIG_TEST.bytes().to_vec();
+        let origin = Origin::signed(alice_public_key());
+
+        assert_noop!(
+            Mvm::propose_multisig_script(
+                origin.clone(),
+                bytecode.clone(),
+                1_000_000,
+                vec![alice_public_key()]
+            ),
+            Error::<Test>::TransactionSignersNumError
+        );
+        assert_noop!(
+            Mvm::propose_multisig_script(
+                origin.clone(),
+                bytecode.clone(),
+                1_000_000,
+                vec![alice_public_key(), alice_public_key()]
+            ),
+            Error::<Test>::InvalidMultisigSigners
+        );
+        assert_noop!(
+            Mvm::propose_multisig_script(
+                Origin::signed(eve_public_key()),
+                bytecode,
+                1_000_000,
+                vec![alice_public_key(), bob_public_key()]
+            ),
+            Error::<Test>::InvalidMultisigSigners
+        );
+    });
+}
+
+#[test]
+/// Only proposer can cancel the script, other accounts can't approve it.
+fn cancel_multisig_script() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let hash = propose_multisig_test(1_000_000);
+
+        assert_noop!(
+            Mvm::approve_multisig_script(Origin::signed(eve_public_key()), hash, 1_000_000),
+            Error::<Test>::NotMultisigSigner
+        );
+        assert_noop!(
+            Mvm::cancel_multisig_script(Origin::signed(bob_public_key()), hash),
+            Error::<Test>::NotMultisigProposer
+        );
+
+        assert_ok!(Mvm::cancel_multisig_script(
+            Origin::signed(alice_public_key()),
+            hash
+        ));
+        assert_noop!(
+            Mvm::approve_multisig_script(Origin::signed(bob_public_key()), hash, 1_000_000),
+            Error::<Test>::UnknownMultisigScript
+        );
+    });
+}
+
+#[test]
+/// Proposer reserves the deposit until the script is closed, open scripts are limited.
+fn multisig_deposit_and_limit() {
+    MultisigDeposit::set(1_000);
+    RuntimeBuilder::new()
+        .set_balances(vec![
+            (alice_public_key(), CurrencyId::NATIVE, UNIT),
+            (bob_public_key(), CurrencyId::NATIVE, UNIT),
+        ])
+        .build()
+        .execute_with(|| {
+            roll_next_block();
+            let alice = alice_public_key();
+
+            let cancelled = propose_multisig_test(1_000_000);
+            let executed = propose_multisig_test(2_000_000);
+            assert_eq!(Balances::reserved_balance(alice), 2_000);
+            assert_eq!(MultisigProposals::<Test>::get(alice), 2);
+            assert_noop!(
+                Mvm::propose_multisig_script(
+                    Origin::signed(alice),
+                    transactions::MULTISIG_TEST.bytes().to_vec(),
+                    3_000_000,
+                    vec![alice, bob_public_key()],
+                ),
+                Error::<Test>::TooManyMultisigProposals
+            );
+
+            assert_ok!(Mvm::cancel_multisig_script(Origin::signed(alice), cancelled));
+            assert_eq!(Balances::reserved_balance(alice), 1_000);
+
+            assert_ok!(Mvm::approve_multisig_script(
+                Origin::signed(bob_public_key()),
+                executed,
+                2_000_000
+            ));
+            assert_eq!(Balances::reserved_balance(alice), 0);
+            assert!(!MultisigProposals::<Test>::contains_key(alice));
+        });
+    MultisigDeposit::set(0);
+}
@@ -0,0 +1,104 @@
+/// Tests related to package metadata.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use sp_mvm::types::{PackageDependency, PackageInfo, PackageMetadata};
+use sp_mvm::{Error, Event as MvmEvent, Packages};
+
+mod common;
+use common::assets::USER_PACKAGE;
+use common::mock::*;
+use common::addr::*;
+
+/// Gas limit of the packages in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+fn info(name: &str, dependencies: Vec<PackageDependency<AccountId>>) -> PackageInfo<AccountId> {
+    PackageInfo {
+        name: name.as_bytes().to_vec(),
+        version: b"1.0.0".to_vec(),
+        dependencies,
+        source_digest: vec![0xab; 32],
+    }
+}
+
+fn publish(info: PackageInfo<AccountId>) -> DispatchResultWithPostInfo {
+    Mvm::publish_package_with_metadata(
+        Origin::signed(bob_public_key()),
+        USER_PACKAGE.bytes().to_vec(),
+        info,
+        GAS_LIMIT,
+    )
+}
+
+#[test]
+/// Metadata is stored with the package modules, republishing is an upgrade.
+fn publish_package_with_metadata() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bob = bob_public_key();
+
+        assert_ok!(publish(info("assets", vec![])));
+        let mut metadata = Packages::<Test>::get(bob, b"assets".to_vec()).unwrap();
+        metadata.modules.sort();
+        assert_eq!(
+            metadata,
+            PackageMetadata {
+                name: b"assets".to_vec(),
+                version: b"1.0.0".to_vec(),
+                upgrade_number: 0,
+                dependencies: vec![],
+                source_digest: vec![0xab; 32],
+                modules: USER_PACKAGE
+                    .modules()
+                    .iter()
+                    .map(|name| name.as_bytes().to_vec())
+                    .collect(),
+            }
+        );
+        let expected = Event::Mvm(MvmEvent::PackageMetadataStored(bob, b"assets".to_vec(), 0));
+        assert!(Sys::events().iter().any(|rec| rec.event == expected));
+
+        assert_ok!(publish(info("assets", vec![])));
+        assert_eq!(Mvm::get_package(&bob, b"assets").unwrap().upgrade_number, 1);
+
+        // The same modules published as another package depending on the first one.
+        let dependency = PackageDependency {
+            account: bob,
+            name: b"assets".to_vec(),
+        };
+        assert_ok!(publish(info("assets-v2", vec![dependency.clone()])));
+        let metadata = Mvm::get_package(&bob, b"assets-v2").unwrap();
+        assert_eq!(metadata.dependencies, vec![dependency]);
+    });
+}
+
+#[test]
+/// Metadata is validated before the package is published.
+fn invalid_package_metadata() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bob = bob_public_key();
+
+        assert_noop!(publish(info("", vec![])), Error::<Test>::InvalidPackageMetadata);
+        let mut too_large = info("assets", vec![]);
+        too_large.source_digest = vec![0; 4096];
+        assert_noop!(publish(too_large), Error::<Test>::InvalidPackageMetadata);
+
+        let dependency = PackageDependency {
+            account: alice_public_key(),
+            name: b"framework".to_vec(),
+        };
+        assert_noop!(
+            publish(info("assets", vec![dependency])),
+            Error::<Test>::UnknownPackageDependency
+        );
+
+        // Upgrade must keep all modules of the published package.
+        assert_ok!(publish(info("assets", vec![])));
+        Packages::<Test>::mutate(bob, b"assets".to_vec(), |metadata| {
+            metadata.as_mut().unwrap().modules.push(b"Removed".to_vec())
+        });
+        assert_noop!(
+            publish(info("assets", vec![])),
+            Error::<Test>::PackageModulesRemoved
+        );
+    });
+}
@@ -0,0 +1,63 @@
+/// Tests related to storage keys of the Move state.
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use parity_scale_codec::Decode;
+use sp_mvm::proof;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Reads SCALE encoded bytes stored under the trie key.
+fn read_trie_value(key: &[u8]) -> Option<Vec<u8>> {
+    sp_io::storage::get(key).map(|value| Vec::<u8>::decode(&mut &value[..]).unwrap())
+}
+
+#[test]
+/// Resource is stored under its trie key.
+fn resource_storage_key() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bob = bob_public_key();
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        utils::execute_tx(bob, &transactions::STORE_U64, None).unwrap();
+
+        let tag = bcs::to_bytes(&StructTag {
+            address: origin_move_addr(),
+            module: Identifier::new(modules::user::STORE.name()).unwrap(),
+            name: Identifier::new("U64").unwrap(),
+            type_params: vec![],
+        })
+        .unwrap();
+        let key = proof::account_resource_storage_key::<Test>(&bob, &tag);
+        assert_eq!(key, proof::resource_storage_key::<Test>(&origin_move_addr(), &tag));
+
+        let resource = Mvm::get_resource(&bob, &tag).unwrap();
+        assert!(resource.is_some());
+        assert_eq!(read_trie_value(&key), resource);
+
+        // Resource of another account isn't stored.
+        let alice_key = proof::account_resource_storage_key::<Test>(&alice_public_key(), &tag);
+        assert_eq!(read_trie_value(&alice_key), None);
+    });
+}
+
+#[test]
+/// Module is stored under its trie key.
+fn module_storage_key() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+
+        let module_id = ModuleId::new(
+            origin_move_addr(),
+            Identifier::new(modules::user::STORE.name()).unwrap(),
+        );
+        let key = proof::module_storage_key::<Test>(&module_id);
+        assert_eq!(read_trie_value(&key), Some(modules::user::STORE.bytes().to_vec()));
+
+        // Key by the BCS encoded id, as served by the runtime API.
+        let encoded_id = bcs::to_bytes(&module_id).unwrap();
+        assert_eq!(proof::encoded_module_storage_key::<Test>(&encoded_id), key);
+    });
+}
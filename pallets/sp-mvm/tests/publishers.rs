@@ -0,0 +1,86 @@
+/// Tests related to module publishing permissions.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::assert_err_ignore_postinfo;
+use sp_runtime::DispatchError;
+use sp_mvm::Error;
+
+mod common;
+use common::assets::{modules, USER_PACKAGE};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+#[test]
+/// Only `UpdateOrigin` can manage publishing permissions.
+fn manage_publishers_requires_update_origin() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = Origin::signed(bob_public_key());
+
+        assert_noop!(
+            Mvm::set_open_publishing(origin.clone(), false),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Mvm::allow_publisher(origin.clone(), bob_public_key()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Mvm::disallow_publisher(origin, bob_public_key()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+/// Add and remove publisher.
+fn allow_and_disallow_publisher() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        assert_ok!(Mvm::allow_publisher(Origin::root(), bob_public_key()));
+        assert_noop!(
+            Mvm::allow_publisher(Origin::root(), bob_public_key()),
+            Error::<Test>::PublisherAlreadyAllowed
+        );
+
+        assert_ok!(Mvm::disallow_publisher(Origin::root(), bob_public_key()));
+        assert_noop!(
+            Mvm::disallow_publisher(Origin::root(), bob_public_key()),
+            Error::<Test>::UnknownPublisher
+        );
+    });
+}
+
+#[test]
+/// Publishing is open by default.
+fn open_publishing_by_default() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        assert!(Mvm::open_publishing());
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+    });
+}
+
+#[test]
+/// Once publishing is closed, only allowlisted accounts and root can publish.
+fn closed_publishing() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        assert_ok!(Mvm::set_open_publishing(Origin::root(), false));
+
+        assert_err_ignore_postinfo!(
+            utils::publish_module(bob_public_key(), &modules::user::STORE, None),
+            Error::<Test>::PublisherNotAllowed
+        );
+        assert_err_ignore_postinfo!(
+            utils::publish_package(bob_public_key(), &USER_PACKAGE, None),
+            Error::<Test>::PublisherNotAllowed
+        );
+        utils::publish_module_as_root(&modules::root::EVENT_PROXY, None).unwrap();
+
+        assert_ok!(Mvm::allow_publisher(Origin::root(), bob_public_key()));
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+
+        assert_ok!(Mvm::disallow_publisher(Origin::root(), bob_public_key()));
+        assert_err_ignore_postinfo!(
+            utils::publish_module(bob_public_key(), &modules::user::STORE, None),
+            Error::<Test>::PublisherNotAllowed
+        );
+    });
+}
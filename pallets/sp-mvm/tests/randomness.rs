@@ -0,0 +1,56 @@
+/// Tests related to the randomness seed for Move contracts.
+use serde::Deserialize;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use sp_mvm::randomness;
+
+mod common;
+use common::mock::*;
+use common::utils;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Seed {
+    seed: Vec<u8>,
+    block: u64,
+}
+
+fn seed_tag() -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(randomness::RANDOMNESS_MODULE).unwrap(),
+        name: Identifier::new(randomness::SEED_STRUCT).unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn current_seed() -> Seed {
+    let (seed, _) = <MockRandomness as frame_support::traits::Randomness<_, _>>::random(
+        randomness::RANDOMNESS_SUBJECT,
+    );
+    Seed {
+        seed: seed.as_ref().to_vec(),
+        block: Sys::block_number(),
+    }
+}
+
+#[test]
+/// Struct tag of the seed is encoded the same way as by Move VM.
+fn seed_tag_encoding() {
+    assert_eq!(randomness::seed_tag(), bcs::to_bytes(&seed_tag()).unwrap());
+}
+
+#[test]
+/// Seed is updated every block.
+fn seed_stored_every_block() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let first = current_seed();
+        utils::check_storage_res(CORE_CODE_ADDRESS, seed_tag(), current_seed());
+
+        roll_next_block();
+        let second = current_seed();
+        utils::check_storage_res(CORE_CODE_ADDRESS, seed_tag(), current_seed());
+
+        assert_ne!(first.seed, second.seed);
+    });
+}
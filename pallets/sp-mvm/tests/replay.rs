@@ -0,0 +1,118 @@
+/// Tests related to replay bundles.
+use std::path::PathBuf;
+use sp_mvm::replay::{ReplayBundle, ReplayCall, ReplayError, ReplayOutcome, REPLAY_MAGIC};
+use sp_mvm::VMStorage;
+use frame_support::traits::OnFinalize;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::replay::{load, replay};
+use common::utils;
+
+/// Default gas limit.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Builds bundle on top of the state with `Store` module published by Bob.
+fn store_bundle(call: ReplayCall, expected: ReplayOutcome) -> ReplayBundle {
+    RuntimeBuilder::new().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        Mvm::on_finalize(Sys::block_number());
+
+        ReplayBundle {
+            block_number: 1,
+            timestamp: TIME_BLOCK_MULTIPLIER,
+            state: VMStorage::<Test>::iter().collect(),
+            balances: vec![],
+            call,
+            expected,
+        }
+    })
+}
+
+#[test]
+/// Bundles from `tests/assets/replays` reproduce outcomes recorded on chain.
+fn replay_bundles() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/assets/replays");
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map_or(true, |ext| ext != "replay") {
+            continue;
+        }
+
+        let bundle = load(&path);
+        assert_eq!(replay(&bundle), bundle.expected, "Replay of {:?}", path);
+    }
+}
+
+#[test]
+/// Bundle call is executed on top of the bundle state.
+fn replay_execute() {
+    let bundle = store_bundle(
+        ReplayCall::Execute {
+            signer: Some(bob_public_key().0),
+            tx_bc: transactions::STORE_U64.bytes().to_vec(),
+            gas_limit: GAS_LIMIT,
+        },
+        ReplayOutcome::Success,
+    );
+    assert_eq!(replay(&bundle), ReplayOutcome::Success);
+
+    // Script can't be executed without the module.
+    let bundle = ReplayBundle {
+        state: vec![],
+        ..bundle
+    };
+    assert_ne!(replay(&bundle), ReplayOutcome::Success);
+}
+
+#[test]
+/// Pallet errors are recorded by index.
+fn replay_publish_error() {
+    let bundle = store_bundle(
+        ReplayCall::PublishModule {
+            signer: Some(bob_public_key().0),
+            module_bc: modules::root::EVENT_PROXY.bytes().to_vec(),
+            gas_limit: GAS_LIMIT,
+        },
+        // ModuleAddressDoesNotMatchSender
+        ReplayOutcome::MvmError(89),
+    );
+    assert_eq!(replay(&bundle), bundle.expected);
+}
+
+#[test]
+/// Bundle is encoded with the magic and version header.
+fn bundle_encoding() {
+    let bundle = ReplayBundle {
+        block_number: 42,
+        timestamp: 4200,
+        state: vec![(vec![1, 2], vec![3])],
+        balances: vec![([1; 32], 1000)],
+        call: ReplayCall::Execute {
+            signer: None,
+            tx_bc: vec![0; 8],
+            gas_limit: GAS_LIMIT,
+        },
+        expected: ReplayOutcome::OtherError(b"BadOrigin".to_vec()),
+    };
+
+    let bytes = bundle.to_bytes();
+    assert!(bytes.starts_with(&REPLAY_MAGIC));
+    assert_eq!(ReplayBundle::from_bytes(&bytes), Ok(bundle));
+
+    assert_eq!(ReplayBundle::from_bytes(&bytes[1..]), Err(ReplayError::InvalidMagic));
+
+    let mut unsupported = bytes.clone();
+    unsupported[REPLAY_MAGIC.len()] = 0;
+    assert_eq!(
+        ReplayBundle::from_bytes(&unsupported),
+        Err(ReplayError::UnsupportedVersion(0))
+    );
+
+    assert_eq!(
+        ReplayBundle::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(ReplayError::InvalidBundle)
+    );
+}
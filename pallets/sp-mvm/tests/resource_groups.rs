@@ -0,0 +1,111 @@
+/// Tests related to resource groups: resources packed into one storage item.
+use move_core_types::identifier::Identifier;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::language_storage::{ModuleId, StructTag, CORE_CODE_ADDRESS};
+use parity_scale_codec::Decode;
+use sp_mvm::{block, proof, randomness, storage};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Reads SCALE encoded bytes stored under the trie key.
+fn read_trie_value(key: &[u8]) -> Option<Vec<u8>> {
+    sp_io::storage::get(key).map(|value| Vec::<u8>::decode(&mut &value[..]).unwrap())
+}
+
+/// Reads the group item published under `address`.
+fn read_group(address: &AccountAddress, group: &[u8]) -> storage::GroupItem {
+    let key = proof::vm_storage_key::<Test>(&storage::resource_key(address, group));
+    storage::decode_group(read_trie_value(&key))
+}
+
+/// BCS encoded tag of the struct of the user `Store` module.
+fn store_tag(name: &str) -> Vec<u8> {
+    bcs::to_bytes(&StructTag {
+        address: origin_move_addr(),
+        module: Identifier::new(modules::user::STORE.name()).unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    })
+    .unwrap()
+}
+
+#[test]
+/// Grouped resource is stored in the group item and read through `get_resource`.
+fn grouped_resource() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let (u64_tag, group_tag) = (store_tag("U64"), store_tag("Group"));
+        set_resource_groups(vec![(u64_tag.clone(), group_tag.clone())]);
+
+        let bob = bob_public_key();
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        utils::execute_tx(bob, &transactions::STORE_U64, None).unwrap();
+
+        let resource = Mvm::get_resource(&bob, &u64_tag).unwrap();
+        assert!(resource.is_some());
+
+        // Not stored under its own key.
+        let own_key = storage::resource_key(&origin_move_addr(), &u64_tag);
+        assert_eq!(read_trie_value(&proof::vm_storage_key::<Test>(&own_key)), None);
+
+        let group = read_group(&origin_move_addr(), &group_tag);
+        assert_eq!(group.len(), 1);
+        assert_eq!(group.get(&u64_tag).cloned(), resource);
+
+        // Proofs are built for the group item.
+        let key = proof::resource_storage_key::<Test>(&origin_move_addr(), &u64_tag);
+        let group_key = storage::resource_key(&origin_move_addr(), &group_tag);
+        assert_eq!(key, proof::vm_storage_key::<Test>(&group_key));
+    });
+}
+
+#[test]
+/// Resources written by the pallet are packed into one group item.
+fn grouped_pallet_resources() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let group_tag = storage::core_struct_tag("block", "Group");
+        set_resource_groups(vec![
+            (block::parent_hash_tag(), group_tag.clone()),
+            (randomness::seed_tag(), group_tag.clone()),
+        ]);
+
+        roll_next_block();
+
+        for key in [block::parent_hash_key(), randomness::seed_key()] {
+            assert_eq!(read_trie_value(&proof::vm_storage_key::<Test>(&key)), None);
+        }
+
+        let group = read_group(&CORE_CODE_ADDRESS, &group_tag);
+        assert_eq!(group.len(), 2);
+        assert!(group.contains_key(&block::parent_hash_tag()));
+        assert!(group.contains_key(&randomness::seed_tag()));
+    });
+}
+
+#[test]
+/// Only resource keys of the member structs are grouped.
+fn group_member_key() {
+    let (u64_tag, group_tag) = (store_tag("U64"), store_tag("Group"));
+    set_resource_groups(vec![(u64_tag.clone(), group_tag.clone())]);
+
+    let address = origin_move_addr();
+    let key = storage::resource_key(&address, &u64_tag);
+    assert_eq!(
+        storage::group_member_key::<MockResourceGroups>(&key),
+        Some((storage::resource_key(&address, &group_tag), u64_tag.clone()))
+    );
+
+    // Ungrouped struct.
+    let key = storage::resource_key(&address, &store_tag("U128"));
+    assert_eq!(storage::group_member_key::<MockResourceGroups>(&key), None);
+    // Module key.
+    let module_id = ModuleId::new(address, Identifier::new(modules::user::STORE.name()).unwrap());
+    let key = module_id.access_vector();
+    assert_eq!(storage::group_member_key::<MockResourceGroups>(&key), None);
+    // No groups.
+    let key = storage::resource_key(&address, &u64_tag);
+    assert_eq!(storage::group_member_key::<()>(&key), None);
+}
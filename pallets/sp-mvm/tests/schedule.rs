@@ -0,0 +1,152 @@
+/// Tests related to scheduled scripts execution.
+use serde::Deserialize;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use frame_support::{assert_noop, assert_ok};
+use frame_support::traits::Currency;
+use sp_mvm::Error;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+const GAS_LIMIT: u64 = 1_000_000;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct StoreU64 {
+    pub val: u64,
+}
+
+fn store_u64_tag() -> StructTag {
+    StructTag {
+        address: origin_move_addr(),
+        module: Identifier::new(modules::user::STORE.name()).unwrap(),
+        name: Identifier::new("U64").unwrap(),
+        type_params: vec![],
+    }
+}
+
+#[test]
+/// Scheduled script is executed at the requested block as the caller.
+fn schedule_execute() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = bob_public_key();
+        let tx_bc = transactions::STORE_U64.bytes().to_vec();
+        utils::publish_module(origin, &modules::user::STORE, None).unwrap();
+
+        roll_next_block();
+        let when = Sys::block_number() + 2;
+        assert_ok!(Mvm::schedule_execute(
+            Origin::signed(origin),
+            when,
+            tx_bc.clone(),
+            GAS_LIMIT
+        ));
+        let script_hash = sp_mvm::types::script_hash(&tx_bc);
+        let expected = MoveEvent::ScriptScheduled(origin, when, script_hash).into();
+        assert!(Sys::events().iter().any(|rec| rec.event == expected));
+
+        // Not executed before the requested block.
+        roll_next_block();
+        let tag = bcs::to_bytes(&store_u64_tag()).unwrap();
+        assert_eq!(Mvm::get_resource(&origin, &tag), Ok(None));
+
+        roll_block_to(when);
+        utils::check_storage_res(origin_move_addr(), store_u64_tag(), StoreU64 { val: 42 });
+    });
+}
+
+#[test]
+/// Scripts can't be scheduled for the current or past blocks.
+fn schedule_execute_in_past() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+
+        assert_noop!(
+            Mvm::schedule_execute(
+                Origin::signed(bob_public_key()),
+                Sys::block_number(),
+                transactions::STORE_U64.bytes().to_vec(),
+                GAS_LIMIT,
+            ),
+            Error::<Test>::ScheduleInPast
+        );
+    });
+}
+
+#[test]
+/// Scheduled scripts are executed with the signed origin, so root signer is not allowed.
+fn schedule_execute_with_root_signer() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        assert_noop!(
+            Mvm::schedule_execute(
+                Origin::signed(bob_public_key()),
+                Sys::block_number() + 1,
+                transactions::ONE_SIGNER_ROOT.bytes().to_vec(),
+                GAS_LIMIT,
+            ),
+            Error::<Test>::TransactionIsNotAllowedError
+        );
+    });
+}
+
+#[test]
+/// Gas limit of the scheduled script is bounded.
+fn schedule_execute_gas_too_large() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+
+        assert_noop!(
+            Mvm::schedule_execute(
+                Origin::signed(bob_public_key()),
+                Sys::block_number() + 1,
+                transactions::STORE_U64.bytes().to_vec(),
+                MaxScheduledGas::get() + 1,
+            ),
+            Error::<Test>::ScheduledGasTooLarge
+        );
+    });
+}
+
+#[test]
+/// Gas fee is paid at the scheduling, pending scripts of the account are limited.
+fn schedule_execute_fee_and_limit() {
+    ScheduledGasUnitPrice::set(2);
+    RuntimeBuilder::new()
+        .set_balances(vec![(bob_public_key(), CurrencyId::NATIVE, UNIT)])
+        .build()
+        .execute_with(|| {
+            let origin = bob_public_key();
+            let tx_bc = transactions::STORE_U64.bytes().to_vec();
+            roll_next_block();
+            let when = Sys::block_number() + 2;
+
+            for _ in 0..MaxScheduledScripts::get() {
+                assert_ok!(Mvm::schedule_execute(
+                    Origin::signed(origin),
+                    when,
+                    tx_bc.clone(),
+                    GAS_LIMIT
+                ));
+            }
+            let fee = 2 * GAS_LIMIT * MaxScheduledScripts::get() as u64;
+            assert_eq!(Balances::free_balance(origin), UNIT - fee);
+
+            assert_noop!(
+                Mvm::schedule_execute(Origin::signed(origin), when, tx_bc.clone(), GAS_LIMIT),
+                Error::<Test>::TooManyScheduledScripts
+            );
+
+            // Executed scripts aren't pending anymore.
+            roll_block_to(when + 1);
+            assert_ok!(Mvm::schedule_execute(
+                Origin::signed(origin),
+                when + 2,
+                tx_bc,
+                GAS_LIMIT
+            ));
+        });
+    ScheduledGasUnitPrice::set(0);
+}
@@ -1,10 +1,13 @@
 /// Tests related to scripts execution.
 use serde::Deserialize;
+use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{StructTag, TypeTag};
 use frame_support::assert_err_ignore_postinfo;
 use frame_support::dispatch::DispatchError;
+use frame_support::traits::Get;
 use sp_runtime::ModuleError;
+use sp_runtime::traits::{BlakeTwo256, Hash};
 use sp_mvm::Event;
 
 mod common;
@@ -15,6 +18,11 @@ use common::utils;
 
 /// Check stored value (u64) inside storage.
 fn check_stored_value(expected: u64) {
+    check_stored_value_at(origin_move_addr(), expected);
+}
+
+/// Check stored value (u64) inside storage of the given address.
+fn check_stored_value_at(owner: AccountAddress, expected: u64) {
     #[derive(Deserialize, Debug, PartialEq)]
     struct StoreU64 {
         pub val: u64,
@@ -29,7 +37,7 @@ fn check_stored_value(expected: u64) {
         type_params: vec![],
     };
 
-    utils::check_storage_res(origin_move_addr(), tag, expected);
+    utils::check_storage_res(owner, tag, expected);
 }
 
 /// Panics with inner message of the passed error.
@@ -142,7 +150,14 @@ fn execute_script_with_event() {
         let expected = Event::Event(guid, tt.to_vec(), 42u64.to_le_bytes().to_vec()).into();
 
         // iterate through array of `EventRecord`s
-        assert!(Sys::events().iter().any(|rec| { rec.event == expected }))
+        assert!(Sys::events().iter().any(|rec| { rec.event == expected }));
+
+        // event is indexed by the hash of its type tag
+        let topic = BlakeTwo256::hash(tt);
+        assert!(Sys::events()
+            .iter()
+            .any(|rec| rec.event == expected && rec.topics == vec![topic]));
+        assert_eq!(Sys::event_topics(topic).len(), 1);
     });
 }
 
@@ -193,3 +208,90 @@ fn execute_with_one_signer_with_root() {
         unwrap_move_err_in_dispatch_err(&error);
     });
 }
+
+#[test]
+/// Oversized script transaction is rejected before deserialization.
+fn execute_too_large_script() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let tx_bc = vec![0; MaxScriptSize::get() as usize + 1];
+        assert_err_ignore_postinfo!(
+            Mvm::execute(Origin::signed(bob_public_key()), tx_bc, 1_000_000),
+            sp_mvm::Error::<Test>::ScriptTooLarge
+        );
+    });
+}
+
+#[test]
+/// Nesting depth of type arguments.
+fn type_arg_depth() {
+    use sp_mvm::types::type_tag_depth;
+
+    let nested = |depth| (1..depth).fold(TypeTag::U8, |tag, _| TypeTag::Vector(Box::new(tag)));
+    assert_eq!(type_tag_depth(&TypeTag::U64), 1);
+    assert_eq!(type_tag_depth(&nested(2)), 2);
+
+    let tag = TypeTag::Struct(StructTag {
+        address: origin_move_addr(),
+        module: Identifier::new("Coins").unwrap(),
+        name: Identifier::new("Pair").unwrap(),
+        type_params: vec![TypeTag::Bool, nested(MaxTypeArgDepth::get())],
+    });
+    assert_eq!(type_tag_depth(&tag), MaxTypeArgDepth::get() + 1);
+}
+
+#[test]
+/// Only root can execute scripts on behalf of an arbitrary address.
+fn execute_as_requires_root() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = bob_public_key();
+        utils::publish_module(origin, &modules::user::STORE, None).unwrap();
+
+        assert_err_ignore_postinfo!(
+            Mvm::execute_as(
+                Origin::signed(origin),
+                alice_move_addr().to_u8(),
+                transactions::STORE_U64.bytes().to_vec(),
+                1_000_000,
+            ),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+/// Root executes script signed by another address.
+fn execute_as_address() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        const EXPECTED: u64 = 42;
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+
+        roll_next_block();
+        let tx_bc = transactions::STORE_U64.bytes().to_vec();
+        let address = alice_move_addr().to_u8();
+        Mvm::execute_as(Origin::root(), address, tx_bc.clone(), 1_000_000)
+            .unwrap_or_else(|err| unwrap_move_err_in_dispatch_err(&err.error));
+
+        check_stored_value_at(alice_move_addr(), EXPECTED);
+
+        let script_hash = sp_mvm::types::script_hash(&tx_bc);
+        let expected = Event::ScriptExecutedAs(address, script_hash).into();
+        assert!(Sys::events().iter().any(|rec| { rec.event == expected }))
+    });
+}
+
+#[test]
+#[should_panic(expected = "TransactionIsNotAllowedError")]
+/// Scripts requiring root signer can't be executed on behalf of an address.
+fn execute_as_with_root_signer() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let error = Mvm::execute_as(
+            Origin::root(),
+            alice_move_addr().to_u8(),
+            transactions::ONE_SIGNER_ROOT.bytes().to_vec(),
+            1_000_000,
+        )
+        .expect_err("tx with root requirement executed on behalf of an address should fail")
+        .error;
+        unwrap_move_err_in_dispatch_err(&error);
+    });
+}
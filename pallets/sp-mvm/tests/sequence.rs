@@ -0,0 +1,73 @@
+/// Tests related to sequence numbers of Move accounts.
+use frame_support::assert_err_ignore_postinfo;
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use sp_mvm::Error;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the scripts in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Executes `STORE_U64` script with the sequence number.
+fn execute_with_sequence(sequence_number: u64) -> DispatchResultWithPostInfo {
+    Mvm::execute_with_sequence(
+        Origin::signed(bob_public_key()),
+        transactions::STORE_U64.bytes().to_vec(),
+        GAS_LIMIT,
+        sequence_number,
+    )
+}
+
+#[test]
+/// Executed scripts bump sequence number of the signer.
+fn execute_bumps_sequence_number() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 0);
+
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 0);
+
+        utils::execute_tx(bob_public_key(), &transactions::STORE_U64, None).unwrap();
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 1);
+        assert_eq!(Mvm::sequence_number(alice_public_key()), 0);
+    });
+}
+
+#[test]
+/// Script is executed with the current sequence number only.
+fn execute_with_sequence_number() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+
+        assert_err_ignore_postinfo!(
+            execute_with_sequence(1),
+            Error::<Test>::SequenceNumberTooNew
+        );
+
+        execute_with_sequence(0).unwrap();
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 1);
+
+        // Replay of the executed script.
+        assert_err_ignore_postinfo!(
+            execute_with_sequence(0),
+            Error::<Test>::SequenceNumberTooOld
+        );
+
+        execute_with_sequence(1).unwrap();
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 2);
+    });
+}
+
+#[test]
+/// Failed scripts don't bump sequence number.
+fn failed_execute_keeps_sequence_number() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        // Module used by the script is not published.
+        assert!(execute_with_sequence(0).is_err());
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 0);
+    });
+}
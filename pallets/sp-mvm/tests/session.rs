@@ -0,0 +1,171 @@
+/// Tests related to session keys of Move dApps.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::assert_err_ignore_postinfo;
+use frame_support::weights::DispatchInfo;
+use sp_mvm::session::CheckSessionKey;
+use sp_mvm::types::{SessionKey, SessionScope};
+use sp_mvm::{Error, Event as MvmEvent, SessionKeys};
+use sp_runtime::traits::SignedExtension;
+use sp_runtime::transaction_validity::InvalidTransaction;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the calls in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Scope of all entry functions of `Store` module published by bob.
+fn store_scope() -> Vec<SessionScope<AccountId>> {
+    vec![SessionScope {
+        owner: bob_public_key(),
+        module: b"Store".to_vec(),
+        function: None,
+    }]
+}
+
+/// Bob authorizes alice as the session key.
+fn authorize(gas_allowance: u64, expires_at: BlockNumber) {
+    assert_ok!(Mvm::authorize_session_key(
+        Origin::signed(bob_public_key()),
+        alice_public_key(),
+        store_scope(),
+        gas_allowance,
+        expires_at,
+    ));
+}
+
+/// Alice calls the transaction on behalf of bob.
+fn execute_with_session_key(tx: &[u8], gas_limit: u64) -> Call {
+    Call::Mvm(sp_mvm::Call::execute_with_session_key {
+        owner: bob_public_key(),
+        tx_bc: tx.to_vec(),
+        gas_limit,
+    })
+}
+
+#[test]
+/// Session key is authorized and revoked by the account.
+fn authorize_and_revoke() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let (bob, alice) = (bob_public_key(), alice_public_key());
+        let expires_at = Sys::block_number() + 10;
+
+        authorize(GAS_LIMIT, expires_at);
+        assert_eq!(
+            last_event(),
+            Event::Mvm(MvmEvent::SessionKeyAuthorized(bob, alice, expires_at))
+        );
+        assert_eq!(
+            SessionKeys::<Test>::get(bob, alice),
+            Some(SessionKey {
+                scope: store_scope(),
+                gas_allowance: GAS_LIMIT,
+                expires_at,
+            })
+        );
+
+        assert_ok!(Mvm::revoke_session_key(Origin::signed(bob), alice));
+        assert_eq!(last_event(), Event::Mvm(MvmEvent::SessionKeyRevoked(bob, alice)));
+        assert_eq!(SessionKeys::<Test>::get(bob, alice), None);
+        assert_noop!(
+            Mvm::revoke_session_key(Origin::signed(bob), alice),
+            Error::<Test>::UnknownSessionKey
+        );
+    });
+}
+
+#[test]
+/// Scope must be non-empty and within `MaxSessionScope`, expiry must be in the future.
+fn invalid_authorization() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let authorize = |scope, expires_at| {
+            Mvm::authorize_session_key(
+                Origin::signed(bob_public_key()),
+                alice_public_key(),
+                scope,
+                GAS_LIMIT,
+                expires_at,
+            )
+        };
+        let expires_at = Sys::block_number() + 10;
+
+        assert_noop!(
+            authorize(vec![], expires_at),
+            Error::<Test>::InvalidSessionScope
+        );
+        let too_wide = store_scope().repeat(MaxSessionScope::get() as usize + 1);
+        assert_noop!(
+            authorize(too_wide, expires_at),
+            Error::<Test>::InvalidSessionScope
+        );
+        assert_noop!(
+            authorize(store_scope(), Sys::block_number()),
+            Error::<Test>::SessionKeyExpired
+        );
+    });
+}
+
+#[test]
+/// Calls are checked against the key, its expiry, gas allowance and scope.
+fn session_key_checks() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let (bob, alice) = (bob_public_key(), alice_public_key());
+        utils::publish_module(bob, &modules::user::STORE, Some(GAS_LIMIT)).unwrap();
+        let tx = transactions::STORE_U64.bytes().to_vec();
+        let execute = |gas_limit| {
+            Mvm::execute_with_session_key(Origin::signed(alice), bob, tx.clone(), gas_limit)
+        };
+
+        assert_err_ignore_postinfo!(execute(GAS_LIMIT), Error::<Test>::UnknownSessionKey);
+
+        let expires_at = Sys::block_number() + 2;
+        authorize(GAS_LIMIT, expires_at);
+        assert_err_ignore_postinfo!(
+            execute(GAS_LIMIT + 1),
+            Error::<Test>::SessionGasAllowanceExceeded
+        );
+        // Raw scripts are out of any scope.
+        assert_err_ignore_postinfo!(execute(GAS_LIMIT), Error::<Test>::SessionCallNotAllowed);
+
+        roll_block_to(expires_at);
+        assert_err_ignore_postinfo!(execute(GAS_LIMIT), Error::<Test>::SessionKeyExpired);
+    });
+}
+
+#[test]
+/// Calls the session key isn't allowed to make are rejected by the signed extension.
+fn check_session_key_extension() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let alice = alice_public_key();
+        let info = DispatchInfo::default();
+        let tx = transactions::STORE_U64.bytes();
+        let validate = |call: &Call| {
+            CheckSessionKey::<Test>::new().validate(&alice, call, &info, 0)
+        };
+
+        // Not a session key call.
+        let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+        assert!(validate(&remark).is_ok());
+
+        let call = execute_with_session_key(tx, GAS_LIMIT);
+        assert_eq!(validate(&call), Err(InvalidTransaction::BadSigner.into()));
+
+        let expires_at = Sys::block_number() + 2;
+        authorize(GAS_LIMIT, expires_at);
+        assert_eq!(
+            validate(&execute_with_session_key(tx, GAS_LIMIT + 1)),
+            Err(InvalidTransaction::Payment.into())
+        );
+        assert_eq!(validate(&call), Err(InvalidTransaction::Call.into()));
+
+        roll_block_to(expires_at);
+        assert_eq!(validate(&call), Err(InvalidTransaction::Stale.into()));
+    });
+}
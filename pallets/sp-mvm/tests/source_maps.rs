@@ -0,0 +1,100 @@
+/// Tests related to source maps published along with modules.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::traits::ReservableCurrency;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use sp_mvm::{Error, Event as MvmEvent, SourceMaps};
+
+mod common;
+use common::assets::modules;
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the modules in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+fn publish_with_source_map(source_map: Vec<u8>) -> DispatchResultWithPostInfo {
+    Mvm::publish_module_with_source_map(
+        Origin::signed(bob_public_key()),
+        modules::user::STORE.bytes().to_vec(),
+        source_map,
+        GAS_LIMIT,
+    )
+}
+
+fn store_id() -> ModuleId {
+    ModuleId::new(
+        to_move_addr(bob_public_key()),
+        Identifier::new("Store").unwrap(),
+    )
+}
+
+fn funded_runtime() -> RuntimeBuilder {
+    RuntimeBuilder::new().set_balances(vec![(
+        bob_public_key(),
+        CurrencyId::NATIVE,
+        INITIAL_BALANCE,
+    )])
+}
+
+#[test]
+/// Source map is stored with the module and served by its id.
+fn publish_source_map() {
+    ModuleDepositPerByte::set(0);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+        let source_map = vec![1; 128];
+
+        assert_ok!(publish_with_source_map(source_map.clone()));
+        assert!(Sys::events().iter().any(|record| record.event
+            == Event::Mvm(MvmEvent::SourceMapStored(bob, b"Store".to_vec()))));
+
+        let entry = SourceMaps::<Test>::get(store_id().access_vector()).unwrap();
+        assert_eq!(entry.source_map, source_map);
+        assert_eq!(entry.depositor, bob);
+
+        let module_id = bcs::to_bytes(&store_id()).unwrap();
+        assert_eq!(Mvm::get_source_map(&module_id), Some(source_map));
+        assert_eq!(Mvm::get_source_map(&[0; 3]), None);
+    });
+}
+
+#[test]
+/// Source map deposit is reserved and released when the module is republished without it.
+fn source_map_deposit() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+        let module_size = modules::user::STORE.bytes().len() as Balance;
+
+        assert_ok!(publish_with_source_map(vec![1; 128]));
+        assert_eq!(
+            SourceMaps::<Test>::get(store_id().access_vector())
+                .unwrap()
+                .deposit,
+            128
+        );
+        assert_eq!(Balances::reserved_balance(&bob), module_size + 128);
+
+        assert_ok!(utils::publish_module(
+            bob,
+            &modules::user::STORE,
+            Some(GAS_LIMIT)
+        ));
+        assert!(!SourceMaps::<Test>::contains_key(store_id().access_vector()));
+        assert_eq!(Balances::reserved_balance(&bob), module_size);
+    });
+}
+
+#[test]
+/// Source maps are bounded by `MaxSourceMapSize`.
+fn source_map_too_large() {
+    funded_runtime().build().execute_with(|| {
+        assert_noop!(
+            publish_with_source_map(vec![1; MaxSourceMapSize::get() as usize + 1]),
+            Error::<Test>::SourceMapTooLarge
+        );
+    });
+}
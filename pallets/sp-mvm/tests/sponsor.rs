@@ -0,0 +1,132 @@
+/// Tests related to sponsored Move transactions.
+use frame_support::{assert_ok, assert_err_ignore_postinfo};
+use frame_support::weights::DispatchInfo;
+use sp_core::Pair;
+use sp_mvm::sponsor::{self, CheckSponsor};
+use sp_mvm::{Error, Event as MvmEvent};
+use sp_runtime::traits::SignedExtension;
+use sp_runtime::transaction_validity::InvalidTransaction;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the scripts in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Bob signs `STORE_U64` sponsored by the sponsor with his current sequence number.
+fn bob_signature(sponsor: &AccountId, valid_thru: BlockNumber) -> AnySignature {
+    let payload = sponsor::payload::<Test>(
+        sponsor,
+        transactions::STORE_U64.bytes(),
+        GAS_LIMIT,
+        valid_thru,
+        Mvm::sequence_number(bob_public_key()),
+    );
+    let pair = sp_core::sr25519::Pair::from_string("//Bob", None).unwrap();
+    AnySignature::from(pair.sign(&payload[..]))
+}
+
+/// Bob's `STORE_U64` submitted by alice.
+fn execute_sponsored(valid_thru: BlockNumber, signature: AnySignature) -> Call {
+    Call::Mvm(sp_mvm::Call::execute_sponsored {
+        sender: bob_public_key(),
+        tx_bc: transactions::STORE_U64.bytes().to_vec(),
+        gas_limit: GAS_LIMIT,
+        valid_thru,
+        signature,
+    })
+}
+
+#[test]
+/// Sponsor submits the transaction executed signed by the sender.
+fn execute_sponsored_transaction() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let (bob, alice) = (bob_public_key(), alice_public_key());
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        let valid_thru = Sys::block_number() + 10;
+        let execute = |signature| {
+            Mvm::execute_sponsored(
+                Origin::signed(alice),
+                bob,
+                transactions::STORE_U64.bytes().to_vec(),
+                GAS_LIMIT,
+                valid_thru,
+                signature,
+            )
+        };
+
+        let signature = bob_signature(&alice, valid_thru);
+        assert_ok!(execute(signature.clone()));
+        let expected = Event::Mvm(MvmEvent::SponsoredTransactionExecuted(alice, bob, 0));
+        assert!(Sys::events().iter().any(|rec| rec.event == expected));
+        assert_eq!(Mvm::sequence_number(bob), 1);
+
+        // The sequence number is bumped, so the signature can't be replayed.
+        assert_err_ignore_postinfo!(execute(signature), Error::<Test>::InvalidSponsoredSignature);
+    });
+}
+
+#[test]
+/// Signature is bound to the sponsor and expires at `valid_thru`.
+fn invalid_sponsored_transaction() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let (bob, alice) = (bob_public_key(), alice_public_key());
+        let valid_thru = Sys::block_number() + 2;
+        let execute = |sponsor, signature| {
+            Mvm::execute_sponsored(
+                Origin::signed(sponsor),
+                bob,
+                transactions::STORE_U64.bytes().to_vec(),
+                GAS_LIMIT,
+                valid_thru,
+                signature,
+            )
+        };
+
+        // Signed for alice, submitted by bob.
+        assert_err_ignore_postinfo!(
+            execute(bob, bob_signature(&alice, valid_thru)),
+            Error::<Test>::InvalidSponsoredSignature
+        );
+
+        roll_block_to(valid_thru);
+        assert_err_ignore_postinfo!(
+            execute(alice, bob_signature(&alice, valid_thru)),
+            Error::<Test>::SponsoredTransactionExpired
+        );
+    });
+}
+
+#[test]
+/// Sponsored calls with invalid or expired signatures are rejected by the signed extension.
+fn check_sponsor_extension() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let alice = alice_public_key();
+        let info = DispatchInfo::default();
+        let validate = |call: &Call| CheckSponsor::<Test>::new().validate(&alice, call, &info, 0);
+
+        // Not a sponsored call.
+        let remark = Call::System(frame_system::Call::remark { remark: vec![] });
+        assert!(validate(&remark).is_ok());
+
+        let valid_thru = Sys::block_number() + 2;
+        let call = execute_sponsored(valid_thru, bob_signature(&alice, valid_thru));
+        assert!(validate(&call).is_ok());
+
+        let other_sponsor =
+            execute_sponsored(valid_thru, bob_signature(&bob_public_key(), valid_thru));
+        assert_eq!(
+            validate(&other_sponsor),
+            Err(InvalidTransaction::BadProof.into())
+        );
+
+        roll_block_to(valid_thru);
+        assert_eq!(validate(&call), Err(InvalidTransaction::Stale.into()));
+    });
+}
@@ -0,0 +1,113 @@
+/// Tests related to modules uploaded in chunks.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::traits::ReservableCurrency;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use sp_mvm::{Error, Event as MvmEvent, StagedModules, VMStorage};
+
+mod common;
+use common::assets::modules;
+use common::mock::*;
+use common::addr::*;
+
+/// Gas limit of the modules in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+fn stage(offset: usize, chunk: &[u8]) -> DispatchResultWithPostInfo {
+    Mvm::stage_module_chunk(
+        Origin::signed(bob_public_key()),
+        offset as u32,
+        chunk.to_vec(),
+    )
+}
+
+fn funded_runtime() -> RuntimeBuilder {
+    RuntimeBuilder::new().set_balances(vec![(
+        bob_public_key(),
+        CurrencyId::NATIVE,
+        INITIAL_BALANCE,
+    )])
+}
+
+#[test]
+/// Module is assembled from chunks and published, the staged deposit becomes module deposit.
+fn publish_staged_module() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+        let module_bc = modules::user::STORE.bytes();
+        let size = module_bc.len();
+
+        for (index, chunk) in module_bc.chunks(size / 3 + 1).enumerate() {
+            assert_ok!(stage(index * (size / 3 + 1), chunk));
+        }
+        assert_eq!(
+            last_event(),
+            Event::Mvm(MvmEvent::ModuleChunkStaged(bob, size as u32))
+        );
+        assert_eq!(StagedModules::<Test>::get(bob).unwrap().bytecode, module_bc);
+        assert_eq!(Balances::reserved_balance(&bob), size as Balance);
+
+        assert_ok!(Mvm::publish_staged_module(
+            Origin::signed(bob),
+            GAS_LIMIT
+        ));
+        assert!(!StagedModules::<Test>::contains_key(bob));
+        assert_eq!(Balances::reserved_balance(&bob), size as Balance);
+
+        let module_id = ModuleId::new(to_move_addr(bob), Identifier::new("Store").unwrap());
+        assert_eq!(
+            VMStorage::<Test>::get(module_id.access_vector()).unwrap(),
+            module_bc
+        );
+    });
+}
+
+#[test]
+/// Chunks are appended in order, the staged module is bounded and can be discarded.
+fn stage_module_chunks() {
+    ModuleDepositPerByte::set(1);
+    funded_runtime().build().execute_with(|| {
+        let bob = bob_public_key();
+
+        assert_noop!(stage(0, &[]), Error::<Test>::InvalidModuleChunk);
+        assert_noop!(
+            stage(0, &vec![0; MaxModuleSize::get() as usize + 1]),
+            Error::<Test>::InvalidModuleChunk
+        );
+        assert_noop!(stage(1, &[0; 4]), Error::<Test>::ModuleChunkOffsetMismatch);
+
+        assert_ok!(stage(0, &[0; 4]));
+        assert_noop!(stage(2, &[0; 4]), Error::<Test>::ModuleChunkOffsetMismatch);
+        assert_ok!(stage(4, &[0; 4]));
+        assert_eq!(Balances::reserved_balance(&bob), 8);
+
+        // Zero offset starts a new module.
+        assert_ok!(stage(0, &[1; 2]));
+        assert_eq!(StagedModules::<Test>::get(bob).unwrap().bytecode, vec![1; 2]);
+        assert_eq!(Balances::reserved_balance(&bob), 2);
+
+        // The deposit of a full sized module exceeds the balance.
+        ModuleDepositPerByte::set(0);
+        let chunk = vec![0; MaxModuleSize::get() as usize];
+        let mut offset = 2;
+        while offset + chunk.len() <= MaxStagedModuleSize::get() as usize {
+            assert_ok!(stage(offset, &chunk));
+            offset += chunk.len();
+        }
+        assert_noop!(stage(offset, &chunk), Error::<Test>::StagedModuleTooLarge);
+
+        // Invalid bytecode isn't published and stays staged.
+        assert!(Mvm::publish_staged_module(Origin::signed(bob), GAS_LIMIT).is_err());
+        assert!(StagedModules::<Test>::contains_key(bob));
+
+        assert_ok!(Mvm::discard_staged_module(Origin::signed(bob)));
+        assert_eq!(last_event(), Event::Mvm(MvmEvent::StagedModuleDiscarded(bob)));
+        assert_eq!(Balances::reserved_balance(&bob), 0);
+        assert_noop!(
+            Mvm::discard_staged_module(Origin::signed(bob)),
+            Error::<Test>::NoStagedModule
+        );
+    });
+}
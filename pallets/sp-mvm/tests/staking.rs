@@ -0,0 +1,94 @@
+/// Tests related to the staking state stored for Move code.
+use serde::Deserialize;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use sp_mvm::staking::{self, StakingInfo};
+use sp_mvm::VMStorage;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct MoveRound {
+    index: u64,
+    first_block: u64,
+    length: u64,
+    collators: Vec<AccountAddress>,
+    total_staked: u128,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct MoveStake {
+    delegated: u128,
+    collator_bond: u128,
+}
+
+fn staking_tag(name: &str) -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(staking::STAKING_MODULE).unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }
+}
+
+#[test]
+/// Struct tags of the staking resources are encoded the same way as by Move VM.
+fn staking_tags_encoding() {
+    assert_eq!(
+        staking::round_tag(),
+        bcs::to_bytes(&staking_tag(staking::ROUND_STRUCT)).unwrap()
+    );
+    assert_eq!(
+        staking::stake_tag(),
+        bcs::to_bytes(&staking_tag(staking::STAKE_STRUCT)).unwrap()
+    );
+}
+
+#[test]
+/// Current round is stored on block initialization.
+fn round_stored_every_block() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_block_to(STAKING_ROUND_LENGTH + 3);
+
+        let expected = MoveRound {
+            index: 1,
+            first_block: STAKING_ROUND_LENGTH,
+            length: STAKING_ROUND_LENGTH,
+            collators: vec![to_move_addr(alice_public_key())],
+            total_staked: (UNIT + UNIT / 2) as u128,
+        };
+        utils::check_storage_res(CORE_CODE_ADDRESS, staking_tag(staking::ROUND_STRUCT), expected);
+    });
+}
+
+#[test]
+/// Stake resource is BCS encoded `0x1::Staking::Stake`.
+fn stake_resource_encoding() {
+    let stake = MockStaking::stake(&bob_public_key()).unwrap();
+    let resource: MoveStake = bcs::from_bytes(&stake.to_resource()).unwrap();
+    assert_eq!(
+        resource,
+        MoveStake {
+            delegated: (UNIT / 2) as u128,
+            collator_bond: 0,
+        }
+    );
+}
+
+#[test]
+/// Stake is removed after the script.
+fn stake_removed_after_script() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        utils::execute_tx(bob_public_key(), &transactions::STORE_U64, None).unwrap();
+
+        let key = staking::stake_key(&to_move_addr(bob_public_key()));
+        assert!(!VMStorage::<Test>::contains_key(key));
+    });
+}
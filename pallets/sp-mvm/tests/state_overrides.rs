@@ -0,0 +1,43 @@
+/// Tests related to resource overrides of the estimations.
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// BCS encoded tag of the struct of the user `Store` module.
+fn store_tag(name: &str) -> Vec<u8> {
+    bcs::to_bytes(&StructTag {
+        address: origin_move_addr(),
+        module: Identifier::new(modules::user::STORE.name()).unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    })
+    .unwrap()
+}
+
+#[test]
+/// Overrides replace and remove resources of the accounts.
+fn override_resources() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bob = bob_public_key();
+        let tag = store_tag("U64");
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        utils::execute_tx(bob, &transactions::STORE_U64, None).unwrap();
+
+        let value = bcs::to_bytes(&7u64).unwrap();
+        assert_eq!(
+            Mvm::override_resources(vec![(bob, tag.clone(), Some(value.clone()))]),
+            Ok(())
+        );
+        assert_eq!(Mvm::get_resource(&bob, &tag), Ok(Some(value)));
+
+        assert_eq!(Mvm::override_resources(vec![(bob, tag.clone(), None)]), Ok(()));
+        assert_eq!(Mvm::get_resource(&bob, &tag), Ok(None));
+
+        assert!(Mvm::override_resources(vec![(bob, vec![1, 2, 3], None)]).is_err());
+    });
+}
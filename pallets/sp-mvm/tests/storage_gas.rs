@@ -0,0 +1,112 @@
+/// Tests related to gas of cold and warm storage accesses.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::assert_err_ignore_postinfo;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
+use sp_runtime::DispatchError;
+use sp_mvm::gas::StorageGasSchedule;
+use sp_mvm::{Error, Event as MvmEvent};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::gas_corpus::gas_used;
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the calls in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Sets the storage gas schedule.
+fn set_schedule(cold_access: u64, warm_access: u64) {
+    let schedule = StorageGasSchedule {
+        cold_access,
+        warm_access,
+    };
+    assert_ok!(Mvm::set_storage_gas_schedule(Origin::root(), schedule));
+}
+
+/// Gas used by storing `U64` resource with the storage gas schedule.
+fn store_gas(cold_access: u64, warm_access: u64) -> u64 {
+    RuntimeBuilder::new().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, Some(GAS_LIMIT)).unwrap();
+        set_schedule(cold_access, warm_access);
+
+        let res = utils::execute_tx(bob_public_key(), &transactions::STORE_U64, Some(GAS_LIMIT));
+        assert_ok!(res);
+        gas_used(res).unwrap()
+    })
+}
+
+#[test]
+/// Only `UpdateOrigin` can set the storage gas schedule.
+fn set_storage_gas_schedule_requires_update_origin() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        assert_eq!(Mvm::storage_gas_schedule(), StorageGasSchedule::default());
+
+        let schedule = StorageGasSchedule {
+            cold_access: 1000,
+            warm_access: 1,
+        };
+        assert_noop!(
+            Mvm::set_storage_gas_schedule(Origin::signed(bob_public_key()), schedule),
+            DispatchError::BadOrigin
+        );
+
+        assert_ok!(Mvm::set_storage_gas_schedule(Origin::root(), schedule));
+        assert_eq!(Mvm::storage_gas_schedule(), schedule);
+        assert_eq!(last_event(), Event::Mvm(MvmEvent::StorageGasScheduleSet(schedule)));
+    });
+}
+
+#[test]
+/// The first access to a storage item is charged as cold one, the next ones as warm.
+fn item_gas() {
+    let schedule = StorageGasSchedule {
+        cold_access: 100,
+        warm_access: 10,
+    };
+    assert_eq!(schedule.item_gas(0), 0);
+    assert_eq!(schedule.item_gas(1), 100);
+    assert_eq!(schedule.item_gas(3), 120);
+}
+
+#[test]
+/// Storage accesses are charged on top of the Move VM gas.
+fn storage_access_gas() {
+    let vm_gas = store_gas(0, 0);
+
+    let cold = store_gas(1000, 0);
+    assert!(cold > vm_gas);
+    assert_eq!((cold - vm_gas) % 1000, 0);
+
+    // Every accessed item is charged once as cold.
+    let items = (cold - vm_gas) / 1000;
+    let warm = store_gas(1000, 1);
+    assert!(warm >= cold);
+    assert!(warm - cold < items * 1000);
+}
+
+#[test]
+/// Call runs out of gas if the storage accesses don't fit the gas limit, changes are reverted.
+fn storage_access_out_of_gas() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let bob = bob_public_key();
+        utils::publish_module(bob, &modules::user::STORE, Some(GAS_LIMIT)).unwrap();
+        set_schedule(GAS_LIMIT, 0);
+
+        let res = utils::execute_tx(bob, &transactions::STORE_U64, Some(GAS_LIMIT));
+        assert_eq!(gas_used(res.clone()), Some(GAS_LIMIT));
+        assert_err_ignore_postinfo!(res, Error::<Test>::OutOfGas);
+
+        let tag = bcs::to_bytes(&StructTag {
+            address: origin_move_addr(),
+            module: Identifier::new(modules::user::STORE.name()).unwrap(),
+            name: Identifier::new("U64").unwrap(),
+            type_params: vec![],
+        })
+        .unwrap();
+        assert_eq!(Mvm::get_resource(&bob, &tag).unwrap(), None);
+    });
+}
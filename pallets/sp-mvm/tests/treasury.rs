@@ -0,0 +1,53 @@
+/// Tests related to the treasury spends of Move code.
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use sp_mvm::{Error, Event as MvmEvent, TreasurySpenders};
+
+mod common;
+use common::mock::*;
+use common::addr::*;
+
+#[test]
+/// Only `UpdateOrigin` can manage treasury spenders.
+fn manage_spenders_requires_update_origin() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let origin = Origin::signed(bob_public_key());
+
+        assert_noop!(
+            Mvm::allow_treasury_spender(origin.clone(), bob_public_key()),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Mvm::disallow_treasury_spender(origin, bob_public_key()),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+/// Add and remove treasury spender.
+fn allow_and_disallow_spender() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        assert_ok!(Mvm::allow_treasury_spender(Origin::root(), bob_public_key()));
+        assert_eq!(
+            last_event(),
+            Event::Mvm(MvmEvent::TreasurySpenderAllowed(bob_public_key()))
+        );
+        assert!(TreasurySpenders::<Test>::contains_key(bob_public_key()));
+        assert_noop!(
+            Mvm::allow_treasury_spender(Origin::root(), bob_public_key()),
+            Error::<Test>::TreasurySpenderAlreadyAllowed
+        );
+
+        assert_ok!(Mvm::disallow_treasury_spender(Origin::root(), bob_public_key()));
+        assert_eq!(
+            last_event(),
+            Event::Mvm(MvmEvent::TreasurySpenderDisallowed(bob_public_key()))
+        );
+        assert_noop!(
+            Mvm::disallow_treasury_spender(Origin::root(), bob_public_key()),
+            Error::<Test>::UnknownTreasurySpender
+        );
+    });
+}
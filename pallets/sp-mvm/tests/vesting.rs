@@ -0,0 +1,94 @@
+/// Tests related to the token lockups stored for Move code.
+use serde::Deserialize;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use sp_mvm::vesting::{self, Lockups};
+use sp_mvm::VMStorage;
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Vesting length in blocks.
+const VESTING_BLOCKS: u32 = 10;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct MoveSchedule {
+    locked: u128,
+    per_block: u128,
+    starting_block: u64,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct MoveLockup {
+    locked: u128,
+    schedules: Vec<MoveSchedule>,
+}
+
+fn lockup_tag() -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(vesting::VESTING_MODULE).unwrap(),
+        name: Identifier::new(vesting::LOCKUP_STRUCT).unwrap(),
+        type_params: vec![],
+    }
+}
+
+fn vested_runtime() -> RuntimeBuilder {
+    RuntimeBuilder::new()
+        .set_balances(vec![(bob_public_key(), CurrencyId::NATIVE, UNIT)])
+        .set_vesting(vec![(bob_public_key(), 0, VESTING_BLOCKS, 0)])
+}
+
+#[test]
+/// Struct tag of the lockup is encoded the same way as by Move VM.
+fn lockup_tag_encoding() {
+    assert_eq!(vesting::lockup_tag(), bcs::to_bytes(&lockup_tag()).unwrap());
+}
+
+#[test]
+/// Lockup resource is BCS encoded `0x1::Vesting::Lockup`.
+fn lockup_resource_encoding() {
+    vested_runtime().build().execute_with(|| {
+        let lockup = MockLockups::lockup(&bob_public_key()).unwrap();
+        let resource: MoveLockup = bcs::from_bytes(&lockup.to_resource()).unwrap();
+        assert_eq!(
+            resource,
+            MoveLockup {
+                locked: UNIT as u128,
+                schedules: vec![MoveSchedule {
+                    locked: UNIT as u128,
+                    per_block: (UNIT / VESTING_BLOCKS as u64) as u128,
+                    starting_block: 0,
+                }],
+            }
+        );
+        assert!(MockLockups::lockup(&alice_public_key()).is_none());
+    });
+}
+
+#[test]
+/// Vest unlocks coins vested by the current block.
+fn vest_unlocks_coins() {
+    vested_runtime().build().execute_with(|| {
+        roll_block_to(5);
+        MockLockups::vest(bob_public_key()).unwrap();
+
+        let lockup = MockLockups::lockup(&bob_public_key()).unwrap();
+        assert_eq!(lockup.locked, (UNIT - UNIT / 2) as u128);
+    });
+}
+
+#[test]
+/// Lockup is removed after the script.
+fn lockup_removed_after_script() {
+    vested_runtime().build().execute_with(|| {
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        utils::execute_tx(bob_public_key(), &transactions::STORE_U64, None).unwrap();
+
+        let key = vesting::lockup_key(&to_move_addr(bob_public_key()));
+        assert!(!VMStorage::<Test>::contains_key(key));
+    });
+}
@@ -26,3 +26,22 @@ fn mvm_cleanup_cache() {
         assert!(!sp_mvm::Pallet::<Test>::is_move_vm_used());
     });
 }
+
+#[test]
+/// VM info reports versions, and stdlib hash depends only on modules under `0x1`.
+fn vm_info() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let info = Mvm::get_vm_info();
+        let (major, minor, patch) = info.pallet_version;
+        assert_eq!(
+            format!("{}.{}.{}", major, minor, patch),
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(info.move_vm_revision, sp_mvm::MOVE_VM_REVISION.as_bytes());
+        assert_eq!(info.features.len(), sp_mvm::FEATURES.len());
+        assert_ne!(info.stdlib_hash, [0; 32]);
+
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+        assert_eq!(Mvm::get_vm_info().stdlib_hash, info.stdlib_hash);
+    });
+}
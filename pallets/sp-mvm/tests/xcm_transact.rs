@@ -0,0 +1,106 @@
+/// Tests related to the failed transacts queue of scripts dispatched by XCM `Transact`.
+use frame_support::{assert_noop, assert_ok};
+use frame_support::assert_err_ignore_postinfo;
+use sp_mvm::{Error, Event as MvmEvent, FailedTransacts, FailedTransactsCount};
+
+mod common;
+use common::assets::{modules, transactions};
+use common::mock::*;
+use common::addr::*;
+use common::utils;
+
+/// Gas limit of the scripts in tests.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Bob's `STORE_U64` dispatched by `Transact`.
+fn transact_store_u64() {
+    assert_ok!(Mvm::transact_execute(
+        Origin::signed(bob_public_key()),
+        transactions::STORE_U64.bytes().to_vec(),
+        GAS_LIMIT,
+    ));
+}
+
+#[test]
+/// Executed scripts aren't queued.
+fn transact_executed() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        utils::publish_module(bob_public_key(), &modules::user::STORE, None).unwrap();
+
+        transact_store_u64();
+        assert_eq!(FailedTransactsCount::<Test>::get(), 0);
+        assert_eq!(Mvm::sequence_number(bob_public_key()), 1);
+    });
+}
+
+#[test]
+/// Failed script is queued and retried once the module it calls is published.
+fn transact_failed_and_retried() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+        let bob = bob_public_key();
+
+        // `Store` module isn't published yet.
+        transact_store_u64();
+        let transact = FailedTransacts::<Test>::get(0).unwrap();
+        assert_eq!(transact.account, bob);
+        assert_eq!(transact.gas_limit, GAS_LIMIT);
+        assert!(Sys::events().iter().any(|rec| rec.event
+            == Event::Mvm(MvmEvent::TransactFailed(0, bob, transact.error))));
+        assert_eq!(Mvm::sequence_number(bob), 0);
+
+        let retry = |max_gas| {
+            Mvm::retry_failed_transact(Origin::signed(alice_public_key()), 0, max_gas)
+        };
+        assert_noop!(
+            retry(GAS_LIMIT - 1),
+            Error::<Test>::FailedTransactGasLimitTooLow
+        );
+        assert!(retry(GAS_LIMIT).is_err());
+        assert!(FailedTransacts::<Test>::contains_key(0));
+
+        utils::publish_module(bob, &modules::user::STORE, None).unwrap();
+        assert_ok!(retry(GAS_LIMIT));
+        assert_eq!(last_event(), Event::Mvm(MvmEvent::FailedTransactRetried(0)));
+        assert!(!FailedTransacts::<Test>::contains_key(0));
+        assert_eq!(FailedTransactsCount::<Test>::get(), 0);
+        assert_eq!(Mvm::sequence_number(bob), 1);
+
+        assert_err_ignore_postinfo!(retry(GAS_LIMIT), Error::<Test>::UnknownFailedTransact);
+    });
+}
+
+#[test]
+/// Failed transacts are discarded by their account or governance, the queue is bounded.
+fn discard_failed_transact() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        roll_next_block();
+
+        for _ in 0..MaxFailedTransacts::get() {
+            transact_store_u64();
+        }
+        // The script is executed before queueing, so the call isn't a no-op.
+        assert_err_ignore_postinfo!(
+            Mvm::transact_execute(
+                Origin::signed(bob_public_key()),
+                transactions::STORE_U64.bytes().to_vec(),
+                GAS_LIMIT,
+            ),
+            Error::<Test>::FailedTransactsQueueFull
+        );
+
+        assert_noop!(
+            Mvm::discard_failed_transact(Origin::signed(alice_public_key()), 0),
+            Error::<Test>::NotFailedTransactAccount
+        );
+        assert_ok!(Mvm::discard_failed_transact(Origin::signed(bob_public_key()), 0));
+        assert_eq!(last_event(), Event::Mvm(MvmEvent::FailedTransactDiscarded(0)));
+        assert_ok!(Mvm::discard_failed_transact(Origin::root(), 1));
+        assert_eq!(FailedTransactsCount::<Test>::get(), 0);
+
+        // Ids aren't reused.
+        transact_store_u64();
+        assert!(FailedTransacts::<Test>::contains_key(2));
+    });
+}
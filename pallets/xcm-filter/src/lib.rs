@@ -0,0 +1,267 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Filters of the cross-chain transfers sent with `pallet-xcm`, configured by governance.
+//!
+//! Reserve transfers and teleports have their own mode: everything, allowlisted destinations
+//! and assets only, or nothing. Assets are identified by their concrete location, e.g. the
+//! location of the currency returned by the runtime `CurrencyIdConvert`.
+
+use frame_support::{pallet_prelude::*, traits::Contains, transactional};
+use frame_system::pallet_prelude::*;
+use sp_std::{boxed::Box, convert::TryFrom, prelude::*};
+use xcm::{
+    latest::{AssetId, MultiAsset, MultiLocation},
+    VersionedMultiLocation,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// Kind of the cross-chain transfer.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TransferKind {
+    /// `reserveTransferAssets`.
+    Reserve,
+    /// `teleportAssets`.
+    Teleport,
+}
+
+/// Which transfers of the kind are allowed.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum FilterMode {
+    /// Any destination and asset.
+    Everything,
+    /// Allowlisted destinations and assets only.
+    Allowlist,
+    /// Transfers are disabled.
+    Nothing,
+}
+
+#[frame_support::pallet]
+pub mod module {
+    use super::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// The origin which may update filters.
+        type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+        /// Weight information for the extrinsics in this module.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Location can't be converted to the current XCM version.
+        BadVersion,
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Filter mode changed. \[kind, mode\]
+        ModeSet(TransferKind, FilterMode),
+        /// Destination added to the allowlist. \[kind, location\]
+        DestinationAllowed(TransferKind, MultiLocation),
+        /// Destination removed from the allowlist. \[kind, location\]
+        DestinationDisallowed(TransferKind, MultiLocation),
+        /// Asset added to the allowlist. \[kind, location\]
+        AssetAllowed(TransferKind, MultiLocation),
+        /// Asset removed from the allowlist. \[kind, location\]
+        AssetDisallowed(TransferKind, MultiLocation),
+    }
+
+    #[pallet::type_value]
+    pub fn DefaultReserveTransferMode() -> FilterMode {
+        FilterMode::Everything
+    }
+
+    #[pallet::type_value]
+    pub fn DefaultTeleportMode() -> FilterMode {
+        FilterMode::Nothing
+    }
+
+    /// Mode of the reserve transfers filter, everything is allowed by default.
+    #[pallet::storage]
+    #[pallet::getter(fn reserve_transfer_mode)]
+    pub type ReserveTransferMode<T: Config> =
+        StorageValue<_, FilterMode, ValueQuery, DefaultReserveTransferMode>;
+
+    /// Mode of the teleports filter, nothing is allowed by default.
+    #[pallet::storage]
+    #[pallet::getter(fn teleport_mode)]
+    pub type TeleportMode<T: Config> =
+        StorageValue<_, FilterMode, ValueQuery, DefaultTeleportMode>;
+
+    /// Allowlisted destinations.
+    ///
+    /// map (TransferKind, MultiLocation) => Option<()>
+    #[pallet::storage]
+    pub type AllowedDestinations<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        TransferKind,
+        Blake2_128Concat,
+        MultiLocation,
+        (),
+        OptionQuery,
+    >;
+
+    /// Allowlisted assets by their concrete location.
+    ///
+    /// map (TransferKind, MultiLocation) => Option<()>
+    #[pallet::storage]
+    pub type AllowedAssets<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        TransferKind,
+        Blake2_128Concat,
+        MultiLocation,
+        (),
+        OptionQuery,
+    >;
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        #[pallet::weight(T::WeightInfo::set_mode())]
+        pub fn set_mode(
+            origin: OriginFor<T>,
+            kind: TransferKind,
+            mode: FilterMode,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            match kind {
+                TransferKind::Reserve => ReserveTransferMode::<T>::put(mode),
+                TransferKind::Teleport => TeleportMode::<T>::put(mode),
+            }
+            Self::deposit_event(Event::ModeSet(kind, mode));
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::update_allowlist())]
+        #[transactional]
+        pub fn allow_destination(
+            origin: OriginFor<T>,
+            kind: TransferKind,
+            location: Box<VersionedMultiLocation>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            let location = Self::location(location)?;
+            if !AllowedDestinations::<T>::contains_key(kind, &location) {
+                AllowedDestinations::<T>::insert(kind, &location, ());
+                Self::deposit_event(Event::DestinationAllowed(kind, location));
+            }
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::update_allowlist())]
+        #[transactional]
+        pub fn disallow_destination(
+            origin: OriginFor<T>,
+            kind: TransferKind,
+            location: Box<VersionedMultiLocation>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            let location = Self::location(location)?;
+            if AllowedDestinations::<T>::take(kind, &location).is_some() {
+                Self::deposit_event(Event::DestinationDisallowed(kind, location));
+            }
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::update_allowlist())]
+        #[transactional]
+        pub fn allow_asset(
+            origin: OriginFor<T>,
+            kind: TransferKind,
+            location: Box<VersionedMultiLocation>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            let location = Self::location(location)?;
+            if !AllowedAssets::<T>::contains_key(kind, &location) {
+                AllowedAssets::<T>::insert(kind, &location, ());
+                Self::deposit_event(Event::AssetAllowed(kind, location));
+            }
+            Ok(())
+        }
+
+        #[pallet::weight(T::WeightInfo::update_allowlist())]
+        #[transactional]
+        pub fn disallow_asset(
+            origin: OriginFor<T>,
+            kind: TransferKind,
+            location: Box<VersionedMultiLocation>,
+        ) -> DispatchResult {
+            T::UpdateOrigin::ensure_origin(origin)?;
+            let location = Self::location(location)?;
+            if AllowedAssets::<T>::take(kind, &location).is_some() {
+                Self::deposit_event(Event::AssetDisallowed(kind, location));
+            }
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Mode of the transfer kind.
+        pub fn mode(kind: TransferKind) -> FilterMode {
+            match kind {
+                TransferKind::Reserve => ReserveTransferMode::<T>::get(),
+                TransferKind::Teleport => TeleportMode::<T>::get(),
+            }
+        }
+
+        /// Whether transfer of `assets` to `dest` is allowed.
+        pub fn is_allowed(
+            kind: TransferKind,
+            dest: &MultiLocation,
+            assets: &[MultiAsset],
+        ) -> bool {
+            match Self::mode(kind) {
+                FilterMode::Everything => true,
+                FilterMode::Nothing => false,
+                FilterMode::Allowlist => {
+                    AllowedDestinations::<T>::contains_key(kind, dest)
+                        && assets.iter().all(|asset| match &asset.id {
+                            AssetId::Concrete(location) => {
+                                AllowedAssets::<T>::contains_key(kind, location)
+                            }
+                            AssetId::Abstract(_) => false,
+                        })
+                }
+            }
+        }
+
+        fn location(location: Box<VersionedMultiLocation>) -> Result<MultiLocation, Error<T>> {
+            MultiLocation::try_from(*location).map_err(|()| Error::<T>::BadVersion)
+        }
+    }
+}
+
+/// Filter of the reserve transfers, `XcmReserveTransferFilter` of `pallet-xcm`.
+pub struct ReserveTransferFilter<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> Contains<(MultiLocation, Vec<MultiAsset>)> for ReserveTransferFilter<T> {
+    fn contains((dest, assets): &(MultiLocation, Vec<MultiAsset>)) -> bool {
+        Pallet::<T>::is_allowed(TransferKind::Reserve, dest, assets)
+    }
+}
+
+/// Filter of the teleports, `XcmTeleportFilter` of `pallet-xcm`.
+pub struct TeleportFilter<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> Contains<(MultiLocation, Vec<MultiAsset>)> for TeleportFilter<T> {
+    fn contains((dest, assets): &(MultiLocation, Vec<MultiAsset>)) -> bool {
+        Pallet::<T>::is_allowed(TransferKind::Teleport, dest, assets)
+    }
+}
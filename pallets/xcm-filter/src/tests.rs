@@ -0,0 +1,131 @@
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+use xcm::latest::{Junction::*, Junctions::*};
+
+fn relay() -> MultiLocation {
+    MultiLocation::parent()
+}
+
+fn sibling() -> MultiLocation {
+    MultiLocation::new(1, X1(Parachain(2000)))
+}
+
+fn asset(location: MultiLocation) -> MultiAsset {
+    (location, 100u128).into()
+}
+
+fn versioned(location: MultiLocation) -> Box<VersionedMultiLocation> {
+    Box::new(location.into())
+}
+
+#[test]
+fn defaults_keep_previous_filters() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert!(ReserveTransferFilter::<Runtime>::contains(&(
+            sibling(),
+            vec![asset(relay())]
+        )));
+        assert!(!TeleportFilter::<Runtime>::contains(&(relay(), vec![asset(relay())])));
+    });
+}
+
+#[test]
+fn set_mode_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+
+        assert_noop!(
+            XcmFilter::set_mode(Origin::signed(5), TransferKind::Reserve, FilterMode::Nothing),
+            BadOrigin
+        );
+
+        assert_ok!(XcmFilter::set_mode(
+            Origin::signed(ALICE),
+            TransferKind::Reserve,
+            FilterMode::Nothing
+        ));
+        System::assert_has_event(Event::XcmFilter(crate::Event::ModeSet(
+            TransferKind::Reserve,
+            FilterMode::Nothing,
+        )));
+        assert!(!ReserveTransferFilter::<Runtime>::contains(&(
+            sibling(),
+            vec![asset(relay())]
+        )));
+
+        assert_ok!(XcmFilter::set_mode(
+            Origin::signed(ALICE),
+            TransferKind::Teleport,
+            FilterMode::Everything
+        ));
+        assert!(TeleportFilter::<Runtime>::contains(&(relay(), vec![asset(relay())])));
+    });
+}
+
+#[test]
+fn allowlist_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+        let transfer = (sibling(), vec![asset(relay())]);
+
+        assert_ok!(XcmFilter::set_mode(
+            Origin::signed(ALICE),
+            TransferKind::Reserve,
+            FilterMode::Allowlist
+        ));
+        assert!(!ReserveTransferFilter::<Runtime>::contains(&transfer));
+
+        assert_noop!(
+            XcmFilter::allow_destination(
+                Origin::signed(5),
+                TransferKind::Reserve,
+                versioned(sibling())
+            ),
+            BadOrigin
+        );
+        assert_ok!(XcmFilter::allow_destination(
+            Origin::signed(ALICE),
+            TransferKind::Reserve,
+            versioned(sibling())
+        ));
+        System::assert_has_event(Event::XcmFilter(crate::Event::DestinationAllowed(
+            TransferKind::Reserve,
+            sibling(),
+        )));
+        // Asset is not allowlisted yet.
+        assert!(!ReserveTransferFilter::<Runtime>::contains(&transfer));
+
+        assert_ok!(XcmFilter::allow_asset(
+            Origin::signed(ALICE),
+            TransferKind::Reserve,
+            versioned(relay())
+        ));
+        assert!(ReserveTransferFilter::<Runtime>::contains(&transfer));
+
+        // Allowlists are separate for every kind.
+        assert!(!TeleportFilter::<Runtime>::contains(&transfer));
+        // Every asset must be allowlisted.
+        assert!(!ReserveTransferFilter::<Runtime>::contains(&(
+            sibling(),
+            vec![asset(relay()), asset(sibling())]
+        )));
+
+        assert_ok!(XcmFilter::disallow_destination(
+            Origin::signed(ALICE),
+            TransferKind::Reserve,
+            versioned(sibling())
+        ));
+        assert!(!ReserveTransferFilter::<Runtime>::contains(&transfer));
+
+        assert_ok!(XcmFilter::disallow_asset(
+            Origin::signed(ALICE),
+            TransferKind::Reserve,
+            versioned(relay())
+        ));
+        assert!(!AllowedAssets::<Runtime>::contains_key(TransferKind::Reserve, relay()));
+    });
+}
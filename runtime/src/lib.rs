@@ -10,7 +10,9 @@ use sp_std::prelude::*;
 use sp_core::OpaqueMetadata;
 use sp_runtime::{
     ApplyExtrinsicResult, create_runtime_str, generic, impl_opaque_keys,
-    traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, ConvertInto},
+    traits::{
+        AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, ConvertInto,
+    },
     transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_api::impl_runtime_apis;
@@ -52,6 +54,7 @@ pub use frame_support::{
     traits::{
         KeyOwnerProofSystem, Randomness, IsInVec, Everything, Nothing, EnsureOrigin,
         OnUnbalanced, Imbalance, Get, Contains, EqualPrivilegeOnly, ConstU32,
+        schedule::{Anon as ScheduleAnon, DispatchTime, LOWEST_PRIORITY},
     },
     weights::{
         Weight, IdentityFee, DispatchClass,
@@ -69,7 +72,11 @@ use frame_system::{
 
 /// Import the Move-pallet.
 pub use sp_mvm::gas::{GasWeightMapping};
-pub use sp_mvm_rpc_runtime::types::MVMApiEstimation;
+pub use sp_mvm_rpc_runtime::types::{
+    MVMApiEstimation, MVMApiAllowedScript, MVMApiVmInfo, MVMApiXcmFee, MVMApiBlockGas,
+    MVMApiFee, MVMApiSubmission, MVMApiPackage, MVMApiTransaction, MVMApiExecutionResult,
+    MVMApiEvent,
+};
 pub use parachain_staking::{InflationInfo, Range};
 pub use pallet_author_slot_filter::EligibilityValue;
 
@@ -104,10 +111,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     // codebase, it _will_ be nox-polkadot.
     impl_name: create_runtime_str!("nox"),
     authoring_version: 1,
-    spec_version: 101,
+    spec_version: 102,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 3,
     state_version: 0,
 };
 
@@ -421,8 +428,8 @@ parameter_types! {
 }
 
 impl pallet_transaction_payment::Config for Runtime {
-    type OnChargeTransaction =
-        pallet_transaction_payment::CurrencyAdapter<Balances, DealWithFees<Runtime>>;
+    // Native fees are distributed with `DealWithFees`, Move VM calls could be paid in tokens.
+    type OnChargeTransaction = fee_currencies::FeeAdapter<Balances, DealWithFees<Runtime>>;
     type TransactionByteFee = TransactionByteFee;
     type WeightToFee = IdentityFee<Balance>;
     type FeeMultiplierUpdate = ();
@@ -439,9 +446,19 @@ parameter_types! {
     pub const ReservedDmpWeight: Weight = MAXIMUM_BLOCK_WEIGHT / 4;
 }
 
+/// Publishes the relay chain block the parachain block is built on to Move contracts.
+pub struct MoveChainInfo;
+impl cumulus_pallet_parachain_system::OnSystemEvent for MoveChainInfo {
+    fn on_validation_data(data: &cumulus_primitives_core::PersistedValidationData) {
+        Mvm::store_chain_info(ParachainInfo::get().into(), data.relay_parent_number);
+    }
+
+    fn on_validation_code_applied() {}
+}
+
 impl cumulus_pallet_parachain_system::Config for Runtime {
     type Event = Event;
-    type OnSystemEvent = ();
+    type OnSystemEvent = MoveChainInfo;
     type SelfParaId = parachain_info::Pallet<Runtime>;
     type OutboundXcmpMessageSource = XcmpQueue;
     type XcmpMessageHandler = XcmpQueue;
@@ -581,6 +598,8 @@ pub type XcmOriginToTransactDispatchOrigin = (
     // Sovereign account converter; this attempts to derive an `AccountId` from the origin location
     // using `LocationToAccountId` and then turn that into the usual `Signed` origin. Useful for
     // foreign chains who want to have a local sovereign account on this chain which they control.
+    // Sibling chains run Move scripts with `Transact` of `Mvm::execute` this way: the sovereign
+    // account is the script signer and its Move address.
     SovereignSignedViaLocation<LocationToAccountId, Origin>,
     // Native converter for Relay-chain (Parent) location; will converts to a `Relay` origin when
     // recognised.
@@ -712,6 +731,39 @@ impl WeightTrader for SimpleWeightTrader {
     }
 }
 
+/// Instructions of the message executed by the chain receiving transferred assets:
+/// `ReserveAssetDeposited` (or `WithdrawAsset`), `ClearOrigin`, `BuyExecution` and
+/// `DepositAsset` (or `DepositReserveAsset` of the reserve chain forwarding the assets).
+const XCM_TRANSFER_INSTRUCTIONS: u64 = 4;
+
+/// Weight and fee of the `orml-xtokens` transfer of the currency to `dest`, executed by the
+/// destination and by the reserve chain of the currency, if the transfer goes through it.
+/// Weight is priced by the weigher and `SimpleWeightTrader` of this chain, so fees of chains
+/// charging otherwise are approximate.
+pub fn xcm_transfer_fee(
+    currency_id: CurrencyId,
+    dest: &MultiLocation,
+) -> Result<(Weight, Balance), &'static str> {
+    use orml_traits::location::{Parse, Reserve};
+
+    let location =
+        CurrencyIdConvert::convert(currency_id).ok_or("Currency can't be transferred")?;
+    let dest = dest.chain_part().ok_or("Invalid destination")?;
+    let reserve = AbsoluteReserveProvider::reserve(&(location.clone(), 1).into())
+        .ok_or("Currency has no reserve")?;
+
+    let hops = if reserve == SelfLocation::get() || reserve == dest { 1 } else { 2 };
+    let weight = UnitWeightCost::get() * XCM_TRANSFER_INSTRUCTIONS * hops;
+
+    let payment = MultiAsset::from((location.clone(), u128::MAX));
+    let unused = SimpleWeightTrader::new()
+        .buy_weight(weight, payment.into())
+        .map_err(|_| "Weight can't be bought with the currency")?;
+    let change = unused.fungible.get(&Concrete(location)).copied().unwrap_or_default();
+
+    Ok((weight, u128::MAX - change))
+}
+
 pub struct XcmConfig;
 impl xcm_executor::Config for XcmConfig {
     type Call = Call;
@@ -750,8 +802,8 @@ impl pallet_xcm::Config for Runtime {
     type ExecuteXcmOrigin = EnsureXcmOrigin<Origin, LocalOriginToLocation>;
     type XcmExecutor = XcmExecutor<XcmConfig>;
     type XcmExecuteFilter = Nothing;
-    type XcmTeleportFilter = Nothing;
-    type XcmReserveTransferFilter = Everything;
+    type XcmTeleportFilter = xcm_filter::TeleportFilter<Runtime>;
+    type XcmReserveTransferFilter = xcm_filter::ReserveTransferFilter<Runtime>;
     type Weigher = FixedWeightBounds<UnitWeightCost, Call, MaxInstructions>;
     type LocationInverter = LocationInverter<Ancestry>;
     type Origin = Origin;
@@ -760,6 +812,13 @@ impl pallet_xcm::Config for Runtime {
     const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
 }
 
+/// Filters of the transfers sent with `pallet_xcm`, managed by root.
+impl xcm_filter::Config for Runtime {
+    type Event = Event;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type WeightInfo = xcm_filter::weights::SubstrateWeight<Runtime>;
+}
+
 impl cumulus_pallet_xcm::Config for Runtime {
     type Event = Event;
     type XcmExecutor = XcmExecutor<XcmConfig>;
@@ -841,6 +900,356 @@ impl GasWeightMapping for MoveVMGasWeightMapping {
 parameter_types! {
     /// VM pallet address (used to reserve funds during VM native operations).
     pub const MVMPalletId: PalletId = PalletId(*b"_nox/mvm");
+    /// Delay before account migration can be enacted, gives time to cancel it if the key is compromised.
+    pub const AccountMigrationDelay: BlockNumber = 1 * DAYS;
+    /// Maximum size of the module bytecode, the largest standard library module is far below.
+    pub const MaxModuleSize: u32 = 64 * 1024;
+    /// Maximum size of the module uploaded in chunks, e.g. framework modules.
+    pub const MaxStagedModuleSize: u32 = 1024 * 1024;
+    /// Maximum size of the source map attached to the module.
+    pub const MaxSourceMapSize: u32 = 256 * 1024;
+    /// Maximum size of the script transaction, including arguments.
+    pub const MaxScriptSize: u32 = 32 * 1024;
+    /// Maximum nesting depth of the script type arguments.
+    pub const MaxTypeArgDepth: u32 = 8;
+    /// Maximum amount of modules (or functions) a session key is allowed to call.
+    pub const MaxSessionScope: u32 = 16;
+    /// Maximum amount of failed XCM-dispatched scripts queued for retries.
+    pub const MaxFailedTransacts: u32 = 1024;
+    /// Deposit per byte of the published module, e.g. 10 PONT for 10 KB module.
+    pub const ModuleDepositPerByte: Balance = CurrencyId::NATIVE.millies().times(1);
+    /// Deposit per byte of the stored resources, a resource of a few fields takes about 100 bytes.
+    pub const ResourceDepositPerByte: Balance = CurrencyId::NATIVE.millies().times(1);
+    /// Deposit reserved per open multisig Move script, script bytecode is up to 32 KB.
+    pub const MultisigDeposit: Balance = CurrencyId::NATIVE.times(10);
+    /// Maximum amount of the open multisig Move scripts proposed by one account.
+    pub const MaxMultisigProposals: u32 = 16;
+    /// Maximum treasury spends of one Move spender per treasury spend period, larger grants go
+    /// through governance.
+    pub const MaxTreasurySpend: u128 = CurrencyId::NATIVE.times(10_000) as u128;
+    /// Gas budget of the Move callbacks executed at the block initialization, 50 ms of the block.
+    pub const MaxCallbackGas: u64 = GAS_PER_SECOND / 20;
+    /// Maximum amount of the Move callback subscriptions, each is checked every block.
+    pub const MaxCallbackSubscriptions: u32 = 256;
+    /// Gas limit of the scheduled Move script, 100 ms of the block.
+    pub const MaxScheduledGas: u64 = GAS_PER_SECOND / 10;
+    /// Maximum amount of the pending Move scripts scheduled by one account.
+    pub const MaxScheduledScripts: u32 = 16;
+    /// Scheduled script gas is paid as the weight of the same execution time is.
+    pub const ScheduledGasUnitPrice: u64 = WEIGHT_PER_GAS;
+    /// One Move callback per account: every block or on deposits.
+    pub const MaxAccountCallbackSubscriptions: u32 = 1;
+    /// Deposit reserved per Move callback subscription.
+    pub const CallbackDeposit: Balance = CurrencyId::NATIVE.times(10);
+    /// Callback gas is paid as the weight of the same execution time is.
+    pub const CallbackGasUnitPrice: u64 = WEIGHT_PER_GAS;
+}
+
+/// Schedules Move scripts as signed `execute` calls with the scheduler pallet.
+pub struct MoveScriptScheduler;
+
+impl sp_mvm::schedule::ScheduleScript<AccountId, BlockNumber> for MoveScriptScheduler {
+    fn schedule_script(
+        who: AccountId,
+        when: BlockNumber,
+        tx_bc: Vec<u8>,
+        gas_limit: u64,
+    ) -> sp_runtime::DispatchResult {
+        let call = Call::Mvm(sp_mvm::Call::execute { tx_bc, gas_limit });
+        <Scheduler as ScheduleAnon<BlockNumber, Call, OriginCaller>>::schedule(
+            DispatchTime::At(when),
+            None,
+            LOWEST_PRIORITY,
+            RawOrigin::Signed(who).into(),
+            call.into(),
+        )
+        .map(|_| ())
+    }
+}
+
+/// Converts location of the Move pallet to XCM `MultiLocation`.
+fn move_location(
+    location: sp_mvm::xtransfer::Location,
+) -> Result<MultiLocation, sp_runtime::DispatchError> {
+    use sp_mvm::xtransfer::Junction as MoveJunction;
+
+    let mut interior = Junctions::Here;
+    for junction in location.junctions {
+        let junction = match junction {
+            MoveJunction::Parachain(id) => Junction::Parachain(id),
+            MoveJunction::AccountId32(id) => Junction::AccountId32 {
+                network: NetworkId::Any,
+                id,
+            },
+            MoveJunction::AccountKey20(key) => Junction::AccountKey20 {
+                network: NetworkId::Any,
+                key,
+            },
+            MoveJunction::GeneralKey(key) => Junction::GeneralKey(key),
+        };
+        interior
+            .push(junction)
+            .map_err(|_| sp_runtime::DispatchError::Other("Too many junctions"))?;
+    }
+    Ok(MultiLocation::new(location.parents, interior))
+}
+
+/// Sends cross-chain transfers requested by Move code with xtokens pallet.
+pub struct MoveXcmTransfer;
+
+impl sp_mvm::xtransfer::XcmTransfer<AccountId> for MoveXcmTransfer {
+    fn transfer(
+        who: AccountId,
+        ticker: Vec<u8>,
+        amount: u64,
+        dest: sp_mvm::xtransfer::Location,
+        dest_weight: u64,
+    ) -> sp_runtime::DispatchResult {
+        let currency_id = CurrencyId::try_from(ticker)
+            .map_err(|_| sp_runtime::DispatchError::Other("Unknown currency ticker"))?;
+        let dest = move_location(dest)?;
+
+        Xtokens::transfer(
+            Origin::signed(who),
+            currency_id,
+            amount,
+            Box::new(xcm::VersionedMultiLocation::V1(dest)),
+            dest_weight,
+        )
+    }
+}
+
+/// Ensures XCM origin (`Transact` with `OriginKind::Xcm`) and converts its location into the
+/// account with `LocationToAccountId`, the same account `SovereignSignedViaLocation` resolves.
+pub struct EnsureXcmAccount;
+
+impl EnsureOrigin<Origin> for EnsureXcmAccount {
+    type Success = AccountId;
+
+    fn try_origin(origin: Origin) -> Result<AccountId, Origin> {
+        use xcm_executor::traits::Convert as XcmConvert;
+
+        let xcm_origin: Result<pallet_xcm::Origin, Origin> = origin.clone().into();
+        match xcm_origin {
+            Ok(pallet_xcm::Origin::Xcm(location)) => {
+                LocationToAccountId::convert(location).map_err(|_| origin)
+            }
+            _ => Err(origin),
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn successful_origin() -> Origin {
+        pallet_xcm::Origin::Xcm(MultiLocation::parent()).into()
+    }
+}
+
+/// Claims assets trapped by `PolkadotXcm`: executes `ClaimAsset` on behalf of the location the
+/// assets are trapped under and deposits them into the beneficiary.
+pub struct MoveAssetClaims;
+
+impl sp_mvm::xtransfer::AssetClaims<AccountId> for MoveAssetClaims {
+    fn claim(
+        origin: sp_mvm::xtransfer::Location,
+        ticker: Vec<u8>,
+        amount: u128,
+        beneficiary: AccountId,
+    ) -> sp_runtime::DispatchResult {
+        let currency_id = CurrencyId::try_from(ticker)
+            .map_err(|_| sp_runtime::DispatchError::Other("Unknown currency ticker"))?;
+        let asset = CurrencyIdConvert::convert(currency_id)
+            .ok_or(sp_runtime::DispatchError::Other("Currency has no location"))?;
+        let origin = move_location(origin)?;
+
+        let message = Xcm(vec![
+            ClaimAsset {
+                assets: MultiAsset::from((asset, amount)).into(),
+                ticket: Here.into(),
+            },
+            DepositAsset {
+                assets: Wild(All),
+                max_assets: 1,
+                beneficiary: Junction::AccountId32 {
+                    network: NetworkId::Any,
+                    id: beneficiary.into(),
+                }
+                .into(),
+            },
+        ]);
+        // Executed by the runtime, weight is credited upfront as `PolkadotXcm::execute` does.
+        let max_weight = UnitWeightCost::get().saturating_mul(MaxInstructions::get() as Weight);
+        XcmExecutor::<XcmConfig>::execute_xcm_in_credit(origin, message, max_weight, max_weight)
+            .ensure_complete()
+            .map_err(|_| sp_runtime::DispatchError::Other("Failed to claim trapped assets"))
+    }
+}
+
+/// Public key of the signer of the current extrinsic, read from its signature: sr25519 and
+/// ed25519 keys are the account ids, ecdsa keys are recovered as `MultiSignature::verify` does.
+pub struct ExtrinsicSenderKeys;
+
+impl sp_mvm::auth::SenderKeyProvider<AccountId> for ExtrinsicSenderKeys {
+    fn sender_key(who: &AccountId) -> Option<sp_mvm::auth::SenderKey> {
+        use sp_core::{Decode, Encode};
+        use sp_io::hashing::blake2_256;
+        use sp_mvm::auth::SenderKey;
+        use sp_mvm::crypto::SignatureScheme;
+
+        let index = System::extrinsic_index()?;
+        let xt = UncheckedExtrinsic::decode(&mut &System::extrinsic_data(index)[..]).ok()?;
+        let (address, signature, extra) = xt.signature?;
+        if address != Address::Id(who.clone()) {
+            return None;
+        }
+
+        let account: &[u8] = who.as_ref();
+        match signature {
+            sp_runtime::MultiSignature::Sr25519(_) => {
+                Some(SenderKey::new(SignatureScheme::Sr25519, account))
+            }
+            sp_runtime::MultiSignature::Ed25519(_) => {
+                Some(SenderKey::new(SignatureScheme::Ed25519, account))
+            }
+            sp_runtime::MultiSignature::Ecdsa(signature) => {
+                // The extrinsic is checked already, the payload is signed as it was.
+                let payload = generic::SignedPayload::new(xt.function, extra).ok()?;
+                let key = payload.using_encoded(|message| {
+                    sp_io::crypto::secp256k1_ecdsa_recover_compressed(
+                        &signature.0,
+                        &blake2_256(message),
+                    )
+                    .ok()
+                })?;
+                // Ecdsa accounts are the hashes of the keys.
+                (blake2_256(&key) == *<AccountId as AsRef<[u8; 32]>>::as_ref(who))
+                    .then(|| SenderKey::new(SignatureScheme::Ecdsa, &key))
+            }
+        }
+    }
+}
+
+/// Lockups of `pallet-vesting`, vested coins are unlocked as by the `vest` extrinsic.
+pub struct MoveLockups;
+
+impl sp_mvm::vesting::Lockups<AccountId> for MoveLockups {
+    fn lockup(who: &AccountId) -> Option<sp_mvm::vesting::Lockup> {
+        use frame_support::traits::VestingSchedule;
+
+        let locked = <Vesting as VestingSchedule<AccountId>>::vesting_balance(who)?;
+        let schedules = Vesting::vesting(who)?
+            .iter()
+            .map(|schedule| sp_mvm::vesting::Schedule {
+                locked: schedule.locked().into(),
+                per_block: schedule.per_block().into(),
+                starting_block: schedule.starting_block().into(),
+            })
+            .collect();
+        Some(sp_mvm::vesting::Lockup {
+            locked: locked.into(),
+            schedules,
+        })
+    }
+
+    fn vest(who: AccountId) -> sp_runtime::DispatchResult {
+        Vesting::vest(Origin::signed(who))
+    }
+}
+
+/// Staking state of `parachain-staking`: rounds are eras, stakes are delegations and bonds of
+/// collator candidates.
+pub struct MoveStakingInfo;
+
+impl sp_mvm::staking::StakingInfo<AccountId> for MoveStakingInfo {
+    fn round() -> Option<sp_mvm::staking::Round<AccountId>> {
+        let round = ParachainStaking::round();
+        Some(sp_mvm::staking::Round {
+            index: round.current.into(),
+            first_block: round.first.into(),
+            length: round.length.into(),
+            collators: ParachainStaking::selected_candidates(),
+            total_staked: ParachainStaking::total().into(),
+        })
+    }
+
+    fn stake(who: &AccountId) -> Option<sp_mvm::staking::Stake> {
+        let delegated = ParachainStaking::delegator_state(who).map(|state| state.total);
+        let collator_bond = ParachainStaking::candidate_info(who).map(|info| info.bond);
+        if delegated.is_none() && collator_bond.is_none() {
+            return None;
+        }
+        Some(sp_mvm::staking::Stake {
+            delegated: delegated.unwrap_or_default().into(),
+            collator_bond: collator_bond.unwrap_or_default().into(),
+        })
+    }
+}
+
+/// Proposals of Move code are public proposals of `pallet-democracy`: the proposer notes the
+/// preimage of `enact_governance_proposal` and proposes it with the deposit.
+pub struct MoveGovernance;
+
+impl sp_mvm::governance::GovernanceBridge<AccountId> for MoveGovernance {
+    fn propose(
+        proposer: AccountId,
+        id: u64,
+        call: &[u8],
+        callback_weight: Weight,
+        deposit: u128,
+    ) -> sp_runtime::DispatchResult {
+        use sp_core::Encode;
+        use frame_support::weights::GetDispatchInfo;
+        use sp_runtime::traits::Hash;
+
+        let call = decode_proposal_call(call)?;
+        let max_weight = call.get_dispatch_info().weight.saturating_add(callback_weight);
+        let enactment = Call::Mvm(sp_mvm::Call::enact_governance_proposal { id, max_weight });
+        let preimage = enactment.encode();
+        let proposal_hash = BlakeTwo256::hash(&preimage);
+        let deposit = Balance::try_from(deposit)
+            .map_err(|_| sp_runtime::DispatchError::Other("Proposal deposit overflow"))?;
+
+        Democracy::note_preimage(Origin::signed(proposer.clone()), preimage)?;
+        Democracy::propose(Origin::signed(proposer), proposal_hash, deposit)
+    }
+
+    fn call_weight(call: &[u8]) -> Option<Weight> {
+        use frame_support::weights::GetDispatchInfo;
+
+        decode_proposal_call(call).ok().map(|call| call.get_dispatch_info().weight)
+    }
+
+    fn dispatch(call: Vec<u8>) -> frame_support::dispatch::DispatchResultWithPostInfo {
+        use frame_support::dispatch::Dispatchable;
+
+        decode_proposal_call(&call)?.dispatch(RawOrigin::Root.into())
+    }
+}
+
+/// Runtime call of the governance proposal of Move code.
+fn decode_proposal_call(call: &[u8]) -> Result<Call, sp_runtime::DispatchError> {
+    use codec::DecodeLimit;
+
+    Call::decode_with_depth_limit(sp_api::MAX_EXTRINSIC_DEPTH, &mut &call[..])
+        .map_err(|_| sp_runtime::DispatchError::Other("Invalid proposal call"))
+}
+
+/// Treasury spends of Move code are spend proposals of `pallet-treasury`, approved right away:
+/// the spender reserves the proposal bond and the beneficiary is paid at the next spend period.
+pub struct MoveTreasurySpend;
+
+impl sp_mvm::treasury::TreasurySpend<AccountId> for MoveTreasurySpend {
+    fn spend(
+        spender: AccountId,
+        beneficiary: AccountId,
+        amount: u128,
+    ) -> sp_runtime::DispatchResult {
+        let amount = Balance::try_from(amount)
+            .map_err(|_| sp_runtime::DispatchError::Other("Treasury spend overflow"))?;
+        let id = Treasury::proposal_count();
+
+        Treasury::propose_spend(Origin::signed(spender), amount, MultiAddress::Id(beneficiary))?;
+        Treasury::approve_proposal(RawOrigin::Root.into(), id)
+    }
 }
 
 /// Configure the Move-pallet in pallets/sp-mvm.
@@ -857,6 +1266,75 @@ impl sp_mvm::Config for Runtime {
     /// Pallet Id.
     type PalletId = MVMPalletId;
 
+    /// Account migration delay.
+    type AccountMigrationDelay = AccountMigrationDelay;
+
+    /// Bounds checked before bytecode verification.
+    type MaxModuleSize = MaxModuleSize;
+    type MaxStagedModuleSize = MaxStagedModuleSize;
+    type MaxSourceMapSize = MaxSourceMapSize;
+    type MaxScriptSize = MaxScriptSize;
+    type MaxTypeArgDepth = MaxTypeArgDepth;
+    type MaxSessionScope = MaxSessionScope;
+
+    /// Scripts are scheduled with the scheduler pallet, their gas is paid at the scheduling.
+    type ScriptScheduler = MoveScriptScheduler;
+    type MaxScheduledGas = MaxScheduledGas;
+    type MaxScheduledScripts = MaxScheduledScripts;
+    type ScheduledGasUnitPrice = ScheduledGasUnitPrice;
+
+    /// Cross-chain transfers requested by Move code are sent with xtokens pallet.
+    type XcmTransfer = MoveXcmTransfer;
+
+    /// Assets trapped by failed incoming transfers are claimed by root into any account.
+    type AssetClaims = MoveAssetClaims;
+
+    /// Scripts are dispatched by XCM `Transact` with `OriginKind::Xcm`, signed by the sovereign
+    /// account of the origin location.
+    type XcmTransactOrigin = EnsureXcmAccount;
+    type MaxFailedTransacts = MaxFailedTransacts;
+
+    /// Randomness seed for Move contracts.
+    type Randomness = RandomnessCollectiveFlip;
+    // Grouping existing resources requires a storage migration.
+    type ResourceGroups = ();
+
+    /// Keys of the extrinsic signers are stored for `0x1::Auth`.
+    type SenderKeys = ExtrinsicSenderKeys;
+
+    /// Lockups of `pallet-vesting` are stored for `0x1::Vesting`.
+    type Lockups = MoveLockups;
+
+    /// Rounds and stakes of `parachain-staking` are stored for `0x1::Staking`.
+    type Staking = MoveStakingInfo;
+
+    /// Move code proposes runtime calls to `pallet-democracy`.
+    type Governance = MoveGovernance;
+
+    /// Allowed Move spenders propose and approve `pallet-treasury` spends.
+    type TreasurySpend = MoveTreasurySpend;
+    type MaxTreasurySpend = MaxTreasurySpend;
+    type TreasurySpendPeriod = SpendPeriod;
+
+    /// Move callbacks are bounded by the block gas budget and paid by their subscribers.
+    type MaxCallbackGas = MaxCallbackGas;
+    type MaxCallbackSubscriptions = MaxCallbackSubscriptions;
+    type MaxAccountCallbackSubscriptions = MaxAccountCallbackSubscriptions;
+    type CallbackDeposit = CallbackDeposit;
+    type CallbackGasUnitPrice = CallbackGasUnitPrice;
+
+    /// Module deposits are reserved in native currency.
+    type Currency = Balances;
+    type ModuleDepositPerByte = ModuleDepositPerByte;
+    type ResourceDepositPerByte = ResourceDepositPerByte;
+
+    /// Multisig proposals reserve deposits in native currency.
+    type MultisigDeposit = MultisigDeposit;
+    type MaxMultisigProposals = MaxMultisigProposals;
+
+    /// Gas tips are distributed as fees.
+    type OnGasTip = DealWithFees<Runtime>;
+
     /// Currency id.
     type CurrencyId = CurrencyId;
 
@@ -1037,6 +1515,27 @@ impl transaction_pause::Config for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub TreasuryAccount: AccountId = TreasuryPalletId::get().into_account();
+}
+
+/// Move VM calls, their fees could be paid in registered tokens.
+pub struct MvmCalls;
+impl Contains<Call> for MvmCalls {
+    fn contains(call: &Call) -> bool {
+        matches!(call, Call::Mvm(_))
+    }
+}
+
+impl fee_currencies::Config for Runtime {
+    type Event = Event;
+    type MultiCurrency = Tokens;
+    type TokenFeeCalls = MvmCalls;
+    type FeeReceiver = TreasuryAccount;
+    type UpdateOrigin = EnsureRoot<AccountId>;
+    type WeightInfo = fee_currencies::weights::PontemWeights<Runtime>;
+}
+
 pub struct BaseCallFilter;
 impl Contains<Call> for BaseCallFilter {
     fn contains(call: &Call) -> bool {
@@ -1082,6 +1581,7 @@ construct_runtime!(
         Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
         Currencies: module_currencies::{Pallet, Call, Storage, Event<T>},
         TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
+        FeeCurrencies: fee_currencies::{Pallet, Call, Storage, Event<T>},
 
         // Staking.
         ParachainStaking: parachain_staking::{Pallet, Call, Storage, Event<T>, Config<T>} = 40,
@@ -1102,14 +1602,15 @@ construct_runtime!(
         Xtokens: orml_xtokens::{Pallet, Storage, Call, Event<T>} = 64,
         UnknownTokens: orml_unknown_tokens::{Pallet, Storage, Event} = 65,
         OrmlXcm: orml_xcm::{Pallet, Call, Event<T>} = 66,
+        XcmFilter: xcm_filter::{Pallet, Call, Storage, Event<T>} = 71,
 
         // Move VM
-        Mvm: sp_mvm::{Pallet, Call, Storage, Config<T>, Event<T>},
-        Groupsign: groupsign::{Pallet, Call, Origin<T>, Event<T>},
-        MultiSig: pallet_multisig::{Pallet, Call, Storage, Event<T>},
+        Mvm: sp_mvm::{Pallet, Call, Storage, Config<T>, Event<T>, ValidateUnsigned} = 67,
+        Groupsign: groupsign::{Pallet, Call, Origin<T>, Event<T>} = 68,
+        MultiSig: pallet_multisig::{Pallet, Call, Storage, Event<T>} = 69,
 
         // Transaction pause
-        TransactionPause: transaction_pause::{Pallet, Call, Storage, Event<T>, Config<T>},
+        TransactionPause: transaction_pause::{Pallet, Call, Storage, Event<T>, Config<T>} = 70,
     }
 );
 
@@ -1132,11 +1633,17 @@ pub type SignedExtra = (
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    sp_mvm::gas_price::ChargeGasPrice<Runtime>,
+    sp_mvm::session::CheckSessionKey<Runtime>,
+    sp_mvm::sponsor::CheckSponsor<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
+/// Runtime migrations, run before pallets ones, e.g. Move resources migrations
+/// (`sp_mvm::migrations::MigrateResources`) once framework structs change their layout.
+pub type Migrations = ();
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
     Runtime,
@@ -1144,8 +1651,139 @@ pub type Executive = frame_executive::Executive<
     frame_system::ChainContext<Runtime>,
     Runtime,
     AllPalletsReversedWithSystemFirst,
+    Migrations,
 >;
 
+/// Inclusion fee (base, length and weight fees) of the signed `execute` extrinsic of the script
+/// with the gas limit, as queried by `TransactionPaymentApi::query_fee_details`. The extrinsic
+/// is signed by a dummy account as by `pontem move execute` (immortal era), so its length is
+/// the one of submitted extrinsics (the gas unit price is a compact field of `ChargeGasPrice`).
+/// The gas price tip (`gas_price::gas_tip`) isn't included.
+pub fn execute_fee(
+    tx_bc: Vec<u8>,
+    gas_limit: u64,
+    gas_unit_price: u64,
+) -> Result<pallet_transaction_payment::InclusionFee<Balance>, &'static str> {
+    use sp_core::Encode;
+
+    let call = Call::Mvm(sp_mvm::Call::execute { tx_bc, gas_limit });
+    let extra: SignedExtra = (
+        frame_system::CheckSpecVersion::new(),
+        frame_system::CheckTxVersion::new(),
+        frame_system::CheckGenesis::new(),
+        frame_system::CheckEra::from(generic::Era::Immortal),
+        frame_system::CheckNonce::from(0),
+        frame_system::CheckWeight::new(),
+        pallet_transaction_payment::ChargeTransactionPayment::from(0),
+        sp_mvm::gas_price::ChargeGasPrice::from(gas_unit_price),
+        sp_mvm::session::CheckSessionKey::new(),
+        sp_mvm::sponsor::CheckSponsor::new(),
+    );
+    let signature = sp_core::sr25519::Signature::from_raw([0; 64]);
+    let uxt = UncheckedExtrinsic::new_signed(
+        call,
+        Address::Id(AccountId::new([0; 32])),
+        signature.into(),
+        extra,
+    );
+    let len = uxt.encoded_size() as u32;
+
+    TransactionPayment::query_fee_details(uxt, len)
+        .inclusion_fee
+        .ok_or("Execution doesn't pay fees")
+}
+
+/// SCALE encoded `execute` call of the script and the unsigned extrinsic with the call, so
+/// clients without the runtime metadata sign the call with the signed extensions and submit it.
+/// The gas unit price is set by the client in `ChargeGasPrice` extension.
+pub fn encode_execute_call(tx_bc: Vec<u8>, gas_limit: u64) -> MVMApiSubmission {
+    use sp_core::Encode;
+
+    let call = Call::Mvm(sp_mvm::Call::execute { tx_bc, gas_limit });
+    MVMApiSubmission {
+        call: call.encode(),
+        unsigned_extrinsic: UncheckedExtrinsic::new_unsigned(call).encode(),
+    }
+}
+
+/// Gas unit price of the signed Move extrinsic, set in its `ChargeGasPrice` extension.
+pub fn gas_unit_price_of(xt: &UncheckedExtrinsic) -> Option<u64> {
+    match (&xt.function, &xt.signature) {
+        (Call::Mvm(call), Some((_, _, extra))) => {
+            sp_mvm::gas_price::gas_limit_of::<Runtime>(call).map(|_| extra.7.gas_unit_price())
+        }
+        _ => None,
+    }
+}
+
+/// Move extrinsic of the block with its outcome, read from the events of the block, so it
+/// has to be called at the block including the extrinsic.
+pub fn get_transaction(extrinsic: Vec<u8>, index: u32) -> Option<MVMApiTransaction<AccountId>> {
+    use sp_core::Decode;
+    use frame_support::dispatch::GetCallName;
+
+    let xt = UncheckedExtrinsic::decode(&mut &extrinsic[..]).ok()?;
+    let call = match &xt.function {
+        Call::Mvm(call) => call,
+        _ => return None,
+    };
+    let gas_limit = sp_mvm::gas_price::gas_limit_of::<Runtime>(call)?;
+    let payload = match call {
+        sp_mvm::Call::execute { tx_bc, .. }
+        | sp_mvm::Call::execute_with_sequence { tx_bc, .. }
+        | sp_mvm::Call::execute_with_session_key { tx_bc, .. }
+        | sp_mvm::Call::execute_sponsored { tx_bc, .. } => tx_bc.clone(),
+        sp_mvm::Call::publish_module { module_bc, .. }
+        | sp_mvm::Call::publish_module_with_source_map { module_bc, .. } => module_bc.clone(),
+        sp_mvm::Call::publish_package { package, .. }
+        | sp_mvm::Call::publish_package_with_metadata { package, .. } => package.clone(),
+        // Staged module is removed from the state once published.
+        _ => Vec::new(),
+    };
+
+    let mut transaction = MVMApiTransaction {
+        signer: match &xt.signature {
+            Some((sp_runtime::MultiAddress::Id(account), ..)) => Some(account.clone()),
+            _ => None,
+        },
+        call: call.get_call_name().as_bytes().to_vec(),
+        payload,
+        gas_limit,
+        gas_unit_price: gas_unit_price_of(&xt).unwrap_or_default(),
+        success: false,
+        result: None,
+        events: Vec::new(),
+    };
+    for record in System::events() {
+        if record.phase != frame_system::Phase::ApplyExtrinsic(index) {
+            continue;
+        }
+        match record.event {
+            Event::System(frame_system::Event::ExtrinsicSuccess { .. }) => {
+                transaction.success = true;
+            }
+            Event::Mvm(sp_mvm::Event::ExecutionResult(result)) => {
+                transaction.result = Some(MVMApiExecutionResult {
+                    gas_used: result.gas_used,
+                    status_code: result.status_code,
+                    abort_code: result.abort_code,
+                    writes: result.writes,
+                    events: result.events,
+                });
+            }
+            Event::Mvm(sp_mvm::Event::Event(guid, ty_tag, message)) => {
+                transaction.events.push(MVMApiEvent {
+                    guid,
+                    ty_tag,
+                    message,
+                });
+            }
+            _ => {}
+        }
+    }
+    Some(transaction)
+}
+
 impl_runtime_apis! {
     impl sp_api::Core<Block> for Runtime {
         fn version() -> RuntimeVersion {
@@ -1243,23 +1881,39 @@ impl_runtime_apis! {
         }
 
         // Estimate gas for publish module.
-        fn estimate_gas_publish(account: AccountId, module_bc: Vec<u8>, gas_limit: u64) -> Result<MVMApiEstimation, sp_runtime::DispatchError> {
+        fn estimate_gas_publish(account: AccountId, module_bc: Vec<u8>, gas_limit: u64, deadline: Option<u64>) -> Result<MVMApiEstimation, sp_runtime::DispatchError> {
+            let gas_limit = Mvm::deadline_gas_limit(gas_limit, deadline);
             // TODO: pass real error.
             let vm_result = Mvm::raw_publish_module(&account, module_bc, gas_limit, true)?;
 
             Ok(MVMApiEstimation {
                 gas_used: vm_result.gas_used,
                 status_code: vm_result.status_code as u64,
+                abort_code: sp_mvm::result::ExecutionResult::from(&vm_result).abort_code,
+            })
+        }
+
+        // Estimate gas for publish package.
+        fn estimate_gas_publish_package(account: AccountId, modules: Vec<Vec<u8>>, gas_limit: u64, deadline: Option<u64>) -> Result<MVMApiEstimation, sp_runtime::DispatchError> {
+            let gas_limit = Mvm::deadline_gas_limit(gas_limit, deadline);
+            let vm_result = Mvm::raw_publish_package(&account, modules, gas_limit, true)?;
+
+            Ok(MVMApiEstimation {
+                gas_used: vm_result.gas_used,
+                status_code: vm_result.status_code as u64,
+                abort_code: sp_mvm::result::ExecutionResult::from(&vm_result).abort_code,
             })
         }
 
         // Estimate gas for execute script.
-        fn estimate_gas_execute(account: AccountId, tx_bc: Vec<u8>, gas_limit: u64) -> Result<MVMApiEstimation, sp_runtime::DispatchError> {
+        fn estimate_gas_execute(account: AccountId, tx_bc: Vec<u8>, gas_limit: u64, deadline: Option<u64>) -> Result<MVMApiEstimation, sp_runtime::DispatchError> {
+            let gas_limit = Mvm::deadline_gas_limit(gas_limit, deadline);
             let vm_result = Mvm::raw_execute_script(&[account], tx_bc, gas_limit, false, true)?;
 
             Ok(MVMApiEstimation {
                 gas_used: vm_result.gas_used,
                 status_code: vm_result.status_code as u64,
+                abort_code: sp_mvm::result::ExecutionResult::from(&vm_result).abort_code,
             })
         }
 
@@ -1281,6 +1935,147 @@ impl_runtime_apis! {
             Mvm::get_resource(&account_id, &tag.as_slice())
         }
 
+        fn get_resource_storage_key(account_id: AccountId, tag: Vec<u8>) -> Vec<u8> {
+            sp_mvm::proof::account_resource_storage_key::<Runtime>(&account_id, &tag)
+        }
+
+        fn get_module_storage_key(module_id: Vec<u8>) -> Vec<u8> {
+            sp_mvm::proof::encoded_module_storage_key::<Runtime>(&module_id)
+        }
+
+        fn get_vm_storage_prefix() -> Vec<u8> {
+            sp_mvm::proof::vm_storage_prefix::<Runtime>().to_vec()
+        }
+
+        fn get_move_address(account_id: AccountId) -> [u8; 32] {
+            sp_mvm::addr::account_to_bytes(&account_id)
+        }
+
+        // Get allowlisted scripts with metadata
+        fn get_allowed_scripts() -> Vec<MVMApiAllowedScript<AccountId>> {
+            Mvm::get_allowed_scripts()
+                .into_iter()
+                .map(|(hash, metadata)| MVMApiAllowedScript {
+                    hash,
+                    description: metadata.description,
+                    proposer: metadata.proposer,
+                })
+                .collect()
+        }
+
+        fn get_vm_info() -> MVMApiVmInfo {
+            let info = Mvm::get_vm_info();
+            MVMApiVmInfo {
+                pallet_version: info.pallet_version,
+                move_vm_revision: info.move_vm_revision,
+                gas_schedule_version: info.gas_schedule_version,
+                stdlib_hash: info.stdlib_hash,
+                features: info.features,
+            }
+        }
+
+        fn estimate_xcm_transfer_fee(ticker: Vec<u8>, dest: Vec<u8>) -> Result<MVMApiXcmFee, Vec<u8>> {
+            use sp_core::Decode;
+
+            let currency_id = CurrencyId::try_from(ticker)
+                .map_err(|_| b"Unknown currency ticker".to_vec())?;
+            let dest = xcm::VersionedMultiLocation::decode(&mut &dest[..])
+                .ok()
+                .and_then(|dest| MultiLocation::try_from(dest).ok())
+                .ok_or_else(|| b"Invalid destination".to_vec())?;
+
+            let (weight, fee) = xcm_transfer_fee(currency_id, &dest)
+                .map_err(|e| e.as_bytes().to_vec())?;
+            Ok(MVMApiXcmFee { weight, fee })
+        }
+
+        fn get_block_gas_usage() -> MVMApiBlockGas {
+            MVMApiBlockGas {
+                used: Mvm::block_gas_used(),
+                limit: Mvm::max_block_gas(),
+            }
+        }
+
+        fn get_sequence_number(account: AccountId) -> u64 {
+            Mvm::sequence_number(account)
+        }
+
+        fn estimate_execute_fee(
+            tx_bc: Vec<u8>,
+            gas_limit: u64,
+            gas_unit_price: u64,
+            ticker: Option<Vec<u8>>,
+        ) -> Result<MVMApiFee, Vec<u8>> {
+            let fee = execute_fee(tx_bc, gas_limit, gas_unit_price)
+                .map_err(|e| e.as_bytes().to_vec())?;
+            let total_fee = fee.base_fee
+                .saturating_add(fee.len_fee)
+                .saturating_add(fee.adjusted_weight_fee);
+
+            let total_fee_in = match ticker {
+                Some(ticker) => {
+                    let currency_id = CurrencyId::try_from(ticker)
+                        .map_err(|_| b"Unknown currency ticker".to_vec())?;
+                    let fee = if currency_id == CurrencyId::NATIVE {
+                        Some(total_fee)
+                    } else {
+                        FeeCurrencies::convert_fee(currency_id, total_fee)
+                    };
+                    Some(fee.ok_or_else(|| b"Fees can't be paid in the currency".to_vec())?)
+                }
+                None => None,
+            };
+
+            Ok(MVMApiFee {
+                base_fee: fee.base_fee.into(),
+                length_fee: fee.len_fee.into(),
+                weight_fee: fee.adjusted_weight_fee.into(),
+                total_fee: total_fee.into(),
+                total_fee_in: total_fee_in.map(Into::into),
+                gas_tip: sp_mvm::gas_price::gas_tip::<Runtime>(gas_limit, gas_unit_price).into(),
+            })
+        }
+
+        fn get_aptos_chain_id() -> u8 {
+            Mvm::aptos_chain_id()
+        }
+
+        fn encode_execute_call(tx_bc: Vec<u8>, gas_limit: u64) -> MVMApiSubmission {
+            encode_execute_call(tx_bc, gas_limit)
+        }
+
+        fn get_package(account: AccountId, name: Vec<u8>) -> Option<MVMApiPackage<AccountId>> {
+            Mvm::get_package(&account, &name).map(|package| MVMApiPackage {
+                name: package.name,
+                version: package.version,
+                upgrade_number: package.upgrade_number,
+                dependencies: package
+                    .dependencies
+                    .into_iter()
+                    .map(|dependency| (dependency.account, dependency.name))
+                    .collect(),
+                source_digest: package.source_digest,
+                modules: package.modules,
+            })
+        }
+
+        fn get_source_map(module_id: Vec<u8>) -> Option<Vec<u8>> {
+            Mvm::get_source_map(&module_id)
+        }
+
+        // Override resources in the state overlay of the calls
+        fn override_resources(overrides: Vec<(AccountId, Vec<u8>, Option<Vec<u8>>)>) -> Result<(), Vec<u8>> {
+            Mvm::override_resources(overrides)
+        }
+
+        fn get_transaction(extrinsic: Vec<u8>, index: u32) -> Option<MVMApiTransaction<AccountId>> {
+            get_transaction(extrinsic, index)
+        }
+
+        fn resolve_move_address(address: [u8; 32]) -> Option<AccountId> {
+            Mvm::resolve_move_address(address)
+        }
+
     }
 
     impl sp_session::SessionKeys<Block> for Runtime {
@@ -1348,6 +2143,19 @@ impl_runtime_apis! {
         }
     }
 
+    #[cfg(feature = "try-runtime")]
+    impl frame_try_runtime::TryRuntime<Block> for Runtime {
+        fn on_runtime_upgrade() -> (Weight, Weight) {
+            // Panics on failed checks, so the upgrade dry-run reports the reason.
+            let weight = Executive::try_runtime_upgrade().unwrap();
+            (weight, RuntimeBlockWeights::get().max_block)
+        }
+
+        fn execute_block_no_check(block: Block) -> Weight {
+            Executive::execute_block_no_check(block)
+        }
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     impl frame_benchmarking::Benchmark<Block> for Runtime {
         fn benchmark_metadata(extra: bool) -> (
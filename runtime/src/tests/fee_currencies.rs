@@ -0,0 +1,76 @@
+/// Test fees payment in non-native currencies.
+use crate::tests::mock::*;
+use frame_support::{assert_ok, weights::{DispatchInfo, GetDispatchInfo}};
+use orml_traits::currency::MultiCurrency;
+use pallet_transaction_payment::ChargeTransactionPayment;
+use sp_runtime::{FixedPointNumber, FixedU128, traits::SignedExtension};
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
+
+#[test]
+/// Move VM call is paid in KSM if account has no native currency.
+fn mvm_call_fee_in_tokens() {
+    let initial_balance = to_unit(1000, CurrencyId::KSM);
+
+    RuntimeBuilder::new()
+        .set_balances(vec![(
+            Accounts::BOB.account(),
+            CurrencyId::KSM,
+            initial_balance,
+        )])
+        .build()
+        .execute_with(|| {
+            let bob = Accounts::BOB.account();
+            let mvm_call = Call::Mvm(sp_mvm::Call::execute {
+                tx_bc: vec![],
+                gas_limit: 1_000,
+            });
+            let transfer_call = Call::Balances(pallet_balances::Call::transfer {
+                dest: sp_runtime::MultiAddress::Id(Accounts::ALICE.account()),
+                value: 1,
+            });
+
+            // KSM isn't registered yet.
+            let info = mvm_call.get_dispatch_info();
+            let charge = |call: &Call, info: &DispatchInfo| {
+                ChargeTransactionPayment::<Runtime>::from(0)
+                    .pre_dispatch(&bob, call, info, 10)
+                    .map(|_| ())
+            };
+            let no_payment = Err(TransactionValidityError::Invalid(InvalidTransaction::Payment));
+            assert_eq!(charge(&mvm_call, &info), no_payment);
+
+            assert_ok!(FeeCurrencies::set_fee_rate(
+                Origin::root(),
+                CurrencyId::KSM,
+                Some(FixedU128::saturating_from_integer(100))
+            ));
+
+            assert_ok!(charge(&mvm_call, &info));
+            assert!(Tokens::free_balance(CurrencyId::KSM, &bob) < initial_balance);
+            assert_eq!(Balances::free_balance(&bob), 0);
+
+            // Only Move VM calls could be paid in tokens.
+            assert_eq!(
+                charge(&transfer_call, &transfer_call.get_dispatch_info()),
+                no_payment
+            );
+        });
+}
+
+#[test]
+/// Fee of the `execute` extrinsic grows with the gas limit and the script length.
+fn execute_fee_estimation() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let fee = execute_fee(vec![], 1_000, 0).unwrap();
+        assert!(fee.base_fee > 0);
+        assert!(fee.len_fee > 0);
+
+        let more_gas = execute_fee(vec![], 1_000_000, 0).unwrap();
+        assert!(more_gas.adjusted_weight_fee > fee.adjusted_weight_fee);
+        assert_eq!(more_gas.len_fee, fee.len_fee);
+
+        let longer = execute_fee(vec![0; 100], 1_000, 0).unwrap();
+        assert!(longer.len_fee > fee.len_fee);
+        assert_eq!(longer.adjusted_weight_fee, fee.adjusted_weight_fee);
+    });
+}
@@ -1,4 +1,5 @@
 pub mod balances;
+pub mod fee_currencies;
 pub mod mock;
 pub mod mvm;
 pub mod runtime_parachain;
@@ -281,3 +281,22 @@ fn transfer_tokens() {
             );
         });
 }
+
+#[test]
+/// Encoded submission is the `execute` call and the unsigned extrinsic with it.
+fn encode_execute_call_submission() {
+    use sp_core::{Decode, Encode};
+
+    let tx_bc = transactions::TRANSFER_PONT.bytes().to_vec();
+    let submission = encode_execute_call(tx_bc.clone(), 1_000_000);
+
+    let call = Call::Mvm(sp_mvm::Call::execute {
+        tx_bc,
+        gas_limit: 1_000_000,
+    });
+    assert_eq!(submission.call, call.encode());
+
+    let xt = UncheckedExtrinsic::decode(&mut &submission.unsigned_extrinsic[..]).unwrap();
+    assert!(xt.signature.is_none());
+    assert_eq!(xt.function, call);
+}
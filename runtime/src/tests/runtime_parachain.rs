@@ -1,6 +1,11 @@
 /// Test parachains locations.
 use crate::tests::mock::*;
-use sp_runtime::traits::Convert;
+use crate::tests::mvm::transactions;
+use frame_support::{assert_ok, weights::GetDispatchInfo};
+use orml_traits::currency::MultiCurrency;
+use sp_core::Encode;
+use sp_runtime::traits::{AccountIdConversion, Convert};
+use xcm_executor::traits::DropAssets;
 
 #[test]
 fn test_currency_id_convertations() {
@@ -42,3 +47,197 @@ fn test_currency_id_convertations() {
         );
     });
 }
+
+#[test]
+fn test_claim_trapped_assets() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let amount = CurrencyId::KSM.times(10);
+        let bob = Accounts::BOB.account();
+        let relay = sp_mvm::xtransfer::Location {
+            parents: 1,
+            junctions: vec![],
+        };
+
+        // Failed incoming transfer from the relay chain.
+        let assets = MultiAssets::from(MultiAsset::from((MultiLocation::parent(), amount)));
+        <PolkadotXcm as DropAssets>::drop_assets(&MultiLocation::parent(), assets.into());
+
+        // Signed origins can't claim.
+        assert!(Mvm::claim_trapped_assets(
+            Origin::signed(bob.clone()),
+            relay.clone(),
+            b"KSM".to_vec(),
+            amount,
+            bob.clone(),
+        )
+        .is_err());
+
+        // Exact currency and amount only.
+        assert!(Mvm::claim_trapped_assets(
+            Origin::root(),
+            relay.clone(),
+            b"KSM".to_vec(),
+            amount - 1,
+            bob.clone(),
+        )
+        .is_err());
+
+        assert_ok!(Mvm::claim_trapped_assets(
+            Origin::root(),
+            relay.clone(),
+            b"KSM".to_vec(),
+            amount,
+            bob.clone(),
+        ));
+        assert_eq!(Tokens::free_balance(CurrencyId::KSM, &bob), amount);
+
+        // Assets are claimed once.
+        assert!(Mvm::claim_trapped_assets(
+            Origin::root(),
+            relay,
+            b"KSM".to_vec(),
+            amount,
+            bob.clone(),
+        )
+        .is_err());
+        assert_eq!(Tokens::free_balance(CurrencyId::KSM, &bob), amount);
+    });
+}
+
+#[test]
+fn test_xcm_transfer_filters() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        use xcm_filter::{FilterMode, TransferKind};
+        type ReserveFilter = <Runtime as pallet_xcm::Config>::XcmReserveTransferFilter;
+        type TeleportFilter = <Runtime as pallet_xcm::Config>::XcmTeleportFilter;
+
+        let ksm = MultiAsset::from((MultiLocation::parent(), CurrencyId::KSM.times(1)));
+        let transfer = (MultiLocation::parent(), vec![ksm]);
+
+        // Reserve transfers are allowed and teleports are disabled by default.
+        assert!(ReserveFilter::contains(&transfer));
+        assert!(!TeleportFilter::contains(&transfer));
+
+        assert_ok!(XcmFilter::set_mode(
+            Origin::root(),
+            TransferKind::Reserve,
+            FilterMode::Allowlist
+        ));
+        assert!(!ReserveFilter::contains(&transfer));
+
+        assert_ok!(XcmFilter::allow_destination(
+            Origin::root(),
+            TransferKind::Reserve,
+            Box::new(MultiLocation::parent().into())
+        ));
+        assert_ok!(XcmFilter::allow_asset(
+            Origin::root(),
+            TransferKind::Reserve,
+            Box::new(MultiLocation::parent().into())
+        ));
+        assert!(ReserveFilter::contains(&transfer));
+    });
+}
+
+#[test]
+fn test_transact_move_script_from_sibling() {
+    let sibling = MultiLocation::new(1, X1(Parachain(2001)));
+    let sovereign: AccountId = Sibling::from(2001).into_account();
+    let initial_balance = CurrencyId::NATIVE.times(100);
+
+    RuntimeBuilder::new()
+        .set_balances(vec![(sovereign.clone(), CurrencyId::NATIVE, initial_balance)])
+        .build()
+        .execute_with(|| {
+            let alice = Accounts::ALICE.account();
+            let alice_balance = Balances::free_balance(&alice);
+
+            // Transfer script sends 50 PONT from the signer to Alice.
+            let call = Call::Mvm(sp_mvm::Call::execute {
+                tx_bc: transactions::TRANSFER_PONT.bytes().to_vec(),
+                gas_limit: 1_000_000,
+            });
+            let call_weight = call.get_dispatch_info().weight;
+
+            let native = CurrencyIdConvert::convert(CurrencyId::NATIVE).unwrap();
+            let fees = MultiAsset::from((native, CurrencyId::NATIVE.times(1)));
+            let message = Xcm(vec![
+                WithdrawAsset(fees.clone().into()),
+                BuyExecution {
+                    fees,
+                    weight_limit: Unlimited,
+                },
+                Transact {
+                    origin_type: OriginKind::SovereignAccount,
+                    require_weight_at_most: call_weight,
+                    call: call.encode().into(),
+                },
+                RefundSurplus,
+                DepositAsset {
+                    assets: Wild(All),
+                    max_assets: 1,
+                    beneficiary: X1(Junction::AccountId32 {
+                        network: Any,
+                        id: sovereign.clone().into(),
+                    })
+                    .into(),
+                },
+            ]);
+            let max_weight = call_weight + UnitWeightCost::get() * 5;
+
+            assert_ok!(
+                XcmExecutor::<XcmConfig>::execute_xcm(sibling, message, max_weight)
+                    .ensure_complete()
+            );
+
+            // Sovereign account of the sibling is the signer of the script.
+            let to_spent = to_unit(50, CurrencyId::NATIVE);
+            assert_eq!(Balances::free_balance(&alice), alice_balance + to_spent);
+            let sovereign_balance = Balances::free_balance(&sovereign);
+            assert!(sovereign_balance < initial_balance - to_spent);
+            assert!(sovereign_balance > initial_balance - to_spent - CurrencyId::NATIVE.times(1));
+        });
+}
+
+#[test]
+fn test_xcm_transfer_fee() {
+    RuntimeBuilder::new().build().execute_with(|| {
+        let sibling = MultiLocation::new(
+            1,
+            X2(
+                Parachain(2001),
+                Junction::AccountId32 {
+                    network: Any,
+                    id: Accounts::BOB.into(),
+                },
+            ),
+        );
+        let relay = MultiLocation::new(
+            1,
+            X1(Junction::AccountId32 {
+                network: Any,
+                id: Accounts::BOB.into(),
+            }),
+        );
+        let weight = UnitWeightCost::get() * XCM_TRANSFER_INSTRUCTIONS;
+
+        // PONT is reserved by this chain, so it's deposited to the sibling directly.
+        assert_eq!(
+            xcm_transfer_fee(CurrencyId::NATIVE, &sibling),
+            Ok((weight, weight as u128 / PONT_PER_WEIGHT))
+        );
+
+        // KSM is withdrawn on the relay chain.
+        let (ksm_weight, ksm_fee) = xcm_transfer_fee(CurrencyId::KSM, &relay).unwrap();
+        assert_eq!(ksm_weight, weight);
+        assert!(ksm_fee > 0);
+
+        // KSM transfer to the sibling goes through the relay chain.
+        assert_eq!(
+            xcm_transfer_fee(CurrencyId::KSM, &sibling).map(|(weight, _)| weight),
+            Ok(weight * 2)
+        );
+
+        assert!(xcm_transfer_fee(CurrencyId::NATIVE, &MultiLocation::here()).is_err());
+    });
+}
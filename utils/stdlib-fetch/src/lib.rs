@@ -52,3 +52,8 @@ pub fn fetch(config: FetchConfig) -> Result<()> {
 
     Ok(())
 }
+
+// Build local Move package with dove.
+pub fn build(path: &str) -> Result<()> {
+    run(path, "dove", &["build", "-b"])
+}